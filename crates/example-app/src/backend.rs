@@ -61,6 +61,8 @@ impl ExampleAppBackend {
             description: Some("Example diagnostic app with managed ECU sub-entity".to_string()),
             href: format!("/vehicle/v1/components/{}", id),
             status: Some("running".to_string()),
+            display_name: None,
+            category: None,
         };
 
         let capabilities = Capabilities {
@@ -232,6 +234,8 @@ impl DiagnosticBackend for ExampleAppBackend {
                         self.entity_info.id, self.ecu_id
                     ),
                     status: Some("not_available".to_string()),
+                    display_name: None,
+                    category: None,
                 }])
             }
         }
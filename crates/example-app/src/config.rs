@@ -5,7 +5,7 @@
 //! config.  The app entity is the authority on its ECU's public interface.
 
 use serde::Deserialize;
-use sovd_uds::config::{OperationConfig, OutputConfig};
+use sovd_uds::config::{OperationConfig, OutputConfig, SessionConfig};
 
 /// Parameter definition for the example app config.
 ///
@@ -48,6 +48,10 @@ pub struct ManagedEcuConfig {
     /// Output (I/O control) definitions exposed by this ECU
     #[serde(default)]
     pub outputs: Vec<OutputConfig>,
+    /// Session sub-functions of the upstream ECU (`[managed_ecu.session]`,
+    /// same keys as the gateway's `[session]`, e.g. `extended_session = 0x43`)
+    #[serde(default, rename = "session")]
+    pub sessions: SessionConfig,
 }
 
 /// Top-level example app configuration
@@ -100,6 +104,7 @@ impl ExampleAppConfig {
                 parameters: std::mem::take(&mut self.parameters),
                 operations: std::mem::take(&mut self.operations),
                 outputs: std::mem::take(&mut self.outputs),
+                sessions: SessionConfig::default(),
             });
         }
     }
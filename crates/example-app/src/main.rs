@@ -167,6 +167,7 @@ async fn try_connect_upstream(
     output_defs: &[sovd_uds::config::OutputConfig],
     param_defs: &[example_app::config::ParameterDef],
     op_defs: &[sovd_uds::config::OperationConfig],
+    sessions: &sovd_uds::config::SessionConfig,
 ) -> anyhow::Result<ManagedEcuBackend> {
     let proxy = SovdProxyBackend::with_options(
         upstream_component,
@@ -188,6 +189,7 @@ async fn try_connect_upstream(
         param_defs.to_vec(),
         op_defs.to_vec(),
         ecu_secret_hex,
+        sessions.clone(),
     )
     .map_err(|e| anyhow::anyhow!("Failed to create managed ECU backend: {}", e))?;
 
@@ -225,7 +227,7 @@ async fn main() -> anyhow::Result<()> {
     config.normalize(&args.upstream_component);
 
     // 2. Resolve ECU identity and config-driven definitions
-    let (ecu_id, ecu_name, ecu_secret, output_defs, param_defs, op_defs) =
+    let (ecu_id, ecu_name, ecu_secret, output_defs, param_defs, op_defs, sessions) =
         if let Some(ref ecu_config) = config.managed_ecu {
             (
                 ecu_config.id.clone(),
@@ -234,6 +236,7 @@ async fn main() -> anyhow::Result<()> {
                 ecu_config.outputs.clone(),
                 ecu_config.parameters.clone(),
                 ecu_config.operations.clone(),
+                ecu_config.sessions.clone(),
             )
         } else {
             (
@@ -243,6 +246,7 @@ async fn main() -> anyhow::Result<()> {
                 Vec::new(),
                 Vec::new(),
                 Vec::new(),
+                Default::default(),
             )
         };
 
@@ -278,6 +282,7 @@ async fn main() -> anyhow::Result<()> {
         &output_defs,
         &param_defs,
         &op_defs,
+        &sessions,
     )
     .await
     {
@@ -342,6 +347,7 @@ async fn main() -> anyhow::Result<()> {
         let output_defs_bg = output_defs;
         let param_defs_bg = param_defs;
         let op_defs_bg = op_defs;
+        let sessions_bg = sessions;
 
         tokio::spawn(async move {
             const RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
@@ -363,6 +369,7 @@ async fn main() -> anyhow::Result<()> {
                     &output_defs_bg,
                     &param_defs_bg,
                     &op_defs_bg,
+                    &sessions_bg,
                 )
                 .await
                 {
@@ -18,7 +18,7 @@ use sovd_core::{
     SecurityMode, SessionMode, SoftwareInfo, VerifyResult,
 };
 use sovd_proxy::SovdProxyBackend;
-use sovd_uds::config::{OperationConfig, OutputConfig};
+use sovd_uds::config::{OperationConfig, OutputConfig, SessionConfig};
 use tokio::sync::RwLock;
 
 use crate::config::ParameterDef;
@@ -49,6 +49,8 @@ pub struct ManagedEcuBackend {
     /// Application-level (outer) session state, independent of the ECU's UDS session.
     /// Values: "default", "programming", "extended".
     local_session: RwLock<String>,
+    /// Session name → UDS sub-function mapping reported for the outer session
+    sessions: SessionConfig,
     /// Config-driven output definitions
     output_definitions: Vec<OutputConfig>,
     /// Config-driven parameter definitions
@@ -79,6 +81,7 @@ impl ManagedEcuBackend {
         parameter_definitions: Vec<ParameterDef>,
         operation_definitions: Vec<OperationConfig>,
         security_secret_hex: Option<&str>,
+        sessions: SessionConfig,
     ) -> Result<Self, String> {
         let entity_info = EntityInfo {
             id: id.to_string(),
@@ -87,6 +90,8 @@ impl ManagedEcuBackend {
             description: Some("Managed ECU sub-entity".to_string()),
             href: format!("/vehicle/v1/components/{}/apps/{}", parent_id, id),
             status: Some("running".to_string()),
+            display_name: None,
+            category: None,
         };
 
         let mut capabilities = Capabilities::uds_ecu();
@@ -126,9 +131,17 @@ impl ManagedEcuBackend {
             parameter_definitions,
             operation_definitions,
             security_secret,
+            sessions,
         })
     }
 
+    /// Configured UDS sub-function for an outer session name
+    fn session_id(&self, session: &str) -> u8 {
+        self.sessions
+            .session_id(session)
+            .unwrap_or(self.sessions.default_session)
+    }
+
     /// Find an output config by ID
     fn find_output_config(&self, output_id: &str) -> Option<&OutputConfig> {
         self.output_definitions.iter().find(|o| o.id == output_id)
@@ -356,15 +369,7 @@ impl DiagnosticBackend for ManagedEcuBackend {
                 .find_output_config(output_id)
                 .ok_or_else(|| BackendError::OutputNotFound(output_id.to_string()))?;
 
-            let (current_value, controlled_by_tester, frozen) =
-                match self.proxy.get_output(output_id).await {
-                    Ok(detail) => (
-                        detail.current_value,
-                        detail.controlled_by_tester,
-                        detail.frozen,
-                    ),
-                    Err(_) => (config.default_value.clone(), false, false),
-                };
+            let detail = self.proxy.get_output(output_id).await.ok();
 
             let data_type_str = config.data_type.as_ref().map(|dt| dt.to_string());
 
@@ -372,10 +377,12 @@ impl DiagnosticBackend for ManagedEcuBackend {
                 id: config.id.clone(),
                 name: config.name.clone(),
                 output_id: config.ioid.clone(),
-                current_value,
+                current_value: detail
+                    .as_ref()
+                    .map_or_else(|| config.default_value.clone(), |d| d.current_value.clone()),
                 default_value: config.default_value.clone(),
-                controlled_by_tester,
-                frozen,
+                controlled_by_tester: detail.as_ref().is_some_and(|d| d.controlled_by_tester),
+                frozen: detail.as_ref().is_some_and(|d| d.frozen),
                 requires_security: config.security_level > 0,
                 security_level: config.security_level,
                 value: None,
@@ -385,6 +392,10 @@ impl DiagnosticBackend for ManagedEcuBackend {
                 min: config.min,
                 max: config.max,
                 allowed: config.allowed.clone(),
+                last_action: detail.as_ref().and_then(|d| d.last_action.clone()),
+                last_value: detail.as_ref().and_then(|d| d.last_value.clone()),
+                last_commanded_at: detail.as_ref().and_then(|d| d.last_commanded_at),
+                session_reset: detail.is_some_and(|d| d.session_reset),
             })
         } else {
             self.proxy.get_output(output_id).await
@@ -434,11 +445,8 @@ impl DiagnosticBackend for ManagedEcuBackend {
         Ok(SessionMode {
             mode: "session".to_string(),
             session: session.clone(),
-            session_id: match session.as_str() {
-                "programming" => 0x02,
-                "extended" => 0x03,
-                _ => 0x01,
-            },
+            session_id: self.session_id(&session),
+            timing: None,
         })
     }
 
@@ -471,11 +479,8 @@ impl DiagnosticBackend for ManagedEcuBackend {
         Ok(SessionMode {
             mode: "session".to_string(),
             session: session_lower.clone(),
-            session_id: match session_lower.as_str() {
-                "programming" => 0x02,
-                "extended" => 0x03,
-                _ => 0x01,
-            },
+            session_id: self.session_id(&session_lower),
+            timing: None,
         })
     }
 
@@ -812,6 +817,7 @@ impl ManagedEcuBackend {
                 state: map_phase_status(&body.phase, &body.status, body.substate.as_deref()),
                 progress: None,
                 error: None,
+                summary: None,
             })),
             Err(sovd_client::flash::FlashError::NoSession) => Ok(None),
             Err(e) => Err(BackendError::Transport(format!(
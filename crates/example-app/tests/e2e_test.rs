@@ -59,6 +59,8 @@ impl MockUpstreamBackend {
                 description: Some("Mock upstream ECU for proxy testing".to_string()),
                 href: format!("/vehicle/v1/components/{}", id),
                 status: Some("online".to_string()),
+                display_name: None,
+                category: None,
             },
             capabilities: Capabilities::uds_ecu(),
             packages: RwLock::new(HashMap::new()),
@@ -261,6 +263,7 @@ impl DiagnosticBackend for MockUpstreamBackend {
             success: true,
             cleared_count: 2,
             message: "All faults cleared".to_string(),
+            verification: None,
         })
     }
 }
@@ -322,6 +325,7 @@ async fn setup(auth_token: Option<&str>) -> TestEnv {
             Vec::new(),
             Vec::new(),
             None,
+            Default::default(),
         )
         .expect("Failed to create managed ECU backend"),
     );
@@ -34,6 +34,10 @@ pub struct EcuConfig {
     #[serde(default)]
     pub transfer: TransferConfig,
 
+    /// Negative response injection (for exercising tester error handling)
+    #[serde(default)]
+    pub injection: InjectionConfig,
+
     /// Parameter definitions (DIDs)
     #[serde(default)]
     pub parameters: Vec<ParameterDef>,
@@ -68,6 +72,7 @@ impl Default for EcuConfig {
             service_ids: ServiceIdConfig::default(),
             security: SecurityConfig::default(),
             transfer: TransferConfig::default(),
+            injection: InjectionConfig::default(),
             parameters: Vec::new(),
             dtcs: Vec::new(),
             outputs: Vec::new(),
@@ -412,6 +417,84 @@ impl Default for TransferConfig {
     }
 }
 
+// =============================================================================
+// Negative Response Injection
+// =============================================================================
+
+/// Negative response injection
+///
+/// Each rule matches a service, optionally narrowed to one identifier, and
+/// answers with an NRC instead of the real response and/or holds the
+/// response back behind responsePending (NRC 0x78) frames. The first rule
+/// with uses left wins.
+///
+/// # Example
+/// ```toml
+/// [injection]
+/// pending_delay_ms = 50
+///
+/// # Reading 0xF405 fails with conditionsNotCorrect
+/// [[injection.rules]]
+/// service = "0x22"
+/// did = "0xF405"
+/// nrc = "0x22"
+///
+/// # The next two routine requests answer after three responsePending frames
+/// [[injection.rules]]
+/// service = "0x31"
+/// pending = 3
+/// times = 2
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionConfig {
+    /// Delay between responsePending frames and the response that follows
+    #[serde(default = "default_pending_delay_ms")]
+    pub pending_delay_ms: u64,
+
+    /// Injection rules, first match wins
+    #[serde(default)]
+    pub rules: Vec<InjectionRule>,
+}
+
+fn default_pending_delay_ms() -> u64 {
+    50
+}
+
+impl Default for InjectionConfig {
+    fn default() -> Self {
+        Self {
+            pending_delay_ms: default_pending_delay_ms(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// One injection rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionRule {
+    /// Service ID the rule applies to (as sent, e.g. an OEM-specific ID)
+    #[serde(deserialize_with = "deserialize_hex_u8")]
+    pub service: u8,
+
+    /// Identifier the request must carry: a requested DID (0x22), the DID
+    /// written (0x2E), the I/O identifier (0x2F) or the routine ID (0x31).
+    /// Absent: every request of the service.
+    #[serde(default, deserialize_with = "deserialize_optional_hex_u16")]
+    pub did: Option<u16>,
+
+    /// NRC to answer with instead of the real response
+    #[serde(default, deserialize_with = "deserialize_optional_hex_u8")]
+    pub nrc: Option<u8>,
+
+    /// responsePending (NRC 0x78) frames sent before the response
+    #[serde(default)]
+    pub pending: u32,
+
+    /// Number of requests the rule applies to. Absent: every request.
+    #[serde(default)]
+    pub times: Option<u32>,
+}
+
 // =============================================================================
 // Parameter (DID) Definitions
 // =============================================================================
@@ -753,6 +836,28 @@ where
     }
 }
 
+/// Deserialize an optional hex u16 (supports "0xF190" or 61840)
+fn deserialize_optional_hex_u16<'de, D>(deserializer: D) -> Result<Option<u16>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Hex(#[serde(deserialize_with = "deserialize_hex_u16")] u16);
+
+    Ok(Option::<Hex>::deserialize(deserializer)?.map(|Hex(n)| n))
+}
+
+/// Deserialize an optional hex u8 (supports "0x22" or 34)
+fn deserialize_optional_hex_u8<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Hex(#[serde(deserialize_with = "deserialize_hex_u8")] u8);
+
+    Ok(Option::<Hex>::deserialize(deserializer)?.map(|Hex(n)| n))
+}
+
 /// Deserialize DTC bytes (supports "010100" or [1, 1, 0])
 fn deserialize_dtc_bytes<'de, D>(deserializer: D) -> Result<[u8; 3], D::Error>
 where
@@ -1402,6 +1507,36 @@ status: 9
         assert_eq!(def.status, 0x09);
     }
 
+    #[test]
+    fn test_parse_injection_rules() {
+        let toml = r#"
+[injection]
+pending_delay_ms = 10
+
+[[injection.rules]]
+service = "0x22"
+did = "0xF405"
+nrc = "0x22"
+
+[[injection.rules]]
+service = 49
+pending = 3
+times = 2
+"#;
+        let config: EcuConfig = toml::from_str(toml).unwrap();
+        let rules = &config.injection.rules;
+        assert_eq!(config.injection.pending_delay_ms, 10);
+        assert_eq!(
+            (rules[0].service, rules[0].did, rules[0].nrc, rules[0].times),
+            (0x22, Some(0xF405), Some(0x22), None)
+        );
+        assert_eq!(
+            (rules[1].service, rules[1].pending, rules[1].times),
+            (0x31, 3, Some(2))
+        );
+        assert!(rules[1].did.is_none() && rules[1].nrc.is_none());
+    }
+
     #[test]
     fn test_value_to_bytes() {
         assert_eq!(
@@ -0,0 +1,67 @@
+//! Negative response injection
+//!
+//! Applies the `[[injection.rules]]` of the ECU config: a matching request
+//! is answered with the rule's NRC instead of the real response, and/or the
+//! response is held back behind responsePending (NRC 0x78) frames. This
+//! lets tests hit tester-side error paths (responsePending retries, security
+//! lockout, out-of-range handling) that the happy-path simulator never
+//! takes.
+
+use parking_lot::Mutex;
+use tracing::info;
+
+use crate::config::{InjectionConfig, InjectionRule};
+
+/// What the injection rules do to one request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Injection {
+    /// responsePending frames to send before the response
+    pub pending: u32,
+    /// NRC to answer with instead of the real response
+    pub nrc: Option<u8>,
+}
+
+/// Injection rules and the uses they have left
+pub struct NrcInjector {
+    rules: Mutex<Vec<InjectionRule>>,
+}
+
+impl NrcInjector {
+    pub fn new(config: &InjectionConfig) -> Self {
+        if !config.rules.is_empty() {
+            info!(
+                "Created ECU simulator with {} injection rules",
+                config.rules.len()
+            );
+        }
+        Self {
+            rules: Mutex::new(config.rules.clone()),
+        }
+    }
+
+    /// Injection for a request of service `sid` carrying `identifiers`,
+    /// using up one application of the first matching rule
+    pub fn take(&self, sid: u8, identifiers: &[u16]) -> Injection {
+        let mut rules = self.rules.lock();
+        let Some(rule) = rules.iter_mut().find(|rule| {
+            rule.service == sid
+                && rule.times != Some(0)
+                && rule.did.is_none_or(|did| identifiers.contains(&did))
+        }) else {
+            return Injection::default();
+        };
+        if let Some(times) = rule.times.as_mut() {
+            *times -= 1;
+        }
+        info!(
+            service = format!("0x{:02X}", sid),
+            nrc = ?rule.nrc,
+            pending = rule.pending,
+            "Injecting negative response"
+        );
+        Injection {
+            pending: rule.pending,
+            nrc: rule.nrc,
+        }
+    }
+}
@@ -27,6 +27,7 @@ use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
 mod config;
+mod injection;
 mod parameters;
 mod sw_package;
 mod uds;
@@ -272,13 +273,19 @@ impl EcuSimulator {
                     Ok(data) if !data.is_empty() => {
                         debug!(request = ?data, "Received UDS request");
 
-                        // Process request and generate response
-                        let response = ecu.process_request(data);
+                        // Process request and generate response, preceded by
+                        // any injected responsePending frames
+                        let frames = ecu.process_request_frames(data);
 
-                        if !response.is_empty() {
-                            debug!(response = ?response, "Sending UDS response");
-                            if let Err(e) = socket_guard.write(&response) {
-                                error!(?e, "Failed to send response");
+                        for (i, response) in frames.iter().enumerate() {
+                            if i > 0 {
+                                std::thread::sleep(ecu.pending_delay());
+                            }
+                            if !response.is_empty() {
+                                debug!(response = ?response, "Sending UDS response");
+                                if let Err(e) = socket_guard.write(response) {
+                                    error!(?e, "Failed to send response");
+                                }
                             }
                         }
                     }
@@ -4,12 +4,13 @@
 
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
 
 use crate::sw_package::FirmwareImage;
 use crc::{Crc, CRC_32_ISO_HDLC};
 use parking_lot::RwLock;
 use rand::Rng;
-use sovd_uds::uds::standard_did;
+use sovd_uds::uds::{standard_did, CompressionMethod};
 use tracing::{debug, info, warn};
 
 /// CRC-32 calculator (ISO HDLC / CRC-32)
@@ -19,6 +20,7 @@ use crate::config::{
     AccessLevel as ConfigAccessLevel, DtcDef, EcuConfig, OutputDef, ParameterDef, RoutineDef,
     ServiceIdConfig,
 };
+use crate::injection::NrcInjector;
 use crate::uds::{
     comm_control_sub_function, control_dtc_setting_sub_function, ddid_sub_function,
     dtc_sub_function, io_control_option, link_baud_rate, link_control_sub_function,
@@ -225,6 +227,8 @@ pub struct DownloadState {
     pub buffer: Vec<u8>,
     /// Expected next block sequence counter
     pub expected_block: u8,
+    /// Compression of the TransferData blocks (dataFormatIdentifier)
+    pub compression: CompressionMethod,
 }
 
 /// State for an active upload transfer (ECU to tester)
@@ -334,6 +338,10 @@ pub struct SimulatedEcu {
     block_counter_start: u8,
     /// Transfer block counter wrap value (after 255)
     block_counter_wrap: u8,
+    /// Negative response injection rules
+    injector: NrcInjector,
+    /// Delay between responsePending frames and the response that follows
+    pending_delay: Duration,
 }
 
 impl SimulatedEcu {
@@ -439,6 +447,8 @@ impl SimulatedEcu {
             previous_firmware_version: RwLock::new(None),
             block_counter_start: config.transfer.block_counter_start,
             block_counter_wrap: config.transfer.block_counter_wrap,
+            injector: NrcInjector::new(&config.injection),
+            pending_delay: Duration::from_millis(config.injection.pending_delay_ms),
         }
     }
 
@@ -467,6 +477,55 @@ impl SimulatedEcu {
 
     /// Process a UDS request and return the response
     pub fn process_request(&self, request: &[u8]) -> Vec<u8> {
+        self.process_request_frames(request)
+            .pop()
+            .unwrap_or_default()
+    }
+
+    /// Process a UDS request and return every frame to send for it: the
+    /// responsePending frames an injection rule asks for, then the response
+    /// (or the injected NRC)
+    pub fn process_request_frames(&self, request: &[u8]) -> Vec<Vec<u8>> {
+        let Some(&sid) = request.first() else {
+            return vec![self.dispatch(request)];
+        };
+        let injection = self.injector.take(sid, &self.request_identifiers(request));
+        let mut frames =
+            vec![negative_response(sid, nrc::RESPONSE_PENDING); injection.pending as usize];
+        frames.push(match injection.nrc {
+            Some(code) => negative_response(sid, code),
+            None => self.dispatch(request),
+        });
+        frames
+    }
+
+    /// Delay between the frames of [`Self::process_request_frames`]
+    pub fn pending_delay(&self) -> Duration {
+        self.pending_delay
+    }
+
+    /// Identifiers an injection rule's `did` is matched against: the DIDs of
+    /// 0x22, the DID of 0x2E, the I/O identifier of 0x2F, the routine ID of
+    /// 0x31
+    fn request_identifiers(&self, request: &[u8]) -> Vec<u16> {
+        let sid = request[0];
+        let identifiers = if sid == self.svc.read_data_by_id {
+            &request[1..]
+        } else if sid == self.svc.write_data_by_id || sid == self.svc.io_control_by_id {
+            request.get(1..3).unwrap_or_default()
+        } else if sid == self.svc.routine_control {
+            request.get(2..4).unwrap_or_default()
+        } else {
+            &[]
+        };
+        identifiers
+            .chunks_exact(2)
+            .map(|id| u16::from_be_bytes([id[0], id[1]]))
+            .collect()
+    }
+
+    /// Answer a UDS request from the simulated ECU state
+    fn dispatch(&self, request: &[u8]) -> Vec<u8> {
         if request.is_empty() {
             return negative_response(0x00, nrc::INCORRECT_MESSAGE_LENGTH);
         }
@@ -1562,7 +1621,18 @@ impl SimulatedEcu {
             return negative_response(service_id::REQUEST_DOWNLOAD, nrc::CONDITIONS_NOT_CORRECT);
         }
 
-        let _data_format = request[1];
+        // dataFormatIdentifier: compression method (high nibble), no encryption
+        let data_format = request[1];
+        let compression = match CompressionMethod::from_nibble(data_format >> 4) {
+            Some(method) if data_format & 0x0F == 0 => method,
+            _ => {
+                debug!(
+                    data_format = format!("0x{:02X}", data_format),
+                    "Download denied: unsupported dataFormatIdentifier"
+                );
+                return negative_response(service_id::REQUEST_DOWNLOAD, nrc::REQUEST_OUT_OF_RANGE);
+            }
+        };
         let addr_len_format = request[2];
 
         let memory_size_len = ((addr_len_format >> 4) & 0x0F) as usize;
@@ -1590,6 +1660,7 @@ impl SimulatedEcu {
         info!(
             address = format!("0x{:08X}", memory_address),
             size = memory_size,
+            compression = ?compression,
             "RequestDownload: initiating download"
         );
 
@@ -1599,6 +1670,7 @@ impl SimulatedEcu {
             received: 0,
             buffer: Vec::with_capacity(memory_size as usize),
             expected_block: self.block_counter_start,
+            compression,
         };
         *self.download_state.write() = Some(download_state);
 
@@ -1780,7 +1852,6 @@ impl SimulatedEcu {
         }
 
         let block_counter = request[1];
-        let data = &request[2..];
 
         let mut download_state_guard = self.download_state.write();
         let download_state = match download_state_guard.as_mut() {
@@ -1799,6 +1870,27 @@ impl SimulatedEcu {
             return negative_response(service_id::TRANSFER_DATA, nrc::WRONG_BLOCK_SEQUENCE_COUNTER);
         }
 
+        // Compressed blocks are inflated on arrival; the announced memory
+        // size counts uncompressed bytes.
+        let remaining = (download_state.total_size - download_state.received) as usize;
+        let data = match download_state
+            .compression
+            .decompress(&request[2..], remaining)
+        {
+            Ok(data) => data,
+            Err(err) => {
+                debug!(
+                    error = %err,
+                    remaining,
+                    "TransferData download: block does not inflate"
+                );
+                return negative_response(
+                    service_id::TRANSFER_DATA,
+                    nrc::UPLOAD_DOWNLOAD_NOT_ACCEPTED,
+                );
+            }
+        };
+
         if download_state.received + data.len() as u32 > download_state.total_size {
             debug!(
                 received = download_state.received,
@@ -1809,7 +1901,7 @@ impl SimulatedEcu {
             return negative_response(service_id::TRANSFER_DATA, nrc::UPLOAD_DOWNLOAD_NOT_ACCEPTED);
         }
 
-        download_state.buffer.extend_from_slice(data);
+        download_state.buffer.extend_from_slice(&data);
         download_state.received += data.len() as u32;
 
         download_state.expected_block = download_state.expected_block.wrapping_add(1);
@@ -2233,3 +2325,119 @@ impl SimulatedEcu {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::InjectionRule;
+
+    /// Programming session + security unlock against the XOR gate
+    fn unlocked_ecu() -> SimulatedEcu {
+        let ecu = SimulatedEcu::new_vtx_ecm(vec![0xFF]);
+        assert_eq!(ecu.process_request(&[0x10, 0x02])[0], 0x50);
+        let seed = ecu.process_request(&[0x27, 0x01])[2..].to_vec();
+        let mut key = vec![0x27, 0x02];
+        key.extend(seed.iter().map(|b| b ^ 0xFF));
+        assert_eq!(ecu.process_request(&key), vec![0x67, 0x02]);
+        ecu
+    }
+
+    fn request_download(data_format: u8, size: u32) -> Vec<u8> {
+        let mut request = vec![0x34, data_format, 0x44, 0x00, 0x00, 0x00, 0x00];
+        request.extend_from_slice(&size.to_be_bytes());
+        request
+    }
+
+    #[test]
+    fn compressed_download_inflates_into_the_image() {
+        let ecu = unlocked_ecu();
+        let payload: Vec<u8> = (0..3000u32).map(|i| (i / 32) as u8).collect();
+        let image = FirmwareImage::build("", "v9.9.9", &payload).to_bytes();
+
+        let response = ecu.process_request(&request_download(0x10, image.len() as u32));
+        assert_eq!(response[0], 0x74, "{response:02X?}");
+
+        let raw_block = CompressionMethod::Deflate.raw_block_size(1024);
+        for (i, chunk) in image.chunks(raw_block).enumerate() {
+            let counter = i as u8;
+            let mut request = vec![0x36, counter];
+            request.extend_from_slice(&CompressionMethod::Deflate.compress(chunk));
+            assert_eq!(ecu.process_request(&request), vec![0x76, counter]);
+        }
+
+        assert_eq!(ecu.process_request(&[0x37]), vec![0x77, 0x00]);
+        assert_eq!(
+            ecu.pending_firmware_version.read().as_deref(),
+            Some("v9.9.9")
+        );
+    }
+
+    fn injecting_ecu(rules: Vec<InjectionRule>) -> SimulatedEcu {
+        let mut config = EcuConfig::default_vtx_ecm();
+        config.injection.rules = rules;
+        SimulatedEcu::from_config(&config, vec![0xFF])
+    }
+
+    fn rule(service: u8) -> InjectionRule {
+        InjectionRule {
+            service,
+            did: None,
+            nrc: None,
+            pending: 0,
+            times: None,
+        }
+    }
+
+    #[test]
+    fn injected_nrc_replaces_the_response_for_one_did() {
+        let ecu = injecting_ecu(vec![InjectionRule {
+            did: Some(0xF405),
+            nrc: Some(nrc::CONDITIONS_NOT_CORRECT),
+            ..rule(0x22)
+        }]);
+
+        assert_eq!(
+            ecu.process_request(&[0x22, 0xF4, 0x05]),
+            vec![0x7F, 0x22, 0x22]
+        );
+        assert_eq!(
+            ecu.process_request(&[0x22, 0xF4, 0x0E, 0xF4, 0x05]),
+            vec![0x7F, 0x22, 0x22]
+        );
+        assert_eq!(ecu.process_request(&[0x22, 0xF4, 0x0E])[0], 0x62);
+    }
+
+    #[test]
+    fn injected_response_pending_precedes_the_response_until_used_up() {
+        let ecu = injecting_ecu(vec![InjectionRule {
+            pending: 2,
+            times: Some(1),
+            ..rule(0x3E)
+        }]);
+
+        assert_eq!(
+            ecu.process_request_frames(&[0x3E, 0x00]),
+            vec![
+                vec![0x7F, 0x3E, 0x78],
+                vec![0x7F, 0x3E, 0x78],
+                vec![0x7E, 0x00]
+            ]
+        );
+        assert_eq!(
+            ecu.process_request_frames(&[0x3E, 0x00]),
+            vec![vec![0x7E, 0x00]]
+        );
+    }
+
+    #[test]
+    fn unsupported_data_format_is_out_of_range() {
+        let ecu = unlocked_ecu();
+        // Compression method 0x2 / encryption method 0x1 are unknown here
+        for data_format in [0x20, 0x01] {
+            assert_eq!(
+                ecu.process_request(&request_download(data_format, 16)),
+                vec![0x7F, 0x34, 0x31]
+            );
+        }
+    }
+}
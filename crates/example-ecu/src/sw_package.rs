@@ -34,8 +34,16 @@
 //! assert!(parsed.verify().is_ok());
 //! assert!(parsed.verify_target("engine_ecu").is_ok());
 //! ```
+//!
+//! # Signed packages
+//!
+//! A package for an ECU with `[ecu.*.flash.signature]` is the image above
+//! followed by the signature trailer of [`sovd_uds::signature`];
+//! [`FirmwareImage::to_signed_bytes`] builds one and
+//! [`FirmwareImage::verify_signature`] checks it.
 
 use sha2::{Digest, Sha256};
+use sovd_uds::signature::{append_signature, PackageVerifier, SignatureError};
 use thiserror::Error;
 
 // ── Layout constants ───────────────────────────────────────────────────────
@@ -92,6 +100,9 @@ pub enum FirmwareImageError {
     #[error("Target ECU mismatch: image targets '{got}', expected '{expected}'")]
     #[allow(dead_code)]
     TargetMismatch { expected: String, got: String },
+
+    #[error("Signature check failed: {0}")]
+    Signature(#[from] SignatureError),
 }
 
 pub type FirmwareImageResult<T> = Result<T, FirmwareImageError>;
@@ -145,6 +156,14 @@ impl FirmwareImage {
         buf
     }
 
+    /// Serialize and append a signature trailer. `sign` gets the image bytes
+    /// and returns the signature over them.
+    pub fn to_signed_bytes(&self, sign: impl FnOnce(&[u8]) -> Vec<u8>) -> Vec<u8> {
+        let image = self.to_bytes();
+        let signature = sign(&image);
+        append_signature(&image, &signature)
+    }
+
     /// Parse from the binary wire format (does NOT verify checksum — call
     /// [`verify`] for that).
     pub fn from_bytes(data: &[u8]) -> FirmwareImageResult<Self> {
@@ -241,6 +260,15 @@ impl FirmwareImage {
         Ok(version)
     }
 
+    /// Verify the signature trailer of a signed package against `verifier`
+    /// and return the image inside it, ready for [`Self::verify_bytes`].
+    pub fn verify_signature<'a>(
+        data: &'a [u8],
+        verifier: &PackageVerifier,
+    ) -> FirmwareImageResult<&'a [u8]> {
+        Ok(verifier.verify(data)?)
+    }
+
     /// Check that this image targets the given ECU.
     pub fn verify_target(&self, expected: &str) -> FirmwareImageResult<()> {
         if !self.target_ecu.is_empty() && self.target_ecu != expected {
@@ -342,6 +370,38 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn signed_image_verifies_and_tampering_does_not() {
+        use ed25519_dalek::pkcs8::{EncodePublicKey, LineEnding};
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let verifier = PackageVerifier::from_config(&sovd_uds::config::SignatureConfig {
+            algorithm: sovd_uds::signature::ALGORITHM_ED25519.to_string(),
+            public_key_pem: key
+                .verifying_key()
+                .to_public_key_pem(LineEnding::LF)
+                .unwrap(),
+        })
+        .unwrap();
+        let image = FirmwareImage::build("engine_ecu", "v2.0.0", &[0xAA; 64]);
+        let mut signed = image.to_signed_bytes(|bytes| key.sign(bytes).to_bytes().to_vec());
+
+        let inner = FirmwareImage::verify_signature(&signed, &verifier).unwrap();
+        assert_eq!(inner, image.to_bytes().as_slice());
+        assert_eq!(FirmwareImage::verify_bytes(inner).unwrap(), "v2.0.0");
+
+        signed[FW_DATA_OFFSET] ^= 0xFF;
+        assert!(matches!(
+            FirmwareImage::verify_signature(&signed, &verifier),
+            Err(FirmwareImageError::Signature(SignatureError::Mismatch))
+        ));
+        assert!(matches!(
+            FirmwareImage::verify_signature(&image.to_bytes(), &verifier),
+            Err(FirmwareImageError::Signature(SignatureError::Unsigned))
+        ));
+    }
+
     #[test]
     fn empty_target_matches_any() {
         let img = FirmwareImage::build("", "v1", &[]);
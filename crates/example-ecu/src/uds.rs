@@ -101,6 +101,7 @@ pub mod nrc {
     pub const UPLOAD_DOWNLOAD_NOT_ACCEPTED: u8 = 0x70;
     pub const GENERAL_PROGRAMMING_FAILURE: u8 = 0x72;
     pub const WRONG_BLOCK_SEQUENCE_COUNTER: u8 = 0x73;
+    pub const RESPONSE_PENDING: u8 = 0x78;
 }
 
 /// Create a positive response for a service
@@ -220,13 +220,16 @@ pub fn route_capability(method: &Method, path: &str) -> Capability {
         } else {
             Capability::OperationsExecute
         }
-    } else if path.contains("/modes") {
+    } else if path.contains("/modes") || path.ends_with("/x-sumo-link") {
         if is_get {
             Capability::Read
         } else {
             Capability::ModesSet
         }
-    } else if path.contains("/data") {
+    } else if path.contains("/data")
+        || path.ends_with("/x-sumo-memory")
+        || path.ends_with("/x-sumo-fingerprints")
+    {
         if is_get {
             Capability::DataRead
         } else {
@@ -938,6 +941,8 @@ mod tests {
                 description: None,
                 href: format!("/vehicle/v1/components/{id}"),
                 status: None,
+                display_name: None,
+                category: None,
             },
             caps: sovd_core::Capabilities::uds_ecu(),
         })
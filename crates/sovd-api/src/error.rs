@@ -28,9 +28,11 @@ pub fn nrc_status(nrc: u8) -> StatusCode {
 ///
 /// Most arms stay within the ISO 17978-3 §5.8 status set:
 /// 200/201/202/204/400/401/404/405/406/409/415/500/501/503/504.
-/// The one exception is `EcuErrorResponse`, whose status is the NRC→HTTP
+/// The exceptions are `EcuErrorResponse`, whose status is the NRC→HTTP
 /// mapping (ISO 17978-3 §8.4, C-131) — §8.4 may add per-method codes
-/// (403/502 for security / ECU-side-failure NRCs) on top of §5.8's set.
+/// (403/502 for security / ECU-side-failure NRCs) on top of §5.8's set —
+/// `Forbidden`, a server-side policy refusal, and `SecurityLockout` (429
+/// with `Retry-After`).
 #[derive(Debug)]
 pub enum ApiError {
     /// 400 Bad Request — `incomplete-request`
@@ -41,6 +43,10 @@ pub enum ApiError {
     /// Spec §5.8 401 covers "authentication required / missing /
     /// insufficient" — i.e. both authn AND authz issues route here.
     Unauthorized(String),
+    /// 403 Forbidden — `insufficient-access-rights`.  Authenticated (or
+    /// open) request refused by server policy, e.g. a subscription outside
+    /// the configured streaming allowlist.
+    Forbidden(String),
     /// 409 Conflict — `precondition-not-fulfilled` (generic).
     Conflict(String),
     /// 409 Conflict — `update-process-in-progress`.
@@ -58,6 +64,13 @@ pub enum ApiError {
     /// 503 Service Unavailable — rate-limited or backpressured.
     /// Spec §5.8 503 may include a `Retry-After` header.
     Throttled(String),
+    /// 429 Too Many Requests — security access locked out after invalid
+    /// keys.  Carries `vendor-specific` error_code with vendor
+    /// `security-lockout` and a `Retry-After` header.
+    SecurityLockout {
+        message: String,
+        retry_after_secs: u64,
+    },
     /// 501 Not Implemented — `sovd-server-misconfigured`.
     NotImplemented(String),
     /// 503 Service Unavailable — upstream protocol problem
@@ -70,6 +83,10 @@ pub enum ApiError {
     /// C-131) — 400/403/502/503 for specific NRC classes, else 409
     /// (state conflict).
     EcuErrorResponse { message: String, nrc: u8, sid: u8 },
+    /// 406 Not Acceptable — the `Accept` header names no format the
+    /// endpoint can produce.  Carries `vendor-specific` error_code with
+    /// vendor `not-acceptable`.
+    NotAcceptable(String),
     /// 415 Unsupported Media Type — F.D3 dispatcher rejects a payload
     /// whose target doesn't match the addressed component.  Carries
     /// `vendor-specific` error_code with vendor `wrong-target`.
@@ -78,9 +95,11 @@ pub enum ApiError {
     Internal(String),
 }
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        let (status, body) = match self {
+impl ApiError {
+    /// HTTP status and `GenericError` body for this error, for handlers that
+    /// embed a per-item error in an otherwise successful response
+    pub(crate) fn into_parts(self) -> (StatusCode, GenericError) {
+        match self {
             ApiError::EcuErrorResponse { message, nrc, sid } => {
                 // NRC→HTTP per the single-source table (ISO 17978-3 §8.4,
                 // C-131): the ECU answered but rejected — map the NRC to the
@@ -110,6 +129,11 @@ impl IntoResponse for ApiError {
                 StatusCode::UNAUTHORIZED,
                 GenericError::new(error_code::INSUFFICIENT_ACCESS_RIGHTS, msg),
             ),
+            ApiError::Forbidden(msg) => (
+                StatusCode::FORBIDDEN,
+                GenericError::new(error_code::INSUFFICIENT_ACCESS_RIGHTS, msg)
+                    .with_param("http_code", "403"),
+            ),
             ApiError::Conflict(msg) => (
                 StatusCode::CONFLICT,
                 GenericError::new(error_code::PRECONDITION_NOT_FULFILLED, msg),
@@ -138,6 +162,14 @@ impl IntoResponse for ApiError {
                 StatusCode::SERVICE_UNAVAILABLE,
                 GenericError::vendor("rate-limited", msg),
             ),
+            ApiError::SecurityLockout {
+                message,
+                retry_after_secs,
+            } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                GenericError::vendor("security-lockout", message)
+                    .with_param("retry_after", retry_after_secs.to_string()),
+            ),
             ApiError::NotImplemented(msg) => (
                 StatusCode::NOT_IMPLEMENTED,
                 GenericError::new(error_code::SOVD_SERVER_MISCONFIGURED, msg),
@@ -150,6 +182,10 @@ impl IntoResponse for ApiError {
                 StatusCode::GATEWAY_TIMEOUT,
                 GenericError::new(error_code::NOT_RESPONDING, msg),
             ),
+            ApiError::NotAcceptable(msg) => (
+                StatusCode::NOT_ACCEPTABLE,
+                GenericError::vendor("not-acceptable", msg).with_param("http_code", "406"),
+            ),
             ApiError::UnsupportedMediaType(msg) => (
                 StatusCode::UNSUPPORTED_MEDIA_TYPE,
                 GenericError::vendor("wrong-target", msg),
@@ -158,7 +194,19 @@ impl IntoResponse for ApiError {
                 StatusCode::INTERNAL_SERVER_ERROR,
                 GenericError::new(error_code::SOVD_SERVER_FAILURE, msg),
             ),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let retry_after = match &self {
+            ApiError::SecurityLockout {
+                retry_after_secs, ..
+            } => Some(*retry_after_secs),
+            _ => None,
         };
+        let (status, body) = self.into_parts();
 
         if status.is_server_error() {
             tracing::error!(
@@ -174,7 +222,15 @@ impl IntoResponse for ApiError {
             );
         }
 
-        (status, Json(body)).into_response()
+        match retry_after {
+            Some(secs) => (
+                status,
+                [(axum::http::header::RETRY_AFTER, secs.to_string())],
+                Json(body),
+            )
+                .into_response(),
+            None => (status, Json(body)).into_response(),
+        }
     }
 }
 
@@ -191,6 +247,7 @@ impl From<BackendError> for ApiError {
             BackendError::SessionRequired(session) => {
                 ApiError::PreconditionFailed(format!("Session change required: {}", session))
             }
+            BackendError::Forbidden(msg) => ApiError::Forbidden(msg),
             BackendError::NotSupported(op) => {
                 ApiError::NotImplemented(format!("Operation not supported: {}", op))
             }
@@ -199,6 +256,11 @@ impl From<BackendError> for ApiError {
                 ApiError::EcuErrorResponse { message, nrc, sid }
             }
             BackendError::RateLimited(msg) => ApiError::Throttled(msg),
+            BackendError::SecurityLockout { remaining_ms } => ApiError::SecurityLockout {
+                message: format!("Security access locked out, retry in {} ms", remaining_ms),
+                // Retry-After is whole seconds; round up so a retry is never early
+                retry_after_secs: remaining_ms.div_ceil(1000),
+            },
             BackendError::Transport(msg) => ApiError::ServiceUnavailable(msg),
             BackendError::InvalidRequest(msg) => ApiError::BadRequest(msg),
             BackendError::Timeout => ApiError::GatewayTimeout("Operation timed out".to_string()),
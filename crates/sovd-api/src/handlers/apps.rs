@@ -22,6 +22,11 @@ pub struct AppInfoResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
     pub href: String,
+    /// Friendly name from the gateway's `[gateway.components]` overlay
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -68,6 +73,8 @@ pub async fn list_apps(
             entity_type: e.entity_type.clone(),
             status: e.status.clone(),
             href: format!("/vehicle/v1/components/{}/apps/{}", component_id, e.id),
+            display_name: e.display_name.clone(),
+            category: e.category.clone(),
         })
         .collect();
 
@@ -103,6 +110,8 @@ pub async fn list_sub_entity_apps(
                 "/vehicle/v1/components/{}/apps/{}/apps/{}",
                 component_id, app_id, e.id
             ),
+            display_name: e.display_name.clone(),
+            category: e.category.clone(),
         })
         .collect();
 
@@ -193,6 +193,8 @@ mod tests {
                     description: None,
                     href: "/vehicle/v1/components/vm1".into(),
                     status: Some("online".into()),
+                    display_name: None,
+                    category: None,
                 },
                 caps,
             }
@@ -24,6 +24,10 @@ pub struct ComponentInfo {
     #[serde(rename = "type")]
     pub entity_type: String,
     pub href: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -58,6 +62,8 @@ pub struct CapabilitiesResponse {
     pub security: bool,
     pub sub_entities: bool,
     pub subscriptions: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dtc_reports: Vec<String>,
 }
 
 impl From<&Capabilities> for CapabilitiesResponse {
@@ -75,6 +81,7 @@ impl From<&Capabilities> for CapabilitiesResponse {
             security: caps.security,
             sub_entities: caps.sub_entities,
             subscriptions: caps.subscriptions,
+            dtc_reports: caps.dtc_reports.clone(),
         }
     }
 }
@@ -105,6 +112,8 @@ pub async fn list_components(
                 description: info.description.clone(),
                 entity_type: info.entity_type.clone(),
                 href: format!("/vehicle/v1/components/{}", id),
+                display_name: info.display_name.clone(),
+                category: info.category.clone(),
             }
         })
         .collect();
@@ -5,16 +5,24 @@
 //!
 //! Conversions are managed via the DidStore from sovd-conv.
 //! Definitions can be loaded from YAML files or registered dynamically.
+//!
+//! Reads of DIDs defined `cacheable` are answered from the read cache for
+//! `[data_cache] ttl_secs`; `Cache-Control: no-cache` on the request forces
+//! a bus read (and refreshes the cache).
 
 use axum::extract::{Path, Query, RawQuery, State};
+use axum::http::{header, HeaderMap};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use sovd_conv::format_did;
+use sovd_conv::{format_did, DidDefinition, DidStore, ReadVia};
 use sovd_core::error::BackendError;
-use sovd_core::DataCategory;
+use sovd_core::{DataCategory, DataValue, DiagnosticBackend, GenericError};
 
 use crate::error::ApiError;
+use crate::handlers::paging::PageQuery;
+use crate::negotiate::Format;
 use crate::state::AppState;
 
 // =============================================================================
@@ -28,6 +36,47 @@ pub struct ReadQuery {
     pub raw: bool,
 }
 
+/// Body of `POST .../data:batch`
+#[derive(Deserialize)]
+pub struct BatchWriteRequest {
+    /// Parameter id → value, written in the order given
+    pub values: OrderedValues,
+    /// Stop at the first failed write; the parameters after it are skipped
+    #[serde(default)]
+    pub stop_on_error: bool,
+}
+
+/// A JSON object's entries in document order (a `serde_json::Map` sorts
+/// its keys)
+pub struct OrderedValues(pub Vec<(String, serde_json::Value)>);
+
+impl<'de> Deserialize<'de> for OrderedValues {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = OrderedValues;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an object of parameter values")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<OrderedValues, A::Error> {
+                let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(OrderedValues(entries))
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
 // =============================================================================
 // Response Types
 // =============================================================================
@@ -39,6 +88,12 @@ pub struct DidListResponse {
     pub count: usize,
     /// List of registered DIDs with their conversions
     pub items: Vec<DidInfoResponse>,
+    /// Matches across all pages; only on a paged listing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<usize>,
+    /// Href of the next page; absent on the last one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
 }
 
 /// Info about a registered DID
@@ -123,6 +178,51 @@ pub struct DidResponse {
     pub timestamp: String,
 }
 
+/// Response for `GET .../data?ids=a,b,c`: one item per requested id, in
+/// request order (vendor extension, listed as `x-sumo-batch-read`)
+#[derive(Serialize)]
+pub struct DataReadListResponse {
+    pub items: Vec<DataReadItem>,
+}
+
+/// A parameter read by a batch, or why it could not be read
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum DataReadItem {
+    Value(DidResponse),
+    Error { id: String, error: GenericError },
+}
+
+/// Response for `POST .../data:batch`: one item per parameter, in request
+/// order (vendor extension, listed as `x-sumo-batch-write`)
+#[derive(Serialize)]
+pub struct BatchWriteResponse {
+    pub items: Vec<BatchWriteItem>,
+    /// Index into `items` of the first write that failed; absent when all
+    /// were written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_index: Option<usize>,
+}
+
+/// Outcome of one parameter of a batch write
+#[derive(Serialize)]
+pub struct BatchWriteItem {
+    pub id: String,
+    pub status: BatchWriteStatus,
+    /// Why the write failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<GenericError>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchWriteStatus {
+    Written,
+    Failed,
+    /// Not attempted: `stop_on_error` and an earlier write failed
+    Skipped,
+}
+
 /// Request for a DID write — spec `{value}` body (ISO 17978-2 ≈line 489:
 /// "the value(s) to be written").
 ///
@@ -176,6 +276,27 @@ fn parse_category_filter(raw_query: &Option<String>) -> Option<Vec<DataCategory>
     present.then_some(cats)
 }
 
+/// Parse the `?ids=a,b,c` batch read from the raw query string; `None` when
+/// absent, i.e. a plain listing.
+pub(crate) fn parse_ids(raw_query: &Option<String>) -> Option<Vec<String>> {
+    let raw = raw_query.as_deref()?;
+    let mut found = None;
+    for pair in raw.split('&').filter(|s| !s.is_empty()) {
+        let (key, val) = pair.split_once('=').unwrap_or((pair, ""));
+        if key == "ids" {
+            let ids: &mut Vec<String> = found.get_or_insert_with(Vec::new);
+            // Parameter ids and DID hex need no percent-decoding
+            ids.extend(
+                val.split(',')
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string),
+            );
+        }
+    }
+    found
+}
+
 /// Retain only items whose category is in the requested set (if any).
 fn apply_category_filter(items: &mut Vec<DidInfoResponse>, filter: &Option<Vec<DataCategory>>) {
     if let Some(wanted) = filter {
@@ -196,11 +317,31 @@ fn apply_category_filter(items: &mut Vec<DidInfoResponse>, filter: &Option<Vec<D
 /// ISO 17978-3 §7.9: `?categories=` (Table 78, explode=true / OR-combined)
 /// filters the returned `ValueMetaData` by their `category`. Absent → no
 /// filter.
+///
+/// `?ids=a,b,c` reads those parameters instead of listing (see
+/// [`read_data_batch`]).
+///
+/// `?limit=`, `?offset=` and `?q=` (id or name substring) answer one page
+/// of the listing (see [`super::paging`]); without them the whole list.
+///
+/// `Accept: application/cbor` returns either as CBOR (see
+/// [`crate::negotiate`]).
 pub async fn list_parameters(
     State(state): State<AppState>,
     Path(component_id): Path<String>,
     RawQuery(raw_query): RawQuery,
-) -> Result<Json<DidListResponse>, ApiError> {
+    Query(page): Query<PageQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let format = Format::negotiate(&headers)?;
+
+    if let Some(ids) = parse_ids(&raw_query) {
+        let backend = state.get_backend(&component_id)?;
+        let use_cache = !wants_no_cache(&headers);
+        let items = read_data_batch(&state, backend.as_ref(), &component_id, &ids, use_cache).await;
+        return Ok(format.respond(&DataReadListResponse { items }));
+    }
+
     let category_filter = parse_category_filter(&raw_query);
 
     let mut items = resolve_data_items(&state, &component_id).await?;
@@ -209,9 +350,26 @@ pub async fn list_parameters(
     // Sort by id for consistent ordering
     items.sort_by(|a, b| a.id.cmp(&b.id));
 
-    Ok(Json(DidListResponse {
-        count: items.len(),
+    if !page.is_requested() {
+        return Ok(format.respond(&DidListResponse {
+            count: items.len(),
+            items,
+            total_count: None,
+            next: None,
+        }));
+    }
+
+    items.retain(|item| page.matches([Some(item.id.as_str()), item.name.as_deref()]));
+    let page = page.paginate(
         items,
+        &format!("/vehicle/v1/components/{component_id}/data"),
+        raw_query.as_deref(),
+    );
+    Ok(format.respond(&DidListResponse {
+        count: page.items.len(),
+        items: page.items,
+        total_count: Some(page.total_count),
+        next: page.next,
     }))
 }
 
@@ -262,9 +420,10 @@ async fn resolve_data_items(
     Ok(definitions
         .into_iter()
         .map(|(did, def)| {
-            // Use semantic id if available, otherwise fall back to DID hex
+            // Use alias / semantic id if available, otherwise fall back to DID hex
             let did_hex = format_did(did);
-            let id = def.id.clone().unwrap_or_else(|| did_hex.clone());
+            let id = semantic_id_for(state, component_id, did, Some(&def))
+                .unwrap_or_else(|| did_hex.clone());
             // §7.9 category: explicit definition category wins, else default
             // by DID number (identification range vs measurement).
             let category = Some(def.resolve_category(did));
@@ -308,6 +467,35 @@ pub async fn list_data_categories(
     Ok(Json(DataCategoryListResponse { items }))
 }
 
+/// Public id for a DID on a component: a configured alias overrides the
+/// definition's own `id`.
+pub(crate) fn semantic_id_for(
+    state: &AppState,
+    component_id: &str,
+    did: u16,
+    def: Option<&DidDefinition>,
+) -> Option<String> {
+    state
+        .data_aliases()
+        .name_for(component_id, did)
+        .map(str::to_string)
+        .or_else(|| def.and_then(|d| d.id.clone()))
+}
+
+/// Read a DID's raw bytes with the service its definition names
+/// (`read_via`): ReadDataByIdentifier by default, a periodic one-shot for
+/// DIDs the ECU only serves through 0x2A.
+pub(crate) async fn read_did_bytes(
+    backend: &dyn DiagnosticBackend,
+    did: u16,
+    def: Option<&DidDefinition>,
+) -> Result<Vec<u8>, BackendError> {
+    match def.map(|d| d.read_via).unwrap_or_default() {
+        ReadVia::Standard => backend.read_raw_did(did).await,
+        ReadVia::Periodic => backend.read_raw_did_periodic(did).await,
+    }
+}
+
 /// Convert a backend [`ParameterInfo`] into the wire [`DidInfoResponse`],
 /// carrying the §7.9 category through. When a backend leaves `category`
 /// unset but exposes a hex DID, fall back to the DID-number default so the
@@ -335,13 +523,28 @@ fn param_info_to_did_info(p: sovd_core::ParameterInfo, component_id: &str) -> Di
 }
 
 /// GET /vehicle/v1/components/:component_id/data/:did
-/// Read a DID value (applies conversion if registered)
+/// Read a DID value (applies conversion if registered); CBOR on
+/// `Accept: application/cbor`
 pub async fn read_parameter(
     State(state): State<AppState>,
     Path((component_id, did)): Path<(String, String)>,
     Query(query): Query<ReadQuery>,
-) -> Result<Json<DidResponse>, ApiError> {
-    read_did_internal(&state, &component_id, &did, query.raw).await
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let format = Format::negotiate(&headers)?;
+    let use_cache = !wants_no_cache(&headers);
+    let Json(body) = read_did_internal(&state, &component_id, &did, query.raw, use_cache).await?;
+    Ok(format.respond(&body))
+}
+
+/// True when the request's `Cache-Control` header carries `no-cache`.
+fn wants_no_cache(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|directive| directive.trim().eq_ignore_ascii_case("no-cache"))
 }
 
 /// PUT /vehicle/v1/components/:component_id/data/:did — 204 No Content per spec.
@@ -354,6 +557,217 @@ pub async fn write_parameter(
     Ok(axum::http::StatusCode::NO_CONTENT)
 }
 
+/// POST /vehicle/v1/components/:component_id/data:batch
+///
+/// Writes several parameters one after the other, each exactly like a
+/// `PUT .../data/{param_id}`, and answers 200 with the outcome of each.
+/// UDS has no transaction spanning several WriteDataByIdentifier
+/// requests, so this is best effort: writes that succeeded before a
+/// failure stay written and are not rolled back. `failed_index` points at
+/// the first failure so the caller can restore or retry from there; with
+/// `stop_on_error` nothing after it is attempted.
+pub async fn write_data_batch(
+    State(state): State<AppState>,
+    Path(component_id): Path<String>,
+    Json(request): Json<BatchWriteRequest>,
+) -> Result<Json<BatchWriteResponse>, ApiError> {
+    state.get_backend(&component_id)?;
+    if request.values.0.is_empty() {
+        return Err(ApiError::BadRequest("No values to write".to_string()));
+    }
+
+    let mut items = Vec::with_capacity(request.values.0.len());
+    let mut failed_index = None;
+    for (index, (id, value)) in request.values.0.into_iter().enumerate() {
+        if failed_index.is_some() && request.stop_on_error {
+            items.push(BatchWriteItem {
+                id,
+                status: BatchWriteStatus::Skipped,
+                error: None,
+            });
+            continue;
+        }
+        let item =
+            match write_did_internal(&state, &component_id, &id, WriteDidRequest { value }).await {
+                Ok(_) => BatchWriteItem {
+                    id,
+                    status: BatchWriteStatus::Written,
+                    error: None,
+                },
+                Err(e) => {
+                    failed_index.get_or_insert(index);
+                    BatchWriteItem {
+                        id,
+                        status: BatchWriteStatus::Failed,
+                        error: Some(e.into_parts().1),
+                    }
+                }
+            };
+        items.push(item);
+    }
+
+    Ok(Json(BatchWriteResponse {
+        items,
+        failed_index,
+    }))
+}
+
+/// Read `ids` on `entity_id` with one [`DiagnosticBackend::read_parameters_batch`]
+/// call, decoded like single reads. Ids resolving to a DID are passed as DID
+/// hex so a UDS backend can pack them into multi-DID requests; DIDs read
+/// via 0x2A are read on their own.
+///
+/// `cacheable` DIDs go through [`AppState::read_cache`] as single reads do:
+/// a hit (unless `use_cache` is false) is left out of the backend call, and
+/// a value read from the backend is cached.
+pub(crate) async fn read_data_batch(
+    state: &AppState,
+    backend: &dyn DiagnosticBackend,
+    entity_id: &str,
+    ids: &[String],
+    use_cache: bool,
+) -> Vec<DataReadItem> {
+    let did_store = state.did_store();
+    let targets: Vec<(&String, Option<(u16, Option<DidDefinition>)>)> = ids
+        .iter()
+        .map(|id| {
+            let did = state.resolve_did(entity_id, id);
+            (
+                id,
+                did.map(|did| (did, did_store.get_for_component(did, entity_id))),
+            )
+        })
+        .collect();
+    let is_periodic = |def: &Option<DidDefinition>| {
+        def.as_ref()
+            .is_some_and(|d| matches!(d.read_via, ReadVia::Periodic))
+    };
+    let is_cacheable = |def: &Option<DidDefinition>| def.as_ref().is_some_and(|d| d.cacheable);
+
+    // Taken before the reads, as for single reads
+    let epoch = backend.session_epoch();
+    let cached: Vec<Option<Vec<u8>>> = targets
+        .iter()
+        .map(|(_, resolved)| match resolved {
+            Some((did, def)) if use_cache && is_cacheable(def) => {
+                state.read_cache.get(entity_id, *did, epoch)
+            }
+            _ => None,
+        })
+        .collect();
+
+    let batch: Vec<String> = targets
+        .iter()
+        .zip(&cached)
+        .filter_map(|((id, resolved), cached)| match resolved {
+            Some(_) if cached.is_some() => None,
+            Some((_, def)) if is_periodic(def) => None,
+            Some((did, _)) => Some(format_did(*did)),
+            None => Some(id.to_string()),
+        })
+        .collect();
+    let mut values = backend.read_parameters_batch(&batch).await.into_iter();
+
+    let mut items = Vec::with_capacity(targets.len());
+    for ((id, resolved), cached) in targets.into_iter().zip(cached) {
+        let item = match (resolved, cached) {
+            (Some((did, def)), Some(bytes)) => {
+                Ok(decoded_did_response(state, entity_id, id, did, def, &bytes))
+            }
+            (Some((did, def)), None) => {
+                let read = if is_periodic(&def) {
+                    read_did_bytes(backend, did, def.as_ref()).await
+                } else {
+                    match values.next().map(|(_, value)| value) {
+                        Some(Ok(dv)) => hex::decode(dv.raw.unwrap_or_default())
+                            .map_err(|e| BackendError::Protocol(format!("Invalid raw value: {e}"))),
+                        Some(Err(e)) => Err(e),
+                        None => Err(BackendError::ParameterNotFound(id.clone())),
+                    }
+                };
+                read.map(|bytes| {
+                    if is_cacheable(&def) {
+                        state
+                            .read_cache
+                            .insert(entity_id, did, epoch, bytes.clone());
+                    }
+                    decoded_did_response(state, entity_id, id, did, def, &bytes)
+                })
+            }
+            (None, _) => match values.next().map(|(_, value)| value) {
+                Some(Ok(dv)) => Ok(data_value_response(id, dv, false)),
+                Some(Err(e)) => Err(e),
+                None => Err(BackendError::ParameterNotFound(id.clone())),
+            },
+        };
+        items.push(match item {
+            Ok(response) => DataReadItem::Value(response),
+            Err(e) => DataReadItem::Error {
+                id: id.clone(),
+                error: ApiError::from(e).into_parts().1,
+            },
+        });
+    }
+    items
+}
+
+/// [`DidResponse`] for raw DID bytes, decoded with the DidStore definition
+/// when there is one.
+fn decoded_did_response(
+    state: &AppState,
+    component_id: &str,
+    param_id: &str,
+    did: u16,
+    component_def: Option<DidDefinition>,
+    raw_bytes: &[u8],
+) -> DidResponse {
+    let semantic_id = semantic_id_for(state, component_id, did, component_def.as_ref())
+        .unwrap_or_else(|| param_id.to_string());
+
+    let (value, unit, converted) = if let Some(def) = component_def {
+        match state.did_store().decode(did, raw_bytes) {
+            Ok(decoded) => (decoded, def.unit, true),
+            Err(_) => (serde_json::json!(hex::encode(raw_bytes)), None, false),
+        }
+    } else {
+        // No definition - return raw hex
+        (serde_json::json!(hex::encode(raw_bytes)), None, false)
+    };
+
+    DidResponse {
+        id: semantic_id,
+        did: format_did(did),
+        value,
+        unit,
+        raw: hex::encode(raw_bytes),
+        length: raw_bytes.len(),
+        converted,
+        timestamp: Utc::now().to_rfc3339(),
+    }
+}
+
+/// [`DidResponse`] for a value the backend resolved itself (proxy/app
+/// backends that resolve parameters via upstream HTTP).
+fn data_value_response(param_id: &str, dv: DataValue, raw_only: bool) -> DidResponse {
+    let raw = dv.raw.clone().unwrap_or_default();
+    let length = dv.length.unwrap_or(0);
+    let has_raw = !raw.is_empty();
+    DidResponse {
+        id: param_id.to_string(),
+        did: dv.did.unwrap_or_default(),
+        value: if raw_only && has_raw {
+            serde_json::json!(raw)
+        } else {
+            dv.value
+        },
+        unit: if raw_only { None } else { dv.unit },
+        raw,
+        length,
+        converted: !raw_only && has_raw,
+        timestamp: Utc::now().to_rfc3339(),
+    }
+}
+
 // =============================================================================
 // Internal Implementation
 // =============================================================================
@@ -363,6 +777,7 @@ async fn read_did_internal(
     component_id: &str,
     param_id: &str,
     raw_only: bool,
+    use_cache: bool,
 ) -> Result<Json<DidResponse>, ApiError> {
     let backend = state.get_backend(component_id)?;
     let did_store = state.did_store();
@@ -372,10 +787,11 @@ async fn read_did_internal(
     // not a slashed `param_id` here.  The flat gateway data-routing branch
     // was retired for C-021 (single canonical data-addressing path).
 
-    // Resolve parameter: try semantic name first, then DID hex format
-    // This allows SOVD-compliant names like "coolant_temperature" while
-    // also supporting raw DID access like "F405" for private data
-    let did_u16 = match did_store.resolve_did(param_id) {
+    // Resolve parameter: configured alias first, then semantic name, then
+    // DID hex format. This allows SOVD-compliant names like
+    // "coolant_temperature" while also supporting raw DID access like
+    // "F405" for private data
+    let did_u16 = match state.resolve_did(component_id, param_id) {
         Some(did) => did,
         None => {
             // DID not in local store — fall back to backend.read_data() for
@@ -383,26 +799,10 @@ async fn read_did_internal(
             let values = backend.read_data(&[param_id.to_string()]).await?;
 
             if let Some(dv) = values.into_iter().next() {
-                let raw = dv.raw.clone().unwrap_or_default();
-                let length = dv.length.unwrap_or(0);
-                let has_raw = !raw.is_empty();
-                return Ok(Json(DidResponse {
-                    id: param_id.to_string(),
-                    did: dv.did.unwrap_or_default(),
-                    value: if raw_only && has_raw {
-                        serde_json::json!(raw)
-                    } else {
-                        dv.value
-                    },
-                    unit: if raw_only { None } else { dv.unit },
-                    raw,
-                    length,
-                    converted: !raw_only && has_raw,
-                    timestamp: Utc::now().to_rfc3339(),
-                }));
+                return Ok(Json(data_value_response(param_id, dv, raw_only)));
             }
 
-            return Err(ApiError::BadRequest(format!(
+            return Err(ApiError::NotFound(format!(
                 "Unknown parameter: {}",
                 param_id
             )));
@@ -412,10 +812,8 @@ async fn read_did_internal(
     // Get the definition for this specific component
     let component_def = did_store.get_for_component(did_u16, component_id);
 
-    // Get the semantic ID (from definition or fall back to param_id)
-    let semantic_id = component_def
-        .as_ref()
-        .and_then(|def| def.id.clone())
+    // Get the semantic ID (component alias, then definition, then param_id)
+    let semantic_id = semantic_id_for(state, component_id, did_u16, component_def.as_ref())
         .unwrap_or_else(|| param_id.to_string());
 
     // Read raw bytes via the backend.
@@ -423,7 +821,29 @@ async fn read_did_internal(
     // synthesize identification data from entity_info if possible, else return a
     // truthful 404 (the DID is not a data resource on this entity — it must be read
     // on its owning child) rather than a misleading 501 sovd-server-misconfigured.
-    let raw_bytes = match backend.read_raw_did(did_u16).await {
+    let cacheable = component_def.as_ref().is_some_and(|d| d.cacheable);
+    // Taken before the read: a session lost during it leaves the entry stale
+    let epoch = backend.session_epoch();
+    let cached = if cacheable && use_cache {
+        state.read_cache.get(component_id, did_u16, epoch)
+    } else {
+        None
+    };
+    let read = match cached {
+        Some(bytes) => Ok(bytes),
+        None => {
+            let read = read_did_bytes(backend.as_ref(), did_u16, component_def.as_ref()).await;
+            // Only a bus read starts a new TTL; re-inserting a hit would keep
+            // a polled entry alive forever
+            if let (true, Ok(bytes)) = (cacheable, &read) {
+                state
+                    .read_cache
+                    .insert(component_id, did_u16, epoch, bytes.clone());
+            }
+            read
+        }
+    };
+    let raw_bytes = match read {
         Ok(bytes) => bytes,
         Err(BackendError::NotSupported(_)) => {
             // Synthesize identification data from entity metadata
@@ -466,26 +886,14 @@ async fn read_did_internal(
     }
 
     // Try to decode using DidStore
-    let (value, unit, converted) = if let Some(def) = component_def {
-        match did_store.decode(did_u16, &raw_bytes) {
-            Ok(decoded) => (decoded, def.unit, true),
-            Err(_) => (serde_json::json!(hex::encode(&raw_bytes)), None, false),
-        }
-    } else {
-        // No definition - return raw hex
-        (serde_json::json!(hex::encode(&raw_bytes)), None, false)
-    };
-
-    Ok(Json(DidResponse {
-        id: semantic_id,
-        did: format_did(did_u16),
-        value,
-        unit,
-        raw: hex::encode(&raw_bytes),
-        length: raw_bytes.len(),
-        converted,
-        timestamp: Utc::now().to_rfc3339(),
-    }))
+    Ok(Json(decoded_did_response(
+        state,
+        component_id,
+        param_id,
+        did_u16,
+        component_def,
+        &raw_bytes,
+    )))
 }
 
 async fn write_did_internal(
@@ -501,18 +909,16 @@ async fn write_did_internal(
     // path (`/apps/{child}/data/{param}` → handlers::sub_entity), not a
     // slashed `param_id` here.  Flat gateway routing retired for C-021.
 
-    // Resolve parameter: try semantic name first, then DID hex format
-    let did_u16 = did_store
-        .resolve_did(param_id)
-        .ok_or_else(|| ApiError::BadRequest(format!("Unknown parameter: {}", param_id)))?;
+    // Resolve parameter: alias, then semantic name, then DID hex format
+    let did_u16 = state
+        .resolve_did(component_id, param_id)
+        .ok_or_else(|| ApiError::NotFound(format!("Unknown parameter: {}", param_id)))?;
 
     // Get the definition for this specific component
     let component_def = did_store.get_for_component(did_u16, component_id);
 
-    // Get the semantic ID (from definition or fall back to param_id)
-    let semantic_id = component_def
-        .as_ref()
-        .and_then(|def| def.id.clone())
+    // Get the semantic ID (component alias, then definition, then param_id)
+    let semantic_id = semantic_id_for(state, component_id, did_u16, component_def.as_ref())
         .unwrap_or_else(|| param_id.to_string());
 
     // Raw-vs-converted inference (C-131): a DID whose definition carries a
@@ -522,16 +928,14 @@ async fn write_did_internal(
     // body hint.
     let has_conversion = component_def.as_ref().is_some_and(|d| d.has_conversion());
     let data = if has_conversion {
-        match did_store.encode(did_u16, &request.value) {
-            Ok(bytes) => bytes,
-            Err(_) => convert_value_to_bytes(&request.value)?,
-        }
+        encode_physical_value(did_store, did_u16, &request.value)?
     } else {
         convert_value_to_bytes(&request.value)?
     };
 
     // Write via backend
     backend.write_raw_did(did_u16, &data).await?;
+    state.read_cache.invalidate(component_id, did_u16);
 
     // Return response with the value as it round-trips: decoded physical for a
     // converted DID, raw hex for a raw/undefined DID.
@@ -568,6 +972,28 @@ fn synthesize_entity_did(did: u16, info: &sovd_core::models::EntityInfo) -> Opti
     }
 }
 
+/// Encode a physical write `value` through the DID's definition
+///
+/// A value the definition rejects — outside its `min`/`max`, an array of
+/// the wrong length, a string that doesn't fit its field — is a 400, so
+/// nothing reaches the bus. Any other encode failure falls back to
+/// [`convert_value_to_bytes`], as before.
+pub(crate) fn encode_physical_value(
+    did_store: &DidStore,
+    did: u16,
+    value: &serde_json::Value,
+) -> Result<Vec<u8>, ApiError> {
+    match did_store.encode(did, value) {
+        Ok(bytes) => Ok(bytes),
+        Err(e) if e.is_rejected_value() => Err(ApiError::BadRequest(format!(
+            "DID {}: {}",
+            format_did(did),
+            e
+        ))),
+        Err(_) => convert_value_to_bytes(value),
+    }
+}
+
 /// Convert a *raw* write `value` to bytes — the path taken when the DID has
 /// no conversion definition (so `value` is a raw byte representation, not a
 /// physical value).
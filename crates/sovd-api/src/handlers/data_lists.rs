@@ -4,12 +4,15 @@
 //! Wire shape:
 //!   POST /vehicle/v1/components/:id/operations/define-data/executions
 //!     body: { "ddid": "0xF200", "source_dids": [...] }
-//!     → defines a UDS dynamic DID via 0x2C 0x02; returns the resulting
+//!        or { "ddid": "0xF200", "memory_regions": [{"address": "0x2048", "size": 4}, ...] }
+//!     → defines a UDS dynamic DID from source DID slices (0x2C 0x01) or
+//!       from raw memory regions (0x2C 0x02); returns the resulting
 //!       `data-lists/{list_id}` reference.
 //!   GET  /vehicle/v1/components/:id/data-lists
 //!     → list defined DDIDs.
 //!   GET  /vehicle/v1/components/:id/data-lists/{list_id}
-//!     → read the current value of the dynamic DID via UDS 0x22.
+//!     → read the current value of the dynamic DID via UDS 0x22, decoded
+//!       when a definition is registered for the DDID.
 //!   DELETE /vehicle/v1/components/:id/data-lists/{list_id}
 //!     → clear via UDS 0x2C 0x03.
 //!
@@ -25,6 +28,7 @@ use serde::{Deserialize, Serialize};
 use sovd_conv::format_did;
 
 use crate::error::ApiError;
+use crate::handlers::memory::parse_number;
 use crate::state::AppState;
 
 #[derive(Debug, Deserialize)]
@@ -32,7 +36,11 @@ pub struct DefineDataRequest {
     /// Dynamic DID to create (hex string, e.g. "0xF200")
     pub ddid: String,
     /// Source DID slices
+    #[serde(default)]
     pub source_dids: Vec<SourceDidDefinition>,
+    /// Memory regions, instead of `source_dids`
+    #[serde(default)]
+    pub memory_regions: Vec<MemoryRegionDefinition>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -45,6 +53,14 @@ pub struct SourceDidDefinition {
     pub size: u8,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct MemoryRegionDefinition {
+    /// Start address, hex (`0x2048`) or decimal
+    pub address: String,
+    /// Number of bytes
+    pub size: u32,
+}
+
 /// Execution result for `operations/define-data/executions`.
 ///
 /// Synchronous completion — the dynamic DID is defined and addressable via
@@ -78,6 +94,11 @@ pub struct DataListInfo {
 pub struct DataListReadResponse {
     pub id: String,
     pub ddid: String,
+    /// Decoded value, when a definition is registered for the DDID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
     pub raw: String,
     pub length: usize,
     /// RFC 3339 read time (ISO 17978-3 C-050).
@@ -111,20 +132,42 @@ pub async fn define_data(
         )));
     }
 
-    let mut sources = Vec::new();
-    for source in &request.source_dids {
-        let source_did = parse_did(&source.did)?;
-        sources.push((source_did, source.position, source.size));
+    match (
+        request.source_dids.is_empty(),
+        request.memory_regions.is_empty(),
+    ) {
+        (false, true) => {
+            let mut sources = Vec::new();
+            for source in &request.source_dids {
+                let source_did = parse_did(&source.did)?;
+                sources.push((source_did, source.position, source.size));
+            }
+            backend.define_data_identifier(ddid, &sources).await?;
+        }
+        (true, false) => {
+            let mut regions = Vec::new();
+            for region in &request.memory_regions {
+                let address = parse_number(&region.address).ok_or_else(|| {
+                    ApiError::BadRequest(format!("Invalid memory address: {}", region.address))
+                })?;
+                regions.push((address, region.size));
+            }
+            backend
+                .define_data_identifier_by_memory(ddid, &regions)
+                .await?;
+        }
+        (true, true) => {
+            return Err(ApiError::BadRequest(
+                "At least one source DID or memory region is required".to_string(),
+            ));
+        }
+        (false, false) => {
+            return Err(ApiError::BadRequest(
+                "A data list is defined from source DIDs or memory regions, not both".to_string(),
+            ));
+        }
     }
 
-    if sources.is_empty() {
-        return Err(ApiError::BadRequest(
-            "At least one source DID is required".to_string(),
-        ));
-    }
-
-    backend.define_data_identifier(ddid, &sources).await?;
-
     let list_id = list_id_for(ddid);
     let href = format!(
         "/vehicle/v1/components/{}/data-lists/{}",
@@ -170,9 +213,20 @@ pub async fn read_data_list(
     let ddid = parse_did(&list_id)?;
     let raw_bytes = backend.read_raw_did(ddid).await?;
 
+    let did_store = state.did_store();
+    let (value, unit) = match did_store.get_for_component(ddid, &component_id) {
+        Some(def) => match did_store.decode(ddid, &raw_bytes) {
+            Ok(value) => (Some(value), def.unit),
+            Err(_) => (None, None),
+        },
+        None => (None, None),
+    };
+
     Ok(Json(DataListReadResponse {
         id: list_id_for(ddid),
         ddid: format_did(ddid),
+        value,
+        unit,
         raw: hex::encode(&raw_bytes),
         length: raw_bytes.len(),
         timestamp: Utc::now().to_rfc3339(),
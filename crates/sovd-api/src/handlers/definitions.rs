@@ -168,6 +168,12 @@ pub async fn list_definitions(
     Ok(Json(response).into_response())
 }
 
+/// GET /admin/definitions/schema
+/// JSON Schema for the definition upload format
+pub async fn get_definition_schema() -> Json<serde_json::Value> {
+    Json(sovd_conv::schema::definition_json_schema())
+}
+
 /// GET /admin/definitions/:did
 /// Get a single definition
 pub async fn get_definition(
@@ -1,18 +1,42 @@
 //! Fault/DTC handlers — ISO 17978-3 §7.8
 
-use axum::extract::{Path, Query, State};
+use axum::extract::{Path, Query, RawQuery, State};
 use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use axum::Json;
 use serde::{Deserialize, Serialize};
-use sovd_core::{Fault, FaultFilter, FaultSeverity};
+use sovd_conv::format_did;
+use sovd_core::{
+    BackendError, ClearFaultsResult, DiagnosticBackend, Fault, FaultFilter, FaultMemory,
+    FaultSeverity,
+};
+use sovd_uds::uds::dtc::{severity_bit, status_bit};
 
 use crate::error::ApiError;
+use crate::handlers::data::semantic_id_for;
+use crate::handlers::paging::PageQuery;
 use crate::state::AppState;
 
 #[derive(Serialize)]
 pub struct FaultsResponse {
     pub items: Vec<FaultInfoResponse>,
     pub total_count: usize,
+    /// Href of the next page of a paged listing; absent on the last one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    /// Status bits the entity supports (UDS DTCStatusAvailabilityMask, as
+    /// `0xNN`). Status flags outside the mask are not meaningful. Vendor
+    /// extension: absent for entities without a status byte.
+    #[serde(
+        rename = "x-sumo-status-availability-mask",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status_availability_mask: Option<String>,
+}
+
+/// Wire form of a DTCStatusAvailabilityMask (matches `status.mask`).
+pub(crate) fn format_availability_mask(mask: Option<u8>) -> Option<String> {
+    mask.map(|m| format!("0x{:02X}", m))
 }
 
 /// Spec §7.8 Table 61 (`Fault`).  Wire fields:
@@ -52,13 +76,186 @@ pub struct ClearFaultsResponse {
     pub message: String,
 }
 
+/// Body of `DELETE .../faults` when the backend verified the clear
+/// (vendor extension, listed as `x-sumo-clear-verification`)
+#[derive(Serialize)]
+pub struct ClearVerificationResponse {
+    /// No fault of the cleared group was found on the re-read
+    pub cleared_confirmed: bool,
+    /// Faults still stored after the clear
+    pub remaining: Vec<FaultInfoResponse>,
+}
+
+/// Session switch made to clear the faults (`?auto_session=true`)
+#[derive(Serialize)]
+pub struct SessionEscalation {
+    /// Session active before the clear
+    pub from: String,
+    /// Session the clear ran in
+    pub to: String,
+    /// `from` was re-entered after the clear
+    pub restored: bool,
+}
+
+/// Body of `DELETE .../faults` after an automatic session switch (vendor
+/// extension, listed as `x-sumo-auto-session`)
+#[derive(Serialize)]
+pub struct EscalatedClearResponse {
+    #[serde(flatten)]
+    pub verification: Option<ClearVerificationResponse>,
+    #[serde(rename = "x-sumo-session-escalation")]
+    pub escalation: SessionEscalation,
+}
+
+/// 204 for a plain clear; 200 with the re-read when the backend verified
+/// it, and with the session switch when one was needed
+pub(crate) fn clear_response(
+    result: ClearFaultsResult,
+    escalation: Option<SessionEscalation>,
+) -> Response {
+    let verification = result.verification.map(|v| ClearVerificationResponse {
+        cleared_confirmed: v.cleared_confirmed,
+        remaining: v.remaining.iter().map(FaultInfoResponse::from).collect(),
+    });
+    match (verification, escalation) {
+        (verification, Some(escalation)) => Json(EscalatedClearResponse {
+            verification,
+            escalation,
+        })
+        .into_response(),
+        (Some(v), None) => Json(v).into_response(),
+        (None, None) => StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+/// Query for `DELETE .../faults`
+#[derive(Deserialize, Default)]
+pub struct ClearFaultsQuery {
+    /// Retry a clear refused for the active session in the extended
+    /// session, then switch back
+    #[serde(default)]
+    pub auto_session: bool,
+}
+
+/// Session an `auto_session` clear escalates to
+const CLEAR_SESSION: &str = "extended";
+
+/// Whether the ECU refused the clear because of the active session
+fn is_session_precondition(err: &BackendError) -> bool {
+    matches!(
+        err,
+        BackendError::SessionRequired(_)
+            | BackendError::EcuError {
+                nrc: 0x22 | 0x7E | 0x7F,
+                ..
+            }
+    )
+}
+
+/// Clear all faults, with `auto_session` escalating to the extended
+/// session when the active one is refused.
+///
+/// The prior session is restored afterwards. If the switch itself fails
+/// the original precondition error is returned, as without `auto_session`.
+pub(crate) async fn clear_all_faults(
+    backend: &dyn DiagnosticBackend,
+    auto_session: bool,
+) -> Result<Response, ApiError> {
+    let refused = match backend.clear_faults(None).await {
+        Ok(result) => return Ok(clear_response(result, None)),
+        Err(e) if auto_session && is_session_precondition(&e) => e,
+        Err(e) => return Err(e.into()),
+    };
+
+    let prior = match backend.get_session_mode().await {
+        Ok(mode) if mode.session != CLEAR_SESSION => mode.session,
+        _ => return Err(refused.into()),
+    };
+    if let Err(e) = backend.set_session_mode(CLEAR_SESSION).await {
+        tracing::warn!(error = %e, "auto_session: could not enter {CLEAR_SESSION} session");
+        return Err(refused.into());
+    }
+
+    let result = backend.clear_faults(None).await;
+    let restored = match backend.set_session_mode(&prior).await {
+        Ok(_) => true,
+        Err(e) => {
+            tracing::warn!(error = %e, session = %prior, "auto_session: could not restore session");
+            false
+        }
+    };
+    Ok(clear_response(
+        result?,
+        Some(SessionEscalation {
+            from: prior,
+            to: CLEAR_SESSION.to_string(),
+            restored,
+        }),
+    ))
+}
+
 /// Query: spec uses integer severity (1..4).  Filter is exact-match.
+/// `severity` also takes a comma list of UDS DTCSeverity names
+/// (`maintenance_only`, `check_at_next_halt`, `check_immediately`) and
+/// `status` one of statusOfDTC bit names (`confirmed,pending`); a fault
+/// matches when any of the named bits is set, like a UDS status mask.
+/// `memory` (`primary` | `mirror`) selects the DTC memory area; unset
+/// reads the primary memory. `limit`, `offset` and `q` page the list (see
+/// [`super::paging`]).
 #[derive(Deserialize, Default)]
 pub struct FaultFilterQuery {
-    pub severity: Option<u8>,
+    pub severity: Option<String>,
+    pub status: Option<String>,
     pub category: Option<String>,
     pub active_only: Option<bool>,
     pub limit: Option<usize>,
+    pub memory: Option<FaultMemory>,
+    pub offset: Option<usize>,
+    pub q: Option<String>,
+}
+
+impl FaultFilterQuery {
+    /// Backend filter for the query; `None` when nothing filters. `limit`
+    /// is left to the caller.
+    pub(crate) fn fault_filter(&self) -> Result<Option<FaultFilter>, ApiError> {
+        let mut filter = FaultFilter {
+            category: self.category.clone(),
+            active_only: self.active_only,
+            memory: self.memory,
+            ..Default::default()
+        };
+        if let Some(severity) = &self.severity {
+            match severity.parse::<u8>() {
+                Ok(level) => filter.severity = Some(FaultSeverity::from(level)),
+                Err(_) => {
+                    filter.dtc_severity_mask =
+                        Some(bit_mask(severity, "severity", severity_bit::from_name)?)
+                }
+            }
+        }
+        if let Some(status) = &self.status {
+            filter.status_mask = Some(bit_mask(status, "status", status_bit::from_name)?);
+        }
+        let filters = filter.severity.is_some()
+            || filter.dtc_severity_mask.is_some()
+            || filter.status_mask.is_some()
+            || filter.category.is_some()
+            || filter.active_only.is_some()
+            || filter.memory.is_some();
+        Ok(filters.then_some(filter))
+    }
+}
+
+/// OR of the bits named in a comma list (`confirmed,pending`)
+fn bit_mask(list: &str, param: &str, bit: fn(&str) -> Option<u8>) -> Result<u8, ApiError> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .try_fold(0u8, |mask, name| {
+            bit(name)
+                .map(|b| mask | b)
+                .ok_or_else(|| ApiError::BadRequest(format!("Unknown {param} '{name}'")))
+        })
 }
 
 impl From<&Fault> for FaultInfoResponse {
@@ -80,35 +277,45 @@ impl From<&Fault> for FaultInfoResponse {
 
 /// GET /vehicle/v1/components/:component_id/faults
 /// List all faults
+///
+/// `?limit=`, `?offset=` and `?q=` (code or name substring) answer one page
+/// of the list; `total_count` then counts the matches on all pages.
 pub async fn list_faults(
     State(state): State<AppState>,
     Path(component_id): Path<String>,
+    RawQuery(raw_query): RawQuery,
     Query(query): Query<FaultFilterQuery>,
 ) -> Result<Json<FaultsResponse>, ApiError> {
     let backend = state.get_backend(&component_id)?;
 
-    let filter = if query.severity.is_some()
-        || query.category.is_some()
-        || query.active_only.is_some()
-        || query.limit.is_some()
-    {
-        Some(FaultFilter {
-            severity: query.severity.map(FaultSeverity::from),
-            category: query.category,
-            active_only: query.active_only,
-            limit: query.limit,
-            ..Default::default()
-        })
-    } else {
-        None
+    // `limit` stays with the paging below: a backend cutting the list
+    // short would make every page after the first empty
+    let filter = query.fault_filter()?;
+    let page = PageQuery {
+        limit: query.limit,
+        offset: query.offset,
+        q: query.q,
     };
 
     let result = backend.get_faults(filter.as_ref()).await?;
-    let total_count = result.faults.len();
+    let items: Vec<FaultInfoResponse> = result
+        .faults
+        .iter()
+        .filter(|fault| page.matches([Some(fault.code.as_str()), Some(fault.message.as_str())]))
+        .map(FaultInfoResponse::from)
+        .collect();
+    let page = page.paginate(
+        items,
+        &format!("/vehicle/v1/components/{component_id}/faults"),
+        raw_query.as_deref(),
+    );
 
-    let items: Vec<FaultInfoResponse> = result.faults.iter().map(FaultInfoResponse::from).collect();
-
-    Ok(Json(FaultsResponse { items, total_count }))
+    Ok(Json(FaultsResponse {
+        items: page.items,
+        total_count: page.total_count,
+        next: page.next,
+        status_availability_mask: format_availability_mask(result.status_availability_mask),
+    }))
 }
 
 /// GET /vehicle/v1/components/:component_id/faults/:fault_id
@@ -123,18 +330,157 @@ pub async fn get_fault(
     Ok(Json(FaultInfoResponse::from(&fault)))
 }
 
+#[derive(Serialize)]
+pub struct FaultSnapshotsResponse {
+    pub items: Vec<FaultSnapshotResponse>,
+}
+
+/// One snapshot (freeze frame) record of a fault
+#[derive(Serialize)]
+pub struct FaultSnapshotResponse {
+    pub record_number: u8,
+    /// Recorded DID values keyed by semantic name (hex DID for DIDs
+    /// without one); hex when the DID has no definition or fails to decode
+    pub data: serde_json::Map<String, serde_json::Value>,
+}
+
+/// GET /vehicle/v1/components/:component_id/faults/:fault_id/snapshots
+///
+/// Snapshot records captured with the fault (UDS 0x19 0x04). The DID
+/// definitions of the component size the values in a record, so a record
+/// holding a DID without a definition can only be split when it is the
+/// last one; otherwise the read answers 503.
+pub async fn get_fault_snapshots(
+    State(state): State<AppState>,
+    Path((component_id, fault_id)): Path<(String, String)>,
+) -> Result<Json<FaultSnapshotsResponse>, ApiError> {
+    let backend = state.get_backend(&component_id)?;
+    let definitions = state.did_store().list_for_component(&component_id);
+    let did_lengths = definitions
+        .iter()
+        .filter_map(|(&did, def)| def.expected_byte_length().map(|len| (did, len)))
+        .collect();
+
+    let snapshots = backend.get_fault_snapshots(&fault_id, &did_lengths).await?;
+
+    let items = snapshots
+        .into_iter()
+        .map(|snapshot| {
+            let data = snapshot
+                .items
+                .iter()
+                .map(|item| {
+                    let def = definitions.get(&item.did);
+                    let name = semantic_id_for(&state, &component_id, item.did, def)
+                        .unwrap_or_else(|| format_did(item.did));
+                    let value = def
+                        .and_then(|def| sovd_conv::decode::decode(def, &item.data).ok())
+                        .unwrap_or_else(|| sovd_conv::decode::decode_bytes(&item.data));
+                    (name, value)
+                })
+                .collect();
+            FaultSnapshotResponse {
+                record_number: snapshot.record_number,
+                data,
+            }
+        })
+        .collect();
+
+    Ok(Json(FaultSnapshotsResponse { items }))
+}
+
+/// Query for `GET .../faults/:fault_id/extended-data`
+#[derive(Deserialize, Default)]
+pub struct ExtendedDataQuery {
+    /// Record number, `0x`-prefixed hex or decimal; default `0xFF` (all)
+    pub record: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct FaultExtendedDataResponse {
+    pub items: Vec<ExtendedDataRecordResponse>,
+}
+
+/// One extended data record of a fault
+#[derive(Serialize)]
+pub struct ExtendedDataRecordResponse {
+    pub record_number: u8,
+    /// Configured meaning of the record, e.g. `occurrence_counter`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Record read as a counter, when configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<u64>,
+    /// Record bytes, hex-encoded
+    pub raw: String,
+}
+
+/// Parse a `0x`-prefixed hex or plain decimal record number
+fn parse_record_number(value: &str) -> Option<u8> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u8::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// GET /vehicle/v1/components/:component_id/faults/:fault_id/extended-data
+///
+/// Extended data records stored with the fault (UDS 0x19 0x06): occurrence
+/// and aging counters plus OEM-specific records. `?record=` selects one
+/// record; every record keeps its raw bytes next to any counter value.
+pub async fn get_fault_extended_data(
+    State(state): State<AppState>,
+    Path((component_id, fault_id)): Path<(String, String)>,
+    Query(query): Query<ExtendedDataQuery>,
+) -> Result<Json<FaultExtendedDataResponse>, ApiError> {
+    let record_number = match query.record.as_deref() {
+        Some(record) => parse_record_number(record).ok_or_else(|| {
+            ApiError::BadRequest(format!("Invalid extended data record: {}", record))
+        })?,
+        None => 0xFF,
+    };
+
+    let backend = state.get_backend(&component_id)?;
+    let records = backend
+        .get_fault_extended_data(&fault_id, record_number)
+        .await?;
+
+    let items = records
+        .into_iter()
+        .map(|record| ExtendedDataRecordResponse {
+            record_number: record.record_number,
+            name: record.name,
+            value: record.value,
+            raw: hex::encode(&record.data),
+        })
+        .collect();
+
+    Ok(Json(FaultExtendedDataResponse { items }))
+}
+
 /// DELETE /vehicle/v1/components/:component_id/faults
 ///
 /// Spec mandates 204 No Content for DELETE on a collection (no body).
 /// `ClearFaultsResponse` kept in the codebase for the typed-client
-/// shape but no longer serialized to the wire.
+/// shape but no longer serialized to the wire. An ECU configured to
+/// verify clears answers 200 with a [`ClearVerificationResponse`] instead,
+/// `cleared_confirmed: false` listing the DTCs that survived the clear.
+///
+/// `?auto_session=true` retries a clear the active session refuses in the
+/// extended session and answers 200 reporting the switch.
+///
+/// A refused clear is 409 `precondition-not-fulfilled`, not 412: ISO
+/// 17978-3 §5.8 has no 412, and every other session refusal is 409 too.
 pub async fn clear_faults(
     State(state): State<AppState>,
     Path(component_id): Path<String>,
-) -> Result<StatusCode, ApiError> {
+    Query(query): Query<ClearFaultsQuery>,
+) -> Result<Response, ApiError> {
     let backend = state.get_backend(&component_id)?;
-    let _ = backend.clear_faults(None).await?;
-    Ok(StatusCode::NO_CONTENT)
+    clear_all_faults(backend.as_ref(), query.auto_session).await
 }
 
 /// DELETE /vehicle/v1/components/:component_id/faults/:fault_id
@@ -0,0 +1,38 @@
+//! Software fingerprints (vendor extension `x-sumo-fingerprints`)
+//!
+//! `GET /vehicle/v1/components/{id}/x-sumo-fingerprints` reads the boot
+//! software, application software and application data fingerprints via
+//! [`DiagnosticBackend::read_fingerprints`] — DIDs 0xF183–0xF185 on a UDS
+//! ECU. Where the ECU's fingerprint layout is configured each item carries
+//! the decoded programming `date` and `tester_serial`; the raw bytes are
+//! always included.
+//!
+//! `fingerprints` is not a resource name from ISO 17978-3 Tables 8/10, so
+//! per C-025 it carries the `x-sumo-` prefix and is listed in
+//! `.well-known/sovd-extensions`.
+//!
+//! [`DiagnosticBackend::read_fingerprints`]: sovd_core::DiagnosticBackend::read_fingerprints
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::Serialize;
+
+use sovd_core::Fingerprint;
+
+use crate::error::ApiError;
+use crate::state::AppState;
+
+#[derive(Debug, Serialize)]
+pub struct FingerprintsResponse {
+    pub items: Vec<Fingerprint>,
+}
+
+/// GET /vehicle/v1/components/:component_id/x-sumo-fingerprints
+pub async fn get_fingerprints(
+    State(state): State<AppState>,
+    Path(component_id): Path<String>,
+) -> Result<Json<FingerprintsResponse>, ApiError> {
+    let backend = state.get_backend(&component_id)?;
+    let items = backend.read_fingerprints().await?;
+    Ok(Json(FingerprintsResponse { items }))
+}
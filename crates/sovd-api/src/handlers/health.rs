@@ -0,0 +1,196 @@
+//! Per-backend health (reachability) view
+//!
+//! `GET /vehicle/v1/health/backends` probes every registered backend
+//! concurrently via [`DiagnosticBackend::health_check`] (TesterPresent on a
+//! UDS ECU) and reports whether its target answered, alongside the last time
+//! it did. Fleet monitoring can then tell an ECU that is asleep (unreachable,
+//! server answering) from a broken server (no answer at all). A backend whose
+//! probe fails outright carries its own `error`; the others are unaffected.
+//! A gateway reports the ECUs behind it under `children`, each probed on its
+//! own.
+//!
+//! `GET /vehicle/v1/status` rolls the same probes up into one `ok` /
+//! `degraded` / `down` signal for fleet dashboards, counting a gateway's
+//! ECUs rather than the gateway.
+//!
+//! [`DiagnosticBackend::health_check`]: sovd_core::DiagnosticBackend::health_check
+
+use std::collections::HashMap;
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use sovd_core::{GenericError, HealthStatus};
+
+use crate::auth::ClientContext;
+use crate::error::ApiError;
+use crate::state::AppState;
+
+#[derive(Serialize)]
+pub struct BackendHealthResponse {
+    pub items: Vec<BackendHealth>,
+}
+
+#[derive(Serialize)]
+pub struct BackendHealth {
+    pub component: String,
+    pub href: String,
+    /// Whether the backend's target answered this probe
+    pub reachable: bool,
+    /// Round-trip time of this probe
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Why the target is considered unreachable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Last time a probe of this backend succeeded (this server's lifetime)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_success: Option<DateTime<Utc>>,
+    /// Why the probe itself failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<GenericError>,
+    /// The sub-entities behind this one (a gateway's ECUs), sorted by id
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<BackendHealth>,
+}
+
+impl BackendHealth {
+    fn new(component: String, href: String) -> Self {
+        Self {
+            component,
+            href,
+            reachable: false,
+            latency_ms: None,
+            detail: None,
+            last_success: None,
+            error: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Take over a probe's outcome, its sub-entities' included
+    fn apply(&mut self, status: HealthStatus) {
+        self.reachable = status.reachable;
+        self.latency_ms = status.latency_ms;
+        self.detail = status.detail;
+        self.children = status
+            .children
+            .into_iter()
+            .map(|(id, child)| {
+                let href = format!("{}/apps/{}", self.href, id);
+                let mut item = BackendHealth::new(id, href);
+                item.apply(child);
+                item
+            })
+            .collect();
+    }
+
+    /// (reachable, total) targets: the entry itself, or with sub-entities
+    /// behind it those
+    fn targets(&self) -> (usize, usize) {
+        if self.children.is_empty() {
+            return (usize::from(self.reachable), 1);
+        }
+        self.children
+            .iter()
+            .map(Self::targets)
+            .fold((0, 0), |(r, t), (cr, ct)| (r + cr, t + ct))
+    }
+
+    /// Record now as the last success of every reachable entry under `key`
+    /// (`gateway/ecu` for sub-entities), and fill in each one's
+    /// `last_success`
+    fn record_success(&mut self, key: &str, last_success: &mut HashMap<String, DateTime<Utc>>) {
+        if self.reachable {
+            last_success.insert(key.to_string(), Utc::now());
+        }
+        self.last_success = last_success.get(key).copied();
+        for child in &mut self.children {
+            let key = format!("{}/{}", key, child.component);
+            child.record_success(&key, last_success);
+        }
+    }
+}
+
+/// GET /vehicle/v1/health/backends
+/// Reachability of every registered backend, probed concurrently.
+///
+/// Filtered like the component listing (C-031): with authentication enabled
+/// only the components the client may access are probed.
+pub async fn get_backend_health(
+    State(state): State<AppState>,
+    client: Option<Extension<ClientContext>>,
+) -> Json<BackendHealthResponse> {
+    let client = client.map(|Extension(c)| c);
+    let items = probe_backends(&state, client.as_ref()).await;
+    Json(BackendHealthResponse { items })
+}
+
+#[derive(Serialize)]
+pub struct VehicleStatusResponse {
+    /// `ok`, `degraded` or `down`, per the configured thresholds
+    pub status: &'static str,
+    /// Targets that answered; a gateway counts the ECUs behind it
+    pub reachable: usize,
+    /// Targets probed; a gateway counts the ECUs behind it
+    pub total: usize,
+    /// Per-component breakdown, as in `GET /vehicle/v1/health/backends`
+    pub components: Vec<BackendHealth>,
+}
+
+/// GET /vehicle/v1/status
+/// One vehicle-wide status rolled up from every backend's health check
+/// ([`crate::state::VehicleStatusThresholds`]), with the per-component
+/// breakdown. Filtered like [`get_backend_health`].
+pub async fn get_vehicle_status(
+    State(state): State<AppState>,
+    client: Option<Extension<ClientContext>>,
+) -> Json<VehicleStatusResponse> {
+    let client = client.map(|Extension(c)| c);
+    let components = probe_backends(&state, client.as_ref()).await;
+    let (reachable, total) = components
+        .iter()
+        .map(BackendHealth::targets)
+        .fold((0, 0), |(r, t), (cr, ct)| (r + cr, t + ct));
+    Json(VehicleStatusResponse {
+        status: state.vehicle_status.rollup(reachable, total),
+        reachable,
+        total,
+        components,
+    })
+}
+
+/// Health-check the backends `client` may access, recording and reporting
+/// each one's last success
+async fn probe_backends(state: &AppState, client: Option<&ClientContext>) -> Vec<BackendHealth> {
+    let probes = state
+        .backends()
+        .iter()
+        .filter(|(id, _)| match client {
+            Some(c) => c.can_access_component(id.as_str()),
+            None => true,
+        })
+        .map(|(id, backend)| async move {
+            let mut item = BackendHealth::new(id.clone(), format!("/vehicle/v1/components/{}", id));
+            match backend.health_check().await {
+                Ok(status) => item.apply(status),
+                Err(e) => {
+                    tracing::debug!(component = %id, error = %e, "Health check failed");
+                    item.error = Some(ApiError::from(e).into_parts().1);
+                }
+            }
+            item
+        });
+    let mut items = futures::future::join_all(probes).await;
+
+    let mut last_success = state.backend_health.0.lock();
+    for item in &mut items {
+        let key = item.component.clone();
+        item.record_success(&key, &mut last_success);
+    }
+    drop(last_success);
+    items.sort_by(|a, b| a.component.cmp(&b.component));
+    items
+}
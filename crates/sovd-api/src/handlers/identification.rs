@@ -0,0 +1,137 @@
+//! Vehicle-wide identification (nameplate) aggregate
+//!
+//! `GET /vehicle/v1/identification` reads every ECU's identification block
+//! (VIN, part number, software version, ...) concurrently via
+//! [`DiagnosticBackend::read_identification`] and returns one item per
+//! entity. A component with sub-entities (a gateway) contributes one item
+//! per child instead of one for itself. An entity that fails carries its own
+//! `error` instead of `fields`; the others are unaffected.
+//!
+//! [`DiagnosticBackend::read_identification`]: sovd_core::DiagnosticBackend::read_identification
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::{Extension, Json};
+use serde::{Deserialize, Serialize};
+
+use sovd_core::{BackendError, DiagnosticBackend, GenericError};
+
+use crate::auth::ClientContext;
+use crate::error::ApiError;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct IdentificationQuery {
+    /// Comma-separated field names to read (e.g. `vin,part_number`);
+    /// omitted reads every field
+    pub fields: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct IdentificationResponse {
+    pub items: Vec<ComponentIdentification>,
+}
+
+#[derive(Serialize)]
+pub struct ComponentIdentification {
+    /// Entity id (the child id for an ECU behind a gateway)
+    pub component: String,
+    pub href: String,
+    /// Field name → value; absent when the entity failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<BTreeMap<String, serde_json::Value>>,
+    /// Why this entity's identification could not be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<GenericError>,
+}
+
+impl ComponentIdentification {
+    fn new(component: &str, href: String) -> Self {
+        Self {
+            component: component.to_string(),
+            href,
+            fields: None,
+            error: None,
+        }
+    }
+
+    fn failed(mut self, err: BackendError) -> Self {
+        tracing::debug!(component = %self.component, error = %err, "Identification read failed");
+        self.error = Some(ApiError::from(err).into_parts().1);
+        self
+    }
+}
+
+/// GET /vehicle/v1/identification
+/// Identification block of every ECU, fanned out concurrently.
+///
+/// Filtered like the component listing (C-031): with authentication enabled
+/// only the components the client may access are read.
+pub async fn get_identification(
+    State(state): State<AppState>,
+    client: Option<Extension<ClientContext>>,
+    Query(query): Query<IdentificationQuery>,
+) -> Json<IdentificationResponse> {
+    let client = client.map(|Extension(c)| c);
+    let fields: Option<Vec<String>> = query.fields.as_deref().map(|f| {
+        f.split(',')
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(str::to_string)
+            .collect()
+    });
+
+    let mut components: Vec<_> = state
+        .backends()
+        .iter()
+        .filter(|(id, _)| match &client {
+            Some(c) => c.can_access_component(id.as_str()),
+            None => true,
+        })
+        .collect();
+    components.sort_by(|a, b| a.0.cmp(b.0));
+
+    // Resolve the entities to read: a gateway stands in for its children.
+    let mut items = Vec::new();
+    let mut targets: Vec<(ComponentIdentification, Arc<dyn DiagnosticBackend>)> = Vec::new();
+    for (id, backend) in components {
+        let href = format!("/vehicle/v1/components/{}", id);
+        if !backend.capabilities().sub_entities {
+            targets.push((ComponentIdentification::new(id, href), backend.clone()));
+            continue;
+        }
+        let children = match backend.list_sub_entities().await {
+            Ok(children) => children,
+            Err(e) => {
+                items.push(ComponentIdentification::new(id, href).failed(e));
+                continue;
+            }
+        };
+        for child in children {
+            let item = ComponentIdentification::new(&child.id, format!("{href}/apps/{}", child.id));
+            match backend.get_sub_entity(&child.id).await {
+                Ok(child_backend) => targets.push((item, child_backend)),
+                Err(e) => items.push(item.failed(e)),
+            }
+        }
+    }
+
+    let reads = targets.into_iter().map(|(mut item, backend)| {
+        let fields = fields.as_deref();
+        async move {
+            match backend.read_identification(fields).await {
+                Ok(values) => {
+                    item.fields = Some(values.into_iter().map(|v| (v.id, v.value)).collect());
+                    item
+                }
+                Err(e) => item.failed(e),
+            }
+        }
+    });
+    items.extend(futures::future::join_all(reads).await);
+    items.sort_by(|a, b| a.href.cmp(&b.href));
+
+    Json(IdentificationResponse { items })
+}
@@ -0,0 +1,81 @@
+//! Bus baud-rate switching (vendor extension `x-sumo-link`)
+//!
+//! `GET /vehicle/v1/components/{id}/x-sumo-link` reports the baud rate the
+//! entity's link runs at. `PUT` with a target baud rate runs the UDS
+//! LinkControl (0x87) handshake via [`DiagnosticBackend::set_link_mode`]:
+//! verify the rate, transition, then move the tester's bus to it — e.g.
+//! from 500k to 1M ahead of a high-speed flash. An ECU refusing the rate
+//! answers the verify step with an NRC, which is reported as-is, and
+//! nothing is transitioned.
+//!
+//! LinkControl has no standardized mode name (ISO 17978-3 Table 343: "not
+//! represented"), which is why the former `modes/link` route was dropped.
+//! Per C-025 this resource carries the `x-sumo-` prefix instead and is
+//! listed in `.well-known/sovd-extensions`.
+//!
+//! [`DiagnosticBackend::set_link_mode`]: sovd_core::DiagnosticBackend::set_link_mode
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::Deserialize;
+use sovd_core::{DiagnosticBackend, LinkControlResult, LinkMode};
+
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Body of `PUT .../x-sumo-link`
+///
+/// `{"baud_rate_id": "1m"}` verifies a fixed rate (LinkControl 0x01),
+/// `{"baud_rate": 1000000}` a specific one (0x02). `action` runs a single
+/// step of the handshake instead (`verify_fixed`, `verify_specific`,
+/// `transition`).
+#[derive(Debug, Deserialize)]
+pub struct LinkRequest {
+    #[serde(default = "default_action")]
+    pub action: String,
+    /// Fixed baud rate identifier: "125k", "250k", "500k", "1m" or the
+    /// raw identifier byte ("0x13")
+    #[serde(default)]
+    pub baud_rate_id: Option<String>,
+    /// Specific baud rate in bit/s
+    #[serde(default)]
+    pub baud_rate: Option<u32>,
+}
+
+fn default_action() -> String {
+    "switch".to_string()
+}
+
+/// GET /vehicle/v1/components/:component_id/x-sumo-link
+pub async fn get_link(
+    State(state): State<AppState>,
+    Path(component_id): Path<String>,
+) -> Result<Json<LinkMode>, ApiError> {
+    let backend = state.get_backend(&component_id)?;
+    Ok(Json(backend.get_link_mode().await?))
+}
+
+/// PUT /vehicle/v1/components/:component_id/x-sumo-link
+pub async fn put_link(
+    State(state): State<AppState>,
+    Path(component_id): Path<String>,
+    Json(request): Json<LinkRequest>,
+) -> Result<Json<LinkControlResult>, ApiError> {
+    let backend = state.get_backend(&component_id)?;
+    set_link(backend.as_ref(), request).await
+}
+
+/// Apply `request` to `backend`; shared with the sub-entity route
+pub(crate) async fn set_link(
+    backend: &dyn DiagnosticBackend,
+    request: LinkRequest,
+) -> Result<Json<LinkControlResult>, ApiError> {
+    let result = backend
+        .set_link_mode(
+            &request.action,
+            request.baud_rate_id.as_deref(),
+            request.baud_rate,
+        )
+        .await?;
+    Ok(Json(result))
+}
@@ -174,6 +174,8 @@ mod tests {
                     description: None,
                     href: format!("/vehicle/v1/components/{id}"),
                     status: Some("online".to_string()),
+                    display_name: None,
+                    category: None,
                 },
                 caps: Capabilities::default(),
             }
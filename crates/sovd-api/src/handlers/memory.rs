@@ -0,0 +1,135 @@
+//! Raw ECU memory reads (vendor extension `x-sumo-memory`)
+//!
+//! `GET /vehicle/v1/components/{id}/x-sumo-memory?address=0x...&size=N`
+//! reads `size` bytes starting at `address` via
+//! [`DiagnosticBackend::read_memory`] — UDS ReadMemoryByAddress (0x23) on a
+//! UDS ECU — for calibration regions that are not exposed as data
+//! identifiers. The bytes come back hex-encoded.
+//!
+//! `GET .../x-sumo-memory/upload?address=0x...&size=N[&format=0x..]` reads
+//! the region out as a transfer instead, via
+//! [`DiagnosticBackend::upload_memory`] — RequestUpload (0x35), TransferData
+//! (0x36) until `size` bytes are in, RequestTransferExit (0x37) — for
+//! regions too large for one 0x23 read, such as crash logs. The assembled
+//! bytes come back as `application/octet-stream`.
+//!
+//! `memory` is not a resource name from ISO 17978-3 Tables 8/10, so per
+//! C-025 it carries the `x-sumo-` prefix and is listed in
+//! `.well-known/sovd-extensions`.
+//!
+//! [`DiagnosticBackend::read_memory`]: sovd_core::DiagnosticBackend::read_memory
+//! [`DiagnosticBackend::upload_memory`]: sovd_core::DiagnosticBackend::upload_memory
+
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use sovd_core::DiagnosticBackend;
+
+use crate::error::ApiError;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct MemoryQuery {
+    /// Start address, hex (`0x2048`) or decimal
+    pub address: String,
+    /// Number of bytes to read, hex or decimal
+    pub size: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MemoryUploadQuery {
+    /// Start address, hex (`0x2048`) or decimal
+    pub address: String,
+    /// Number of bytes to upload, hex or decimal
+    pub size: String,
+    /// dataFormatIdentifier of the RequestUpload, hex or decimal
+    /// (default `0x00`: neither compressed nor encrypted)
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryResponse {
+    /// Start address as `0x`-prefixed hex
+    pub address: String,
+    pub size: u32,
+    /// Memory contents, hex-encoded
+    pub data: String,
+}
+
+/// Parse a `0x`-prefixed hex or plain decimal number
+pub(crate) fn parse_number(value: &str) -> Option<u64> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// Parse the `address` and `size` of a memory region
+fn parse_region(address: &str, size: &str) -> Result<(u64, u32), ApiError> {
+    let address = parse_number(address)
+        .ok_or_else(|| ApiError::BadRequest(format!("Invalid memory address: {}", address)))?;
+    let size = parse_number(size)
+        .and_then(|s| u32::try_from(s).ok())
+        .filter(|s| *s > 0)
+        .ok_or_else(|| ApiError::BadRequest(format!("Invalid memory size: {}", size)))?;
+    Ok((address, size))
+}
+
+/// Validate the query and read the memory from `backend`
+pub(crate) async fn read_memory_from(
+    backend: &dyn DiagnosticBackend,
+    query: &MemoryQuery,
+) -> Result<MemoryResponse, ApiError> {
+    let (address, size) = parse_region(&query.address, &query.size)?;
+
+    let data = backend.read_memory(address, size).await?;
+    Ok(MemoryResponse {
+        address: format!("0x{:X}", address),
+        size,
+        data: hex::encode(data),
+    })
+}
+
+/// GET /vehicle/v1/components/:component_id/x-sumo-memory
+pub async fn read_memory(
+    State(state): State<AppState>,
+    Path(component_id): Path<String>,
+    Query(query): Query<MemoryQuery>,
+) -> Result<Json<MemoryResponse>, ApiError> {
+    let backend = state.get_backend(&component_id)?;
+    Ok(Json(read_memory_from(backend.as_ref(), &query).await?))
+}
+
+/// Validate the query and upload the memory from `backend`
+pub(crate) async fn upload_memory_from(
+    backend: &dyn DiagnosticBackend,
+    query: &MemoryUploadQuery,
+) -> Result<impl IntoResponse, ApiError> {
+    let (address, size) = parse_region(&query.address, &query.size)?;
+    let format = match query.format.as_deref() {
+        Some(format) => parse_number(format)
+            .and_then(|f| u8::try_from(f).ok())
+            .ok_or_else(|| ApiError::BadRequest(format!("Invalid data format: {}", format)))?,
+        None => 0x00,
+    };
+
+    let data = backend.upload_memory(address, size, format).await?;
+    Ok(([(header::CONTENT_TYPE, "application/octet-stream")], data))
+}
+
+/// GET /vehicle/v1/components/:component_id/x-sumo-memory/upload
+pub async fn upload_memory(
+    State(state): State<AppState>,
+    Path(component_id): Path<String>,
+    Query(query): Query<MemoryUploadQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let backend = state.get_backend(&component_id)?;
+    upload_memory_from(backend.as_ref(), &query).await
+}
@@ -212,7 +212,7 @@ const PATHS: &[PathEntry] = &[
     PathEntry {
         method: "POST",
         path: "/vehicle/v1/components/{component_id}/operations/define-data/executions",
-        summary: "Define a dynamic data list (UDS 0x2C 0x02).",
+        summary: "Define a dynamic data list from source DIDs or memory (UDS 0x2C 0x01 / 0x02).",
     },
     PathEntry {
         method: "GET",
@@ -702,13 +702,267 @@ pub async fn sovd_extensions() -> Json<serde_json::Value> {
                 "kind":      "sub-resource",
                 "endpoints": [
                     "PUT /vehicle/v1/components/{id}/updates/{update_id}/bulk-data/{part_id}",
+                    "HEAD /vehicle/v1/components/{id}/updates/{update_id}/bulk-data/{part_id}",
                     "GET /vehicle/v1/components/{id}/updates/{update_id}/bulk-data"
                 ],
+                "headers":   ["Content-Range", "Upload-Offset", "Upload-Length"],
                 "spec":      "tasks/spec-aligned-updates-wire.md sec 2.3",
                 "summary": "Client streams update bytes to the server. \
                             Spec model assumes server-pulls-from-OTA \
                             backend; bulk-data is the reverse channel \
-                            for workstation / workshop deployments."
+                            for workstation / workshop deployments. A PUT \
+                            with Content-Range is one in-order chunk of \
+                            the part (202 + Upload-Offset until the last); \
+                            HEAD reports the offset to resume from."
+            },
+            "x-sumo-dry-run": {
+                "kind":   "request field",
+                "where":  "POST /vehicle/v1/components/{id}/updates",
+                "fields": ["x-sumo-dry-run", "x-sumo-flash-summary"],
+                "summary": "Registers a rehearsal update. Prepare verifies \
+                            the parts and negotiates RequestDownload with \
+                            the ECU but sends no TransferData; the planned \
+                            block count and size land in \
+                            x-sumo-flash-summary.plan. Execute is refused."
+            },
+            "x-sumo-identification": {
+                "kind":     "server-level resource",
+                "endpoint": "GET /vehicle/v1/identification",
+                "query":    ["fields"],
+                "summary": "Identification block (VIN, part number, \
+                            software version, ...) of every component in \
+                            one call, read concurrently; a component that \
+                            fails carries its own error. ?fields=vin,part_number \
+                            limits which identification DIDs are read."
+            },
+            "x-sumo-backend-health": {
+                "kind":     "server-level resource",
+                "endpoint": "GET /vehicle/v1/health/backends",
+                "fields":   ["reachable", "latency_ms", "detail", "last_success"],
+                "summary": "Probes every backend concurrently (TesterPresent \
+                            on a UDS ECU) and reports whether its target \
+                            answered and when it last did, telling an ECU \
+                            that is asleep from a server that is broken."
+            },
+            "x-sumo-vehicle-status": {
+                "kind":     "server-level resource",
+                "endpoint": "GET /vehicle/v1/status",
+                "values":   ["ok", "degraded", "down"],
+                "fields":   ["status", "reachable", "total", "components"],
+                "summary": "Backend health rolled up into one vehicle \
+                            status: ok when at least [vehicle_status] \
+                            ok_ratio of the backends are reachable (default \
+                            all), down at or below down_ratio (default \
+                            none), degraded in between. components is the \
+                            x-sumo-backend-health breakdown."
+            },
+            "x-sumo-topology": {
+                "kind":     "server-level resource",
+                "endpoint": "GET /vehicle/v1/topology",
+                "fields":   ["type", "transport", "address", "reachable", "children"],
+                "summary": "The vehicle's components as a tree, gateways \
+                            nesting the ECUs and servers behind them, with \
+                            each node's transport kind, bus address and \
+                            reachability, for E/E architecture views and \
+                            spotting misplaced auto-discovered ECUs."
+            },
+            "x-sumo-memory": {
+                "kind":      "sub-resource",
+                "endpoints": [
+                    "GET /vehicle/v1/components/{id}/x-sumo-memory",
+                    "GET /vehicle/v1/components/{id}/apps/{app_id}/x-sumo-memory",
+                    "GET /vehicle/v1/components/{id}/x-sumo-memory/upload",
+                    "GET /vehicle/v1/components/{id}/apps/{app_id}/x-sumo-memory/upload"
+                ],
+                "query":     ["address", "size", "format"],
+                "summary": "Raw memory read (UDS ReadMemoryByAddress 0x23) \
+                            of size bytes at address, hex-encoded — for \
+                            calibration regions not exposed as data \
+                            identifiers. /upload reads the region out as a \
+                            transfer (RequestUpload 0x35, TransferData, \
+                            RequestTransferExit) and returns the bytes as \
+                            application/octet-stream; format is the \
+                            dataFormatIdentifier (default 0x00)."
+            },
+            "x-sumo-batch-read": {
+                "kind":      "query-param",
+                "endpoints": [
+                    "GET /vehicle/v1/components/{id}/data",
+                    "GET /vehicle/v1/components/{id}/apps/{app_id}/data"
+                ],
+                "query":     ["ids"],
+                "summary": "?ids=a,b,c reads the listed parameters in one \
+                            call instead of listing them: one item per id, \
+                            in request order, each the single-read body or \
+                            its own error. UDS ECUs configured for it pack \
+                            several DIDs into each ReadDataByIdentifier."
+            },
+            "x-sumo-batch-write": {
+                "kind":     "sub-resource",
+                "endpoint": "POST /vehicle/v1/components/{id}/data:batch",
+                "fields":   ["values", "stop_on_error", "items", "failed_index"],
+                "summary": "Writes the parameters of values (id to value) \
+                            in order, each like a single PUT, and reports \
+                            written / failed / skipped per parameter. Best \
+                            effort: UDS has no transaction, so writes \
+                            before a failure are not rolled back; \
+                            failed_index is the first failure. With \
+                            stop_on_error the rest are skipped."
+            },
+            "x-sumo-paging": {
+                "kind":      "query-param",
+                "endpoints": [
+                    "GET /vehicle/v1/components/{id}/data",
+                    "GET /vehicle/v1/components/{id}/faults"
+                ],
+                "query":     ["limit", "offset", "q"],
+                "fields":    ["total_count", "next"],
+                "summary": "Answers one page of the list instead of all of \
+                            it: q keeps items whose id or name contains it, \
+                            offset skips into the matches and limit caps \
+                            the page. total_count counts the matches on \
+                            all pages; next is the href of the following \
+                            page, absent on the last."
+            },
+            "x-sumo-dtc-filter": {
+                "kind":      "query-param",
+                "endpoints": [
+                    "GET /vehicle/v1/components/{id}/faults",
+                    "GET /vehicle/v1/components/{id}/apps/{app}/faults"
+                ],
+                "query":     ["status", "severity"],
+                "summary": "status takes a comma list of UDS statusOfDTC \
+                            bit names (confirmed, pending, test_failed, \
+                            ...) and severity, besides the spec integer, \
+                            DTCSeverity names (maintenance_only, \
+                            check_at_next_halt, check_immediately). A fault \
+                            matches when any named bit is set; severity \
+                            names need an ECU reporting DTC severity."
+            },
+            "x-sumo-clear-verification": {
+                "kind":   "response body",
+                "where":  "DELETE /vehicle/v1/components/{id}/faults",
+                "fields": ["cleared_confirmed", "remaining"],
+                "summary": "For ECUs configured with verify_clear, the DTCs \
+                            are re-read after the clear and the DELETE \
+                            answers 200 with whether the clear took effect \
+                            and the faults still stored, instead of 204."
+            },
+            "x-sumo-auto-session": {
+                "kind":      "query-param",
+                "endpoints": [
+                    "DELETE /vehicle/v1/components/{id}/faults",
+                    "DELETE /vehicle/v1/components/{id}/apps/{app_id}/faults"
+                ],
+                "query":     ["auto_session"],
+                "fields":    ["x-sumo-session-escalation"],
+                "summary": "?auto_session=true retries a clear the active \
+                            session refuses in the extended session, then \
+                            switches back, answering 200 with the switch \
+                            (from, to, restored). If the switch fails the \
+                            original precondition error (409 \
+                            precondition-not-fulfilled) is returned."
+            },
+            "x-sumo-fingerprints": {
+                "kind":      "sub-resource",
+                "endpoints": [
+                    "GET /vehicle/v1/components/{id}/x-sumo-fingerprints",
+                    "GET /vehicle/v1/components/{id}/apps/{app_id}/x-sumo-fingerprints"
+                ],
+                "fields":    ["type", "did", "date", "tester_serial", "raw"],
+                "summary": "Boot/application software and application data \
+                            fingerprints (DIDs 0xF183-0xF185). date and \
+                            tester_serial are decoded when the ECU's \
+                            fingerprint format is configured; raw is \
+                            always present."
+            },
+            "x-sumo-transport-stats": {
+                "kind":      "sub-resource",
+                "endpoints": [
+                    "GET /vehicle/v1/components/{id}/x-sumo-transport-stats",
+                    "GET /vehicle/v1/components/{id}/apps/{app_id}/x-sumo-transport-stats"
+                ],
+                "fields":    ["kind", "tx_dl", "can_fd", "max_message_len"],
+                "summary": "Transport the entity is reached over. On CAN, \
+                            tx_dl is the ISO-TP TX data length in effect \
+                            (8 on classic CAN, up to 64 on CAN FD) and \
+                            max_message_len the largest UDS message one \
+                            request can carry."
+            },
+            "x-sumo-link": {
+                "kind":      "sub-resource",
+                "endpoints": [
+                    "GET /vehicle/v1/components/{id}/x-sumo-link",
+                    "PUT /vehicle/v1/components/{id}/x-sumo-link",
+                    "GET /vehicle/v1/components/{id}/apps/{app_id}/x-sumo-link",
+                    "PUT /vehicle/v1/components/{id}/apps/{app_id}/x-sumo-link"
+                ],
+                "fields":    ["baud_rate_id", "baud_rate", "action"],
+                "summary": "UDS LinkControl (0x87), which has no SOVD mode. \
+                            PUT {\"baud_rate_id\": \"1m\"} (or baud_rate in \
+                            bit/s) verifies the rate, transitions and \
+                            switches the CAN interface to it; a refused \
+                            verify reports the ECU's NRC and transitions \
+                            nothing. GET reports the current baud rate."
+            },
+            "x-sumo-mode": {
+                "kind":   "request/response field",
+                "where":  "POST /vehicle/v1/components/{id}/cyclic-subscriptions",
+                "values": ["cyclic", "on_change"],
+                "fields": ["type", "deadband", "deadband_pct"],
+                "summary": "{\"type\": \"on_change\", \"deadband\": d, \
+                            \"deadband_pct\": p} samples at the interval's \
+                            rate but only emits an event when the decoded \
+                            value changed since the last one emitted \
+                            (numbers by more than d and by more than p \
+                            percent of the last value; both default to 0). \
+                            Omitted or cyclic: every sample is emitted."
+            },
+            "x-sumo-units": {
+                "kind":   "event payload field",
+                "where":  "cyclic-subscription EventEnvelope payload",
+                "fields": ["x-sumo-units", "x-sumo-unit-passthrough"],
+                "summary": "With [subscriptions.normalize_units] naming a \
+                            target unit for the parameter, values are \
+                            converted from the definition's unit and \
+                            x-sumo-units maps the parameter to the unit \
+                            applied. A value that does not convert streams \
+                            unchanged in its own unit with \
+                            x-sumo-unit-passthrough: true."
+            },
+            "x-sumo-websocket": {
+                "kind":      "delivery",
+                "endpoints": [
+                    "GET /vehicle/v1/components/{id}/cyclic-subscriptions/{sub_id} (WebSocket upgrade)"
+                ],
+                "controls":  ["pause", "resume", "set_interval"],
+                "summary": "The cyclic-subscription resource also accepts a \
+                            WebSocket upgrade: one EventEnvelope per text \
+                            frame, as in the SSE data lines. The client \
+                            sends {\"action\": ...} messages to pause, \
+                            resume or change the interval of the live \
+                            subscription; a refused one is answered with \
+                            an error envelope."
+            },
+            "x-sumo-event": {
+                "kind":   "EventEnvelope field",
+                "where":  "GET /vehicle/v1/components/{id}/cyclic-subscriptions/{sub_id} (SSE, WebSocket)",
+                "values": ["resumed"],
+                "summary": "Marks an envelope with neither payload nor \
+                            error. resumed: the ECU was reset and the \
+                            server re-armed the subscription once it \
+                            answered again (subscription_recovery); \
+                            values sampled in the gap are lost."
+            },
+            "x-sumo-timing": {
+                "kind":   "response field",
+                "where":  "GET|PUT /vehicle/v1/components/{id}/modes/session",
+                "fields": ["p2_server_max_ms", "p2_star_server_max_ms"],
+                "summary": "P2/P2* server timing the ECU advertised in its \
+                            DiagnosticSessionControl (0x10) response, which \
+                            the server adopts as its response timeouts for \
+                            the active session. Absent when the ECU sent \
+                            no sessionParameterRecord."
             },
             "x-sumo-multiple": {
                 "kind":  "value token",
@@ -0,0 +1,253 @@
+//! Prometheus transport metrics (feature `prometheus`)
+//!
+//! `GET /metrics` renders every registered backend's
+//! [`DiagnosticBackend::transport_metrics`] in the Prometheus text
+//! exposition format, one series per component: request, timeout and
+//! retransmit counters, message and frame counts, the flow-control STmin
+//! wait and a round-trip latency histogram. The ISO-TP block size, STmin
+//! and TX data length from [`DiagnosticBackend::transport_stats`] are
+//! exported as gauges next to them, so slow reads can be correlated with
+//! the link settings in one query.
+//!
+//! Backends without a transport of their own (gateways, proxies) are left
+//! out. `/metrics` is outside the SOVD entity tree and not public: with
+//! authentication enabled the scraper needs a token like any other client.
+//!
+//! [`DiagnosticBackend::transport_metrics`]: sovd_core::DiagnosticBackend::transport_metrics
+//! [`DiagnosticBackend::transport_stats`]: sovd_core::DiagnosticBackend::transport_stats
+
+use std::fmt::Write;
+
+use axum::extract::State;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+
+use sovd_core::{TransportMetrics, TransportStats};
+
+use crate::state::AppState;
+
+/// Prometheus text exposition format
+const TEXT_FORMAT: &str = "text/plain; version=0.0.4";
+
+/// One component's transport, as scraped
+struct Scrape {
+    component: String,
+    stats: TransportStats,
+    metrics: TransportMetrics,
+}
+
+/// GET /metrics
+pub async fn get_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    let mut ids: Vec<&String> = state.backends().keys().collect();
+    ids.sort();
+    let mut scrapes = Vec::new();
+    for id in ids {
+        let backend = &state.backends()[id];
+        let Ok(metrics) = backend.transport_metrics().await else {
+            continue;
+        };
+        scrapes.push(Scrape {
+            component: id.clone(),
+            stats: backend.transport_stats().await.unwrap_or_default(),
+            metrics,
+        });
+    }
+    ([(CONTENT_TYPE, TEXT_FORMAT)], render(&scrapes))
+}
+
+/// Write one metric family: its HELP/TYPE header and a sample per scrape
+fn family(
+    out: &mut String,
+    scrapes: &[Scrape],
+    name: &str,
+    kind: &str,
+    help: &str,
+    value: impl Fn(&Scrape) -> Option<f64>,
+) {
+    let samples: Vec<_> = scrapes
+        .iter()
+        .filter_map(|s| value(s).map(|v| (&s.component, v)))
+        .collect();
+    if samples.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    for (component, v) in samples {
+        let _ = writeln!(out, "{name}{{component=\"{component}\"}} {v}");
+    }
+}
+
+fn render(scrapes: &[Scrape]) -> String {
+    let mut out = String::new();
+    let counters: [(&str, &str, fn(&TransportMetrics) -> f64); 9] = [
+        ("requests", "UDS requests sent expecting a response", |m| {
+            m.requests as f64
+        }),
+        ("timeouts", "Requests that got no response in time", |m| {
+            m.timeouts as f64
+        }),
+        ("errors", "Requests that failed on the transport", |m| {
+            m.errors as f64
+        }),
+        ("retransmits", "Requests re-sent after timing out", |m| {
+            m.retransmits as f64
+        }),
+        ("messages_sent", "UDS messages sent", |m| {
+            m.messages_sent as f64
+        }),
+        ("messages_received", "UDS responses received", |m| {
+            m.messages_received as f64
+        }),
+        ("frames_sent", "Link frames sent", |m| m.frames_sent as f64),
+        ("frames_received", "Link frames received", |m| {
+            m.frames_received as f64
+        }),
+        (
+            "flow_control_wait_seconds",
+            "STmin wait our flow control imposed on consecutive frames",
+            |m| m.flow_control_wait_us as f64 / 1e6,
+        ),
+    ];
+    for (name, help, value) in counters {
+        family(
+            &mut out,
+            scrapes,
+            &format!("sovd_transport_{name}_total"),
+            "counter",
+            help,
+            |s| Some(value(&s.metrics)),
+        );
+    }
+
+    let gauges: [(&str, &str, fn(&TransportStats) -> Option<f64>); 3] = [
+        ("isotp_tx_dl", "ISO-TP TX data length", |s| {
+            s.tx_dl.map(f64::from)
+        }),
+        ("isotp_block_size", "ISO-TP block size we advertise", |s| {
+            s.block_size.map(f64::from)
+        }),
+        ("isotp_st_min_seconds", "ISO-TP STmin we advertise", |s| {
+            s.st_min_us.map(|us| f64::from(us) / 1e6)
+        }),
+    ];
+    for (name, help, value) in gauges {
+        family(
+            &mut out,
+            scrapes,
+            &format!("sovd_transport_{name}"),
+            "gauge",
+            help,
+            |s| value(&s.stats),
+        );
+    }
+
+    if !scrapes.is_empty() {
+        let name = "sovd_transport_request_duration_seconds";
+        let _ = writeln!(out, "# HELP {name} Round-trip latency of answered requests");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        for s in scrapes {
+            let latency = &s.metrics.latency;
+            let component = &s.component;
+            for bucket in &latency.buckets {
+                let le = bucket.le_ms as f64 / 1000.0;
+                let _ = writeln!(
+                    out,
+                    "{name}_bucket{{component=\"{component}\",le=\"{le}\"}} {}",
+                    bucket.count
+                );
+            }
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{component=\"{component}\",le=\"+Inf\"}} {}",
+                latency.count
+            );
+            let _ = writeln!(
+                out,
+                "{name}_sum{{component=\"{component}\"}} {}",
+                latency.sum_ms / 1000.0
+            );
+            let _ = writeln!(
+                out,
+                "{name}_count{{component=\"{component}\"}} {}",
+                latency.count
+            );
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovd_core::{LatencyBucket, LatencyHistogram};
+
+    #[test]
+    fn renders_counters_gauges_and_histogram() {
+        let scrapes = [Scrape {
+            component: "engine_ecu".to_string(),
+            stats: TransportStats {
+                kind: "socketcan".to_string(),
+                tx_dl: Some(8),
+                block_size: Some(0),
+                st_min_us: Some(5000),
+                ..Default::default()
+            },
+            metrics: TransportMetrics {
+                requests: 4,
+                retransmits: 1,
+                flow_control_wait_us: 1_500_000,
+                latency: LatencyHistogram {
+                    buckets: vec![
+                        LatencyBucket { le_ms: 5, count: 1 },
+                        LatencyBucket {
+                            le_ms: 50,
+                            count: 3,
+                        },
+                    ],
+                    sum_ms: 60.0,
+                    count: 3,
+                },
+                ..Default::default()
+            },
+        }];
+
+        let text = render(&scrapes);
+
+        assert!(text.contains("# TYPE sovd_transport_requests_total counter\n"));
+        assert!(text.contains("sovd_transport_requests_total{component=\"engine_ecu\"} 4\n"));
+        assert!(text.contains("sovd_transport_retransmits_total{component=\"engine_ecu\"} 1\n"));
+        assert!(text.contains(
+            "sovd_transport_flow_control_wait_seconds_total{component=\"engine_ecu\"} 1.5\n"
+        ));
+        assert!(
+            text.contains("sovd_transport_isotp_st_min_seconds{component=\"engine_ecu\"} 0.005\n")
+        );
+        assert!(text.contains(
+            "sovd_transport_request_duration_seconds_bucket{component=\"engine_ecu\",le=\"0.05\"} 3\n"
+        ));
+        assert!(text.contains(
+            "sovd_transport_request_duration_seconds_bucket{component=\"engine_ecu\",le=\"+Inf\"} 3\n"
+        ));
+        assert!(text.contains(
+            "sovd_transport_request_duration_seconds_sum{component=\"engine_ecu\"} 0.06\n"
+        ));
+    }
+
+    #[test]
+    fn non_can_transport_has_no_isotp_gauges() {
+        let scrapes = [Scrape {
+            component: "ecu".to_string(),
+            stats: TransportStats {
+                kind: "doip".to_string(),
+                ..Default::default()
+            },
+            metrics: TransportMetrics::default(),
+        }];
+
+        let text = render(&scrapes);
+
+        assert!(text.contains("sovd_transport_requests_total{component=\"ecu\"} 0\n"));
+        assert!(!text.contains("isotp"));
+    }
+}
@@ -10,6 +10,10 @@ pub mod data;
 pub mod data_lists;
 pub mod definitions;
 pub mod faults;
+pub mod fingerprints;
+pub mod health;
+pub mod identification;
+pub mod link;
 // F.D8b: handlers::files + handlers::flash deleted.  The legacy
 // wire shapes they served are replaced by /updates (F.D2).
 // C-025: handlers::discovery (POST /discovery) + handlers::streams
@@ -19,12 +23,18 @@ pub mod faults;
 // `cyclic-subscriptions/{id}` resource itself under content negotiation.
 pub mod logs;
 pub mod logs_ext;
+pub mod memory;
 pub mod meta;
+#[cfg(feature = "prometheus")]
+pub mod metrics;
 pub mod modes;
 pub mod operations;
+pub mod paging;
 pub mod reset;
 pub mod software;
 pub mod stubs;
 pub mod sub_entity;
 pub mod subscriptions;
+pub mod topology;
+pub mod transport_stats;
 pub mod updates;
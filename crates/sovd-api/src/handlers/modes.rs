@@ -14,7 +14,7 @@ use std::sync::Arc;
 use axum::extract::{Path, Query, State};
 use axum::Json;
 use serde::{Deserialize, Serialize};
-use sovd_core::{DiagnosticBackend, SecurityState};
+use sovd_core::{DiagnosticBackend, SecurityState, SessionTimingParameters};
 
 use crate::error::ApiError;
 use crate::state::AppState;
@@ -64,6 +64,9 @@ pub struct SessionModeResponse {
     pub id: String,
     /// Current session value
     pub value: String,
+    /// P2/P2* the ECU advertised for the active session (vendor extension)
+    #[serde(rename = "x-sumo-timing", skip_serializing_if = "Option::is_none")]
+    pub timing: Option<SessionTimingParameters>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -130,6 +133,7 @@ pub async fn get_session_mode(
     Ok(Json(SessionModeResponse {
         id: "session".to_string(),
         value: mode.session,
+        timing: mode.timing,
     }))
 }
 
@@ -148,9 +152,12 @@ pub async fn put_session_mode(
         backend.clone()
     };
     let mode = target_backend.set_session_mode(&request.value).await?;
+    // Cached reads may not hold in the new session.
+    state.read_cache.invalidate_component(&component_id);
     Ok(Json(SessionModeResponse {
         id: "session".to_string(),
         value: mode.session,
+        timing: mode.timing,
     }))
 }
 
@@ -3,7 +3,8 @@
 //! Wire shape (Phase E — C-080):
 //!
 //!   `POST /vehicle/v1/components/{id}/operations/{op_id}/executions`
-//!     body: `{parameters?: "<hex>" | <io-control-object>}`
+//!     body: `{parameters?: "<hex>" | <io-control-object>}`, or for
+//!     RoutineControl ops `{params: {...}}` / `{raw_option_record: "<hex>"}`
 //!     → `202 Accepted` + `Location: .../executions/{exec_id}` +
 //!       placeholder `OperationExecution { status: running }` body.
 //!       The backend call runs in a tokio task; clients poll
@@ -17,13 +18,26 @@
 //!
 //!   `DELETE /vehicle/v1/components/{id}/operations/{op_id}/executions/{exec_id}`
 //!     → 204 No Content (UDS RoutineControl 0x31 0x02 stop).
+//!
+//! IO control ops also take `{"action": "ramp", ...}` and
+//! `{"action": "sequence", ...}` (see `sovd_uds::output_conv::OutputProfile`):
+//! the execution stays `running` while a background task sends the
+//! short-term adjustments, its `result` reporting progress as
+//! `{sent, total, value}`.  `DELETE` stops the task and returns control
+//! to the ECU; a profile that runs out keeps its last value.
+
+use std::sync::Arc;
 
 use axum::extract::{Path, Query, State};
 use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
-use sovd_core::{IoControlAction, OperationExecution, OperationInfo, OperationStatus};
+use sovd_core::{
+    DiagnosticBackend, IoControlAction, OperationExecution, OperationInfo, OperationStatus,
+};
+use sovd_uds::output_conv::OutputProfile;
+use tokio::sync::watch;
 use uuid::Uuid;
 
 use crate::error::ApiError;
@@ -66,7 +80,7 @@ pub struct OperationInfoResponse {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub allowed: Vec<String>,
     /// Supported IO control actions: `return_to_ecu`, `reset_to_default`,
-    /// `freeze`, `short_term_adjust`.
+    /// `freeze`, `short_term_adjust`, and the `ramp` / `sequence` profiles.
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub control_types: Vec<String>,
     /// Current raw value as hex string (populated on `op.read` for
@@ -86,6 +100,19 @@ pub struct OperationInfoResponse {
     /// `true` when the value is frozen via `freeze_current_state`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub frozen: Option<bool>,
+    /// Last IO control action the ECU accepted for the output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_action: Option<String>,
+    /// Typed value of the last short-term adjustment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_value: Option<serde_json::Value>,
+    /// When the last IO control action was accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_commanded_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// `true` when the session the tester took control in has since
+    /// reset, which handed the output back to the ECU.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_reset: Option<bool>,
 }
 
 fn default_operation_response() -> OperationInfoResponse {
@@ -107,6 +134,10 @@ fn default_operation_response() -> OperationInfoResponse {
         default: None,
         controlled_by_tester: None,
         frozen: None,
+        last_action: None,
+        last_value: None,
+        last_commanded_at: None,
+        session_reset: None,
     }
 }
 
@@ -130,11 +161,23 @@ impl From<&OperationInfo> for OperationInfoResponse {
 ///   - String — hex-encoded RoutineControl bytes (UDS 0x31 path).
 ///   - Object — structured IO control request (UDS 0x2F path),
 ///     `{"action": "freeze" | "reset_to_default" | "return_to_ecu"
-///     | "short_term_adjust", "value": <optional>}`.
+///     | "short_term_adjust", "value": <optional>}`, or a `ramp` /
+///     `sequence` profile of short-term adjustments.
+///
+/// RoutineControl ops can instead start the routine (sub-function 0x01)
+/// from `params` or `raw_option_record`; at most one of the three keys may
+/// be given.
 #[derive(Debug, Deserialize, Default)]
 pub struct StartExecutionRequest {
     #[serde(default)]
     pub parameters: Option<serde_json::Value>,
+    /// Named routine parameters, encoded by the backend from the
+    /// operation's parameter layout into the option record.
+    #[serde(default)]
+    pub params: Option<serde_json::Value>,
+    /// Hex option record, sent as-is after the routine identifier.
+    #[serde(default)]
+    pub raw_option_record: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -223,13 +266,16 @@ pub async fn get_operation(
         let cfg = state.get_output_config(&component_id, &operation_id);
 
         // Always-known per-spec control set (the four UDS 0x2F sub-
-        // functions); plumbed via the OperationInfoResponse so a
-        // single GET answers "what can I do".
+        // functions, plus the short-term adjustment profiles); plumbed
+        // via the OperationInfoResponse so a single GET answers "what
+        // can I do".
         let control_types = vec![
             "return_to_ecu".to_string(),
             "reset_to_default".to_string(),
             "freeze".to_string(),
             "short_term_adjust".to_string(),
+            "ramp".to_string(),
+            "sequence".to_string(),
         ];
 
         return Ok(Json(OperationInfoResponse {
@@ -248,6 +294,10 @@ pub async fn get_operation(
             default: detail.as_ref().and_then(|d| d.default.clone()),
             controlled_by_tester: detail.as_ref().map(|d| d.controlled_by_tester),
             frozen: detail.as_ref().map(|d| d.frozen),
+            last_action: detail.as_ref().and_then(|d| d.last_action.clone()),
+            last_value: detail.as_ref().and_then(|d| d.last_value.clone()),
+            last_commanded_at: detail.as_ref().and_then(|d| d.last_commanded_at),
+            session_reset: detail.as_ref().map(|d| d.session_reset),
             ..default_operation_response()
         }));
     }
@@ -306,29 +356,100 @@ pub async fn start_operation_execution(
         Routine {
             params: Vec<u8>,
         },
+        Profile {
+            profile: OutputProfile,
+            cancel: watch::Receiver<bool>,
+        },
     }
+    let exec_id = Uuid::new_v4().to_string();
     let dispatch = if is_output {
-        let (action, value) = parse_io_control_params(request.parameters.as_ref())?;
-        Dispatch::IoControl { action, value }
+        if request.params.is_some() || request.raw_option_record.is_some() {
+            return Err(ApiError::BadRequest(format!(
+                "Operation '{}' is an IO control op; `params` and `raw_option_record` \
+                 apply to RoutineControl ops only",
+                operation_id
+            )));
+        }
+        let profile = match request.parameters.as_ref() {
+            Some(serde_json::Value::Object(obj)) => OutputProfile::from_params(obj)
+                .map_err(|e| ApiError::BadRequest(format!("Invalid IO control profile: {}", e)))?,
+            _ => None,
+        };
+        if let Some(mut profile) = profile {
+            // Every setpoint is range-checked up-front, so a bad one
+            // refuses the whole profile before the output moves.
+            for (_, value) in &mut profile.setpoints {
+                *value = backend.check_output_value(&operation_id, value).await?;
+            }
+            // A profile ending past what the clock can represent could
+            // never be scheduled to its end
+            if profile
+                .length()
+                .is_some_and(|length| tokio::time::Instant::now().checked_add(length).is_none())
+            {
+                return Err(ApiError::BadRequest(
+                    "Invalid IO control profile: runs for longer than can be scheduled".into(),
+                ));
+            }
+            let cancel = state
+                .output_profiles
+                .start(&component_id, &operation_id, &exec_id);
+            Dispatch::Profile { profile, cancel }
+        } else {
+            let (action, mut value) = parse_io_control_params(request.parameters.as_ref())?;
+            // Out-of-range adjustments are refused here (400), not after the
+            // 202; a clamped value replaces the requested one.
+            if let (IoControlAction::ShortTermAdjust, Some(v)) = (action, value.as_ref()) {
+                value = Some(backend.check_output_value(&operation_id, v).await?);
+            }
+            Dispatch::IoControl { action, value }
+        }
     } else {
-        let params: Vec<u8> = match request.parameters.as_ref() {
-            Some(serde_json::Value::String(hex)) => hex::decode(hex)
-                .map_err(|e| ApiError::BadRequest(format!("Invalid hex parameters: {}", e)))?,
-            Some(serde_json::Value::Null) | None => Vec::new(),
-            Some(other) => {
-                return Err(ApiError::BadRequest(format!(
-                    "Operation '{}' is a RoutineControl op; parameters must be a hex string, got {}",
-                    operation_id, other
-                )));
+        let supplied = [
+            request.parameters.is_some(),
+            request.params.is_some(),
+            request.raw_option_record.is_some(),
+        ];
+        if supplied.iter().filter(|s| **s).count() > 1 {
+            return Err(ApiError::BadRequest(
+                "Give only one of `parameters`, `params` and `raw_option_record`".into(),
+            ));
+        }
+
+        // `params` and `raw_option_record` carry only the option record;
+        // prepend the start sub-function that `parameters` spells out.
+        let params: Vec<u8> = if let Some(named) = request.params.as_ref() {
+            let record = backend
+                .encode_operation_params(&operation_id, named)
+                .await?;
+            [&[0x01], record.as_slice()].concat()
+        } else if let Some(hex) = request.raw_option_record.as_ref() {
+            let record = hex::decode(hex).map_err(|e| {
+                ApiError::BadRequest(format!("Invalid hex raw_option_record: {}", e))
+            })?;
+            [&[0x01], record.as_slice()].concat()
+        } else {
+            match request.parameters.as_ref() {
+                Some(serde_json::Value::String(hex)) => hex::decode(hex)
+                    .map_err(|e| ApiError::BadRequest(format!("Invalid hex parameters: {}", e)))?,
+                Some(serde_json::Value::Null) | None => Vec::new(),
+                Some(other) => {
+                    return Err(ApiError::BadRequest(format!(
+                        "Operation '{}' is a RoutineControl op; parameters must be a hex string, got {}",
+                        operation_id, other
+                    )));
+                }
             }
         };
+        // Session and security gates from the operation's config, refused
+        // here rather than as a failed execution.
+        backend.check_operation_preconditions(&operation_id).await?;
         Dispatch::Routine { params }
     };
 
     // Allocate exec_id + seed the cache with a Running placeholder so
     // GET .../executions/{exec_id} returns running/completed/failed/
     // stopped — never 404 for the exec_id we just handed out.
-    let exec_id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now();
     let initial = OperationExecution {
         execution_id: exec_id.clone(),
@@ -387,6 +508,23 @@ pub async fn start_operation_execution(
                     },
                 }
             }
+            Dispatch::Profile { profile, cancel } => {
+                let run = ProfileRun {
+                    state: &task_state,
+                    backend: &backend,
+                    component_id: &task_component_id,
+                    operation_id: &task_operation_id,
+                    exec_id: &task_exec_id,
+                    started_at: now,
+                };
+                let result = run.drive(&profile, cancel).await;
+                task_state.output_profiles.finish(
+                    &task_component_id,
+                    &task_operation_id,
+                    &task_exec_id,
+                );
+                result
+            }
         };
         task_state
             .operation_executions
@@ -422,6 +560,148 @@ pub async fn start_operation_execution(
     ))
 }
 
+/// One IO control profile execution in progress
+struct ProfileRun<'a> {
+    state: &'a AppState,
+    backend: &'a Arc<dyn DiagnosticBackend>,
+    component_id: &'a str,
+    operation_id: &'a str,
+    exec_id: &'a str,
+    started_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// How a profile run ended
+enum ProfileEnd {
+    Completed,
+    Cancelled,
+    Failed(String),
+}
+
+impl ProfileRun<'_> {
+    /// Send the profile's short-term adjustments on schedule, recording
+    /// progress after each one, until it runs out or `cancel` turns
+    /// `true`.  Returns the terminal execution.
+    async fn drive(
+        &self,
+        profile: &OutputProfile,
+        mut cancel: watch::Receiver<bool>,
+    ) -> OperationExecution {
+        let total = profile.total();
+        let start = tokio::time::Instant::now();
+        let mut sent = 0u64;
+        let mut last = None;
+        let mut pass = 0u32;
+
+        let end = 'run: loop {
+            if profile.repeat.is_some_and(|passes| pass >= passes) {
+                break ProfileEnd::Completed;
+            }
+            for (offset, value) in &profile.setpoints {
+                // Only a profile repeating until cancelled can run this far
+                let Some(due) = profile
+                    .period
+                    .checked_mul(pass)
+                    .and_then(|pass_start| pass_start.checked_add(*offset))
+                    .and_then(|at| start.checked_add(at))
+                else {
+                    break 'run ProfileEnd::Failed(
+                        "profile ran past what can be scheduled".to_string(),
+                    );
+                };
+                let cancelled = tokio::select! {
+                    _ = tokio::time::sleep_until(due) => false,
+                    Ok(_) = cancel.wait_for(|cancelled| *cancelled) => true,
+                };
+                if cancelled {
+                    break 'run ProfileEnd::Cancelled;
+                }
+                if let Err(e) = self
+                    .backend
+                    .control_output(
+                        self.operation_id,
+                        IoControlAction::ShortTermAdjust,
+                        Some(value.clone()),
+                    )
+                    .await
+                {
+                    break 'run ProfileEnd::Failed(format!("{e:?}"));
+                }
+                sent += 1;
+                last = Some(value.clone());
+                self.record(
+                    OperationStatus::Running,
+                    profile_progress(sent, total, last.as_ref()),
+                    None,
+                );
+            }
+            pass += 1;
+        };
+
+        let progress = profile_progress(sent, total, last.as_ref());
+        let (status, error) = match end {
+            // The output keeps the last value under tester control
+            ProfileEnd::Completed => (OperationStatus::Completed, None),
+            ProfileEnd::Cancelled => (OperationStatus::Stopped, self.return_to_ecu().await),
+            ProfileEnd::Failed(e) => {
+                if let Some(release) = self.return_to_ecu().await {
+                    tracing::warn!(
+                        component = %self.component_id,
+                        operation = %self.operation_id,
+                        error = %release,
+                        "IO control profile: return to ECU failed"
+                    );
+                }
+                (OperationStatus::Failed, Some(e))
+            }
+        };
+        self.execution(status, progress, error, Some(chrono::Utc::now()))
+    }
+
+    /// Hand the output back to the ECU; the error, if it refused
+    async fn return_to_ecu(&self) -> Option<String> {
+        self.backend
+            .control_output(self.operation_id, IoControlAction::ReturnToEcu, None)
+            .await
+            .err()
+            .map(|e| format!("{e:?}"))
+    }
+
+    fn record(&self, status: OperationStatus, result: serde_json::Value, error: Option<String>) {
+        let execution = self.execution(status, result, error, None);
+        self.state
+            .operation_executions
+            .record(self.component_id, self.operation_id, execution);
+    }
+
+    fn execution(
+        &self,
+        status: OperationStatus,
+        result: serde_json::Value,
+        error: Option<String>,
+        completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> OperationExecution {
+        OperationExecution {
+            execution_id: self.exec_id.to_string(),
+            operation_id: self.operation_id.to_string(),
+            status,
+            result: Some(result),
+            started_at: self.started_at,
+            completed_at,
+            error,
+        }
+    }
+}
+
+/// Progress of a profile: adjustments sent, out of `total` (`null` when
+/// it repeats until cancelled), and the value last sent
+fn profile_progress(
+    sent: u64,
+    total: Option<u64>,
+    value: Option<&serde_json::Value>,
+) -> serde_json::Value {
+    serde_json::json!({ "sent": sent, "total": total, "value": value })
+}
+
 /// GET /vehicle/v1/components/:component_id/operations/:operation_id/executions/:exec_id
 ///
 /// Polls the backend's current operation state.  `exec_id` is accepted
@@ -489,12 +769,19 @@ fn parse_io_control_params(
 
 /// DELETE /vehicle/v1/components/:component_id/operations/:operation_id/executions/:exec_id
 ///
-/// Stops the operation (UDS RoutineControl 0x31 0x02).
+/// Stops the operation (UDS RoutineControl 0x31 0x02).  A running IO
+/// control profile is cancelled instead, which returns control to the ECU.
 pub async fn stop_operation_execution(
     State(state): State<AppState>,
-    Path((component_id, operation_id, _exec_id)): Path<(String, String, String)>,
+    Path((component_id, operation_id, exec_id)): Path<(String, String, String)>,
 ) -> Result<StatusCode, ApiError> {
     let backend = state.get_backend(&component_id)?;
+    if state
+        .output_profiles
+        .cancel(&component_id, &operation_id, &exec_id)
+    {
+        return Ok(StatusCode::NO_CONTENT);
+    }
     backend.stop_operation(&operation_id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
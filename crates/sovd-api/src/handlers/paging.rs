@@ -0,0 +1,139 @@
+//! Paging and search on collection listings
+//!
+//! `GET .../data` and `GET .../faults` answer the whole collection in one
+//! response. With `?limit=`, `?offset=` or `?q=` the handler instead
+//! answers one page: `q` keeps items whose id or name contains it
+//! (case-insensitive), `offset` skips into the matches and `limit` caps
+//! the page. `total_count` is the number of matches across all pages and
+//! `next` the href of the following page, absent on the last one. `next`
+//! repeats the request's other query parameters, so following it keeps
+//! any category or severity filter.
+
+use serde::Deserialize;
+
+/// `?limit=&offset=&q=`
+#[derive(Debug, Default, Deserialize)]
+pub struct PageQuery {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub q: Option<String>,
+}
+
+/// One page of a listing
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Matches across all pages
+    pub total_count: usize,
+    /// Href of the following page; `None` on the last one
+    pub next: Option<String>,
+}
+
+impl PageQuery {
+    /// Whether the client asked for a page rather than the whole list
+    pub fn is_requested(&self) -> bool {
+        self.limit.is_some() || self.offset.is_some() || self.q.is_some()
+    }
+
+    /// Whether an item with these id / name fields matches `q`
+    pub fn matches<'a>(&self, fields: impl IntoIterator<Item = Option<&'a str>>) -> bool {
+        let Some(q) = self.q.as_deref().filter(|q| !q.is_empty()) else {
+            return true;
+        };
+        let q = q.to_lowercase();
+        fields
+            .into_iter()
+            .flatten()
+            .any(|field| field.to_lowercase().contains(&q))
+    }
+
+    /// Cut the page out of `items` (already filtered by [`Self::matches`]
+    /// and in their final order). `path` and `raw_query` are the request's,
+    /// to build `next` from.
+    pub fn paginate<T>(&self, items: Vec<T>, path: &str, raw_query: Option<&str>) -> Page<T> {
+        let total_count = items.len();
+        let offset = self.offset.unwrap_or(0).min(total_count);
+        let limit = self.limit.unwrap_or(total_count);
+        let items: Vec<T> = items.into_iter().skip(offset).take(limit).collect();
+        // A zero limit never advances, so it gets no `next`
+        let end = offset + items.len();
+        let next = (limit > 0 && end < total_count).then(|| next_href(path, raw_query, end));
+        Page {
+            items,
+            total_count,
+            next,
+        }
+    }
+}
+
+/// `path` with the request's query, `offset` replaced by `offset`
+fn next_href(path: &str, raw_query: Option<&str>, offset: usize) -> String {
+    let mut pairs: Vec<&str> = raw_query
+        .unwrap_or("")
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| pair.split_once('=').map_or(*pair, |(key, _)| key) != "offset")
+        .collect();
+    let offset = format!("offset={offset}");
+    pairs.push(&offset);
+    format!("{path}?{}", pairs.join("&"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(limit: Option<usize>, offset: Option<usize>, q: Option<&str>) -> PageQuery {
+        PageQuery {
+            limit,
+            offset,
+            q: q.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn pages_link_to_the_next_until_the_last() {
+        let page = query(Some(2), None, None).paginate(
+            vec![1, 2, 3, 4, 5],
+            "/vehicle/v1/components/ecu/data",
+            Some("categories=currentData&limit=2"),
+        );
+        assert_eq!(page.items, vec![1, 2]);
+        assert_eq!(page.total_count, 5);
+        assert_eq!(
+            page.next.as_deref(),
+            Some("/vehicle/v1/components/ecu/data?categories=currentData&limit=2&offset=2")
+        );
+
+        let page = query(Some(2), Some(4), None).paginate(
+            vec![1, 2, 3, 4, 5],
+            "/data",
+            Some("limit=2&offset=4"),
+        );
+        assert_eq!(page.items, vec![5]);
+        assert_eq!(page.next, None);
+    }
+
+    #[test]
+    fn offset_past_the_end_is_an_empty_page() {
+        let page = query(None, Some(10), None).paginate(vec![1, 2], "/data", Some("offset=10"));
+        assert!(page.items.is_empty());
+        assert_eq!(page.total_count, 2);
+        assert_eq!(page.next, None);
+    }
+
+    #[test]
+    fn zero_limit_has_no_next() {
+        let page = query(Some(0), None, None).paginate(vec![1, 2], "/data", Some("limit=0"));
+        assert!(page.items.is_empty());
+        assert_eq!(page.next, None);
+    }
+
+    #[test]
+    fn q_matches_id_or_name_ignoring_case() {
+        let q = query(None, None, Some("Temp"));
+        assert!(q.matches([Some("coolant_temp"), None]));
+        assert!(q.matches([Some("F405"), Some("Coolant Temperature")]));
+        assert!(!q.matches([Some("vin"), Some("Vehicle Identification")]));
+        assert!(query(None, None, None).matches([Some("vin")]));
+    }
+}
@@ -92,6 +92,7 @@ pub async fn status_restart(
     let backend = state.get_backend(&component_id)?;
     let (reset_type_byte, reset_type_name) = parse_reset_type(&request.reset_type)?;
     let power_down_time = backend.ecu_reset(reset_type_byte).await?;
+    state.read_cache.invalidate_component(&component_id);
 
     let exec_id = Uuid::new_v4().to_string();
     let href = format!(
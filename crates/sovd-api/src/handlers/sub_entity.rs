@@ -14,7 +14,7 @@
 
 use std::sync::Arc;
 
-use axum::extract::{Path, Query, State};
+use axum::extract::{Path, Query, RawQuery, State};
 use axum::http::StatusCode;
 use axum::Json;
 use chrono::Utc;
@@ -24,8 +24,11 @@ use crate::error::ApiError;
 use crate::state::AppState;
 
 // Re-use response types from sibling handler modules.
-use super::data::{DidInfoResponse, DidListResponse, DidResponse, ReadQuery};
-use super::faults::{FaultFilterQuery, FaultInfoResponse, FaultsResponse};
+use super::data::{DataReadListResponse, DidInfoResponse, DidListResponse, DidResponse, ReadQuery};
+use super::faults::{ClearFaultsQuery, FaultFilterQuery, FaultInfoResponse, FaultsResponse};
+use super::fingerprints::FingerprintsResponse;
+use super::link::LinkRequest;
+use super::memory::{MemoryQuery, MemoryResponse, MemoryUploadQuery};
 // F.D8b: handlers::files + handlers::flash deleted along with the
 // /flash and /files wires; the legacy sub-entity handlers below
 // referenced their response types and are themselves retired now.
@@ -34,7 +37,7 @@ use super::modes::{
 };
 use super::operations::{OperationInfoResponse, OperationsResponse};
 use axum::response::IntoResponse;
-use sovd_core::{FaultFilter, FaultSeverity, OperationStatus, SecurityState};
+use sovd_core::{FaultFilter, OperationStatus, SecurityState};
 
 /// Resolve a sub-entity backend from a `(component_id, app_id)` path.
 ///
@@ -109,6 +112,9 @@ pub async fn status_restart(
     };
 
     let power_down_time = backend.ecu_reset(reset_type_byte).await?;
+    state
+        .read_cache
+        .invalidate_component(&backend.entity_info().id);
 
     let exec_id = Uuid::new_v4().to_string();
     let href = format!(
@@ -167,6 +173,7 @@ pub async fn get_session_mode(
     Ok(Json(SessionModeResponse {
         id: "session".to_string(),
         value: mode.session,
+        timing: mode.timing,
     }))
 }
 
@@ -178,9 +185,13 @@ pub async fn put_session_mode(
 ) -> Result<Json<SessionModeResponse>, ApiError> {
     let backend = resolve(&state, &component_id, &app_id).await?;
     let mode = backend.set_session_mode(&request.value).await?;
+    state
+        .read_cache
+        .invalidate_component(&backend.entity_info().id);
     Ok(Json(SessionModeResponse {
         id: "session".to_string(),
         value: mode.session,
+        timing: mode.timing,
     }))
 }
 
@@ -249,12 +260,23 @@ pub async fn put_security_mode(
 // =========================================================================
 
 /// GET .../apps/:app_id/data
+///
+/// `?ids=a,b,c` reads those parameters instead of listing them.
 pub async fn list_sub_entity_parameters(
     State(state): State<AppState>,
     Path((component_id, app_id)): Path<(String, String)>,
-) -> Result<Json<DidListResponse>, ApiError> {
+    RawQuery(raw_query): RawQuery,
+) -> Result<axum::response::Response, ApiError> {
     let backend = resolve(&state, &component_id, &app_id).await?;
     let sub_entity_id = backend.entity_info().id.clone();
+    if let Some(ids) = super::data::parse_ids(&raw_query) {
+        // Single reads through a gateway are never served from the read
+        // cache; neither are these
+        let items =
+            super::data::read_data_batch(&state, backend.as_ref(), &sub_entity_id, &ids, false)
+                .await;
+        return Ok(Json(DataReadListResponse { items }).into_response());
+    }
     let base = format!(
         "/vehicle/v1/components/{}/apps/{}/data",
         component_id, app_id
@@ -289,7 +311,13 @@ pub async fn list_sub_entity_parameters(
             .collect();
         items.sort_by(|a, b| a.id.cmp(&b.id));
         let count = items.len();
-        return Ok(Json(DidListResponse { count, items }));
+        return Ok(Json(DidListResponse {
+            count,
+            items,
+            total_count: None,
+            next: None,
+        })
+        .into_response());
     }
 
     // Fall back to backend.list_parameters() (proxy backends that get params from upstream)
@@ -320,7 +348,13 @@ pub async fn list_sub_entity_parameters(
         })
         .collect();
     let count = items.len();
-    Ok(Json(DidListResponse { count, items }))
+    Ok(Json(DidListResponse {
+        count,
+        items,
+        total_count: None,
+        next: None,
+    })
+    .into_response())
 }
 
 /// GET .../apps/:app_id/data/:param_id
@@ -337,14 +371,19 @@ pub async fn read_sub_entity_parameter(
     // Proxy backends don't have local DIDs and should fall through to read_data().
     let has_local_dids = did_store.has_component_specific_dids(&sub_entity_id);
     if has_local_dids {
-        if let Some(did_u16) = did_store.resolve_did(&param_id) {
+        if let Some(did_u16) = state.resolve_did(&sub_entity_id, &param_id) {
             let component_def = did_store.get_for_component(did_u16, &sub_entity_id);
-            let semantic_id = component_def
-                .as_ref()
-                .and_then(|def| def.id.clone())
-                .unwrap_or_else(|| param_id.clone());
+            let semantic_id = super::data::semantic_id_for(
+                &state,
+                &sub_entity_id,
+                did_u16,
+                component_def.as_ref(),
+            )
+            .unwrap_or_else(|| param_id.clone());
 
-            let raw_bytes = backend.read_raw_did(did_u16).await?;
+            let raw_bytes =
+                super::data::read_did_bytes(backend.as_ref(), did_u16, component_def.as_ref())
+                    .await?;
 
             if query.raw {
                 return Ok(Json(DidResponse {
@@ -424,21 +463,20 @@ pub async fn write_sub_entity_parameter(
 
     let has_local_dids = did_store.has_component_specific_dids(&sub_entity_id);
     if has_local_dids {
-        if let Some(did_u16) = did_store.resolve_did(&param_id) {
+        if let Some(did_u16) = state.resolve_did(&sub_entity_id, &param_id) {
             let component_def = did_store.get_for_component(did_u16, &sub_entity_id);
             // Raw-vs-converted inference (C-131): encode physical values only
             // when the DID carries a real conversion; otherwise treat `value`
             // as a raw byte representation.
             let has_conversion = component_def.as_ref().is_some_and(|d| d.has_conversion());
             let data = if has_conversion {
-                match did_store.encode(did_u16, &request.value) {
-                    Ok(bytes) => bytes,
-                    Err(_) => super::data::convert_value_to_bytes(&request.value)?,
-                }
+                super::data::encode_physical_value(did_store, did_u16, &request.value)?
             } else {
                 super::data::convert_value_to_bytes(&request.value)?
             };
             backend.write_raw_did(did_u16, &data).await?;
+            // The ECU may be cached as a top-level component of its own
+            state.read_cache.invalidate(&sub_entity_id, did_u16);
             return Ok(StatusCode::NO_CONTENT);
         }
     }
@@ -446,6 +484,8 @@ pub async fn write_sub_entity_parameter(
     // Fall back to backend.write_data() for proxy backends
     let data = super::data::convert_value_to_bytes(&request.value)?;
     backend.write_data(&param_id, &data).await?;
+    // Which DID the backend wrote is unknown here
+    state.read_cache.invalidate_component(&sub_entity_id);
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -462,11 +502,8 @@ pub async fn list_sub_entity_faults(
     let backend = resolve(&state, &component_id, &app_id).await?;
 
     let filter = FaultFilter {
-        severity: query.severity.map(FaultSeverity::from),
-        category: query.category.clone(),
-        active_only: query.active_only,
-        since: None,
         limit: query.limit,
+        ..query.fault_filter()?.unwrap_or_default()
     };
 
     let result = backend
@@ -496,7 +533,14 @@ pub async fn list_sub_entity_faults(
         .collect();
 
     let total_count = items.len();
-    Ok(Json(FaultsResponse { items, total_count }))
+    Ok(Json(FaultsResponse {
+        items,
+        total_count,
+        next: None,
+        status_availability_mask: super::faults::format_availability_mask(
+            result.status_availability_mask,
+        ),
+    }))
 }
 
 /// GET .../apps/:app_id/faults/:fault_id
@@ -528,14 +572,16 @@ pub async fn get_sub_entity_fault(
     }))
 }
 
-/// DELETE .../apps/:app_id/faults — 204 No Content per spec.
+/// DELETE .../apps/:app_id/faults — 204 No Content per spec (200 with the
+/// re-read when the ECU verifies clears, or after `?auto_session=true`
+/// switched sessions).
 pub async fn clear_sub_entity_faults(
     State(state): State<AppState>,
     Path((component_id, app_id)): Path<(String, String)>,
-) -> Result<StatusCode, ApiError> {
+    Query(query): Query<ClearFaultsQuery>,
+) -> Result<axum::response::Response, ApiError> {
     let backend = resolve(&state, &component_id, &app_id).await?;
-    let _ = backend.clear_faults(None).await.map_err(ApiError::from)?;
-    Ok(StatusCode::NO_CONTENT)
+    super::faults::clear_all_faults(backend.as_ref(), query.auto_session).await
 }
 
 // =========================================================================
@@ -660,3 +706,79 @@ pub async fn stop_sub_entity_operation_execution(
         .map_err(ApiError::from)?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+// =========================================================================
+// Memory (x-sumo-memory)
+// =========================================================================
+
+/// GET .../apps/:app_id/x-sumo-memory
+pub async fn read_sub_entity_memory(
+    State(state): State<AppState>,
+    Path((component_id, app_id)): Path<(String, String)>,
+    Query(query): Query<MemoryQuery>,
+) -> Result<Json<MemoryResponse>, ApiError> {
+    let backend = resolve(&state, &component_id, &app_id).await?;
+    Ok(Json(
+        super::memory::read_memory_from(backend.as_ref(), &query).await?,
+    ))
+}
+
+/// GET .../apps/:app_id/x-sumo-memory/upload
+pub async fn upload_sub_entity_memory(
+    State(state): State<AppState>,
+    Path((component_id, app_id)): Path<(String, String)>,
+    Query(query): Query<MemoryUploadQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let backend = resolve(&state, &component_id, &app_id).await?;
+    super::memory::upload_memory_from(backend.as_ref(), &query).await
+}
+
+// =========================================================================
+// Fingerprints (x-sumo-fingerprints)
+// =========================================================================
+
+/// GET .../apps/:app_id/x-sumo-fingerprints
+pub async fn get_sub_entity_fingerprints(
+    State(state): State<AppState>,
+    Path((component_id, app_id)): Path<(String, String)>,
+) -> Result<Json<FingerprintsResponse>, ApiError> {
+    let backend = resolve(&state, &component_id, &app_id).await?;
+    let items = backend.read_fingerprints().await?;
+    Ok(Json(FingerprintsResponse { items }))
+}
+
+// =========================================================================
+// Transport stats (x-sumo-transport-stats)
+// =========================================================================
+
+/// GET .../apps/:app_id/x-sumo-transport-stats
+pub async fn get_sub_entity_transport_stats(
+    State(state): State<AppState>,
+    Path((component_id, app_id)): Path<(String, String)>,
+) -> Result<Json<sovd_core::TransportStats>, ApiError> {
+    let backend = resolve(&state, &component_id, &app_id).await?;
+    Ok(Json(backend.transport_stats().await?))
+}
+
+// =========================================================================
+// Link baud rate (x-sumo-link)
+// =========================================================================
+
+/// GET .../apps/:app_id/x-sumo-link
+pub async fn get_sub_entity_link(
+    State(state): State<AppState>,
+    Path((component_id, app_id)): Path<(String, String)>,
+) -> Result<Json<sovd_core::LinkMode>, ApiError> {
+    let backend = resolve(&state, &component_id, &app_id).await?;
+    Ok(Json(backend.get_link_mode().await?))
+}
+
+/// PUT .../apps/:app_id/x-sumo-link
+pub async fn put_sub_entity_link(
+    State(state): State<AppState>,
+    Path((component_id, app_id)): Path<(String, String)>,
+    Json(request): Json<LinkRequest>,
+) -> Result<Json<sovd_core::LinkControlResult>, ApiError> {
+    let backend = resolve(&state, &component_id, &app_id).await?;
+    super::link::set_link(backend.as_ref(), request).await
+}
@@ -27,23 +27,52 @@
 //! `cyclic-subscriptions` is one such name, the retired `streams` was
 //! not. Single resource per subscription per spec; for multi-parameter
 //! consumers, open N subscriptions and join the streams client-side.
+//!
+//! Vendor extension `x-sumo-mode` on the create body: `{"type": "on_change",
+//! "deadband": 0.5}` still samples at the interval's rate but only emits an
+//! event when the decoded value moved since the last one emitted (numbers by
+//! more than `deadband`, and by more than `deadband_pct` percent of the last
+//! value), which cuts traffic for slow-moving signals.
+//!
+//! With authentication enabled a subscription is bound to the principal that
+//! created it: attaching to its SSE stream with a token for a different
+//! subject is refused with 403, on top of the unguessable subscription id.
+//!
+//! With `[subscriptions.normalize_units]` naming a target unit for the
+//! parameter, each sample's value is converted from its definition's unit
+//! and the payload carries the unit applied as `x-sumo-units`. A value that
+//! can't be converted (unknown unit, different quantity, not a number)
+//! streams unchanged in its own unit, flagged `x-sumo-unit-passthrough`.
+//!
+//! Vendor extension: the same resource also accepts a WebSocket upgrade.
+//! The socket carries the same EventEnvelope JSON as the SSE `data:` lines,
+//! one text frame per event, and takes control messages on the live
+//! subscription: `{"action": "pause"}` (drops the backend subscription until
+//! resumed), `{"action": "resume"}` and `{"action": "set_interval",
+//! "interval": "fast"}` (same effect as the `PUT`). A rejected control
+//! message is answered with an error envelope; the stream stays open.
 
+use axum::extract::rejection::WebSocketUpgradeRejection;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::extract::{Path, State};
 use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
 use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::IntoResponse;
+use axum::Extension;
 use axum::Json;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::convert::Infallible;
+use std::pin::Pin;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio_stream::wrappers::BroadcastStream;
-use tokio_stream::StreamExt;
+use tokio_stream::{Stream, StreamExt};
 use uuid::Uuid;
 
+use crate::auth::ClientContext;
 use crate::error::ApiError;
 use crate::state::AppState;
 
@@ -60,11 +89,28 @@ impl SubscriptionManager {
         }
     }
 
+    /// Create unless `component_id` already holds `max_per_component`
+    /// subscriptions (`None` if it does).  Count and insert happen under one
+    /// write lock so concurrent creates cannot overshoot the cap.  `owner`
+    /// is the creating principal's subject (`None` with auth disabled).
     pub async fn create(
         &self,
         component_id: String,
         request: CyclicSubscriptionRequest,
-    ) -> CyclicSubscription {
+        max_per_component: Option<usize>,
+        owner: Option<String>,
+    ) -> Option<CyclicSubscription> {
+        let mut subscriptions = self.subscriptions.write().await;
+        if let Some(max) = max_per_component {
+            let held = subscriptions
+                .values()
+                .filter(|s| s.component_id == component_id)
+                .count();
+            if held >= max {
+                return None;
+            }
+        }
+
         let subscription_id = Uuid::new_v4().to_string();
         let now = Utc::now();
         let expires_at = request
@@ -77,17 +123,16 @@ impl SubscriptionManager {
             resource: request.resource,
             interval: request.interval,
             protocol: request.protocol.unwrap_or_else(default_protocol),
+            mode: request.mode,
             status: "active".to_string(),
             created_at: now,
             expires_at,
+            owner,
         };
 
-        self.subscriptions
-            .write()
-            .await
-            .insert(subscription_id, subscription.clone());
+        subscriptions.insert(subscription_id, subscription.clone());
 
-        subscription
+        Some(subscription)
     }
 
     pub async fn get(&self, subscription_id: &str) -> Option<CyclicSubscription> {
@@ -146,10 +191,31 @@ pub struct CyclicSubscription {
     pub resource: String,
     pub interval: SubscriptionInterval,
     pub protocol: String,
+    #[serde(
+        rename = "x-sumo-mode",
+        default,
+        skip_serializing_if = "SubscriptionMode::is_cyclic"
+    )]
+    pub mode: SubscriptionMode,
     pub status: String,
     pub created_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<DateTime<Utc>>,
+    /// Subject of the principal that created the subscription; only it may
+    /// attach to the stream.  Never serialized.
+    #[serde(skip)]
+    pub owner: Option<String>,
+}
+
+impl CyclicSubscription {
+    /// Whether `client` may attach to this subscription's stream.  An
+    /// unbound subscription (auth disabled at creation) is open to anyone.
+    pub fn accessible_by(&self, client: Option<&ClientContext>) -> bool {
+        match &self.owner {
+            None => true,
+            Some(owner) => client.is_some_and(|c| &c.subject == owner),
+        }
+    }
 }
 
 /// Spec line 358 — coarse-grained update cadence enum.
@@ -179,6 +245,56 @@ impl SubscriptionInterval {
     }
 }
 
+/// When a subscription's stream emits (vendor extension `x-sumo-mode`).
+///
+/// The backend is sampled at the interval's rate either way; `on_change`
+/// only suppresses the events that carry no news.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SubscriptionMode {
+    /// Every sample (the spec behaviour).
+    #[default]
+    Cyclic,
+    /// Only samples whose decoded value differs from the last emitted one;
+    /// a numeric value must move by more than `deadband` and by more than
+    /// `deadband_pct` percent of the last emitted value.
+    OnChange {
+        #[serde(default)]
+        deadband: f64,
+        #[serde(default)]
+        deadband_pct: f64,
+    },
+}
+
+impl SubscriptionMode {
+    fn is_cyclic(&self) -> bool {
+        matches!(self, Self::Cyclic)
+    }
+
+    /// Whether to emit `value` given the `last` value emitted (if any).
+    pub fn should_emit(&self, last: Option<&serde_json::Value>, value: &serde_json::Value) -> bool {
+        let (
+            Self::OnChange {
+                deadband,
+                deadband_pct,
+            },
+            Some(last),
+        ) = (self, last)
+        else {
+            return true;
+        };
+        match (last.as_f64(), value.as_f64()) {
+            (Some(last), Some(value)) => {
+                let moved = (value - last).abs();
+                // Any move away from zero is an unbounded relative change
+                let beyond_pct = last == 0.0 || moved > deadband_pct / 100.0 * last.abs();
+                moved > *deadband && beyond_pct
+            }
+            _ => last != value,
+        }
+    }
+}
+
 /// Request body for creating a cyclic subscription.
 #[derive(Debug, Deserialize)]
 pub struct CyclicSubscriptionRequest {
@@ -193,6 +309,9 @@ pub struct CyclicSubscriptionRequest {
     /// Optional auto-expiry in seconds.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub duration: Option<u32>,
+    /// Emit every sample (default) or only changes (`x-sumo-mode`).
+    #[serde(rename = "x-sumo-mode", default)]
+    pub mode: SubscriptionMode,
 }
 
 /// Request body for `PUT .../cyclic-subscriptions/{id}` — update cadence
@@ -210,6 +329,19 @@ fn default_protocol() -> String {
     "sse".to_string()
 }
 
+/// Reject an interval faster than `[subscriptions] max_rate_hz`.
+fn check_rate_limit(state: &AppState, interval: SubscriptionInterval) -> Result<(), ApiError> {
+    match state.subscription_limits.max_rate_hz {
+        Some(max) if interval.rate_hz() > max => Err(ApiError::BadRequest(format!(
+            "subscription interval {:?} ({} Hz) exceeds limit max_rate_hz = {}",
+            interval,
+            interval.rate_hz(),
+            max
+        ))),
+        _ => Ok(()),
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct CyclicSubscriptionsResponse {
     pub items: Vec<CyclicSubscription>,
@@ -217,16 +349,17 @@ pub struct CyclicSubscriptionsResponse {
 
 /// Verify a single data parameter is GET-able on `backend` the same way
 /// the data path resolves it (`read_did_internal` / the sub-entity reader):
-/// DidStore resolution under `entity_id`, presence in `list_parameters`, or a
-/// non-empty `read_data` probe.  Returns `true` if any of those succeed.
+/// alias/DidStore resolution under `entity_id`, presence in `list_parameters`,
+/// or a non-empty `read_data` probe.  Returns `true` if any of those succeed.
 async fn param_is_get_able(
+    state: &AppState,
+    entity_id: &str,
     backend: &Arc<dyn sovd_core::DiagnosticBackend>,
-    did_store: &sovd_conv::DidStore,
     param: &str,
 ) -> bool {
-    // A DidStore-known parameter (semantic name or raw hex DID) is
+    // An alias or DidStore-known parameter (semantic name or raw hex DID) is
     // addressable — the same first step the data reader takes.
-    if did_store.resolve_did(param).is_some() {
+    if state.resolve_did(entity_id, param).is_some() {
         return true;
     }
     // Backend-resolved parameter (proxy/app entities): listed or readable.
@@ -270,8 +403,6 @@ async fn validate_subscription_resource(
             "subscription resource must not be empty".into(),
         ));
     }
-    let did_store = state.did_store();
-
     let bad = |r: &str| {
         ApiError::BadRequest(format!(
             "subscription resource {r:?} is not a GET-able same-entity parameter"
@@ -284,7 +415,7 @@ async fn validate_subscription_resource(
     if let Some(param) = resource.strip_prefix("data/") {
         if !param.is_empty()
             && !param.contains('/')
-            && param_is_get_able(backend, did_store, param).await
+            && param_is_get_able(state, component_id, backend, param).await
         {
             return Ok(param.to_string());
         }
@@ -305,7 +436,7 @@ async fn validate_subscription_resource(
             )),
             other => ApiError::from(other),
         })?;
-        if param_is_get_able(&child_backend, did_store, param).await {
+        if param_is_get_able(state, child, &child_backend, param).await {
             return Ok(format!("{child}/{param}"));
         }
         return Err(ApiError::BadRequest(format!(
@@ -314,7 +445,7 @@ async fn validate_subscription_resource(
     }
 
     // Bare direct resource on the addressed entity (param-id or hex DID).
-    if param_is_get_able(backend, did_store, resource).await {
+    if param_is_get_able(state, component_id, backend, resource).await {
         return Ok(resource.to_string());
     }
     Err(bad(resource))
@@ -324,6 +455,7 @@ async fn validate_subscription_resource(
 pub async fn create_cyclic_subscription(
     State(state): State<AppState>,
     Path(component_id): Path<String>,
+    client: Option<Extension<ClientContext>>,
     Json(mut request): Json<CyclicSubscriptionRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     let backend = state.get_backend(&component_id)?;
@@ -336,15 +468,53 @@ pub async fn create_cyclic_subscription(
         ));
     }
 
+    check_rate_limit(&state, request.interval)?;
+
+    if let SubscriptionMode::OnChange {
+        deadband,
+        deadband_pct,
+    } = request.mode
+    {
+        for (field, bound) in [("deadband", deadband), ("deadband_pct", deadband_pct)] {
+            if !bound.is_finite() || bound < 0.0 {
+                return Err(ApiError::BadRequest(format!(
+                    "x-sumo-mode {field} must be a non-negative number, got {bound}"
+                )));
+            }
+        }
+    }
+
     // C-073: the subscribed resource must be same-entity and GET-able.
     // Persist the canonical (normalized) form the SSE delivery path resolves.
     request.resource =
         validate_subscription_resource(&state, &component_id, backend, &request.resource).await?;
+    if !state
+        .subscription_limits
+        .allows(&state, &component_id, &request.resource)
+    {
+        return Err(ApiError::Forbidden(format!(
+            "parameters not eligible for streaming on component {:?}: [{}]",
+            component_id, request.resource
+        )));
+    }
 
+    let max_per_component = state.subscription_limits.max_subscriptions_per_component;
     let subscription = state
         .subscription_manager
-        .create(component_id.clone(), request)
-        .await;
+        .create(
+            component_id.clone(),
+            request,
+            max_per_component,
+            client.map(|Extension(c)| c.subject),
+        )
+        .await
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "component {:?} is at its subscription limit max_subscriptions_per_component = {}",
+                component_id,
+                max_per_component.unwrap_or_default()
+            ))
+        })?;
 
     let resource_path = format!(
         "/vehicle/v1/components/{}/cyclic-subscriptions/{}",
@@ -392,6 +562,9 @@ pub async fn update_cyclic_subscription(
             "duration must be > 0; omit the field for no expiry".to_string(),
         ));
     }
+    if let Some(interval) = request.interval {
+        check_rate_limit(&state, interval)?;
+    }
     state
         .subscription_manager
         .update(&subscription_id, request.interval, request.duration)
@@ -407,6 +580,8 @@ pub async fn update_cyclic_subscription(
 ///
 ///   * `Accept: text/event-stream` → the SSE event stream (the events
 ///     this subscription's cadence produces).
+///   * a WebSocket upgrade → the same events over a WebSocket, plus
+///     pause/resume/rate control messages (vendor extension).
 ///   * any other `Accept` → the `CyclicSubscription` details (JSON).
 ///
 /// This is why there is no separate non-standard `streams` resource
@@ -414,10 +589,27 @@ pub async fn update_cyclic_subscription(
 pub async fn get_cyclic_subscription(
     State(state): State<AppState>,
     Path((component_id, subscription_id)): Path<(String, String)>,
+    client: Option<Extension<ClientContext>>,
+    ws: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
     headers: HeaderMap,
 ) -> Result<axum::response::Response, ApiError> {
+    if let Ok(ws) = ws {
+        let client = client.map(|Extension(c)| c);
+        let subscription =
+            attachable_subscription(&state, &component_id, &subscription_id, client.as_ref())
+                .await?;
+        // Subscribe before upgrading so a backend refusal is still an
+        // HTTP error rather than a socket that closes straight away.
+        let seq_counter = Arc::new(AtomicU64::new(1));
+        let events = subscription_events(&state, &subscription, seq_counter.clone()).await?;
+        return Ok(ws.on_upgrade(move |socket| {
+            websocket_session(socket, state, subscription, events, seq_counter)
+        }));
+    }
+
     if wants_event_stream(&headers) {
-        return stream_subscription(&state, &component_id, &subscription_id)
+        let client = client.map(|Extension(c)| c);
+        return stream_subscription(&state, &component_id, &subscription_id, client.as_ref())
             .await
             .map(IntoResponse::into_response);
     }
@@ -466,8 +658,16 @@ struct StreamEvent {
     /// Conditional error payload (mutually exclusive with `payload`).
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<sovd_core::GenericError>,
+    /// Vendor marker for events carrying neither payload nor error:
+    /// `resumed` after the backend re-armed the subscription (e.g. after an
+    /// ECU reset); values sampled in the gap are lost.
+    #[serde(rename = "x-sumo-event", skip_serializing_if = "Option::is_none")]
+    event: Option<&'static str>,
 }
 
+/// Serialized EventEnvelopes of one attachment to a subscription
+type EventStream = Pin<Box<dyn Stream<Item = String> + Send>>;
+
 /// SSE delivery for a cyclic subscription (§7.10.3). Invoked by
 /// [`get_cyclic_subscription`] when the client sends
 /// `Accept: text/event-stream`; the subscription resource IS the stream.
@@ -475,8 +675,28 @@ async fn stream_subscription(
     state: &AppState,
     component_id: &str,
     subscription_id: &str,
+    client: Option<&ClientContext>,
 ) -> Result<impl IntoResponse, ApiError> {
-    // Look up the cyclic subscription.
+    let subscription =
+        attachable_subscription(state, component_id, subscription_id, client).await?;
+    let events = subscription_events(state, &subscription, Arc::new(AtomicU64::new(1))).await?;
+    let stream = events.map(|json| Ok::<_, Infallible>(Event::default().data(json)));
+
+    // C-070 (§5.2.2): axum's `Sse` responder emits `Content-Type:
+    // text/event-stream`; `KeepAlive` adds the comment-line heartbeat the
+    // spec's `Connection: keep-alive` requirement maps to. Both asserted in
+    // `spec_update_flow::sse_subscription_stream_carries_event_stream_ct`.
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// Look up the subscription a client attaches to, checking it lives on
+/// `component_id` and belongs to `client`.
+async fn attachable_subscription(
+    state: &AppState,
+    component_id: &str,
+    subscription_id: &str,
+    client: Option<&ClientContext>,
+) -> Result<CyclicSubscription, ApiError> {
     let subscription = state
         .subscription_manager
         .get(subscription_id)
@@ -492,6 +712,24 @@ async fn stream_subscription(
         )));
     }
 
+    if !subscription.accessible_by(client) {
+        return Err(ApiError::Forbidden(format!(
+            "Subscription {} belongs to a different principal",
+            subscription_id
+        )));
+    }
+
+    Ok(subscription)
+}
+
+/// Subscribe to the backend at the subscription's cadence and turn its data
+/// points into serialized EventEnvelopes. Sequence numbers come from
+/// `seq_counter`, so a re-subscription (rate change) continues the count.
+async fn subscription_events(
+    state: &AppState,
+    subscription: &CyclicSubscription,
+    seq_counter: Arc<AtomicU64>,
+) -> Result<EventStream, ApiError> {
     let backend = state.get_backend(&subscription.component_id)?;
 
     // Spec subscriptions carry a single `resource` (path or param-id).
@@ -507,7 +745,7 @@ async fn stream_subscription(
     // child-local id (the gateway does not re-prefix them), so did_to_info
     // is keyed on that local id, not the prefixed resource.
     let did_str = if let Some((child, param)) = resource_param.split_once('/') {
-        if let Some(did) = did_store.resolve_did(param) {
+        if let Some(did) = state.resolve_did(child, param) {
             let did_hex = format!("{:04X}", did);
             did_to_info.insert(did_hex.clone(), (param.to_string(), did));
             format!("{child}/{did_hex}")
@@ -515,7 +753,7 @@ async fn stream_subscription(
             did_to_info.insert(param.to_string(), (param.to_string(), 0));
             format!("{child}/{param}")
         }
-    } else if let Some(did) = did_store.resolve_did(&resource_param) {
+    } else if let Some(did) = state.resolve_did(&subscription.component_id, &resource_param) {
         let did_hex = format!("{:04X}", did);
         did_to_info.insert(did_hex.clone(), (resource_param.clone(), did));
         did_hex
@@ -533,20 +771,32 @@ async fn stream_subscription(
             ApiError::from(e)
         })?;
 
-    // Sequence counter for events.
-    let seq_counter = Arc::new(AtomicU64::new(1));
+    let normalization = unit_normalization(state, &did_to_info);
+
+    // Last value sent, for on-change suppression.
+    let mode = subscription.mode;
+    let mut last_emitted: Option<serde_json::Value> = None;
 
-    // Convert the broadcast receiver to an SSE stream of EventEnvelopes.
+    // Convert the broadcast receiver to a stream of EventEnvelopes.
     let stream = BroadcastStream::new(receiver).filter_map(move |result| {
         let did_to_info = did_to_info.clone();
         let seq_counter = seq_counter.clone();
         let did_store = did_store.clone();
 
         match result {
+            Ok(data_point) if data_point.is_resumed() => {
+                // Delivery resumed after a gap: tell the client, and emit the
+                // next value even if unchanged.
+                last_emitted = None;
+                let event = StreamEvent {
+                    timestamp: Utc::now().to_rfc3339(),
+                    payload: None,
+                    error: None,
+                    event: Some("resumed"),
+                };
+                Some(serde_json::to_string(&event).unwrap_or_default())
+            }
             Ok(data_point) => {
-                let seq = seq_counter.fetch_add(1, Ordering::SeqCst);
-                let timestamp = Utc::now().to_rfc3339();
-
                 // Look up parameter name and DID from the data point ID.
                 let (param_name, did) = did_to_info
                     .get(&data_point.id)
@@ -567,21 +817,42 @@ async fn stream_subscription(
                 } else {
                     data_point.value
                 };
+                let (converted_value, units) = match &normalization {
+                    Some(normalization) => {
+                        let (value, unit, converted) = normalization.apply(converted_value);
+                        (value, Some((unit, converted)))
+                    }
+                    None => (converted_value, None),
+                };
+
+                if !mode.should_emit(last_emitted.as_ref(), &converted_value) {
+                    return None;
+                }
+                last_emitted = Some(converted_value.clone());
+
+                // Numbered after suppression so emitted events stay contiguous.
+                let seq = seq_counter.fetch_add(1, Ordering::SeqCst);
+                let timestamp = Utc::now().to_rfc3339();
 
                 // EventEnvelope.payload: {seq, values{<param>: <val>}}.
-                let payload = serde_json::json!({
+                let mut payload = serde_json::json!({
                     "seq": seq,
-                    "values": { param_name: converted_value },
+                    "values": { param_name.clone(): converted_value },
                 });
+                if let Some((unit, converted)) = units {
+                    payload["x-sumo-units"] = serde_json::json!({ param_name: unit });
+                    if !converted {
+                        payload["x-sumo-unit-passthrough"] = serde_json::json!(true);
+                    }
+                }
                 let event = StreamEvent {
                     timestamp,
                     payload: Some(payload),
                     error: None,
+                    event: None,
                 };
 
-                Some(Ok::<_, Infallible>(
-                    Event::default().data(serde_json::to_string(&event).unwrap_or_default()),
-                ))
+                Some(serde_json::to_string(&event).unwrap_or_default())
             }
             Err(lag) => {
                 // Broadcast lag — consumer can't keep up. Spec
@@ -596,19 +867,173 @@ async fn stream_subscription(
                     timestamp,
                     payload: None,
                     error: Some(err),
+                    event: None,
                 };
-                Some(Ok::<_, Infallible>(
-                    Event::default().data(serde_json::to_string(&event).unwrap_or_default()),
-                ))
+                Some(serde_json::to_string(&event).unwrap_or_default())
             }
         }
     });
 
-    // C-070 (§5.2.2): axum's `Sse` responder emits `Content-Type:
-    // text/event-stream`; `KeepAlive` adds the comment-line heartbeat the
-    // spec's `Connection: keep-alive` requirement maps to. Both asserted in
-    // `spec_update_flow::sse_subscription_stream_carries_event_stream_ct`.
-    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+    Ok(Box::pin(stream))
+}
+
+/// Target-unit conversion of one subscription's values
+/// (`[subscriptions.normalize_units]`).
+struct UnitNormalization {
+    /// Unit of the decoded values, from the parameter's definition
+    source: Option<String>,
+    target: String,
+}
+
+impl UnitNormalization {
+    /// The value in the target unit, or unchanged in its source unit when
+    /// it doesn't convert; with the unit applied and whether it converted.
+    fn apply(&self, value: serde_json::Value) -> (serde_json::Value, Option<String>, bool) {
+        let converted = match self.source.as_deref() {
+            Some(source) if source == self.target => Some(value.clone()),
+            Some(source) => sovd_conv::units::convert_json(&value, source, &self.target),
+            None => None,
+        };
+        match converted {
+            Some(converted) => (converted, Some(self.target.clone()), true),
+            None => (value, self.source.clone(), false),
+        }
+    }
+}
+
+/// Normalization configured for the subscribed parameter, matched by the
+/// subscribed id or the definition's semantic id (so a hex DID subscription
+/// is normalized like its named parameter).
+fn unit_normalization(
+    state: &AppState,
+    did_to_info: &HashMap<String, (String, u16)>,
+) -> Option<UnitNormalization> {
+    let targets = &state.subscription_limits.normalize_units;
+    if targets.is_empty() {
+        return None;
+    }
+    let (param, did) = did_to_info.values().next()?;
+    let definition = (*did != 0)
+        .then(|| state.did_store_arc().get(*did))
+        .flatten();
+    let target = targets.get(param).or_else(|| {
+        definition
+            .as_ref()
+            .and_then(|d| d.id.as_ref())
+            .and_then(|id| targets.get(id))
+    })?;
+    Some(UnitNormalization {
+        source: definition.and_then(|d| d.unit),
+        target: target.clone(),
+    })
+}
+
+/// Control message a WebSocket client sends on its live subscription.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum StreamControl {
+    /// Stop sampling until resumed.
+    Pause,
+    /// Sample again after a pause.
+    Resume,
+    /// Change the cadence in place, like the `PUT`.
+    SetInterval { interval: SubscriptionInterval },
+}
+
+/// WebSocket delivery for a cyclic subscription: forwards `events` as text
+/// frames and applies the client's control messages until either side
+/// closes.
+async fn websocket_session(
+    mut socket: WebSocket,
+    state: AppState,
+    mut subscription: CyclicSubscription,
+    events: EventStream,
+    seq_counter: Arc<AtomicU64>,
+) {
+    // `None` while paused: dropping the stream ends the backend subscription.
+    let mut events = Some(events);
+    loop {
+        tokio::select! {
+            event = next_event(&mut events) => {
+                let Some(json) = event else { break };
+                if socket.send(Message::Text(json.into())).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    // Pings are answered by axum; other frames carry no controls.
+                    Some(Ok(_)) => continue,
+                };
+                let applied = match serde_json::from_str::<StreamControl>(text.as_str()) {
+                    Ok(control) => {
+                        apply_control(&state, &mut subscription, &mut events, &seq_counter, control)
+                            .await
+                    }
+                    Err(e) => Err(ApiError::BadRequest(format!("invalid control message: {e}"))),
+                };
+                if let Err(e) = applied {
+                    let event = StreamEvent {
+                        timestamp: Utc::now().to_rfc3339(),
+                        payload: None,
+                        error: Some(e.into_parts().1),
+                        event: None,
+                    };
+                    let json = serde_json::to_string(&event).unwrap_or_default();
+                    if socket.send(Message::Text(json.into())).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Next event of a live stream; never resolves while paused.
+async fn next_event(events: &mut Option<EventStream>) -> Option<String> {
+    match events {
+        Some(events) => events.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn apply_control(
+    state: &AppState,
+    subscription: &mut CyclicSubscription,
+    events: &mut Option<EventStream>,
+    seq_counter: &Arc<AtomicU64>,
+    control: StreamControl,
+) -> Result<(), ApiError> {
+    match control {
+        StreamControl::Pause => *events = None,
+        StreamControl::Resume => {
+            if events.is_none() {
+                *events =
+                    Some(subscription_events(state, subscription, seq_counter.clone()).await?);
+            }
+        }
+        StreamControl::SetInterval { interval } => {
+            check_rate_limit(state, interval)?;
+            *subscription = state
+                .subscription_manager
+                .update(&subscription.subscription_id, Some(interval), None)
+                .await
+                .ok_or_else(|| {
+                    ApiError::NotFound(format!(
+                        "Subscription not found: {}",
+                        subscription.subscription_id
+                    ))
+                })?;
+            // Re-subscribe at the new rate; a paused stream picks it up on resume.
+            if events.is_some() {
+                *events =
+                    Some(subscription_events(state, subscription, seq_counter.clone()).await?);
+            }
+        }
+    }
+    Ok(())
 }
 
 /// DELETE /vehicle/v1/components/:component_id/cyclic-subscriptions/:subscription_id
@@ -625,3 +1050,42 @@ pub async fn delete_cyclic_subscription(
         )))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn on_change(deadband: f64, deadband_pct: f64) -> SubscriptionMode {
+        SubscriptionMode::OnChange {
+            deadband,
+            deadband_pct,
+        }
+    }
+
+    #[test]
+    fn relative_deadband_scales_with_the_last_value() {
+        let mode = on_change(0.0, 10.0);
+        assert!(!mode.should_emit(Some(&json!(100.0)), &json!(109.0)));
+        assert!(mode.should_emit(Some(&json!(100.0)), &json!(111.0)));
+        assert!(mode.should_emit(Some(&json!(-100.0)), &json!(-111.0)));
+        assert!(!mode.should_emit(Some(&json!(1.0)), &json!(1.05)));
+    }
+
+    #[test]
+    fn relative_deadband_from_zero_emits_any_change() {
+        let mode = on_change(0.0, 10.0);
+        assert!(mode.should_emit(Some(&json!(0.0)), &json!(0.001)));
+        assert!(!mode.should_emit(Some(&json!(0.0)), &json!(0.0)));
+    }
+
+    #[test]
+    fn both_deadbands_must_be_exceeded() {
+        let mode = on_change(2.0, 10.0);
+        // 15% but only 1.5 absolute
+        assert!(!mode.should_emit(Some(&json!(10.0)), &json!(11.5)));
+        // 2.5 absolute but only 2.5%
+        assert!(!mode.should_emit(Some(&json!(100.0)), &json!(102.5)));
+        assert!(mode.should_emit(Some(&json!(10.0)), &json!(12.5)));
+    }
+}
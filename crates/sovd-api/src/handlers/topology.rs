@@ -0,0 +1,69 @@
+//! Vehicle topology view
+//!
+//! `GET /vehicle/v1/topology` returns the E/E architecture behind this
+//! server as a tree for visualization tools: a `vehicle` root, every
+//! component below it, and below those their sub-entities (a gateway's
+//! ECUs, an HPC's apps), nested as deep as gateways are. Each node carries
+//! its transport kind, address and whether it answered a health probe, as
+//! built by [`DiagnosticBackend::topology`]; components are probed
+//! concurrently. Auto-discovered ECUs show up under the gateway that
+//! registered them, so a misplaced one is easy to spot.
+//!
+//! [`DiagnosticBackend::topology`]: sovd_core::DiagnosticBackend::topology
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use sovd_core::TopologyNode;
+
+use crate::auth::ClientContext;
+use crate::state::AppState;
+
+/// GET /vehicle/v1/topology
+/// The components this server federates, as a tree.
+///
+/// Filtered like the component listing (C-031): with authentication enabled
+/// only the components the client may access are probed.
+pub async fn get_topology(
+    State(state): State<AppState>,
+    client: Option<Extension<ClientContext>>,
+) -> Json<TopologyNode> {
+    let client = client.map(|Extension(c)| c);
+    let probes = state
+        .backends()
+        .iter()
+        .filter(|(id, _)| match &client {
+            Some(c) => c.can_access_component(id.as_str()),
+            None => true,
+        })
+        .map(|(id, backend)| async move {
+            let mut node = match backend.topology().await {
+                Ok(node) => node,
+                Err(e) => {
+                    tracing::debug!(component = %id, error = %e, "Topology probe failed");
+                    TopologyNode::from_entity(backend.entity_info())
+                }
+            };
+            // The hrefs this server routes: components, then their apps
+            node.id = id.clone();
+            node.href = format!("/vehicle/v1/components/{}", id);
+            for child in &mut node.children {
+                child.href = format!("/vehicle/v1/components/{}/apps/{}", id, child.id);
+            }
+            node
+        });
+    let mut children = futures::future::join_all(probes).await;
+    children.sort_by(|a, b| a.id.cmp(&b.id));
+
+    Json(TopologyNode {
+        id: "vehicle".to_string(),
+        name: "Vehicle".to_string(),
+        entity_type: "vehicle".to_string(),
+        href: "/vehicle/v1/components".to_string(),
+        display_name: None,
+        category: None,
+        transport: None,
+        address: None,
+        reachable: Some(true),
+        children,
+    })
+}
@@ -0,0 +1,30 @@
+//! Transport link view (vendor extension `x-sumo-transport-stats`)
+//!
+//! `GET /vehicle/v1/components/{id}/x-sumo-transport-stats` reports the
+//! transport an entity is reached over via
+//! [`DiagnosticBackend::transport_stats`]: its kind and, on CAN, the ISO-TP
+//! TX data length (`tx_dl`) in effect — 8 on classic CAN, up to 64 on CAN
+//! FD — which bounds how many bytes each frame of a transfer carries.
+//!
+//! `transport-stats` is not a resource name from ISO 17978-3 Tables 8/10,
+//! so per C-025 it carries the `x-sumo-` prefix and is listed in
+//! `.well-known/sovd-extensions`.
+//!
+//! [`DiagnosticBackend::transport_stats`]: sovd_core::DiagnosticBackend::transport_stats
+
+use axum::extract::{Path, State};
+use axum::Json;
+
+use sovd_core::TransportStats;
+
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// GET /vehicle/v1/components/:component_id/x-sumo-transport-stats
+pub async fn get_transport_stats(
+    State(state): State<AppState>,
+    Path(component_id): Path<String>,
+) -> Result<Json<TransportStats>, ApiError> {
+    let backend = state.get_backend(&component_id)?;
+    Ok(Json(backend.transport_stats().await?))
+}
@@ -10,12 +10,15 @@
 //! |---------------------------------|---------|
 //! | `POST /updates`                 | (none — SOVD-side allocation of update_id) |
 //! | `PUT /bulk-data/{part_id}`      | `receive_package_stream` (returns file_id, recorded per part) |
+//! | `PUT /bulk-data/{part_id}` + `Content-Range` | (none until the last chunk — spooled to a temp file, then `receive_package_stream`; a spool no chunk reaches for `partial_part_ttl` is deleted) |
+//! | `HEAD /bulk-data/{part_id}`     | (none — `Upload-Offset` of the part) |
 //! | `POST /executions {verify}`     | `verify_package` per part, then `start_flash` (allocates transfer_id) |
 //! | `POST /executions {finalize}`   | `finalize_flash` + `activate` |
 //! | `POST /executions {commit}`     | `commit_flash` |
 //! | `POST /executions {rollback}`   | `rollback_flash` |
 //! | `POST /executions {abort}`      | `abort_flash(transfer_id)` if known; SOVD state cleared |
 //! | `DELETE /updates/{id}`          | same as abort |
+//! | `PUT /prepare` (`x-sumo-dry-run`) | `start_flash_dry_run` (RequestDownload only, no TransferData) |
 //!
 //! The dispatcher / per-part SUIT awareness arrives in F.D3.
 
@@ -35,7 +38,9 @@ use sovd_core::{PackageStream, UpdatePackageContext, UpdatePackageDescriptor, Up
 use uuid::Uuid;
 
 use crate::error::ApiError;
-use crate::state::{AppState, Phase, Status, UpdatePart, UpdateState, UpdatesEntry};
+use crate::state::{
+    AppState, PartialPart, Phase, Status, UpdatePart, UpdateState, UpdatesEntry, UpdatesStore,
+};
 
 /// Reserved update-package id (§7.18.1.5). On servers that self-select,
 /// `GET /updates/autonomous` resolves to a concrete installable id; SOVDd
@@ -97,6 +102,12 @@ pub struct RegisterUpdateRequest {
     /// `preconditions` from it; a format-aware backend may parse more.
     #[serde(default)]
     pub manifest: Option<serde_json::Value>,
+    /// Vendor extension: rehearse the flash without programming. Prepare
+    /// negotiates RequestDownload and reports the block plan in
+    /// `x-sumo-flash-summary`; no TransferData is sent and the update can
+    /// never be executed.
+    #[serde(rename = "x-sumo-dry-run", default)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -137,6 +148,17 @@ pub struct UpdateStatusBody {
     /// of defaulting every component to `Local`.
     #[serde(rename = "x-sumo-reset-kind", skip_serializing_if = "Option::is_none")]
     pub reset_kind: Option<sovd_core::ResetKind>,
+    /// Vendor extension: the backend's flash outcome record (bytes,
+    /// duration, throughput, retries, verified version). Present once the
+    /// update is terminal and the backend reported one.
+    #[serde(
+        rename = "x-sumo-flash-summary",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub flash_summary: Option<sovd_core::FlashSummary>,
+    /// Vendor extension: the update was registered as a dry run.
+    #[serde(rename = "x-sumo-dry-run", skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -160,6 +182,21 @@ pub struct PartUploadResponse {
     pub href: String,
 }
 
+/// Reply to a `Content-Range` chunk that leaves the part incomplete
+#[derive(Debug, Serialize)]
+pub struct PartChunkResponse {
+    pub part_id: String,
+    /// Bytes received so far; the next chunk starts here
+    pub received: u64,
+    /// Declared full size
+    pub size: u64,
+}
+
+/// `Upload-Offset`: bytes of a part the server holds (tus naming)
+const UPLOAD_OFFSET: &str = "upload-offset";
+/// `Upload-Length`: declared full size of a part (tus naming)
+const UPLOAD_LENGTH: &str = "upload-length";
+
 /// `PUT /updates/{id}/execute` query parameters.
 ///
 /// `x-sumo-control=orchestrated` opts the request into the Phase B
@@ -194,6 +231,7 @@ pub async fn register_update(
 ) -> Result<impl IntoResponse, ApiError> {
     // Verify the component exists before allocating an id.
     let backend = state.get_backend(&component_id)?;
+    expire_partial_parts(&state.updates, state.updates_config.partial_part_ttl).await;
 
     let req = body.map(|Json(b)| b).unwrap_or_default();
 
@@ -225,6 +263,7 @@ pub async fn register_update(
         _ => Uuid::new_v4().to_string(),
     };
     let manifest = req.manifest;
+    let dry_run = req.dry_run;
 
     // Collision handling on a (now possibly client-stable) id, scoped to the
     // component. A still-active package → 409 update-process-in-progress
@@ -280,20 +319,25 @@ pub async fn register_update(
     // pattern in `ManagedEcuBackend`, errors with `InvalidRequest`)
     // simply skip this step. The actual flash session will be opened
     // later when the package is ready (during the /executions wire's
-    // `verify` action or at `PUT /prepare`).
-    let transfer_id = match backend.start_flash().await {
-        Ok(tid) => Some(tid),
-        // Backends that don't preallocate (`NotSupported`), or that require an
-        // already-verified package (`InvalidRequest`, the tier-1 supplier
-        // pattern), legitimately skip this step — the session opens later at
-        // verify/prepare.
-        Err(sovd_core::BackendError::NotSupported(_))
-        | Err(sovd_core::BackendError::InvalidRequest(_)) => None,
-        // Any other error is real — surface it. In particular `Busy` (409) means
-        // the bank set is in trial mode and must be committed/rolled back first;
-        // falling through here would let the later bulk-data PUT hit the
-        // unguarded legacy path and wipe the rollback bank.
-        Err(e) => return Err(e.into()),
+    // `verify` action or at `PUT /prepare`). A dry run never opens a real
+    // session; prepare starts the dry-run transfer instead.
+    let transfer_id = if dry_run {
+        None
+    } else {
+        match backend.start_flash().await {
+            Ok(tid) => Some(tid),
+            // Backends that don't preallocate (`NotSupported`), or that require an
+            // already-verified package (`InvalidRequest`, the tier-1 supplier
+            // pattern), legitimately skip this step — the session opens later at
+            // verify/prepare.
+            Err(sovd_core::BackendError::NotSupported(_))
+            | Err(sovd_core::BackendError::InvalidRequest(_)) => None,
+            // Any other error is real — surface it. In particular `Busy` (409) means
+            // the bank set is in trial mode and must be committed/rolled back first;
+            // falling through here would let the later bulk-data PUT hit the
+            // unguarded legacy path and wipe the rollback bank.
+            Err(e) => return Err(e.into()),
+        }
     };
 
     // Capture the component's declared ResetKind once, while it's idle
@@ -324,8 +368,10 @@ pub async fn register_update(
                 substate: None,
                 reset_kind,
                 transfer_id,
+                dry_run,
                 task_handle: None,
                 verdict_tx: None,
+                partial_parts: Vec::new(),
             },
         );
     }
@@ -488,7 +534,10 @@ pub async fn delete_update(
     if let Some(tid) = transfer_id {
         let _ = backend.abort_flash(&tid).await;
     }
-    state.updates.0.lock().remove(&update_id);
+    let removed = state.updates.0.lock().remove(&update_id);
+    for partial in removed.iter().flat_map(|e| &e.partial_parts) {
+        let _ = tokio::fs::remove_file(&partial.path).await;
+    }
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -549,7 +598,7 @@ pub async fn put_prepare(
 
     // Snapshot the parts list + transfer_id under the lock; bail if
     // the entry isn't in a startable phase/status.
-    let (parts, transfer_id) = {
+    let (parts, transfer_id, dry_run) = {
         let mut store = state.updates.0.lock();
         let entry = store
             .get_mut(&update_id)
@@ -566,6 +615,12 @@ pub async fn put_prepare(
                 "prepare called before any /bulk-data part uploaded".into(),
             ));
         }
+        if let Some(partial) = entry.partial_parts.first() {
+            return Err(ApiError::Conflict(format!(
+                "part {} is incomplete: {} of {} bytes uploaded",
+                partial.part_id, partial.received, partial.total
+            )));
+        }
         entry.phase = Phase::Prepare;
         entry.status = Status::InProgress;
         entry.progress = Some(0);
@@ -576,7 +631,7 @@ pub async fn put_prepare(
             .iter()
             .map(|p| (p.part_id.clone(), p.file_id.clone(), p.sha256.clone()))
             .collect::<Vec<_>>();
-        (parts, entry.transfer_id.clone())
+        (parts, entry.transfer_id.clone(), entry.dry_run)
     };
 
     // Spawn the prepare task. Use AbortHandle so DELETE can cancel.
@@ -611,7 +666,19 @@ pub async fn put_prepare(
                 Ok(()) => Ok(()),
                 Err(sovd_core::BackendError::NotSupported(_)) => {
                     if part_id == "manifest" {
-                        backend.verify_package(file_id).await.map(|_| ())
+                        // An invalid package (e.g. a failed signature
+                        // check) stops the update here
+                        backend.verify_package(file_id).await.and_then(|result| {
+                            if result.valid {
+                                Ok(())
+                            } else {
+                                Err(sovd_core::BackendError::InvalidRequest(
+                                    result
+                                        .error
+                                        .unwrap_or_else(|| "package is invalid".to_string()),
+                                ))
+                            }
+                        })
                     } else {
                         Ok(())
                     }
@@ -633,6 +700,43 @@ pub async fn put_prepare(
             }
         }
 
+        // A dry run negotiates the download and stops; the plan lands in
+        // the backend's flash summary.
+        if dry_run {
+            let _ = mutate_entry(&task_state, &task_update_id, |entry| {
+                entry.step = Some("planning dry-run flash".into());
+            });
+            let planned = match backend.start_flash_dry_run().await {
+                Ok(tid) => {
+                    let _ = mutate_entry(&task_state, &task_update_id, |entry| {
+                        entry.transfer_id = Some(tid.clone());
+                    });
+                    await_flash_settled(backend.as_ref(), &tid).await
+                }
+                Err(e) => Err(ApiError::from(e)),
+            };
+            let _ = mutate_entry(&task_state, &task_update_id, |entry| {
+                match planned {
+                    Ok(()) => {
+                        entry.status = Status::Completed;
+                        entry.progress = Some(100);
+                        entry.step = Some("dry run planned, nothing written".into());
+                    }
+                    Err(e) => {
+                        entry.status = Status::Failed;
+                        entry.step = Some("dry run failed".into());
+                        entry.error = Some(crate::state::UpdateError {
+                            error_code: "update-preparation-failed".into(),
+                            message: format!("dry run: {e:?}"),
+                            parameters: None,
+                        });
+                    }
+                }
+                entry.task_handle = None;
+            });
+            return;
+        }
+
         // If register_update couldn't preallocate the backend flash
         // session (NotSupported / not-yet-ready), retry now — by this
         // point any verified-package precondition the backend wanted
@@ -735,6 +839,12 @@ pub async fn put_execute(
                 entry.phase.as_str()
             )));
         }
+        if entry.dry_run {
+            return Err(ApiError::Conflict(format!(
+                "update {update_id} is a dry run and cannot be executed; \
+                 register it again without x-sumo-dry-run"
+            )));
+        }
         // Spec §7.18.6: execute requires prepare to have completed.
         if !(entry.phase == Phase::Prepare && entry.status == Status::Completed
             || entry.phase == Phase::Execute && entry.status == Status::Failed)
@@ -1190,25 +1300,40 @@ pub async fn get_status(
     State(state): State<AppState>,
     Path((component_id, update_id)): Path<(String, String)>,
 ) -> Result<Json<UpdateStatusBody>, ApiError> {
-    let _ = state.get_backend(&component_id)?;
-    let store = state.updates.0.lock();
-    let entry = store
-        .get(&update_id)
-        .filter(|e| e.component_id == component_id)
-        .ok_or_else(|| ApiError::NotFound(format!("update {update_id} not found")))?;
-    Ok(Json(UpdateStatusBody {
-        phase: entry.phase.as_str(),
-        status: entry.status.as_str(),
-        progress: entry.progress,
-        step: entry.step.clone(),
-        error: if entry.status == Status::Failed {
-            entry.error.clone()
-        } else {
-            None
-        },
-        substate: entry.substate,
-        reset_kind: entry.reset_kind,
-    }))
+    let backend = state.get_backend(&component_id)?;
+    let (mut body, transfer_id) = {
+        let store = state.updates.0.lock();
+        let entry = store
+            .get(&update_id)
+            .filter(|e| e.component_id == component_id)
+            .ok_or_else(|| ApiError::NotFound(format!("update {update_id} not found")))?;
+        let body = UpdateStatusBody {
+            phase: entry.phase.as_str(),
+            status: entry.status.as_str(),
+            progress: entry.progress,
+            step: entry.step.clone(),
+            error: if entry.status == Status::Failed {
+                entry.error.clone()
+            } else {
+                None
+            },
+            substate: entry.substate,
+            reset_kind: entry.reset_kind,
+            flash_summary: None,
+            dry_run: entry.dry_run,
+        };
+        let terminal = matches!(entry.status, Status::Completed | Status::Failed);
+        (body, entry.transfer_id.clone().filter(|_| terminal))
+    };
+    if let Some(tid) = transfer_id {
+        // Best effort: the summary decorates the status, it never fails it.
+        body.flash_summary = backend
+            .get_flash_status(&tid)
+            .await
+            .ok()
+            .and_then(|s| s.summary);
+    }
+    Ok(Json(body))
 }
 
 /// GET /vehicle/v1/components/{component_id}/updates/{update_id}/bulk-data
@@ -1230,13 +1355,22 @@ pub async fn list_bulk_data(
 }
 
 /// PUT /vehicle/v1/components/{component_id}/updates/{update_id}/bulk-data/{part_id}
+///
+/// The body is the whole part, streamed to the backend. With
+/// `Content-Range: bytes {start}-{end}/{total}` it is one chunk of the part
+/// instead: chunks are appended to a temp file in order (each must start
+/// where the previous one ended) and answered 202 with `Upload-Offset`;
+/// the chunk completing `total` hands the file to the backend and is
+/// answered like a whole-part upload. After a disconnect, `HEAD` on the
+/// part tells where to resume.
 pub async fn put_bulk_data_part(
     State(state): State<AppState>,
     Path((component_id, update_id, part_id)): Path<(String, String, String)>,
     headers: HeaderMap,
     body: Body,
-) -> Result<impl IntoResponse, ApiError> {
+) -> Result<axum::response::Response, ApiError> {
     let backend = state.get_backend(&component_id)?;
+    expire_partial_parts(&state.updates, state.updates_config.partial_part_ttl).await;
     {
         let store = state.updates.0.lock();
         let entry = store
@@ -1255,18 +1389,398 @@ pub async fn put_bulk_data_part(
         }
     }
 
+    if let Some(range) = headers.get(header::CONTENT_RANGE) {
+        let range = range
+            .to_str()
+            .ok()
+            .and_then(parse_content_range)
+            .ok_or_else(|| {
+                ApiError::BadRequest(
+                    "Content-Range must be 'bytes {start}-{end}/{total}'".to_string(),
+                )
+            })?;
+        return put_part_chunk(
+            &state,
+            backend,
+            &component_id,
+            &update_id,
+            part_id,
+            range,
+            body,
+        )
+        .await;
+    }
+
     let content_length = headers
         .get(header::CONTENT_LENGTH)
         .and_then(|v| v.to_str().ok())
         .and_then(|v| v.parse::<u64>().ok());
+    let data_stream = body
+        .into_data_stream()
+        .map(|chunk| chunk.map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>));
+    let uploaded = store_part(
+        &state,
+        backend,
+        &component_id,
+        &update_id,
+        part_id,
+        Box::pin(data_stream),
+        content_length,
+    )
+    .await?;
+    Ok(uploaded.into_response())
+}
+
+/// HEAD /vehicle/v1/components/{component_id}/updates/{update_id}/bulk-data/{part_id}
+///
+/// `Upload-Offset` / `Upload-Length` of the part: where a chunked upload
+/// resumes, or equal for a complete part.
+pub async fn head_bulk_data_part(
+    State(state): State<AppState>,
+    Path((component_id, update_id, part_id)): Path<(String, String, String)>,
+) -> Result<impl IntoResponse, ApiError> {
+    expire_partial_parts(&state.updates, state.updates_config.partial_part_ttl).await;
+    let store = state.updates.0.lock();
+    let entry = store
+        .get(&update_id)
+        .filter(|e| e.component_id == component_id)
+        .ok_or_else(|| ApiError::NotFound(format!("update {update_id} not found")))?;
+    let (offset, length) =
+        if let Some(partial) = entry.partial_parts.iter().find(|p| p.part_id == part_id) {
+            (partial.received, partial.total)
+        } else if let Some(part) = entry.parts.iter().find(|p| p.part_id == part_id) {
+            (part.size, part.size)
+        } else {
+            return Err(ApiError::NotFound(format!("part {part_id} not found")));
+        };
+    Ok([
+        (UPLOAD_OFFSET, HeaderValue::from(offset)),
+        (UPLOAD_LENGTH, HeaderValue::from(length)),
+    ])
+}
+
+/// `Content-Range: bytes {start}-{end}/{total}`, as (start, end, total)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ContentRange {
+    start: u64,
+    /// Inclusive
+    end: u64,
+    total: u64,
+}
+
+fn parse_content_range(value: &str) -> Option<ContentRange> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, end) = range.split_once('-')?;
+    let range = ContentRange {
+        start: start.trim().parse().ok()?,
+        end: end.trim().parse().ok()?,
+        total: total.trim().parse().ok()?,
+    };
+    (range.start <= range.end && range.end < range.total).then_some(range)
+}
+
+/// Write one `Content-Range` chunk to the part's temp file, and hand the
+/// file to the backend once the part is complete
+async fn put_part_chunk(
+    state: &AppState,
+    backend: &dyn sovd_core::DiagnosticBackend,
+    component_id: &str,
+    update_id: &str,
+    part_id: String,
+    range: ContentRange,
+    body: Body,
+) -> Result<axum::response::Response, ApiError> {
+    // Claim the part's temp file, checking the chunk continues it
+    let path = {
+        let mut store = state.updates.0.lock();
+        let entry = store
+            .get_mut(update_id)
+            .ok_or_else(|| ApiError::NotFound(format!("update {update_id} not found")))?;
+        let partial = match entry
+            .partial_parts
+            .iter()
+            .position(|p| p.part_id == part_id)
+        {
+            Some(idx) => &mut entry.partial_parts[idx],
+            None if range.start == 0 => {
+                entry.partial_parts.push(PartialPart {
+                    part_id: part_id.clone(),
+                    total: range.total,
+                    received: 0,
+                    path: std::env::temp_dir().join(format!("sovd-upload-{}", Uuid::new_v4())),
+                    writing: false,
+                    last_chunk_at: std::time::Instant::now(),
+                });
+                entry.state = UpdateState::Uploading;
+                entry.partial_parts.last_mut().expect("just pushed")
+            }
+            None => {
+                return Err(ApiError::Conflict(format!(
+                    "no upload of part {part_id} in progress; the first chunk starts at 0"
+                )))
+            }
+        };
+        // A first chunk again restarts the part
+        if range.start == 0 && !partial.writing {
+            partial.total = range.total;
+            partial.received = 0;
+        }
+        if partial.total != range.total {
+            return Err(ApiError::Conflict(format!(
+                "part {part_id} was declared {} bytes, not {}",
+                partial.total, range.total
+            )));
+        }
+        if partial.received != range.start {
+            return Err(ApiError::Conflict(format!(
+                "part {part_id} continues at byte {}, not {}",
+                partial.received, range.start
+            )));
+        }
+        if partial.writing {
+            return Err(ApiError::Conflict(format!(
+                "a chunk of part {part_id} is already being written"
+            )));
+        }
+        partial.writing = true;
+        partial.last_chunk_at = std::time::Instant::now();
+        partial.path.clone()
+    };
 
+    let writing = ChunkWriting {
+        state,
+        update_id,
+        part_id: &part_id,
+    };
+    let written = write_chunk(&path, range, body).await;
+    drop(writing);
+    let received = {
+        let mut store = state.updates.0.lock();
+        let partial = store
+            .get_mut(update_id)
+            .and_then(|e| e.partial_parts.iter_mut().find(|p| p.part_id == part_id));
+        let Some(partial) = partial else {
+            // The update was deleted while the chunk was in flight
+            let _ = std::fs::remove_file(&path);
+            return Err(ApiError::NotFound(format!("update {update_id} not found")));
+        };
+        if written.is_ok() {
+            partial.received = range.end + 1;
+        }
+        partial.last_chunk_at = std::time::Instant::now();
+        partial.received
+    };
+    written?;
+
+    if received < range.total {
+        let body = PartChunkResponse {
+            part_id,
+            received,
+            size: range.total,
+        };
+        return Ok((
+            StatusCode::ACCEPTED,
+            [
+                (UPLOAD_OFFSET, HeaderValue::from(received)),
+                (UPLOAD_LENGTH, HeaderValue::from(range.total)),
+            ],
+            Json(body),
+        )
+            .into_response());
+    }
+
+    // Complete: the spooled part goes to the backend like a streamed one
+    if let Some(entry) = state.updates.0.lock().get_mut(update_id) {
+        entry.partial_parts.retain(|p| p.part_id != part_id);
+    }
+    let file = tokio::fs::File::open(&path)
+        .await
+        .map_err(|e| ApiError::Internal(format!("open spooled part: {e}")));
+    let uploaded = match file {
+        Ok(file) => {
+            store_part(
+                state,
+                backend,
+                component_id,
+                update_id,
+                part_id,
+                file_stream(file),
+                Some(range.total),
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+    let _ = tokio::fs::remove_file(&path).await;
+    Ok(uploaded?.into_response())
+}
+
+/// Drop the chunked parts of every update that no chunk has reached for
+/// `ttl` ([`UpdatesConfig::partial_part_ttl`]), deleting their spool
+/// files, so an upload its client abandoned does not hold disk space until
+/// the update is deleted
+///
+/// [`UpdatesConfig::partial_part_ttl`]: crate::state::UpdatesConfig::partial_part_ttl
+async fn expire_partial_parts(updates: &UpdatesStore, ttl: std::time::Duration) {
+    let mut expired = Vec::new();
+    for (update_id, entry) in updates.0.lock().iter_mut() {
+        entry.partial_parts.retain(|p| {
+            let stale = !p.writing && p.last_chunk_at.elapsed() >= ttl;
+            if stale {
+                tracing::info!(
+                    update_id = %update_id,
+                    part_id = %p.part_id,
+                    received = p.received,
+                    "Chunked part upload abandoned; spool file deleted"
+                );
+                expired.push(p.path.clone());
+            }
+            !stale
+        });
+    }
+    for path in expired {
+        let _ = tokio::fs::remove_file(path).await;
+    }
+}
+
+/// Run [`expire_partial_parts`] every half `partial_part_ttl`, so an
+/// abandoned spool file is deleted even when no further update request
+/// arrives. The task ends once the last `AppState` holding the store is
+/// dropped.
+pub(crate) fn spawn_partial_part_sweeper(state: &AppState) {
+    let updates = Arc::downgrade(&state.updates.0);
+    let ttl = state.updates_config.partial_part_ttl;
+    let period = (ttl / 2).max(std::time::Duration::from_millis(50));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        interval.tick().await; // nothing to expire at startup
+        loop {
+            interval.tick().await;
+            let Some(updates) = updates.upgrade() else {
+                break;
+            };
+            expire_partial_parts(&UpdatesStore(updates), ttl).await;
+        }
+    });
+}
+
+/// Clears the part's `writing` claim when dropped, so a chunk whose
+/// request goes away mid-body (client disconnect) does not leave the part
+/// refusing every later chunk
+struct ChunkWriting<'a> {
+    state: &'a AppState,
+    update_id: &'a str,
+    part_id: &'a str,
+}
+
+impl Drop for ChunkWriting<'_> {
+    fn drop(&mut self) {
+        let mut store = self.state.updates.0.lock();
+        let partial = store.get_mut(self.update_id).and_then(|e| {
+            e.partial_parts
+                .iter_mut()
+                .find(|p| p.part_id == self.part_id)
+        });
+        if let Some(partial) = partial {
+            partial.writing = false;
+        }
+    }
+}
+
+/// Write a chunk body at `range.start` of the temp file. A body shorter or
+/// longer than the range leaves the file as it was.
+async fn write_chunk(
+    path: &std::path::Path,
+    range: ContentRange,
+    body: Body,
+) -> Result<(), ApiError> {
+    use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+    let io_err = |e: std::io::Error| ApiError::Internal(format!("spool part chunk: {e}"));
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(path)
+        .await
+        .map_err(io_err)?;
+    // Anything past `start` is a restarted or failed earlier attempt
+    file.set_len(range.start).await.map_err(io_err)?;
+    file.seek(std::io::SeekFrom::Start(range.start))
+        .await
+        .map_err(io_err)?;
+    let expected = range.end - range.start + 1;
+    let mut written = 0u64;
+    let mut stream = body.into_data_stream();
+    let mut result = Ok(());
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                result = Err(ApiError::BadRequest(format!("chunk body: {e}")));
+                break;
+            }
+        };
+        written += chunk.len() as u64;
+        if written > expected {
+            break;
+        }
+        if let Err(e) = file.write_all(&chunk).await {
+            result = Err(io_err(e));
+            break;
+        }
+    }
+    if result.is_ok() && written != expected {
+        result = Err(ApiError::BadRequest(format!(
+            "chunk body is {written} bytes, Content-Range announces {expected}"
+        )));
+    }
+    if result.is_err() {
+        file.set_len(range.start).await.map_err(io_err)?;
+        return result;
+    }
+    file.flush().await.map_err(io_err)
+}
+
+/// Read a spooled part back as a [`PackageStream`]
+fn file_stream(file: tokio::fs::File) -> PackageStream {
+    use tokio::io::AsyncReadExt;
+
+    // The file is dropped after a read error, ending the stream
+    Box::pin(futures::stream::unfold(Some(file), |file| async move {
+        let mut file = file?;
+        let mut buf = vec![0u8; 64 * 1024];
+        match file.read(&mut buf).await {
+            Ok(0) => None,
+            Ok(n) => {
+                buf.truncate(n);
+                Some((Ok(axum::body::Bytes::from(buf)), Some(file)))
+            }
+            Err(e) => Some((
+                Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>),
+                None,
+            )),
+        }
+    }))
+}
+
+/// Stream a complete part to the backend, hashing it on the way, and record
+/// it on the update
+async fn store_part(
+    state: &AppState,
+    backend: &dyn sovd_core::DiagnosticBackend,
+    component_id: &str,
+    update_id: &str,
+    part_id: String,
+    stream: PackageStream,
+    content_length: Option<u64>,
+) -> Result<impl IntoResponse, ApiError> {
     let hasher = Arc::new(Mutex::new(Sha256::new()));
     let size_counter = Arc::new(AtomicU64::new(0));
     let hasher_clone = hasher.clone();
     let size_clone = size_counter.clone();
-    let data_stream = body.into_data_stream().map(move |chunk_res| {
-        let chunk =
-            chunk_res.map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+    let data_stream = stream.map(move |chunk_res| {
+        let chunk = chunk_res?;
         size_clone.fetch_add(chunk.len() as u64, Ordering::Relaxed);
         if let Ok(mut h) = hasher_clone.lock() {
             h.update(&chunk);
@@ -1286,9 +1800,10 @@ pub async fn put_bulk_data_part(
         .finalize();
     let sha256 = hex::encode(digest);
 
-    {
+    let replaced_partial = {
         let mut store = state.updates.0.lock();
-        if let Some(entry) = store.get_mut(&update_id) {
+        let mut replaced = None;
+        if let Some(entry) = store.get_mut(update_id) {
             entry.parts.retain(|p| p.part_id != part_id);
             entry.parts.push(UpdatePart {
                 part_id: part_id.clone(),
@@ -1296,14 +1811,26 @@ pub async fn put_bulk_data_part(
                 sha256: sha256.clone(),
                 file_id,
             });
+            // A whole-part upload supersedes an unfinished chunked one
+            if let Some(idx) = entry
+                .partial_parts
+                .iter()
+                .position(|p| p.part_id == part_id)
+            {
+                replaced = Some(entry.partial_parts.remove(idx).path);
+            }
             entry.state = UpdateState::Uploading;
         }
+        replaced
+    };
+    if let Some(path) = replaced_partial {
+        let _ = tokio::fs::remove_file(path).await;
     }
 
     let href = format!(
         "/vehicle/v1/components/{}/updates/{}/bulk-data/{}",
         component_id,
-        enc(&update_id),
+        enc(update_id),
         enc(&part_id)
     );
     let mut response_headers = HeaderMap::new();
@@ -17,6 +17,7 @@
 pub mod auth;
 pub mod error;
 pub mod handlers;
+mod negotiate;
 pub mod state;
 pub mod workshop_ca;
 
@@ -25,7 +26,10 @@ pub use auth::{
     IssuerConfig,
 };
 pub use error::ApiError;
-pub use state::AppState;
+pub use state::{
+    AppState, DataAliasCollision, DataAliases, ReadCacheConfig, SubscriptionLimits,
+    VehicleStatusThresholds,
+};
 
 // Re-export DidStore from sovd-conv for convenience
 pub use sovd_conv::{DataType, DidDefinition, DidStore};
@@ -37,13 +41,23 @@ use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
 /// Create the SOVD REST API router with the given application state
+///
+/// Spawns the sweeper for abandoned update uploads, so it must be called
+/// from within a Tokio runtime.
 pub fn create_router(state: AppState) -> Router {
+    handlers::updates::spawn_partial_part_sweeper(&state);
+
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
         .allow_headers(Any);
 
-    Router::new()
+    let router = Router::new();
+    // Transport metrics for Prometheus scrapers (see handlers::metrics)
+    #[cfg(feature = "prometheus")]
+    let router = router.route("/metrics", get(handlers::metrics::get_metrics));
+
+    router
         // Health check
         .route("/health", get(|| async { "OK" }))
         // Spec §7.4 — version-info is version-INDEPENDENT (constant
@@ -74,6 +88,36 @@ pub fn create_router(state: AppState) -> Router {
             "/.well-known/sovd-extensions",
             get(handlers::meta::sovd_extensions),
         )
+        // Vehicle-wide identification aggregate (vendor extension,
+        // listed in `.well-known/sovd-extensions`).
+        //
+        // C-025 scope note: like `/admin/*`, this is rooted off the
+        // `/vehicle/v1/components/{id}/…` entity tree and is not a resource
+        // of any one entity — it aggregates every component's
+        // identification block for fleet inventory — so it does not put a
+        // non-standard name on an entity.
+        .route(
+            "/vehicle/v1/identification",
+            get(handlers::identification::get_identification),
+        )
+        // Per-backend reachability (vendor extension, listed in
+        // `.well-known/sovd-extensions`). Unlike `/health`, which only says
+        // the server is up, this probes every backend's target; same C-025
+        // scope note as the identification aggregate.
+        .route(
+            "/vehicle/v1/health/backends",
+            get(handlers::health::get_backend_health),
+        )
+        // The same probes rolled up into one vehicle status (ok / degraded /
+        // down) for fleet dashboards.
+        .route(
+            "/vehicle/v1/status",
+            get(handlers::health::get_vehicle_status),
+        )
+        // Components and their sub-entities as one tree with transport,
+        // address and reachability per node (vendor extension, listed in
+        // `.well-known/sovd-extensions`); same C-025 scope note.
+        .route("/vehicle/v1/topology", get(handlers::topology::get_topology))
         // Component routes
         .route(
             "/vehicle/v1/components",
@@ -92,6 +136,12 @@ pub fn create_router(state: AppState) -> Router {
             "/vehicle/v1/components/{component_id}/data/{param_id}",
             get(handlers::data::read_parameter).put(handlers::data::write_parameter),
         )
+        // Vendor batch write: several parameters in order, best effort
+        // (UDS has no multi-DID transaction), per-parameter outcome.
+        .route(
+            "/vehicle/v1/components/{component_id}/data:batch",
+            post(handlers::data::write_data_batch),
+        )
         // Child-ECU data behind a gateway is addressed via the sub-entity
         // path (`/apps/{child}/data/{param}`), NOT a flat
         // `/data/{child}/{param}` route.  The dedicated flat gateway
@@ -110,6 +160,14 @@ pub fn create_router(state: AppState) -> Router {
             "/vehicle/v1/components/{component_id}/faults/{fault_id}",
             get(handlers::faults::get_fault).delete(handlers::faults::delete_fault),
         )
+        .route(
+            "/vehicle/v1/components/{component_id}/faults/{fault_id}/snapshots",
+            get(handlers::faults::get_fault_snapshots),
+        )
+        .route(
+            "/vehicle/v1/components/{component_id}/faults/{fault_id}/extended-data",
+            get(handlers::faults::get_fault_extended_data),
+        )
         // Active-only DTCs are exposed via the spec faults filter:
         //   GET /faults?active_only=true
         // No dedicated /dtcs route — kept the codebase one collection
@@ -345,6 +403,31 @@ pub fn create_router(state: AppState) -> Router {
             "/vehicle/v1/components/{component_id}/apps/{app_id}/faults/{fault_id}",
             get(handlers::sub_entity::get_sub_entity_fault),
         )
+        // Sub-entity raw memory reads (x-sumo-memory, see the entity-root route)
+        .route(
+            "/vehicle/v1/components/{component_id}/apps/{app_id}/x-sumo-memory",
+            get(handlers::sub_entity::read_sub_entity_memory),
+        )
+        .route(
+            "/vehicle/v1/components/{component_id}/apps/{app_id}/x-sumo-memory/upload",
+            get(handlers::sub_entity::upload_sub_entity_memory),
+        )
+        // Sub-entity software fingerprints (x-sumo-fingerprints)
+        .route(
+            "/vehicle/v1/components/{component_id}/apps/{app_id}/x-sumo-fingerprints",
+            get(handlers::sub_entity::get_sub_entity_fingerprints),
+        )
+        // Sub-entity transport link view (x-sumo-transport-stats)
+        .route(
+            "/vehicle/v1/components/{component_id}/apps/{app_id}/x-sumo-transport-stats",
+            get(handlers::sub_entity::get_sub_entity_transport_stats),
+        )
+        // Sub-entity bus baud-rate switching (x-sumo-link)
+        .route(
+            "/vehicle/v1/components/{component_id}/apps/{app_id}/x-sumo-link",
+            get(handlers::sub_entity::get_sub_entity_link)
+                .put(handlers::sub_entity::put_sub_entity_link),
+        )
         // Sub-entity operation routes — same executions sub-resource
         // pattern as the entity-root operations (§7.14).
         .route(
@@ -439,7 +522,8 @@ pub fn create_router(state: AppState) -> Router {
         )
         .route(
             "/vehicle/v1/components/{component_id}/updates/{update_id}/bulk-data/{part_id}",
-            put(handlers::updates::put_bulk_data_part),
+            put(handlers::updates::put_bulk_data_part)
+                .head(handlers::updates::head_bulk_data_part),
         )
         // ISO 17978-3 §7.18 spec verbs — async 202 + Location :: /status.
         // The F.D8b vendor-extension `/executions{action}` wire (deprecated
@@ -486,6 +570,39 @@ pub fn create_router(state: AppState) -> Router {
             "/vehicle/v1/components/{component_id}/x-sumo-force-rollback",
             put(handlers::updates::put_x_sumo_force_rollback),
         )
+        // Raw memory reads (UDS ReadMemoryByAddress 0x23) for regions not
+        // exposed as DIDs. `memory` is not a Table 8/10 resource name, so
+        // per C-025 it is vendor-prefixed and listed in
+        // `.well-known/sovd-extensions`.
+        .route(
+            "/vehicle/v1/components/{component_id}/x-sumo-memory",
+            get(handlers::memory::read_memory),
+        )
+        // Memory read-out as a transfer (RequestUpload 0x35 + TransferData)
+        // for regions too large for one 0x23 read
+        .route(
+            "/vehicle/v1/components/{component_id}/x-sumo-memory/upload",
+            get(handlers::memory::upload_memory),
+        )
+        // Software fingerprints (DIDs 0xF183-0xF185), decoded where the
+        // ECU's layout is configured. Vendor-prefixed per C-025.
+        .route(
+            "/vehicle/v1/components/{component_id}/x-sumo-fingerprints",
+            get(handlers::fingerprints::get_fingerprints),
+        )
+        // Transport link view (kind, effective ISO-TP tx_dl) for tuning
+        // CAN FD transfers. Vendor-prefixed per C-025.
+        .route(
+            "/vehicle/v1/components/{component_id}/x-sumo-transport-stats",
+            get(handlers::transport_stats::get_transport_stats),
+        )
+        // Bus baud-rate switching (UDS LinkControl 0x87 handshake). Link
+        // control has no standardized mode name, so not `modes/link`:
+        // vendor-prefixed per C-025.
+        .route(
+            "/vehicle/v1/components/{component_id}/x-sumo-link",
+            get(handlers::link::get_link).put(handlers::link::put_link),
+        )
         // Admin routes - DID definitions management.
         //
         // C-025 scope note: `/admin/*` is a server administration API,
@@ -502,6 +619,10 @@ pub fn create_router(state: AppState) -> Router {
                 .post(handlers::definitions::upload_definitions)
                 .delete(handlers::definitions::clear_definitions),
         )
+        .route(
+            "/admin/definitions/schema",
+            get(handlers::definitions::get_definition_schema),
+        )
         .route(
             "/admin/definitions/{did}",
             get(handlers::definitions::get_definition)
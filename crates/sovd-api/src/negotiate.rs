@@ -0,0 +1,128 @@
+//! Response format negotiation for the data endpoints
+//!
+//! `GET .../data` and `GET .../data/{param_id}` honor `Accept:
+//! application/cbor`, serializing the same models as CBOR for
+//! bandwidth-constrained vehicle links. JSON stays the default: a missing
+//! `Accept`, `*/*` or `application/*` get JSON, and an `Accept` naming
+//! neither format is refused with 406 rather than silently answered in
+//! JSON. Error bodies are always JSON, and streaming (SSE) is unaffected.
+
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+use crate::error::ApiError;
+
+/// CBOR media type (RFC 8949)
+pub const CBOR: &str = "application/cbor";
+
+/// Serialization of a data response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Cbor,
+}
+
+impl Format {
+    /// Pick the format for `headers`' `Accept`, preferring the highest
+    /// q-value and, between equals, the one listed first
+    pub fn negotiate(headers: &HeaderMap) -> Result<Self, ApiError> {
+        let Some(accept) = headers.get(header::ACCEPT) else {
+            return Ok(Self::Json);
+        };
+        let accept = accept.to_str().unwrap_or_default();
+        if accept.trim().is_empty() {
+            return Ok(Self::Json);
+        }
+
+        let mut best: Option<(f32, Self)> = None;
+        for range in accept.split(',') {
+            let mut parts = range.split(';');
+            let media_type = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+            let quality = parts
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            let format = match media_type.as_str() {
+                "application/json" | "application/*" | "*/*" => Self::Json,
+                CBOR => Self::Cbor,
+                _ => continue,
+            };
+            if quality > 0.0 && best.is_none_or(|(q, _)| quality > q) {
+                best = Some((quality, format));
+            }
+        }
+
+        best.map(|(_, format)| format).ok_or_else(|| {
+            ApiError::NotAcceptable(format!(
+                "cannot produce {accept}; supported: application/json, {CBOR}"
+            ))
+        })
+    }
+
+    /// Serialize `body` as a 200 response in this format
+    pub fn respond<T: Serialize>(self, body: &T) -> Response {
+        match self {
+            Self::Json => Json(body).into_response(),
+            Self::Cbor => {
+                let mut bytes = Vec::new();
+                if let Err(e) = ciborium::into_writer(body, &mut bytes) {
+                    return ApiError::Internal(format!("CBOR encoding failed: {e}"))
+                        .into_response();
+                }
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, HeaderValue::from_static(CBOR))],
+                    bytes,
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accept(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn json_is_the_default() {
+        assert_eq!(Format::negotiate(&HeaderMap::new()).unwrap(), Format::Json);
+        assert_eq!(Format::negotiate(&accept("*/*")).unwrap(), Format::Json);
+        assert_eq!(
+            Format::negotiate(&accept("text/html, */*;q=0.8")).unwrap(),
+            Format::Json
+        );
+    }
+
+    #[test]
+    fn cbor_wins_by_quality_then_order() {
+        assert_eq!(
+            Format::negotiate(&accept("application/cbor")).unwrap(),
+            Format::Cbor
+        );
+        assert_eq!(
+            Format::negotiate(&accept("application/json;q=0.5, application/cbor")).unwrap(),
+            Format::Cbor
+        );
+        assert_eq!(
+            Format::negotiate(&accept("application/json, application/cbor")).unwrap(),
+            Format::Json
+        );
+    }
+
+    #[test]
+    fn unsupported_accept_is_not_acceptable() {
+        for value in ["text/plain", "application/xml", "application/cbor;q=0"] {
+            let err = Format::negotiate(&accept(value)).unwrap_err();
+            assert!(matches!(err, ApiError::NotAcceptable(_)), "{value}");
+        }
+    }
+}
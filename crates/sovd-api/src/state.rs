@@ -3,11 +3,14 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use sovd_conv::DidStore;
 use sovd_core::{DiagnosticBackend, OperationExecution};
 use sovd_uds::config::OutputConfig;
+use tokio::sync::watch;
 
 use crate::auth::{AuthContext, Authorizer};
 use crate::error::ApiError;
@@ -64,6 +67,139 @@ impl OperationExecutionCache {
     }
 }
 
+/// Running IO control profiles (`ramp` / `sequence` executions), keyed
+/// like [`OperationExecutionCache`], so a `DELETE` of the execution can
+/// stop them.
+#[derive(Default)]
+pub struct OutputProfileRuns {
+    inner: Mutex<HashMap<(String, String, String), watch::Sender<bool>>>,
+}
+
+impl OutputProfileRuns {
+    /// Register a run; the receiver turns `true` once it is cancelled
+    pub fn start(&self, component_id: &str, op_id: &str, exec_id: &str) -> watch::Receiver<bool> {
+        let (cancel_tx, cancel_rx) = watch::channel(false);
+        self.inner.lock().insert(
+            (
+                component_id.to_string(),
+                op_id.to_string(),
+                exec_id.to_string(),
+            ),
+            cancel_tx,
+        );
+        cancel_rx
+    }
+
+    /// Cancel a run. `false` when no such run is in progress.
+    pub fn cancel(&self, component_id: &str, op_id: &str, exec_id: &str) -> bool {
+        self.inner
+            .lock()
+            .remove(&(
+                component_id.to_string(),
+                op_id.to_string(),
+                exec_id.to_string(),
+            ))
+            .is_some_and(|cancel_tx| cancel_tx.send(true).is_ok())
+    }
+
+    /// Forget a run that has ended
+    pub fn finish(&self, component_id: &str, op_id: &str, exec_id: &str) {
+        self.inner.lock().remove(&(
+            component_id.to_string(),
+            op_id.to_string(),
+            exec_id.to_string(),
+        ));
+    }
+}
+
+/// Read cache tuning (`[data_cache]` in the sovdd config).
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct ReadCacheConfig {
+    /// How long a cached read is served before the ECU is asked again.
+    pub ttl_secs: u64,
+}
+
+impl Default for ReadCacheConfig {
+    fn default() -> Self {
+        Self { ttl_secs: 300 }
+    }
+}
+
+/// Raw bytes of recent reads of `cacheable` DIDs keyed by
+/// `(component_id, did)`, served instead of a bus read until the TTL runs
+/// out.  A write to the DID drops its entry; a session change or reset
+/// drops the component's.  Each entry also carries the backend's
+/// [`session_epoch`](DiagnosticBackend::session_epoch) at the read, so a
+/// session the ECU dropped on its own (S3 lapse, failed keepalive, reset
+/// through another route) invalidates it too.
+#[derive(Debug)]
+pub struct ReadCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<(String, u16), CachedRead>>,
+}
+
+#[derive(Debug)]
+struct CachedRead {
+    read_at: Instant,
+    epoch: Option<u64>,
+    bytes: Vec<u8>,
+}
+
+impl Default for ReadCache {
+    fn default() -> Self {
+        Self::new(&ReadCacheConfig::default())
+    }
+}
+
+impl ReadCache {
+    pub fn new(config: &ReadCacheConfig) -> Self {
+        Self {
+            ttl: Duration::from_secs(config.ttl_secs),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Cached bytes of `did` on `component_id`, unless expired or read in
+    /// another session epoch than `epoch`.
+    pub fn get(&self, component_id: &str, did: u16, epoch: Option<u64>) -> Option<Vec<u8>> {
+        let mut entries = self.entries.lock();
+        let key = (component_id.to_string(), did);
+        match entries.get(&key) {
+            Some(entry) if entry.read_at.elapsed() < self.ttl && entry.epoch == epoch => {
+                Some(entry.bytes.clone())
+            }
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Remember `bytes` of `did`, read in session epoch `epoch`.
+    pub fn insert(&self, component_id: &str, did: u16, epoch: Option<u64>, bytes: Vec<u8>) {
+        self.entries.lock().insert(
+            (component_id.to_string(), did),
+            CachedRead {
+                read_at: Instant::now(),
+                epoch,
+                bytes,
+            },
+        );
+    }
+
+    /// Drop the entry for one DID (after a write).
+    pub fn invalidate(&self, component_id: &str, did: u16) {
+        self.entries.lock().remove(&(component_id.to_string(), did));
+    }
+
+    /// Drop every entry of a component (after a session change or reset).
+    pub fn invalidate_component(&self, component_id: &str) {
+        self.entries.lock().retain(|(c, _), _| c != component_id);
+    }
+}
+
 /// Per-component log configuration — Spec §7.21 `logs/config`.
 ///
 /// Initialised on first read with server defaults; mutated by
@@ -78,6 +214,12 @@ pub struct LogConfigStore(pub Arc<Mutex<HashMap<String, serde_json::Value>>>);
 #[derive(Clone, Debug, Default)]
 pub struct ClearDataStatusStore(pub Arc<Mutex<HashMap<String, String>>>);
 
+/// Per-component time of the last health check the backend passed, for
+/// `GET /vehicle/v1/health/backends`; a gateway's ECUs are keyed
+/// `gateway/ecu`.  Held in memory only.
+#[derive(Clone, Debug, Default)]
+pub struct BackendHealthStore(pub Arc<Mutex<HashMap<String, DateTime<Utc>>>>);
+
 /// Per-update tracking for the spec-compliant `/updates` collection.
 ///
 /// F.D2 adds a thin wire alias over the existing flash backend; the
@@ -132,6 +274,9 @@ pub struct UpdatesEntry {
     pub reset_kind: Option<sovd_core::ResetKind>,
     /// Backend's transfer_id, populated once `start_flash` runs.
     pub transfer_id: Option<String>,
+    /// Registered with `x-sumo-dry-run`: prepare runs
+    /// `start_flash_dry_run` and execute is refused.
+    pub dry_run: bool,
     /// Abort handle for the in-flight prepare/execute task, so
     /// `DELETE /updates/{id}` can cancel it.  `None` when no task
     /// is running; cleared when a task completes.
@@ -142,6 +287,9 @@ pub struct UpdatesEntry {
     /// `Some` when `PUT /execute?x-sumo-control=orchestrated` runs
     /// and the task pauses at `substate=awaiting-verdict`.
     pub verdict_tx: Option<tokio::sync::watch::Sender<Verdict>>,
+    /// Parts arriving in `Content-Range` chunks that are not complete yet.
+    /// Each becomes an [`UpdatePart`] once its last byte is in.
+    pub partial_parts: Vec<PartialPart>,
 }
 
 /// Subset of `GenericError` (sovd-core) carried in `UpdatesEntry.error`.
@@ -164,6 +312,24 @@ pub struct UpdatePart {
     pub file_id: String,
 }
 
+/// A part uploaded in `Content-Range` chunks, spooled to a temp file until
+/// the declared size is in
+#[derive(Clone, Debug)]
+pub struct PartialPart {
+    pub part_id: String,
+    /// Declared full size (the `/total` of `Content-Range`)
+    pub total: u64,
+    /// Bytes received so far; the next chunk must start here
+    pub received: u64,
+    /// Temp file holding bytes `0..received`
+    pub path: std::path::PathBuf,
+    /// A chunk is being written; a concurrent one is refused
+    pub writing: bool,
+    /// When the part last took a chunk; see
+    /// [`UpdatesConfig::partial_part_ttl`]
+    pub last_chunk_at: std::time::Instant,
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum UpdateState {
     #[default]
@@ -260,14 +426,179 @@ pub struct UpdatesConfig {
     /// Time the execute task will wait for an `x-sumo-commit` or
     /// `x-sumo-rollback` verdict before timing out.
     pub orchestrated_watchdog: std::time::Duration,
+    /// Time a `Content-Range` upload may go without a chunk before its
+    /// spool file is deleted; the part then restarts at byte 0.
+    pub partial_part_ttl: std::time::Duration,
 }
 
 impl Default for UpdatesConfig {
     fn default() -> Self {
         Self {
             orchestrated_watchdog: std::time::Duration::from_secs(600),
+            partial_part_ttl: std::time::Duration::from_secs(3600),
+        }
+    }
+}
+
+/// Server-side caps on cyclic subscriptions (`[subscriptions]` in the
+/// sovdd config), enforced when a subscription is created or re-timed.
+/// `None` leaves a dimension unlimited.
+///
+/// A subscription names exactly one resource (ISO 17978-3 §7.10), so the
+/// number of parameters a component streams is bounded by
+/// `max_subscriptions_per_component`.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct SubscriptionLimits {
+    /// Highest accepted polling rate, compared against the interval's
+    /// concrete rate (`fast` = 20 Hz, `normal` = 5 Hz, `slow` = 2 Hz).
+    pub max_rate_hz: Option<u32>,
+    /// Active subscriptions (and therefore streamed parameters) per component.
+    pub max_subscriptions_per_component: Option<usize>,
+    /// Per-component allowlist of parameter ids eligible for streaming
+    /// (`[subscriptions.allowed_parameters]`).  A component without an
+    /// entry streams anything GET-able; one with an entry rejects other
+    /// parameters with 403.
+    pub allowed_parameters: HashMap<String, Vec<String>>,
+    /// Parameter id → unit its streamed values are converted to
+    /// (`[subscriptions.normalize_units]`), so one quantity reported in
+    /// `kPa` by one ECU and `bar` by another streams in a single unit.
+    /// Applies on every component; the source unit is the definition's.
+    pub normalize_units: HashMap<String, String>,
+}
+
+/// Rollup of backend reachability into the overall vehicle status
+/// (`[vehicle_status]` in the sovdd config), by the fraction of backends
+/// whose health check found their target reachable; behind a gateway each
+/// ECU counts as a backend of its own.
+///
+/// `ok` from `ok_ratio` up, `down` at or below `down_ratio`, `degraded`
+/// in between.  The defaults give `ok` only when every backend answers
+/// and `down` only when none does.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct VehicleStatusThresholds {
+    /// Smallest reachable fraction that still counts as `ok`
+    pub ok_ratio: f64,
+    /// Largest reachable fraction that counts as `down`
+    pub down_ratio: f64,
+}
+
+impl Default for VehicleStatusThresholds {
+    fn default() -> Self {
+        Self {
+            ok_ratio: 1.0,
+            down_ratio: 0.0,
+        }
+    }
+}
+
+impl VehicleStatusThresholds {
+    /// `ok`, `degraded` or `down` for `reachable` of `total` backends; a
+    /// vehicle without backends is `down`
+    pub fn rollup(&self, reachable: usize, total: usize) -> &'static str {
+        if total == 0 {
+            return "down";
+        }
+        let ratio = reachable as f64 / total as f64;
+        if ratio >= self.ok_ratio {
+            "ok"
+        } else if ratio <= self.down_ratio {
+            "down"
+        } else {
+            "degraded"
+        }
+    }
+}
+
+impl SubscriptionLimits {
+    /// Whether `resource` may be streamed from `component_id`.  An entry
+    /// matches by id, or by resolving to the same DID (so `F40C` is
+    /// allowed when `engine_rpm` is listed, and vice versa).
+    pub fn allows(&self, state: &AppState, component_id: &str, resource: &str) -> bool {
+        let Some(allowed) = self.allowed_parameters.get(component_id) else {
+            return true;
+        };
+        let did = state.resolve_did(component_id, resource);
+        allowed.iter().any(|param| {
+            param == resource || (did.is_some() && state.resolve_did(component_id, param) == did)
+        })
+    }
+}
+
+/// Two DIDs claimed the same semantic name for one component.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("data alias '{name}' on component '{component_id}' maps to both 0x{existing:04X} and 0x{conflicting:04X}")]
+pub struct DataAliasCollision {
+    pub component_id: String,
+    pub name: String,
+    pub existing: u16,
+    pub conflicting: u16,
+}
+
+/// Config-driven semantic name → DID table, scoped per component.
+///
+/// Consulted by the data handlers before the [`DidStore`] name index, so
+/// one name (e.g. `engine_rpm`) can point at a different DID on each ECU
+/// without touching the shared `DidDefinition.id`.
+#[derive(Clone, Debug, Default)]
+pub struct DataAliases {
+    // component_id -> name -> DID
+    by_component: HashMap<String, HashMap<String, u16>>,
+}
+
+impl DataAliases {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `name` → `did` for `component_id`.  Re-registering the same
+    /// pair is a no-op; claiming the name for a different DID is a collision.
+    pub fn insert(
+        &mut self,
+        component_id: &str,
+        name: &str,
+        did: u16,
+    ) -> Result<(), DataAliasCollision> {
+        let names = self
+            .by_component
+            .entry(component_id.to_string())
+            .or_default();
+        match names.get(name) {
+            Some(&existing) if existing != did => Err(DataAliasCollision {
+                component_id: component_id.to_string(),
+                name: name.to_string(),
+                existing,
+                conflicting: did,
+            }),
+            Some(_) => Ok(()),
+            None => {
+                names.insert(name.to_string(), did);
+                Ok(())
+            }
         }
     }
+
+    /// Resolve an alias for a component.
+    pub fn resolve(&self, component_id: &str, name: &str) -> Option<u16> {
+        self.by_component.get(component_id)?.get(name).copied()
+    }
+
+    /// Reverse lookup: the alias a component uses for `did`, if any.
+    /// When several names map to the same DID the lexically first wins so
+    /// listings stay stable.
+    pub fn name_for(&self, component_id: &str, did: u16) -> Option<&str> {
+        self.by_component
+            .get(component_id)?
+            .iter()
+            .filter(|(_, &d)| d == did)
+            .map(|(name, _)| name.as_str())
+            .min()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_component.values().all(|m| m.is_empty())
+    }
 }
 
 /// Application state shared across all handlers
@@ -277,20 +608,32 @@ pub struct AppState {
     backends: Arc<HashMap<String, Arc<dyn DiagnosticBackend>>>,
     /// DID conversion store (shared across all backends)
     did_store: Arc<DidStore>,
+    /// Per-component semantic name → DID aliases (checked before `did_store`)
+    data_aliases: Arc<DataAliases>,
     /// Subscription manager
     pub subscription_manager: Arc<SubscriptionManager>,
     /// Output configs per component: component_id -> Vec<OutputConfig>
     output_configs: Arc<HashMap<String, Vec<OutputConfig>>>,
     /// Bounded cache of recent operation executions for `GET ../executions/{id}`.
     pub operation_executions: Arc<OperationExecutionCache>,
+    /// IO control ramps/sequences in progress, for cancellation.
+    pub output_profiles: Arc<OutputProfileRuns>,
     /// Per-component logs/config persistence.
     pub log_config: LogConfigStore,
     /// Per-component clear-data activity status.
     pub clear_data_status: ClearDataStatusStore,
+    /// Per-component last successful health check.
+    pub backend_health: BackendHealthStore,
+    /// Recent reads of `cacheable` DIDs.
+    pub read_cache: Arc<ReadCache>,
     /// Per-update part tracking for the `/updates` collection.
     pub updates: UpdatesStore,
     /// Tunable knobs for the `/updates` lifecycle.
     pub updates_config: Arc<UpdatesConfig>,
+    /// Caps enforced on cyclic-subscription creation.
+    pub subscription_limits: Arc<SubscriptionLimits>,
+    /// Thresholds rolling backend health up into `GET /vehicle/v1/status`.
+    pub vehicle_status: Arc<VehicleStatusThresholds>,
     /// Client→SOVDd authentication context (JWT-bearer slice). Defaults to
     /// disabled (open surface); set via [`AppState::with_auth`].
     auth: Arc<dyn Authorizer>,
@@ -302,13 +645,19 @@ impl AppState {
         Self {
             backends: Arc::new(backends),
             did_store: Arc::new(DidStore::new()),
+            data_aliases: Arc::new(DataAliases::default()),
             subscription_manager: Arc::new(SubscriptionManager::new()),
             output_configs: Arc::new(HashMap::new()),
             operation_executions: Arc::new(OperationExecutionCache::default()),
+            output_profiles: Arc::new(OutputProfileRuns::default()),
             log_config: LogConfigStore::default(),
             clear_data_status: ClearDataStatusStore::default(),
+            backend_health: BackendHealthStore::default(),
+            read_cache: Arc::new(ReadCache::default()),
             updates: UpdatesStore::default(),
             updates_config: Arc::new(UpdatesConfig::default()),
+            subscription_limits: Arc::new(SubscriptionLimits::default()),
+            vehicle_status: Arc::new(VehicleStatusThresholds::default()),
             auth: Arc::new(AuthContext::default()),
         }
     }
@@ -321,13 +670,19 @@ impl AppState {
         Self {
             backends: Arc::new(backends),
             did_store,
+            data_aliases: Arc::new(DataAliases::default()),
             subscription_manager: Arc::new(SubscriptionManager::new()),
             output_configs: Arc::new(HashMap::new()),
             operation_executions: Arc::new(OperationExecutionCache::default()),
+            output_profiles: Arc::new(OutputProfileRuns::default()),
             log_config: LogConfigStore::default(),
             clear_data_status: ClearDataStatusStore::default(),
+            backend_health: BackendHealthStore::default(),
+            read_cache: Arc::new(ReadCache::default()),
             updates: UpdatesStore::default(),
             updates_config: Arc::new(UpdatesConfig::default()),
+            subscription_limits: Arc::new(SubscriptionLimits::default()),
+            vehicle_status: Arc::new(VehicleStatusThresholds::default()),
             auth: Arc::new(AuthContext::default()),
         }
     }
@@ -341,13 +696,19 @@ impl AppState {
         Self {
             backends: Arc::new(backends),
             did_store,
+            data_aliases: Arc::new(DataAliases::default()),
             subscription_manager: Arc::new(SubscriptionManager::new()),
             output_configs: Arc::new(output_configs),
             operation_executions: Arc::new(OperationExecutionCache::default()),
+            output_profiles: Arc::new(OutputProfileRuns::default()),
             log_config: LogConfigStore::default(),
             clear_data_status: ClearDataStatusStore::default(),
+            backend_health: BackendHealthStore::default(),
+            read_cache: Arc::new(ReadCache::default()),
             updates: UpdatesStore::default(),
             updates_config: Arc::new(UpdatesConfig::default()),
+            subscription_limits: Arc::new(SubscriptionLimits::default()),
+            vehicle_status: Arc::new(VehicleStatusThresholds::default()),
             auth: Arc::new(AuthContext::default()),
         }
     }
@@ -359,6 +720,33 @@ impl AppState {
         self
     }
 
+    /// Replace the read cache with one tuned by `config`.  Builder-style
+    /// consume + return.
+    pub fn with_read_cache(mut self, config: ReadCacheConfig) -> Self {
+        self.read_cache = Arc::new(ReadCache::new(&config));
+        self
+    }
+
+    /// Attach cyclic-subscription limits.  Builder-style consume + return.
+    pub fn with_subscription_limits(mut self, limits: SubscriptionLimits) -> Self {
+        self.subscription_limits = Arc::new(limits);
+        self
+    }
+
+    /// Override the vehicle status rollup thresholds.  Builder-style
+    /// consume + return.
+    pub fn with_vehicle_status_thresholds(mut self, thresholds: VehicleStatusThresholds) -> Self {
+        self.vehicle_status = Arc::new(thresholds);
+        self
+    }
+
+    /// Attach the per-component data alias table.  Builder-style consume +
+    /// return.
+    pub fn with_data_aliases(mut self, aliases: DataAliases) -> Self {
+        self.data_aliases = Arc::new(aliases);
+        self
+    }
+
     /// Attach the client-authentication context (JWT-bearer slice).
     /// Builder-style consume + return.
     pub fn with_auth(mut self, auth: Arc<AuthContext>) -> Self {
@@ -409,6 +797,19 @@ impl AppState {
         &self.did_store
     }
 
+    /// Get the data alias table
+    pub fn data_aliases(&self) -> &DataAliases {
+        &self.data_aliases
+    }
+
+    /// Resolve a data resource id for a component: a configured alias wins,
+    /// then the DidStore semantic name, then DID hex.
+    pub fn resolve_did(&self, component_id: &str, param_id: &str) -> Option<u16> {
+        self.data_aliases
+            .resolve(component_id, param_id)
+            .or_else(|| self.did_store.resolve_did(param_id))
+    }
+
     /// Get the DID store Arc (for sharing)
     pub fn did_store_arc(&self) -> Arc<DidStore> {
         self.did_store.clone()
@@ -152,6 +152,8 @@ impl MockBackend {
                 description: None,
                 href: format!("/vehicle/v1/components/{id}"),
                 status: Some("online".to_string()),
+                display_name: None,
+                category: None,
             },
             capabilities: Capabilities::default(),
         }
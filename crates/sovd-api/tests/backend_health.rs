@@ -0,0 +1,133 @@
+//! `GET /vehicle/v1/health/backends` — in-process router tests.
+//!
+//! Two UDS ECUs over the mock transport, probed with TesterPresent:
+//!   * an ECU that answers is reachable, with a latency and a
+//!     `last_success`;
+//!   * an ECU whose link is down is unreachable and, never having answered,
+//!     has no `last_success`;
+//!   * once it went quiet again, it keeps the `last_success` of its last
+//!     answer;
+//!   * behind a gateway, each ECU is probed and reported on its own.
+//!
+//! Drives real `UdsBackend`s over the mock transport (see `common`).
+
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_gateway::GatewayBackend;
+use sovd_uds::transport::mock::MockTransportAdapter;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// `awake` answers; `asleep` has its link down
+async fn server() -> (TestServer, Arc<MockTransportAdapter>) {
+    let (awake, _) = common::uds_ecu(common::ecu_config("awake", "awake"));
+    let (asleep, asleep_mock) = common::uds_ecu(common::ecu_config("asleep", "asleep"));
+    asleep_mock.set_connected(false);
+
+    let server = common::server(vec![("awake", awake as _), ("asleep", asleep as _)]).await;
+    (server, asleep_mock)
+}
+
+/// `gw` with `awake` and `asleep` behind it
+async fn gateway_server() -> TestServer {
+    let (awake, _) = common::uds_ecu(common::ecu_config("awake", "awake"));
+    let (asleep, asleep_mock) = common::uds_ecu(common::ecu_config("asleep", "asleep"));
+    asleep_mock.set_connected(false);
+    let mut gateway = GatewayBackend::new("gw", "Gateway", None);
+    gateway.register_backend(awake);
+    gateway.register_backend(asleep);
+
+    common::server(vec![("gw", Arc::new(gateway))]).await
+}
+
+/// Health items keyed by component
+async fn health(server: &TestServer) -> HashMap<String, serde_json::Value> {
+    let resp = reqwest::get(format!("{}/vehicle/v1/health/backends", server.base_url()))
+        .await
+        .expect("get");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    body["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .map(|item| {
+            (
+                item["component"].as_str().unwrap().to_string(),
+                item.clone(),
+            )
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn answering_ecu_is_reachable() {
+    let (server, _) = server().await;
+
+    let items = health(&server).await;
+
+    let awake = &items["awake"];
+    assert_eq!(awake["reachable"], true, "{awake}");
+    assert!(awake["latency_ms"].is_u64(), "{awake}");
+    assert!(awake["last_success"].is_string(), "{awake}");
+    assert_eq!(awake["href"], "/vehicle/v1/components/awake");
+}
+
+#[tokio::test]
+async fn silent_ecu_is_unreachable() {
+    let (server, _) = server().await;
+
+    let items = health(&server).await;
+
+    let asleep = &items["asleep"];
+    assert_eq!(asleep["reachable"], false, "{asleep}");
+    assert!(asleep["detail"].is_string(), "{asleep}");
+    assert!(asleep.get("last_success").is_none(), "{asleep}");
+}
+
+#[tokio::test]
+async fn last_success_outlives_the_link() {
+    let (server, asleep_mock) = server().await;
+
+    asleep_mock.set_connected(true);
+    let answered = health(&server).await["asleep"].clone();
+    assert_eq!(answered["reachable"], true, "{answered}");
+
+    asleep_mock.set_connected(false);
+    let quiet = health(&server).await["asleep"].clone();
+    assert_eq!(quiet["reachable"], false, "{quiet}");
+    assert_eq!(quiet["last_success"], answered["last_success"]);
+}
+
+#[tokio::test]
+async fn gateway_reports_each_ecu_behind_it() {
+    let server = gateway_server().await;
+
+    let gw = health(&server).await["gw"].clone();
+    assert_eq!(gw["reachable"], true, "{gw}");
+    assert_eq!(gw["detail"], "1 of 2 backends unreachable", "{gw}");
+
+    let children = gw["children"].as_array().expect("children");
+    let ids: Vec<&str> = children
+        .iter()
+        .map(|c| c["component"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, ["asleep", "awake"]);
+
+    let (asleep, awake) = (&children[0], &children[1]);
+    assert_eq!(asleep["reachable"], false, "{asleep}");
+    assert!(asleep.get("last_success").is_none(), "{asleep}");
+    assert_eq!(asleep["href"], "/vehicle/v1/components/gw/apps/asleep");
+    assert_eq!(awake["reachable"], true, "{awake}");
+    assert!(awake["last_success"].is_string(), "{awake}");
+}
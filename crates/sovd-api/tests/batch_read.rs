@@ -0,0 +1,108 @@
+//! `GET .../data?ids=a,b,c` batch reads — in-process router tests.
+//!
+//! With `?ids=` the data list endpoint reads the listed parameters instead
+//! of listing them:
+//!   * one item per id, in request order, decoded like a single read;
+//!   * an id that cannot be read carries its own `error`, the rest succeed;
+//!   * a UDS ECU configured with `data_read.max_dids_per_request` gets a
+//!     single multi-DID ReadDataByIdentifier (0x22).
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors `memory.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_conv::types::DataType;
+use sovd_conv::{DidDefinition, DidStore};
+use sovd_uds::config::DataReadConfig;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::{UdsBackend, UdsBackendConfig};
+
+use sovd_api::AppState;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// UDS ECU packing up to 8 DIDs per 0x22, answering `22 F40C F405` at once
+async fn server() -> (TestServer, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(
+        vec![0x22, 0xF4, 0x0C, 0xF4, 0x05],
+        vec![0x62, 0xF4, 0x0C, 0x0B, 0xB8, 0xF4, 0x05, 0x5A],
+    );
+    let config = UdsBackendConfig {
+        data_read: DataReadConfig {
+            max_dids_per_request: Some(8),
+        },
+        ..common::ecu_config("ecu", "Batching ECU")
+    };
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+
+    let store = DidStore::new();
+    store.register(
+        0xF40C,
+        DidDefinition::scaled(DataType::Uint16, 0.25, 0.0)
+            .with_id("engine_rpm")
+            .with_unit("rpm"),
+    );
+
+    let backends = common::to_map(vec![("ecu", Arc::new(backend))]);
+    let state = AppState::with_did_store(backends, Arc::new(store));
+    let server = common::serve(state).await;
+    (server, mock)
+}
+
+async fn get(server: &TestServer, path: &str) -> serde_json::Value {
+    let url = format!("{}{}", server.base_url(), path);
+    let resp = reqwest::get(url).await.expect("get data");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    resp.json().await.unwrap()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn ids_are_read_in_one_request() {
+    let (server, mock) = server().await;
+    let body = get(
+        &server,
+        "/vehicle/v1/components/ecu/data?ids=engine_rpm,F405,nope",
+    )
+    .await;
+
+    let items = body["items"].as_array().expect("items");
+    assert_eq!(items.len(), 3, "{body}");
+
+    assert_eq!(items[0]["id"], "engine_rpm", "{body}");
+    assert_eq!(items[0]["value"].as_f64(), Some(750.0), "{body}");
+    assert_eq!(items[0]["unit"], "rpm", "{body}");
+    assert_eq!(items[0]["converted"], true, "{body}");
+
+    assert_eq!(items[1]["did"], "F405", "{body}");
+    assert_eq!(items[1]["raw"], "5a", "{body}");
+
+    // Not a parameter nor a DID: its own error, the others still read
+    assert_eq!(items[2]["id"], "nope", "{body}");
+    assert!(items[2]["error"]["error_code"].is_string(), "{body}");
+    assert!(items[2].get("value").is_none(), "{body}");
+
+    assert_eq!(
+        mock.sent_requests(),
+        vec![vec![0x22, 0xF4, 0x0C, 0xF4, 0x05]]
+    );
+}
+
+#[tokio::test]
+async fn without_ids_the_data_is_listed() {
+    let (server, mock) = server().await;
+    let body = get(&server, "/vehicle/v1/components/ecu/data").await;
+
+    assert_eq!(body["count"], 1, "{body}");
+    assert_eq!(body["items"][0]["id"], "engine_rpm", "{body}");
+    assert!(mock.sent_requests().is_empty());
+}
@@ -0,0 +1,152 @@
+//! `Accept: application/cbor` on the data endpoints — in-process router tests.
+//!
+//! The data read and list handlers serialize their usual models as CBOR
+//! when asked to:
+//!   * single reads, the parameter list and `?ids=` batch reads answer
+//!     `Content-Type: application/cbor` with the same fields as the JSON;
+//!   * an `Accept` naming neither JSON nor CBOR is refused with 406;
+//!   * other resources (here the cyclic-subscription details) stay JSON.
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors `batch_read.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_conv::types::DataType;
+use sovd_conv::{DidDefinition, DidStore};
+use sovd_uds::UdsBackend;
+
+use sovd_api::AppState;
+
+const CBOR: &str = "application/cbor";
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// UDS ECU over the mock transport (`22 F40C` answers 3000 rpm raw)
+async fn server() -> TestServer {
+    let mock = common::mock();
+    let config = common::ecu_config("ecu", "CBOR ECU");
+    let backend = UdsBackend::with_transport(config, mock).expect("uds backend");
+
+    let store = DidStore::new();
+    store.register(
+        0xF40C,
+        DidDefinition::scaled(DataType::Uint16, 0.25, 0.0)
+            .with_id("engine_rpm")
+            .with_unit("rpm"),
+    );
+
+    let backends = common::to_map(vec![("ecu", Arc::new(backend))]);
+    let state = AppState::with_did_store(backends, Arc::new(store));
+    common::serve(state).await
+}
+
+async fn get(server: &TestServer, path: &str, accept: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!("{}{}", server.base_url(), path))
+        .header(reqwest::header::ACCEPT, accept)
+        .send()
+        .await
+        .expect("get")
+}
+
+/// Assert a CBOR 200 and decode its body
+async fn cbor_body(resp: reqwest::Response) -> serde_json::Value {
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    assert_eq!(resp.headers()[reqwest::header::CONTENT_TYPE], CBOR);
+    let bytes = resp.bytes().await.unwrap();
+    ciborium::from_reader(bytes.as_ref()).expect("valid CBOR")
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn single_read_is_served_as_cbor() {
+    let server = server().await;
+    let resp = get(&server, "/vehicle/v1/components/ecu/data/engine_rpm", CBOR).await;
+    let body = cbor_body(resp).await;
+
+    assert_eq!(body["id"], "engine_rpm", "{body}");
+    assert_eq!(body["value"].as_f64(), Some(750.0), "{body}");
+    assert_eq!(body["unit"], "rpm", "{body}");
+}
+
+#[tokio::test]
+async fn list_and_batch_reads_are_served_as_cbor() {
+    let server = server().await;
+
+    let list = cbor_body(get(&server, "/vehicle/v1/components/ecu/data", CBOR).await).await;
+    let ids: Vec<&str> = list["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .filter_map(|item| item["id"].as_str())
+        .collect();
+    assert!(ids.contains(&"engine_rpm"), "{list}");
+
+    let batch = cbor_body(
+        get(
+            &server,
+            "/vehicle/v1/components/ecu/data?ids=engine_rpm",
+            "application/json;q=0.5, application/cbor",
+        )
+        .await,
+    )
+    .await;
+    assert_eq!(batch["items"][0]["value"].as_f64(), Some(750.0), "{batch}");
+}
+
+#[tokio::test]
+async fn json_stays_the_default() {
+    let server = server().await;
+    let resp = get(&server, "/vehicle/v1/components/ecu/data/engine_rpm", "*/*").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["value"].as_f64(), Some(750.0), "{body}");
+}
+
+#[tokio::test]
+async fn unsupported_accept_is_not_acceptable() {
+    let server = server().await;
+    let resp = get(
+        &server,
+        "/vehicle/v1/components/ecu/data/engine_rpm",
+        "text/plain",
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::NOT_ACCEPTABLE);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["vendor_code"], "not-acceptable", "{body}");
+}
+
+#[tokio::test]
+async fn other_resources_stay_json() {
+    let server = server().await;
+    let created: serde_json::Value = reqwest::Client::new()
+        .post(format!(
+            "{}/vehicle/v1/components/ecu/cyclic-subscriptions",
+            server.base_url()
+        ))
+        .json(&serde_json::json!({ "resource": "engine_rpm", "interval": "slow" }))
+        .send()
+        .await
+        .expect("create subscription")
+        .json()
+        .await
+        .unwrap();
+
+    let path = format!(
+        "/vehicle/v1/components/ecu/cyclic-subscriptions/{}",
+        created["subscription_id"].as_str().unwrap()
+    );
+    let resp = get(&server, &path, CBOR).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["resource"], "engine_rpm", "{body}");
+}
@@ -0,0 +1,327 @@
+//! `Content-Range` chunked part uploads — in-process router tests.
+//!
+//! A 2000-byte image uploaded to `/updates/{id}/bulk-data/manifest` in
+//! chunks:
+//!   * each incomplete chunk is a 202 with `Upload-Offset`, which `HEAD`
+//!     reports too, and prepare is refused until the part is complete;
+//!   * the last chunk is a 201 with the sha256 of the whole image, which
+//!     then prepares like a single-PUT upload;
+//!   * a chunk that does not continue the part is a 409, a body that does
+//!     not match its range a 400, and neither moves the offset;
+//!   * a chunk whose client disconnects mid-body does not block the next;
+//!   * a part no chunk reaches for `partial_part_ttl` is dropped, and its
+//!     spool file deleted even if no further request arrives;
+//!   * `FlashClient::upload_file` resumes a part another client started.
+//!
+//! Drives a real `UdsBackend`; mirrors `flash_dry_run.rs`.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sovd_client::testing::TestServer;
+use sovd_uds::UdsBackend;
+
+use sovd_api::{state::UpdatesConfig, AppState};
+
+/// Firmware image: 2000 bytes, not uniform so misplaced chunks show
+fn image() -> Vec<u8> {
+    (0..2000u32).map(|i| (i % 251) as u8).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+async fn server() -> TestServer {
+    server_with(UpdatesConfig::default()).await
+}
+
+async fn server_with(updates_config: UpdatesConfig) -> TestServer {
+    common::serve(state_with(updates_config)).await
+}
+
+fn state_with(updates_config: UpdatesConfig) -> AppState {
+    let mock = common::mock();
+    // maxNumberOfBlockLength 0x0102
+    mock.add_response(vec![0x34], vec![0x74, 0x20, 0x01, 0x02]);
+    let config = common::ecu_config("ecu", "ecu");
+    let backend = UdsBackend::with_transport(config, mock).expect("uds backend");
+    let backends = common::to_map(vec![("ecu", Arc::new(backend))]);
+    AppState::new(backends).with_updates_config(updates_config)
+}
+
+fn url(server: &TestServer, path: &str) -> String {
+    format!(
+        "{}/vehicle/v1/components/ecu/updates{path}",
+        server.base_url()
+    )
+}
+
+/// Register a dry-run update (prepare reports the bytes it planned)
+async fn register(server: &TestServer) -> String {
+    let resp = reqwest::Client::new()
+        .post(url(server, ""))
+        .json(&serde_json::json!({ "x-sumo-dry-run": true }))
+        .send()
+        .await
+        .expect("register");
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+    let body: Value = resp.json().await.unwrap();
+    body["update_id"].as_str().unwrap().to_string()
+}
+
+/// PUT bytes `start..end` of the image as one chunk
+async fn put_chunk(server: &TestServer, id: &str, start: usize, end: usize) -> reqwest::Response {
+    let image = image();
+    reqwest::Client::new()
+        .put(url(server, &format!("/{id}/bulk-data/manifest")))
+        .header("content-type", "application/octet-stream")
+        .header(
+            "content-range",
+            format!("bytes {start}-{}/{}", end - 1, image.len()),
+        )
+        .body(image[start..end].to_vec())
+        .send()
+        .await
+        .expect("chunk")
+}
+
+async fn head_offset(server: &TestServer, id: &str) -> (u64, u64) {
+    let resp = reqwest::Client::new()
+        .head(url(server, &format!("/{id}/bulk-data/manifest")))
+        .send()
+        .await
+        .expect("head");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    (
+        header_u64(&resp, "upload-offset"),
+        header_u64(&resp, "upload-length"),
+    )
+}
+
+fn header_u64(resp: &reqwest::Response, name: &str) -> u64 {
+    resp.headers()[name].to_str().unwrap().parse().unwrap()
+}
+
+async fn put_prepare(server: &TestServer, id: &str) -> reqwest::StatusCode {
+    reqwest::Client::new()
+        .put(url(server, &format!("/{id}/prepare")))
+        .send()
+        .await
+        .expect("prepare")
+        .status()
+}
+
+async fn wait_finished(server: &TestServer, id: &str) -> Value {
+    for _ in 0..200 {
+        let status: Value = reqwest::get(url(server, &format!("/{id}/status")))
+            .await
+            .expect("status")
+            .json()
+            .await
+            .unwrap();
+        if matches!(status["status"].as_str(), Some("completed" | "failed")) {
+            return status;
+        }
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+    panic!("prepare never finished");
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn chunks_assemble_the_part() {
+    let server = server().await;
+    let id = register(&server).await;
+
+    let resp = put_chunk(&server, &id, 0, 1000).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::ACCEPTED);
+    assert_eq!(header_u64(&resp, "upload-offset"), 1000);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["received"], 1000);
+    assert_eq!(body["size"], 2000);
+    assert_eq!(head_offset(&server, &id).await, (1000, 2000));
+    assert_eq!(
+        put_prepare(&server, &id).await,
+        reqwest::StatusCode::CONFLICT
+    );
+
+    let resp = put_chunk(&server, &id, 1000, 2000).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["size"], 2000);
+    assert_eq!(body["sha256"], hex::encode(Sha256::digest(image())));
+    assert_eq!(head_offset(&server, &id).await, (2000, 2000));
+
+    assert_eq!(
+        put_prepare(&server, &id).await,
+        reqwest::StatusCode::ACCEPTED
+    );
+    let status = wait_finished(&server, &id).await;
+    assert_eq!(status["status"], "completed", "{status}");
+    assert_eq!(status["x-sumo-flash-summary"]["plan"]["bytes_total"], 2000);
+}
+
+#[tokio::test]
+async fn out_of_order_and_short_chunks_are_refused() {
+    let server = server().await;
+    let id = register(&server).await;
+
+    // Nothing to continue yet
+    let resp = put_chunk(&server, &id, 500, 1000).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CONFLICT);
+
+    assert_eq!(
+        put_chunk(&server, &id, 0, 500).await.status(),
+        reqwest::StatusCode::ACCEPTED
+    );
+    // Skips bytes 500..700
+    let resp = put_chunk(&server, &id, 700, 1000).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CONFLICT);
+
+    // Range announces 500 bytes, body carries 10
+    let resp = reqwest::Client::new()
+        .put(url(&server, &format!("/{id}/bulk-data/manifest")))
+        .header("content-range", "bytes 500-999/2000")
+        .body(vec![0u8; 10])
+        .send()
+        .await
+        .expect("chunk");
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    assert_eq!(head_offset(&server, &id).await, (500, 2000));
+    let resp = put_chunk(&server, &id, 500, 2000).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["sha256"], hex::encode(Sha256::digest(image())));
+}
+
+#[tokio::test]
+async fn disconnect_mid_chunk_frees_the_part() {
+    let server = server().await;
+    let id = register(&server).await;
+
+    // A chunk announcing 1000 bytes whose client sends 100 and stalls
+    let (tx, rx) = futures::channel::mpsc::unbounded::<Result<Vec<u8>, std::io::Error>>();
+    tx.unbounded_send(Ok(image()[..100].to_vec())).unwrap();
+    let stalled = tokio::spawn(
+        reqwest::Client::new()
+            .put(url(&server, &format!("/{id}/bulk-data/manifest")))
+            .header("content-range", "bytes 0-999/2000")
+            .header("content-length", "1000")
+            .body(reqwest::Body::wrap_stream(rx))
+            .send(),
+    );
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(
+        put_chunk(&server, &id, 0, 1000).await.status(),
+        reqwest::StatusCode::CONFLICT
+    );
+
+    // The client goes away; the part takes chunks again
+    stalled.abort();
+    drop(tx);
+    let mut status = reqwest::StatusCode::CONFLICT;
+    for _ in 0..40 {
+        status = put_chunk(&server, &id, 0, 1000).await.status();
+        if status != reqwest::StatusCode::CONFLICT {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+    assert_eq!(status, reqwest::StatusCode::ACCEPTED);
+    assert_eq!(head_offset(&server, &id).await, (1000, 2000));
+}
+
+#[tokio::test]
+async fn abandoned_part_expires() {
+    let server = server_with(UpdatesConfig {
+        partial_part_ttl: Duration::from_millis(100),
+        ..Default::default()
+    })
+    .await;
+    let id = register(&server).await;
+    assert_eq!(
+        put_chunk(&server, &id, 0, 500).await.status(),
+        reqwest::StatusCode::ACCEPTED
+    );
+    assert_eq!(head_offset(&server, &id).await, (500, 2000));
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let resp = reqwest::Client::new()
+        .head(url(&server, &format!("/{id}/bulk-data/manifest")))
+        .send()
+        .await
+        .expect("head");
+    assert_eq!(resp.status(), reqwest::StatusCode::NOT_FOUND);
+    // Nothing left to continue; the part starts over
+    assert_eq!(
+        put_chunk(&server, &id, 500, 1000).await.status(),
+        reqwest::StatusCode::CONFLICT
+    );
+    assert_eq!(
+        put_chunk(&server, &id, 0, 2000).await.status(),
+        reqwest::StatusCode::CREATED
+    );
+}
+
+#[tokio::test]
+async fn abandoned_spool_file_is_deleted_without_further_requests() {
+    let state = state_with(UpdatesConfig {
+        partial_part_ttl: Duration::from_millis(100),
+        ..Default::default()
+    });
+    let updates = state.updates.clone();
+    let server = common::serve(state).await;
+    let id = register(&server).await;
+    assert_eq!(
+        put_chunk(&server, &id, 0, 500).await.status(),
+        reqwest::StatusCode::ACCEPTED
+    );
+    let spool = updates.0.lock()[&id].partial_parts[0].path.clone();
+    assert!(spool.exists());
+
+    // No request reaches the server from here on
+    tokio::time::sleep(Duration::from_millis(400)).await;
+
+    assert!(!spool.exists(), "{}", spool.display());
+    assert!(updates.0.lock()[&id].partial_parts.is_empty());
+}
+
+#[tokio::test]
+async fn flash_client_resumes_a_started_part() {
+    let server = server().await;
+    let id = register(&server).await;
+    // Another client got the first 600 bytes in before it went away
+    assert_eq!(
+        put_chunk(&server, &id, 0, 600).await.status(),
+        reqwest::StatusCode::ACCEPTED
+    );
+    let path = std::env::temp_dir().join(format!("chunked-upload-{}.bin", uuid::Uuid::new_v4()));
+    std::fs::write(&path, image()).unwrap();
+
+    let client = sovd_client::FlashClient::for_sovd(server.base_url(), "ecu").expect("client");
+    client.attach(&id).await.unwrap();
+    let mut seen = Vec::new();
+    let uploaded = client
+        .upload_file(
+            "manifest",
+            &path,
+            700,
+            Some(|p: &sovd_client::flash::UploadProgress| seen.push(p.bytes_received)),
+        )
+        .await;
+    std::fs::remove_file(&path).unwrap();
+
+    let uploaded = uploaded.expect("upload_file");
+    assert_eq!(uploaded.size, 2000);
+    assert_eq!(uploaded.sha256, hex::encode(Sha256::digest(image())));
+    assert_eq!(seen, [1300, 2000]);
+}
@@ -0,0 +1,182 @@
+//! `?auto_session=true` on `DELETE .../faults` — in-process router tests.
+//!
+//! The ECU only clears DTCs in the extended session:
+//!   * without `auto_session` a clear from the default session is refused
+//!     (409 `precondition-not-fulfilled`; ISO 17978-3 §5.8 has no 412)
+//!     and the session is left alone;
+//!   * with it, the server enters extended, clears, switches back to the
+//!     prior session and reports the switch as `x-sumo-session-escalation`;
+//!   * if the switch itself fails, the original refusal is returned.
+//!
+//! Mirrors the `TestServer` pattern from `on_change_subscription.rs`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use sovd_client::testing::TestServer;
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, ClearFaultsResult, DataValue, DiagnosticBackend,
+    EntityInfo, FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+    SessionMode,
+};
+
+use sovd_api::{create_router, AppState};
+
+// ---------------------------------------------------------------------------
+// Mock backend
+// ---------------------------------------------------------------------------
+
+struct EcuBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    session: Mutex<String>,
+    /// Sessions entered via `set_session_mode`, in order
+    switches: Mutex<Vec<String>>,
+    /// Refuse every session change
+    locked: bool,
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for EcuBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn clear_faults(&self, _group: Option<u32>) -> BackendResult<ClearFaultsResult> {
+        if *self.session.lock().unwrap() != "extended" {
+            return Err(BackendError::SessionRequired("extended".to_string()));
+        }
+        Ok(ClearFaultsResult {
+            success: true,
+            cleared_count: 2,
+            message: "cleared".to_string(),
+            verification: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+    async fn get_session_mode(&self) -> BackendResult<SessionMode> {
+        Ok(SessionMode {
+            mode: "session".to_string(),
+            session: self.session.lock().unwrap().clone(),
+            session_id: 0x01,
+            timing: None,
+        })
+    }
+    async fn set_session_mode(&self, session: &str) -> BackendResult<SessionMode> {
+        if self.locked {
+            return Err(BackendError::Protocol(
+                "session change rejected".to_string(),
+            ));
+        }
+        *self.session.lock().unwrap() = session.to_string();
+        self.switches.lock().unwrap().push(session.to_string());
+        self.get_session_mode().await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+async fn server(locked: bool) -> (TestServer, Arc<EcuBackend>) {
+    let backend = Arc::new(EcuBackend {
+        info: EntityInfo {
+            id: "ecu".to_string(),
+            name: "ecu ECU".to_string(),
+            entity_type: "ecu".to_string(),
+            description: None,
+            href: "/vehicle/v1/components/ecu".to_string(),
+            status: Some("online".to_string()),
+            display_name: None,
+            category: None,
+        },
+        capabilities: Capabilities::default(),
+        session: Mutex::new("default".to_string()),
+        switches: Mutex::new(vec![]),
+        locked,
+    });
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu".to_string(), backend.clone());
+    let server = TestServer::start(create_router(AppState::new(backends)))
+        .await
+        .expect("test server");
+    (server, backend)
+}
+
+async fn clear(server: &TestServer, query: &str) -> reqwest::Response {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/faults{query}",
+        server.base_url()
+    );
+    reqwest::Client::new()
+        .delete(url)
+        .send()
+        .await
+        .expect("clear faults")
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn auto_session_clears_from_default_and_restores() {
+    let (server, backend) = server(false).await;
+    let resp = clear(&server, "?auto_session=true").await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(
+        body["x-sumo-session-escalation"],
+        serde_json::json!({ "from": "default", "to": "extended", "restored": true })
+    );
+    assert_eq!(*backend.switches.lock().unwrap(), ["extended", "default"]);
+    assert_eq!(*backend.session.lock().unwrap(), "default");
+}
+
+#[tokio::test]
+async fn clear_without_auto_session_is_refused() {
+    let (server, backend) = server(false).await;
+    let resp = clear(&server, "").await;
+
+    // precondition-not-fulfilled maps to 409, never 412 (see error.rs)
+    assert_eq!(resp.status(), reqwest::StatusCode::CONFLICT);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["error_code"], "precondition-not-fulfilled", "{body}");
+    assert!(backend.switches.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn failed_switch_returns_the_original_refusal() {
+    let (server, _backend) = server(true).await;
+    let resp = clear(&server, "?auto_session=true").await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::CONFLICT);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert!(
+        body["message"]
+            .as_str()
+            .unwrap()
+            .contains("Session change required"),
+        "{body}"
+    );
+}
@@ -0,0 +1,122 @@
+//! Helpers shared by the router tests: a real `UdsBackend` over the mock
+//! transport, a gateway exposing one ECU, and a server over a set of
+//! backends.
+
+// Each test binary uses only some of these
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+use sovd_uds::config::{MockConfig, TransportConfig};
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::{UdsBackend, UdsBackendConfig};
+
+use sovd_api::{create_router, AppState};
+
+/// Mock transport answering without latency
+pub fn mock() -> Arc<MockTransportAdapter> {
+    Arc::new(MockTransportAdapter::new(&MockConfig { latency_ms: 0 }))
+}
+
+/// Config of ECU `id` on the mock transport, everything else default
+pub fn ecu_config(id: &str, name: &str) -> UdsBackendConfig {
+    UdsBackendConfig::new(
+        id,
+        name,
+        TransportConfig::Mock(MockConfig { latency_ms: 0 }),
+    )
+}
+
+/// `UdsBackend` for `config` over a fresh mock transport
+pub fn uds_ecu(config: UdsBackendConfig) -> (Arc<UdsBackend>, Arc<MockTransportAdapter>) {
+    let mock = mock();
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+    (Arc::new(backend), mock)
+}
+
+/// Server over `backends`, keyed by component ID
+pub async fn server(backends: Vec<(&str, Arc<dyn DiagnosticBackend>)>) -> TestServer {
+    serve(AppState::new(to_map(backends))).await
+}
+
+/// Server over `state`
+pub async fn serve(state: AppState) -> TestServer {
+    TestServer::start(create_router(state))
+        .await
+        .expect("test server")
+}
+
+/// `backends` keyed by component ID, as `AppState` takes them
+pub fn to_map(
+    backends: Vec<(&str, Arc<dyn DiagnosticBackend>)>,
+) -> HashMap<String, Arc<dyn DiagnosticBackend>> {
+    backends
+        .into_iter()
+        .map(|(id, backend)| (id.to_string(), backend))
+        .collect()
+}
+
+/// Gateway `gw` exposing `ecu` as its only sub-entity, `ecu`
+pub fn gateway(ecu: Arc<dyn DiagnosticBackend>) -> Arc<dyn DiagnosticBackend> {
+    Arc::new(GatewayBackend {
+        info: EntityInfo {
+            id: "gw".to_string(),
+            name: "Gateway".to_string(),
+            entity_type: "gateway".to_string(),
+            description: None,
+            href: "/vehicle/v1/components/gw".to_string(),
+            status: Some("online".to_string()),
+            display_name: None,
+            category: None,
+        },
+        capabilities: Capabilities::default(),
+        ecu,
+    })
+}
+
+struct GatewayBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    ecu: Arc<dyn DiagnosticBackend>,
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for GatewayBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+    async fn get_sub_entity(&self, id: &str) -> BackendResult<Arc<dyn DiagnosticBackend>> {
+        if id == "ecu" {
+            Ok(self.ecu.clone())
+        } else {
+            Err(BackendError::EntityNotFound(id.to_string()))
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Config-driven semantic name → DID aliases — in-process router tests.
+//!
+//! Covers the per-component alias table on `AppState`:
+//!   * an alias resolves on `GET /data/{alias}` and overrides the DID
+//!     definition's own `id` in the response and in the `GET /data` listing;
+//!   * one alias name points at a different DID on each component;
+//!   * the definition's id / DID hex keep resolving alongside the alias;
+//!   * two DIDs claiming one name for one component is a collision;
+//!   * a semantic id and its hex DID read the same decoded value, and an
+//!     id that resolves to neither is a 404.
+//!
+//! Mirrors the `TestServer` pattern from `data_categories.rs`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_conv::types::DataType;
+use sovd_conv::{DidDefinition, DidStore};
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+
+use sovd_api::{create_router, AppState, DataAliasCollision, DataAliases};
+
+// ---------------------------------------------------------------------------
+// Mock backend
+// ---------------------------------------------------------------------------
+
+/// DidStore-backed ECU serving raw bytes for two speed DIDs.
+struct StoreBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    did_values: HashMap<u16, Vec<u8>>,
+}
+
+impl StoreBackend {
+    fn new(id: &str) -> Self {
+        let mut did_values = HashMap::new();
+        did_values.insert(0xF40C, vec![0x1C, 0x20]); // 7200 * 0.25 = 1800 rpm
+        did_values.insert(0xF40D, vec![0x0F, 0xA0]); // 4000 * 0.25 = 1000 rpm
+        Self {
+            info: EntityInfo {
+                id: id.to_string(),
+                name: format!("{id} ECU"),
+                entity_type: "ecu".to_string(),
+                description: None,
+                href: format!("/vehicle/v1/components/{id}"),
+                status: Some("online".to_string()),
+                display_name: None,
+                category: None,
+            },
+            capabilities: Capabilities::default(),
+            did_values,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for StoreBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![])
+    }
+    async fn read_raw_did(&self, did: u16) -> BackendResult<Vec<u8>> {
+        self.did_values
+            .get(&did)
+            .cloned()
+            .ok_or_else(|| BackendError::ParameterNotFound(format!("DID 0x{did:04X} not found")))
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn store() -> Arc<DidStore> {
+    let store = DidStore::new();
+    store.register(
+        0xF40C,
+        DidDefinition::scaled(DataType::Uint16, 0.25, 0.0)
+            .with_id("engine_rpm")
+            .with_name("Engine RPM")
+            .with_unit("rpm"),
+    );
+    store.register(
+        0xF40D,
+        DidDefinition::scaled(DataType::Uint16, 0.25, 0.0)
+            .with_id("aux_rpm")
+            .with_name("Auxiliary RPM")
+            .with_unit("rpm"),
+    );
+    Arc::new(store)
+}
+
+/// `engine_speed` → F40C on `ecu_a`, → F40D on `ecu_b`.
+fn aliases() -> DataAliases {
+    let mut aliases = DataAliases::new();
+    aliases.insert("ecu_a", "engine_speed", 0xF40C).unwrap();
+    aliases.insert("ecu_b", "engine_speed", 0xF40D).unwrap();
+    aliases
+}
+
+async fn server() -> TestServer {
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu_a".to_string(), Arc::new(StoreBackend::new("ecu_a")));
+    backends.insert("ecu_b".to_string(), Arc::new(StoreBackend::new("ecu_b")));
+    let state = AppState::with_did_store(backends, store()).with_data_aliases(aliases());
+    TestServer::start(create_router(state))
+        .await
+        .expect("test server")
+}
+
+async fn get_json(server: &TestServer, path: &str) -> serde_json::Value {
+    let url = format!("{}{}", server.base_url(), path);
+    let resp = reqwest::Client::new().get(url).send().await.expect("get");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK, "GET {path}");
+    resp.json().await.expect("json")
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn alias_overrides_definition_id_on_read() {
+    let server = server().await;
+    let body = get_json(&server, "/vehicle/v1/components/ecu_a/data/engine_speed").await;
+
+    assert_eq!(body["id"], "engine_speed", "alias is the public id: {body}");
+    assert_eq!(body["did"], "F40C", "{body}");
+    assert_eq!(
+        body["value"], 1800.0,
+        "decoded via F40C's definition: {body}"
+    );
+    assert_eq!(body["unit"], "rpm", "{body}");
+}
+
+#[tokio::test]
+async fn alias_points_at_a_different_did_per_component() {
+    let server = server().await;
+    let body = get_json(&server, "/vehicle/v1/components/ecu_b/data/engine_speed").await;
+
+    assert_eq!(body["id"], "engine_speed", "{body}");
+    assert_eq!(body["did"], "F40D", "{body}");
+    assert_eq!(body["value"], 1000.0, "{body}");
+}
+
+#[tokio::test]
+async fn definition_id_and_hex_still_resolve_under_alias() {
+    let server = server().await;
+    for path in [
+        "/vehicle/v1/components/ecu_a/data/engine_rpm",
+        "/vehicle/v1/components/ecu_a/data/F40C",
+    ] {
+        let body = get_json(&server, path).await;
+        assert_eq!(body["did"], "F40C", "{path}: {body}");
+        // The component's alias stays the canonical id in the response.
+        assert_eq!(body["id"], "engine_speed", "{path}: {body}");
+    }
+}
+
+#[tokio::test]
+async fn semantic_id_and_hex_did_read_the_same_value() {
+    let server = server().await;
+    // ecu_b has no alias on F40C, so its definition id is the public one.
+    let by_id = get_json(&server, "/vehicle/v1/components/ecu_b/data/engine_rpm").await;
+    let by_did = get_json(&server, "/vehicle/v1/components/ecu_b/data/F40C").await;
+
+    assert_eq!(by_id["value"], 1800.0, "{by_id}");
+    assert_eq!(by_id["value"], by_did["value"]);
+    assert_eq!(by_id["id"], by_did["id"]);
+    assert_eq!(by_id["did"], by_did["did"]);
+}
+
+#[tokio::test]
+async fn unresolvable_id_is_not_found() {
+    let server = server().await;
+    for path in [
+        "/vehicle/v1/components/ecu_a/data/no_such_signal",
+        "/vehicle/v1/components/ecu_a/data/F4FF",
+    ] {
+        let url = format!("{}{}", server.base_url(), path);
+        let resp = reqwest::Client::new().get(url).send().await.expect("get");
+        assert_eq!(resp.status(), reqwest::StatusCode::NOT_FOUND, "GET {path}");
+    }
+}
+
+#[tokio::test]
+async fn listing_uses_alias_as_item_id() {
+    let server = server().await;
+    let body = get_json(&server, "/vehicle/v1/components/ecu_a/data").await;
+
+    let items = body["items"].as_array().expect("items");
+    let speed = items
+        .iter()
+        .find(|i| i["did"] == "F40C")
+        .expect("F40C listed");
+    assert_eq!(speed["id"], "engine_speed", "{body}");
+    assert_eq!(
+        speed["href"], "/vehicle/v1/components/ecu_a/data/engine_speed",
+        "{body}"
+    );
+    // F40D has no alias on ecu_a — keeps its definition id.
+    let aux = items
+        .iter()
+        .find(|i| i["did"] == "F40D")
+        .expect("F40D listed");
+    assert_eq!(aux["id"], "aux_rpm", "{body}");
+}
+
+#[test]
+fn two_dids_claiming_one_name_collide() {
+    let mut aliases = DataAliases::new();
+    aliases.insert("ecu_a", "engine_speed", 0xF40C).unwrap();
+    // Same pair again is idempotent.
+    aliases.insert("ecu_a", "engine_speed", 0xF40C).unwrap();
+    // Same name on another component is independent.
+    aliases.insert("ecu_b", "engine_speed", 0xF40D).unwrap();
+
+    let err = aliases
+        .insert("ecu_a", "engine_speed", 0xF40D)
+        .expect_err("collision");
+    assert_eq!(
+        err,
+        DataAliasCollision {
+            component_id: "ecu_a".to_string(),
+            name: "engine_speed".to_string(),
+            existing: 0xF40C,
+            conflicting: 0xF40D,
+        }
+    );
+    // The original mapping survives the rejected insert.
+    assert_eq!(aliases.resolve("ecu_a", "engine_speed"), Some(0xF40C));
+}
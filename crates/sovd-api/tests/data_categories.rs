@@ -53,6 +53,8 @@ impl StoreBackend {
                 description: None,
                 href: format!("/vehicle/v1/components/{id}"),
                 status: Some("online".to_string()),
+                display_name: None,
+                category: None,
             },
             capabilities: Capabilities::default(),
             did_values,
@@ -112,6 +114,8 @@ impl ParamBackend {
                 description: None,
                 href: format!("/vehicle/v1/components/{id}"),
                 status: Some("online".to_string()),
+                display_name: None,
+                category: None,
             },
             capabilities: Capabilities::default(),
         }
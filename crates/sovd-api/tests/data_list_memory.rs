@@ -0,0 +1,126 @@
+//! Dynamic data lists composed from memory — in-process router tests.
+//!
+//! A UDS ECU over the mock transport:
+//!   * `memory_regions` in the define-data request becomes one
+//!     DynamicallyDefineDataIdentifier 0x2C 0x02 with a shared ALFID;
+//!   * reading the data list then decodes the DDID through its registered
+//!     definition;
+//!   * a request with both `source_dids` and `memory_regions`, or neither,
+//!     is a 400 and nothing is sent.
+//!
+//! Drives a real `UdsBackend`; mirrors `flash_dry_run.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use serde_json::Value;
+use sovd_client::testing::TestServer;
+use sovd_conv::types::DataType;
+use sovd_conv::{DidDefinition, DidStore};
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::UdsBackend;
+
+use sovd_api::AppState;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+async fn server() -> (TestServer, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(vec![0x2C, 0x02], vec![0x6C, 0x02, 0xF2, 0x00]);
+    // Two 16-bit counters, 1000 and 42
+    mock.add_response(
+        vec![0x22, 0xF2, 0x00],
+        vec![0x62, 0xF2, 0x00, 0x03, 0xE8, 0x00, 0x2A],
+    );
+    let config = common::ecu_config("ecu", "ecu");
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+    let backends = common::to_map(vec![("ecu", Arc::new(backend))]);
+
+    let store = DidStore::new();
+    store.register(
+        0xF200,
+        DidDefinition::array(DataType::Uint16, 2).with_id("bringup_counters"),
+    );
+    let state = AppState::with_did_store(backends, Arc::new(store));
+    let server = common::serve(state).await;
+    (server, mock)
+}
+
+async fn define(server: &TestServer, body: Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!(
+            "{}/vehicle/v1/components/ecu/operations/define-data/executions",
+            server.base_url()
+        ))
+        .json(&body)
+        .send()
+        .await
+        .expect("define")
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn memory_regions_define_a_readable_ddid() {
+    let (server, mock) = server().await;
+
+    let resp = define(
+        &server,
+        serde_json::json!({
+            "ddid": "0xF200",
+            "memory_regions": [
+                { "address": "0x2048", "size": 2 },
+                { "address": "0x2100", "size": 2 }
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+    let body: Value = resp.json().await.unwrap();
+
+    // ALFID 0x12: 1-byte sizes, 2-byte addresses
+    assert_eq!(
+        mock.sent_requests(),
+        vec![vec![
+            0x2C, 0x02, 0xF2, 0x00, 0x12, 0x20, 0x48, 0x02, 0x21, 0x00, 0x02
+        ]]
+    );
+
+    let read: Value = reqwest::get(format!(
+        "{}{}",
+        server.base_url(),
+        body["href"].as_str().unwrap()
+    ))
+    .await
+    .expect("read")
+    .json()
+    .await
+    .unwrap();
+    assert_eq!(read["raw"], "03e8002a", "{read}");
+    assert_eq!(read["value"], serde_json::json!([1000, 42]), "{read}");
+}
+
+#[tokio::test]
+async fn sources_and_regions_are_exclusive() {
+    let (server, mock) = server().await;
+
+    let both = define(
+        &server,
+        serde_json::json!({
+            "ddid": "0xF200",
+            "source_dids": [{ "did": "0xF190", "position": 1, "size": 4 }],
+            "memory_regions": [{ "address": "0x2048", "size": 2 }]
+        }),
+    )
+    .await;
+    let neither = define(&server, serde_json::json!({ "ddid": "0xF200" })).await;
+
+    assert_eq!(both.status(), reqwest::StatusCode::BAD_REQUEST);
+    assert_eq!(neither.status(), reqwest::StatusCode::BAD_REQUEST);
+    assert!(mock.sent_requests().is_empty());
+}
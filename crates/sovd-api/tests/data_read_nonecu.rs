@@ -35,6 +35,8 @@ impl NonEcuBackend {
                 description: None,
                 href: format!("/vehicle/v1/components/{id}"),
                 status: Some("online".to_string()),
+                display_name: None,
+                category: None,
             },
             capabilities: Capabilities::default(),
         }
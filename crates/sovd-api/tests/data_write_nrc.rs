@@ -53,6 +53,8 @@ impl WriteBackend {
                 description: None,
                 href: format!("/vehicle/v1/components/{id}"),
                 status: Some("online".to_string()),
+                display_name: None,
+                category: None,
             },
             capabilities: Capabilities::default(),
             nrc: AtomicU8::new(0),
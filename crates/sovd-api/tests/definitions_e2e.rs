@@ -50,6 +50,8 @@ impl MockBackend {
                 description: Some("Mock ECU for testing".to_string()),
                 href: format!("/vehicle/v1/components/{}", id),
                 status: Some("online".to_string()),
+                display_name: None,
+                category: None,
             },
             capabilities: Capabilities::default(),
             did_values,
@@ -176,6 +178,17 @@ dids:
     assert_eq!(result.loaded, 2);
 }
 
+#[tokio::test]
+async fn test_definition_schema_endpoint() {
+    let server = create_test_server().await;
+    let client = &server.client;
+
+    let schema = client.get_definition_schema().await.unwrap();
+    assert_eq!(schema, sovd_conv::schema::definition_json_schema());
+    // Not shadowed by the `{did}` route.
+    assert_eq!(schema["title"], "SOVD DID definition file");
+}
+
 #[tokio::test]
 async fn test_list_definitions() {
     let server = create_test_server().await;
@@ -470,6 +483,8 @@ impl AdvancedMockBackend {
                 description: Some("ECU for advanced type tests".to_string()),
                 href: "/vehicle/v1/components/adv_ecu".to_string(),
                 status: Some("online".to_string()),
+                display_name: None,
+                category: None,
             },
             capabilities: Capabilities::default(),
             did_values,
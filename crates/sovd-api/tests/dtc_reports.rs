@@ -0,0 +1,101 @@
+//! Declared DTC reports (`fault_memory.reports`) — in-process router tests.
+//!
+//! The mock ECU implements only reportDTCByStatusMask (0x19 0x02) and
+//! answers the severity report (0x19 0x08) with subFunctionNotSupported:
+//!   * declared as status-mask only, the faults are listed without ever
+//!     asking for the severity report;
+//!   * declared with severity-mask, the refusal falls back to the
+//!     status-mask report and still lists the faults;
+//!   * the component capabilities show the declared reports.
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors
+//! `fault_extended_data.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_uds::config::DtcReport;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::UdsBackend;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// UDS ECU without the severity report, declaring `reports`
+async fn server(reports: Vec<DtcReport>) -> (TestServer, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(vec![0x19, 0x08], vec![0x7F, 0x19, 0x12]);
+    let mut config = common::ecu_config("ecu", "Status-mask ECU");
+    config.fault_memory.reports = reports;
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+
+    let server = common::server(vec![("ecu", Arc::new(backend))]).await;
+    (server, mock)
+}
+
+async fn get_json(server: &TestServer, path: &str) -> serde_json::Value {
+    let resp = reqwest::get(format!("{}{}", server.base_url(), path))
+        .await
+        .expect("get");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    resp.json().await.unwrap()
+}
+
+fn fault_codes(body: &serde_json::Value) -> Vec<&str> {
+    body["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .filter_map(|f| f["code"].as_str())
+        .collect()
+}
+
+fn severity_requests(mock: &MockTransportAdapter) -> usize {
+    mock.sent_requests()
+        .iter()
+        .filter(|r| r[..2] == [0x19, 0x08])
+        .count()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn status_mask_only_ecu_lists_faults_without_severity_report() {
+    let (server, mock) = server(vec![DtcReport::StatusMask]).await;
+
+    let body = get_json(&server, "/vehicle/v1/components/ecu/faults").await;
+
+    assert_eq!(fault_codes(&body).len(), 2, "{body}");
+    assert_eq!(severity_requests(&mock), 0);
+}
+
+#[tokio::test]
+async fn refused_severity_report_still_lists_faults() {
+    let (server, mock) = server(vec![DtcReport::StatusMask, DtcReport::SeverityMask]).await;
+
+    let first = get_json(&server, "/vehicle/v1/components/ecu/faults").await;
+    let second = get_json(&server, "/vehicle/v1/components/ecu/faults").await;
+
+    assert_eq!(fault_codes(&first).len(), 2, "{first}");
+    assert_eq!(fault_codes(&first), fault_codes(&second));
+    // Refused once, then the status-mask report only
+    assert_eq!(severity_requests(&mock), 1);
+}
+
+#[tokio::test]
+async fn capabilities_show_declared_reports() {
+    let (server, _mock) = server(vec![DtcReport::StatusMask]).await;
+
+    let body = get_json(&server, "/vehicle/v1/components/ecu").await;
+
+    assert_eq!(
+        body["capabilities"]["dtc_reports"],
+        serde_json::json!(["status-mask"]),
+        "{body}"
+    );
+}
@@ -0,0 +1,98 @@
+//! `?status=` / `?severity=` DTC bit-name filters — in-process router tests.
+//!
+//! The mock ECU reports three DTCs through the severity report (0x19 0x08):
+//!   * `?status=confirmed,pending` keeps the faults with either bit set;
+//!   * `?severity=check_immediately` keeps the faults the ECU rated so,
+//!     while the spec integer `?severity=1` still filters as before;
+//!   * an unknown bit name is a 400 and nothing is sent.
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors
+//! `dtc_reports.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_uds::config::DtcReport;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::UdsBackend;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+async fn server() -> (TestServer, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(
+        vec![0x19, 0x08],
+        vec![
+            0x59, 0x08, 0xFF, // Header + status availability mask
+            0x80, 0x10, 0x01, 0x01, 0x00, 0x08, // check immediately, confirmed
+            0x20, 0x10, 0x01, 0x02, 0x00, 0x04, // maintenance only, pending
+            0x40, 0x10, 0x01, 0x03, 0x00, 0x01, // next halt, test failed
+        ],
+    );
+    let mut config = common::ecu_config("ecu", "ecu");
+    config.fault_memory.reports = vec![DtcReport::StatusMask, DtcReport::SeverityMask];
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+
+    let server = common::server(vec![("ecu", Arc::new(backend))]).await;
+    (server, mock)
+}
+
+async fn fault_codes(server: &TestServer, query: &str) -> Vec<String> {
+    let resp = reqwest::get(format!(
+        "{}/vehicle/v1/components/ecu/faults?{query}",
+        server.base_url()
+    ))
+    .await
+    .expect("get");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK, "{query}");
+    let body: serde_json::Value = resp.json().await.unwrap();
+    body["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .filter_map(|f| f["code"].as_str().map(str::to_string))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn status_names_match_any_bit() {
+    let (server, _mock) = server().await;
+
+    let codes = fault_codes(&server, "status=confirmed,pending").await;
+
+    assert_eq!(codes, ["P0101", "P0102"]);
+}
+
+#[tokio::test]
+async fn severity_names_and_spec_integer() {
+    let (server, _mock) = server().await;
+
+    let named = fault_codes(&server, "severity=check_immediately").await;
+    let spec = fault_codes(&server, "severity=1").await;
+
+    assert_eq!(named, ["P0101"]);
+    assert_eq!(spec, ["P0101"]);
+}
+
+#[tokio::test]
+async fn unknown_bit_name_is_rejected() {
+    let (server, mock) = server().await;
+
+    let resp = reqwest::get(format!(
+        "{}/vehicle/v1/components/ecu/faults?status=confirmed,stale",
+        server.base_url()
+    ))
+    .await
+    .expect("get");
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+    assert!(mock.sent_requests().is_empty());
+}
@@ -0,0 +1,114 @@
+//! DTC extended data records on `faults/{fault_id}/extended-data` —
+//! in-process router tests.
+//!
+//! The server reads the extended data records of a DTC (UDS 0x19 0x06):
+//!   * all records by default, each with its raw bytes, and configured
+//!     records named and read as counters;
+//!   * `?record=` asks the ECU for a single record;
+//!   * a record number that is not a byte is a 400.
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors
+//! `fault_snapshots.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_uds::config::ExtendedDataRecordConfig;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::UdsBackend;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// UDS ECU storing an occurrence counter, an aging counter and an OEM
+/// record for DTC 012345
+async fn server() -> (TestServer, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(
+        vec![0x19, 0x06, 0x01, 0x23, 0x45, 0xFF],
+        vec![
+            0x59, 0x06, 0x01, 0x23, 0x45, 0x09, // DTC + status
+            0x01, 0x03, // occurrence counter
+            0x02, 0x28, // aging counter
+            0x90, 0xCA, 0xFE, // OEM record
+        ],
+    );
+    mock.add_response(
+        vec![0x19, 0x06, 0x01, 0x23, 0x45, 0x02],
+        vec![0x59, 0x06, 0x01, 0x23, 0x45, 0x09, 0x02, 0x28],
+    );
+    let mut config = common::ecu_config("ecu", "Extended data ECU");
+    config.fault_memory.extended_data = vec![
+        ExtendedDataRecordConfig {
+            record: 0x01,
+            name: "occurrence_counter".to_string(),
+            length: 1,
+        },
+        ExtendedDataRecordConfig {
+            record: 0x02,
+            name: "aging_counter".to_string(),
+            length: 1,
+        },
+    ];
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+
+    let server = common::server(vec![("ecu", Arc::new(backend))]).await;
+    (server, mock)
+}
+
+async fn get_extended_data(server: &TestServer, query: &str) -> reqwest::Response {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/faults/012345/extended-data{query}",
+        server.base_url()
+    );
+    reqwest::get(url).await.expect("get extended data")
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn all_records_keep_raw_bytes_next_to_counters() {
+    let (server, _mock) = server().await;
+
+    let resp = get_extended_data(&server, "").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "items": [
+                { "record_number": 1, "name": "occurrence_counter", "value": 3, "raw": "03" },
+                { "record_number": 2, "name": "aging_counter", "value": 40, "raw": "28" },
+                { "record_number": 144, "raw": "cafe" },
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+async fn record_query_reads_a_single_record() {
+    let (server, mock) = server().await;
+
+    let resp = get_extended_data(&server, "?record=0x02").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["items"].as_array().unwrap().len(), 1, "{body}");
+    assert_eq!(body["items"][0]["name"], "aging_counter");
+    assert!(mock
+        .sent_requests()
+        .contains(&vec![0x19, 0x06, 0x01, 0x23, 0x45, 0x02]));
+}
+
+#[tokio::test]
+async fn record_outside_a_byte_is_rejected() {
+    let (server, mock) = server().await;
+
+    let resp = get_extended_data(&server, "?record=0x100").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+    assert!(mock.sent_requests().is_empty());
+}
@@ -0,0 +1,120 @@
+//! DTC snapshot records on `faults/{fault_id}/snapshots` — in-process
+//! router tests.
+//!
+//! The server reads all snapshot records of a DTC (UDS 0x19 0x04) and
+//! decodes them with the component's DID definitions:
+//!   * defined DIDs are sized by their definition and keyed by semantic id;
+//!   * an undefined DID closing a record is sized by reading that record
+//!     alone, keyed by its hex DID and returned as hex;
+//!   * an undefined DID in the middle of a record cannot be split (503).
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors
+//! `security_lockout.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_conv::types::DataType;
+use sovd_conv::{DidDefinition, DidStore};
+use sovd_uds::UdsBackend;
+
+use sovd_api::AppState;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn store() -> Arc<DidStore> {
+    let store = DidStore::new();
+    store.register(
+        0xF40C,
+        DidDefinition::scaled(DataType::Uint16, 0.25, 0.0)
+            .with_id("engine_rpm")
+            .with_unit("rpm"),
+    );
+    store.register(
+        0xF40D,
+        DidDefinition::scaled(DataType::Uint8, 1.0, 0.0)
+            .with_id("vehicle_speed")
+            .with_unit("km/h"),
+    );
+    Arc::new(store)
+}
+
+/// UDS ECU answering 0x19 0x04 for DTC 012345 with `snapshot_response`,
+/// and for single records with the `(record, response)` pairs
+async fn server(snapshot_response: Vec<u8>, records: Vec<(u8, Vec<u8>)>) -> TestServer {
+    let mock = common::mock();
+    mock.add_response(vec![0x19, 0x04, 0x01, 0x23, 0x45, 0xFF], snapshot_response);
+    for (record, response) in records {
+        mock.add_response(vec![0x19, 0x04, 0x01, 0x23, 0x45, record], response);
+    }
+    let config = common::ecu_config("ecu", "Snapshot ECU");
+    let backend = UdsBackend::with_transport(config, mock).expect("uds backend");
+
+    let backends = common::to_map(vec![("ecu", Arc::new(backend))]);
+    common::serve(AppState::with_did_store(backends, store())).await
+}
+
+async fn get_snapshots(server: &TestServer) -> reqwest::Response {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/faults/012345/snapshots",
+        server.base_url()
+    );
+    reqwest::get(url).await.expect("get snapshots")
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn snapshot_records_decode_by_semantic_name() {
+    let all_records = vec![
+        0x59, 0x04, 0x01, 0x23, 0x45, 0x09, // DTC + status
+        0x01, 0x02, // record 1, two DIDs
+        0xF4, 0x0D, 0x32, // vehicle_speed = 50
+        0xF4, 0x0C, 0x1F, 0x40, // engine_rpm = 8000 * 0.25
+        0x02, 0x02, // record 2, two DIDs
+        0xF4, 0x0D, 0x00, // vehicle_speed = 0
+        0x12, 0x34, 0xDE, 0xAD, // undefined, closing record 2
+    ];
+    let record_2 = vec![
+        0x59, 0x04, 0x01, 0x23, 0x45, 0x09, // DTC + status
+        0x02, 0x02, 0xF4, 0x0D, 0x00, 0x12, 0x34, 0xDE, 0xAD, // record 2 alone
+    ];
+    let server = server(all_records, vec![(0x02, record_2)]).await;
+
+    let resp = get_snapshots(&server).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    let items = body["items"].as_array().expect("items");
+    assert_eq!(items.len(), 2, "{body}");
+
+    assert_eq!(items[0]["record_number"], 1);
+    assert_eq!(items[0]["data"]["vehicle_speed"].as_f64(), Some(50.0));
+    assert_eq!(items[0]["data"]["engine_rpm"].as_f64(), Some(2000.0));
+
+    assert_eq!(items[1]["record_number"], 2);
+    assert_eq!(items[1]["data"]["vehicle_speed"].as_f64(), Some(0.0));
+    assert_eq!(items[1]["data"]["1234"], "dead", "{body}");
+}
+
+#[tokio::test]
+async fn undefined_did_inside_a_record_is_unavailable() {
+    let server = server(
+        vec![
+            0x59, 0x04, 0x01, 0x23, 0x45, 0x09, // DTC + status
+            0x01, 0x02, // record 1, two DIDs
+            0x12, 0x34, 0xDE, 0xAD, // undefined, cannot be sized
+            0xF4, 0x0D, 0x32,
+        ],
+        vec![],
+    )
+    .await;
+
+    let resp = get_snapshots(&server).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+}
@@ -0,0 +1,117 @@
+//! `x-sumo-fingerprints` software fingerprints — in-process router tests.
+//!
+//! `GET .../x-sumo-fingerprints` must read DIDs 0xF183–0xF185 and return one
+//! item per fingerprint the ECU carries:
+//!   * decoded into `date` and `tester_serial` when the ECU's fingerprint
+//!     format is configured, with the raw bytes alongside;
+//!   * raw only when no format is configured;
+//!   * on the sub-entity route (`/components/{gw}/apps/{ecu}/...`) too.
+//!
+//! Drives a real `UdsBackend` over the mock transport (see `common`).
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_core::DiagnosticBackend;
+use sovd_uds::config::FingerprintConfig;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::{UdsBackend, UdsBackendConfig};
+
+// ---------------------------------------------------------------------------
+// Backends
+// ---------------------------------------------------------------------------
+
+/// UDS ECU carrying boot and application software fingerprints (BCD date
+/// + ASCII tester serial) but no application data fingerprint
+fn ecu(format: Option<&str>) -> (Arc<UdsBackend>, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(
+        vec![0x22, 0xF1, 0x83],
+        vec![0x62, 0xF1, 0x83, 0x23, 0x11, 0x02, b'B', b'T', b'0', b'1'],
+    );
+    mock.add_response(
+        vec![0x22, 0xF1, 0x84],
+        vec![0x62, 0xF1, 0x84, 0x24, 0x03, 0x15, b'W', b'S', b'4', b'2'],
+    );
+    mock.add_response(vec![0x22, 0xF1, 0x85], vec![0x7F, 0x22, 0x31]);
+    let config = UdsBackendConfig {
+        fingerprints: FingerprintConfig {
+            format: format.map(str::to_string),
+        },
+        ..common::ecu_config("ecu", "Programmed ECU")
+    };
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+    (Arc::new(backend), mock)
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Server with the ECU registered directly (`ecu`) or behind a gateway (`gw`)
+async fn server(direct: bool, format: Option<&str>) -> TestServer {
+    let (ecu, _) = ecu(format);
+    let backends = if direct {
+        vec![("ecu", ecu as Arc<dyn DiagnosticBackend>)]
+    } else {
+        vec![("gw", common::gateway(ecu))]
+    };
+    common::server(backends).await
+}
+
+async fn get_items(server: &TestServer, path: &str) -> Vec<serde_json::Value> {
+    let url = format!("{}{}", server.base_url(), path);
+    let resp = reqwest::get(url).await.expect("get fingerprints");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK, "GET {path}");
+    let body: serde_json::Value = resp.json().await.unwrap();
+    body["items"].as_array().expect("items").clone()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn configured_format_is_decoded() {
+    let server = server(true, Some("bcd-date-ascii")).await;
+    let items = get_items(&server, "/vehicle/v1/components/ecu/x-sumo-fingerprints").await;
+
+    // 0xF185 answered requestOutOfRange and is left out.
+    assert_eq!(items.len(), 2, "{items:?}");
+    assert_eq!(items[0]["type"], "boot_software");
+    assert_eq!(items[0]["did"], "F183");
+    assert_eq!(items[0]["date"], "2023-11-02");
+    assert_eq!(items[0]["tester_serial"], "BT01");
+    assert_eq!(items[1]["type"], "application_software");
+    assert_eq!(items[1]["date"], "2024-03-15");
+    assert_eq!(items[1]["tester_serial"], "WS42");
+    assert_eq!(items[1]["raw"], "24031557533432");
+}
+
+#[tokio::test]
+async fn unconfigured_format_is_returned_raw() {
+    let server = server(true, None).await;
+    let items = get_items(&server, "/vehicle/v1/components/ecu/x-sumo-fingerprints").await;
+
+    assert_eq!(items.len(), 2, "{items:?}");
+    for item in &items {
+        assert!(item.get("date").is_none(), "{item}");
+        assert!(item.get("tester_serial").is_none(), "{item}");
+    }
+    assert_eq!(items[0]["raw"], "23110242543031");
+}
+
+#[tokio::test]
+async fn sub_entity_route_reads_fingerprints() {
+    let server = server(false, Some("bcd-date-ascii")).await;
+    let items = get_items(
+        &server,
+        "/vehicle/v1/components/gw/apps/ecu/x-sumo-fingerprints",
+    )
+    .await;
+
+    assert_eq!(items.len(), 2, "{items:?}");
+    assert_eq!(items[0]["tester_serial"], "BT01");
+}
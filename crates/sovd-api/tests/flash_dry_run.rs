@@ -0,0 +1,146 @@
+//! `x-sumo-dry-run` updates — in-process router tests.
+//!
+//! A UDS ECU over the mock transport, registered with `x-sumo-dry-run`:
+//!   * prepare verifies the upload and negotiates RequestDownload, then
+//!     reports the block plan in `x-sumo-flash-summary` without sending a
+//!     single TransferData (0x36);
+//!   * execute is refused, so a dry run can never program the ECU;
+//!   * without the flag the same flow transfers the image.
+//!
+//! Drives a real `UdsBackend`; mirrors `spec_update_flow.rs`.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value;
+use sovd_client::testing::TestServer;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::UdsBackend;
+
+/// Firmware image: 2000 bytes, 8 blocks at the negotiated block length
+const IMAGE: [u8; 2000] = [0xA5; 2000];
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+async fn server() -> (TestServer, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    // maxNumberOfBlockLength 0x0102
+    mock.add_response(vec![0x34], vec![0x74, 0x20, 0x01, 0x02]);
+    let config = common::ecu_config("ecu", "ecu");
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+    let server = common::server(vec![("ecu", Arc::new(backend))]).await;
+    (server, mock)
+}
+
+fn url(server: &TestServer, path: &str) -> String {
+    format!(
+        "{}/vehicle/v1/components/ecu/updates{path}",
+        server.base_url()
+    )
+}
+
+/// Register an update, upload the image and run prepare to its end
+async fn prepare(server: &TestServer, register: Value) -> (String, Value) {
+    let http = reqwest::Client::new();
+    let resp = http
+        .post(url(server, ""))
+        .json(&register)
+        .send()
+        .await
+        .expect("register");
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+    let body: Value = resp.json().await.unwrap();
+    let id = body["update_id"].as_str().unwrap().to_string();
+
+    let resp = http
+        .put(url(server, &format!("/{id}/bulk-data/manifest")))
+        .header("content-type", "application/octet-stream")
+        .body(IMAGE.to_vec())
+        .send()
+        .await
+        .expect("upload");
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+
+    let resp = http
+        .put(url(server, &format!("/{id}/prepare")))
+        .send()
+        .await
+        .expect("prepare");
+    assert_eq!(resp.status(), reqwest::StatusCode::ACCEPTED);
+
+    for _ in 0..200 {
+        let status: Value = http
+            .get(url(server, &format!("/{id}/status")))
+            .send()
+            .await
+            .expect("status")
+            .json()
+            .await
+            .unwrap();
+        if matches!(status["status"].as_str(), Some("completed" | "failed")) {
+            return (id, status);
+        }
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+    panic!("prepare never finished");
+}
+
+fn sent_service(mock: &MockTransportAdapter, sid: u8) -> usize {
+    mock.sent_requests()
+        .iter()
+        .filter(|r| r.first() == Some(&sid))
+        .count()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn dry_run_reports_plan_without_transfer_data() {
+    let (server, mock) = server().await;
+
+    let (_, status) = prepare(&server, serde_json::json!({ "x-sumo-dry-run": true })).await;
+
+    assert_eq!(status["status"], "completed", "{status}");
+    assert_eq!(status["x-sumo-dry-run"], true);
+    let summary = &status["x-sumo-flash-summary"];
+    assert_eq!(summary["dry_run"], true, "{status}");
+    assert_eq!(summary["bytes_transferred"], 0);
+    assert_eq!(summary["plan"]["bytes_total"], 2000);
+    assert_eq!(summary["plan"]["block_count"], 8);
+    assert!(summary["plan"]["block_size"].as_u64().unwrap() > 0);
+
+    assert_eq!(sent_service(&mock, 0x34), 1);
+    assert_eq!(sent_service(&mock, 0x36), 0);
+}
+
+#[tokio::test]
+async fn dry_run_cannot_be_executed() {
+    let (server, mock) = server().await;
+    let (id, _) = prepare(&server, serde_json::json!({ "x-sumo-dry-run": true })).await;
+
+    let resp = reqwest::Client::new()
+        .put(url(&server, &format!("/{id}/execute")))
+        .send()
+        .await
+        .expect("execute");
+
+    assert_eq!(resp.status(), reqwest::StatusCode::CONFLICT);
+    assert_eq!(sent_service(&mock, 0x36), 0);
+}
+
+#[tokio::test]
+async fn regular_prepare_transfers_image() {
+    let (server, mock) = server().await;
+
+    let (_, status) = prepare(&server, serde_json::json!({})).await;
+
+    assert_eq!(status["status"], "completed", "{status}");
+    assert!(status.get("x-sumo-dry-run").is_none(), "{status}");
+    assert_eq!(sent_service(&mock, 0x36), 8);
+}
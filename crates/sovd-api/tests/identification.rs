@@ -0,0 +1,299 @@
+//! `GET /vehicle/v1/identification` aggregate — in-process router tests.
+//!
+//! Covers the vehicle-wide identification fan-out:
+//!   * every component's identification block is returned, tagged with its
+//!     component id and href;
+//!   * `?fields=` is passed down so only the listed fields are read;
+//!   * a component that cannot be read carries its own `error` while the
+//!     others still return their fields;
+//!   * a gateway is expanded into one item per child ECU, addressed by its
+//!     sub-entity href.
+//!
+//! Mirrors the `TestServer` pattern from `data_aliases.rs`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use sovd_client::testing::TestServer;
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+
+use sovd_api::{create_router, AppState};
+
+// ---------------------------------------------------------------------------
+// Mock backend
+// ---------------------------------------------------------------------------
+
+/// ECU with a fixed identification block; `None` leaves
+/// `read_identification` at the trait default (not supported).
+struct IdentBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    identification: Option<Vec<(&'static str, &'static str)>>,
+    /// `fields` argument of every `read_identification` call
+    requested: Mutex<Vec<Option<Vec<String>>>>,
+}
+
+impl IdentBackend {
+    fn new(id: &str, identification: Option<Vec<(&'static str, &'static str)>>) -> Self {
+        Self {
+            info: EntityInfo {
+                id: id.to_string(),
+                name: format!("{id} ECU"),
+                entity_type: "ecu".to_string(),
+                description: None,
+                href: format!("/vehicle/v1/components/{id}"),
+                status: Some("online".to_string()),
+                display_name: None,
+                category: None,
+            },
+            capabilities: Capabilities::default(),
+            identification,
+            requested: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for IdentBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn read_identification(
+        &self,
+        fields: Option<&[String]>,
+    ) -> BackendResult<Vec<DataValue>> {
+        self.requested
+            .lock()
+            .unwrap()
+            .push(fields.map(|f| f.to_vec()));
+        let Some(identification) = &self.identification else {
+            return Err(BackendError::NotSupported("read_identification".into()));
+        };
+        Ok(identification
+            .iter()
+            .filter(|(key, _)| fields.is_none_or(|f| f.iter().any(|f| f == key)))
+            .map(|(key, value)| DataValue::new(*key, *key, serde_json::json!(value)))
+            .collect())
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+/// Gateway hosting child ECUs as sub-entities; has no identification itself.
+struct GatewayBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    children: Vec<Arc<IdentBackend>>,
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for GatewayBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+    async fn list_sub_entities(&self) -> BackendResult<Vec<EntityInfo>> {
+        Ok(self.children.iter().map(|c| c.info.clone()).collect())
+    }
+    async fn get_sub_entity(&self, id: &str) -> BackendResult<Arc<dyn DiagnosticBackend>> {
+        self.children
+            .iter()
+            .find(|c| c.info.id == id)
+            .map(|c| c.clone() as Arc<dyn DiagnosticBackend>)
+            .ok_or_else(|| BackendError::EntityNotFound(id.to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// `ecu_a` and `ecu_b` report different VINs; `ecu_c` cannot be read.
+async fn server() -> (TestServer, Arc<IdentBackend>) {
+    let ecu_a = Arc::new(IdentBackend::new(
+        "ecu_a",
+        Some(vec![
+            ("vin", "WVWZZZ1JZXW000001"),
+            ("part_number", "PN-A"),
+            ("ecu_sw_version", "1.0.0"),
+        ]),
+    ));
+    let ecu_b = IdentBackend::new(
+        "ecu_b",
+        Some(vec![
+            ("vin", "WVWZZZ1JZXW000002"),
+            ("part_number", "PN-B"),
+            ("ecu_sw_version", "2.1.0"),
+        ]),
+    );
+    let ecu_c = IdentBackend::new("ecu_c", None);
+
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu_a".to_string(), ecu_a.clone());
+    backends.insert("ecu_b".to_string(), Arc::new(ecu_b));
+    backends.insert("ecu_c".to_string(), Arc::new(ecu_c));
+    let server = TestServer::start(create_router(AppState::new(backends)))
+        .await
+        .expect("test server");
+    (server, ecu_a)
+}
+
+async fn get_items(server: &TestServer, path: &str) -> Vec<serde_json::Value> {
+    let url = format!("{}{}", server.base_url(), path);
+    let resp = reqwest::Client::new().get(url).send().await.expect("get");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK, "GET {path}");
+    let body: serde_json::Value = resp.json().await.expect("json");
+    body["items"].as_array().expect("items").clone()
+}
+
+fn item<'a>(items: &'a [serde_json::Value], component: &str) -> &'a serde_json::Value {
+    items
+        .iter()
+        .find(|i| i["component"] == component)
+        .unwrap_or_else(|| panic!("{component} listed: {items:?}"))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn aggregate_tags_each_component() {
+    let (server, _) = server().await;
+    let items = get_items(&server, "/vehicle/v1/identification").await;
+
+    assert_eq!(items.len(), 3);
+    let a = item(&items, "ecu_a");
+    assert_eq!(a["href"], "/vehicle/v1/components/ecu_a");
+    assert_eq!(a["fields"]["vin"], "WVWZZZ1JZXW000001", "{a}");
+    assert_eq!(a["fields"]["ecu_sw_version"], "1.0.0", "{a}");
+    let b = item(&items, "ecu_b");
+    assert_eq!(b["fields"]["vin"], "WVWZZZ1JZXW000002", "{b}");
+    assert_eq!(b["fields"]["part_number"], "PN-B", "{b}");
+}
+
+#[tokio::test]
+async fn fields_query_limits_what_is_read() {
+    let (server, ecu_a) = server().await;
+    let items = get_items(&server, "/vehicle/v1/identification?fields=vin,part_number").await;
+
+    for component in ["ecu_a", "ecu_b"] {
+        let fields = item(&items, component)["fields"]
+            .as_object()
+            .expect("fields")
+            .clone();
+        let mut keys: Vec<&str> = fields.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["part_number", "vin"], "{component}");
+    }
+    // The filter reaches the backend, not just the response.
+    assert_eq!(
+        *ecu_a.requested.lock().unwrap(),
+        vec![Some(vec!["vin".to_string(), "part_number".to_string()])]
+    );
+}
+
+#[tokio::test]
+async fn failing_component_is_isolated() {
+    let (server, _) = server().await;
+    let items = get_items(&server, "/vehicle/v1/identification").await;
+
+    let c = item(&items, "ecu_c");
+    assert!(c.get("fields").is_none(), "{c}");
+    assert!(c["error"]["error_code"].is_string(), "{c}");
+    assert!(
+        c["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("read_identification"),
+        "{c}"
+    );
+    // The healthy components are unaffected.
+    assert!(item(&items, "ecu_a")["fields"]["vin"].is_string());
+    assert!(item(&items, "ecu_b")["fields"]["vin"].is_string());
+}
+
+#[tokio::test]
+async fn gateway_is_expanded_into_its_children() {
+    let gateway = GatewayBackend {
+        info: EntityInfo {
+            id: "gw".to_string(),
+            name: "Vehicle gateway".to_string(),
+            entity_type: "gateway".to_string(),
+            description: None,
+            href: "/vehicle/v1/components/gw".to_string(),
+            status: Some("online".to_string()),
+            display_name: None,
+            category: None,
+        },
+        capabilities: Capabilities::gateway(),
+        children: vec![
+            Arc::new(IdentBackend::new(
+                "engine",
+                Some(vec![("vin", "WVWZZZ1JZXW000003")]),
+            )),
+            Arc::new(IdentBackend::new(
+                "body",
+                Some(vec![("vin", "WVWZZZ1JZXW000003")]),
+            )),
+        ],
+    };
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("gw".to_string(), Arc::new(gateway));
+    let server = TestServer::start(create_router(AppState::new(backends)))
+        .await
+        .expect("test server");
+
+    let items = get_items(&server, "/vehicle/v1/identification").await;
+
+    assert_eq!(items.len(), 2, "one item per child, none for gw: {items:?}");
+    let engine = item(&items, "engine");
+    assert_eq!(engine["href"], "/vehicle/v1/components/gw/apps/engine");
+    assert_eq!(engine["fields"]["vin"], "WVWZZZ1JZXW000003", "{engine}");
+    assert_eq!(
+        item(&items, "body")["href"],
+        "/vehicle/v1/components/gw/apps/body"
+    );
+}
@@ -0,0 +1,178 @@
+//! `x-sumo-link` baud-rate switching — in-process router tests.
+//!
+//! `PUT .../x-sumo-link` runs the LinkControl (0x87) handshake:
+//!   * verify (0x01 with the fixed rate ID), transition (0x03), then the
+//!     transport is switched to the new bitrate, which `GET` reports;
+//!   * a specific rate in bit/s is verified with 0x02;
+//!   * an ECU refusing the verify step surfaces its NRC, and neither the
+//!     transition nor the bus switch happens;
+//!   * no other request reaches the ECU between the transition and the bus
+//!     switch, and a bus that cannot switch leaves the old rate reported.
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors
+//! `transport_stats.rs`.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value;
+use sovd_client::testing::TestServer;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::UdsBackend;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+async fn server(mock: Arc<MockTransportAdapter>) -> TestServer {
+    let config = common::ecu_config("ecu", "ecu");
+    let backend = UdsBackend::with_transport(config, mock).expect("uds backend");
+    common::server(vec![("ecu", Arc::new(backend))]).await
+}
+
+fn url(server: &TestServer) -> String {
+    format!(
+        "{}/vehicle/v1/components/ecu/x-sumo-link",
+        server.base_url()
+    )
+}
+
+async fn put_link(server: &TestServer, body: Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .put(url(server))
+        .json(&body)
+        .send()
+        .await
+        .expect("put x-sumo-link")
+}
+
+/// LinkControl requests the ECU received, in order
+fn link_requests(mock: &MockTransportAdapter) -> Vec<Vec<u8>> {
+    mock.sent_requests()
+        .into_iter()
+        .filter(|request| request.first() == Some(&0x87))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn switch_verifies_transitions_and_moves_the_bus() {
+    let mock = common::mock();
+    let server = server(mock.clone()).await;
+
+    let resp = put_link(&server, serde_json::json!({ "baud_rate_id": "1m" })).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["action"], "switch");
+    assert_eq!(body["baud_rate"], 1_000_000);
+
+    assert_eq!(
+        link_requests(&mock),
+        [vec![0x87, 0x01, 0x13], vec![0x87, 0x03]]
+    );
+    assert_eq!(mock.bitrate_changes(), [1_000_000]);
+
+    let link: Value = reqwest::get(url(&server))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(link["current_baud_rate"], 1_000_000);
+    assert!(link.get("pending_baud_rate").is_none(), "{link}");
+}
+
+#[tokio::test]
+async fn specific_rate_is_verified_with_0x02() {
+    let mock = common::mock();
+    let server = server(mock.clone()).await;
+
+    let resp = put_link(&server, serde_json::json!({ "baud_rate": 250_000 })).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+
+    assert_eq!(
+        link_requests(&mock),
+        [vec![0x87, 0x02, 0x03, 0xD0, 0x90], vec![0x87, 0x03]]
+    );
+    assert_eq!(mock.bitrate_changes(), [250_000]);
+}
+
+#[tokio::test]
+async fn refused_verify_reports_the_nrc_and_stays_put() {
+    let mock = common::mock();
+    // requestOutOfRange: the ECU does not support 1 Mbit/s
+    mock.add_response(vec![0x87, 0x01], vec![0x7F, 0x87, 0x31]);
+    let server = server(mock.clone()).await;
+
+    let resp = put_link(&server, serde_json::json!({ "baud_rate_id": "1m" })).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["parameters"]["nrc"][0], "0x31", "{body}");
+
+    assert_eq!(link_requests(&mock), [vec![0x87, 0x01, 0x13]]);
+    assert!(mock.bitrate_changes().is_empty());
+
+    let link: Value = reqwest::get(url(&server))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(link["current_baud_rate"], 500_000);
+}
+
+#[tokio::test]
+async fn missing_target_is_a_bad_request() {
+    let server = server(common::mock()).await;
+    let resp = put_link(&server, serde_json::json!({})).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn requests_wait_until_the_bus_has_switched() {
+    let mock = common::mock();
+    mock.set_bitrate_delay(Duration::from_millis(100));
+    let server = Arc::new(server(mock.clone()).await);
+
+    let switch = tokio::spawn({
+        let server = server.clone();
+        async move { put_link(&server, serde_json::json!({ "baud_rate_id": "1m" })).await }
+    });
+    // The transition is answered; the bus is still switching
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    let read = reqwest::get(format!(
+        "{}/vehicle/v1/components/ecu/data/F190",
+        server.base_url()
+    ))
+    .await
+    .unwrap();
+    assert_eq!(read.status(), reqwest::StatusCode::OK);
+
+    // Sent at the old rate, the read would have come back before the switch
+    assert_eq!(mock.bitrate_changes(), [1_000_000]);
+    assert_eq!(switch.await.unwrap().status(), reqwest::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn bus_that_cannot_switch_keeps_the_old_rate() {
+    let mock = common::mock();
+    mock.refuse_bitrates(true);
+    let server = server(mock.clone()).await;
+
+    let resp = put_link(&server, serde_json::json!({ "baud_rate_id": "1m" })).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+
+    let link: Value = reqwest::get(url(&server))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(link["current_baud_rate"], 500_000);
+    assert!(link.get("pending_baud_rate").is_none(), "{link}");
+}
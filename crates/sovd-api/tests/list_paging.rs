@@ -0,0 +1,215 @@
+//! `?limit=` / `?offset=` / `?q=` on the data and fault lists — in-process
+//! router tests.
+//!
+//! Five DIDs are defined for the component and the backend stores five
+//! faults:
+//!   * without paging parameters the lists are unchanged: everything, no
+//!     `next`, and no `total_count` on the data list;
+//!   * `limit` cuts a page, `total_count` counts all matches and `next`
+//!     leads to the following page, keeping the other query parameters;
+//!   * `q` matches id or name, ignoring case, before the page is cut.
+//!
+//! Mirrors the mock-backend pattern from `write_range.rs`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::Value;
+use sovd_client::testing::TestServer;
+use sovd_conv::types::DataType;
+use sovd_conv::{DidDefinition, DidStore};
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataValue, DiagnosticBackend, EntityInfo, Fault,
+    FaultFilter, FaultSeverity, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+
+use sovd_api::{create_router, AppState};
+
+// ---------------------------------------------------------------------------
+// Mock backend
+// ---------------------------------------------------------------------------
+
+struct EcuBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+}
+
+fn fault(code: &str, message: &str) -> Fault {
+    Fault {
+        id: code.to_string(),
+        code: code.to_string(),
+        severity: FaultSeverity::Warning,
+        message: message.to_string(),
+        category: None,
+        first_occurrence: None,
+        last_occurrence: None,
+        occurrence_count: None,
+        active: true,
+        status: None,
+        href: format!("/vehicle/v1/components/ecu/faults/{code}"),
+    }
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for EcuBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![
+                fault("P0101", "Mass air flow range"),
+                fault("P0117", "Coolant temperature low"),
+                fault("P0118", "Coolant temperature high"),
+                fault("P0300", "Random misfire"),
+                fault("P0420", "Catalyst efficiency"),
+            ],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn store() -> Arc<DidStore> {
+    let store = DidStore::new();
+    for (did, id) in [
+        (0xF190, "vin"),
+        (0xF405, "coolant_temp"),
+        (0xF40C, "engine_rpm"),
+        (0xF40D, "vehicle_speed"),
+        (0xF40F, "intake_air_temp"),
+    ] {
+        store.register(did, DidDefinition::scalar(DataType::Uint8).with_id(id));
+    }
+    Arc::new(store)
+}
+
+async fn server() -> TestServer {
+    let backend = Arc::new(EcuBackend {
+        info: EntityInfo {
+            id: "ecu".to_string(),
+            name: "ecu ECU".to_string(),
+            entity_type: "ecu".to_string(),
+            description: None,
+            href: "/vehicle/v1/components/ecu".to_string(),
+            status: Some("online".to_string()),
+            display_name: None,
+            category: None,
+        },
+        capabilities: Capabilities::default(),
+    });
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu".to_string(), backend);
+    let state = AppState::with_did_store(backends, store());
+    TestServer::start(create_router(state))
+        .await
+        .expect("test server")
+}
+
+/// GET `path` (with query) and return the JSON body
+async fn get(server: &TestServer, path: &str) -> Value {
+    let resp = reqwest::get(format!("{}{path}", server.base_url()))
+        .await
+        .expect("get");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK, "{path}");
+    resp.json().await.unwrap()
+}
+
+fn ids(body: &Value, key: &str) -> Vec<String> {
+    body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item[key].as_str().unwrap().to_string())
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn unpaged_lists_are_unchanged() {
+    let server = server().await;
+
+    let data = get(&server, "/vehicle/v1/components/ecu/data").await;
+    assert_eq!(data["count"], 5);
+    assert!(data.get("total_count").is_none(), "{data}");
+    assert!(data.get("next").is_none(), "{data}");
+
+    let faults = get(&server, "/vehicle/v1/components/ecu/faults").await;
+    assert_eq!(faults["total_count"], 5);
+    assert!(faults.get("next").is_none(), "{faults}");
+}
+
+#[tokio::test]
+async fn data_pages_follow_next_to_the_end() {
+    let server = server().await;
+
+    let first = get(&server, "/vehicle/v1/components/ecu/data?limit=2").await;
+    assert_eq!(ids(&first, "id"), ["coolant_temp", "engine_rpm"]);
+    assert_eq!(first["count"], 2);
+    assert_eq!(first["total_count"], 5);
+    let next = first["next"].as_str().unwrap();
+    assert_eq!(next, "/vehicle/v1/components/ecu/data?limit=2&offset=2");
+
+    let second = get(&server, next).await;
+    assert_eq!(ids(&second, "id"), ["intake_air_temp", "vehicle_speed"]);
+
+    let last = get(&server, second["next"].as_str().unwrap()).await;
+    assert_eq!(ids(&last, "id"), ["vin"]);
+    assert!(last.get("next").is_none(), "{last}");
+}
+
+#[tokio::test]
+async fn q_filters_before_paging() {
+    let server = server().await;
+
+    let data = get(&server, "/vehicle/v1/components/ecu/data?q=TEMP&limit=1").await;
+    assert_eq!(ids(&data, "id"), ["coolant_temp"]);
+    assert_eq!(data["total_count"], 2);
+    assert_eq!(
+        data["next"],
+        "/vehicle/v1/components/ecu/data?q=TEMP&limit=1&offset=1"
+    );
+
+    let faults = get(&server, "/vehicle/v1/components/ecu/faults?q=coolant").await;
+    assert_eq!(ids(&faults, "code"), ["P0117", "P0118"]);
+    assert_eq!(faults["total_count"], 2);
+    assert!(faults.get("next").is_none(), "{faults}");
+}
+
+#[tokio::test]
+async fn fault_offset_skips_into_the_list() {
+    let server = server().await;
+
+    let faults = get(
+        &server,
+        "/vehicle/v1/components/ecu/faults?severity=3&offset=1&limit=3",
+    )
+    .await;
+    assert_eq!(ids(&faults, "code"), ["P0117", "P0118", "P0300"]);
+    assert_eq!(faults["total_count"], 5);
+    assert_eq!(
+        faults["next"],
+        "/vehicle/v1/components/ecu/faults?severity=3&limit=3&offset=4"
+    );
+}
@@ -0,0 +1,174 @@
+//! `x-sumo-memory` raw memory reads — in-process router tests.
+//!
+//! `GET .../x-sumo-memory?address=&size=` must send ReadMemoryByAddress
+//! (0x23) with the ALFID sized to the request and return the bytes hex-encoded:
+//!   * on the component route (`/components/{ecu}/x-sumo-memory`);
+//!   * on the sub-entity route (`/components/{gw}/apps/{ecu}/x-sumo-memory`);
+//!
+//! and reject a malformed address or size with 400 before anything is sent.
+//! `.../x-sumo-memory/upload` reads the region out with RequestUpload
+//! (0x35), TransferData (0x36) and RequestTransferExit (0x37) instead and
+//! returns the raw bytes, on both routes as well.
+//!
+//! Drives a real `UdsBackend` over the mock transport (see `common`).
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_core::DiagnosticBackend;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::UdsBackend;
+
+// ---------------------------------------------------------------------------
+// Backends
+// ---------------------------------------------------------------------------
+
+/// UDS ECU answering `23 12 20 48 04` with four bytes of calibration data,
+/// and uploading the same four bytes in two TransferData blocks
+fn ecu() -> (Arc<UdsBackend>, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(
+        vec![0x23, 0x12, 0x20, 0x48, 0x04],
+        vec![0x63, 0xCA, 0xFE, 0x00, 0x01],
+    );
+    mock.add_response(vec![0x35], vec![0x75, 0x20, 0x00, 0x04]);
+    mock.add_response(vec![0x36, 0x01], vec![0x76, 0x01, 0xCA, 0xFE]);
+    mock.add_response(vec![0x36, 0x02], vec![0x76, 0x02, 0x00, 0x01]);
+    let config = common::ecu_config("ecu", "Calibrated ECU");
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+    (Arc::new(backend), mock)
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Server with the ECU registered directly (`ecu`) or behind a gateway (`gw`)
+async fn server(direct: bool) -> (TestServer, Arc<MockTransportAdapter>) {
+    let (ecu, mock) = ecu();
+    let backends = if direct {
+        vec![("ecu", ecu as Arc<dyn DiagnosticBackend>)]
+    } else {
+        vec![("gw", common::gateway(ecu))]
+    };
+    (common::server(backends).await, mock)
+}
+
+async fn get(server: &TestServer, path: &str) -> reqwest::Response {
+    let url = format!("{}{}", server.base_url(), path);
+    reqwest::get(url).await.expect("get memory")
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn component_route_reads_memory() {
+    let (server, mock) = server(true).await;
+    let resp = get(
+        &server,
+        "/vehicle/v1/components/ecu/x-sumo-memory?address=0x2048&size=4",
+    )
+    .await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["address"], "0x2048", "{body}");
+    assert_eq!(body["size"], 4, "{body}");
+    assert_eq!(body["data"], "cafe0001", "{body}");
+    assert_eq!(
+        mock.sent_requests(),
+        vec![vec![0x23, 0x12, 0x20, 0x48, 0x04]]
+    );
+}
+
+#[tokio::test]
+async fn sub_entity_route_reads_memory() {
+    let (server, mock) = server(false).await;
+    let resp = get(
+        &server,
+        "/vehicle/v1/components/gw/apps/ecu/x-sumo-memory?address=8264&size=0x4",
+    )
+    .await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["data"], "cafe0001", "{body}");
+    assert_eq!(
+        mock.sent_requests(),
+        vec![vec![0x23, 0x12, 0x20, 0x48, 0x04]]
+    );
+}
+
+#[tokio::test]
+async fn malformed_query_is_rejected_unsent() {
+    let (server, mock) = server(true).await;
+    for query in ["address=0xZZ&size=4", "address=0x2048&size=0"] {
+        let path = format!("/vehicle/v1/components/ecu/x-sumo-memory?{query}");
+        let resp = get(&server, &path).await;
+        assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST, "{query}");
+    }
+    assert!(mock.sent_requests().is_empty());
+}
+
+#[tokio::test]
+async fn component_route_uploads_memory() {
+    let (server, mock) = server(true).await;
+    let resp = get(
+        &server,
+        "/vehicle/v1/components/ecu/x-sumo-memory/upload?address=0x2048&size=4",
+    )
+    .await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "application/octet-stream");
+    assert_eq!(
+        resp.bytes().await.unwrap().as_ref(),
+        [0xCA, 0xFE, 0x00, 0x01]
+    );
+    assert_eq!(
+        mock.sent_requests(),
+        vec![
+            vec![0x35, 0x00, 0x12, 0x20, 0x48, 0x04],
+            vec![0x36, 0x01],
+            vec![0x36, 0x02],
+            vec![0x37],
+        ]
+    );
+}
+
+#[tokio::test]
+async fn sub_entity_route_uploads_memory() {
+    let (server, mock) = server(false).await;
+    let resp = get(
+        &server,
+        "/vehicle/v1/components/gw/apps/ecu/x-sumo-memory/upload?address=0x2048&size=4&format=0x00",
+    )
+    .await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    assert_eq!(
+        resp.bytes().await.unwrap().as_ref(),
+        [0xCA, 0xFE, 0x00, 0x01]
+    );
+    assert_eq!(mock.sent_requests().last().unwrap(), &vec![0x37]);
+}
+
+#[tokio::test]
+async fn malformed_upload_query_is_rejected_unsent() {
+    let (server, mock) = server(true).await;
+    for query in [
+        "address=0x2048&size=0",
+        "address=0x2048&size=4&format=0x100",
+        // Encrypted transfers cannot be read back
+        "address=0x2048&size=4&format=0x01",
+    ] {
+        let path = format!("/vehicle/v1/components/ecu/x-sumo-memory/upload?{query}");
+        let resp = get(&server, &path).await;
+        assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST, "{query}");
+    }
+    assert!(mock.sent_requests().is_empty());
+}
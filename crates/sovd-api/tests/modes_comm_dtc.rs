@@ -55,6 +55,8 @@ impl ModesBackend {
                 description: None,
                 href: format!("/vehicle/v1/components/{id}"),
                 status: Some("online".to_string()),
+                display_name: None,
+                category: None,
             },
             capabilities: Capabilities::default(),
             comm: Mutex::new("enable-rx-tx".to_string()),
@@ -0,0 +1,326 @@
+//! `x-sumo-mode` on cyclic subscriptions — in-process router tests.
+//!
+//! The backend publishes the same sample series to every subscription:
+//!   * without a mode (cyclic) every sample becomes an SSE event;
+//!   * `on_change` with a deadband only emits values that moved by more
+//!     than the deadband since the last emitted one, numbered contiguously;
+//!   * a relative deadband (`deadband_pct`) does the same in percent of the
+//!     last emitted value, over SSE and WebSocket alike;
+//!   * the mode is echoed on the created subscription, and an invalid
+//!     deadband is rejected.
+//!
+//! Mirrors the `TestServer` pattern from `subscription_limits.rs`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use sovd_client::testing::TestServer;
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataPoint, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::Message;
+
+use sovd_api::{create_router, AppState};
+
+/// Samples every subscriber receives, in order
+const SAMPLES: [f64; 6] = [40.0, 40.0, 40.2, 45.0, 45.5, 50.0];
+
+// ---------------------------------------------------------------------------
+// Mock backend
+// ---------------------------------------------------------------------------
+
+struct EcuBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    data_tx: broadcast::Sender<DataPoint>,
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for EcuBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![ParameterInfo {
+            id: "coolant_temp".to_string(),
+            name: "Coolant temperature".to_string(),
+            description: None,
+            unit: Some("degC".to_string()),
+            data_type: None,
+            read_only: true,
+            href: "/vehicle/v1/components/ecu/data/coolant_temp".to_string(),
+            did: None,
+            category: None,
+        }])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn subscribe_data(
+        &self,
+        _param_ids: &[String],
+        _rate_hz: u32,
+    ) -> BackendResult<broadcast::Receiver<DataPoint>> {
+        // Subscribe first so the receiver buffers the whole series.
+        let rx = self.data_tx.subscribe();
+        for value in SAMPLES {
+            let _ = self.data_tx.send(DataPoint {
+                id: "coolant_temp".to_string(),
+                value: serde_json::json!(value),
+                unit: None,
+                timestamp: chrono::Utc::now(),
+            });
+        }
+        Ok(rx)
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+async fn server() -> TestServer {
+    let backend = EcuBackend {
+        info: EntityInfo {
+            id: "ecu".to_string(),
+            name: "ecu ECU".to_string(),
+            entity_type: "ecu".to_string(),
+            description: None,
+            href: "/vehicle/v1/components/ecu".to_string(),
+            status: Some("online".to_string()),
+            display_name: None,
+            category: None,
+        },
+        capabilities: Capabilities::default(),
+        data_tx: broadcast::channel(16).0,
+    };
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu".to_string(), Arc::new(backend));
+    TestServer::start(create_router(AppState::new(backends)))
+        .await
+        .expect("test server")
+}
+
+async fn create(server: &TestServer, body: serde_json::Value) -> reqwest::Response {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/cyclic-subscriptions",
+        server.base_url()
+    );
+    reqwest::Client::new()
+        .post(url)
+        .json(&body)
+        .send()
+        .await
+        .expect("create subscription")
+}
+
+/// Attach to the subscription's SSE stream and collect event payloads until
+/// `count` arrived or the stream stays quiet for a moment.
+async fn payloads(server: &TestServer, sub_id: &str, count: usize) -> Vec<serde_json::Value> {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/cyclic-subscriptions/{sub_id}",
+        server.base_url()
+    );
+    let mut resp = reqwest::Client::new()
+        .get(url)
+        .header(reqwest::header::ACCEPT, "text/event-stream")
+        .send()
+        .await
+        .expect("open subscription SSE");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+
+    let mut text = String::new();
+    let mut events = Vec::new();
+    while events.len() < count {
+        match tokio::time::timeout(Duration::from_millis(500), resp.chunk()).await {
+            Ok(Ok(Some(chunk))) => text.push_str(&String::from_utf8_lossy(&chunk)),
+            _ => break,
+        }
+        // Only complete events: the text after the last blank line may
+        // still be arriving.
+        let complete = &text[..text.rfind("\n\n").unwrap_or(0)];
+        events = complete
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(|data| serde_json::from_str::<serde_json::Value>(data.trim()).unwrap())
+            .map(|event| event["payload"].clone())
+            .collect();
+    }
+    events
+}
+
+/// [`payloads`] over the subscription's WebSocket instead of SSE.
+async fn ws_payloads(server: &TestServer, sub_id: &str, count: usize) -> Vec<serde_json::Value> {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/cyclic-subscriptions/{sub_id}",
+        server.base_url().replacen("http", "ws", 1)
+    );
+    let (mut socket, _) = tokio_tungstenite::connect_async(url)
+        .await
+        .expect("open subscription WebSocket");
+
+    let mut events = Vec::new();
+    while events.len() < count {
+        match tokio::time::timeout(Duration::from_millis(500), socket.next()).await {
+            Ok(Some(Ok(Message::Text(text)))) => {
+                let event: serde_json::Value = serde_json::from_str(&text).unwrap();
+                events.push(event["payload"].clone());
+            }
+            Ok(Some(Ok(_))) => {}
+            _ => break,
+        }
+    }
+    events
+}
+
+/// (seq, value) of each payload
+fn samples(events: &[serde_json::Value]) -> Vec<(u64, f64)> {
+    events
+        .iter()
+        .map(|p| {
+            (
+                p["seq"].as_u64().unwrap(),
+                p["values"]["coolant_temp"].as_f64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+/// Id of a new `on_change` subscription with a 12% relative deadband
+async fn create_relative(server: &TestServer) -> String {
+    let resp = create(
+        server,
+        serde_json::json!({
+            "resource": "coolant_temp",
+            "interval": "slow",
+            "x-sumo-mode": { "type": "on_change", "deadband_pct": 12.0 },
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+    let created: serde_json::Value = resp.json().await.unwrap();
+    created["subscription_id"].as_str().unwrap().to_string()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn cyclic_emits_every_sample() {
+    let server = server().await;
+    let created: serde_json::Value = create(
+        &server,
+        serde_json::json!({ "resource": "coolant_temp", "interval": "slow" }),
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    assert!(created.get("x-sumo-mode").is_none(), "{created}");
+
+    let events = payloads(&server, created["subscription_id"].as_str().unwrap(), 6).await;
+    let values: Vec<f64> = events
+        .iter()
+        .map(|p| p["values"]["coolant_temp"].as_f64().unwrap())
+        .collect();
+    assert_eq!(values, SAMPLES);
+}
+
+#[tokio::test]
+async fn on_change_suppresses_samples_within_deadband() {
+    let server = server().await;
+    let resp = create(
+        &server,
+        serde_json::json!({
+            "resource": "coolant_temp",
+            "interval": "slow",
+            "x-sumo-mode": { "type": "on_change", "deadband": 1.0 },
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+    let created: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(created["x-sumo-mode"]["type"], "on_change", "{created}");
+
+    // 40 → (40, 40.2 unchanged) → 45 → (45.5 unchanged) → 50
+    let events = payloads(&server, created["subscription_id"].as_str().unwrap(), 4).await;
+    let values: Vec<(u64, f64)> = events
+        .iter()
+        .map(|p| {
+            (
+                p["seq"].as_u64().unwrap(),
+                p["values"]["coolant_temp"].as_f64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(values, vec![(1, 40.0), (2, 45.0), (3, 50.0)]);
+}
+
+#[tokio::test]
+async fn negative_deadband_is_rejected() {
+    let server = server().await;
+    let resp = create(
+        &server,
+        serde_json::json!({
+            "resource": "coolant_temp",
+            "interval": "slow",
+            "x-sumo-mode": { "type": "on_change", "deadband": -1.0 },
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+}
+
+// 40 → (40, 40.2 within 12%) → 45 (+12.5%) → (45.5, 50 within 12% of 45)
+const RELATIVE: [(u64, f64); 2] = [(1, 40.0), (2, 45.0)];
+
+#[tokio::test]
+async fn relative_deadband_over_sse() {
+    let server = server().await;
+    let id = create_relative(&server).await;
+
+    assert_eq!(samples(&payloads(&server, &id, 3).await), RELATIVE);
+}
+
+#[tokio::test]
+async fn relative_deadband_over_websocket() {
+    let server = server().await;
+    let id = create_relative(&server).await;
+
+    assert_eq!(samples(&ws_payloads(&server, &id, 3).await), RELATIVE);
+}
+
+#[tokio::test]
+async fn negative_relative_deadband_is_rejected() {
+    let server = server().await;
+    let resp = create(
+        &server,
+        serde_json::json!({
+            "resource": "coolant_temp",
+            "interval": "slow",
+            "x-sumo-mode": { "type": "on_change", "deadband_pct": -5.0 },
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+}
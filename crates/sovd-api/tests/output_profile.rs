@@ -0,0 +1,184 @@
+//! IO control `ramp` / `sequence` profiles — in-process router tests.
+//!
+//! A profile execution sends timed short-term adjustments (0x2F 0x03) from
+//! a background task:
+//!   * a ramp sends every step, reports `{sent, total, value}` and completes
+//!     holding its end value;
+//!   * `DELETE` of a running sequence stops it and sends returnControlToECU
+//!     (0x2F 0x00), leaving the execution `stopped`;
+//!   * an out-of-range setpoint refuses the whole profile (400) before
+//!     anything is sent.
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors
+//! `output_range.rs`.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value;
+use sovd_client::testing::TestServer;
+use sovd_uds::config::{DataType, OutputConfig, RangePolicy};
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::{UdsBackend, UdsBackendConfig};
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// UDS ECU with a 0–100 uint8 `fan` output (0xF004, raw = physical)
+async fn server() -> (TestServer, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(vec![0x2F, 0xF0, 0x04, 0x03], vec![0x6F, 0xF0, 0x04, 0x03]);
+    mock.add_response(vec![0x2F, 0xF0, 0x04, 0x00], vec![0x6F, 0xF0, 0x04, 0x00]);
+    let config = UdsBackendConfig {
+        outputs: vec![OutputConfig {
+            id: "fan".to_string(),
+            name: "fan".to_string(),
+            ioid: "0xF004".to_string(),
+            default_value: "00".to_string(),
+            description: None,
+            security_level: 0,
+            data_type: Some(DataType::Uint8),
+            unit: Some("%".to_string()),
+            scale: 1.0,
+            offset: 0.0,
+            min: Some(0.0),
+            max: Some(100.0),
+            allowed: vec![],
+            range_policy: RangePolicy::Reject,
+        }],
+        ..common::ecu_config("ecu", "Output ECU")
+    };
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+
+    let server = common::server(vec![("ecu", Arc::new(backend))]).await;
+    (server, mock)
+}
+
+/// Start a profile on `fan`
+async fn start(server: &TestServer, parameters: Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!(
+            "{}/vehicle/v1/components/ecu/operations/fan/executions",
+            server.base_url()
+        ))
+        .json(&serde_json::json!({ "parameters": parameters }))
+        .send()
+        .await
+        .expect("start execution")
+}
+
+fn execution_url(server: &TestServer, accepted: &reqwest::Response) -> String {
+    assert_eq!(accepted.status(), reqwest::StatusCode::ACCEPTED);
+    let location = accepted.headers()[reqwest::header::LOCATION]
+        .to_str()
+        .unwrap();
+    format!("{}{}", server.base_url(), location)
+}
+
+/// Poll the execution until `done` holds for it
+async fn poll_until(url: &str, done: impl Fn(&Value) -> bool) -> Value {
+    for _ in 0..100 {
+        let exec: Value = reqwest::get(url).await.unwrap().json().await.unwrap();
+        if done(&exec) {
+            return exec;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("execution never got there");
+}
+
+fn io_control_requests(mock: &MockTransportAdapter) -> Vec<Vec<u8>> {
+    mock.sent_requests()
+        .into_iter()
+        .filter(|r| r.first() == Some(&0x2F))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn ramp_sends_every_step_and_holds_the_end_value() {
+    let (server, mock) = server().await;
+    let accepted = start(
+        &server,
+        serde_json::json!({
+            "action": "ramp", "start": 0, "end": 40, "duration_ms": 200, "step_ms": 100
+        }),
+    )
+    .await;
+    let url = execution_url(&server, &accepted);
+
+    let exec = poll_until(&url, |exec| exec["status"] != "running").await;
+    assert_eq!(exec["status"], "completed", "{exec}");
+    assert_eq!(
+        exec["result"],
+        serde_json::json!({ "sent": 3, "total": 3, "value": 40 })
+    );
+    assert_eq!(
+        io_control_requests(&mock),
+        [
+            vec![0x2F, 0xF0, 0x04, 0x03, 0x00],
+            vec![0x2F, 0xF0, 0x04, 0x03, 0x14],
+            vec![0x2F, 0xF0, 0x04, 0x03, 0x28],
+        ]
+    );
+}
+
+#[tokio::test]
+async fn cancelled_sequence_returns_control_to_ecu() {
+    let (server, mock) = server().await;
+    let accepted = start(
+        &server,
+        serde_json::json!({
+            "action": "sequence",
+            "setpoints": [
+                { "value": 10, "hold_ms": 50 },
+                { "value": 20, "hold_ms": 50 }
+            ],
+            "repeat": 0
+        }),
+    )
+    .await;
+    let url = execution_url(&server, &accepted);
+
+    // Repeats until cancelled: progress has no total
+    let exec = poll_until(&url, |exec| exec["result"]["sent"].as_u64() >= Some(2)).await;
+    assert_eq!(exec["status"], "running", "{exec}");
+    assert_eq!(exec["result"]["total"], Value::Null, "{exec}");
+
+    let resp = reqwest::Client::new().delete(&url).send().await.unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::NO_CONTENT);
+
+    let exec = poll_until(&url, |exec| exec["status"] != "running").await;
+    assert_eq!(exec["status"], "stopped", "{exec}");
+    let requests = io_control_requests(&mock);
+    assert_eq!(requests.last().unwrap(), &vec![0x2F, 0xF0, 0x04, 0x00]);
+
+    // Nothing is sent after control went back to the ECU
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(io_control_requests(&mock).len(), requests.len());
+}
+
+#[tokio::test]
+async fn out_of_range_setpoint_refuses_the_profile() {
+    let (server, mock) = server().await;
+    let resp = start(
+        &server,
+        serde_json::json!({
+            "action": "sequence",
+            "setpoints": [
+                { "value": 50, "hold_ms": 100 },
+                { "value": 150, "hold_ms": 100 }
+            ]
+        }),
+    )
+    .await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+    assert!(io_control_requests(&mock).is_empty());
+}
@@ -0,0 +1,158 @@
+//! Output `min`/`max` on short-term adjustment — in-process router tests.
+//!
+//! Adjusting an I/O output (an operation execution with
+//! `{action: "short_term_adjust", value}`) checks the physical value against
+//! the output's configured range before any 0x2F is sent:
+//!   * in range → accepted, the result echoes the physical value and the raw
+//!     bytes sent;
+//!   * above `max` with `range_policy = reject` → 400, nothing sent;
+//!   * above `max` with `range_policy = clamp` → `max` is sent instead.
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors `memory.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_uds::config::{DataType, OutputConfig, RangePolicy};
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::{UdsBackend, UdsBackendConfig};
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// 0–100 % duty cycle over a uint8 (raw 255 = 100 %)
+fn pwm_output(id: &str, ioid: &str, range_policy: RangePolicy) -> OutputConfig {
+    OutputConfig {
+        id: id.to_string(),
+        name: id.to_string(),
+        ioid: ioid.to_string(),
+        default_value: "80".to_string(),
+        description: None,
+        security_level: 0,
+        data_type: Some(DataType::Uint8),
+        unit: Some("%".to_string()),
+        scale: 0.392157,
+        offset: 0.0,
+        min: Some(0.0),
+        max: Some(100.0),
+        allowed: vec![],
+        range_policy,
+    }
+}
+
+/// UDS ECU with a rejecting `pwm` (0xF004) and a clamping `pwm_clamp`
+/// (0xF005) output
+async fn server() -> (TestServer, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(vec![0x2F, 0xF0, 0x04, 0x03], vec![0x6F, 0xF0, 0x04, 0x03]);
+    mock.add_response(vec![0x2F, 0xF0, 0x05, 0x03], vec![0x6F, 0xF0, 0x05, 0x03]);
+    let config = UdsBackendConfig {
+        outputs: vec![
+            pwm_output("pwm", "0xF004", RangePolicy::Reject),
+            pwm_output("pwm_clamp", "0xF005", RangePolicy::Clamp),
+        ],
+        ..common::ecu_config("ecu", "Output ECU")
+    };
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+
+    let server = common::server(vec![("ecu", Arc::new(backend))]).await;
+    (server, mock)
+}
+
+/// Start a short-term adjustment of `output` to `value`
+async fn adjust(server: &TestServer, output: &str, value: serde_json::Value) -> reqwest::Response {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/operations/{output}/executions",
+        server.base_url()
+    );
+    reqwest::Client::new()
+        .post(url)
+        .json(&serde_json::json!({
+            "parameters": { "action": "short_term_adjust", "value": value },
+        }))
+        .send()
+        .await
+        .expect("start execution")
+}
+
+/// Poll the accepted execution until it leaves `running`, returning its result
+async fn completed_result(server: &TestServer, accepted: reqwest::Response) -> serde_json::Value {
+    assert_eq!(accepted.status(), reqwest::StatusCode::ACCEPTED);
+    let location = accepted.headers()[reqwest::header::LOCATION]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let url = format!("{}{}", server.base_url(), location);
+    for _ in 0..50 {
+        let exec: serde_json::Value = reqwest::get(&url).await.unwrap().json().await.unwrap();
+        if exec["status"] != "running" {
+            assert_eq!(exec["status"], "completed", "{exec}");
+            return exec["result"].clone();
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    panic!("execution still running");
+}
+
+fn io_control_requests(mock: &MockTransportAdapter) -> Vec<Vec<u8>> {
+    mock.sent_requests()
+        .into_iter()
+        .filter(|r| r.first() == Some(&0x2F))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn adjust_within_range_is_sent() {
+    let (server, mock) = server().await;
+    let result = completed_result(
+        &server,
+        adjust(&server, "pwm", serde_json::json!(50.0)).await,
+    )
+    .await;
+
+    assert_eq!(result["success"], true, "{result}");
+    assert_eq!(result["raw_sent"], "7f", "{result}");
+    let value = result["value"].as_f64().expect("physical value");
+    assert!((value - 49.8).abs() < 0.1, "{result}");
+    assert_eq!(
+        io_control_requests(&mock),
+        vec![vec![0x2F, 0xF0, 0x04, 0x03, 0x7F]]
+    );
+}
+
+#[tokio::test]
+async fn adjust_above_max_is_rejected() {
+    let (server, mock) = server().await;
+    let resp = adjust(&server, "pwm", serde_json::json!(150)).await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+    let body = resp.text().await.unwrap();
+    assert!(body.contains("[0, 100]"), "error names the range: {body}");
+    assert!(io_control_requests(&mock).is_empty());
+}
+
+#[tokio::test]
+async fn adjust_above_max_is_clamped() {
+    let (server, mock) = server().await;
+    let result = completed_result(
+        &server,
+        adjust(&server, "pwm_clamp", serde_json::json!(150)).await,
+    )
+    .await;
+
+    assert_eq!(result["success"], true, "{result}");
+    assert_eq!(result["raw_sent"], "ff", "{result}");
+    let value = result["value"].as_f64().expect("physical value");
+    assert!((value - 100.0).abs() < 0.01, "{result}");
+    assert_eq!(
+        io_control_requests(&mock),
+        vec![vec![0x2F, 0xF0, 0x05, 0x03, 0xFF]]
+    );
+}
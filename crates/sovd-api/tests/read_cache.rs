@@ -0,0 +1,332 @@
+//! Read cache for `cacheable` DIDs — in-process router tests.
+//!
+//! The VIN (0xF190) is defined `cacheable`, coolant temperature (0xF405) is
+//! not. The backend counts its raw reads:
+//!   * repeated VIN reads hit the bus once; coolant reads every time;
+//!   * `Cache-Control: no-cache` forces a bus read;
+//!   * a write to the DID, a session change and an expired TTL each make
+//!     the next read go to the bus again, as do a write through the gateway
+//!     the ECU sits behind and a session the ECU dropped on its own (a new
+//!     session epoch);
+//!   * polling faster than the TTL does not keep an entry alive;
+//!   * `?ids=` batch reads share the cache with single reads.
+//!
+//! Mirrors the mock-backend pattern from `clear_auto_session.rs`.
+
+mod common;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use sovd_client::testing::TestServer;
+use sovd_conv::types::DataType;
+use sovd_conv::{DidDefinition, DidStore};
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo, SessionMode,
+};
+
+use sovd_api::{create_router, AppState, ReadCacheConfig};
+
+// ---------------------------------------------------------------------------
+// Mock backend
+// ---------------------------------------------------------------------------
+
+struct EcuBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    /// DID of every raw read
+    reads: Mutex<Vec<u16>>,
+    session_epoch: AtomicU64,
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for EcuBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn read_raw_did(&self, did: u16) -> BackendResult<Vec<u8>> {
+        self.reads.lock().unwrap().push(did);
+        match did {
+            0xF190 => Ok(b"WVWZZZ1JZXW000001".to_vec()),
+            _ => Ok(vec![132]),
+        }
+    }
+    async fn read_parameters_batch(
+        &self,
+        ids: &[String],
+    ) -> Vec<(String, BackendResult<DataValue>)> {
+        let mut results = Vec::with_capacity(ids.len());
+        for id in ids {
+            let did = u16::from_str_radix(id, 16).expect("batch reads DIDs as hex");
+            let value = self.read_raw_did(did).await.map(|bytes| DataValue {
+                raw: Some(hex::encode(bytes)),
+                ..DataValue::new(id.clone(), id.clone(), serde_json::Value::Null)
+            });
+            results.push((id.clone(), value));
+        }
+        results
+    }
+    async fn write_raw_did(&self, _did: u16, _data: &[u8]) -> BackendResult<()> {
+        Ok(())
+    }
+    async fn set_session_mode(&self, session: &str) -> BackendResult<SessionMode> {
+        Ok(SessionMode {
+            mode: "session".to_string(),
+            session: session.to_string(),
+            session_id: 0x03,
+            timing: None,
+        })
+    }
+    fn session_epoch(&self) -> Option<u64> {
+        Some(self.session_epoch.load(Ordering::SeqCst))
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn store() -> Arc<DidStore> {
+    let store = DidStore::new();
+    let mut vin = DidDefinition::scalar(DataType::String).with_id("vin");
+    vin.cacheable = true;
+    vin.writable = true;
+    vin.component_id = Some("ecu".to_string());
+    store.register(0xF190, vin);
+    store.register(
+        0xF405,
+        DidDefinition::scaled(DataType::Uint8, 1.0, -40.0).with_id("coolant_temp"),
+    );
+    Arc::new(store)
+}
+
+async fn server(config: ReadCacheConfig) -> (TestServer, Arc<EcuBackend>) {
+    let backend = Arc::new(EcuBackend {
+        info: EntityInfo {
+            id: "ecu".to_string(),
+            name: "ecu ECU".to_string(),
+            entity_type: "ecu".to_string(),
+            description: None,
+            href: "/vehicle/v1/components/ecu".to_string(),
+            status: Some("online".to_string()),
+            display_name: None,
+            category: None,
+        },
+        capabilities: Capabilities::default(),
+        reads: Mutex::new(Vec::new()),
+        session_epoch: AtomicU64::new(0),
+    });
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu".to_string(), backend.clone());
+    // The same ECU, also reachable behind a gateway
+    backends.insert("gw".to_string(), common::gateway(backend.clone()));
+    let state = AppState::with_did_store(backends, store()).with_read_cache(config);
+    let server = TestServer::start(create_router(state))
+        .await
+        .expect("test server");
+    (server, backend)
+}
+
+async fn read(server: &TestServer, param: &str, no_cache: bool) -> serde_json::Value {
+    let mut request = reqwest::Client::new().get(format!(
+        "{}/vehicle/v1/components/ecu/data/{param}",
+        server.base_url()
+    ));
+    if no_cache {
+        request = request.header(reqwest::header::CACHE_CONTROL, "no-cache");
+    }
+    let resp = request.send().await.expect("read");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    resp.json().await.unwrap()
+}
+
+async fn put(server: &TestServer, path: &str, body: serde_json::Value) {
+    let resp = reqwest::Client::new()
+        .put(format!(
+            "{}/vehicle/v1/components/ecu/{path}",
+            server.base_url()
+        ))
+        .json(&body)
+        .send()
+        .await
+        .expect("put");
+    assert!(resp.status().is_success(), "PUT {path}: {}", resp.status());
+}
+
+fn reads_of(backend: &EcuBackend, did: u16) -> usize {
+    backend
+        .reads
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|&&d| d == did)
+        .count()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn cacheable_did_is_read_once() {
+    let (server, backend) = server(ReadCacheConfig::default()).await;
+
+    for _ in 0..3 {
+        let body = read(&server, "vin", false).await;
+        assert_eq!(body["value"], "WVWZZZ1JZXW000001", "{body}");
+        read(&server, "coolant_temp", false).await;
+    }
+
+    assert_eq!(reads_of(&backend, 0xF190), 1);
+    assert_eq!(reads_of(&backend, 0xF405), 3);
+}
+
+#[tokio::test]
+async fn no_cache_header_bypasses_cache() {
+    let (server, backend) = server(ReadCacheConfig::default()).await;
+
+    read(&server, "vin", false).await;
+    read(&server, "vin", true).await;
+
+    assert_eq!(reads_of(&backend, 0xF190), 2);
+}
+
+#[tokio::test]
+async fn write_invalidates_cached_did() {
+    let (server, backend) = server(ReadCacheConfig::default()).await;
+    read(&server, "vin", false).await;
+
+    put(
+        &server,
+        "data/vin",
+        serde_json::json!({ "value": "WVWZZZ1JZXW000002" }),
+    )
+    .await;
+    read(&server, "vin", false).await;
+
+    assert_eq!(reads_of(&backend, 0xF190), 2);
+}
+
+#[tokio::test]
+async fn session_change_invalidates_cache() {
+    let (server, backend) = server(ReadCacheConfig::default()).await;
+    read(&server, "vin", false).await;
+
+    put(
+        &server,
+        "modes/session",
+        serde_json::json!({ "value": "extended" }),
+    )
+    .await;
+    read(&server, "vin", false).await;
+
+    assert_eq!(reads_of(&backend, 0xF190), 2);
+}
+
+#[tokio::test]
+async fn expired_entry_is_read_again() {
+    let (server, backend) = server(ReadCacheConfig { ttl_secs: 0 }).await;
+
+    read(&server, "vin", false).await;
+    read(&server, "vin", false).await;
+
+    assert_eq!(reads_of(&backend, 0xF190), 2);
+}
+
+#[tokio::test]
+async fn write_through_gateway_invalidates_cached_did() {
+    let (server, backend) = server(ReadCacheConfig::default()).await;
+    read(&server, "vin", false).await;
+
+    let resp = reqwest::Client::new()
+        .put(format!(
+            "{}/vehicle/v1/components/gw/apps/ecu/data/vin",
+            server.base_url()
+        ))
+        .json(&serde_json::json!({ "value": "WVWZZZ1JZXW000002" }))
+        .send()
+        .await
+        .expect("put");
+    assert!(resp.status().is_success(), "{}", resp.status());
+    read(&server, "vin", false).await;
+
+    assert_eq!(reads_of(&backend, 0xF190), 2);
+}
+
+#[tokio::test]
+async fn session_lost_by_the_ecu_invalidates_cache() {
+    let (server, backend) = server(ReadCacheConfig::default()).await;
+    read(&server, "vin", false).await;
+
+    // S3 lapsed: the backend counts a new session epoch
+    backend.session_epoch.fetch_add(1, Ordering::SeqCst);
+    read(&server, "vin", false).await;
+    read(&server, "vin", false).await;
+
+    assert_eq!(reads_of(&backend, 0xF190), 2);
+}
+
+#[tokio::test]
+async fn polling_does_not_extend_the_ttl() {
+    let (server, backend) = server(ReadCacheConfig { ttl_secs: 1 }).await;
+
+    // Well within the TTL between polls, well past it overall
+    for _ in 0..6 {
+        read(&server, "vin", false).await;
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    }
+
+    assert_eq!(reads_of(&backend, 0xF190), 2);
+}
+
+#[tokio::test]
+async fn batch_reads_share_the_cache() {
+    let (server, backend) = server(ReadCacheConfig::default()).await;
+    let batch = |no_cache: bool| {
+        let mut request = reqwest::Client::new().get(format!(
+            "{}/vehicle/v1/components/ecu/data?ids=vin,coolant_temp",
+            server.base_url()
+        ));
+        if no_cache {
+            request = request.header(reqwest::header::CACHE_CONTROL, "no-cache");
+        }
+        request.send()
+    };
+
+    read(&server, "vin", false).await;
+    let body: serde_json::Value = batch(false).await.expect("batch").json().await.unwrap();
+    assert_eq!(body["items"][0]["value"], "WVWZZZ1JZXW000001", "{body}");
+    assert_eq!(reads_of(&backend, 0xF190), 1, "served from the single read");
+    assert_eq!(reads_of(&backend, 0xF405), 1);
+
+    batch(true).await.expect("batch");
+    read(&server, "vin", false).await;
+    assert_eq!(
+        reads_of(&backend, 0xF190),
+        2,
+        "single read served from the batch"
+    );
+}
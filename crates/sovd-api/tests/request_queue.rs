@@ -0,0 +1,101 @@
+//! Per-ECU request queue — in-process router tests.
+//!
+//! Requests to one UDS ECU go out one at a time:
+//!   * concurrent reads of one ECU are sent one after the other;
+//!   * reads of different ECUs still run side by side;
+//!   * a request finding `request_queue.max_depth` others waiting is
+//!     refused with 503 instead of queueing.
+//!
+//! Drives real `UdsBackend`s over a mock transport with latency; mirrors
+//! `topology.rs`.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use sovd_client::testing::TestServer;
+use sovd_core::DiagnosticBackend;
+use sovd_uds::config::{MockConfig, RequestQueueConfig};
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::{UdsBackend, UdsBackendConfig};
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Time the mock ECU takes to answer each request
+const LATENCY: Duration = Duration::from_millis(200);
+
+fn ecu(id: &str, max_depth: usize) -> Arc<dyn DiagnosticBackend> {
+    let mock = Arc::new(MockTransportAdapter::new(&MockConfig {
+        latency_ms: LATENCY.as_millis() as u64,
+    }));
+    mock.add_response(vec![0x22, 0xF4, 0x05], vec![0x62, 0xF4, 0x05, 0x5A]);
+    let config = UdsBackendConfig {
+        request_queue: RequestQueueConfig { max_depth },
+        ..common::ecu_config(id, id)
+    };
+    Arc::new(UdsBackend::with_transport(config, mock).expect("uds backend"))
+}
+
+/// Read DID 0xF405 of each component at once; statuses in `components` order
+async fn read_all(server: &TestServer, components: &[&str]) -> Vec<u16> {
+    let client = reqwest::Client::new();
+    let reads = components.iter().map(|component| {
+        let url = format!(
+            "{}/vehicle/v1/components/{}/data/F405",
+            server.base_url(),
+            component
+        );
+        let client = client.clone();
+        async move {
+            client
+                .get(url)
+                .send()
+                .await
+                .expect("read")
+                .status()
+                .as_u16()
+        }
+    });
+    futures::future::join_all(reads).await
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn reads_of_one_ecu_take_turns() {
+    let server = common::server(vec![("ecu", ecu("ecu", 16))]).await;
+
+    let started = Instant::now();
+    let statuses = read_all(&server, &["ecu", "ecu", "ecu"]).await;
+    assert_eq!(statuses, [200, 200, 200]);
+    assert!(started.elapsed() >= LATENCY * 3, "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn different_ecus_are_read_side_by_side() {
+    let server = common::server(vec![
+        ("engine", ecu("engine", 0)),
+        ("brakes", ecu("brakes", 0)),
+    ])
+    .await;
+
+    let started = Instant::now();
+    let statuses = read_all(&server, &["engine", "brakes"]).await;
+    assert_eq!(statuses, [200, 200]);
+    assert!(started.elapsed() < LATENCY * 2, "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn full_queue_is_service_unavailable() {
+    let server = common::server(vec![("ecu", ecu("ecu", 1))]).await;
+
+    // One on the wire, one waiting, the third refused
+    let mut statuses = read_all(&server, &["ecu", "ecu", "ecu"]).await;
+    statuses.sort();
+    assert_eq!(statuses, [200, 200, 503]);
+}
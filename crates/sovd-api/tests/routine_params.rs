@@ -0,0 +1,174 @@
+//! Structured and raw option records on routine executions — in-process
+//! router tests.
+//!
+//! A RoutineControl execution can be started from:
+//!   * `{params: {...}}` — named values encoded via the operation's
+//!     `params_def`;
+//!   * `{raw_option_record: "<hex>"}` — the option record sent as-is.
+//!
+//! Both send `31 01 <rid> <record>`, so equivalent inputs produce the same
+//! request bytes; supplying both is a 400 and nothing is sent.
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors
+//! `output_range.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_uds::config::{DataType, OperationConfig, RoutineParamConfig};
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::{UdsBackend, UdsBackendConfig};
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn param(name: &str, data_type: DataType, scale: f64) -> RoutineParamConfig {
+    RoutineParamConfig {
+        name: name.to_string(),
+        data_type,
+        scale,
+        offset: 0.0,
+        length: None,
+    }
+}
+
+/// UDS ECU with `set_idle_target` (RID 0x0210): a uint8 mode followed by a
+/// uint16 target in 0.5 rpm steps
+async fn server() -> (TestServer, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(
+        vec![0x31, 0x01, 0x02, 0x10],
+        vec![0x71, 0x01, 0x02, 0x10, 0x00],
+    );
+    let config = UdsBackendConfig {
+        operations: vec![OperationConfig {
+            id: "set_idle_target".to_string(),
+            name: "Set idle target".to_string(),
+            rid: "0x0210".to_string(),
+            description: None,
+            security_level: 0,
+            required_session: None,
+            result_faults: vec![],
+            params_def: vec![
+                param("mode", DataType::Uint8, 1.0),
+                param("target_rpm", DataType::Uint16, 0.5),
+            ],
+        }],
+        ..common::ecu_config("ecu", "Routine ECU")
+    };
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+
+    let server = common::server(vec![("ecu", Arc::new(backend))]).await;
+    (server, mock)
+}
+
+async fn execute(server: &TestServer, body: serde_json::Value) -> reqwest::Response {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/operations/set_idle_target/executions",
+        server.base_url()
+    );
+    reqwest::Client::new()
+        .post(url)
+        .json(&body)
+        .send()
+        .await
+        .expect("start execution")
+}
+
+/// Poll the accepted execution until it leaves `running`
+async fn wait_completed(server: &TestServer, accepted: reqwest::Response) {
+    assert_eq!(accepted.status(), reqwest::StatusCode::ACCEPTED);
+    let location = accepted.headers()[reqwest::header::LOCATION]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let url = format!("{}{}", server.base_url(), location);
+    for _ in 0..50 {
+        let exec: serde_json::Value = reqwest::get(&url).await.unwrap().json().await.unwrap();
+        if exec["status"] != "running" {
+            assert_eq!(exec["status"], "completed", "{exec}");
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    panic!("execution still running");
+}
+
+fn routine_requests(mock: &MockTransportAdapter) -> Vec<Vec<u8>> {
+    mock.sent_requests()
+        .into_iter()
+        .filter(|r| r.first() == Some(&0x31))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn structured_params_are_encoded_via_params_def() {
+    let (server, mock) = server().await;
+    let resp = execute(
+        &server,
+        serde_json::json!({ "params": { "mode": 1, "target_rpm": 850 } }),
+    )
+    .await;
+    wait_completed(&server, resp).await;
+
+    // 850 rpm / 0.5 = 1700 = 0x06A4
+    assert_eq!(
+        routine_requests(&mock),
+        vec![vec![0x31, 0x01, 0x02, 0x10, 0x01, 0x06, 0xA4]]
+    );
+}
+
+#[tokio::test]
+async fn equivalent_params_and_raw_option_record_send_the_same_bytes() {
+    let (server, mock) = server().await;
+    let resp = execute(
+        &server,
+        serde_json::json!({ "params": { "mode": 1, "target_rpm": 850 } }),
+    )
+    .await;
+    wait_completed(&server, resp).await;
+    let resp = execute(
+        &server,
+        serde_json::json!({ "raw_option_record": "0106A4" }),
+    )
+    .await;
+    wait_completed(&server, resp).await;
+
+    let sent = routine_requests(&mock);
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0], sent[1]);
+}
+
+#[tokio::test]
+async fn params_and_raw_option_record_together_are_rejected() {
+    let (server, mock) = server().await;
+    let resp = execute(
+        &server,
+        serde_json::json!({
+            "params": { "mode": 1, "target_rpm": 850 },
+            "raw_option_record": "0106A4",
+        }),
+    )
+    .await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+    assert!(routine_requests(&mock).is_empty());
+}
+
+#[tokio::test]
+async fn params_not_matching_params_def_are_rejected() {
+    let (server, mock) = server().await;
+    let resp = execute(&server, serde_json::json!({ "params": { "mode": 1 } })).await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+    let body = resp.text().await.unwrap();
+    assert!(body.contains("target_rpm"), "error names the field: {body}");
+    assert!(routine_requests(&mock).is_empty());
+}
@@ -0,0 +1,166 @@
+//! Session and security preconditions of routines — in-process router tests.
+//!
+//! `flash_prep` (RID 0x0300) is configured with `required_session =
+//! "programming"` and `security_level = 1`. Executions are refused before
+//! the 202, naming the unmet precondition, and nothing reaches the ECU:
+//!   * outside the programming session (here extended) with 409
+//!     `precondition-not-fulfilled`;
+//!   * in the programming session but locked with 401;
+//!   * with both met the routine runs.
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors
+//! `routine_params.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_uds::config::OperationConfig;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::{UdsBackend, UdsBackendConfig};
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// UDS ECU whose `flash_prep` routine needs the programming session and
+/// security level 1; no transparent unlock
+async fn server() -> (TestServer, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(vec![0x10, 0x02], vec![0x50, 0x02, 0x00, 0x19, 0x01, 0xF4]);
+    mock.add_response(vec![0x27, 0x01], vec![0x67, 0x01, 0xAA, 0xBB]);
+    mock.add_response(vec![0x27, 0x02], vec![0x67, 0x02]);
+    mock.add_response(
+        vec![0x31, 0x01, 0x03, 0x00],
+        vec![0x71, 0x01, 0x03, 0x00, 0x00],
+    );
+    let config = UdsBackendConfig {
+        operations: vec![OperationConfig {
+            id: "flash_prep".to_string(),
+            name: "Prepare flash".to_string(),
+            rid: "0x0300".to_string(),
+            description: None,
+            security_level: 1,
+            required_session: Some("programming".to_string()),
+            result_faults: vec![],
+            params_def: vec![],
+        }],
+        ..common::ecu_config("ecu", "Routine ECU")
+    };
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+
+    let server = common::server(vec![("ecu", Arc::new(backend))]).await;
+    (server, mock)
+}
+
+async fn put_mode(server: &TestServer, mode: &str, body: serde_json::Value) {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/modes/{mode}",
+        server.base_url()
+    );
+    let resp = reqwest::Client::new()
+        .put(url)
+        .json(&body)
+        .send()
+        .await
+        .expect("put mode");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK, "PUT modes/{mode}");
+}
+
+async fn execute(server: &TestServer) -> reqwest::Response {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/operations/flash_prep/executions",
+        server.base_url()
+    );
+    reqwest::Client::new()
+        .post(url)
+        .json(&serde_json::json!({}))
+        .send()
+        .await
+        .expect("start execution")
+}
+
+fn routine_requests(mock: &MockTransportAdapter) -> usize {
+    mock.sent_requests()
+        .iter()
+        .filter(|r| r.first() == Some(&0x31))
+        .count()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn wrong_session_is_refused() {
+    let (server, mock) = server().await;
+    put_mode(
+        &server,
+        "session",
+        serde_json::json!({ "value": "extended" }),
+    )
+    .await;
+
+    let resp = execute(&server).await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::CONFLICT);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["error_code"], "precondition-not-fulfilled", "{body}");
+    let message = body["message"].as_str().unwrap();
+    assert!(message.contains("programming session"), "{message}");
+    assert_eq!(routine_requests(&mock), 0);
+}
+
+#[tokio::test]
+async fn locked_security_is_refused() {
+    let (server, mock) = server().await;
+    put_mode(
+        &server,
+        "session",
+        serde_json::json!({ "value": "programming" }),
+    )
+    .await;
+
+    let resp = execute(&server).await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::UNAUTHORIZED);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    let message = body["message"].as_str().unwrap();
+    assert!(message.contains("level 1"), "{message}");
+    assert_eq!(routine_requests(&mock), 0);
+}
+
+#[tokio::test]
+async fn runs_with_session_and_security_met() {
+    let (server, mock) = server().await;
+    put_mode(
+        &server,
+        "session",
+        serde_json::json!({ "value": "programming" }),
+    )
+    .await;
+    put_mode(
+        &server,
+        "security",
+        serde_json::json!({ "value": "level1_requestseed" }),
+    )
+    .await;
+    put_mode(
+        &server,
+        "security",
+        serde_json::json!({ "value": "level1", "key": "5544" }),
+    )
+    .await;
+
+    let resp = execute(&server).await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::ACCEPTED);
+    for _ in 0..50 {
+        if routine_requests(&mock) > 0 {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    panic!("routine never started");
+}
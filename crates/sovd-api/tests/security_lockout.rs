@@ -0,0 +1,91 @@
+//! Security access lockout on `modes/security` — in-process router tests.
+//!
+//! Once the ECU answers a key with exceededNumberOfAttempts (NRC 0x36), the
+//! server locks security access out for `lockout_ms`:
+//!   * the key request itself answers 429 with a `Retry-After` header;
+//!   * further seed requests are refused with 429 without reaching the ECU.
+//!
+//! Drives a real `UdsBackend` over the mock transport; mirrors
+//! `session_modes.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::UdsBackend;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// UDS ECU that issues a seed and rejects every key with NRC 0x36
+async fn server() -> (TestServer, Arc<MockTransportAdapter>) {
+    let mock = common::mock();
+    mock.add_response(vec![0x27, 0x01], vec![0x67, 0x01, 0xAA, 0xBB]);
+    mock.add_response(vec![0x27, 0x02], vec![0x7F, 0x27, 0x36]);
+    let mut config = common::ecu_config("ecu", "Locked ECU");
+    config.sessions.security_handshake.lockout_ms = 2500;
+    let backend = UdsBackend::with_transport(config, mock.clone()).expect("uds backend");
+
+    let server = common::server(vec![("ecu", Arc::new(backend))]).await;
+    (server, mock)
+}
+
+async fn put_security(server: &TestServer, body: serde_json::Value) -> reqwest::Response {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/modes/security",
+        server.base_url()
+    );
+    reqwest::Client::new()
+        .put(url)
+        .json(&body)
+        .send()
+        .await
+        .expect("put security mode")
+}
+
+fn seed_requests(mock: &MockTransportAdapter) -> usize {
+    mock.sent_requests()
+        .iter()
+        .filter(|r| r.as_slice() == [0x27, 0x01])
+        .count()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn exceeded_attempts_answers_429_with_retry_after() {
+    let (server, mock) = server().await;
+
+    let resp = put_security(
+        &server,
+        serde_json::json!({ "value": "level1_requestseed" }),
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+
+    let resp = put_security(
+        &server,
+        serde_json::json!({ "value": "level1", "key": "5544" }),
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+    // 2500 ms rounds up to whole seconds
+    assert_eq!(resp.headers()[reqwest::header::RETRY_AFTER], "3");
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["vendor_code"], "security-lockout", "{body}");
+
+    // Refused locally until the lockout has passed
+    let resp = put_security(
+        &server,
+        serde_json::json!({ "value": "level1_requestseed" }),
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+    assert!(resp.headers().contains_key(reqwest::header::RETRY_AFTER));
+    assert_eq!(seed_requests(&mock), 1);
+}
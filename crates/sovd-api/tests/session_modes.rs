@@ -0,0 +1,131 @@
+//! Configured session sub-functions on `modes/session` — in-process router tests.
+//!
+//! An ECU whose `[sessions]` config moves `extended_session` to 0x43 must
+//! get `10 43` for `PUT modes/session {"value": "extended"}` on every route
+//! that reaches it:
+//!   * the component route (`/components/{ecu}/modes/session`);
+//!   * the component route with `?target=` into a gateway;
+//!   * the sub-entity route (`/components/{gw}/apps/{ecu}/modes/session`);
+//!
+//! and the session reads back as `extended`, not as the raw byte. The P2/P2*
+//! the ECU advertises in its 0x10 response are reported as `x-sumo-timing`.
+//!
+//! Drives a real `UdsBackend` over the mock transport (see `common`).
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_core::DiagnosticBackend;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::UdsBackend;
+
+// ---------------------------------------------------------------------------
+// Backends
+// ---------------------------------------------------------------------------
+
+/// UDS ECU with a non-standard extended session (0x43)
+fn ecu() -> (Arc<UdsBackend>, Arc<MockTransportAdapter>) {
+    let mut config = common::ecu_config("ecu", "OEM ECU");
+    config.sessions.extended_session = 0x43;
+    common::uds_ecu(config)
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Server with the ECU registered directly (`ecu`) or behind a gateway (`gw`)
+async fn server(direct: bool) -> (TestServer, Arc<MockTransportAdapter>) {
+    let (ecu, mock) = ecu();
+    let backends = if direct {
+        vec![("ecu", ecu as Arc<dyn DiagnosticBackend>)]
+    } else {
+        vec![("gw", common::gateway(ecu))]
+    };
+    (common::server(backends).await, mock)
+}
+
+async fn put_extended(server: &TestServer, path: &str) -> serde_json::Value {
+    let url = format!("{}{}", server.base_url(), path);
+    let resp = reqwest::Client::new()
+        .put(url)
+        .json(&serde_json::json!({ "value": "extended" }))
+        .send()
+        .await
+        .expect("put session");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK, "PUT {path}");
+    resp.json().await.expect("json")
+}
+
+fn assert_sent_0x43(mock: &MockTransportAdapter, path: &str) {
+    let sent = mock.sent_requests();
+    assert!(
+        sent.contains(&vec![0x10, 0x43]),
+        "{path}: expected 10 43, sent {sent:02X?}"
+    );
+    assert!(
+        !sent.contains(&vec![0x10, 0x03]),
+        "{path}: standard 10 03 must not be sent, sent {sent:02X?}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn component_route_sends_configured_sub_function() {
+    let (server, mock) = server(true).await;
+    let path = "/vehicle/v1/components/ecu/modes/session";
+    let body = put_extended(&server, path).await;
+
+    assert_sent_0x43(&mock, path);
+    assert_eq!(body["value"], "extended", "{body}");
+
+    let url = format!("{}{}", server.base_url(), path);
+    let body: serde_json::Value = reqwest::get(url).await.unwrap().json().await.unwrap();
+    assert_eq!(body["value"], "extended", "reads back by name: {body}");
+}
+
+#[tokio::test]
+async fn target_query_sends_configured_sub_function() {
+    let (server, mock) = server(false).await;
+    let path = "/vehicle/v1/components/gw/modes/session?target=ecu";
+    let body = put_extended(&server, path).await;
+
+    assert_sent_0x43(&mock, path);
+    assert_eq!(body["value"], "extended", "{body}");
+}
+
+#[tokio::test]
+async fn sub_entity_route_sends_configured_sub_function() {
+    let (server, mock) = server(false).await;
+    let path = "/vehicle/v1/components/gw/apps/ecu/modes/session";
+    let body = put_extended(&server, path).await;
+
+    assert_sent_0x43(&mock, path);
+    assert_eq!(body["value"], "extended", "{body}");
+}
+
+#[tokio::test]
+async fn session_reports_advertised_timing() {
+    let (server, mock) = server(true).await;
+    let path = "/vehicle/v1/components/ecu/modes/session";
+    let url = format!("{}{}", server.base_url(), path);
+    let body: serde_json::Value = reqwest::get(&url).await.unwrap().json().await.unwrap();
+    assert!(body.get("x-sumo-timing").is_none(), "{body}");
+
+    // P2 50 ms, P2* 200 x 10 ms
+    mock.add_response(vec![0x10, 0x43], vec![0x50, 0x43, 0x00, 0x32, 0x00, 0xC8]);
+    let body = put_extended(&server, path).await;
+    assert_eq!(body["x-sumo-timing"]["p2_server_max_ms"], 50, "{body}");
+    assert_eq!(
+        body["x-sumo-timing"]["p2_star_server_max_ms"], 2000,
+        "{body}"
+    );
+
+    let body: serde_json::Value = reqwest::get(&url).await.unwrap().json().await.unwrap();
+    assert_eq!(body["x-sumo-timing"]["p2_server_max_ms"], 50, "{body}");
+}
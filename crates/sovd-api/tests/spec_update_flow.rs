@@ -64,6 +64,8 @@ impl MockBackend {
                 description: Some(format!("{shape} mock for spec-update tests")),
                 href: format!("/vehicle/v1/components/{id}"),
                 status: Some("online".into()),
+                display_name: None,
+                category: None,
             },
             capabilities: Capabilities {
                 software_update: true,
@@ -202,6 +204,7 @@ impl DiagnosticBackend for MockBackend {
                 percent: 100.0,
             }),
             error: None,
+            summary: None,
         })
     }
 
@@ -281,6 +284,7 @@ async fn spawn_with_watchdog(
     );
     let state = AppState::new(backends).with_updates_config(UpdatesConfig {
         orchestrated_watchdog: watchdog,
+        ..Default::default()
     });
     let router = create_router(state);
     let server = TestServer::start(router).await.expect("test server");
@@ -0,0 +1,221 @@
+//! `[subscriptions]` limits — in-process router tests.
+//!
+//! Covers `SubscriptionLimits` on `AppState`:
+//!   * an interval faster than `max_rate_hz` is rejected on create and on
+//!     re-time (PUT), naming the limit;
+//!   * a component at `max_subscriptions_per_component` (the cap on streamed
+//!     parameters, one resource per subscription) rejects the next create;
+//!   * a subscription inside every limit is created;
+//!   * a component with a streaming allowlist accepts listed parameters
+//!     (by id or hex DID) and answers 403 naming any other.
+//!
+//! Mirrors the `TestServer` pattern from `data_aliases.rs`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_conv::types::DataType;
+use sovd_conv::{DidDefinition, DidStore};
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+
+use sovd_api::{create_router, AppState, SubscriptionLimits};
+
+// ---------------------------------------------------------------------------
+// Mock backend
+// ---------------------------------------------------------------------------
+
+struct EcuBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+}
+
+impl EcuBackend {
+    fn new(id: &str) -> Self {
+        Self {
+            info: EntityInfo {
+                id: id.to_string(),
+                name: format!("{id} ECU"),
+                entity_type: "ecu".to_string(),
+                description: None,
+                href: format!("/vehicle/v1/components/{id}"),
+                status: Some("online".to_string()),
+                display_name: None,
+                category: None,
+            },
+            capabilities: Capabilities::default(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for EcuBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn store() -> Arc<DidStore> {
+    let store = DidStore::new();
+    store.register(
+        0xF40C,
+        DidDefinition::scaled(DataType::Uint16, 0.25, 0.0).with_id("engine_rpm"),
+    );
+    store.register(
+        0xF405,
+        DidDefinition::scaled(DataType::Uint8, 1.0, -40.0).with_id("coolant_temp"),
+    );
+    Arc::new(store)
+}
+
+/// `normal` (5 Hz) is the fastest accepted interval; two subscriptions per
+/// component.  Only `ecu_b` has a streaming allowlist (`engine_rpm`).
+async fn server() -> TestServer {
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu_a".to_string(), Arc::new(EcuBackend::new("ecu_a")));
+    backends.insert("ecu_b".to_string(), Arc::new(EcuBackend::new("ecu_b")));
+    let mut allowed_parameters = HashMap::new();
+    allowed_parameters.insert("ecu_b".to_string(), vec!["engine_rpm".to_string()]);
+    let state =
+        AppState::with_did_store(backends, store()).with_subscription_limits(SubscriptionLimits {
+            max_rate_hz: Some(5),
+            max_subscriptions_per_component: Some(2),
+            allowed_parameters,
+            normalize_units: HashMap::new(),
+        });
+    TestServer::start(create_router(state))
+        .await
+        .expect("test server")
+}
+
+async fn create(server: &TestServer, resource: &str, interval: &str) -> reqwest::Response {
+    create_on(server, "ecu_a", resource, interval).await
+}
+
+async fn create_on(
+    server: &TestServer,
+    component: &str,
+    resource: &str,
+    interval: &str,
+) -> reqwest::Response {
+    let url = format!(
+        "{}/vehicle/v1/components/{component}/cyclic-subscriptions",
+        server.base_url()
+    );
+    reqwest::Client::new()
+        .post(url)
+        .json(&serde_json::json!({ "resource": resource, "interval": interval }))
+        .send()
+        .await
+        .expect("create subscription")
+}
+
+async fn assert_rejected(resp: reqwest::Response, limit: &str) {
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+    let body = resp.text().await.unwrap();
+    assert!(body.contains(limit), "error must name {limit}: {body}");
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn subscription_within_limits_is_created() {
+    let server = server().await;
+    let resp = create(&server, "engine_rpm", "normal").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn rate_above_limit_is_rejected() {
+    let server = server().await;
+    assert_rejected(create(&server, "engine_rpm", "fast").await, "max_rate_hz").await;
+
+    // Re-timing an accepted subscription is held to the same limit.
+    let created: serde_json::Value = create(&server, "engine_rpm", "slow")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let url = format!(
+        "{}/vehicle/v1/components/ecu_a/cyclic-subscriptions/{}",
+        server.base_url(),
+        created["subscription_id"].as_str().unwrap()
+    );
+    let resp = reqwest::Client::new()
+        .put(url)
+        .json(&serde_json::json!({ "interval": "fast" }))
+        .send()
+        .await
+        .unwrap();
+    assert_rejected(resp, "max_rate_hz").await;
+}
+
+#[tokio::test]
+async fn parameter_count_above_limit_is_rejected() {
+    let server = server().await;
+    for resource in ["engine_rpm", "coolant_temp"] {
+        let resp = create(&server, resource, "slow").await;
+        assert_eq!(resp.status(), reqwest::StatusCode::CREATED, "{resource}");
+    }
+
+    assert_rejected(
+        create(&server, "F40C", "slow").await,
+        "max_subscriptions_per_component",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn allowlisted_parameter_is_created() {
+    let server = server().await;
+    for resource in ["engine_rpm", "F40C"] {
+        let resp = create_on(&server, "ecu_b", resource, "slow").await;
+        assert_eq!(resp.status(), reqwest::StatusCode::CREATED, "{resource}");
+    }
+}
+
+#[tokio::test]
+async fn parameter_outside_allowlist_is_forbidden() {
+    let server = server().await;
+    let resp = create_on(&server, "ecu_b", "coolant_temp", "slow").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::FORBIDDEN);
+    let body = resp.text().await.unwrap();
+    assert!(
+        body.contains("coolant_temp"),
+        "rejected parameter listed: {body}"
+    );
+
+    // No allowlist on ecu_a: the same parameter streams there.
+    let resp = create(&server, "coolant_temp", "slow").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+}
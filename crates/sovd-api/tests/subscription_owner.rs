@@ -0,0 +1,181 @@
+//! Cyclic subscriptions bound to their creating principal — in-process
+//! router tests.
+//!
+//! With authentication enabled, a subscription remembers the subject that
+//! created it:
+//!   * attaching to its SSE stream with another subject's token is 403;
+//!   * the creating subject attaches normally (200, `text/event-stream`).
+//!
+//! The injected authorizer maps each bearer token verbatim to a subject with
+//! full component scope; mirrors `on_change_subscription.rs`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sovd_api::{create_router, AccessRequest, AppState, Authorizer, ClientContext};
+use sovd_client::testing::TestServer;
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataPoint, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+use tokio::sync::broadcast;
+
+// ---------------------------------------------------------------------------
+// Authorizer + mock backend
+// ---------------------------------------------------------------------------
+
+/// Every bearer token is its own subject
+struct TokenIsSubject;
+
+#[async_trait::async_trait]
+impl Authorizer for TokenIsSubject {
+    async fn authorize(&self, req: &AccessRequest<'_>) -> Result<ClientContext, String> {
+        let token = req
+            .bearer
+            .and_then(|h| h.strip_prefix("Bearer "))
+            .ok_or("missing bearer token")?;
+        Ok(ClientContext {
+            subject: token.to_string(),
+            scopes: vec!["component:*".to_string()],
+        })
+    }
+}
+
+struct EcuBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    data_tx: broadcast::Sender<DataPoint>,
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for EcuBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![ParameterInfo {
+            id: "coolant_temp".to_string(),
+            name: "Coolant temperature".to_string(),
+            description: None,
+            unit: Some("degC".to_string()),
+            data_type: None,
+            read_only: true,
+            href: "/vehicle/v1/components/ecu/data/coolant_temp".to_string(),
+            did: None,
+            category: None,
+        }])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn subscribe_data(
+        &self,
+        _param_ids: &[String],
+        _rate_hz: u32,
+    ) -> BackendResult<broadcast::Receiver<DataPoint>> {
+        Ok(self.data_tx.subscribe())
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+async fn server() -> TestServer {
+    let backend = EcuBackend {
+        info: EntityInfo {
+            id: "ecu".to_string(),
+            name: "ecu ECU".to_string(),
+            entity_type: "ecu".to_string(),
+            description: None,
+            href: "/vehicle/v1/components/ecu".to_string(),
+            status: Some("online".to_string()),
+            display_name: None,
+            category: None,
+        },
+        capabilities: Capabilities::default(),
+        data_tx: broadcast::channel(16).0,
+    };
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu".to_string(), Arc::new(backend));
+    let state = AppState::new(backends).with_authorizer(Arc::new(TokenIsSubject));
+    TestServer::start(create_router(state))
+        .await
+        .expect("test server")
+}
+
+/// Create a subscription as `token`, returning its id
+async fn create(server: &TestServer, token: &str) -> String {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/cyclic-subscriptions",
+        server.base_url()
+    );
+    let resp = reqwest::Client::new()
+        .post(url)
+        .bearer_auth(token)
+        .json(&serde_json::json!({ "resource": "coolant_temp", "interval": "slow" }))
+        .send()
+        .await
+        .expect("create subscription");
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert!(body.get("owner").is_none(), "owner must not leak: {body}");
+    body["subscription_id"].as_str().unwrap().to_string()
+}
+
+async fn attach(server: &TestServer, sub_id: &str, token: &str) -> reqwest::Response {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/cyclic-subscriptions/{sub_id}",
+        server.base_url()
+    );
+    reqwest::Client::new()
+        .get(url)
+        .bearer_auth(token)
+        .header(reqwest::header::ACCEPT, "text/event-stream")
+        .send()
+        .await
+        .expect("attach to stream")
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn stream_refuses_a_different_principal() {
+    let server = server().await;
+    let sub_id = create(&server, "alice").await;
+
+    let resp = attach(&server, &sub_id, "bob").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn stream_admits_the_creating_principal() {
+    let server = server().await;
+    let sub_id = create(&server, "alice").await;
+
+    let resp = attach(&server, &sub_id, "alice").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let ct = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    assert!(ct.starts_with("text/event-stream"), "got {ct:?}");
+}
@@ -0,0 +1,173 @@
+//! Resumed marker on cyclic subscriptions — in-process router tests.
+//!
+//! A backend that re-armed a subscription after an interruption (the UDS
+//! backend after an ECU reset, with `subscription_recovery`) puts a
+//! `DataPoint::resumed()` marker into the stream. The backend here emits a
+//! sample, the marker and another sample:
+//!   * the marker arrives as an envelope with `x-sumo-event: resumed` and
+//!     neither payload nor error, over SSE and WebSocket alike;
+//!   * the samples around it keep contiguous sequence numbers.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use sovd_client::testing::TestServer;
+use sovd_client::{StreamEvent, Subscription, SubscriptionInterval};
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataPoint, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+use tokio::sync::broadcast;
+
+use sovd_api::{create_router, AppState};
+
+// ---------------------------------------------------------------------------
+// Mock backend
+// ---------------------------------------------------------------------------
+
+struct EcuBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    /// Keeps the channels open so the streams don't end
+    senders: Mutex<Vec<broadcast::Sender<DataPoint>>>,
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for EcuBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![ParameterInfo {
+            id: "coolant_temp".to_string(),
+            name: "Coolant temperature".to_string(),
+            description: None,
+            unit: Some("degC".to_string()),
+            data_type: None,
+            read_only: true,
+            href: "/vehicle/v1/components/ecu/data/coolant_temp".to_string(),
+            did: None,
+            category: None,
+        }])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn subscribe_data(
+        &self,
+        _param_ids: &[String],
+        _rate_hz: u32,
+    ) -> BackendResult<broadcast::Receiver<DataPoint>> {
+        let (tx, rx) = broadcast::channel(16);
+        let _ = tx.send(sample(40.0));
+        let _ = tx.send(DataPoint::resumed());
+        let _ = tx.send(sample(41.0));
+        self.senders.lock().unwrap().push(tx);
+        Ok(rx)
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn sample(value: f64) -> DataPoint {
+    DataPoint {
+        id: "coolant_temp".to_string(),
+        value: serde_json::json!(value),
+        unit: None,
+        timestamp: chrono::Utc::now(),
+    }
+}
+
+async fn server() -> TestServer {
+    let backend = Arc::new(EcuBackend {
+        info: EntityInfo {
+            id: "ecu".to_string(),
+            name: "ecu ECU".to_string(),
+            entity_type: "ecu".to_string(),
+            description: None,
+            href: "/vehicle/v1/components/ecu".to_string(),
+            status: Some("online".to_string()),
+            display_name: None,
+            category: None,
+        },
+        capabilities: Capabilities::default(),
+        senders: Mutex::new(Vec::new()),
+    });
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu".to_string(), backend);
+    TestServer::start(create_router(AppState::new(backends)))
+        .await
+        .expect("test server")
+}
+
+async fn next_event(sub: &mut Subscription) -> StreamEvent {
+    tokio::time::timeout(Duration::from_secs(2), sub.next())
+        .await
+        .expect("event in time")
+        .expect("stream open")
+        .expect("valid event")
+}
+
+/// Sample, resumed marker, sample
+async fn assert_resumed_between_samples(mut sub: Subscription) {
+    let first = next_event(&mut sub).await;
+    assert_eq!(first.sequence(), Some(1));
+    assert!(!first.is_resumed());
+
+    let marker = next_event(&mut sub).await;
+    assert!(marker.is_resumed(), "{marker:?}");
+    assert!(
+        marker.payload.is_none() && marker.error.is_none(),
+        "{marker:?}"
+    );
+
+    let second = next_event(&mut sub).await;
+    assert_eq!(second.sequence(), Some(2));
+    assert_eq!(second.get_f64("coolant_temp"), Some(41.0));
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn sse_stream_carries_resumed_marker() {
+    let server = server().await;
+    let sub = server
+        .client()
+        .subscribe("ecu", "coolant_temp", SubscriptionInterval::Slow)
+        .await
+        .expect("subscribe over SSE");
+
+    assert_resumed_between_samples(sub).await;
+}
+
+#[tokio::test]
+async fn websocket_carries_resumed_marker() {
+    let server = server().await;
+    let sub = server
+        .client()
+        .subscribe_ws("ecu", "coolant_temp", SubscriptionInterval::Slow)
+        .await
+        .expect("subscribe over WebSocket");
+
+    assert_resumed_between_samples(sub).await;
+}
@@ -0,0 +1,77 @@
+//! `GET /vehicle/v1/topology` — in-process router tests.
+//!
+//! The topology is a `vehicle` root with one node per component:
+//!   * a UDS ECU reports its transport kind and answers its TesterPresent
+//!     probe (`reachable`);
+//!   * nodes link to the component routes and are sorted by id;
+//!   * an ECU whose link is down stays in the tree, unreachable.
+//!
+//! Drives real `UdsBackend`s over the mock transport; mirrors
+//! `link_switch.rs`.
+
+mod common;
+
+use std::sync::Arc;
+
+use serde_json::Value;
+use sovd_client::testing::TestServer;
+use sovd_core::DiagnosticBackend;
+use sovd_uds::transport::mock::MockTransportAdapter;
+use sovd_uds::UdsBackend;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn ecu(id: &str, mock: Arc<MockTransportAdapter>) -> Arc<dyn DiagnosticBackend> {
+    let config = common::ecu_config(id, id);
+    Arc::new(UdsBackend::with_transport(config, mock).expect("uds backend"))
+}
+
+async fn topology(server: &TestServer) -> Value {
+    let resp = reqwest::get(format!("{}/vehicle/v1/topology", server.base_url()))
+        .await
+        .expect("get topology");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    resp.json().await.unwrap()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn components_hang_off_the_vehicle_root() {
+    let server = common::server(vec![
+        ("engine", ecu("engine", common::mock())),
+        ("brakes", ecu("brakes", common::mock())),
+    ])
+    .await;
+
+    let root = topology(&server).await;
+    assert_eq!(root["id"], "vehicle");
+    assert_eq!(root["type"], "vehicle");
+
+    let children = root["children"].as_array().unwrap();
+    let ids: Vec<&str> = children.iter().map(|c| c["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["brakes", "engine"]);
+
+    let engine = &children[1];
+    assert_eq!(engine["type"], "ecu");
+    assert_eq!(engine["href"], "/vehicle/v1/components/engine");
+    assert_eq!(engine["transport"], "mock");
+    assert_eq!(engine["reachable"], true);
+    assert!(engine.get("children").is_none(), "{engine}");
+}
+
+#[tokio::test]
+async fn silent_ecu_stays_in_the_tree_unreachable() {
+    let silent = common::mock();
+    silent.set_connected(false);
+    let server = common::server(vec![("engine", ecu("engine", silent))]).await;
+
+    let root = topology(&server).await;
+    let engine = &root["children"][0];
+    assert_eq!(engine["id"], "engine");
+    assert_eq!(engine["reachable"], false, "{engine}");
+}
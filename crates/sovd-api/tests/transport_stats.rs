@@ -0,0 +1,80 @@
+//! `x-sumo-transport-stats` transport link view — in-process router tests.
+//!
+//! `GET .../x-sumo-transport-stats` reports the transport an entity is
+//! reached over:
+//!   * a UDS ECU reports its transport kind, leaving out CAN-only fields on
+//!     a non-CAN transport;
+//!   * on the sub-entity route (`/components/{gw}/apps/{ecu}/...`) too;
+//!   * an entity without a transport of its own answers 501.
+//!
+//! The socketcan `tx_dl` itself is covered next to the adapter (needs a
+//! CAN FD `vcan0`). Uses the helpers in `common`.
+
+mod common;
+
+use std::sync::Arc;
+
+use sovd_client::testing::TestServer;
+use sovd_core::DiagnosticBackend;
+use sovd_uds::UdsBackend;
+
+// ---------------------------------------------------------------------------
+// Backends
+// ---------------------------------------------------------------------------
+
+fn ecu() -> Arc<dyn DiagnosticBackend> {
+    let mock = common::mock();
+    mock.set_max_message_len(Some(4095));
+    let config = common::ecu_config("ecu", "Linked ECU");
+    Arc::new(UdsBackend::with_transport(config, mock).expect("uds backend"))
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Server with the ECU registered directly (`ecu`) and behind a gateway (`gw`)
+async fn server() -> TestServer {
+    common::server(vec![("ecu", ecu()), ("gw", common::gateway(ecu()))]).await
+}
+
+async fn get(server: &TestServer, path: &str) -> reqwest::Response {
+    let url = format!("{}{}", server.base_url(), path);
+    reqwest::get(url).await.expect("get transport stats")
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn ecu_reports_its_transport() {
+    let server = server().await;
+    let resp = get(&server, "/vehicle/v1/components/ecu/x-sumo-transport-stats").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({ "kind": "mock", "max_message_len": 4095 })
+    );
+}
+
+#[tokio::test]
+async fn sub_entity_route_reports_the_ecu_transport() {
+    let server = server().await;
+    let resp = get(
+        &server,
+        "/vehicle/v1/components/gw/apps/ecu/x-sumo-transport-stats",
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["kind"], "mock");
+}
+
+#[tokio::test]
+async fn entity_without_a_transport_is_not_implemented() {
+    let server = server().await;
+    let resp = get(&server, "/vehicle/v1/components/gw/x-sumo-transport-stats").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::NOT_IMPLEMENTED);
+}
@@ -0,0 +1,247 @@
+//! `[subscriptions.normalize_units]` — in-process router tests.
+//!
+//! The backend streams raw DID samples; the definitions give
+//! `boost_pressure` (0xF40B) in kPa and `engine_rpm` (0xF40C) in rpm:
+//!   * `boost_pressure` normalized to bar streams converted values with
+//!     `x-sumo-units` naming bar;
+//!   * subscribing by hex DID is normalized like the named parameter;
+//!   * `engine_rpm` normalized to bar can't convert, so it streams unchanged
+//!     in rpm, flagged `x-sumo-unit-passthrough`;
+//!   * a parameter without a target unit streams as before.
+//!
+//! Mirrors `on_change_subscription.rs`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use sovd_client::testing::TestServer;
+use sovd_conv::types::DataType;
+use sovd_conv::{DidDefinition, DidStore};
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataPoint, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+use tokio::sync::broadcast;
+
+use sovd_api::{create_router, AppState, SubscriptionLimits};
+
+/// Raw samples every subscriber receives: 250 kPa, then 101 kPa (or rpm)
+const SAMPLES: [&str; 2] = ["00fa", "0065"];
+
+// ---------------------------------------------------------------------------
+// Mock backend
+// ---------------------------------------------------------------------------
+
+struct EcuBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    data_tx: broadcast::Sender<DataPoint>,
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for EcuBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn subscribe_data(
+        &self,
+        param_ids: &[String],
+        _rate_hz: u32,
+    ) -> BackendResult<broadcast::Receiver<DataPoint>> {
+        // Subscribe first so the receiver buffers the whole series.
+        let rx = self.data_tx.subscribe();
+        for value in SAMPLES {
+            let _ = self.data_tx.send(DataPoint {
+                id: param_ids[0].clone(),
+                value: serde_json::json!(value),
+                unit: None,
+                timestamp: chrono::Utc::now(),
+            });
+        }
+        Ok(rx)
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn store() -> Arc<DidStore> {
+    let store = DidStore::new();
+    store.register(
+        0xF40B,
+        DidDefinition::scaled(DataType::Uint16, 1.0, 0.0)
+            .with_id("boost_pressure")
+            .with_unit("kPa"),
+    );
+    store.register(
+        0xF40C,
+        DidDefinition::scaled(DataType::Uint16, 1.0, 0.0)
+            .with_id("engine_rpm")
+            .with_unit("rpm"),
+    );
+    store.register(
+        0xF40D,
+        DidDefinition::scaled(DataType::Uint16, 1.0, 0.0)
+            .with_id("rail_pressure")
+            .with_unit("kPa"),
+    );
+    Arc::new(store)
+}
+
+/// `boost_pressure` and `engine_rpm` normalized to bar; `rail_pressure` not
+async fn server() -> TestServer {
+    let backend = EcuBackend {
+        info: EntityInfo {
+            id: "ecu".to_string(),
+            name: "ecu ECU".to_string(),
+            entity_type: "ecu".to_string(),
+            description: None,
+            href: "/vehicle/v1/components/ecu".to_string(),
+            status: Some("online".to_string()),
+            display_name: None,
+            category: None,
+        },
+        capabilities: Capabilities::default(),
+        data_tx: broadcast::channel(16).0,
+    };
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu".to_string(), Arc::new(backend));
+    let mut normalize_units = HashMap::new();
+    normalize_units.insert("boost_pressure".to_string(), "bar".to_string());
+    normalize_units.insert("engine_rpm".to_string(), "bar".to_string());
+    let state =
+        AppState::with_did_store(backends, store()).with_subscription_limits(SubscriptionLimits {
+            normalize_units,
+            ..Default::default()
+        });
+    TestServer::start(create_router(state))
+        .await
+        .expect("test server")
+}
+
+/// Subscribe to `resource` and collect the payloads of its first samples
+async fn payloads(server: &TestServer, resource: &str) -> Vec<serde_json::Value> {
+    let url = format!(
+        "{}/vehicle/v1/components/ecu/cyclic-subscriptions",
+        server.base_url()
+    );
+    let created: serde_json::Value = reqwest::Client::new()
+        .post(&url)
+        .json(&serde_json::json!({ "resource": resource, "interval": "slow" }))
+        .send()
+        .await
+        .expect("create subscription")
+        .json()
+        .await
+        .unwrap();
+    let sub_id = created["subscription_id"].as_str().expect("created");
+
+    let mut resp = reqwest::Client::new()
+        .get(format!("{url}/{sub_id}"))
+        .header(reqwest::header::ACCEPT, "text/event-stream")
+        .send()
+        .await
+        .expect("open subscription SSE");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+
+    let mut text = String::new();
+    let mut events = Vec::new();
+    while events.len() < SAMPLES.len() {
+        match tokio::time::timeout(Duration::from_millis(500), resp.chunk()).await {
+            Ok(Ok(Some(chunk))) => text.push_str(&String::from_utf8_lossy(&chunk)),
+            _ => break,
+        }
+        let complete = &text[..text.rfind("\n\n").unwrap_or(0)];
+        events = complete
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(|data| serde_json::from_str::<serde_json::Value>(data.trim()).unwrap())
+            .map(|event| event["payload"].clone())
+            .collect();
+    }
+    assert_eq!(events.len(), SAMPLES.len(), "{events:?}");
+    events
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn kpa_source_streams_in_bar() {
+    let server = server().await;
+
+    let events = payloads(&server, "boost_pressure").await;
+
+    let values: Vec<f64> = events
+        .iter()
+        .map(|p| p["values"]["boost_pressure"].as_f64().unwrap())
+        .collect();
+    assert_eq!(values, [2.5, 1.01]);
+    for payload in &events {
+        assert_eq!(
+            payload["x-sumo-units"]["boost_pressure"], "bar",
+            "{payload}"
+        );
+        assert!(
+            payload.get("x-sumo-unit-passthrough").is_none(),
+            "{payload}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn hex_did_is_normalized_like_its_name() {
+    let server = server().await;
+
+    let events = payloads(&server, "F40B").await;
+
+    assert_eq!(events[0]["values"]["F40B"], 2.5, "{}", events[0]);
+    assert_eq!(events[0]["x-sumo-units"]["F40B"], "bar");
+}
+
+#[tokio::test]
+async fn unconvertible_unit_passes_through_flagged() {
+    let server = server().await;
+
+    let events = payloads(&server, "engine_rpm").await;
+
+    let payload = &events[0];
+    assert_eq!(payload["values"]["engine_rpm"], 250, "{payload}");
+    assert_eq!(payload["x-sumo-units"]["engine_rpm"], "rpm");
+    assert_eq!(payload["x-sumo-unit-passthrough"], true);
+}
+
+#[tokio::test]
+async fn unconfigured_parameter_streams_unchanged() {
+    let server = server().await;
+
+    let events = payloads(&server, "rail_pressure").await;
+
+    let payload = &events[0];
+    assert_eq!(payload["values"]["rail_pressure"], 250, "{payload}");
+    assert!(payload.get("x-sumo-units").is_none(), "{payload}");
+}
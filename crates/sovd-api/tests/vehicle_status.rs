@@ -0,0 +1,120 @@
+//! `GET /vehicle/v1/status` — in-process router tests.
+//!
+//! Three UDS ECUs over the mock transport, some with their link down:
+//!   * all answering rolls up to `ok`;
+//!   * one silent rolls up to `degraded`;
+//!   * none answering rolls up to `down`;
+//!   * a lowered `ok_ratio` tolerates one silent ECU;
+//!   * behind a gateway, the ECUs count rather than the gateway.
+//!
+//! Drives real `UdsBackend`s; mirrors `backend_health.rs`.
+
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sovd_core::DiagnosticBackend;
+use sovd_gateway::GatewayBackend;
+use sovd_uds::UdsBackend;
+
+use sovd_api::{AppState, VehicleStatusThresholds};
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn backend(id: &str, connected: bool) -> Arc<dyn DiagnosticBackend> {
+    let mock = common::mock();
+    mock.set_connected(connected);
+    let config = common::ecu_config(id, id);
+    Arc::new(UdsBackend::with_transport(config, mock).expect("uds backend"))
+}
+
+/// ECUs `ecu0`.. with the given link states
+fn state(connected: &[bool]) -> AppState {
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    for (i, &up) in connected.iter().enumerate() {
+        let id = format!("ecu{i}");
+        backends.insert(id.clone(), backend(&id, up));
+    }
+    AppState::new(backends)
+}
+
+/// Gateway `gw` with ECUs `ecu0`.. behind it, with the given link states
+fn gateway_state(connected: &[bool]) -> AppState {
+    let mut gateway = GatewayBackend::new("gw", "Gateway", None);
+    for (i, &up) in connected.iter().enumerate() {
+        gateway.register_backend(backend(&format!("ecu{i}"), up));
+    }
+    let backends = common::to_map(vec![("gw", Arc::new(gateway))]);
+    AppState::new(backends)
+}
+
+async fn status(state: AppState) -> serde_json::Value {
+    let server = common::serve(state).await;
+    let resp = reqwest::get(format!("{}/vehicle/v1/status", server.base_url()))
+        .await
+        .expect("get");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    resp.json().await.unwrap()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn all_reachable_is_ok() {
+    let body = status(state(&[true, true, true])).await;
+
+    assert_eq!(body["status"], "ok", "{body}");
+    assert_eq!(body["reachable"], 3);
+    assert_eq!(body["total"], 3);
+    assert_eq!(body["components"].as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn one_unreachable_is_degraded() {
+    let body = status(state(&[true, false, true])).await;
+
+    assert_eq!(body["status"], "degraded", "{body}");
+    assert_eq!(body["reachable"], 2);
+    let silent = &body["components"][1];
+    assert_eq!(silent["component"], "ecu1");
+    assert_eq!(silent["reachable"], false, "{silent}");
+}
+
+#[tokio::test]
+async fn none_reachable_is_down() {
+    let body = status(state(&[false, false, false])).await;
+
+    assert_eq!(body["status"], "down", "{body}");
+    assert_eq!(body["reachable"], 0);
+}
+
+#[tokio::test]
+async fn thresholds_are_configurable() {
+    let thresholds = VehicleStatusThresholds {
+        ok_ratio: 0.6,
+        down_ratio: 0.0,
+    };
+    let body = status(state(&[true, false, true]).with_vehicle_status_thresholds(thresholds)).await;
+
+    assert_eq!(body["status"], "ok", "{body}");
+}
+
+#[tokio::test]
+async fn gateway_ecus_roll_up_one_by_one() {
+    let body = status(gateway_state(&[true, false, true])).await;
+    assert_eq!(body["status"], "degraded", "{body}");
+    assert_eq!(body["reachable"], 2);
+    assert_eq!(body["total"], 3);
+    let gw = &body["components"][0];
+    assert_eq!(gw["children"][1]["component"], "ecu1");
+    assert_eq!(gw["children"][1]["reachable"], false, "{gw}");
+
+    let body = status(gateway_state(&[false, false])).await;
+    assert_eq!(body["status"], "down", "{body}");
+    assert_eq!(body["total"], 2);
+}
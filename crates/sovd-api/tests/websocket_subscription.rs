@@ -0,0 +1,228 @@
+//! WebSocket delivery of cyclic subscriptions — in-process router tests.
+//!
+//! The backend answers every `subscribe_data` with two samples and records
+//! the requested rate, so the tests can see when the server re-subscribes:
+//!   * the socket carries the same EventEnvelopes as the SSE stream;
+//!   * `set_interval` re-subscribes at the new rate, updates the resource
+//!     and keeps the sequence numbers running;
+//!   * `pause`/`resume` drop and re-create the backend subscription;
+//!   * a refused control message arrives as an error event.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use sovd_client::testing::TestServer;
+use sovd_client::{StreamEvent, Subscription, SubscriptionInterval};
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataPoint, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+use tokio::sync::broadcast;
+
+use sovd_api::{create_router, AppState, SubscriptionLimits};
+
+// ---------------------------------------------------------------------------
+// Mock backend
+// ---------------------------------------------------------------------------
+
+struct EcuBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    /// Rate of every `subscribe_data` call
+    rates: Mutex<Vec<u32>>,
+    /// Keeps the channels open so the streams don't end
+    senders: Mutex<Vec<broadcast::Sender<DataPoint>>>,
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for EcuBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![ParameterInfo {
+            id: "coolant_temp".to_string(),
+            name: "Coolant temperature".to_string(),
+            description: None,
+            unit: Some("degC".to_string()),
+            data_type: None,
+            read_only: true,
+            href: "/vehicle/v1/components/ecu/data/coolant_temp".to_string(),
+            did: None,
+            category: None,
+        }])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn subscribe_data(
+        &self,
+        _param_ids: &[String],
+        rate_hz: u32,
+    ) -> BackendResult<broadcast::Receiver<DataPoint>> {
+        self.rates.lock().unwrap().push(rate_hz);
+        let (tx, rx) = broadcast::channel(16);
+        for value in [40.0, 41.0] {
+            let _ = tx.send(DataPoint {
+                id: "coolant_temp".to_string(),
+                value: serde_json::json!(value),
+                unit: None,
+                timestamp: chrono::Utc::now(),
+            });
+        }
+        self.senders.lock().unwrap().push(tx);
+        Ok(rx)
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// `normal` (5 Hz) is the fastest accepted interval.
+async fn server() -> (TestServer, Arc<EcuBackend>) {
+    let backend = Arc::new(EcuBackend {
+        info: EntityInfo {
+            id: "ecu".to_string(),
+            name: "ecu ECU".to_string(),
+            entity_type: "ecu".to_string(),
+            description: None,
+            href: "/vehicle/v1/components/ecu".to_string(),
+            status: Some("online".to_string()),
+            display_name: None,
+            category: None,
+        },
+        capabilities: Capabilities::default(),
+        rates: Mutex::new(Vec::new()),
+        senders: Mutex::new(Vec::new()),
+    });
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu".to_string(), backend.clone());
+    let state = AppState::new(backends).with_subscription_limits(SubscriptionLimits {
+        max_rate_hz: Some(5),
+        max_subscriptions_per_component: None,
+        allowed_parameters: HashMap::new(),
+        normalize_units: HashMap::new(),
+    });
+    let server = TestServer::start(create_router(state))
+        .await
+        .expect("test server");
+    (server, backend)
+}
+
+async fn next_event(sub: &mut Subscription) -> StreamEvent {
+    tokio::time::timeout(Duration::from_secs(2), sub.next())
+        .await
+        .expect("event in time")
+        .expect("stream open")
+        .expect("valid event")
+}
+
+/// Next `count` events as (seq, value)
+async fn samples(sub: &mut Subscription, count: usize) -> Vec<(u64, f64)> {
+    let mut samples = Vec::new();
+    for _ in 0..count {
+        let event = next_event(sub).await;
+        samples.push((
+            event.sequence().unwrap(),
+            event.get_f64("coolant_temp").unwrap(),
+        ));
+    }
+    samples
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn websocket_delivers_event_envelopes() {
+    let (server, backend) = server().await;
+    let mut sub = server
+        .client()
+        .subscribe_ws("ecu", "coolant_temp", SubscriptionInterval::Slow)
+        .await
+        .expect("subscribe over WebSocket");
+
+    assert_eq!(samples(&mut sub, 2).await, vec![(1, 40.0), (2, 41.0)]);
+    assert_eq!(*backend.rates.lock().unwrap(), vec![2]);
+}
+
+#[tokio::test]
+async fn set_interval_resubscribes_at_the_new_rate() {
+    let (server, backend) = server().await;
+    let client = server.client();
+    let mut sub = client
+        .subscribe_ws("ecu", "coolant_temp", SubscriptionInterval::Slow)
+        .await
+        .expect("subscribe over WebSocket");
+    samples(&mut sub, 2).await;
+
+    sub.set_interval(SubscriptionInterval::Normal)
+        .await
+        .expect("send control");
+
+    // The new subscription's samples continue the sequence.
+    assert_eq!(samples(&mut sub, 2).await, vec![(3, 40.0), (4, 41.0)]);
+    assert_eq!(*backend.rates.lock().unwrap(), vec![2, 5]);
+    let details = client
+        .get_cyclic_subscription("ecu", sub.id())
+        .await
+        .expect("subscription details");
+    assert_eq!(details.interval, SubscriptionInterval::Normal);
+}
+
+#[tokio::test]
+async fn pause_and_resume_recreate_the_backend_subscription() {
+    let (server, backend) = server().await;
+    let mut sub = server
+        .client()
+        .subscribe_ws("ecu", "coolant_temp", SubscriptionInterval::Slow)
+        .await
+        .expect("subscribe over WebSocket");
+    samples(&mut sub, 2).await;
+
+    sub.pause().await.expect("pause");
+    sub.resume().await.expect("resume");
+
+    assert_eq!(samples(&mut sub, 2).await, vec![(3, 40.0), (4, 41.0)]);
+    assert_eq!(*backend.rates.lock().unwrap(), vec![2, 2]);
+}
+
+#[tokio::test]
+async fn refused_control_arrives_as_error_event() {
+    let (server, backend) = server().await;
+    let mut sub = server
+        .client()
+        .subscribe_ws("ecu", "coolant_temp", SubscriptionInterval::Slow)
+        .await
+        .expect("subscribe over WebSocket");
+    samples(&mut sub, 2).await;
+
+    // `fast` is 20 Hz, above max_rate_hz = 5.
+    sub.set_interval(SubscriptionInterval::Fast)
+        .await
+        .expect("send control");
+
+    let event = next_event(&mut sub).await;
+    assert!(event.payload.is_none(), "{event:?}");
+    let error = event.error.expect("error envelope");
+    assert!(error.message.contains("max_rate_hz"), "{}", error.message);
+    assert_eq!(*backend.rates.lock().unwrap(), vec![2]);
+}
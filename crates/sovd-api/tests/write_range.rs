@@ -0,0 +1,190 @@
+//! Definition `min`/`max` and `array` length on data writes — in-process
+//! router tests.
+//!
+//! The idle speed target (0x0120) is defined 600..=1200 rpm, the wheel
+//! pressure thresholds (0x0121) as a 4-element array 0..=250 kPa. The
+//! backend records every raw write:
+//!   * an in-range value is encoded and written;
+//!   * a value outside `min`/`max` is a 400 and nothing is written;
+//!   * an out-of-range array element, or the wrong element count, is a 400
+//!     and nothing is written.
+//!
+//! Mirrors the mock-backend pattern from `read_cache.rs`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use sovd_client::testing::TestServer;
+use sovd_conv::types::DataType;
+use sovd_conv::{DidDefinition, DidStore};
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+
+use sovd_api::{create_router, AppState};
+
+// ---------------------------------------------------------------------------
+// Mock backend
+// ---------------------------------------------------------------------------
+
+struct EcuBackend {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    /// `(did, bytes)` of every raw write
+    writes: Mutex<Vec<(u16, Vec<u8>)>>,
+}
+
+#[async_trait::async_trait]
+impl DiagnosticBackend for EcuBackend {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn write_raw_did(&self, did: u16, data: &[u8]) -> BackendResult<()> {
+        self.writes.lock().unwrap().push((did, data.to_vec()));
+        Ok(())
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn store() -> Arc<DidStore> {
+    let store = DidStore::new();
+    let mut idle = DidDefinition::scaled(DataType::Uint16, 1.0, 0.0)
+        .with_id("idle_speed_target")
+        .with_bounds(600.0, 1200.0);
+    idle.writable = true;
+    store.register(0x0120, idle);
+    let mut thresholds = DidDefinition::array(DataType::Uint8, 4)
+        .with_id("tire_pressure_thresholds")
+        .with_bounds(0.0, 250.0);
+    thresholds.writable = true;
+    store.register(0x0121, thresholds);
+    Arc::new(store)
+}
+
+async fn server() -> (TestServer, Arc<EcuBackend>) {
+    let backend = Arc::new(EcuBackend {
+        info: EntityInfo {
+            id: "ecu".to_string(),
+            name: "ecu ECU".to_string(),
+            entity_type: "ecu".to_string(),
+            description: None,
+            href: "/vehicle/v1/components/ecu".to_string(),
+            status: Some("online".to_string()),
+            display_name: None,
+            category: None,
+        },
+        capabilities: Capabilities::default(),
+        writes: Mutex::new(Vec::new()),
+    });
+    let mut backends: HashMap<String, Arc<dyn DiagnosticBackend>> = HashMap::new();
+    backends.insert("ecu".to_string(), backend.clone());
+    let state = AppState::with_did_store(backends, store());
+    let server = TestServer::start(create_router(state))
+        .await
+        .expect("test server");
+    (server, backend)
+}
+
+async fn write(server: &TestServer, param: &str, value: serde_json::Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .put(format!(
+            "{}/vehicle/v1/components/ecu/data/{param}",
+            server.base_url()
+        ))
+        .json(&serde_json::json!({ "value": value }))
+        .send()
+        .await
+        .expect("write")
+}
+
+fn writes(backend: &EcuBackend) -> Vec<(u16, Vec<u8>)> {
+    backend.writes.lock().unwrap().clone()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn in_range_value_is_written() {
+    let (server, backend) = server().await;
+
+    let resp = write(&server, "idle_speed_target", serde_json::json!(800)).await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::NO_CONTENT);
+    assert_eq!(writes(&backend), vec![(0x0120, vec![0x03, 0x20])]);
+}
+
+#[tokio::test]
+async fn out_of_range_value_is_rejected_before_the_bus() {
+    let (server, backend) = server().await;
+
+    for value in [300, 5000] {
+        let resp = write(&server, "idle_speed_target", serde_json::json!(value)).await;
+        assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST, "{value}");
+    }
+
+    assert!(writes(&backend).is_empty());
+}
+
+#[tokio::test]
+async fn array_element_out_of_range_is_rejected() {
+    let (server, backend) = server().await;
+
+    let resp = write(
+        &server,
+        "tire_pressure_thresholds",
+        serde_json::json!([220, 220, 300, 220]),
+    )
+    .await;
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+    assert!(writes(&backend).is_empty());
+}
+
+#[tokio::test]
+async fn array_with_wrong_element_count_is_rejected() {
+    let (server, backend) = server().await;
+
+    let resp = write(
+        &server,
+        "tire_pressure_thresholds",
+        serde_json::json!([220, 220, 220]),
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    let resp = write(
+        &server,
+        "tire_pressure_thresholds",
+        serde_json::json!([220, 220, 220, 220]),
+    )
+    .await;
+    assert_eq!(resp.status(), reqwest::StatusCode::NO_CONTENT);
+    assert_eq!(writes(&backend), vec![(0x0121, vec![220; 4])]);
+}
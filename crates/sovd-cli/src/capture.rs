@@ -0,0 +1,189 @@
+//! Parquet capture for `monitor -o parquet --out <FILE>` (feature `parquet`)
+//!
+//! One row per stream event: `timestamp` (RFC 3339, as sent by the server),
+//! `sequence`, then one nullable UTF-8 column per monitored parameter
+//! holding the value exactly as the CSV output prints it. The schema is
+//! fixed from the parameter list when the capture starts; values for
+//! parameters outside it are dropped, and a parameter missing from an
+//! event is null in that row.
+//!
+//! Rows are buffered and written out as a row group every
+//! [`ROW_GROUP_ROWS`] rows or [`FLUSH_INTERVAL`], whichever comes first, so
+//! a long capture never holds more than one group in memory.
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use arrow_array::{ArrayRef, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use sovd_client::StreamEvent;
+
+use crate::commands::monitor::format_json_value;
+
+/// Rows per row group
+const ROW_GROUP_ROWS: usize = 1024;
+
+/// Longest a buffered row waits before its row group is written
+const FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Parquet file being filled from a monitor stream
+pub struct ParquetCapture {
+    writer: ArrowWriter<File>,
+    schema: SchemaRef,
+    params: Vec<String>,
+    timestamps: Vec<String>,
+    sequences: Vec<u64>,
+    /// One buffered column per entry in `params`
+    values: Vec<Vec<Option<String>>>,
+    last_flush: Instant,
+    rows: usize,
+}
+
+impl ParquetCapture {
+    /// Create `path` with a schema derived from `params`
+    pub fn create(path: &Path, params: &[String]) -> Result<Self> {
+        let fields: Vec<Field> = [
+            Field::new("timestamp", DataType::Utf8, false),
+            Field::new("sequence", DataType::UInt64, false),
+        ]
+        .into_iter()
+        .chain(params.iter().map(|p| Field::new(p, DataType::Utf8, true)))
+        .collect();
+        let schema = Arc::new(Schema::new(fields));
+
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create capture file: {}", path.display()))?;
+        let writer = ArrowWriter::try_new(file, schema.clone(), None)
+            .context("Failed to start Parquet writer")?;
+
+        Ok(Self {
+            writer,
+            schema,
+            params: params.to_vec(),
+            timestamps: Vec::new(),
+            sequences: Vec::new(),
+            values: vec![Vec::new(); params.len()],
+            last_flush: Instant::now(),
+            rows: 0,
+        })
+    }
+
+    /// Append one event as a row (error-only events are skipped)
+    pub fn push(&mut self, event: &StreamEvent) -> Result<()> {
+        let Some(values) = event.values() else {
+            return Ok(());
+        };
+
+        self.timestamps.push(event.timestamp.clone());
+        self.sequences.push(event.sequence().unwrap_or(0));
+        for (column, param) in self.values.iter_mut().zip(&self.params) {
+            column.push(values.get(param).map(format_json_value));
+        }
+        self.rows += 1;
+
+        if self.timestamps.len() >= ROW_GROUP_ROWS || self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write buffered rows as one row group
+    fn flush(&mut self) -> Result<()> {
+        self.last_flush = Instant::now();
+        if self.timestamps.is_empty() {
+            return Ok(());
+        }
+
+        let columns: Vec<ArrayRef> = [
+            Arc::new(StringArray::from(std::mem::take(&mut self.timestamps))) as ArrayRef,
+            Arc::new(UInt64Array::from(std::mem::take(&mut self.sequences))),
+        ]
+        .into_iter()
+        .chain(
+            self.values
+                .iter_mut()
+                .map(|column| Arc::new(StringArray::from(std::mem::take(column))) as ArrayRef),
+        )
+        .collect();
+
+        let batch = RecordBatch::try_new(self.schema.clone(), columns)?;
+        self.writer.write(&batch)?;
+        // Close the row group now rather than at the writer's size limit.
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Write any buffered rows and the file footer; returns the row count
+    pub fn finish(mut self) -> Result<usize> {
+        self.flush()?;
+        self.writer
+            .close()
+            .context("Failed to finish Parquet file")?;
+        Ok(self.rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn event(seq: u64, values: serde_json::Value) -> StreamEvent {
+        serde_json::from_value(serde_json::json!({
+            "timestamp": format!("2026-01-01T00:00:0{seq}Z"),
+            "payload": { "seq": seq, "values": values },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn captured_events_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.parquet");
+        let params = vec!["engine_rpm".to_string(), "coolant_temp".to_string()];
+
+        let mut capture = ParquetCapture::create(&path, &params).unwrap();
+        capture
+            .push(&event(1, serde_json::json!({ "engine_rpm": 1800.0 })))
+            .unwrap();
+        capture
+            .push(&event(
+                2,
+                serde_json::json!({ "engine_rpm": 1850.5, "coolant_temp": 90 }),
+            ))
+            .unwrap();
+        // Not in the schema fixed at start — dropped, row still written.
+        capture
+            .push(&event(3, serde_json::json!({ "oil_temp": 95 })))
+            .unwrap();
+        assert_eq!(capture.finish().unwrap(), 3);
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap()).unwrap();
+        assert_eq!(builder.metadata().file_metadata().num_rows(), 3);
+        let columns: Vec<&str> = builder
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect();
+        assert_eq!(
+            columns,
+            ["timestamp", "sequence", "engine_rpm", "coolant_temp"]
+        );
+
+        let batch = builder.build().unwrap().next().unwrap().unwrap();
+        let rpm = batch
+            .column_by_name("engine_rpm")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(rpm.value(1), "1850.5");
+        assert!(rpm.is_null(2));
+    }
+}
@@ -0,0 +1,12 @@
+//! Completions command - shell completion scripts
+
+use std::io::Write;
+
+use clap::Command;
+use clap_complete::Shell;
+
+/// Write the completion script of `cmd` for `shell` to `out`
+pub fn completions(shell: Shell, mut cmd: Command, out: &mut dyn Write) {
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, out);
+}
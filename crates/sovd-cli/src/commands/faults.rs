@@ -1,9 +1,12 @@
 //! Faults command - fault/DTC management
 
+use std::collections::HashMap;
+
 use anyhow::Result;
-use sovd_client::SovdClient;
+use sovd_client::{ParameterInfo, SovdClient};
 
-use crate::output::{FaultRow, OutputContext};
+use crate::commands::read::format_value;
+use crate::output::{ExtendedDataRow, FaultRow, OutputContext, SnapshotRow};
 
 /// List and manage faults
 pub async fn faults(
@@ -16,7 +19,14 @@ pub async fn faults(
     if clear {
         // Clear all faults
         let result = client.clear_faults(ecu).await?;
-        if result.success {
+        if result.cleared_confirmed == Some(false) {
+            let codes: Vec<&str> = result.remaining.iter().map(|f| f.code.as_str()).collect();
+            ctx.warn(&format!(
+                "Clear acknowledged but {} fault(s) still stored: {}",
+                codes.len(),
+                codes.join(", ")
+            ));
+        } else if result.success {
             ctx.success(&format!(
                 "Cleared {} fault(s)",
                 result.cleared_count.unwrap_or(0)
@@ -69,6 +79,82 @@ pub async fn faults(
     Ok(())
 }
 
+/// Show the snapshot (freeze frame) records of a fault, one row per
+/// recorded parameter
+pub async fn fault_snapshots(
+    client: &SovdClient,
+    ecu: &str,
+    dtc: &str,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let snapshots = client.get_fault_snapshots(ecu, dtc).await?;
+    if snapshots.is_empty() {
+        ctx.info(&format!("No snapshot records stored for {}", dtc));
+        return Ok(());
+    }
+
+    // Names and units come from the parameter list; an ECU that cannot
+    // list them still gets its values printed
+    let params: HashMap<String, ParameterInfo> = match client.list_parameters(ecu).await {
+        Ok(params) => params
+            .items
+            .into_iter()
+            .map(|p| (p.id.clone(), p))
+            .collect(),
+        Err(_) => HashMap::new(),
+    };
+
+    let rows: Vec<SnapshotRow> = snapshots
+        .into_iter()
+        .flat_map(|snapshot| {
+            let record = format!("0x{:02X}", snapshot.record_number);
+            let params = &params;
+            snapshot.data.into_iter().map(move |(id, value)| {
+                let param = params.get(&id);
+                SnapshotRow {
+                    record: record.clone(),
+                    name: param.and_then(|p| p.name.clone()).unwrap_or_default(),
+                    unit: param.and_then(|p| p.unit.clone()).unwrap_or_default(),
+                    value: format_value(&value),
+                    parameter: id,
+                }
+            })
+        })
+        .collect();
+
+    ctx.print(&rows);
+    Ok(())
+}
+
+/// Show the extended data records of a fault: all of them, or only
+/// `record`
+pub async fn fault_extended_data(
+    client: &SovdClient,
+    ecu: &str,
+    dtc: &str,
+    record: Option<u8>,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let records = client.get_fault_extended_data(ecu, dtc, record).await?;
+    if records.is_empty() {
+        ctx.info(&format!("No extended data records stored for {}", dtc));
+        return Ok(());
+    }
+
+    let rows: Vec<ExtendedDataRow> = records
+        .into_iter()
+        .map(|r| ExtendedDataRow {
+            record: format!("0x{:02X}", r.record_number),
+            name: r.name.unwrap_or_else(|| "-".to_string()),
+            value: r.value.map_or_else(|| "-".to_string(), |v| v.to_string()),
+            raw: r.raw,
+        })
+        .collect();
+
+    ctx.print(&rows);
+    Ok(())
+}
+
 /// Spec §7.8 severity is integer 1..4; render as the well-known label
 /// for human-friendly CLI output.
 fn severity_label(s: u8) -> String {
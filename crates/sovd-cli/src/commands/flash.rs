@@ -44,13 +44,26 @@ pub async fn flash(client: &FlashClient, file_path: &Path, ctx: &OutputContext)
     // `manifest` is the default part_id when the caller doesn't
     // care — single-part flashes don't need a real SUIT envelope
     // structure.  Multi-part callers use the typed primitives.
-    client
+    let summary = client
         .flash_update("manifest", &firmware, "hard", Some(progress))
         .await
         .context("flash_update failed")?;
 
     pb.finish_with_message("Complete!");
     ctx.success("\nFirmware update completed successfully");
+    if let Some(s) = summary {
+        ctx.info(&format!(
+            "{} bytes in {} blocks, {:.1}s ({:.1} KiB/s), {} retries{}",
+            s.bytes_transferred,
+            s.blocks_transferred,
+            s.duration_ms as f64 / 1000.0,
+            s.throughput_bytes_per_sec / 1024.0,
+            s.retries,
+            s.verified_version
+                .map(|v| format!(", version {v}"))
+                .unwrap_or_default()
+        ));
+    }
 
     Ok(())
 }
@@ -2,6 +2,7 @@
 
 pub mod actuate;
 pub mod bulk_data;
+pub mod completions;
 pub mod faults;
 pub mod flash;
 pub mod info;
@@ -17,7 +18,8 @@ pub mod unlock;
 pub mod write;
 
 pub use actuate::actuate;
-pub use faults::faults;
+pub use completions::completions;
+pub use faults::{fault_extended_data, fault_snapshots, faults};
 pub use flash::flash;
 pub use info::info;
 pub use list::list;
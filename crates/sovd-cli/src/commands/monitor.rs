@@ -2,11 +2,21 @@
 
 use anyhow::Result;
 use futures::stream::{select_all, SelectAll, StreamExt};
-use sovd_client::{SovdClient, StreamError, StreamEvent, Subscription, SubscriptionInterval};
+use sovd_client::{
+    DataListItem, SovdClient, StreamError, StreamEvent, Subscription, SubscriptionInterval,
+};
+use std::collections::HashMap;
+#[cfg(feature = "dashboard")]
+use std::io::IsTerminal;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use crate::output::{OutputContext, OutputFormat, StreamRow};
+#[cfg(feature = "parquet")]
+use crate::capture::ParquetCapture;
+#[cfg(feature = "dashboard")]
+use crate::dashboard::{self, Dashboard};
+use crate::output::{flatten_value, OutputContext, OutputFormat, StreamRow};
 
 /// Active event source for the monitor loop.
 ///
@@ -50,14 +60,56 @@ fn rate_to_interval(rate: u32) -> SubscriptionInterval {
     }
 }
 
-/// Monitor parameters in real-time via SSE streaming
+/// Whether `--dashboard` can take over the terminal; otherwise the
+/// monitor falls back to line output
+#[cfg(feature = "dashboard")]
+fn dashboard_usable(ctx: &OutputContext) -> bool {
+    if ctx.format != OutputFormat::Table {
+        ctx.warn("--dashboard only applies to table output; printing lines");
+        false
+    } else if !std::io::stdout().is_terminal() {
+        ctx.info("stdout is not a terminal; printing lines instead of the dashboard");
+        false
+    } else {
+        true
+    }
+}
+
+/// Monitor parameters in real-time via SSE streaming.
+///
+/// With `-o parquet` the events are written to `out` instead of stdout;
+/// with `dashboard` they update a live table when stdout is a terminal.
 pub async fn monitor(
     client: &SovdClient,
     ecu: &str,
     params: Vec<String>,
     rate: u32,
+    out: Option<&Path>,
+    dashboard: bool,
     ctx: &OutputContext,
 ) -> Result<()> {
+    if out.is_some() && ctx.format != OutputFormat::Parquet {
+        ctx.warn("--out is only used with -o parquet; printing to stdout");
+    }
+    #[cfg(not(feature = "dashboard"))]
+    if dashboard {
+        anyhow::bail!("--dashboard needs sovd-cli built with the `dashboard` feature");
+    }
+    #[cfg(feature = "dashboard")]
+    let dashboard = dashboard && dashboard_usable(ctx);
+    #[cfg(not(feature = "parquet"))]
+    if ctx.format == OutputFormat::Parquet {
+        anyhow::bail!("-o parquet needs sovd-cli built with the `parquet` feature");
+    }
+    #[cfg(feature = "parquet")]
+    let mut capture = match ctx.format {
+        OutputFormat::Parquet => {
+            let path = out.ok_or_else(|| anyhow::anyhow!("-o parquet needs --out <FILE>"))?;
+            Some((ParquetCapture::create(path, &params)?, path))
+        }
+        _ => None,
+    };
+
     ctx.info(&format!(
         "Subscribing to {} parameter(s) at {}Hz...",
         params.len(),
@@ -77,6 +129,27 @@ pub async fn monitor(
         streams: select_all(subs),
     };
 
+    #[cfg(feature = "dashboard")]
+    if dashboard {
+        // Units are a nicety: an ECU that cannot list its parameters still
+        // gets a dashboard
+        let units: HashMap<String, String> = match client.list_parameters(ecu).await {
+            Ok(list) => list
+                .items
+                .into_iter()
+                .filter_map(|p| Some((p.id, p.unit?)))
+                .collect(),
+            Err(_) => HashMap::new(),
+        };
+        let failure = run_dashboard(&mut stream, Dashboard::new(ecu, &params, &units)).await?;
+        if let Some(e) = failure {
+            ctx.error(&format!("Stream error: {}", e));
+        }
+        stream.cancel().await?;
+        ctx.success("Subscription cancelled");
+        return Ok(());
+    }
+
     // Set up Ctrl+C handler
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
@@ -89,20 +162,29 @@ pub async fn monitor(
         println!();
     }
 
-    // For CSV, print header once
-    if ctx.format == OutputFormat::Csv {
-        let headers: Vec<&str> = std::iter::once("timestamp")
-            .chain(std::iter::once("sequence"))
-            .chain(params.iter().map(|s| s.as_str()))
-            .collect();
-        println!("{}", headers.join(","));
-    }
+    // For CSV, the header is written once, before the first sample
+    let mut series = match ctx.format {
+        OutputFormat::Csv => Some(SeriesCsv::new(
+            std::io::stdout(),
+            csv_columns(client, ecu, &params).await,
+        )?),
+        _ => None,
+    };
 
     while running.load(Ordering::SeqCst) {
         tokio::select! {
             event = stream.next() => {
                 match event {
                     Some(Ok(data)) => {
+                        #[cfg(feature = "parquet")]
+                        if let Some((capture, _)) = capture.as_mut() {
+                            capture.push(&data)?;
+                            continue;
+                        }
+                        if let Some(series) = series.as_mut() {
+                            series.push(&data)?;
+                            continue;
+                        }
                         print_stream_event(&data, &params, ctx);
                     }
                     Some(Err(e)) => {
@@ -128,9 +210,109 @@ pub async fn monitor(
     stream.cancel().await?;
     ctx.success("Subscription cancelled");
 
+    #[cfg(feature = "parquet")]
+    if let Some((capture, path)) = capture {
+        let rows = capture.finish()?;
+        ctx.success(&format!("Wrote {} rows to {}", rows, path.display()));
+    }
+
     Ok(())
 }
 
+/// Drive the dashboard until the user quits or the stream ends. The
+/// terminal is restored before returning; a stream error is handed back
+/// so it is reported on the restored screen.
+#[cfg(feature = "dashboard")]
+async fn run_dashboard(
+    stream: &mut MonitorStream,
+    mut dashboard: Dashboard,
+) -> Result<Option<StreamError>> {
+    let mut terminal = ratatui::init();
+    let result = async {
+        loop {
+            terminal.draw(|frame| dashboard.render(frame))?;
+            if dashboard::quit_requested()? {
+                return Ok(None);
+            }
+            tokio::select! {
+                event = stream.next() => match event {
+                    Some(Ok(event)) => dashboard.update(&event),
+                    Some(Err(e)) => return Ok(Some(e)),
+                    None => return Ok(None),
+                },
+                // Keep checking for a quit key while the stream is quiet
+                _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {}
+            }
+        }
+    }
+    .await;
+    ratatui::restore();
+    result
+}
+
+/// CSV columns for `params`: one per parameter, or one per field of a
+/// parameter whose current value is an object. A parameter that cannot be
+/// read up front keeps a single column.
+async fn csv_columns(client: &SovdClient, ecu: &str, params: &[String]) -> Vec<String> {
+    let ids: Vec<&str> = params.iter().map(String::as_str).collect();
+    let items = client.read_data_batch(ecu, &ids).await.unwrap_or_default();
+    let mut columns = Vec::new();
+    for (i, param) in params.iter().enumerate() {
+        match items.get(i) {
+            Some(DataListItem::Value(data)) => columns.extend(
+                flatten_value(param, &data.value)
+                    .into_iter()
+                    .map(|(k, _)| k),
+            ),
+            _ => columns.push(param.clone()),
+        }
+    }
+    columns
+}
+
+/// Time-series CSV for `monitor -o csv`: `timestamp`, `sequence` and the
+/// parameter columns, one row per sample. Cells of parameters a sample
+/// does not carry stay empty, as do fields outside the columns fixed at
+/// the start.
+struct SeriesCsv<W: std::io::Write> {
+    writer: csv::Writer<W>,
+    columns: Vec<String>,
+}
+
+impl<W: std::io::Write> SeriesCsv<W> {
+    fn new(out: W, columns: Vec<String>) -> csv::Result<Self> {
+        let mut writer = csv::Writer::from_writer(out);
+        writer.write_record(
+            ["timestamp", "sequence"]
+                .into_iter()
+                .chain(columns.iter().map(String::as_str)),
+        )?;
+        writer.flush()?;
+        Ok(Self { writer, columns })
+    }
+
+    /// Write one row; flushed at once so a pipe or `tail -f` sees it
+    fn push(&mut self, event: &StreamEvent) -> csv::Result<()> {
+        let Some(values) = event.values() else {
+            return Ok(());
+        };
+        let mut cells: HashMap<String, String> = values
+            .iter()
+            .flat_map(|(param, value)| flatten_value(param, value))
+            .collect();
+        let sequence = event.sequence().unwrap_or(0).to_string();
+        self.writer.write_record(
+            [event.timestamp.clone(), sequence].into_iter().chain(
+                self.columns
+                    .iter()
+                    .map(|column| cells.remove(column).unwrap_or_default()),
+            ),
+        )?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
 /// Print a stream event in the appropriate format
 fn print_stream_event(event: &sovd_client::StreamEvent, params: &[String], ctx: &OutputContext) {
     // EventEnvelope: skip events with no success payload (error-only).
@@ -158,28 +340,18 @@ fn print_stream_event(event: &sovd_client::StreamEvent, params: &[String], ctx:
                 }
             }
         }
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Parquet => {
             // Print each event as JSON
             if let Ok(json) = serde_json::to_string(event) {
                 println!("{}", json);
             }
         }
-        OutputFormat::Csv => {
-            // Print as CSV row
-            let values: Vec<String> = std::iter::once(event.timestamp.to_string())
-                .chain(std::iter::once(sequence.to_string()))
-                .chain(
-                    params
-                        .iter()
-                        .map(|p| values.get(p).map(format_json_value).unwrap_or_default()),
-                )
-                .collect();
-            println!("{}", values.join(","));
-        }
+        // Written through `SeriesCsv`
+        OutputFormat::Csv => {}
     }
 }
 
-fn format_json_value(value: &serde_json::Value) -> String {
+pub(crate) fn format_json_value(value: &serde_json::Value) -> String {
     match value {
         serde_json::Value::String(s) => s.clone(),
         serde_json::Value::Number(n) => n.to_string(),
@@ -188,3 +360,44 @@ fn format_json_value(value: &serde_json::Value) -> String {
         other => other.to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(seq: u64, values: serde_json::Value) -> StreamEvent {
+        serde_json::from_value(serde_json::json!({
+            "timestamp": format!("2026-01-01T00:00:0{seq}Z"),
+            "payload": { "seq": seq, "values": values },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn series_csv_has_a_column_per_parameter_and_field() {
+        let columns = vec![
+            "engine_rpm".to_string(),
+            "gps.lat".to_string(),
+            "gps.lon".to_string(),
+        ];
+        let mut out = Vec::new();
+        let mut series = SeriesCsv::new(&mut out, columns).unwrap();
+        series
+            .push(&event(1, serde_json::json!({ "engine_rpm": 1800 })))
+            .unwrap();
+        series
+            .push(&event(
+                2,
+                serde_json::json!({ "gps": { "lat": 48.1, "lon": 11.6 }, "status": "a,b" }),
+            ))
+            .unwrap();
+        drop(series);
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "timestamp,sequence,engine_rpm,gps.lat,gps.lon\n\
+             2026-01-01T00:00:01Z,1,1800,,\n\
+             2026-01-01T00:00:02Z,2,,48.1,11.6\n"
+        );
+    }
+}
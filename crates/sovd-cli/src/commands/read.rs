@@ -1,9 +1,13 @@
 //! Read command - read data parameters
 
+use std::collections::HashMap;
+
 use anyhow::Result;
-use sovd_client::SovdClient;
+use sovd_client::{DataListItem, ParametersResponse, SovdClient};
 
-use crate::output::{DataRow, OutputContext, ParameterRow};
+use crate::output::{
+    flatten_value, DataCsvRow, DataRow, OutputContext, OutputFormat, ParameterRow,
+};
 
 /// List available parameters for an ECU
 pub async fn data(client: &SovdClient, ecu: &str, ctx: &OutputContext) -> Result<()> {
@@ -33,40 +37,26 @@ pub async fn read(
     all: bool,
     ctx: &OutputContext,
 ) -> Result<()> {
-    let param_ids: Vec<&str> = if all {
-        // Get all parameter IDs
-        let available = client.list_parameters(ecu).await?;
-        let ids: Vec<String> = available.items.into_iter().map(|p| p.id).collect();
-
-        // Read all parameters
-        let mut rows = Vec::new();
-        for id in &ids {
-            match client.read_data(ecu, id).await {
-                Ok(data) => {
-                    rows.push(DataRow {
-                        parameter: id.clone(),
-                        value: format_value(&data.value),
-                        unit: data.unit.unwrap_or_default(),
-                        raw: data.raw.unwrap_or_default(),
-                    });
-                }
-                Err(e) => {
-                    rows.push(DataRow {
-                        parameter: id.clone(),
-                        value: format!("Error: {}", e),
-                        unit: String::new(),
-                        raw: String::new(),
-                    });
-                }
-            }
-        }
-        ctx.print(&rows);
-        return Ok(());
+    // CSV rows carry display names; those are a nicety when the
+    // parameters are named explicitly
+    let available = if all {
+        Some(client.list_parameters(ecu).await?)
+    } else if ctx.format == OutputFormat::Csv {
+        client.list_parameters(ecu).await.ok()
     } else {
-        params.iter().map(|s| s.as_str()).collect()
+        None
+    };
+    let ids: Vec<String> = match (&available, all) {
+        (Some(available), true) => available.items.iter().map(|p| p.id.clone()).collect(),
+        _ => params.to_vec(),
     };
+    let param_ids: Vec<&str> = ids.iter().map(|s| s.as_str()).collect();
 
-    if param_ids.len() == 1 {
+    if ctx.format == OutputFormat::Csv {
+        return read_csv(client, ecu, &param_ids, available, ctx).await;
+    }
+
+    if param_ids.len() == 1 && !all {
         // Single parameter read
         let data = client.read_data(ecu, param_ids[0]).await?;
         let row = DataRow {
@@ -77,17 +67,25 @@ pub async fn read(
         };
         ctx.print_one(&row);
     } else {
-        // Batch read
+        // Batch read: one request, one result per parameter
         let results = client.read_data_batch(ecu, &param_ids).await?;
 
         let rows: Vec<DataRow> = results
             .into_iter()
             .zip(param_ids.iter())
-            .map(|(data, id)| DataRow {
-                parameter: id.to_string(),
-                value: format_value(&data.value),
-                unit: data.unit.unwrap_or_default(),
-                raw: data.raw.unwrap_or_default(),
+            .map(|(item, id)| match item {
+                DataListItem::Value(data) => DataRow {
+                    parameter: id.to_string(),
+                    value: format_value(&data.value),
+                    unit: data.unit.unwrap_or_default(),
+                    raw: data.raw.unwrap_or_default(),
+                },
+                DataListItem::Error { error, .. } => DataRow {
+                    parameter: id.to_string(),
+                    value: format!("Error: {}", error.message),
+                    unit: String::new(),
+                    raw: String::new(),
+                },
             })
             .collect();
 
@@ -97,8 +95,61 @@ pub async fn read(
     Ok(())
 }
 
+/// Export a read as CSV: one row per parameter, or per field of an
+/// object-valued one (`id.field`), stamped with the server's read time
+async fn read_csv(
+    client: &SovdClient,
+    ecu: &str,
+    param_ids: &[&str],
+    available: Option<ParametersResponse>,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let names: HashMap<String, String> = available
+        .map(|available| {
+            available
+                .items
+                .into_iter()
+                .filter_map(|p| Some((p.id, p.name?)))
+                .collect()
+        })
+        .unwrap_or_default();
+    let results = client.read_data_batch(ecu, param_ids).await?;
+
+    let mut rows = Vec::new();
+    for (item, id) in results.into_iter().zip(param_ids) {
+        let name = names.get(*id).cloned().unwrap_or_default();
+        match item {
+            DataListItem::Value(data) => {
+                let unit = data.unit.unwrap_or_default();
+                let timestamp = data.timestamp.unwrap_or_default();
+                rows.extend(
+                    flatten_value(id, &data.value)
+                        .into_iter()
+                        .map(|(id, value)| DataCsvRow {
+                            id,
+                            name: name.clone(),
+                            value,
+                            unit: unit.clone(),
+                            timestamp: timestamp.clone(),
+                        }),
+                );
+            }
+            DataListItem::Error { error, .. } => rows.push(DataCsvRow {
+                id: id.to_string(),
+                name,
+                value: format!("Error: {}", error.message),
+                unit: String::new(),
+                timestamp: String::new(),
+            }),
+        }
+    }
+
+    ctx.print(&rows);
+    Ok(())
+}
+
 /// Format a JSON value for display
-fn format_value(value: &serde_json::Value) -> String {
+pub(crate) fn format_value(value: &serde_json::Value) -> String {
     match value {
         serde_json::Value::Null => "-".to_string(),
         serde_json::Value::String(s) => s.clone(),
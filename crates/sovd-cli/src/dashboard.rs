@@ -0,0 +1,184 @@
+//! Live table for `monitor --dashboard` (feature `dashboard`)
+//!
+//! One row per monitored parameter with its latest value, unit, the
+//! smallest and largest value seen, and the rate its updates arrive at,
+//! redrawn in place as stream events come in. Min/max only track numeric
+//! values; the rate is measured from event arrival, not the server's
+//! timestamps, so it shows what the bench actually sees.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::Constraint;
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Row, Table};
+use ratatui::Frame;
+use sovd_client::StreamEvent;
+
+use crate::commands::monitor::format_json_value;
+
+/// What the dashboard knows about one parameter
+struct ParamStats {
+    parameter: String,
+    unit: String,
+    latest: Option<serde_json::Value>,
+    min: Option<f64>,
+    max: Option<f64>,
+    updates: u64,
+    first: Option<Instant>,
+    last: Option<Instant>,
+}
+
+impl ParamStats {
+    fn record(&mut self, value: &serde_json::Value, at: Instant) {
+        if let Some(number) = value.as_f64() {
+            self.min = Some(self.min.map_or(number, |min| min.min(number)));
+            self.max = Some(self.max.map_or(number, |max| max.max(number)));
+        }
+        self.latest = Some(value.clone());
+        self.updates += 1;
+        self.first.get_or_insert(at);
+        self.last = Some(at);
+    }
+
+    /// Updates per second between the first and the latest update
+    fn rate(&self) -> Option<f64> {
+        let span = self.last?.duration_since(self.first?).as_secs_f64();
+        (self.updates > 1 && span > 0.0).then(|| (self.updates - 1) as f64 / span)
+    }
+
+    fn row(&self) -> Row<'static> {
+        let number = |n: Option<f64>| n.map_or_else(|| "-".to_string(), |n| n.to_string());
+        Row::new([
+            self.parameter.clone(),
+            self.latest
+                .as_ref()
+                .map_or_else(|| "-".to_string(), format_json_value),
+            self.unit.clone(),
+            number(self.min),
+            number(self.max),
+            self.rate()
+                .map_or_else(|| "-".to_string(), |rate| format!("{:.1} Hz", rate)),
+        ])
+    }
+}
+
+/// Latest values of the monitored parameters
+pub struct Dashboard {
+    title: String,
+    rows: Vec<ParamStats>,
+}
+
+impl Dashboard {
+    /// Dashboard for `params` of `ecu`; `units` maps parameter IDs to
+    /// their unit, parameters missing from it show none
+    pub fn new(ecu: &str, params: &[String], units: &HashMap<String, String>) -> Self {
+        Self {
+            title: format!(" {} - q to quit ", ecu),
+            rows: params
+                .iter()
+                .map(|param| ParamStats {
+                    parameter: param.clone(),
+                    unit: units.get(param).cloned().unwrap_or_default(),
+                    latest: None,
+                    min: None,
+                    max: None,
+                    updates: 0,
+                    first: None,
+                    last: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Take in the values of a stream event
+    pub fn update(&mut self, event: &StreamEvent) {
+        if let Some(values) = event.values() {
+            self.record(values, Instant::now());
+        }
+    }
+
+    fn record(&mut self, values: &HashMap<String, serde_json::Value>, at: Instant) {
+        for row in &mut self.rows {
+            if let Some(value) = values.get(&row.parameter) {
+                row.record(value, at);
+            }
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let header = Row::new(["Parameter", "Value", "Unit", "Min", "Max", "Rate"])
+            .style(Style::new().add_modifier(Modifier::BOLD));
+        let widths = [
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Length(8),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+        ];
+        let table = Table::new(self.rows.iter().map(ParamStats::row), widths)
+            .header(header)
+            .block(Block::bordered().title(self.title.as_str()));
+        frame.render_widget(table, frame.area());
+    }
+}
+
+/// Whether `q`, Esc or Ctrl+C was pressed since the last call. The
+/// terminal is in raw mode, so Ctrl+C arrives as a key, not SIGINT.
+pub fn quit_requested() -> Result<bool> {
+    while event::poll(Duration::ZERO)? {
+        if let Event::Key(key) = event::read()? {
+            let ctrl_c =
+                key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+            if key.kind == KeyEventKind::Press
+                && (ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc))
+            {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(values: serde_json::Value) -> HashMap<String, serde_json::Value> {
+        serde_json::from_value(values).unwrap()
+    }
+
+    #[test]
+    fn tracks_latest_min_max_and_rate() {
+        let params = vec!["engine_rpm".to_string(), "gear".to_string()];
+        let units = HashMap::from([("engine_rpm".to_string(), "rpm".to_string())]);
+        let mut dashboard = Dashboard::new("engine_ecu", &params, &units);
+
+        let start = Instant::now();
+        dashboard.record(&values(serde_json::json!({ "engine_rpm": 1800.0 })), start);
+        dashboard.record(
+            &values(serde_json::json!({ "engine_rpm": 950, "gear": "D" })),
+            start + Duration::from_millis(500),
+        );
+        dashboard.record(
+            &values(serde_json::json!({ "engine_rpm": 2400.5 })),
+            start + Duration::from_secs(1),
+        );
+
+        let rpm = &dashboard.rows[0];
+        assert_eq!(rpm.unit, "rpm");
+        assert_eq!(rpm.latest, Some(serde_json::json!(2400.5)));
+        assert_eq!((rpm.min, rpm.max), (Some(950.0), Some(2400.5)));
+        assert_eq!(rpm.rate(), Some(2.0));
+
+        // Not numeric: no min/max; one update: no rate yet
+        let gear = &dashboard.rows[1];
+        assert_eq!(gear.unit, "");
+        assert_eq!(gear.latest, Some(serde_json::json!("D")));
+        assert_eq!((gear.min, gear.max), (None, None));
+        assert_eq!(gear.rate(), None);
+    }
+}
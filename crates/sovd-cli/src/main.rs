@@ -2,12 +2,17 @@
 //!
 //! A comprehensive CLI for interacting with SOVD-compliant diagnostic servers.
 
+#[cfg(feature = "parquet")]
+mod capture;
 mod commands;
 mod config;
+#[cfg(feature = "dashboard")]
+mod dashboard;
 mod output;
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
 use sovd_client::flash::FlashClient;
 use sovd_client::SovdClient;
 use std::path::PathBuf;
@@ -127,6 +132,18 @@ enum Commands {
         /// Clear all faults
         #[arg(long)]
         clear: bool,
+
+        /// Show the snapshot (freeze frame) records of a DTC
+        #[arg(long, value_name = "DTC", conflicts_with_all = ["clear", "extended"])]
+        snapshot: Option<String>,
+
+        /// Show the extended data records of a DTC
+        #[arg(long, value_name = "DTC", conflicts_with = "clear")]
+        extended: Option<String>,
+
+        /// Extended data record number (default: all records)
+        #[arg(long, requires = "extended")]
+        record: Option<u8>,
     },
 
     /// Monitor parameters in real-time (SSE streaming)
@@ -141,6 +158,15 @@ enum Commands {
         /// Update rate in Hz
         #[arg(long, default_value = "1")]
         rate: u32,
+
+        /// Capture file for `-o parquet` (requires the `parquet` feature)
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Live-updating table of the latest values (requires the
+        /// `dashboard` feature; line output when stdout is not a terminal)
+        #[arg(long)]
+        dashboard: bool,
     },
 
     /// Change diagnostic session
@@ -334,6 +360,15 @@ enum Commands {
         #[arg(long, short = 'd')]
         dir: Option<String>,
     },
+
+    /// Print a shell completion script, e.g.
+    /// `sovd-cli completions bash > /etc/bash_completion.d/sovd-cli`
+    #[command(hide = true)]
+    Completions {
+        /// Shell: bash, zsh, fish, powershell, elvish
+        #[arg(value_enum)]
+        shell: Shell,
+    },
 }
 
 #[tokio::main]
@@ -395,14 +430,42 @@ async fn main() -> Result<()> {
             commands::write(&client, ecu, param, value, &ctx).await?;
         }
 
-        Commands::Faults { ecu, active, clear } => {
+        Commands::Faults {
+            ecu,
+            active,
+            clear,
+            snapshot,
+            extended,
+            record,
+        } => {
             let client = create_client(&merged.server, &auth)?;
-            commands::faults(&client, ecu, *active, *clear, &ctx).await?;
+            if let Some(dtc) = snapshot {
+                commands::fault_snapshots(&client, ecu, dtc, &ctx).await?;
+            } else if let Some(dtc) = extended {
+                commands::fault_extended_data(&client, ecu, dtc, *record, &ctx).await?;
+            } else {
+                commands::faults(&client, ecu, *active, *clear, &ctx).await?;
+            }
         }
 
-        Commands::Monitor { ecu, params, rate } => {
+        Commands::Monitor {
+            ecu,
+            params,
+            rate,
+            out,
+            dashboard,
+        } => {
             let client = create_client(&merged.server, &auth)?;
-            commands::monitor(&client, ecu, params.clone(), *rate, &ctx).await?;
+            commands::monitor(
+                &client,
+                ecu,
+                params.clone(),
+                *rate,
+                out.as_deref(),
+                *dashboard,
+                &ctx,
+            )
+            .await?;
         }
 
         Commands::Session { ecu, session_type } => {
@@ -555,6 +618,10 @@ async fn main() -> Result<()> {
             )
             .await?;
         }
+
+        Commands::Completions { shell } => {
+            commands::completions(*shell, Cli::command(), &mut std::io::stdout());
+        }
     }
 
     Ok(())
@@ -609,6 +676,7 @@ impl From<OutputFormat> for &str {
             OutputFormat::Table => "table",
             OutputFormat::Json => "json",
             OutputFormat::Csv => "csv",
+            OutputFormat::Parquet => "parquet",
         }
     }
 }
@@ -688,4 +756,69 @@ mod tests {
             _ => panic!("expected Logs"),
         }
     }
+
+    /// `completions` is hidden from help but parses every supported shell,
+    /// and the script it writes covers the subcommands.
+    #[test]
+    fn completions_cover_the_subcommands() {
+        for shell in ["bash", "zsh", "fish", "powershell"] {
+            let cli = Cli::try_parse_from(["sovd-cli", "completions", shell]).expect(shell);
+            assert!(matches!(cli.command, Commands::Completions { .. }));
+        }
+        assert!(Cli::command()
+            .find_subcommand("completions")
+            .is_some_and(|c| c.is_hide_set()));
+
+        let mut script = Vec::new();
+        commands::completions(Shell::Bash, Cli::command(), &mut script);
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("sovd-cli"));
+        assert!(script.contains("faults"));
+        assert!(script.contains("--dashboard"));
+    }
+
+    /// `--snapshot` and `--extended` take the DTC; they exclude each other
+    /// and `--clear`, and `--record` only narrows `--extended`.
+    #[test]
+    fn faults_snapshot_and_extended_take_a_dtc() {
+        let cli = Cli::try_parse_from(["sovd-cli", "faults", "ecu", "--snapshot", "P0123"])
+            .expect("parse snapshot");
+        match cli.command {
+            Commands::Faults {
+                snapshot, extended, ..
+            } => {
+                assert_eq!(snapshot.as_deref(), Some("P0123"));
+                assert!(extended.is_none());
+            }
+            _ => panic!("expected Faults"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "sovd-cli",
+            "faults",
+            "ecu",
+            "--extended",
+            "P0123",
+            "--record",
+            "1",
+        ])
+        .expect("parse extended");
+        match cli.command {
+            Commands::Faults {
+                extended, record, ..
+            } => {
+                assert_eq!(extended.as_deref(), Some("P0123"));
+                assert_eq!(record, Some(1));
+            }
+            _ => panic!("expected Faults"),
+        }
+
+        let rejected = |flags: &[&str]| {
+            Cli::try_parse_from(["sovd-cli", "faults", "ecu"].iter().chain(flags)).is_err()
+        };
+        assert!(rejected(&["--snapshot", "P0123", "--extended", "P0123"]));
+        assert!(rejected(&["--snapshot", "P0123", "--clear"]));
+        assert!(rejected(&["--extended", "P0123", "--clear"]));
+        assert!(rejected(&["--record", "1"]));
+    }
 }
@@ -1,4 +1,4 @@
-//! Output formatting for sovd-cli (table, json, csv)
+//! Output formatting for sovd-cli (table, json, csv, parquet)
 
 use clap::ValueEnum;
 use colored::Colorize;
@@ -15,6 +15,9 @@ pub enum OutputFormat {
     Json,
     /// CSV format
     Csv,
+    /// Apache Parquet capture file (`monitor --out`; other commands print
+    /// JSON). Needs the `parquet` feature.
+    Parquet,
 }
 
 /// Context for output rendering
@@ -75,7 +78,7 @@ impl OutputContext {
                     println!("{}", table);
                 }
             }
-            OutputFormat::Json => {
+            OutputFormat::Json | OutputFormat::Parquet => {
                 println!(
                     "{}",
                     serde_json::to_string_pretty(data).unwrap_or_else(|_| "[]".to_string())
@@ -94,7 +97,7 @@ impl OutputContext {
                 let table = Table::new([data]).to_string();
                 println!("{}", table);
             }
-            OutputFormat::Json => {
+            OutputFormat::Json | OutputFormat::Parquet => {
                 println!(
                     "{}",
                     serde_json::to_string_pretty(data).unwrap_or_else(|_| "{}".to_string())
@@ -114,7 +117,7 @@ impl OutputContext {
                     println!("{}: {}", key.bold(), value);
                 }
             }
-            OutputFormat::Json => {
+            OutputFormat::Json | OutputFormat::Parquet => {
                 let map: std::collections::HashMap<&str, &str> =
                     pairs.iter().map(|(k, v)| (*k, v.as_str())).collect();
                 println!(
@@ -136,43 +139,34 @@ impl OutputContext {
 
 /// Print data as CSV
 fn print_csv<T: Serialize>(data: &[T]) {
-    if data.is_empty() {
-        return;
+    if let Err(e) = write_csv(std::io::stdout().lock(), data) {
+        eprintln!("{}", format!("CSV output failed: {}", e).red());
     }
+}
 
-    // Get field names from the first item
-    let first = serde_json::to_value(&data[0]).unwrap_or_default();
-    if let serde_json::Value::Object(map) = &first {
-        // Print header
-        let headers: Vec<&str> = map.keys().map(|s| s.as_str()).collect();
-        println!("{}", headers.join(","));
-
-        // Print rows
-        for item in data {
-            if let Ok(serde_json::Value::Object(row)) = serde_json::to_value(item) {
-                let values: Vec<String> = headers
-                    .iter()
-                    .map(|h| {
-                        row.get(*h)
-                            .map(|v| match v {
-                                serde_json::Value::String(s) => escape_csv(s),
-                                other => escape_csv(&other.to_string()),
-                            })
-                            .unwrap_or_default()
-                    })
-                    .collect();
-                println!("{}", values.join(","));
-            }
-        }
+/// Write data as CSV: a header of the field names in declaration order,
+/// then one record per item. Values holding commas, quotes or line breaks
+/// are quoted.
+fn write_csv<T: Serialize, W: std::io::Write>(out: W, data: &[T]) -> csv::Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    for item in data {
+        writer.serialize(item)?;
     }
+    writer.flush()?;
+    Ok(())
 }
 
-/// Escape a value for CSV output
-fn escape_csv(value: &str) -> String {
-    if value.contains(',') || value.contains('"') || value.contains('\n') {
-        format!("\"{}\"", value.replace('"', "\"\""))
-    } else {
-        value.to_string()
+/// Flatten a value into CSV cells: an object gives one `key.field` cell
+/// per field (recursively), anything else a single cell under `key`
+pub fn flatten_value(key: &str, value: &serde_json::Value) -> Vec<(String, String)> {
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => map
+            .iter()
+            .flat_map(|(field, value)| flatten_value(&format!("{}.{}", key, field), value))
+            .collect(),
+        serde_json::Value::String(s) => vec![(key.to_string(), s.clone())],
+        serde_json::Value::Null => vec![(key.to_string(), String::new())],
+        other => vec![(key.to_string(), other.to_string())],
     }
 }
 
@@ -219,6 +213,22 @@ pub struct DataRow {
     pub raw: String,
 }
 
+/// Data value export for `read -o csv`: one row per parameter, or per
+/// field of an object-valued one, with the server's read time
+#[derive(Debug, Tabled, Serialize)]
+pub struct DataCsvRow {
+    #[tabled(rename = "ID")]
+    pub id: String,
+    #[tabled(rename = "Name")]
+    pub name: String,
+    #[tabled(rename = "Value")]
+    pub value: String,
+    #[tabled(rename = "Unit")]
+    pub unit: String,
+    #[tabled(rename = "Timestamp")]
+    pub timestamp: String,
+}
+
 /// Fault display for faults command
 #[derive(Debug, Tabled, Serialize)]
 pub struct FaultRow {
@@ -234,6 +244,34 @@ pub struct FaultRow {
     pub category: String,
 }
 
+/// Snapshot (freeze frame) value display for `faults --snapshot`
+#[derive(Debug, Tabled, Serialize)]
+pub struct SnapshotRow {
+    #[tabled(rename = "Record")]
+    pub record: String,
+    #[tabled(rename = "Parameter")]
+    pub parameter: String,
+    #[tabled(rename = "Name")]
+    pub name: String,
+    #[tabled(rename = "Value")]
+    pub value: String,
+    #[tabled(rename = "Unit")]
+    pub unit: String,
+}
+
+/// Extended data record display for `faults --extended`
+#[derive(Debug, Tabled, Serialize)]
+pub struct ExtendedDataRow {
+    #[tabled(rename = "Record")]
+    pub record: String,
+    #[tabled(rename = "Name")]
+    pub name: String,
+    #[tabled(rename = "Value")]
+    pub value: String,
+    #[tabled(rename = "Raw")]
+    pub raw: String,
+}
+
 /// Output display for the logs command (SOVD §7.21 entries).
 #[derive(Debug, Tabled, Serialize)]
 pub struct LogRow {
@@ -300,3 +338,48 @@ pub struct StreamRow {
     #[tabled(rename = "Value")]
     pub value: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_keeps_field_order_and_quotes_values() {
+        let rows = [DataCsvRow {
+            id: "position".to_string(),
+            name: "GPS position".to_string(),
+            value: "48.1, 11.6".to_string(),
+            unit: "say \"deg\"".to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+        }];
+        let mut out = Vec::new();
+        write_csv(&mut out, &rows).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "id,name,value,unit,timestamp\n\
+             position,GPS position,\"48.1, 11.6\",\"say \"\"deg\"\"\",2026-01-01T00:00:00Z\n"
+        );
+    }
+
+    #[test]
+    fn objects_flatten_to_dotted_keys() {
+        let value = serde_json::json!({
+            "lat": 48.1,
+            "fix": { "mode": "3d", "sats": 9 },
+            "note": null,
+        });
+        assert_eq!(
+            flatten_value("gps", &value),
+            [
+                ("gps.fix.mode".to_string(), "3d".to_string()),
+                ("gps.fix.sats".to_string(), "9".to_string()),
+                ("gps.lat".to_string(), "48.1".to_string()),
+                ("gps.note".to_string(), String::new()),
+            ]
+        );
+        assert_eq!(
+            flatten_value("rpm", &serde_json::json!(1800)),
+            [("rpm".to_string(), "1800".to_string())]
+        );
+    }
+}
@@ -1,14 +1,30 @@
 //! SOVD HTTP Client implementation
 
+use std::future::Future;
+use std::sync::Arc;
 use std::time::Duration;
 
 use reqwest::{Client, StatusCode};
+use sovd_core::NegativeResponseCode;
 use tracing::{debug, instrument};
 use url::Url;
 
 use crate::error::{Result, SovdClientError};
 use crate::types::*;
 
+/// Media type of CBOR data responses (`binary` feature)
+const CBOR: &str = "application/cbor";
+
+/// Whether a response carries a CBOR body
+#[cfg(feature = "binary")]
+fn is_cbor(response: &reqwest::Response) -> bool {
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with(CBOR))
+}
+
 /// URL-encode a resource ID for use in path segments.
 ///
 /// Gateway-prefixed IDs like `"vtx_ecm/vin"` must be encoded to
@@ -45,6 +61,10 @@ fn operation_to_output(op: OperationInfo) -> OutputInfo {
         },
         controlled_by_tester: op.controlled_by_tester,
         frozen: op.frozen,
+        last_action: op.last_action,
+        last_value: op.last_value,
+        last_commanded_at: op.last_commanded_at,
+        session_reset: op.session_reset,
         requires_security: Some(op.requires_security),
         security_level: Some(op.security_level),
     }
@@ -78,6 +98,65 @@ pub(crate) fn apply_tls(
 pub struct SovdClient {
     client: Client,
     base_url: Url,
+    /// `Authorization` header of a bearer client, for requests that don't go
+    /// through `client` (WebSocket upgrades)
+    auth: Option<reqwest::header::HeaderValue>,
+    options: ClientOptions,
+}
+
+/// Computes the security key for a seed
+pub type KeyFn = Arc<dyn Fn(&[u8]) -> Vec<u8> + Send + Sync>;
+
+/// Security access the client re-establishes after a session escalation
+#[derive(Clone)]
+pub struct UnlockCredentials {
+    /// Level to unlock
+    pub level: SecurityLevel,
+    /// Seed-to-key algorithm for `level`
+    pub key: KeyFn,
+}
+
+impl std::fmt::Debug for UnlockCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnlockCredentials")
+            .field("level", &self.level)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Behaviour options fixed at client construction
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    /// On a data read or write the ECU refuses in the active session
+    /// (requestOutOfRange, securityAccessDenied,
+    /// (sub)serviceNotSupportedInActiveSession), switch to `session`,
+    /// unlock with `credentials` when set, and retry once
+    pub auto_session: bool,
+    /// Session entered when escalating (default: extended)
+    pub session: SessionType,
+    /// Security access to re-establish after escalating
+    pub credentials: Option<UnlockCredentials>,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            auto_session: false,
+            session: SessionType::Extended,
+            credentials: None,
+        }
+    }
+}
+
+/// Whether a negative response may clear in a different session
+fn escalates(nrc: NegativeResponseCode) -> bool {
+    matches!(
+        nrc,
+        NegativeResponseCode::RequestOutOfRange
+            | NegativeResponseCode::SecurityAccessDenied
+            | NegativeResponseCode::SubFunctionNotSupportedInActiveSession
+            | NegativeResponseCode::ServiceNotSupportedInActiveSession
+    )
 }
 
 impl SovdClient {
@@ -152,7 +231,33 @@ impl SovdClient {
 
         let base_url = Url::parse(base_url)?;
 
-        Ok(Self { client, base_url })
+        Ok(Self {
+            client,
+            base_url,
+            auth: None,
+            options: ClientOptions::default(),
+        })
+    }
+
+    /// Create a new SOVD client with [`ClientOptions`]
+    ///
+    /// ```rust,ignore
+    /// let client = SovdClient::with_options(
+    ///     "http://localhost:9080",
+    ///     ClientOptions {
+    ///         auto_session: true,
+    ///         credentials: Some(UnlockCredentials {
+    ///             level: SecurityLevel::LEVEL_1,
+    ///             key: Arc::new(|seed| seed.iter().map(|b| b ^ 0xFF).collect()),
+    ///         }),
+    ///         ..Default::default()
+    ///     },
+    /// )?;
+    /// ```
+    pub fn with_options(base_url: &str, options: ClientOptions) -> Result<Self> {
+        let mut client = Self::new(base_url)?;
+        client.options = options;
+        Ok(client)
     }
 
     /// Create a new SOVD client that sends a bearer token with every request.
@@ -178,9 +283,10 @@ impl SovdClient {
         ca_cert_pem: Option<&[u8]>,
     ) -> Result<Self> {
         let mut headers = reqwest::header::HeaderMap::new();
-        let header_value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+        let mut header_value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
             .map_err(|e| SovdClientError::ParseError(format!("Invalid auth token: {}", e)))?;
-        headers.insert(reqwest::header::AUTHORIZATION, header_value);
+        header_value.set_sensitive(true);
+        headers.insert(reqwest::header::AUTHORIZATION, header_value.clone());
 
         let client = apply_tls(
             Client::builder()
@@ -194,7 +300,12 @@ impl SovdClient {
 
         let base_url = Url::parse(base_url)?;
 
-        Ok(Self { client, base_url })
+        Ok(Self {
+            client,
+            base_url,
+            auth: Some(header_value),
+            options: ClientOptions::default(),
+        })
     }
 
     /// Get the base URL
@@ -311,10 +422,35 @@ impl SovdClient {
             .base_url
             .join(&format!("/vehicle/v1/components/{}/data", component_id))?;
 
-        let response = self.client.get(url).send().await?;
+        let response = self.data_get(url).send().await?;
         self.handle_response(response).await
     }
 
+    /// List parameters a page at a time
+    ///
+    /// Requests `page_size` parameters per round trip and follows the
+    /// server's `next` link until the last page, returning them all. `q`
+    /// keeps only parameters whose id or name contains it.
+    #[instrument(skip(self))]
+    pub async fn list_parameters_paged(
+        &self,
+        component_id: &str,
+        page_size: usize,
+        q: Option<&str>,
+    ) -> Result<Vec<ParameterInfo>> {
+        let url = self.page_url(
+            &format!("/vehicle/v1/components/{}/data", component_id),
+            page_size,
+            q,
+        )?;
+        self.follow_pages(
+            url,
+            |url| self.data_get(url),
+            |page: ParametersResponse| (page.items, page.next),
+        )
+        .await
+    }
+
     /// List parameters for a sub-entity (accessed via gateway's apps route)
     ///
     /// Routes through: `GET /vehicle/v1/components/{component_id}/apps/{app_path}/data`
@@ -408,13 +544,18 @@ impl SovdClient {
     /// ```
     #[instrument(skip(self))]
     pub async fn read_data(&self, component_id: &str, param_id: &str) -> Result<DataResponse> {
+        self.with_session_escalation(component_id, || self.read_data_once(component_id, param_id))
+            .await
+    }
+
+    async fn read_data_once(&self, component_id: &str, param_id: &str) -> Result<DataResponse> {
         let url = self.base_url.join(&format!(
             "/vehicle/v1/components/{}/data/{}",
             component_id,
             encode_path_segment(param_id)
         ))?;
 
-        let response = self.client.get(url).send().await?;
+        let response = self.data_get(url).send().await?;
         self.handle_response(response).await
     }
 
@@ -429,6 +570,13 @@ impl SovdClient {
     /// For client-side conversion of private data, see [`DataResponse::raw_bytes`].
     #[instrument(skip(self))]
     pub async fn read_data_raw(&self, component_id: &str, param_id: &str) -> Result<DataResponse> {
+        self.with_session_escalation(component_id, || {
+            self.read_data_raw_once(component_id, param_id)
+        })
+        .await
+    }
+
+    async fn read_data_raw_once(&self, component_id: &str, param_id: &str) -> Result<DataResponse> {
         let mut url = self.base_url.join(&format!(
             "/vehicle/v1/components/{}/data/{}",
             component_id,
@@ -436,27 +584,30 @@ impl SovdClient {
         ))?;
         url.set_query(Some("raw=true"));
 
-        let response = self.client.get(url).send().await?;
+        let response = self.data_get(url).send().await?;
         self.handle_response(response).await
     }
 
-    /// Read multiple parameter values
+    /// Read multiple parameter values in one request
+    ///
+    /// Returns one item per id, in order; a parameter that cannot be read
+    /// carries its own error without failing the others.
     #[instrument(skip(self))]
     pub async fn read_data_batch(
         &self,
         component_id: &str,
         param_ids: &[&str],
-    ) -> Result<Vec<DataResponse>> {
+    ) -> Result<Vec<DataListItem>> {
         let params = param_ids.join(",");
         let url = self.base_url.join(&format!(
             "/vehicle/v1/components/{}/data?ids={}",
             component_id, params
         ))?;
 
-        let response = self.client.get(url).send().await?;
+        let response = self.data_get(url).send().await?;
         self.handle_response::<DataListResponse>(response)
             .await
-            .map(|r| r.data)
+            .map(|r| r.items)
     }
 
     /// Write a parameter value
@@ -474,7 +625,41 @@ impl SovdClient {
         ))?;
 
         let request = WriteDataRequest { value };
-        let response = self.client.put(url).json(&request).send().await?;
+        self.with_session_escalation(component_id, || self.put_data(url.clone(), &request))
+            .await
+    }
+
+    /// Write several parameters in order with one request
+    ///
+    /// Best effort: the ECU has no transaction across writes, so values
+    /// written before a failure stay written.
+    /// [`BatchWriteResponse::failed_index`] is the first failure; with
+    /// `stop_on_error` the parameters after it are not attempted.
+    #[instrument(skip(self, values))]
+    pub async fn write_data_batch(
+        &self,
+        component_id: &str,
+        values: &[(&str, serde_json::Value)],
+        stop_on_error: bool,
+    ) -> Result<BatchWriteResponse> {
+        let url = self.base_url.join(&format!(
+            "/vehicle/v1/components/{}/data:batch",
+            component_id
+        ))?;
+        let request = BatchWriteRequest {
+            values: values
+                .iter()
+                .map(|(id, value)| (id.to_string(), value.clone()))
+                .collect(),
+            stop_on_error,
+        };
+
+        let response = self.client.post(url).json(&request).send().await?;
+        self.handle_response(response).await
+    }
+
+    async fn put_data(&self, url: Url, request: &WriteDataRequest) -> Result<()> {
+        let response = self.client.put(url).json(request).send().await?;
 
         if response.status().is_success() {
             Ok(())
@@ -574,13 +759,8 @@ impl SovdClient {
         ))?;
 
         let request = WriteDataRequest { value };
-        let response = self.client.put(url).json(&request).send().await?;
-
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            Err(self.extract_error(response).await)
-        }
+        self.with_session_escalation(component_id, || self.put_data(url.clone(), &request))
+            .await
     }
 
     // =========================================================================
@@ -600,6 +780,31 @@ impl SovdClient {
             .map(|r| r.items)
     }
 
+    /// Get faults/DTCs a page at a time
+    ///
+    /// Like [`Self::list_parameters_paged`]: `page_size` faults per round
+    /// trip, following `next` to the last page. `q` keeps only faults whose
+    /// code or name contains it.
+    #[instrument(skip(self))]
+    pub async fn get_faults_paged(
+        &self,
+        component_id: &str,
+        page_size: usize,
+        q: Option<&str>,
+    ) -> Result<Vec<FaultInfo>> {
+        let url = self.page_url(
+            &format!("/vehicle/v1/components/{}/faults", component_id),
+            page_size,
+            q,
+        )?;
+        self.follow_pages(
+            url,
+            |url| self.client.get(url),
+            |page: FaultsResponse| (page.items, page.next),
+        )
+        .await
+    }
+
     /// Get faults/DTCs filtered by category
     #[instrument(skip(self))]
     pub async fn get_faults_filtered(
@@ -634,24 +839,106 @@ impl SovdClient {
         self.handle_response(response).await
     }
 
+    /// Get the snapshot (freeze frame) records of a fault
+    #[instrument(skip(self))]
+    pub async fn get_fault_snapshots(
+        &self,
+        component_id: &str,
+        fault_id: &str,
+    ) -> Result<Vec<FaultSnapshot>> {
+        let url = self.base_url.join(&format!(
+            "/vehicle/v1/components/{}/faults/{}/snapshots",
+            component_id,
+            encode_path_segment(fault_id)
+        ))?;
+
+        let response = self.client.get(url).send().await?;
+        self.handle_response::<FaultSnapshotsResponse>(response)
+            .await
+            .map(|r| r.items)
+    }
+
+    /// Get the extended data records of a fault: every record, or only
+    /// `record` when given
+    #[instrument(skip(self))]
+    pub async fn get_fault_extended_data(
+        &self,
+        component_id: &str,
+        fault_id: &str,
+        record: Option<u8>,
+    ) -> Result<Vec<FaultExtendedDataRecord>> {
+        let mut url = self.base_url.join(&format!(
+            "/vehicle/v1/components/{}/faults/{}/extended-data",
+            component_id,
+            encode_path_segment(fault_id)
+        ))?;
+        if let Some(record) = record {
+            url.set_query(Some(&format!("record={}", record)));
+        }
+
+        let response = self.client.get(url).send().await?;
+        self.handle_response::<FaultExtendedDataResponse>(response)
+            .await
+            .map(|r| r.items)
+    }
+
     /// Clear all faults/DTCs from a component.
     ///
     /// Wire: `DELETE /components/{id}/faults` → **204 No Content** per
     /// spec.  The returned `ClearFaultsResponse` is a courtesy
     /// success-shape derived from the status code; the server no
-    /// longer emits a body for collection deletes.
+    /// longer emits a body for collection deletes — except for an ECU
+    /// that verifies clears, which answers 200 with `cleared_confirmed`
+    /// and the `remaining` faults.
     #[instrument(skip(self))]
     pub async fn clear_faults(&self, component_id: &str) -> Result<ClearFaultsResponse> {
-        let url = self
+        self.clear_faults_with(component_id, false).await
+    }
+
+    /// Clear all faults, letting the server enter the extended session
+    /// for the clear when the active session refuses it
+    /// (`?auto_session=true`).
+    ///
+    /// `session_escalation` is set when the server switched sessions.
+    #[instrument(skip(self))]
+    pub async fn clear_faults_auto_session(
+        &self,
+        component_id: &str,
+    ) -> Result<ClearFaultsResponse> {
+        self.clear_faults_with(component_id, true).await
+    }
+
+    async fn clear_faults_with(
+        &self,
+        component_id: &str,
+        auto_session: bool,
+    ) -> Result<ClearFaultsResponse> {
+        let mut url = self
             .base_url
             .join(&format!("/vehicle/v1/components/{}/faults", component_id))?;
+        if auto_session {
+            url.set_query(Some("auto_session=true"));
+        }
 
         let response = self.client.delete(url).send().await?;
-        if response.status().is_success() {
+        if response.status() == StatusCode::OK {
+            let body: ClearVerificationBody = response.json().await?;
+            Ok(ClearFaultsResponse {
+                success: true,
+                cleared_count: None,
+                message: None,
+                cleared_confirmed: body.cleared_confirmed,
+                remaining: body.remaining,
+                session_escalation: body.session_escalation,
+            })
+        } else if response.status().is_success() {
             Ok(ClearFaultsResponse {
                 success: true,
                 cleared_count: None,
                 message: None,
+                cleared_confirmed: None,
+                remaining: Vec::new(),
+                session_escalation: None,
             })
         } else {
             Err(self.extract_error(response).await)
@@ -1266,6 +1553,21 @@ impl SovdClient {
             .map_err(|e: String| SovdClientError::ParseError(e))
     }
 
+    /// P2/P2* timing the ECU advertised in its DiagnosticSessionControl
+    /// response for the active session, if any
+    ///
+    /// The server uses these for its own response timeouts; some ECUs grant
+    /// longer ones in the programming session only.
+    #[instrument(skip(self))]
+    pub async fn get_session_timing(
+        &self,
+        component_id: &str,
+    ) -> Result<Option<sovd_core::SessionTimingParameters>> {
+        self.get_mode(component_id, "session")
+            .await
+            .map(|mode| mode.timing)
+    }
+
     /// Change diagnostic session
     #[instrument(skip(self))]
     pub async fn set_session(&self, component_id: &str, session: SessionType) -> Result<()> {
@@ -1546,6 +1848,43 @@ impl SovdClient {
         .map_err(|e| SovdClientError::StreamError(e.to_string()))
     }
 
+    /// Subscribe like [`subscribe`](Self::subscribe), but receive the events
+    /// over a WebSocket
+    ///
+    /// The returned subscription yields the same [`StreamEvent`](crate::StreamEvent)s
+    /// and additionally accepts `pause()`, `resume()` and `set_interval()`
+    /// on the live stream.
+    #[instrument(skip(self))]
+    pub async fn subscribe_ws(
+        &self,
+        component_id: &str,
+        resource: &str,
+        interval: SubscriptionInterval,
+    ) -> Result<crate::streaming::Subscription> {
+        use crate::streaming::Subscription;
+
+        let response = self
+            .create_cyclic_subscription(component_id, resource, interval)
+            .await?;
+
+        // Same resource as the SSE stream, upgraded to a WebSocket.
+        let stream_url = format!(
+            "/vehicle/v1/components/{}/cyclic-subscriptions/{}",
+            component_id, response.subscription_id
+        );
+
+        Subscription::connect_ws(
+            self.base_url.clone(),
+            self.client.clone(),
+            response.subscription_id,
+            Some(component_id.to_string()),
+            &stream_url,
+            self.auth.as_ref(),
+        )
+        .await
+        .map_err(|e| SovdClientError::StreamError(e.to_string()))
+    }
+
     // `subscribe_inline` (the non-spec inline `?parameters=` streamer) and
     // the global flat-namespace subscriptions were retired for C-025 —
     // `streams` is not a standardized resource name. All streaming goes
@@ -1727,6 +2066,43 @@ impl SovdClient {
         self.handle_response(response).await
     }
 
+    /// Get the JSON Schema describing the definition upload format
+    #[instrument(skip(self))]
+    pub async fn get_definition_schema(&self) -> Result<serde_json::Value> {
+        let url = self.base_url.join("/admin/definitions/schema")?;
+        let response = self.client.get(url).send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Fetch the server's DID definitions into a local `DidStore`
+    ///
+    /// Pulls the full YAML export (`GET /admin/definitions?format=yaml`), so
+    /// [`read_data_raw`](Self::read_data_raw) results can be decoded
+    /// client-side without keeping a copy of the definition files. See
+    /// [`DefinitionCache`](crate::DefinitionCache) for a TTL-cached variant.
+    #[cfg(feature = "conversion")]
+    #[instrument(skip(self))]
+    pub async fn fetch_definitions(&self) -> Result<sovd_conv::DidStore> {
+        let mut url = self.base_url.join("/admin/definitions")?;
+        url.query_pairs_mut().append_pair("format", "yaml");
+        let response = self.client.get(url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(self.extract_error(response).await);
+        }
+
+        let yaml = response.text().await?;
+        sovd_conv::DidStore::from_yaml(&yaml)
+            .map_err(|e| SovdClientError::ParseError(format!("Invalid definitions: {}", e)))
+    }
+
+    /// Create a [`DefinitionCache`](crate::DefinitionCache) backed by this
+    /// client, refetching definitions once they are older than `ttl`
+    #[cfg(feature = "conversion")]
+    pub fn definition_cache(&self, ttl: Duration) -> crate::DefinitionCache {
+        crate::DefinitionCache::new(self.clone(), ttl)
+    }
+
     /// Upload DID definitions (YAML format)
     #[instrument(skip(self, yaml_content))]
     pub async fn upload_definitions(
@@ -1777,6 +2153,12 @@ impl SovdClient {
         let status = response.status();
 
         if status.is_success() {
+            #[cfg(feature = "binary")]
+            if is_cbor(&response) {
+                let body = response.bytes().await?;
+                return ciborium::from_reader(body.as_ref())
+                    .map_err(|e| SovdClientError::ParseError(e.to_string()));
+            }
             response
                 .json()
                 .await
@@ -1786,6 +2168,78 @@ impl SovdClient {
         }
     }
 
+    /// GET request to a data endpoint; with the `binary` feature it asks
+    /// for CBOR, which [`Self::handle_response`] decodes transparently
+    /// Run `request`; with [`ClientOptions::auto_session`], a negative
+    /// response that may clear in another session escalates the session
+    /// (unlocking when credentials are configured) and retries once.
+    async fn with_session_escalation<T, F, Fut>(&self, component_id: &str, request: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        match request().await {
+            Err(e) if self.options.auto_session && e.nrc().is_some_and(escalates) => {
+                debug!(component_id, nrc = ?e.nrc(), session = ?self.options.session, "escalating session");
+                self.set_session(component_id, self.options.session).await?;
+                if let Some(credentials) = &self.options.credentials {
+                    let seed = self
+                        .security_access_request_seed(component_id, credentials.level)
+                        .await?;
+                    let key = (credentials.key)(&seed);
+                    self.security_access_send_key(component_id, credentials.level, &key)
+                        .await?;
+                }
+                request().await
+            }
+            other => other,
+        }
+    }
+
+    fn data_get(&self, url: Url) -> reqwest::RequestBuilder {
+        let request = self.client.get(url);
+        if cfg!(feature = "binary") {
+            request.header(reqwest::header::ACCEPT, CBOR)
+        } else {
+            request
+        }
+    }
+
+    /// First page of a paged listing at `path`
+    fn page_url(&self, path: &str, page_size: usize, q: Option<&str>) -> Result<Url> {
+        let mut url = self.base_url.join(path)?;
+        {
+            let mut qp = url.query_pairs_mut();
+            qp.append_pair("limit", &page_size.to_string());
+            if let Some(q) = q {
+                qp.append_pair("q", q);
+            }
+        }
+        Ok(url)
+    }
+
+    /// GET `url` and every page its `next` links lead to, collecting the items
+    async fn follow_pages<R, I>(
+        &self,
+        mut url: Url,
+        request: impl Fn(Url) -> reqwest::RequestBuilder,
+        split: impl Fn(R) -> (Vec<I>, Option<String>),
+    ) -> Result<Vec<I>>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let mut items = Vec::new();
+        loop {
+            let response = request(url).send().await?;
+            let (page, next) = split(self.handle_response(response).await?);
+            items.extend(page);
+            match next {
+                Some(next) => url = self.base_url.join(&next)?,
+                None => return Ok(items),
+            }
+        }
+    }
+
     /// Extract error from failed response
     async fn extract_error(&self, response: reqwest::Response) -> SovdClientError {
         let status = response.status();
@@ -1801,7 +2255,17 @@ impl SovdClient {
         // Older servers that haven't migrated may still send ad-hoc shapes;
         // fall back to the HTTP status line in that case.
         let message = match response.json::<ErrorResponse>().await {
-            Ok(err) => err.message,
+            Ok(err) => {
+                // An ECU rejection carries the UDS service and NRC
+                if let Some((service, nrc)) = err.negative_response() {
+                    return SovdClientError::NegativeResponse {
+                        nrc: nrc.into(),
+                        service,
+                        message: err.message,
+                    };
+                }
+                err.message
+            }
             Err(_) => format!("HTTP {}", status),
         };
 
@@ -0,0 +1,66 @@
+//! Client-side cache of the server's DID definitions
+//!
+//! Requires the `conversion` feature.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use sovd_conv::DidStore;
+use tokio::sync::Mutex;
+
+use crate::client::SovdClient;
+use crate::error::Result;
+
+/// DID definitions fetched from `/admin/definitions`, refreshed on a TTL
+///
+/// [`store`](Self::store) returns the cached [`DidStore`] while it is younger
+/// than the TTL and refetches it otherwise. Concurrent callers share one
+/// fetch. A failed refresh is returned as an error; the previous store is
+/// kept for the next attempt.
+pub struct DefinitionCache {
+    client: SovdClient,
+    ttl: Duration,
+    cached: Mutex<Option<(Instant, Arc<DidStore>)>>,
+}
+
+impl DefinitionCache {
+    /// Create an empty cache; the first [`store`](Self::store) call fetches
+    pub fn new(client: SovdClient, ttl: Duration) -> Self {
+        Self {
+            client,
+            ttl,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Current definitions, refetched if older than the TTL
+    pub async fn store(&self) -> Result<Arc<DidStore>> {
+        let mut cached = self.cached.lock().await;
+        if let Some((fetched_at, store)) = cached.as_ref() {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(store.clone());
+            }
+        }
+        self.fetch_into(&mut cached).await
+    }
+
+    /// Refetch now, regardless of the TTL
+    pub async fn refresh(&self) -> Result<Arc<DidStore>> {
+        let mut cached = self.cached.lock().await;
+        self.fetch_into(&mut cached).await
+    }
+
+    /// Drop the cached store so the next [`store`](Self::store) refetches
+    pub async fn invalidate(&self) {
+        *self.cached.lock().await = None;
+    }
+
+    async fn fetch_into(
+        &self,
+        cached: &mut Option<(Instant, Arc<DidStore>)>,
+    ) -> Result<Arc<DidStore>> {
+        let store = Arc::new(self.client.fetch_definitions().await?);
+        *cached = Some((Instant::now(), store.clone()));
+        Ok(store)
+    }
+}
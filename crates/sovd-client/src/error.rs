@@ -1,5 +1,6 @@
 //! Error types for SOVD client operations
 
+use sovd_core::NegativeResponseCode;
 use thiserror::Error;
 
 /// Result type alias for SOVD client operations
@@ -24,6 +25,15 @@ pub enum SovdClientError {
     #[error("Server error {status}: {message}")]
     ServerError { status: u16, message: String },
 
+    /// The ECU rejected the request with a UDS negative response, as
+    /// reported in the server's `error-response` body
+    #[error("ECU negative response {nrc} (NRC 0x{nrc:02X}, SID 0x{service:02X}): {message}")]
+    NegativeResponse {
+        nrc: NegativeResponseCode,
+        service: u8,
+        message: String,
+    },
+
     /// Failed to parse response
     #[error("Failed to parse response: {0}")]
     ParseError(String),
@@ -69,4 +79,12 @@ impl SovdClientError {
             message: message.into(),
         }
     }
+
+    /// The ECU's negative response code, if the ECU rejected the request
+    pub fn nrc(&self) -> Option<NegativeResponseCode> {
+        match self {
+            Self::NegativeResponse { nrc, .. } => Some(*nrc),
+            _ => None,
+        }
+    }
 }
@@ -6,6 +6,7 @@
 //! ```text
 //! open_update                                 (POST /updates)
 //! upload_part × N                             (PUT  /updates/{id}/bulk-data/{part_id})
+//!   or upload_file (resumable Content-Range chunks of a file on disk)
 //! prepare                                     (PUT  /updates/{id}/prepare)  — async 202+poll
 //! execute(orchestrated: bool)                 (PUT  /updates/{id}/execute)  — async 202+poll
 //! ecu_reset                                   (PUT  /components/{id}/status/restart)
@@ -97,6 +98,9 @@ pub enum FlashError {
 
     #[error("No /updates session open — call open_update first")]
     NoSession,
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 pub type Result<T> = std::result::Result<T, FlashError>;
@@ -146,6 +150,10 @@ pub struct UpdateStatusBody {
     /// Absent on servers that haven't migrated → `None`.
     #[serde(default, rename = "x-sumo-reset-kind")]
     pub reset_kind: Option<sovd_core::ResetKind>,
+    /// Vendor extension: flash outcome record (bytes, duration,
+    /// throughput, retries, verified version) once the update is terminal.
+    #[serde(default, rename = "x-sumo-flash-summary")]
+    pub flash_summary: Option<sovd_core::FlashSummary>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -291,6 +299,22 @@ impl FlashClient {
     }
 }
 
+/// `Upload-Offset` of a bulk-data part response
+fn upload_offset(resp: &reqwest::Response) -> Option<u64> {
+    resp.headers()
+        .get("upload-offset")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+fn upload_progress(bytes_received: u64, bytes_total: u64) -> UploadProgress {
+    UploadProgress {
+        bytes_received,
+        bytes_total: Some(bytes_total),
+        percent: Some(bytes_received as f64 * 100.0 / bytes_total as f64),
+    }
+}
+
 /// Form a URL-clean, spec-exemplar §7.18 package-id from a human `name` +
 /// `version`: slugify the name (lowercase; each run of non-alphanumerics
 /// collapses to a single `-`, edges trimmed) and suffix `-{version}`.
@@ -444,6 +468,97 @@ impl FlashClient {
             .await
     }
 
+    /// `PUT …/bulk-data/{part_id}` of a file on disk in `Content-Range`
+    /// chunks of `chunk_size` bytes, so only one chunk is ever in memory and
+    /// a dropped connection costs at most one chunk. `progress` gets the
+    /// bytes the server holds after every chunk.
+    ///
+    /// Resumable: the part's `Upload-Offset` is asked (`HEAD`) first and the
+    /// upload continues from there. After a disconnect, or in a new process
+    /// [`attach`](Self::attach)ed to the update_id, calling `upload_file`
+    /// again with the same part and file picks up where the server stopped.
+    /// Lazily opens an update session if none is currently open.
+    #[instrument(skip(self, path, progress))]
+    pub async fn upload_file<F>(
+        &self,
+        part_id: &str,
+        path: impl AsRef<std::path::Path>,
+        chunk_size: usize,
+        mut progress: Option<F>,
+    ) -> Result<PartUploadResponse>
+    where
+        F: FnMut(&UploadProgress),
+    {
+        use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+        let update_id = self.ensure_session().await?;
+        let encoded_part = utf8_percent_encode(part_id, PART_SEGMENT_ENCODE).to_string();
+        let url = self.build_url(&self.config.updates_part_path(&update_id, &encoded_part))?;
+        let mut file = tokio::fs::File::open(path.as_ref()).await?;
+        let total = file.metadata().await?.len();
+        if total == 0 {
+            // An empty part has no byte range to send
+            return self.upload_part(part_id, &[]).await;
+        }
+
+        let mut offset = self.uploaded_offset(url.clone(), total).await?;
+        if offset > 0 {
+            info!(offset, total, "resuming part {part_id}");
+        }
+        let mut buf = vec![0u8; chunk_size.max(1)];
+        loop {
+            let len = (total - offset).min(buf.len() as u64) as usize;
+            file.seek(std::io::SeekFrom::Start(offset)).await?;
+            file.read_exact(&mut buf[..len]).await?;
+            let end = offset + len as u64 - 1;
+            let mut req = self
+                .client
+                .put(url.clone())
+                .header("content-type", "application/octet-stream")
+                .header("content-range", format!("bytes {offset}-{end}/{total}"));
+            req = self.add_auth(req);
+            let resp = req
+                .timeout(Duration::from_millis(self.config.timeouts.upload_ms))
+                .body(buf[..len].to_vec())
+                .send()
+                .await?;
+            if resp.status() == StatusCode::ACCEPTED {
+                offset = upload_offset(&resp).unwrap_or(end + 1);
+            } else {
+                let done: PartUploadResponse = self.handle_response(resp).await?;
+                if let Some(ref mut p) = progress {
+                    p(&upload_progress(total, total));
+                }
+                return Ok(done);
+            }
+            if let Some(ref mut p) = progress {
+                p(&upload_progress(offset, total));
+            }
+        }
+    }
+
+    /// Bytes of a `total`-sized part the server already holds: 0 for a
+    /// new part, and for a complete one of another size (re-uploaded)
+    async fn uploaded_offset(&self, url: Url, total: u64) -> Result<u64> {
+        let mut req = self.client.head(url);
+        req = self.add_auth(req);
+        let resp = req.send().await?;
+        let length = resp
+            .headers()
+            .get("upload-length")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok());
+        Ok(match (resp.status().is_success(), length) {
+            (true, Some(length)) if length == total => {
+                // A complete part is sent again from the start
+                upload_offset(&resp)
+                    .filter(|&offset| offset < total)
+                    .unwrap_or(0)
+            }
+            _ => 0,
+        })
+    }
+
     /// `POST /executions {action: "verify"}`.  Legacy
     /// vendor-extension wire.
     ///
@@ -797,6 +912,8 @@ impl FlashClient {
     /// that need orchestrator-driven commit/rollback over a banked
     /// trial use the typed primitives (open_update + upload_part +
     /// prepare + execute(true) + spec_commit / spec_rollback).
+    ///
+    /// Returns the server's flash summary, if it reports one.
     #[instrument(skip(self, data, progress))]
     pub async fn flash_update<F>(
         &self,
@@ -804,7 +921,7 @@ impl FlashClient {
         data: &[u8],
         _reset_type: &str,
         mut progress: Option<F>,
-    ) -> Result<()>
+    ) -> Result<Option<sovd_core::FlashSummary>>
     where
         F: FnMut(FlashUpdatePhase),
     {
@@ -842,7 +959,7 @@ impl FlashClient {
         if let Some(ref mut p) = progress {
             p(FlashUpdatePhase::Complete);
         }
-        Ok(())
+        Ok(executed.flash_summary.or(prepared.flash_summary))
     }
 }
 
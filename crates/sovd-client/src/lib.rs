@@ -47,6 +47,36 @@
 //! let value = store.decode(0xF405, &raw)?; // Returns 92
 //! ```
 //!
+//! When the server does hold the definitions, fetch them instead of
+//! duplicating the YAML on the client:
+//!
+//! ```rust,ignore
+//! let cache = client.definition_cache(Duration::from_secs(300));
+//! let store = cache.store().await?; // refetched once older than 5 min
+//! let value = store.decode(0xF405, &raw)?;
+//! ```
+//!
+//! # CBOR Data Reads
+//!
+//! With the `binary` feature, `list_parameters`, `read_data`,
+//! `read_data_raw` and `read_data_batch` ask for `application/cbor`,
+//! which is smaller on constrained vehicle links, and decode it into the
+//! same types. Nothing else changes: other requests and streaming stay JSON.
+//!
+//! # Automatic Session Escalation
+//!
+//! With [`ClientOptions::auto_session`], data reads and writes the ECU
+//! refuses in the active session (requestOutOfRange, securityAccessDenied,
+//! (sub)serviceNotSupportedInActiveSession) switch to the extended session,
+//! unlock when [`UnlockCredentials`] are configured, and retry once:
+//!
+//! ```rust,ignore
+//! let client = SovdClient::with_options(url, ClientOptions {
+//!     auto_session: true,
+//!     ..Default::default()
+//! })?;
+//! ```
+//!
 //! # Testing
 //!
 //! The `testing` module (behind the `test-util` feature) provides utilities
@@ -64,6 +94,8 @@
 //! ```
 
 mod client;
+#[cfg(feature = "conversion")]
+pub mod definitions;
 mod error;
 pub mod flash;
 pub mod streaming;
@@ -71,7 +103,7 @@ pub mod streaming;
 pub mod testing;
 mod types;
 
-pub use client::SovdClient;
+pub use client::{ClientOptions, KeyFn, SovdClient, UnlockCredentials};
 pub use error::{Result, SovdClientError};
 pub use types::*;
 
@@ -87,3 +119,5 @@ pub use sovd_core::models::{DataValue, EntityInfo, Fault};
 // Re-export sovd-conv when "conversion" feature is enabled
 #[cfg(feature = "conversion")]
 pub use sovd_conv as conv;
+#[cfg(feature = "conversion")]
+pub use definitions::DefinitionCache;
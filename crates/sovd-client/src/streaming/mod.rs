@@ -1,6 +1,7 @@
 //! Streaming support for SOVD subscriptions
 //!
-//! Provides SSE (Server-Sent Events) streaming for real-time parameter data.
+//! Provides SSE (Server-Sent Events) streaming for real-time parameter data,
+//! or the same events over a WebSocket via `SovdClient::subscribe_ws`.
 //!
 //! # Example
 //!
@@ -4,13 +4,20 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use bytes::Bytes;
-use futures::stream::{Stream, StreamExt};
+use futures::stream::{SplitSink, Stream, StreamExt};
+use futures::SinkExt;
+use reqwest::header::HeaderValue;
 use reqwest::Client;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 use tracing::{debug, warn};
 use url::Url;
 
 use super::parser::SseParser;
 use super::types::{StreamError, StreamEvent, StreamResult};
+use crate::types::SubscriptionInterval;
 
 /// An active subscription that streams events from the server
 ///
@@ -20,6 +27,8 @@ use super::types::{StreamError, StreamEvent, StreamResult};
 ///
 /// - Created via `SovdClient::subscribe()` (attaches to the
 ///   `cyclic-subscriptions/{id}` resource with `Accept: text/event-stream`)
+///   or `SovdClient::subscribe_ws()` (upgrades the same resource to a
+///   WebSocket, which also takes `pause()`/`resume()`/`set_interval()`)
 /// - Events are consumed via `next()` or the `Stream` trait
 /// - Call `cancel()` for explicit cleanup, or let it drop
 ///
@@ -45,13 +54,24 @@ pub struct Subscription {
     /// HTTP client for cleanup request
     http_client: Client,
 
-    /// Inner stream state (wrapped for pinning)
-    inner: Pin<Box<SubscriptionInner>>,
+    /// Where the events come from
+    transport: Transport,
 
     /// Whether the subscription has been cancelled
     cancelled: bool,
 }
 
+/// How events reach the subscription
+enum Transport {
+    /// SSE over a streaming HTTP response (state wrapped for pinning)
+    Sse(Pin<Box<SubscriptionInner>>),
+    /// WebSocket: decoded incoming frames, and the sink for control messages
+    Ws {
+        events: Pin<Box<dyn Stream<Item = StreamResult<StreamEvent>> + Send>>,
+        control: SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>,
+    },
+}
+
 struct SubscriptionInner {
     /// The underlying byte stream from reqwest
     byte_stream: Pin<Box<dyn Stream<Item = Result<Bytes, reqwest::Error>> + Send>>,
@@ -101,11 +121,77 @@ impl Subscription {
             component_id,
             base_url,
             http_client,
-            inner: Box::pin(SubscriptionInner {
+            transport: Transport::Sse(Box::pin(SubscriptionInner {
                 byte_stream: Box::pin(byte_stream),
                 parser: SseParser::new(),
                 event_buffer: Vec::new(),
-            }),
+            })),
+            cancelled: false,
+        })
+    }
+
+    /// Create a new subscription by upgrading the stream URL to a WebSocket
+    ///
+    /// `auth` is sent as the `Authorization` header of the upgrade request,
+    /// since the HTTP client's default headers don't apply to the socket.
+    pub(crate) async fn connect_ws(
+        base_url: Url,
+        http_client: Client,
+        subscription_id: String,
+        component_id: Option<String>,
+        stream_url: &str,
+        auth: Option<&HeaderValue>,
+    ) -> StreamResult<Self> {
+        let mut ws_url = base_url
+            .join(stream_url)
+            .map_err(|e| StreamError::Parse(format!("Invalid stream URL: {}", e)))?;
+        let scheme = if ws_url.scheme() == "https" {
+            "wss"
+        } else {
+            "ws"
+        };
+        ws_url
+            .set_scheme(scheme)
+            .map_err(|_| StreamError::Parse(format!("Invalid stream URL: {}", ws_url)))?;
+
+        debug!("Connecting to WebSocket stream: {}", ws_url);
+
+        let mut request = ws_url.as_str().into_client_request().map_err(ws_error)?;
+        if let Some(auth) = auth {
+            let value = tungstenite::http::HeaderValue::from_bytes(auth.as_bytes())
+                .map_err(|e| StreamError::Parse(format!("Invalid auth header: {}", e)))?;
+            request
+                .headers_mut()
+                .insert(tungstenite::http::header::AUTHORIZATION, value);
+        }
+
+        let (socket, _) = tokio_tungstenite::connect_async(request)
+            .await
+            .map_err(ws_error)?;
+        let (control, incoming) = socket.split();
+
+        // Text frames carry one EventEnvelope each; the server sends no
+        // binary frames, and pings/close are handled by tungstenite.
+        let events = incoming.filter_map(|message| async move {
+            match message {
+                Ok(Message::Text(text)) => Some(
+                    serde_json::from_str::<StreamEvent>(text.as_str())
+                        .map_err(|e| StreamError::Parse(format!("Invalid event: {}", e))),
+                ),
+                Ok(_) => None,
+                Err(e) => Some(Err(ws_error(e))),
+            }
+        });
+
+        Ok(Self {
+            subscription_id,
+            component_id,
+            base_url,
+            http_client,
+            transport: Transport::Ws {
+                events: Box::pin(events),
+                control,
+            },
             cancelled: false,
         })
     }
@@ -122,6 +208,44 @@ impl Subscription {
         <Self as StreamExt>::next(self).await
     }
 
+    /// Stop the server sampling until [`resume`](Self::resume) is called
+    ///
+    /// Only available on WebSocket subscriptions.
+    pub async fn pause(&mut self) -> StreamResult<()> {
+        self.send_control(serde_json::json!({ "action": "pause" }))
+            .await
+    }
+
+    /// Resume a paused subscription
+    pub async fn resume(&mut self) -> StreamResult<()> {
+        self.send_control(serde_json::json!({ "action": "resume" }))
+            .await
+    }
+
+    /// Change the polling cadence of the live subscription
+    ///
+    /// Only available on WebSocket subscriptions; an SSE subscription is
+    /// updated with `SovdClient::update_cyclic_subscription` instead. A
+    /// refused change arrives as an error event on the stream.
+    pub async fn set_interval(&mut self, interval: SubscriptionInterval) -> StreamResult<()> {
+        self.send_control(serde_json::json!({
+            "action": "set_interval",
+            "interval": interval,
+        }))
+        .await
+    }
+
+    async fn send_control(&mut self, control: serde_json::Value) -> StreamResult<()> {
+        let Transport::Ws { control: sink, .. } = &mut self.transport else {
+            return Err(StreamError::Unsupported(
+                "control messages need a WebSocket subscription".to_string(),
+            ));
+        };
+        sink.send(Message::Text(control.to_string().into()))
+            .await
+            .map_err(ws_error)
+    }
+
     /// Cancel the subscription and clean up resources
     ///
     /// This sends a DELETE request to remove the subscription from the server.
@@ -174,17 +298,20 @@ impl Stream for Subscription {
             return Poll::Ready(None);
         }
 
-        // First, check if we have buffered events
-        if !self.inner.event_buffer.is_empty() {
-            return Poll::Ready(Some(self.inner.event_buffer.remove(0)));
-        }
-
-        // Poll the underlying byte stream
-        let inner = self.inner.as_mut();
+        let inner = match &mut self.transport {
+            Transport::Sse(inner) => inner.as_mut(),
+            Transport::Ws { events, .. } => return events.as_mut().poll_next(cx),
+        };
 
         // SAFETY: We're not moving the inner struct, just accessing its fields
         let inner_ref = unsafe { Pin::get_unchecked_mut(inner) };
 
+        // First, check if we have buffered events
+        if !inner_ref.event_buffer.is_empty() {
+            return Poll::Ready(Some(inner_ref.event_buffer.remove(0)));
+        }
+
+        // Poll the underlying byte stream
         match Pin::new(&mut inner_ref.byte_stream).poll_next(cx) {
             Poll::Ready(Some(Ok(bytes))) => {
                 // Parse the bytes
@@ -236,3 +363,18 @@ impl Drop for Subscription {
         }
     }
 }
+
+/// Map a WebSocket failure; a refused upgrade keeps its HTTP status
+fn ws_error(e: tungstenite::Error) -> StreamError {
+    match e {
+        tungstenite::Error::Http(response) => StreamError::Server {
+            status: response.status().as_u16(),
+            message: response
+                .body()
+                .as_deref()
+                .map(|body| String::from_utf8_lossy(body).into_owned())
+                .unwrap_or_default(),
+        },
+        e => StreamError::WebSocket(e.to_string()),
+    }
+}
@@ -24,6 +24,15 @@ pub struct StreamEvent {
     /// Conditional error payload (mutually exclusive with `payload`).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error: Option<ErrorResponse>,
+
+    /// Vendor marker for events with neither payload nor error; see
+    /// [`Self::is_resumed`].
+    #[serde(
+        rename = "x-sumo-event",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub event: Option<String>,
 }
 
 /// Shape of the success `payload` for cyclic-subscription events.
@@ -37,6 +46,12 @@ pub struct StreamPayload {
 }
 
 impl StreamEvent {
+    /// Whether the server re-armed the subscription after an interruption
+    /// (e.g. an ECU reset); values sampled in the gap are lost.
+    pub fn is_resumed(&self) -> bool {
+        self.event.as_deref() == Some("resumed")
+    }
+
     /// Sequence number from the success payload, if any.
     pub fn sequence(&self) -> Option<u64> {
         self.payload.as_ref().map(|p| p.seq)
@@ -107,6 +122,10 @@ pub enum StreamError {
     #[error("Connection error: {0}")]
     Connection(#[from] reqwest::Error),
 
+    /// WebSocket transport error
+    #[error("WebSocket error: {0}")]
+    WebSocket(String),
+
     /// Failed to parse SSE event
     #[error("Parse error: {0}")]
     Parse(String),
@@ -122,6 +141,10 @@ pub enum StreamError {
     /// Subscription was cancelled
     #[error("Subscription cancelled")]
     Cancelled,
+
+    /// Operation not available on this subscription's transport
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
 }
 
 /// Result type for streaming operations
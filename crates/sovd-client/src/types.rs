@@ -37,6 +37,12 @@ pub struct Component {
     /// Sub-entities/apps endpoint URL (if supported)
     #[serde(default)]
     pub apps: Option<String>,
+    /// Friendly name, if the server's metadata overlay sets one
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Category, if the server's metadata overlay sets one
+    #[serde(default)]
+    pub category: Option<String>,
 }
 
 /// Component capabilities
@@ -69,6 +75,10 @@ pub struct ComponentCapabilities {
     /// §7.20 bulk-data collection (log-file download / large payloads).
     #[serde(default)]
     pub bulk_data: bool,
+    /// ReadDTCInformation reports the ECU declares (`status-mask`,
+    /// `severity-mask`, `user-def-memory`); empty when not declared
+    #[serde(default)]
+    pub dtc_reports: Vec<String>,
 }
 
 /// List of components response
@@ -156,6 +166,12 @@ pub struct ParameterInfo {
 pub struct ParametersResponse {
     pub count: usize,
     pub items: Vec<ParameterInfo>,
+    /// Matches across all pages; only on a paged listing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<usize>,
+    /// Href of the next page; `None` on the last one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
 }
 
 /// Data read response (DID response format)
@@ -226,10 +242,19 @@ impl DataResponse {
     }
 }
 
-/// Multiple data values response
+/// Batch read response (`GET .../data?ids=a,b,c`), one item per requested
+/// id in request order
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataListResponse {
-    pub data: Vec<DataResponse>,
+    pub items: Vec<DataListItem>,
+}
+
+/// One parameter of a batch read: its value, or why it could not be read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DataListItem {
+    Value(DataResponse),
+    Error { id: String, error: ErrorResponse },
 }
 
 /// Write data request
@@ -238,6 +263,52 @@ pub struct WriteDataRequest {
     pub value: serde_json::Value,
 }
 
+/// Batch write request (`POST .../data:batch`)
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchWriteRequest {
+    /// Parameter id → value, written in this order
+    #[serde(serialize_with = "serialize_ordered")]
+    pub values: Vec<(String, serde_json::Value)>,
+    /// Stop at the first failed write
+    pub stop_on_error: bool,
+}
+
+/// Serialize `(key, value)` pairs as a JSON object, keeping their order
+fn serialize_ordered<S: serde::Serializer>(
+    values: &[(String, serde_json::Value)],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_map(values.iter().map(|(k, v)| (k, v)))
+}
+
+/// Batch write response: one item per parameter, in request order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchWriteResponse {
+    pub items: Vec<BatchWriteItem>,
+    /// Index of the first failed write; `None` when all were written.
+    /// Writes before it stay written: UDS has no transaction to roll back.
+    #[serde(default)]
+    pub failed_index: Option<usize>,
+}
+
+/// Outcome of one parameter of a batch write
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchWriteItem {
+    pub id: String,
+    pub status: BatchWriteStatus,
+    #[serde(default)]
+    pub error: Option<ErrorResponse>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchWriteStatus {
+    Written,
+    Failed,
+    /// Not attempted after an earlier failure (`stop_on_error`)
+    Skipped,
+}
+
 // =============================================================================
 // Fault Types
 // =============================================================================
@@ -274,6 +345,52 @@ pub struct FaultsResponse {
     pub items: Vec<FaultInfo>,
     #[serde(default)]
     pub total_count: usize,
+    /// Href of the next page of a paged listing; `None` on the last one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    /// Supported status bits (`0xNN`), if the server reports them
+    #[serde(
+        default,
+        rename = "x-sumo-status-availability-mask",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status_availability_mask: Option<String>,
+}
+
+/// One snapshot (freeze frame) record of a fault
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultSnapshot {
+    pub record_number: u8,
+    /// Recorded values keyed by parameter ID (hex DID when the server has
+    /// no definition for it)
+    #[serde(default)]
+    pub data: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Fault snapshots response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultSnapshotsResponse {
+    pub items: Vec<FaultSnapshot>,
+}
+
+/// One extended data record of a fault
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultExtendedDataRecord {
+    pub record_number: u8,
+    /// Configured meaning of the record, e.g. `occurrence_counter`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Record read as a counter, when configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<u64>,
+    /// Record bytes, hex-encoded
+    pub raw: String,
+}
+
+/// Fault extended data response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultExtendedDataResponse {
+    pub items: Vec<FaultExtendedDataRecord>,
 }
 
 /// Clear faults response
@@ -284,6 +401,41 @@ pub struct ClearFaultsResponse {
     pub cleared_count: Option<u32>,
     #[serde(default)]
     pub message: Option<String>,
+    /// Whether a re-read confirmed the clear; `None` when the server did
+    /// not verify it (204)
+    #[serde(default)]
+    pub cleared_confirmed: Option<bool>,
+    /// Faults still stored after a verified clear
+    #[serde(default)]
+    pub remaining: Vec<FaultInfo>,
+    /// Session switch the server made to clear (`auto_session`)
+    #[serde(default)]
+    pub session_escalation: Option<SessionEscalation>,
+}
+
+/// Session switch made by an `auto_session` clear
+/// (`x-sumo-session-escalation`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionEscalation {
+    /// Session active before the clear
+    pub from: String,
+    /// Session the clear ran in
+    pub to: String,
+    /// `from` was re-entered after the clear
+    pub restored: bool,
+}
+
+/// Body of a `DELETE .../faults` answered with 200: a verified clear
+/// (`x-sumo-clear-verification`) and/or a session switch
+/// (`x-sumo-auto-session`)
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ClearVerificationBody {
+    #[serde(default)]
+    pub cleared_confirmed: Option<bool>,
+    #[serde(default)]
+    pub remaining: Vec<FaultInfo>,
+    #[serde(rename = "x-sumo-session-escalation", default)]
+    pub session_escalation: Option<SessionEscalation>,
 }
 
 // =============================================================================
@@ -333,6 +485,14 @@ pub struct OperationInfo {
     pub controlled_by_tester: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub frozen: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_action: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_value: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commanded_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_reset: Option<bool>,
 }
 
 /// Operations list response
@@ -514,6 +674,12 @@ pub struct AppInfo {
     /// Capabilities (present in detail responses, absent in list responses)
     #[serde(default)]
     pub capabilities: Option<ComponentCapabilities>,
+    /// Friendly name from a gateway's metadata overlay
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Category from a gateway's metadata overlay
+    #[serde(default)]
+    pub category: Option<String>,
 }
 
 /// Apps list response
@@ -625,6 +791,22 @@ pub struct ErrorResponse {
     pub parameters: std::collections::BTreeMap<String, Vec<String>>,
 }
 
+impl ErrorResponse {
+    /// `(service, nrc)` of an `error-response` body, i.e. an ECU that
+    /// answered with a UDS negative response
+    pub fn negative_response(&self) -> Option<(u8, u8)> {
+        if self.error_code != "error-response" {
+            return None;
+        }
+        let byte = |key: &str| {
+            let value = self.parameters.get(key)?.first()?;
+            let hex = value.trim_start_matches("0x").trim_start_matches("0X");
+            u8::from_str_radix(hex, 16).ok()
+        };
+        Some((byte("service")?, byte("nrc")?))
+    }
+}
+
 // =============================================================================
 // Session/Security Types
 // =============================================================================
@@ -834,6 +1016,9 @@ pub struct ModeResponse {
     /// Link state (for link mode)
     #[serde(default)]
     pub link_state: Option<String>,
+    /// P2/P2* the ECU advertised for the active session (session mode)
+    #[serde(default, rename = "x-sumo-timing")]
+    pub timing: Option<sovd_core::SessionTimingParameters>,
 }
 
 // =============================================================================
@@ -881,6 +1066,18 @@ pub struct OutputInfo {
     /// Whether the output is currently frozen
     #[serde(default)]
     pub frozen: Option<bool>,
+    /// Last I/O control action the ECU accepted (e.g. "short_term_adjust")
+    #[serde(default)]
+    pub last_action: Option<String>,
+    /// Value commanded by the last short-term adjustment
+    #[serde(default)]
+    pub last_value: Option<serde_json::Value>,
+    /// When the last I/O control action was accepted (RFC 3339)
+    #[serde(default)]
+    pub last_commanded_at: Option<String>,
+    /// Whether a session reset has since handed the output back to the ECU
+    #[serde(default)]
+    pub session_reset: Option<bool>,
     /// Whether the output requires security unlock
     #[serde(default)]
     pub requires_security: Option<bool>,
@@ -914,6 +1111,9 @@ pub struct OutputControlResponse {
     /// Typed new value (decoded from raw bytes using type metadata)
     #[serde(default)]
     pub value: Option<serde_json::Value>,
+    /// Raw control state sent with a short-term adjustment (hex string)
+    #[serde(default)]
+    pub raw_sent: Option<String>,
     /// Error message (if failed)
     #[serde(default)]
     pub error: Option<String>,
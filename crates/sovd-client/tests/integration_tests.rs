@@ -4,7 +4,8 @@
 //! This ensures the client stays in sync with the API.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use sovd_api::{create_router, AppState};
@@ -12,8 +13,9 @@ use sovd_client::testing::TestServer;
 use sovd_conv::DidStore;
 use sovd_core::{
     BackendError, BackendResult, Capabilities, ClearFaultsResult, DataValue, DiagnosticBackend,
-    EntityInfo, Fault, FaultFilter, FaultSeverity, FaultsResult, OperationExecution, OperationInfo,
-    OperationStatus, ParameterInfo,
+    EntityInfo, Fault, FaultExtendedData, FaultFilter, FaultSeverity, FaultSnapshot,
+    FaultSnapshotItem, FaultsResult, OperationExecution, OperationInfo, OperationStatus,
+    ParameterInfo, SecurityMode, SecurityState, SessionMode, SessionTimingParameters,
 };
 
 // =============================================================================
@@ -27,6 +29,9 @@ struct MockBackend {
     did_values: HashMap<u16, Vec<u8>>,
     faults: Vec<Fault>,
     operations: Vec<OperationInfo>,
+    /// Active diagnostic session
+    session: Mutex<String>,
+    unlocked: AtomicBool,
 }
 
 impl MockBackend {
@@ -38,6 +43,8 @@ impl MockBackend {
         did_values.insert(0xF40C, vec![0x1C, 0x20]);
         // VIN
         did_values.insert(0xF190, b"WF0XXXGCDX1234567".to_vec());
+        // Boot software fingerprint, outside the default session only
+        did_values.insert(0xF15B, vec![0x20, 0x24]);
 
         let faults = vec![Fault {
             id: "P0123".to_string(),
@@ -76,11 +83,15 @@ impl MockBackend {
                 description: Some("Mock ECU for testing".to_string()),
                 href: format!("/vehicle/v1/components/{}", id),
                 status: Some("online".to_string()),
+                display_name: None,
+                category: None,
             },
             capabilities: Capabilities::default(),
             did_values,
             faults,
             operations,
+            session: Mutex::new("default".to_string()),
+            unlocked: AtomicBool::new(false),
         }
     }
 }
@@ -104,16 +115,97 @@ impl DiagnosticBackend for MockBackend {
     }
 
     async fn read_raw_did(&self, did: u16) -> BackendResult<Vec<u8>> {
+        if did == 0xF15B && *self.session.lock().unwrap() == "default" {
+            return Err(BackendError::EcuError {
+                nrc: 0x31,
+                sid: 0x22,
+                message: "requestOutOfRange".to_string(),
+            });
+        }
         self.did_values
             .get(&did)
             .cloned()
             .ok_or_else(|| BackendError::ParameterNotFound(format!("DID 0x{:04X} not found", did)))
     }
 
-    async fn write_raw_did(&self, _did: u16, _data: &[u8]) -> BackendResult<()> {
+    async fn write_raw_did(&self, did: u16, _data: &[u8]) -> BackendResult<()> {
+        // The VIN is only writable at the end of line
+        if did == 0xF190 {
+            return Err(BackendError::EcuError {
+                nrc: 0x22,
+                sid: 0x2E,
+                message: "conditionsNotCorrect".to_string(),
+            });
+        }
+        // The fingerprint is writable once unlocked
+        if did == 0xF15B && !self.unlocked.load(Ordering::SeqCst) {
+            return Err(BackendError::EcuError {
+                nrc: 0x33,
+                sid: 0x2E,
+                message: "securityAccessDenied".to_string(),
+            });
+        }
         Ok(())
     }
 
+    async fn get_session_mode(&self) -> BackendResult<SessionMode> {
+        let session = self.session.lock().unwrap().clone();
+        // Only the extended session advertises timing
+        let timing = (session == "extended").then_some(SessionTimingParameters {
+            p2_server_max_ms: 25,
+            p2_star_server_max_ms: 5000,
+        });
+        Ok(SessionMode {
+            mode: "session".to_string(),
+            session,
+            session_id: 0x03,
+            timing,
+        })
+    }
+
+    async fn set_session_mode(&self, session: &str) -> BackendResult<SessionMode> {
+        *self.session.lock().unwrap() = session.to_string();
+        self.unlocked.store(false, Ordering::SeqCst);
+        Ok(SessionMode {
+            mode: "session".to_string(),
+            session: session.to_string(),
+            session_id: 0x03,
+            timing: None,
+        })
+    }
+
+    async fn set_security_mode(
+        &self,
+        value: &str,
+        key: Option<&[u8]>,
+    ) -> BackendResult<SecurityMode> {
+        if value.ends_with("_requestseed") {
+            return Ok(SecurityMode {
+                mode: "security".to_string(),
+                state: SecurityState::SeedAvailable,
+                level: Some(1),
+                available_levels: None,
+                seed: Some("1234".to_string()),
+            });
+        }
+        // Key is the seed inverted
+        if key != Some(&[0xED, 0xCB]) {
+            return Err(BackendError::EcuError {
+                nrc: 0x35,
+                sid: 0x27,
+                message: "invalidKey".to_string(),
+            });
+        }
+        self.unlocked.store(true, Ordering::SeqCst);
+        Ok(SecurityMode {
+            mode: "security".to_string(),
+            state: SecurityState::Unlocked,
+            level: Some(1),
+            available_levels: None,
+            seed: None,
+        })
+    }
+
     async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
         Ok(FaultsResult {
             faults: self.faults.clone(),
@@ -126,9 +218,49 @@ impl DiagnosticBackend for MockBackend {
             success: true,
             cleared_count: self.faults.len() as u32,
             message: "Cleared all faults".to_string(),
+            verification: None,
         })
     }
 
+    async fn get_fault_snapshots(
+        &self,
+        _fault_id: &str,
+        _did_lengths: &HashMap<u16, usize>,
+    ) -> BackendResult<Vec<FaultSnapshot>> {
+        Ok(vec![FaultSnapshot {
+            record_number: 1,
+            items: vec![FaultSnapshotItem {
+                did: 0xF405,
+                data: vec![0x84],
+            }],
+        }])
+    }
+
+    async fn get_fault_extended_data(
+        &self,
+        _fault_id: &str,
+        record_number: u8,
+    ) -> BackendResult<Vec<FaultExtendedData>> {
+        let records = vec![
+            FaultExtendedData {
+                record_number: 0x01,
+                name: Some("occurrence_counter".to_string()),
+                value: Some(3),
+                data: vec![0x03],
+            },
+            FaultExtendedData {
+                record_number: 0x90,
+                name: None,
+                value: None,
+                data: vec![0xCA, 0xFE],
+            },
+        ];
+        Ok(records
+            .into_iter()
+            .filter(|r| record_number == 0xFF || r.record_number == record_number)
+            .collect())
+    }
+
     async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
         Ok(self.operations.clone())
     }
@@ -248,6 +380,120 @@ async fn test_read_did_raw() {
     assert_eq!(response.converted, Some(false));
 }
 
+#[tokio::test]
+async fn test_write_rejected_by_ecu_carries_nrc() {
+    use sovd_core::NegativeResponseCode;
+
+    let server = create_test_server().await;
+
+    let err = server
+        .client
+        .write_did("example_ecu", 0xF190, serde_json::json!("574630"))
+        .await
+        .unwrap_err();
+
+    match &err {
+        sovd_client::SovdClientError::NegativeResponse { nrc, service, .. } => {
+            assert_eq!(*nrc, NegativeResponseCode::ConditionsNotCorrect);
+            assert_eq!(*service, 0x2E);
+        }
+        other => panic!("expected a negative response, got {other:?}"),
+    }
+    assert_eq!(err.nrc(), Some(NegativeResponseCode::ConditionsNotCorrect));
+
+    // Errors the ECU never saw carry no NRC
+    let err = server
+        .client
+        .read_did("example_ecu", 0xF1A0)
+        .await
+        .unwrap_err();
+    assert_eq!(err.nrc(), None);
+}
+
+#[tokio::test]
+async fn test_auto_session_escalates_and_retries() {
+    use sovd_client::{ClientOptions, SecurityLevel, SovdClient, UnlockCredentials};
+
+    let server = create_test_server().await;
+
+    // Off by default: the refusal surfaces as is
+    let err = server
+        .client
+        .read_did("example_ecu", 0xF15B)
+        .await
+        .unwrap_err();
+    assert_eq!(err.nrc().map(u8::from), Some(0x31));
+
+    let client = SovdClient::with_options(
+        &server.base_url(),
+        ClientOptions {
+            auto_session: true,
+            credentials: Some(UnlockCredentials {
+                level: SecurityLevel::LEVEL_1,
+                key: Arc::new(|seed| seed.iter().map(|b| !b).collect()),
+            }),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let response = client.read_did("example_ecu", 0xF15B).await.unwrap();
+    assert_eq!(response.raw_bytes().unwrap(), vec![0x20, 0x24]);
+
+    // The escalation also unlocked, so the write goes through first time
+    client
+        .write_did("example_ecu", 0xF15B, serde_json::json!("2025"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_session_timing_is_reported() {
+    use sovd_client::SessionType;
+
+    let server = create_test_server().await;
+    let client = &server.client;
+
+    assert_eq!(
+        client.get_session_timing("example_ecu").await.unwrap(),
+        None
+    );
+
+    client
+        .set_session("example_ecu", SessionType::Extended)
+        .await
+        .unwrap();
+    let timing = client
+        .get_session_timing("example_ecu")
+        .await
+        .unwrap()
+        .expect("extended session timing");
+    assert_eq!(timing.p2_server_max_ms, 25);
+    assert_eq!(timing.p2_star_server_max_ms, 5000);
+}
+
+#[tokio::test]
+async fn test_auto_session_without_credentials_retries_once() {
+    use sovd_client::{ClientOptions, SovdClient};
+
+    let server = create_test_server().await;
+    let client = SovdClient::with_options(
+        &server.base_url(),
+        ClientOptions {
+            auto_session: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    // The session switch doesn't unlock, so the retry is refused again
+    let err = client
+        .write_did("example_ecu", 0xF15B, serde_json::json!("2025"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.nrc().map(u8::from), Some(0x33));
+}
+
 #[tokio::test]
 async fn test_read_data_with_conversion() {
     // Create a DID store with conversions
@@ -315,6 +561,95 @@ async fn test_list_parameters_with_definitions() {
     assert_eq!(params.count, 2);
 }
 
+#[tokio::test]
+async fn test_list_parameters_paged_follows_next() {
+    let did_store = Arc::new(DidStore::new());
+    for (did, name) in [
+        (0xF405, "Coolant Temperature"),
+        (0xF40C, "Engine RPM"),
+        (0xF40D, "Vehicle Speed"),
+        (0xF40F, "Intake Air Temperature"),
+        (0xF42F, "Fuel Level"),
+    ] {
+        did_store.register(
+            did,
+            sovd_conv::DidDefinition::scalar(sovd_conv::DataType::Uint8).with_name(name),
+        );
+    }
+
+    let server = create_test_server_with_store(did_store).await;
+
+    let params = server
+        .client
+        .list_parameters_paged("example_ecu", 2, None)
+        .await
+        .unwrap();
+    let dids: Vec<&str> = params.iter().map(|p| p.did.as_str()).collect();
+    assert_eq!(dids, ["F405", "F40C", "F40D", "F40F", "F42F"]);
+
+    let params = server
+        .client
+        .list_parameters_paged("example_ecu", 1, Some("temperature"))
+        .await
+        .unwrap();
+    let dids: Vec<&str> = params.iter().map(|p| p.did.as_str()).collect();
+    assert_eq!(dids, ["F405", "F40F"]);
+}
+
+#[tokio::test]
+async fn test_write_data_batch_reports_each_parameter() {
+    use sovd_client::BatchWriteStatus;
+
+    let server = create_test_server().await;
+    let values = [
+        ("F40C", serde_json::json!("0bb8")),
+        ("F190", serde_json::json!("57565a")),
+        ("F405", serde_json::json!("5a")),
+    ];
+
+    let result = server
+        .client
+        .write_data_batch("example_ecu", &values, false)
+        .await
+        .unwrap();
+    let statuses: Vec<_> = result.items.iter().map(|i| i.status).collect();
+    assert_eq!(
+        statuses,
+        [
+            BatchWriteStatus::Written,
+            BatchWriteStatus::Failed,
+            BatchWriteStatus::Written
+        ]
+    );
+    assert_eq!(result.items[1].id, "F190");
+    assert!(result.items[1].error.is_some());
+    assert_eq!(result.failed_index, Some(1));
+
+    let result = server
+        .client
+        .write_data_batch("example_ecu", &values, true)
+        .await
+        .unwrap();
+    assert_eq!(result.items[2].status, BatchWriteStatus::Skipped);
+    assert_eq!(result.failed_index, Some(1));
+}
+
+#[tokio::test]
+async fn test_write_data_batch_all_written() {
+    use sovd_client::BatchWriteStatus;
+
+    let server = create_test_server().await;
+
+    let result = server
+        .client
+        .write_data_batch("example_ecu", &[("F40C", serde_json::json!("0bb8"))], true)
+        .await
+        .unwrap();
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].status, BatchWriteStatus::Written);
+    assert_eq!(result.failed_index, None);
+}
+
 // =============================================================================
 // Fault Tests
 // =============================================================================
@@ -336,6 +671,26 @@ async fn test_get_faults() {
     assert!(active, "expected active fault per testFailed status bit");
 }
 
+#[tokio::test]
+async fn test_get_faults_paged() {
+    let server = create_test_server().await;
+
+    let faults = server
+        .client
+        .get_faults_paged("example_ecu", 1, None)
+        .await
+        .unwrap();
+    assert_eq!(faults.len(), 1);
+    assert_eq!(faults[0].code, "P0123");
+
+    let faults = server
+        .client
+        .get_faults_paged("example_ecu", 1, Some("P9999"))
+        .await
+        .unwrap();
+    assert!(faults.is_empty());
+}
+
 #[tokio::test]
 async fn test_get_fault_detail() {
     let server = create_test_server().await;
@@ -348,6 +703,51 @@ async fn test_get_fault_detail() {
     assert_eq!(fault.code, "P0123");
 }
 
+#[tokio::test]
+async fn test_get_fault_snapshots() {
+    let did_store = Arc::new(DidStore::new());
+    did_store.register(
+        0xF405,
+        sovd_conv::DidDefinition::scaled(sovd_conv::DataType::Uint8, 1.0, -40.0)
+            .with_name("Coolant Temperature")
+            .with_unit("°C"),
+    );
+    let server = create_test_server_with_store(did_store).await;
+
+    let snapshots = server
+        .client
+        .get_fault_snapshots("example_ecu", "P0123")
+        .await
+        .unwrap();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].record_number, 1);
+    // 0x84 = 132, 132 - 40 = 92
+    assert_eq!(snapshots[0].data["F405"], 92);
+}
+
+#[tokio::test]
+async fn test_get_fault_extended_data() {
+    let server = create_test_server().await;
+
+    let records = server
+        .client
+        .get_fault_extended_data("example_ecu", "P0123", None)
+        .await
+        .unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].name.as_deref(), Some("occurrence_counter"));
+    assert_eq!(records[0].value, Some(3));
+    assert_eq!(records[1].raw, "cafe");
+
+    let records = server
+        .client
+        .get_fault_extended_data("example_ecu", "P0123", Some(0x90))
+        .await
+        .unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].record_number, 0x90);
+}
+
 #[tokio::test]
 async fn test_clear_faults() {
     let server = create_test_server().await;
@@ -669,6 +1069,93 @@ async fn test_client_side_conversion() {
     assert_eq!(rpm, 1800); // 7200 * 0.25
 }
 
+/// Fetch the server's definitions instead of hand-authoring them, then
+/// decode a raw read locally with the fetched store
+#[tokio::test]
+async fn test_fetch_definitions_for_client_side_decode() {
+    let server = create_test_server().await;
+
+    let yaml = r#"
+dids:
+  0xF405:
+    id: coolant_temperature
+    type: uint8
+    offset: -40.0
+    unit: °C
+  0xF40C:
+    id: engine_rpm
+    type: uint16
+    scale: 0.25
+    unit: rpm
+"#;
+    server.client.upload_definitions(yaml).await.unwrap();
+
+    let store = server.client.fetch_definitions().await.unwrap();
+    assert_eq!(store.len(), 2);
+    assert_eq!(store.resolve_did("engine_rpm"), Some(0xF40C));
+
+    let response = server
+        .client
+        .read_data_raw("example_ecu", "F405")
+        .await
+        .unwrap();
+    let temp = store
+        .decode(0xF405, &response.raw_bytes().unwrap())
+        .unwrap();
+    assert_eq!(temp, 92); // 132 - 40
+
+    let response = server
+        .client
+        .read_data_raw("example_ecu", "F40C")
+        .await
+        .unwrap();
+    let rpm = store
+        .decode(0xF40C, &response.raw_bytes().unwrap())
+        .unwrap();
+    assert_eq!(rpm, 1800); // 7200 * 0.25
+}
+
+#[tokio::test]
+async fn test_definition_cache_ttl() {
+    use std::time::Duration;
+
+    let server = create_test_server().await;
+    let upload = |offset: f64| format!("dids:\n  0xF405:\n    type: uint8\n    offset: {offset}\n");
+    server
+        .client
+        .upload_definitions(&upload(-40.0))
+        .await
+        .unwrap();
+
+    let cache = server.client.definition_cache(Duration::from_secs(3600));
+    let store = cache.store().await.unwrap();
+    assert_eq!(store.decode(0xF405, &[132]).unwrap(), 92);
+
+    // Server-side change is not seen until the TTL lapses ...
+    server
+        .client
+        .upload_definitions(&upload(-50.0))
+        .await
+        .unwrap();
+    let store = cache.store().await.unwrap();
+    assert_eq!(store.decode(0xF405, &[132]).unwrap(), 92);
+
+    // ... or the cache is refreshed explicitly.
+    let store = cache.refresh().await.unwrap();
+    assert_eq!(store.decode(0xF405, &[132]).unwrap(), 82);
+
+    // A zero TTL refetches on every call.
+    let eager = server.client.definition_cache(Duration::ZERO);
+    eager.store().await.unwrap();
+    server
+        .client
+        .upload_definitions(&upload(-60.0))
+        .await
+        .unwrap();
+    let store = eager.store().await.unwrap();
+    assert_eq!(store.decode(0xF405, &[132]).unwrap(), 72);
+}
+
 // =============================================================================
 // Full Workflow Test
 // =============================================================================
@@ -0,0 +1,215 @@
+//! Trailing checksum fields
+//!
+//! A DID may end in a checksum over (part of) its payload. On decode the
+//! trailer is verified and stripped before the payload is converted; on
+//! encode it is computed and appended. Checksums wider than one byte are
+//! stored big-endian.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ConvError, ConvResult};
+
+/// Checksum algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumAlgorithm {
+    /// CRC-8 SAE J1850 (poly 0x1D, init 0xFF, xor-out 0xFF)
+    Crc8SaeJ1850,
+    /// CRC-16-CCITT (poly 0x1021, init 0xFFFF, not reflected)
+    Crc16Ccitt,
+    /// Additive sum of the covered bytes, truncated to the checksum width
+    Sum,
+}
+
+impl ChecksumAlgorithm {
+    /// Natural width of the algorithm in bytes
+    pub fn default_width(&self) -> usize {
+        match self {
+            ChecksumAlgorithm::Crc16Ccitt => 2,
+            ChecksumAlgorithm::Crc8SaeJ1850 | ChecksumAlgorithm::Sum => 1,
+        }
+    }
+
+    /// Compute the checksum of `data`, truncated to `width` bytes
+    pub fn compute(&self, data: &[u8], width: usize) -> u32 {
+        let value = match self {
+            ChecksumAlgorithm::Crc8SaeJ1850 => crc8_sae_j1850(data) as u32,
+            ChecksumAlgorithm::Crc16Ccitt => crc16_ccitt(data) as u32,
+            ChecksumAlgorithm::Sum => data.iter().fold(0u32, |acc, b| acc.wrapping_add(*b as u32)),
+        };
+        if width >= 4 {
+            value
+        } else {
+            value & ((1u32 << (width * 8)) - 1)
+        }
+    }
+}
+
+/// Trailing checksum declaration (YAML `checksum:`)
+///
+/// ```yaml
+/// checksum:
+///   algorithm: crc8_sae_j1850
+///   start: 0      # first covered payload byte (default 0)
+///   end: 6        # one past the last covered byte (default: whole payload)
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecksumDef {
+    /// Algorithm used to compute the checksum
+    pub algorithm: ChecksumAlgorithm,
+    /// Width of the trailer in bytes (defaults to the algorithm's natural width)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<usize>,
+    /// First payload byte covered by the checksum
+    #[serde(default)]
+    pub start: usize,
+    /// One past the last payload byte covered (defaults to the end of the payload)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<usize>,
+}
+
+impl ChecksumDef {
+    /// Checksum over the whole payload at the algorithm's natural width
+    pub fn new(algorithm: ChecksumAlgorithm) -> Self {
+        Self {
+            algorithm,
+            width: None,
+            start: 0,
+            end: None,
+        }
+    }
+
+    /// Restrict the covered payload bytes to `start..end`
+    pub fn with_range(mut self, start: usize, end: usize) -> Self {
+        self.start = start;
+        self.end = Some(end);
+        self
+    }
+
+    /// Width of the trailer in bytes
+    pub fn byte_len(&self) -> usize {
+        self.width.unwrap_or_else(|| self.algorithm.default_width())
+    }
+
+    /// Compute the checksum over the covered part of `payload`
+    pub fn compute(&self, payload: &[u8]) -> ConvResult<u32> {
+        let end = self.end.unwrap_or(payload.len());
+        if end > payload.len() {
+            return Err(ConvError::DataTooShort {
+                expected: end,
+                actual: payload.len(),
+            });
+        }
+        if self.start > end {
+            return Err(ConvError::InvalidData(format!(
+                "Checksum range {}..{} is empty",
+                self.start, end
+            )));
+        }
+        Ok(self
+            .algorithm
+            .compute(&payload[self.start..end], self.byte_len()))
+    }
+
+    /// Verify the trailer of `data` and return the payload in front of it
+    pub fn verify<'a>(&self, data: &'a [u8]) -> ConvResult<&'a [u8]> {
+        let width = self.byte_len();
+        if data.len() < width {
+            return Err(ConvError::DataTooShort {
+                expected: width,
+                actual: data.len(),
+            });
+        }
+        let (payload, trailer) = data.split_at(data.len() - width);
+        let actual = trailer.iter().fold(0u32, |acc, b| (acc << 8) | *b as u32);
+        let expected = self.compute(payload)?;
+        if expected != actual {
+            return Err(ConvError::ChecksumMismatch { expected, actual });
+        }
+        Ok(payload)
+    }
+
+    /// Append the checksum of `payload` to it
+    pub fn append(&self, payload: &mut Vec<u8>) -> ConvResult<()> {
+        let value = self.compute(payload)?;
+        let width = self.byte_len();
+        payload.extend(
+            (0..width)
+                .rev()
+                .map(|i| value.checked_shr(i as u32 * 8).unwrap_or(0) as u8),
+        );
+        Ok(())
+    }
+}
+
+/// CRC-8 SAE J1850
+pub fn crc8_sae_j1850(data: &[u8]) -> u8 {
+    let mut crc = 0xFFu8;
+    for byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x1D
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc ^ 0xFF
+}
+
+/// CRC-16-CCITT (the "FALSE" variant: init 0xFFFF, no xor-out)
+pub fn crc16_ccitt(data: &[u8]) -> u16 {
+    let mut crc = 0xFFFFu16;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_values() {
+        assert_eq!(crc8_sae_j1850(b"123456789"), 0x4B);
+        assert_eq!(crc16_ccitt(b"123456789"), 0x29B1);
+        assert_eq!(ChecksumAlgorithm::Sum.compute(&[0xF0, 0x20], 1), 0x10);
+        assert_eq!(ChecksumAlgorithm::Sum.compute(&[0xF0, 0x20], 2), 0x0110);
+    }
+
+    #[test]
+    fn test_verify_and_append() {
+        let def = ChecksumDef::new(ChecksumAlgorithm::Crc16Ccitt);
+        let mut data = b"123456789".to_vec();
+        def.append(&mut data).unwrap();
+        assert_eq!(&data[9..], &[0x29, 0xB1]);
+        assert_eq!(def.verify(&data).unwrap(), b"123456789");
+
+        data[0] = b'0';
+        match def.verify(&data) {
+            Err(ConvError::ChecksumMismatch { actual, .. }) => assert_eq!(actual, 0x29B1),
+            other => panic!("expected checksum mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_partial_range() {
+        // Only bytes 1..3 are covered; byte 0 may change freely
+        let def = ChecksumDef::new(ChecksumAlgorithm::Sum).with_range(1, 3);
+        assert!(def.verify(&[0xAA, 0x01, 0x02, 0x03]).is_ok());
+        assert!(def.verify(&[0x55, 0x01, 0x02, 0x03]).is_ok());
+        assert!(def.verify(&[0x55, 0x01, 0x03, 0x03]).is_err());
+
+        // Range past the payload
+        assert!(def.verify(&[0x01, 0x02]).is_err());
+    }
+}
@@ -6,21 +6,43 @@ use serde_json::{json, Value};
 
 use crate::definition::DidDefinition;
 use crate::error::{ConvError, ConvResult};
-use crate::precision::to_json_number;
-use crate::types::{ByteOrder, DataType};
+use crate::precision::to_json_number_with;
+use crate::types::{ByteOrder, DataType, DateFormat};
 
 /// Decode raw bytes according to definition
+///
+/// A declared checksum trailer is verified and stripped first; a mismatch
+/// yields [`ConvError::ChecksumMismatch`].
 pub fn decode(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
+    match &def.checksum {
+        Some(checksum) => decode_payload(def, checksum.verify(data)?),
+        None => decode_payload(def, data),
+    }
+}
+
+fn decode_payload(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
+    // Handle composite fields, each with its own type and byte order
+    if def.is_composite() {
+        return decode_composite(def, data);
+    }
+
     // Handle string type
     if matches!(def.data_type, DataType::String) {
         return decode_string(def, data);
     }
 
-    // Handle raw bytes type
-    if matches!(def.data_type, DataType::Bytes) {
+    // Handle raw bytes type; an enum on bytes keys on the whole payload
+    if matches!(def.data_type, DataType::Bytes) && !def.is_enum() {
         return Ok(decode_bytes(data));
     }
 
+    // Handle packed BCD
+    match def.data_type {
+        DataType::Bcd => return decode_bcd(def, data),
+        DataType::Date { format } => return decode_date(format, data),
+        _ => {}
+    }
+
     // Handle bit fields specially
     if def.is_bitfield() {
         return decode_bitfield(def, data);
@@ -50,11 +72,49 @@ pub fn decode(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
     decode_scalar(def, data)
 }
 
+/// Decode back-to-back fields into an object keyed by field name
+fn decode_composite(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
+    let fields = def.fields.as_deref().unwrap_or_default();
+    let mut result = serde_json::Map::new();
+    let mut offset = 0;
+
+    for (i, field) in fields.iter().enumerate() {
+        let len = match field.byte_len() {
+            Some(len) => len,
+            None if i + 1 == fields.len() => data.len().saturating_sub(offset),
+            None => {
+                return Err(ConvError::InvalidData(format!(
+                    "Field '{}' needs a length",
+                    field.name
+                )))
+            }
+        };
+        check_length(data, offset, len)?;
+        let mut field_def = field.as_definition();
+        field_def.precision_policy = def.precision_policy;
+        let value = decode(&field_def, &data[offset..offset + len])?;
+        result.insert(field.name.clone(), value);
+        offset += len;
+    }
+
+    Ok(Value::Object(result))
+}
+
+/// A physical value as a JSON number, rounded by the definition's policy
+fn json_number(def: &DidDefinition, physical: f64, scale: f64) -> Value {
+    to_json_number_with(physical, scale, &def.precision_policy.unwrap_or_default())
+}
+
 /// Decode a single scalar value
 fn decode_scalar(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
     let raw = read_raw_value(def, data, 0)?;
+    if let Some(lookup) = &def.lookup {
+        // No scale to derive the precision from: explicit, else 2 places
+        let resolution = 10f64.powi(-i32::from(def.precision.unwrap_or(2)));
+        return Ok(json_number(def, lookup.to_physical(raw)?, resolution));
+    }
     let physical = raw * def.scale + def.offset;
-    Ok(to_json_number(physical, def.scale))
+    Ok(json_number(def, physical, def.scale))
 }
 
 /// Decode a 1D array
@@ -74,7 +134,7 @@ fn decode_array(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
         if offset + elem_size <= data.len() {
             let raw = read_raw_value(def, data, offset)?;
             let physical = raw * def.scale + def.offset;
-            values.push(to_json_number(physical, def.scale));
+            values.push(json_number(def, physical, def.scale));
         } else {
             values.push(Value::Null);
         }
@@ -117,7 +177,7 @@ fn decode_map(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
             if offset + elem_size <= data.len() {
                 let raw = read_raw_value(def, data, offset)?;
                 let physical = raw * def.scale + def.offset;
-                row_values.push(to_json_number(physical, def.scale));
+                row_values.push(json_number(def, physical, def.scale));
             } else {
                 row_values.push(Value::Null);
             }
@@ -155,6 +215,11 @@ fn decode_map(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
 }
 
 /// Decode histogram data
+///
+/// `counts` holds one entry per bin in wire order and `ranges` the
+/// `[lower, upper]` edges of each (`null` for an open under/overflow end).
+/// Counters stuck at the maximum of their type are listed in
+/// `saturated_bins`, with `saturated: true`.
 fn decode_histogram(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
     let hist_def = def
         .histogram
@@ -166,23 +231,36 @@ fn decode_histogram(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
         .byte_size()
         .ok_or_else(|| ConvError::InvalidData("Variable-length type in histogram".to_string()))?;
 
-    let num_bins = hist_def.bins.len();
-    let mut counts = Vec::with_capacity(num_bins);
+    let num_bins = hist_def.bin_count();
+    check_length(data, 0, num_bins * elem_size)?;
+    let counter_max = def.data_type.max_value();
 
+    let mut counts = Vec::with_capacity(num_bins);
+    let mut saturated = Vec::new();
     for i in 0..num_bins {
-        let offset = i * elem_size;
-        if offset + elem_size <= data.len() {
-            let raw = read_raw_value(def, data, offset)?;
-            let physical = raw * def.scale + def.offset;
-            counts.push(to_json_number(physical, def.scale));
-        } else {
-            counts.push(json!(0));
+        let raw = read_raw_value(def, data, i * elem_size)?;
+        if counter_max.is_some_and(|max| raw >= max) {
+            saturated.push(i);
         }
+        let physical = raw * def.scale + def.offset;
+        counts.push(json_number(def, physical, def.scale));
     }
 
+    let ranges: Vec<Value> = hist_def
+        .ranges()
+        .into_iter()
+        .map(|(lower, upper)| json!([lower, upper]))
+        .collect();
+
     let mut result = serde_json::Map::new();
     result.insert("counts".to_string(), Value::Array(counts));
     result.insert("bins".to_string(), json!(hist_def.bins));
+    result.insert("ranges".to_string(), Value::Array(ranges));
+
+    if !saturated.is_empty() {
+        result.insert("saturated".to_string(), json!(true));
+        result.insert("saturated_bins".to_string(), json!(saturated));
+    }
 
     if let Some(labels) = &hist_def.labels {
         result.insert("labels".to_string(), json!(labels));
@@ -206,20 +284,27 @@ fn decode_enum(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
         .as_ref()
         .ok_or_else(|| ConvError::InvalidData("Not an enum".to_string()))?;
 
-    let raw = read_raw_value(def, data, 0)?;
-    let raw_int = raw.round() as u32;
+    let raw_int = match def.data_type {
+        DataType::Bytes => {
+            let len = def.length.unwrap_or(data.len());
+            check_length(data, 0, len)?;
+            if len > 4 {
+                return Err(ConvError::InvalidData(format!(
+                    "Enum key of {} bytes exceeds 32 bits",
+                    len
+                )));
+            }
+            data[..len]
+                .iter()
+                .fold(0u32, |acc, &b| (acc << 8) | u32::from(b))
+        }
+        _ => read_raw_value(def, data, 0)?.round() as u32,
+    };
 
-    if let Some(label) = enum_map.get(&raw_int) {
-        Ok(json!({
-            "value": raw_int,
-            "label": label
-        }))
-    } else {
-        Ok(json!({
-            "value": raw_int,
-            "label": null
-        }))
-    }
+    Ok(json!({
+        "value": raw_int,
+        "label": enum_map.label(raw_int).unwrap_or("unknown")
+    }))
 }
 
 /// Decode bit fields
@@ -381,20 +466,38 @@ fn read_raw_value(def: &DidDefinition, data: &[u8], offset: usize) -> ConvResult
             };
             Ok(raw)
         }
-        DataType::String | DataType::Bytes => {
-            // For strings/bytes, return 0 (these are handled separately)
+        DataType::String | DataType::Bytes | DataType::Bcd | DataType::Date { .. } => {
+            // For strings/bytes/BCD, return 0 (these are handled separately)
             Ok(0.0)
         }
     }
 }
 
 /// Decode string data
+///
+/// A `null_terminated` string ends at its first NUL code unit; trailing
+/// padding (`fill_byte` or NUL) is dropped either way.
 pub fn decode_string(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
     let len = def.length.unwrap_or(data.len()).min(data.len());
-    let s = String::from_utf8_lossy(&data[..len])
-        .trim_end_matches('\0')
-        .to_string();
-    Ok(json!(s))
+    let unit = def.encoding.unit_len();
+    let mut text = &data[..len];
+
+    if def.null_terminated {
+        if let Some(end) = text.chunks(unit).position(|u| u.iter().all(|&b| b == 0)) {
+            text = &text[..end * unit];
+        }
+    }
+
+    let fill = def.fill_byte.unwrap_or(0);
+    while text.len() >= unit
+        && text[text.len() - unit..]
+            .iter()
+            .all(|&b| b == fill || b == 0)
+    {
+        text = &text[..text.len() - unit];
+    }
+
+    Ok(json!(def.encoding.decode(text)))
 }
 
 /// Decode raw bytes as hex string
@@ -402,6 +505,102 @@ pub fn decode_bytes(data: &[u8]) -> Value {
     json!(hex::encode(data))
 }
 
+/// Best-effort view of bytes without a definition
+///
+/// Returns `{ raw_hex, length, ascii_preview }`; the preview shows printable
+/// ASCII bytes as-is and every other byte as `.`.
+pub fn decode_annotated(data: &[u8]) -> Value {
+    let ascii_preview: String = data
+        .iter()
+        .map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            }
+        })
+        .collect();
+    json!({
+        "raw_hex": hex::encode(data),
+        "length": data.len(),
+        "ascii_preview": ascii_preview,
+    })
+}
+
+/// Decode packed BCD as a number, with scale/offset applied
+fn decode_bcd(def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
+    let len = def.length.unwrap_or(data.len());
+    check_length(data, 0, len)?;
+    let raw = bcd_digits(&data[..len])?
+        .iter()
+        .try_fold(0u64, |n, d| n.checked_mul(10)?.checked_add(*d as u64))
+        .ok_or_else(|| ConvError::InvalidData(format!("BCD value of {} bytes overflows", len)))?;
+    let physical = raw as f64 * def.scale + def.offset;
+    Ok(json_number(def, physical, def.scale))
+}
+
+/// Decode a packed BCD date as an ISO-8601 `YYYY-MM-DD` string
+fn decode_date(format: DateFormat, data: &[u8]) -> ConvResult<Value> {
+    check_length(data, 0, format.byte_len())?;
+    let digits = bcd_digits(&data[..format.byte_len()])?;
+    let number = |d: &[u8]| d.iter().fold(0u32, |n, d| n * 10 + *d as u32);
+
+    let (year, month, day) = if format.is_day_first() {
+        (
+            number(&digits[4..]),
+            number(&digits[2..4]),
+            number(&digits[..2]),
+        )
+    } else {
+        let y = digits.len() - 4;
+        (
+            number(&digits[..y]),
+            number(&digits[y..y + 2]),
+            number(&digits[y + 2..]),
+        )
+    };
+    let year = if format.has_short_year() {
+        2000 + year
+    } else {
+        year
+    };
+
+    if !is_valid_date(year, month, day) {
+        return Err(ConvError::InvalidData(format!(
+            "invalid date {:04}-{:02}-{:02}",
+            year, month, day
+        )));
+    }
+    Ok(json!(format!("{:04}-{:02}-{:02}", year, month, day)))
+}
+
+/// Decimal digits of packed BCD bytes, most significant first
+fn bcd_digits(data: &[u8]) -> ConvResult<Vec<u8>> {
+    let mut digits = Vec::with_capacity(data.len() * 2);
+    for (offset, &byte) in data.iter().enumerate() {
+        for digit in [byte >> 4, byte & 0x0F] {
+            if digit > 9 {
+                return Err(ConvError::InvalidBcd { byte, offset });
+            }
+            digits.push(digit);
+        }
+    }
+    Ok(digits)
+}
+
+/// Whether `year`-`month`-`day` is a calendar date
+pub(crate) fn is_valid_date(year: u32, month: u32, day: u32) -> bool {
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
 fn check_length(data: &[u8], offset: usize, required: usize) -> ConvResult<()> {
     if offset + required > data.len() {
         Err(ConvError::DataTooShort {
@@ -416,6 +615,8 @@ fn check_length(data: &[u8], offset: usize, required: usize) -> ConvResult<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::checksum::{ChecksumAlgorithm, ChecksumDef};
+    use crate::definition::{EnumKey, EnumMap, FieldDef, LookupTable, OutOfRange};
     use std::collections::HashMap;
 
     #[test]
@@ -468,17 +669,51 @@ mod tests {
     #[test]
     fn test_decode_enum() {
         let mut def = DidDefinition::scalar(DataType::Uint8);
-        def.enum_map = Some(HashMap::from([
-            (0, "Off".to_string()),
-            (1, "Cranking".to_string()),
-            (2, "Running".to_string()),
-        ]));
+        def.enum_map = Some(
+            HashMap::from([
+                (0, "Off".to_string()),
+                (1, "Cranking".to_string()),
+                (2, "Running".to_string()),
+            ])
+            .into(),
+        );
 
         let value = decode(&def, &[2]).unwrap();
         assert_eq!(value["value"], json!(2));
         assert_eq!(value["label"], json!("Running"));
     }
 
+    #[test]
+    fn test_decode_enum_ranges() {
+        let mut def = DidDefinition::scalar(DataType::Uint16);
+        let mut map = EnumMap::default();
+        map.insert(EnumKey::Range(0x0000, 0x000F), "idle");
+        map.insert(EnumKey::Range(0x0010, 0x001F), "active");
+        map.insert(EnumKey::Value(0x0100), "fault");
+        def.enum_map = Some(map);
+
+        assert_eq!(
+            decode(&def, &[0x00, 0x1A]).unwrap(),
+            json!({ "value": 0x1A, "label": "active" })
+        );
+        assert_eq!(decode(&def, &[0x01, 0x00]).unwrap()["label"], "fault");
+        // Unmapped values decode with an `unknown` label, not an error
+        assert_eq!(
+            decode(&def, &[0x02, 0x00]).unwrap(),
+            json!({ "value": 0x0200, "label": "unknown" })
+        );
+    }
+
+    #[test]
+    fn test_decode_enum_bytes_key_width() {
+        let mut def = DidDefinition::scalar(DataType::Bytes);
+        def.length = Some(3);
+        def.enum_map = Some([(0x010203, "v1.2.3".to_string())].into());
+
+        assert_eq!(decode(&def, &[1, 2, 3]).unwrap()["label"], "v1.2.3");
+        assert!(decode(&def, &[1, 2]).is_err());
+    }
+
     #[test]
     fn test_decode_bitfield() {
         let mut def = DidDefinition::scalar(DataType::Uint8);
@@ -535,4 +770,192 @@ mod tests {
 
         assert_eq!(value["values"], json!([[1, 2], [3, 4]]));
     }
+
+    #[test]
+    fn test_decode_histogram() {
+        let def = DidDefinition::histogram(DataType::Uint16, vec![0.0, 1000.0, 2000.0]);
+
+        let value = decode(&def, &[0x00, 0x64, 0x01, 0x2C]).unwrap();
+
+        assert_eq!(value["counts"], json!([100, 300]));
+        assert_eq!(value["bins"], json!([0.0, 1000.0, 2000.0]));
+        assert_eq!(value["ranges"], json!([[0.0, 1000.0], [1000.0, 2000.0]]));
+        assert!(value.get("saturated").is_none());
+    }
+
+    #[test]
+    fn test_decode_histogram_under_overflow_and_saturation() {
+        let mut def = DidDefinition::histogram(DataType::Uint16, vec![-40.0, 0.0, 40.0]);
+        def.byte_order = ByteOrder::Little;
+        let hist = def.histogram.as_mut().unwrap();
+        hist.underflow = true;
+        hist.overflow = true;
+
+        let value = decode(&def, &[0x05, 0x00, 0xFF, 0xFF, 0x10, 0x00, 0x02, 0x00]).unwrap();
+
+        assert_eq!(value["counts"], json!([5, 65535, 16, 2]));
+        assert_eq!(
+            value["ranges"],
+            json!([[null, -40.0], [-40.0, 0.0], [0.0, 40.0], [40.0, null]])
+        );
+        assert_eq!(value["saturated"], true);
+        assert_eq!(value["saturated_bins"], json!([1]));
+
+        // One counter per bin is required
+        assert!(matches!(
+            decode(&def, &[0x05, 0x00, 0xFF, 0xFF, 0x10, 0x00]),
+            Err(ConvError::DataTooShort { expected: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn test_decode_bcd() {
+        let def = DidDefinition::scalar(DataType::Bcd);
+        assert_eq!(decode(&def, &[0x12, 0x34, 0x56]).unwrap(), json!(123456));
+
+        let def = DidDefinition::scaled(DataType::Bcd, 0.1, 0.0);
+        assert_eq!(decode(&def, &[0x01, 0x25]).unwrap(), json!(12.5));
+    }
+
+    #[test]
+    fn test_decode_bcd_invalid_nibble() {
+        let def = DidDefinition::scalar(DataType::Bcd);
+        let err = decode(&def, &[0x12, 0x3A]).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ConvError::InvalidBcd {
+                    byte: 0x3A,
+                    offset: 1
+                }
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_decode_date() {
+        let date = |format| {
+            DidDefinition::scalar(DataType::Date {
+                format: format_of(format),
+            })
+        };
+        assert_eq!(
+            decode(&date("YYYYMMDD"), &[0x20, 0x24, 0x03, 0x15]).unwrap(),
+            json!("2024-03-15")
+        );
+        assert_eq!(
+            decode(&date("YYMMDD"), &[0x24, 0x03, 0x15]).unwrap(),
+            json!("2024-03-15")
+        );
+        assert_eq!(
+            decode(&date("DDMMYY"), &[0x15, 0x03, 0x24]).unwrap(),
+            json!("2024-03-15")
+        );
+
+        // Not a calendar date, and not BCD
+        assert!(decode(&date("YYMMDD"), &[0x23, 0x02, 0x29]).is_err());
+        assert!(matches!(
+            decode(&date("YYMMDD"), &[0x24, 0x0F, 0x15]),
+            Err(ConvError::InvalidBcd { offset: 1, .. })
+        ));
+    }
+
+    fn format_of(format: &str) -> DateFormat {
+        format.parse().unwrap()
+    }
+
+    #[test]
+    fn test_decode_composite_mixed_byte_order() {
+        // Big-endian u16 followed by a little-endian u32
+        let mut def = DidDefinition::scalar(DataType::Bytes);
+        def.fields = Some(vec![
+            FieldDef::new("speed", DataType::Uint16).with_scale(0.5, 0.0),
+            FieldDef::new("odometer", DataType::Uint32)
+                .with_byte_order(ByteOrder::Little)
+                .with_scale(0.1, 0.0),
+        ]);
+
+        let value = decode(&def, &[0x01, 0x2C, 0x39, 0x30, 0x00, 0x00]).unwrap();
+        assert_eq!(value, json!({ "speed": 150, "odometer": 1234.5 }));
+
+        assert!(matches!(
+            decode(&def, &[0x01, 0x2C, 0x39, 0x30]),
+            Err(ConvError::DataTooShort {
+                expected: 6,
+                actual: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn test_decode_composite_trailing_string() {
+        let mut def = DidDefinition::default();
+        def.fields = Some(vec![
+            FieldDef::new("version", DataType::Uint8),
+            FieldDef::new("label", DataType::String),
+        ]);
+        assert_eq!(
+            decode(&def, b"\x02boot").unwrap(),
+            json!({ "version": 2, "label": "boot" })
+        );
+
+        // Only the last field may be unsized
+        def.fields.as_mut().unwrap().reverse();
+        assert!(decode(&def, b"boot\x02").is_err());
+    }
+
+    #[test]
+    fn test_decode_checksum_trailer() {
+        // Engine RPM (raw 7200 * 0.25) followed by CRC-8 SAE J1850
+        let mut def = DidDefinition::scaled(DataType::Uint16, 0.25, 0.0);
+        def.checksum = Some(ChecksumDef::new(ChecksumAlgorithm::Crc8SaeJ1850));
+        assert_eq!(decode(&def, &[0x1C, 0x20, 0xFA]).unwrap(), json!(1800));
+
+        assert!(matches!(
+            decode(&def, &[0x1C, 0x21, 0xFA]),
+            Err(ConvError::ChecksumMismatch { actual: 0xFA, .. })
+        ));
+    }
+
+    /// NTC thermistor: ADC counts fall as temperature rises
+    fn thermistor(out_of_range: OutOfRange) -> DidDefinition {
+        let mut def = DidDefinition::scalar(DataType::Uint16).with_unit("°C");
+        def.lookup = Some(LookupTable {
+            breakpoints: vec![100.0, 500.0, 900.0],
+            values: vec![120.0, 40.0, -20.0],
+            out_of_range,
+        });
+        def
+    }
+
+    #[test]
+    fn test_decode_lookup_interpolates() {
+        let def = thermistor(OutOfRange::Clamp);
+        assert_eq!(decode(&def, &[0x01, 0xF4]).unwrap(), json!(40)); // 500
+        assert_eq!(decode(&def, &[0x01, 0x2C]).unwrap(), json!(80)); // 300
+        assert_eq!(decode(&def, &[0x02, 0xBC]).unwrap(), json!(10)); // 700
+        assert_eq!(decode(&def, &[0x02, 0xA3]).unwrap(), json!(13.75)); // 675
+    }
+
+    #[test]
+    fn test_decode_lookup_out_of_range() {
+        let clamp = thermistor(OutOfRange::Clamp);
+        assert_eq!(decode(&clamp, &[0x00, 0x32]).unwrap(), json!(120)); // 50
+        assert_eq!(decode(&clamp, &[0x03, 0xB6]).unwrap(), json!(-20)); // 950
+
+        let extrapolate = thermistor(OutOfRange::Extrapolate);
+        assert_eq!(decode(&extrapolate, &[0x00, 0x32]).unwrap(), json!(130));
+        assert_eq!(decode(&extrapolate, &[0x03, 0xB6]).unwrap(), json!(-27.5));
+    }
+
+    #[test]
+    fn test_decode_lookup_rejects_bad_table() {
+        let mut def = thermistor(OutOfRange::Clamp);
+        def.lookup.as_mut().unwrap().breakpoints = vec![500.0, 100.0, 900.0];
+        assert!(decode(&def, &[0x01, 0xF4]).is_err());
+
+        def.lookup.as_mut().unwrap().breakpoints = vec![100.0, 500.0];
+        assert!(decode(&def, &[0x01, 0xF4]).is_err());
+    }
 }
@@ -7,7 +7,10 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use sovd_core::DataCategory;
 
-use crate::types::{Axis, BitField, ByteOrder, DataType};
+use crate::checksum::ChecksumDef;
+use crate::error::{ConvError, ConvResult};
+use crate::precision::PrecisionPolicy;
+use crate::types::{Axis, BitField, ByteOrder, DataType, StringEncoding};
 
 /// Complete definition for a single DID
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +60,20 @@ pub struct DidDefinition {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub length: Option<usize>,
 
+    /// Character encoding of a `string` (default UTF-8)
+    #[serde(default, skip_serializing_if = "StringEncoding::is_utf8")]
+    pub encoding: StringEncoding,
+
+    /// A `string` ends at its first NUL inside the field; encoding writes
+    /// the terminator, which must fit in `length`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub null_terminated: bool,
+
+    /// Byte a `string` is padded with up to `length` (default 0x00);
+    /// trailing fill is stripped on decode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_byte: Option<u8>,
+
     /// Array length for 1D arrays
     #[serde(skip_serializing_if = "Option::is_none")]
     pub array: Option<usize>,
@@ -73,18 +90,40 @@ pub struct DidDefinition {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub histogram: Option<HistogramDefinition>,
 
-    /// Enum mapping for discrete values
+    /// Piecewise-linear raw → physical table replacing scale/offset for a
+    /// scalar (nonlinear sensors such as thermistors)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lookup: Option<LookupTable>,
+
+    /// Enum mapping for discrete values, keyed by single raw values or
+    /// ranges of them
     #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
-    pub enum_map: Option<HashMap<u32, String>>,
+    pub enum_map: Option<EnumMap>,
 
     /// Bit field definitions (for status bytes)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bits: Option<Vec<BitFieldDef>>,
 
+    /// Heterogeneous fields packed back to back, each with its own type and
+    /// byte order. Decodes to an object keyed by field name; the DID-level
+    /// type, byte order and scaling are ignored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<FieldDef>>,
+
+    /// Checksum trailing the payload, verified on decode and appended on
+    /// encode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<ChecksumDef>,
+
     /// Explicit precision override (decimal places)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub precision: Option<u8>,
 
+    /// Rounding of decoded values; unset ⇒ the file's `meta` default, else
+    /// scale-derived
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision_policy: Option<PrecisionPolicy>,
+
     /// Bit mask to apply before scaling
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bit_mask: Option<u32>,
@@ -98,6 +137,12 @@ pub struct DidDefinition {
     #[serde(default)]
     pub writable: bool,
 
+    /// Whether reads of this DID may be answered from the server's read
+    /// cache instead of the bus. For values that don't change within a
+    /// session (VIN, part numbers, hardware version); defaults to false.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cacheable: bool,
+
     /// ISO 17978-3 §7.9 data category (Table 70). When present in a YAML
     /// definition (a `category:` key, e.g. `category: identData`), it is
     /// authoritative for this DID; otherwise the category is derived from the
@@ -106,12 +151,36 @@ pub struct DidDefinition {
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub category: Option<DataCategory>,
 
+    /// UDS service used to read this DID. Defaults to ReadDataByIdentifier
+    /// (0x22); `periodic` is for DIDs the ECU only serves through
+    /// ReadDataByPeriodicIdentifier (0x2A).
+    #[serde(default, skip_serializing_if = "ReadVia::is_standard")]
+    pub read_via: ReadVia,
+
     /// Component ID this DID belongs to (set automatically from file meta)
     /// None = global (available to all components)
     #[serde(skip)]
     pub component_id: Option<String>,
 }
 
+/// How a DID is read from the ECU
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadVia {
+    /// ReadDataByIdentifier (0x22)
+    #[default]
+    Standard,
+    /// One-shot ReadDataByPeriodicIdentifier (0x2A): start a single periodic
+    /// transmission, take the first sample, stop it
+    Periodic,
+}
+
+impl ReadVia {
+    fn is_standard(&self) -> bool {
+        *self == ReadVia::Standard
+    }
+}
+
 fn default_scale() -> f64 {
     1.0
 }
@@ -130,17 +199,26 @@ impl Default for DidDefinition {
             min: None,
             max: None,
             length: None,
+            encoding: StringEncoding::Utf8,
+            null_terminated: false,
+            fill_byte: None,
             array: None,
             labels: None,
             map: None,
             histogram: None,
+            lookup: None,
             enum_map: None,
             bits: None,
+            fields: None,
+            checksum: None,
             precision: None,
+            precision_policy: None,
             bit_mask: None,
             bit_shift: None,
             writable: false,
+            cacheable: false,
             category: None,
+            read_via: ReadVia::Standard,
             component_id: None,
         }
     }
@@ -188,6 +266,22 @@ impl DidDefinition {
         }
     }
 
+    /// Create a histogram definition over the given bin edges
+    pub fn histogram(data_type: DataType, bins: Vec<f64>) -> Self {
+        Self {
+            data_type,
+            histogram: Some(HistogramDefinition {
+                bins,
+                underflow: false,
+                overflow: false,
+                labels: None,
+                axis_name: None,
+                axis_unit: None,
+            }),
+            ..Default::default()
+        }
+    }
+
     /// Add a semantic identifier (SOVD-compliant, e.g., "coolant_temperature")
     pub fn with_id(mut self, id: impl Into<String>) -> Self {
         self.id = Some(id.into());
@@ -262,9 +356,14 @@ impl DidDefinition {
         self.bits.is_some() && !self.bits.as_ref().unwrap().is_empty()
     }
 
+    /// Check if this is a composite of heterogeneous fields
+    pub fn is_composite(&self) -> bool {
+        self.fields.as_ref().is_some_and(|f| !f.is_empty())
+    }
+
     /// Check if this has enum mapping
     pub fn is_enum(&self) -> bool {
-        self.enum_map.is_some() && !self.enum_map.as_ref().unwrap().is_empty()
+        self.enum_map.as_ref().is_some_and(|m| !m.is_empty())
     }
 
     /// Whether this definition carries a meaningful byte↔physical *conversion*
@@ -286,8 +385,11 @@ impl DidDefinition {
         if self.is_array()
             || self.is_map()
             || self.is_histogram()
+            || self.lookup.is_some()
             || self.is_bitfield()
             || self.is_enum()
+            || self.is_composite()
+            || self.checksum.is_some()
             || self.labels.is_some()
         {
             return true;
@@ -312,13 +414,26 @@ impl DidDefinition {
             .unwrap_or_else(|| crate::precision::precision_from_scale(self.scale))
     }
 
-    /// Calculate expected byte length
+    /// Calculate expected byte length, including any checksum trailer
     pub fn expected_byte_length(&self) -> Option<usize> {
+        let trailer = self.checksum.as_ref().map_or(0, ChecksumDef::byte_len);
+        self.payload_byte_length().map(|len| len + trailer)
+    }
+
+    fn payload_byte_length(&self) -> Option<usize> {
         // For variable-length types
         if let Some(len) = self.length {
             return Some(len);
         }
 
+        if let Some(fields) = self.fields.as_ref().filter(|f| !f.is_empty()) {
+            return fields.iter().map(FieldDef::byte_len).sum();
+        }
+
+        if let DataType::Date { format } = self.data_type {
+            return Some(format.byte_len());
+        }
+
         let elem_size = self.data_type.byte_size()?;
 
         if let Some(map) = &self.map {
@@ -326,7 +441,7 @@ impl DidDefinition {
         } else if let Some(arr_len) = self.array {
             Some(arr_len * elem_size)
         } else if let Some(hist) = &self.histogram {
-            Some(hist.bins.len() * elem_size)
+            Some(hist.bin_count() * elem_size)
         } else {
             Some(elem_size)
         }
@@ -349,10 +464,16 @@ pub struct MapDefinition {
 }
 
 /// Histogram configuration
+///
+/// One counter per bin, in wire order: the underflow bin (if any), one bin
+/// between each pair of neighbouring edges, then the overflow bin (if any).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistogramDefinition {
     /// Bin edges (N+1 edges for N bins, or N edges if overflow bin)
     pub bins: Vec<f64>,
+    /// Whether there's an underflow bin for values below the first edge
+    #[serde(default)]
+    pub underflow: bool,
     /// Whether there's an overflow bin for values above last edge
     #[serde(default)]
     pub overflow: bool,
@@ -367,6 +488,311 @@ pub struct HistogramDefinition {
     pub axis_unit: Option<String>,
 }
 
+impl HistogramDefinition {
+    /// Number of counters on the wire, under/overflow bins included
+    pub fn bin_count(&self) -> usize {
+        self.bins.len().saturating_sub(1) + self.underflow as usize + self.overflow as usize
+    }
+
+    /// Lower and upper edge of each bin in wire order; `None` is open
+    pub fn ranges(&self) -> Vec<(Option<f64>, Option<f64>)> {
+        let mut ranges = Vec::with_capacity(self.bin_count());
+        if self.underflow {
+            ranges.push((None, self.bins.first().copied()));
+        }
+        ranges.extend(self.bins.windows(2).map(|w| (Some(w[0]), Some(w[1]))));
+        if self.overflow {
+            ranges.push((self.bins.last().copied(), None));
+        }
+        ranges
+    }
+}
+
+/// Piecewise-linear scaling table: `breakpoints` are raw values in
+/// ascending order, `values` the physical value at each one. Decoding
+/// interpolates between neighbouring breakpoints, encoding interpolates
+/// back, so `values` must be monotonic for encoding to be unambiguous.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LookupTable {
+    /// Raw breakpoints, strictly ascending
+    pub breakpoints: Vec<f64>,
+    /// Physical value at each breakpoint
+    pub values: Vec<f64>,
+    /// What a value beyond the first or last breakpoint maps to
+    #[serde(default)]
+    pub out_of_range: OutOfRange,
+}
+
+/// Handling of values outside a [`LookupTable`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutOfRange {
+    /// Pin to the nearest end of the table
+    #[default]
+    Clamp,
+    /// Continue the slope of the outermost segment
+    Extrapolate,
+}
+
+impl LookupTable {
+    /// Physical value for a raw value
+    pub fn to_physical(&self, raw: f64) -> ConvResult<f64> {
+        self.check()?;
+        Ok(self.interpolate(&self.breakpoints, &self.values, raw))
+    }
+
+    /// Raw value for a physical value (the inverse interpolation)
+    pub fn to_raw(&self, physical: f64) -> ConvResult<f64> {
+        self.check()?;
+        let decreasing = self.values.first() > self.values.last();
+        if decreasing {
+            // Interpolate over the reversed table so `x` ascends
+            let xs: Vec<f64> = self.values.iter().rev().copied().collect();
+            let ys: Vec<f64> = self.breakpoints.iter().rev().copied().collect();
+            if !xs.is_sorted() {
+                return Err(self.not_monotonic());
+            }
+            Ok(self.interpolate(&xs, &ys, physical))
+        } else {
+            if !self.values.is_sorted() {
+                return Err(self.not_monotonic());
+            }
+            Ok(self.interpolate(&self.values, &self.breakpoints, physical))
+        }
+    }
+
+    fn check(&self) -> ConvResult<()> {
+        if self.breakpoints.len() < 2 || self.breakpoints.len() != self.values.len() {
+            return Err(ConvError::InvalidData(format!(
+                "lookup table needs matching breakpoints and values (at least 2), got {} and {}",
+                self.breakpoints.len(),
+                self.values.len()
+            )));
+        }
+        if self.breakpoints.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(ConvError::InvalidData(
+                "lookup table breakpoints must be strictly ascending".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn not_monotonic(&self) -> ConvError {
+        ConvError::InvalidData("lookup table values must be monotonic to encode".to_string())
+    }
+
+    /// `y` at `x` along the polyline through `(xs, ys)`; `xs` ascending
+    fn interpolate(&self, xs: &[f64], ys: &[f64], x: f64) -> f64 {
+        let last = xs.len() - 1;
+        if self.out_of_range == OutOfRange::Clamp {
+            if x <= xs[0] {
+                return ys[0];
+            }
+            if x >= xs[last] {
+                return ys[last];
+            }
+        }
+        // Segment containing `x`, or the outermost one when extrapolating
+        let i = xs[1..last].partition_point(|&bp| bp < x);
+        let (x0, x1, y0, y1) = (xs[i], xs[i + 1], ys[i], ys[i + 1]);
+        if x1 == x0 {
+            return y0;
+        }
+        y0 + (x - x0) * (y1 - y0) / (x1 - x0)
+    }
+}
+
+/// Raw value → label mapping of an enum DID (YAML `enum:`)
+///
+/// A key is a single raw value (`2`, `0x0102`) or an inclusive range
+/// (`0x10-0x1F`), so several OEM state encodings can share one label. The
+/// key width follows the DID's `type`. An exact key wins over a range;
+/// otherwise the first range containing the value, in declaration order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnumMap {
+    entries: Vec<(EnumKey, String)>,
+}
+
+/// Key of one [`EnumMap`] entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumKey {
+    /// A single raw value
+    Value(u32),
+    /// Raw values `start..=end`
+    Range(u32, u32),
+}
+
+impl EnumMap {
+    /// Add an entry after the existing ones
+    pub fn insert(&mut self, key: EnumKey, label: impl Into<String>) {
+        self.entries.push((key, label.into()));
+    }
+
+    /// Label for a raw value: its exact key, else the first matching range
+    pub fn label(&self, raw: u32) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(key, _)| *key == EnumKey::Value(raw))
+            .or_else(|| self.entries.iter().find(|(key, _)| key.contains(raw)))
+            .map(|(_, label)| label.as_str())
+    }
+
+    /// Entries in declaration order
+    pub fn iter(&self) -> impl Iterator<Item = &(EnumKey, String)> {
+        self.entries.iter()
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl EnumKey {
+    /// Whether `raw` falls under this key
+    pub fn contains(&self, raw: u32) -> bool {
+        match *self {
+            EnumKey::Value(value) => raw == value,
+            EnumKey::Range(start, end) => (start..=end).contains(&raw),
+        }
+    }
+}
+
+impl std::fmt::Display for EnumKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnumKey::Value(value) => write!(f, "{}", value),
+            EnumKey::Range(start, end) => write!(f, "0x{:X}-0x{:X}", start, end),
+        }
+    }
+}
+
+impl std::str::FromStr for EnumKey {
+    type Err = ConvError;
+
+    /// `16`, `0x10`, or a range `0x10-0x1F` (either bound decimal or hex)
+    fn from_str(s: &str) -> ConvResult<Self> {
+        fn bound(s: &str) -> Option<u32> {
+            let s = s.trim();
+            match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => s.parse().ok(),
+            }
+        }
+        let invalid = || ConvError::InvalidData(format!("Invalid enum key: {}", s));
+        match s.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (
+                    bound(start).ok_or_else(invalid)?,
+                    bound(end).ok_or_else(invalid)?,
+                );
+                if start > end {
+                    return Err(invalid());
+                }
+                Ok(EnumKey::Range(start, end))
+            }
+            None => bound(s).map(EnumKey::Value).ok_or_else(invalid),
+        }
+    }
+}
+
+impl From<HashMap<u32, String>> for EnumMap {
+    fn from(map: HashMap<u32, String>) -> Self {
+        let mut entries: Vec<_> = map
+            .into_iter()
+            .map(|(value, label)| (EnumKey::Value(value), label))
+            .collect();
+        entries.sort_by_key(|(key, _)| match key {
+            EnumKey::Value(value) | EnumKey::Range(value, _) => *value,
+        });
+        Self { entries }
+    }
+}
+
+impl<const N: usize> From<[(u32, String); N]> for EnumMap {
+    fn from(entries: [(u32, String); N]) -> Self {
+        HashMap::from(entries).into()
+    }
+}
+
+impl Serialize for EnumMap {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for (key, label) in &self.entries {
+            match key {
+                EnumKey::Value(value) => map.serialize_entry(value, label)?,
+                EnumKey::Range(..) => map.serialize_entry(&key.to_string(), label)?,
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for EnumMap {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MapVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for MapVisitor {
+            type Value = EnumMap;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a map of raw values or ranges to labels")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut access: A,
+            ) -> Result<EnumMap, A::Error> {
+                let mut map = EnumMap::default();
+                while let Some((key, label)) = access.next_entry::<EnumKey, String>()? {
+                    map.insert(key, label);
+                }
+                Ok(map)
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for EnumKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl serde::de::Visitor<'_> for KeyVisitor {
+            type Value = EnumKey;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a raw value or a range like 0x10-0x1F")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<EnumKey, E> {
+                u32::try_from(v)
+                    .map(EnumKey::Value)
+                    .map_err(|_| E::custom(format!("enum key {} exceeds 32 bits", v)))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<EnumKey, E> {
+                u32::try_from(v)
+                    .map(EnumKey::Value)
+                    .map_err(|_| E::custom(format!("enum key {} out of range", v)))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<EnumKey, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(KeyVisitor)
+    }
+}
+
 /// Bit field definition (for YAML parsing)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BitFieldDef {
@@ -382,6 +808,78 @@ pub struct BitFieldDef {
     pub enum_map: Option<HashMap<u32, String>>,
 }
 
+/// One field of a composite DID (YAML `fields:`), in wire order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDef {
+    /// Key of the field in the decoded object
+    pub name: String,
+    /// Primitive data type
+    #[serde(rename = "type")]
+    pub data_type: DataType,
+    /// Byte order of this field, independent of its neighbours
+    #[serde(default)]
+    pub byte_order: ByteOrder,
+    /// Scale factor: physical = raw * scale + offset
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+    /// Offset: physical = raw * scale + offset
+    #[serde(default)]
+    pub offset: f64,
+    /// Unit string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Fixed byte length (strings, bytes, BCD). Only the last field may
+    /// leave a variable-length type unsized; it then takes the remainder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<usize>,
+}
+
+impl FieldDef {
+    /// Create a field with the default big-endian byte order and no scaling
+    pub fn new(name: impl Into<String>, data_type: DataType) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+            byte_order: ByteOrder::Big,
+            scale: 1.0,
+            offset: 0.0,
+            unit: None,
+            length: None,
+        }
+    }
+
+    /// Set the byte order
+    pub fn with_byte_order(mut self, byte_order: ByteOrder) -> Self {
+        self.byte_order = byte_order;
+        self
+    }
+
+    /// Add scale/offset
+    pub fn with_scale(mut self, scale: f64, offset: f64) -> Self {
+        self.scale = scale;
+        self.offset = offset;
+        self
+    }
+
+    /// The field as a standalone scalar definition
+    pub fn as_definition(&self) -> DidDefinition {
+        DidDefinition {
+            data_type: self.data_type,
+            byte_order: self.byte_order,
+            scale: self.scale,
+            offset: self.offset,
+            unit: self.unit.clone(),
+            length: self.length,
+            ..Default::default()
+        }
+    }
+
+    /// Encoded size in bytes, if fixed
+    pub fn byte_len(&self) -> Option<usize> {
+        self.as_definition().expected_byte_length()
+    }
+}
+
 fn default_width() -> u8 {
     1
 }
@@ -491,6 +989,18 @@ mod tests {
         assert_eq!(def.category, None);
     }
 
+    #[test]
+    fn test_read_via_deserializes_from_yaml_key() {
+        let yaml = "id: boost_pressure\ntype: uint16\nread_via: periodic\n";
+        let def: DidDefinition = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(def.read_via, ReadVia::Periodic);
+
+        // Absent `read_via:` → plain ReadDataByIdentifier, and not serialized.
+        let def: DidDefinition = serde_yaml::from_str("id: vin\ntype: string\n").unwrap();
+        assert_eq!(def.read_via, ReadVia::Standard);
+        assert!(!serde_yaml::to_string(&def).unwrap().contains("read_via"));
+    }
+
     #[test]
     fn test_component_availability() {
         // No component_id - global, available to all
@@ -530,4 +1040,72 @@ mod tests {
         enumd.enum_map = Some([(0, "off".to_string()), (1, "on".to_string())].into());
         assert!(enumd.has_conversion());
     }
+
+    #[test]
+    fn test_enum_ranges() {
+        let yaml = r#"
+type: uint16
+enum:
+  0x00-0x0F: idle
+  0x0005: self_test
+  0x10-0x1F: active
+  0x0100: fault
+  0x00-0xFFFF: shadowed
+"#;
+        let def: DidDefinition = serde_yaml::from_str(yaml).unwrap();
+        let map = def.enum_map.as_ref().unwrap();
+        assert_eq!(map.len(), 5);
+        assert_eq!(map.label(0x03), Some("idle"));
+        // An exact key wins over an earlier range
+        assert_eq!(map.label(0x05), Some("self_test"));
+        assert_eq!(map.label(0x1F), Some("active"));
+        assert_eq!(map.label(0x0100), Some("fault"));
+        // The catch-all range only sees what the earlier keys don't
+        assert_eq!(map.label(0x0200), Some("shadowed"));
+
+        // Ranges round-trip as `0x..-0x..` strings, single values as integers
+        let yaml = serde_yaml::to_string(&def).unwrap();
+        assert!(yaml.contains("0x10-0x1F: active"), "{yaml}");
+        let back: DidDefinition = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(back.enum_map, def.enum_map);
+    }
+
+    #[test]
+    fn test_enum_key_parse() {
+        assert_eq!("16".parse::<EnumKey>().unwrap(), EnumKey::Value(16));
+        assert_eq!("0x10".parse::<EnumKey>().unwrap(), EnumKey::Value(16));
+        assert_eq!(
+            "0x10 - 31".parse::<EnumKey>().unwrap(),
+            EnumKey::Range(16, 31)
+        );
+        assert!("0x1F-0x10".parse::<EnumKey>().is_err());
+        assert!("idle".parse::<EnumKey>().is_err());
+    }
+
+    #[test]
+    fn test_composite_definition() {
+        let yaml = r#"
+id: motor_status
+fields:
+  - { name: speed, type: uint16 }
+  - { name: odometer, type: uint32, byte_order: little, scale: 0.1, unit: km }
+"#;
+        let def: DidDefinition = serde_yaml::from_str(yaml).unwrap();
+        assert!(def.is_composite());
+        assert!(def.has_conversion());
+        assert_eq!(def.expected_byte_length(), Some(6));
+
+        let fields = def.fields.as_ref().unwrap();
+        assert_eq!(fields[0].byte_order, ByteOrder::Big);
+        assert_eq!(fields[1].byte_order, ByteOrder::Little);
+        assert_eq!(fields[1].scale, 0.1);
+
+        // An unsized trailing string leaves the total open
+        let mut def = def;
+        def.fields
+            .as_mut()
+            .unwrap()
+            .push(FieldDef::new("label", DataType::String));
+        assert_eq!(def.expected_byte_length(), None);
+    }
 }
@@ -6,10 +6,32 @@ use serde_json::Value;
 
 use crate::definition::DidDefinition;
 use crate::error::{ConvError, ConvResult};
-use crate::types::{ByteOrder, DataType};
+use crate::types::{ByteOrder, DataType, DateFormat};
 
 /// Encode a value according to definition
+///
+/// A declared checksum is computed over the encoded payload and appended.
 pub fn encode(def: &DidDefinition, value: &Value) -> ConvResult<Vec<u8>> {
+    let mut bytes = encode_payload(def, value)?;
+    if let Some(checksum) = &def.checksum {
+        checksum.append(&mut bytes)?;
+    }
+    Ok(bytes)
+}
+
+fn encode_payload(def: &DidDefinition, value: &Value) -> ConvResult<Vec<u8>> {
+    if def.is_composite() {
+        return encode_composite(def, value);
+    }
+
+    if let DataType::Date { format } = def.data_type {
+        return encode_date(format, value);
+    }
+
+    if def.is_histogram() {
+        return encode_histogram(def, value);
+    }
+
     match value {
         Value::Number(n) => {
             let physical = n
@@ -63,33 +85,89 @@ pub fn encode(def: &DidDefinition, value: &Value) -> ConvResult<Vec<u8>> {
     }
 }
 
-/// Encode a single scalar value
-fn encode_scalar(def: &DidDefinition, physical: f64) -> ConvResult<Vec<u8>> {
-    // Reverse the scale/offset: raw = (physical - offset) / scale
-    let raw = ((physical - def.offset) / def.scale).round();
-
-    // Validate bounds
-    if let (Some(min), Some(max)) = (def.min, def.max) {
-        if physical < min || physical > max {
-            return Err(ConvError::ValueOutOfRange {
-                value: physical,
-                min,
-                max,
-            });
+/// Encode an object keyed by field name, field by field in wire order
+fn encode_composite(def: &DidDefinition, value: &Value) -> ConvResult<Vec<u8>> {
+    let fields = def.fields.as_deref().unwrap_or_default();
+    let obj = value
+        .as_object()
+        .ok_or_else(|| ConvError::InvalidData("Composite value not an object".to_string()))?;
+    let mut bytes = Vec::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        let field_value = obj
+            .get(&field.name)
+            .ok_or_else(|| ConvError::InvalidData(format!("Missing field: {}", field.name)))?;
+        let encoded = encode(&field.as_definition(), field_value)?;
+        match field.byte_len() {
+            Some(len) if encoded.len() != len => {
+                return Err(ConvError::InvalidData(format!(
+                    "Field '{}' encodes to {} bytes, expected {}",
+                    field.name,
+                    encoded.len(),
+                    len
+                )))
+            }
+            None if i + 1 < fields.len() => {
+                return Err(ConvError::InvalidData(format!(
+                    "Field '{}' needs a length",
+                    field.name
+                )))
+            }
+            _ => {}
         }
+        bytes.extend(encoded);
     }
 
+    Ok(bytes)
+}
+
+/// Encode a single scalar value
+fn encode_scalar(def: &DidDefinition, physical: f64) -> ConvResult<Vec<u8>> {
+    check_range(def, physical)?;
+
+    // Reverse the lookup table, or the scale/offset: raw = (physical - offset) / scale
+    let raw = match &def.lookup {
+        Some(lookup) => lookup.to_raw(physical)?.round(),
+        None => ((physical - def.offset) / def.scale).round(),
+    };
+
     write_raw_value(def, raw)
 }
 
+/// Reject a physical value outside the definition's declared `min`/`max`
+///
+/// A missing bound is open, so a definition with only `max` still caps
+/// the value.
+fn check_range(def: &DidDefinition, physical: f64) -> ConvResult<()> {
+    let min = def.min.unwrap_or(f64::NEG_INFINITY);
+    let max = def.max.unwrap_or(f64::INFINITY);
+    if physical < min || physical > max {
+        return Err(ConvError::ValueOutOfRange {
+            value: physical,
+            min,
+            max,
+        });
+    }
+    Ok(())
+}
+
 /// Encode a 1D array
 fn encode_array(def: &DidDefinition, values: &[Value]) -> ConvResult<Vec<u8>> {
+    if let Some(expected) = def.array {
+        if values.len() != expected {
+            return Err(ConvError::ElementCount {
+                expected,
+                actual: values.len(),
+            });
+        }
+    }
     let mut bytes = Vec::new();
 
     for value in values {
         let physical = value
             .as_f64()
             .ok_or_else(|| ConvError::InvalidData("Array element not a number".to_string()))?;
+        check_range(def, physical)?;
         let raw = ((physical - def.offset) / def.scale).round();
         bytes.extend(write_raw_value(def, raw)?);
     }
@@ -110,6 +188,7 @@ fn encode_map(def: &DidDefinition, rows: &[Value]) -> ConvResult<Vec<u8>> {
             let physical = cell
                 .as_f64()
                 .ok_or_else(|| ConvError::InvalidData("Map cell not a number".to_string()))?;
+            check_range(def, physical)?;
             let raw = ((physical - def.offset) / def.scale).round();
             bytes.extend(write_raw_value(def, raw)?);
         }
@@ -118,13 +197,68 @@ fn encode_map(def: &DidDefinition, rows: &[Value]) -> ConvResult<Vec<u8>> {
     Ok(bytes)
 }
 
-/// Encode a string value
+/// Encode histogram counts, given as the decoded object or a bare array
+///
+/// Exactly one count per bin, under/overflow bins included.
+fn encode_histogram(def: &DidDefinition, value: &Value) -> ConvResult<Vec<u8>> {
+    let num_bins = def.histogram.as_ref().map_or(0, |h| h.bin_count());
+    let counts = match value {
+        Value::Array(counts) => counts,
+        Value::Object(obj) => obj
+            .get("counts")
+            .and_then(Value::as_array)
+            .ok_or_else(|| ConvError::InvalidData("Histogram without counts".to_string()))?,
+        _ => {
+            return Err(ConvError::InvalidData(
+                "Histogram not an array or object".to_string(),
+            ))
+        }
+    };
+    if counts.len() != num_bins {
+        return Err(ConvError::ElementCount {
+            expected: num_bins,
+            actual: counts.len(),
+        });
+    }
+
+    let mut bytes = Vec::new();
+    for count in counts {
+        let physical = count
+            .as_f64()
+            .ok_or_else(|| ConvError::InvalidData("Histogram count not a number".to_string()))?;
+        check_range(def, physical)?;
+        let raw = ((physical - def.offset) / def.scale).round();
+        bytes.extend(write_raw_value(def, raw)?);
+    }
+
+    Ok(bytes)
+}
+
+/// Encode a string value in the definition's encoding
+///
+/// A fixed-length field is padded with `fill_byte`; a string that doesn't
+/// fit (terminator included) is refused rather than cut.
 fn encode_string(def: &DidDefinition, s: &str) -> ConvResult<Vec<u8>> {
-    let mut bytes = s.as_bytes().to_vec();
+    let mut bytes = def
+        .encoding
+        .encode(s)
+        .map_err(|ch| ConvError::Unencodable {
+            ch,
+            encoding: def.encoding,
+        })?;
+
+    if def.null_terminated {
+        bytes.resize(bytes.len() + def.encoding.unit_len(), 0);
+    }
 
-    // Pad or truncate to fixed length if specified
     if let Some(len) = def.length {
-        bytes.resize(len, 0);
+        if bytes.len() > len {
+            return Err(ConvError::StringTooLong {
+                actual: bytes.len(),
+                max: len,
+            });
+        }
+        bytes.resize(len, def.fill_byte.unwrap_or(0));
     }
 
     Ok(bytes)
@@ -182,13 +316,85 @@ fn write_raw_value(def: &DidDefinition, raw: f64) -> ConvResult<Vec<u8>> {
             ByteOrder::Big => raw.to_be_bytes().to_vec(),
             ByteOrder::Little => raw.to_le_bytes().to_vec(),
         }),
-        DataType::String | DataType::Bytes => Ok(vec![]),
+        DataType::Bcd => encode_bcd(def, raw),
+        DataType::String | DataType::Bytes | DataType::Date { .. } => Ok(vec![]),
+    }
+}
+
+/// Encode a non-negative integer as packed BCD, zero-padded to `length`
+fn encode_bcd(def: &DidDefinition, raw: f64) -> ConvResult<Vec<u8>> {
+    if !(0.0..1e19).contains(&raw) {
+        return Err(ConvError::InvalidData(format!(
+            "{} cannot be encoded as BCD",
+            raw
+        )));
+    }
+    let mut digits = (raw as u64).to_string();
+    if let Some(len) = def.length {
+        if digits.len() > len * 2 {
+            return Err(ConvError::InvalidData(format!(
+                "{} does not fit in {} BCD bytes",
+                digits, len
+            )));
+        }
+        digits = format!("{:0>width$}", digits, width = len * 2);
+    } else if digits.len() % 2 == 1 {
+        digits.insert(0, '0');
     }
+    Ok(pack_bcd(&digits))
+}
+
+/// Encode an ISO-8601 `YYYY-MM-DD` string as a packed BCD date
+fn encode_date(format: DateFormat, value: &Value) -> ConvResult<Vec<u8>> {
+    let invalid = || ConvError::InvalidData(format!("expected a YYYY-MM-DD date, got {}", value));
+    let s = value.as_str().ok_or_else(invalid)?;
+    let parts: Vec<&str> = s.split('-').collect();
+    let [year, month, day] = parts[..] else {
+        return Err(invalid());
+    };
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return Err(invalid());
+    }
+    let number = |part: &str| part.parse::<u32>().map_err(|_| invalid());
+    let (year, month, day) = (number(year)?, number(month)?, number(day)?);
+    if !crate::decode::is_valid_date(year, month, day) {
+        return Err(invalid());
+    }
+
+    let year = if format.has_short_year() {
+        if !(2000..=2099).contains(&year) {
+            return Err(ConvError::InvalidData(format!(
+                "year {} cannot be stored as {}",
+                year, format
+            )));
+        }
+        format!("{:02}", year % 100)
+    } else {
+        format!("{:04}", year)
+    };
+    let digits = if format.is_day_first() {
+        format!("{:02}{:02}{}", day, month, year)
+    } else {
+        format!("{}{:02}{:02}", year, month, day)
+    };
+    Ok(pack_bcd(&digits))
+}
+
+/// Pack an even-length string of decimal digits two per byte
+fn pack_bcd(digits: &str) -> Vec<u8> {
+    digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| ((pair[0] - b'0') << 4) | (pair[1] - b'0'))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::checksum::{ChecksumAlgorithm, ChecksumDef};
+    use crate::definition::{FieldDef, LookupTable, OutOfRange};
+    use crate::types::StringEncoding;
     use serde_json::json;
 
     #[test]
@@ -269,6 +475,49 @@ mod tests {
         assert!(bytes.starts_with(b"WF0XXXGCDX12345"));
     }
 
+    #[test]
+    fn test_encode_string_encodings() {
+        let mut def = DidDefinition::scalar(DataType::String);
+        def.length = Some(8);
+        def.encoding = StringEncoding::Utf16be;
+        def.null_terminated = true;
+
+        let bytes = encode(&def, &json!("Tür")).unwrap();
+        assert_eq!(bytes, vec![0x00, b'T', 0x00, 0xFC, 0x00, b'r', 0x00, 0x00]);
+        assert_eq!(crate::decode::decode(&def, &bytes).unwrap(), json!("Tür"));
+
+        def.encoding = StringEncoding::Latin1;
+        def.fill_byte = Some(b' ');
+        let bytes = encode(&def, &json!("Tür")).unwrap();
+        assert_eq!(bytes, b"T\xFCr\0    ".to_vec());
+        assert_eq!(crate::decode::decode(&def, &bytes).unwrap(), json!("Tür"));
+
+        def.encoding = StringEncoding::Ascii;
+        assert!(matches!(
+            encode(&def, &json!("Tür")),
+            Err(ConvError::Unencodable { ch: 'ü', .. })
+        ));
+    }
+
+    #[test]
+    fn test_encode_string_too_long() {
+        let mut def = DidDefinition::scalar(DataType::String);
+        def.length = Some(4);
+
+        assert!(encode(&def, &json!("ABCD")).is_ok());
+        assert!(matches!(
+            encode(&def, &json!("ABCDE")),
+            Err(ConvError::StringTooLong { actual: 5, max: 4 })
+        ));
+
+        // The terminator has to fit too
+        def.null_terminated = true;
+        assert!(matches!(
+            encode(&def, &json!("ABCD")),
+            Err(ConvError::StringTooLong { actual: 5, max: 4 })
+        ));
+    }
+
     #[test]
     fn test_encode_little_endian() {
         let mut def = DidDefinition::scaled(DataType::Uint16, 1.0, 0.0);
@@ -289,4 +538,175 @@ mod tests {
         let result = encode(&def, &json!(300));
         assert!(matches!(result, Err(ConvError::ValueOutOfRange { .. })));
     }
+
+    #[test]
+    fn test_encode_single_bound() {
+        let mut def = DidDefinition::scaled(DataType::Uint8, 1.0, 0.0);
+        def.max = Some(100.0);
+
+        assert!(encode(&def, &json!(0)).is_ok());
+        let result = encode(&def, &json!(101));
+        assert!(matches!(
+            result,
+            Err(ConvError::ValueOutOfRange { max, .. }) if max == 100.0
+        ));
+    }
+
+    #[test]
+    fn test_encode_array_bounds_and_count() {
+        let def = DidDefinition::array(DataType::Uint8, 4).with_bounds(0.0, 100.0);
+
+        assert!(encode(&def, &json!([1, 2, 3, 4])).is_ok());
+        assert!(matches!(
+            encode(&def, &json!([1, 2, 300, 4])),
+            Err(ConvError::ValueOutOfRange { value, .. }) if value == 300.0
+        ));
+        assert!(matches!(
+            encode(&def, &json!([1, 2, 3])),
+            Err(ConvError::ElementCount {
+                expected: 4,
+                actual: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_encode_histogram() {
+        let mut def = DidDefinition::histogram(DataType::Uint16, vec![0.0, 1000.0, 2000.0]);
+        def.byte_order = ByteOrder::Little;
+        def.histogram.as_mut().unwrap().overflow = true;
+
+        let bytes = vec![0x64, 0x00, 0x2C, 0x01, 0xFF, 0xFF];
+        assert_eq!(encode(&def, &json!([100, 300, 65535])).unwrap(), bytes);
+        // The decoded object encodes back to the same bytes
+        let decoded = crate::decode::decode(&def, &bytes).unwrap();
+        assert_eq!(encode(&def, &decoded).unwrap(), bytes);
+
+        assert!(matches!(
+            encode(&def, &json!([100, 300])),
+            Err(ConvError::ElementCount {
+                expected: 3,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_encode_bcd() {
+        let def = DidDefinition::scalar(DataType::Bcd);
+        assert_eq!(
+            encode(&def, &json!(123456)).unwrap(),
+            vec![0x12, 0x34, 0x56]
+        );
+        assert_eq!(encode(&def, &json!(123)).unwrap(), vec![0x01, 0x23]);
+
+        let mut def = DidDefinition::scalar(DataType::Bcd);
+        def.length = Some(3);
+        assert_eq!(encode(&def, &json!(42)).unwrap(), vec![0x00, 0x00, 0x42]);
+        assert!(encode(&def, &json!(1234567)).is_err());
+        assert!(encode(&def, &json!(-1)).is_err());
+    }
+
+    #[test]
+    fn test_encode_date_round_trip() {
+        for (format, bytes) in [
+            ("YYYYMMDD", vec![0x20, 0x24, 0x03, 0x15]),
+            ("YYMMDD", vec![0x24, 0x03, 0x15]),
+            ("DDMMYYYY", vec![0x15, 0x03, 0x20, 0x24]),
+        ] {
+            let def = DidDefinition::scalar(DataType::Date {
+                format: format.parse().unwrap(),
+            });
+            assert_eq!(
+                encode(&def, &json!("2024-03-15")).unwrap(),
+                bytes,
+                "{format}"
+            );
+            assert_eq!(
+                crate::decode::decode(&def, &bytes).unwrap(),
+                json!("2024-03-15")
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_date_rejects_invalid() {
+        let def = DidDefinition::scalar(DataType::Date {
+            format: DateFormat::Yymmdd,
+        });
+        assert!(encode(&def, &json!("2024-02-30")).is_err());
+        assert!(encode(&def, &json!("15.03.2024")).is_err());
+        assert!(encode(&def, &json!(20240315)).is_err());
+        // Two-digit years only cover 2000-2099
+        assert!(encode(&def, &json!("1999-12-31")).is_err());
+    }
+
+    #[test]
+    fn test_encode_composite_round_trip() {
+        let mut def = DidDefinition::default();
+        def.fields = Some(vec![
+            FieldDef::new("speed", DataType::Uint16).with_scale(0.5, 0.0),
+            FieldDef::new("odometer", DataType::Uint32)
+                .with_byte_order(ByteOrder::Little)
+                .with_scale(0.1, 0.0),
+            FieldDef::new("serial", DataType::Bytes),
+        ]);
+        def.fields.as_mut().unwrap()[2].length = Some(2);
+
+        let bytes = vec![0x01, 0x2C, 0x39, 0x30, 0x00, 0x00, 0xAB, 0xCD];
+        let value = crate::decode::decode(&def, &bytes).unwrap();
+        assert_eq!(
+            value,
+            json!({ "speed": 150, "odometer": 1234.5, "serial": "abcd" })
+        );
+        assert_eq!(encode(&def, &value).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_encode_composite_rejects_bad_fields() {
+        let mut def = DidDefinition::default();
+        def.fields = Some(vec![
+            FieldDef::new("speed", DataType::Uint16),
+            FieldDef::new("serial", DataType::Bytes),
+        ]);
+        def.fields.as_mut().unwrap()[1].length = Some(2);
+
+        assert!(encode(&def, &json!({ "speed": 1 })).is_err());
+        assert!(encode(&def, &json!({ "speed": 1, "serial": "abcdef" })).is_err());
+        assert!(encode(&def, &json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn test_encode_appends_checksum() {
+        let mut def = DidDefinition::scaled(DataType::Uint16, 0.25, 0.0);
+        def.checksum = Some(ChecksumDef::new(ChecksumAlgorithm::Crc16Ccitt));
+        let bytes = encode(&def, &json!(1800)).unwrap();
+        assert_eq!(bytes, vec![0x1C, 0x20, 0x7F, 0x73]);
+        assert_eq!(crate::decode::decode(&def, &bytes).unwrap(), json!(1800));
+
+        // Additive sum over the first byte only
+        def.checksum = Some(ChecksumDef::new(ChecksumAlgorithm::Sum).with_range(0, 1));
+        assert_eq!(encode(&def, &json!(1800)).unwrap(), vec![0x1C, 0x20, 0x1C]);
+    }
+
+    #[test]
+    fn test_encode_lookup_inverts_table() {
+        // NTC thermistor: ADC counts fall as temperature rises
+        let mut def = DidDefinition::scalar(DataType::Uint16);
+        def.lookup = Some(LookupTable {
+            breakpoints: vec![100.0, 500.0, 900.0],
+            values: vec![120.0, 40.0, -20.0],
+            out_of_range: OutOfRange::Clamp,
+        });
+        assert_eq!(encode(&def, &json!(80)).unwrap(), vec![0x01, 0x2C]); // 300
+        assert_eq!(encode(&def, &json!(10)).unwrap(), vec![0x02, 0xBC]); // 700
+        assert_eq!(encode(&def, &json!(150)).unwrap(), vec![0x00, 0x64]); // clamped to 100
+
+        def.lookup.as_mut().unwrap().out_of_range = OutOfRange::Extrapolate;
+        assert_eq!(encode(&def, &json!(130)).unwrap(), vec![0x00, 0x32]); // 50
+
+        // Non-monotonic physical values have no unique inverse
+        def.lookup.as_mut().unwrap().values = vec![120.0, 40.0, 60.0];
+        assert!(encode(&def, &json!(50)).is_err());
+    }
 }
@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use crate::types::StringEncoding;
+
 /// Errors that can occur during DID conversion
 #[derive(Debug, Error)]
 pub enum ConvError {
@@ -21,10 +23,30 @@ pub enum ConvError {
     #[error("invalid data: {0}")]
     InvalidData(String),
 
+    /// Packed BCD byte with a nibble above 9
+    #[error("invalid BCD byte 0x{byte:02X} at offset {offset}")]
+    InvalidBcd { byte: u8, offset: usize },
+
+    /// Trailing checksum does not match the payload
+    #[error("checksum mismatch: expected 0x{expected:X}, got 0x{actual:X}")]
+    ChecksumMismatch { expected: u32, actual: u32 },
+
     /// Value out of range for encoding
     #[error("value out of range: {value} not in [{min}, {max}]")]
     ValueOutOfRange { value: f64, min: f64, max: f64 },
 
+    /// Array value with a different element count than the definition declares
+    #[error("wrong element count: expected {expected}, got {actual}")]
+    ElementCount { expected: usize, actual: usize },
+
+    /// String that doesn't fit its fixed-length field
+    #[error("string too long: {actual} bytes, field holds {max}")]
+    StringTooLong { actual: usize, max: usize },
+
+    /// Character the string's encoding can't represent
+    #[error("character {ch:?} not representable in {encoding}")]
+    Unencodable { ch: char, encoding: StringEncoding },
+
     /// YAML parsing error
     #[error("YAML parse error: {0}")]
     YamlError(#[from] serde_yaml::Error),
@@ -38,6 +60,21 @@ pub enum ConvError {
     JsonError(#[from] serde_json::Error),
 }
 
+impl ConvError {
+    /// Whether encoding failed because the value breaks the definition
+    /// (range, element count, string fit) rather than because the value
+    /// wasn't in a shape the definition converts
+    pub fn is_rejected_value(&self) -> bool {
+        matches!(
+            self,
+            ConvError::ValueOutOfRange { .. }
+                | ConvError::ElementCount { .. }
+                | ConvError::StringTooLong { .. }
+                | ConvError::Unencodable { .. }
+        )
+    }
+}
+
 /// Result type for DID conversion operations
 pub type ConvResult<T> = Result<T, ConvError>;
 
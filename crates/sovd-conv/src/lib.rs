@@ -81,25 +81,53 @@
 //! | Enum | Discrete states | Gear position (P, R, N, D) |
 //! | Bitfield | Packed boolean/multi-bit | Status byte |
 //! | Histogram | Binned counts | Operating time distribution |
+//! | Composite | Named fields, each with its own type and byte order | Motor status |
+//!
+//! A scalar whose transfer function isn't linear (e.g. a thermistor) can
+//! replace `scale`/`offset` with a `lookup:` table of raw breakpoints and
+//! physical values, interpolated both ways.
+//!
+//! Any of these may end in a `checksum:` trailer (CRC-8 SAE J1850,
+//! CRC-16-CCITT or an additive sum) that is verified on decode and appended
+//! on encode.
+//!
+//! A JSON Schema for definition files is available from
+//! [`schema::definition_json_schema`].
+//!
+//! Decoded values can be converted between units of the same quantity
+//! (`kPa` ↔ `bar`, `°C` ↔ `°F`, ...) with [`units::convert_unit`].
 
+pub mod checksum;
 pub mod decode;
 pub mod definition;
 pub mod encode;
 pub mod error;
 pub mod precision;
+pub mod processor;
+pub mod schema;
 pub mod store;
 pub mod types;
+pub mod units;
 
 // Re-export main types
-pub use definition::{BitFieldDef, DidDefinition, HistogramDefinition, MapDefinition};
+pub use checksum::{ChecksumAlgorithm, ChecksumDef};
+pub use definition::{
+    BitFieldDef, DidDefinition, EnumKey, EnumMap, FieldDef, HistogramDefinition, LookupTable,
+    MapDefinition, OutOfRange, ReadVia,
+};
 // §7.9 DataCategory is owned by sovd-core; re-export so sovd-conv consumers
 // (e.g. the API data handler) can name it through one crate.
 pub use error::{format_did, parse_did, ConvError, ConvResult};
-pub use precision::{precision_from_scale, round_for_scale, to_json_number};
+pub use precision::{
+    precision_from_scale, round_for_scale, round_with_policy, to_json_number, to_json_number_with,
+    PrecisionMode, PrecisionPolicy, RoundingMode,
+};
+pub use processor::{PostProcessor, PreProcessor};
 #[doc(no_inline)]
 pub use sovd_core::DataCategory;
 pub use store::{DidStore, StoreMeta};
-pub use types::{Axis, BitField, ByteOrder, DataType, Shape};
+pub use types::{Axis, BitField, ByteOrder, DataType, DateFormat, Shape, StringEncoding};
+pub use units::{convert_unit, Quantity};
 
 /// Prelude module for convenient imports
 pub mod prelude {
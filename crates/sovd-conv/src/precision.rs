@@ -1,6 +1,13 @@
 //! Floating point precision handling
 //!
 //! Avoids ugly values like 13.000000001 by rounding to appropriate precision.
+//!
+//! By default the precision is derived from the scale factor. A
+//! [`PrecisionPolicy`] on a definition (or in the file's `meta` as a default)
+//! selects fixed decimal places or significant figures and the rounding mode
+//! instead; without one, values decode exactly as before.
+
+use serde::{Deserialize, Serialize};
 
 /// Determine appropriate decimal places from scale factor
 ///
@@ -57,11 +64,100 @@ pub fn round_for_scale(value: f64, scale: f64) -> f64 {
     round_to_precision(value, precision)
 }
 
+/// How decoded values are rounded
+///
+/// ```yaml
+/// precision_policy:
+///   mode: significant    # scale (default) | decimals | significant
+///   digits: 4            # decimal places, or significant figures
+///   rounding: half_even  # half_up (default) | half_even
+/// ```
+///
+/// The default policy is the scale-derived rounding of [`round_for_scale`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrecisionPolicy {
+    /// Where the number of digits comes from
+    #[serde(default)]
+    pub mode: PrecisionMode,
+    /// Decimal places (`decimals`) or significant figures (`significant`);
+    /// unset falls back to the scale-derived precision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digits: Option<u8>,
+    /// How a value halfway between two candidates is rounded
+    #[serde(default)]
+    pub rounding: RoundingMode,
+}
+
+/// Source of the number of digits a value is rounded to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrecisionMode {
+    /// Decimal places derived from the scale factor
+    #[default]
+    Scale,
+    /// A fixed number of decimal places
+    Decimals,
+    /// A fixed number of significant figures
+    Significant,
+}
+
+/// Tie-breaking rule when rounding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Halves round away from zero (2.5 → 3, -2.5 → -3)
+    #[default]
+    HalfUp,
+    /// Halves round to the even neighbour (2.5 → 2, 3.5 → 4)
+    HalfEven,
+}
+
+impl RoundingMode {
+    /// Round to the nearest integer
+    pub fn round(self, value: f64) -> f64 {
+        match self {
+            RoundingMode::HalfUp => value.round(),
+            RoundingMode::HalfEven => value.round_ties_even(),
+        }
+    }
+}
+
+impl PrecisionPolicy {
+    /// Decimal places `value` is rounded to; negative rounds to tens,
+    /// hundreds, ... (significant figures of a large value)
+    fn decimals(&self, value: f64, scale: f64) -> i32 {
+        match (self.mode, self.digits) {
+            (PrecisionMode::Decimals, Some(digits)) => digits as i32,
+            (PrecisionMode::Significant, Some(digits)) if value != 0.0 && value.is_finite() => {
+                digits as i32 - (value.abs().log10().floor() as i32 + 1)
+            }
+            _ => precision_from_scale(scale) as i32,
+        }
+    }
+}
+
+/// Round a value produced with `scale` according to `policy`
+pub fn round_with_policy(value: f64, scale: f64, policy: &PrecisionPolicy) -> f64 {
+    let decimals = policy.decimals(value, scale);
+    if decimals >= 0 {
+        let factor = 10_f64.powi(decimals);
+        policy.rounding.round(value * factor) / factor
+    } else {
+        let factor = 10_f64.powi(-decimals);
+        policy.rounding.round(value / factor) * factor
+    }
+}
+
 /// Format a value as a clean JSON number
 ///
 /// Ensures we don't get ugly representations like 1.4000000000000001
 pub fn to_json_number(value: f64, scale: f64) -> serde_json::Value {
-    let rounded = round_for_scale(value, scale);
+    to_json_number_with(value, scale, &PrecisionPolicy::default())
+}
+
+/// Format a value as a clean JSON number, rounded according to `policy`
+pub fn to_json_number_with(value: f64, scale: f64, policy: &PrecisionPolicy) -> serde_json::Value {
+    let rounded = round_with_policy(value, scale, policy);
 
     // Check if it's effectively an integer
     if (rounded - rounded.round()).abs() < f64::EPSILON {
@@ -137,6 +233,55 @@ mod tests {
         assert_eq!(v, serde_json::json!(1.4));
     }
 
+    #[test]
+    fn test_default_policy_matches_scale_rounding() {
+        let policy = PrecisionPolicy::default();
+        for (value, scale) in [(1.4000000000001, 0.01), (1.45000001, 0.1), (92.4, 1.0)] {
+            assert_eq!(
+                round_with_policy(value, scale, &policy),
+                round_for_scale(value, scale)
+            );
+        }
+    }
+
+    #[test]
+    fn test_fixed_decimals_and_half_even() {
+        let mut policy = PrecisionPolicy {
+            mode: PrecisionMode::Decimals,
+            digits: Some(2),
+            rounding: RoundingMode::HalfUp,
+        };
+        // Ignores the scale
+        assert_eq!(round_with_policy(1.23456, 1.0, &policy), 1.23);
+        assert_eq!(round_with_policy(0.125, 1.0, &policy), 0.13);
+
+        policy.rounding = RoundingMode::HalfEven;
+        assert_eq!(round_with_policy(0.125, 1.0, &policy), 0.12);
+        assert_eq!(round_with_policy(0.375, 1.0, &policy), 0.38);
+
+        policy.digits = Some(0);
+        assert_eq!(round_with_policy(2.5, 0.1, &policy), 2.0);
+        assert_eq!(round_with_policy(-2.5, 0.1, &policy), -2.0);
+        assert_eq!(round_with_policy(3.5, 0.1, &policy), 4.0);
+    }
+
+    #[test]
+    fn test_significant_figures() {
+        let policy = PrecisionPolicy {
+            mode: PrecisionMode::Significant,
+            digits: Some(3),
+            rounding: RoundingMode::HalfUp,
+        };
+        assert_eq!(round_with_policy(1234.5, 0.1, &policy), 1230.0);
+        assert_eq!(round_with_policy(0.0012345, 0.0001, &policy), 0.00123);
+        assert_eq!(round_with_policy(-98765.0, 1.0, &policy), -98800.0);
+        assert_eq!(round_with_policy(0.0, 0.1, &policy), 0.0);
+        assert_eq!(
+            to_json_number_with(12345.0, 1.0, &policy),
+            serde_json::json!(12300)
+        );
+    }
+
     #[test]
     fn test_to_json_array() {
         let values = [1.0, 1.1, 1.2, 1.3];
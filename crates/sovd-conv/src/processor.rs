@@ -0,0 +1,102 @@
+//! Per-DID conversion hooks
+//!
+//! Escape hatch for OEM DIDs whose conversion the declarative types can't
+//! express (combining fields, bit-swizzling, ...). A [`PostProcessor`] runs
+//! after the standard decode of its DID and a [`PreProcessor`] before the
+//! standard encode; both are registered on [`DidStore`](crate::DidStore).
+//!
+//! Closures with the matching signature implement the traits, so a hook
+//! can be registered inline.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use serde_json::Value;
+
+use crate::error::ConvResult;
+
+/// Transforms the decoded value of a DID
+pub trait PostProcessor: Send + Sync {
+    /// Transform `value`, the standard decode of `raw`
+    fn post_decode(&self, value: Value, raw: &[u8]) -> ConvResult<Value>;
+}
+
+impl<F> PostProcessor for F
+where
+    F: Fn(Value, &[u8]) -> ConvResult<Value> + Send + Sync,
+{
+    fn post_decode(&self, value: Value, raw: &[u8]) -> ConvResult<Value> {
+        self(value, raw)
+    }
+}
+
+/// Transforms a value before the standard encode of a DID
+///
+/// The inverse of a [`PostProcessor`]: it turns the value a client writes
+/// back into the shape the declarative definition encodes.
+pub trait PreProcessor: Send + Sync {
+    /// Transform `value` into the input of the standard encode
+    fn pre_encode(&self, value: &Value) -> ConvResult<Value>;
+}
+
+impl<F> PreProcessor for F
+where
+    F: Fn(&Value) -> ConvResult<Value> + Send + Sync,
+{
+    fn pre_encode(&self, value: &Value) -> ConvResult<Value> {
+        self(value)
+    }
+}
+
+/// Hooks registered on a store, keyed by DID
+#[derive(Default)]
+pub(crate) struct Processors {
+    post: RwLock<HashMap<u16, Arc<dyn PostProcessor>>>,
+    pre: RwLock<HashMap<u16, Arc<dyn PreProcessor>>>,
+}
+
+impl Processors {
+    pub(crate) fn set_post(&self, did: u16, processor: Arc<dyn PostProcessor>) {
+        self.post.write().unwrap().insert(did, processor);
+    }
+
+    pub(crate) fn set_pre(&self, did: u16, processor: Arc<dyn PreProcessor>) {
+        self.pre.write().unwrap().insert(did, processor);
+    }
+
+    pub(crate) fn remove(&self, did: u16) -> bool {
+        let post = self.post.write().unwrap().remove(&did).is_some();
+        let pre = self.pre.write().unwrap().remove(&did).is_some();
+        post || pre
+    }
+
+    /// Run the post-processor of `did`, if any
+    pub(crate) fn post_decode(&self, did: u16, value: Value, raw: &[u8]) -> ConvResult<Value> {
+        // Clone the hook out so it doesn't run under the lock
+        let processor = self.post.read().unwrap().get(&did).cloned();
+        match processor {
+            Some(p) => p.post_decode(value, raw),
+            None => Ok(value),
+        }
+    }
+
+    /// Run the pre-processor of `did`; `None` when it has none
+    pub(crate) fn pre_encode(&self, did: u16, value: &Value) -> Option<ConvResult<Value>> {
+        let processor = self.pre.read().unwrap().get(&did).cloned();
+        processor.map(|p| p.pre_encode(value))
+    }
+}
+
+impl fmt::Debug for Processors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut post: Vec<u16> = self.post.read().unwrap().keys().copied().collect();
+        let mut pre: Vec<u16> = self.pre.read().unwrap().keys().copied().collect();
+        post.sort_unstable();
+        pre.sort_unstable();
+        f.debug_struct("Processors")
+            .field("post", &post)
+            .field("pre", &pre)
+            .finish()
+    }
+}
@@ -0,0 +1,538 @@
+//! JSON Schema for the YAML/JSON definition file format
+//!
+//! [`definition_json_schema`] describes a complete definition file (`meta` +
+//! `dids`) so editors can validate a file before uploading it to
+//! `POST /admin/definitions`. Every DID shape the decoder understands is
+//! covered: scalars (with scale/offset, masks and precision), arrays,
+//! maps, histograms, enums, bit fields and composites of heterogeneous
+//! fields, each optionally followed by a checksum trailer.
+//!
+//! The schema is stricter than the loader in one respect: unknown keys are
+//! rejected, so a misspelt `sacle:` is caught here instead of silently
+//! falling back to the default.
+//!
+//! A file that validates:
+//!
+//! ```yaml
+//! meta:
+//!   name: Engine ECU
+//!   component_id: engine_ecu
+//!   version: "1.0"
+//!
+//! dids:
+//!   0xF405:
+//!     id: coolant_temperature
+//!     name: Coolant Temperature
+//!     type: uint8
+//!     offset: -40.0
+//!     unit: °C
+//!     min: -40
+//!     max: 215
+//!
+//!   0xF401:
+//!     id: gear_position
+//!     type: uint8
+//!     enum:
+//!       0: P
+//!       1: R
+//!       2: N
+//!       3: D
+//!
+//!   0xF410:
+//!     id: engine_status
+//!     type: uint8
+//!     bits:
+//!       - { name: running, bit: 0 }
+//!       - { name: mode, bit: 1, width: 2, enum: { 0: idle, 1: eco, 2: sport } }
+//!
+//!   0xF420:
+//!     id: wheel_speeds
+//!     type: uint16
+//!     scale: 0.01
+//!     unit: km/h
+//!     array: 4
+//!     labels: [FL, FR, RL, RR]
+//!
+//!   0xF500:
+//!     id: fuel_injection_map
+//!     type: uint16
+//!     scale: 0.01
+//!     unit: ms
+//!     map:
+//!       rows: 2
+//!       cols: 3
+//!       row_axis: { name: RPM, unit: rpm, breakpoints: [800, 1200] }
+//!       col_axis: { name: Load, unit: "%", breakpoints: [0, 50, 100] }
+//!
+//!   0xF600:
+//!     id: coolant_histogram
+//!     type: uint32
+//!     histogram:
+//!       bins: [-40, 0, 40, 80, 120]
+//!       overflow: true
+//!       axis_name: Temperature
+//!       axis_unit: °C
+//! ```
+
+use serde_json::{json, Value};
+
+use crate::types::{DataType, DateFormat};
+use sovd_core::DataCategory;
+
+/// DID keys: `0xF405`, `F405`, or decimal.
+const DID_KEY_PATTERN: &str = "^(0[xX])?[0-9A-Fa-f]{1,4}$";
+
+/// Enum value keys: decimal or `0x`-prefixed hex raw values.
+const ENUM_KEY_PATTERN: &str = "^(0[xX][0-9A-Fa-f]+|[0-9]+)$";
+
+/// DID enum keys: a raw value as above, or an inclusive range `0x10-0x1F`.
+const ENUM_RANGE_KEY_PATTERN: &str =
+    "^(0[xX][0-9A-Fa-f]+|[0-9]+)(\\s*-\\s*(0[xX][0-9A-Fa-f]+|[0-9]+))?$";
+
+const DATA_TYPES: [DataType; 15] = [
+    DataType::Uint8,
+    DataType::Uint16,
+    DataType::Uint32,
+    DataType::Int8,
+    DataType::Int16,
+    DataType::Int32,
+    DataType::Float32,
+    DataType::Float64,
+    DataType::String,
+    DataType::Bytes,
+    DataType::Bcd,
+    DataType::Date {
+        format: DateFormat::Yyyymmdd,
+    },
+    DataType::Date {
+        format: DateFormat::Yymmdd,
+    },
+    DataType::Date {
+        format: DateFormat::Ddmmyyyy,
+    },
+    DataType::Date {
+        format: DateFormat::Ddmmyy,
+    },
+];
+
+const CATEGORIES: [DataCategory; 4] = [
+    DataCategory::IdentData,
+    DataCategory::CurrentData,
+    DataCategory::StoredData,
+    DataCategory::SysInfo,
+];
+
+/// JSON Schema (draft-07) for a definition file as accepted by
+/// [`DidStore::from_yaml`](crate::DidStore::from_yaml).
+pub fn definition_json_schema() -> Value {
+    let data_types: Vec<String> = DATA_TYPES.iter().map(|t| t.to_string()).collect();
+    let categories: Vec<Value> = CATEGORIES
+        .iter()
+        .map(|c| serde_json::to_value(c).expect("DataCategory serializes to a string"))
+        .collect();
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "$id": "urn:sovd:did-definitions",
+        "title": "SOVD DID definition file",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "meta": { "$ref": "#/definitions/meta" },
+            "dids": {
+                "type": "object",
+                "propertyNames": { "pattern": DID_KEY_PATTERN },
+                "additionalProperties": { "$ref": "#/definitions/did_definition" }
+            }
+        },
+        "definitions": {
+            "meta": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "name": { "type": "string" },
+                    "component_id": { "type": "string" },
+                    "version": { "type": "string" },
+                    "description": { "type": "string" },
+                    "precision_policy": { "$ref": "#/definitions/precision_policy" }
+                }
+            },
+            "data_type": {
+                "description": "Primitive type of each raw element",
+                "enum": data_types
+            },
+            "byte_order": { "enum": ["big", "little"] },
+            "category": {
+                "description": "ISO 17978-3 §7.9 data category",
+                "enum": categories
+            },
+            "labels": {
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "enum_map": {
+                "description": "Raw value → display string",
+                "type": "object",
+                "propertyNames": { "pattern": ENUM_KEY_PATTERN },
+                "additionalProperties": { "type": "string" }
+            },
+            "did_enum_map": {
+                "description": "Raw value or range → display string",
+                "type": "object",
+                "propertyNames": { "pattern": ENUM_RANGE_KEY_PATTERN },
+                "additionalProperties": { "type": "string" }
+            },
+            "axis": {
+                "type": "object",
+                "additionalProperties": false,
+                "required": ["name", "breakpoints"],
+                "properties": {
+                    "name": { "type": "string" },
+                    "unit": { "type": "string" },
+                    "breakpoints": { "type": "array", "items": { "type": "number" } },
+                    "labels": { "$ref": "#/definitions/labels" }
+                }
+            },
+            "map": {
+                "type": "object",
+                "additionalProperties": false,
+                "required": ["rows", "cols"],
+                "properties": {
+                    "rows": { "type": "integer", "minimum": 1 },
+                    "cols": { "type": "integer", "minimum": 1 },
+                    "row_axis": { "$ref": "#/definitions/axis" },
+                    "col_axis": { "$ref": "#/definitions/axis" }
+                }
+            },
+            "histogram": {
+                "type": "object",
+                "additionalProperties": false,
+                "required": ["bins"],
+                "properties": {
+                    "bins": { "type": "array", "items": { "type": "number" }, "minItems": 1 },
+                    "underflow": { "type": "boolean" },
+                    "overflow": { "type": "boolean" },
+                    "labels": { "$ref": "#/definitions/labels" },
+                    "axis_name": { "type": "string" },
+                    "axis_unit": { "type": "string" }
+                }
+            },
+            "precision_policy": {
+                "description": "Rounding of decoded values; default scale-derived, half up",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "mode": { "enum": ["scale", "decimals", "significant"] },
+                    "digits": { "type": "integer", "minimum": 0, "maximum": 255 },
+                    "rounding": { "enum": ["half_up", "half_even"] }
+                },
+                "if": {
+                    "properties": { "mode": { "enum": ["decimals", "significant"] } },
+                    "required": ["mode"]
+                },
+                "then": { "required": ["digits"] }
+            },
+            "lookup": {
+                "description": "Piecewise-linear raw → physical table",
+                "type": "object",
+                "additionalProperties": false,
+                "required": ["breakpoints", "values"],
+                "properties": {
+                    "breakpoints": { "type": "array", "items": { "type": "number" }, "minItems": 2 },
+                    "values": { "type": "array", "items": { "type": "number" }, "minItems": 2 },
+                    "out_of_range": { "enum": ["clamp", "extrapolate"] }
+                }
+            },
+            "bit_field": {
+                "type": "object",
+                "additionalProperties": false,
+                "required": ["name", "bit"],
+                "properties": {
+                    "name": { "type": "string" },
+                    "bit": { "type": "integer", "minimum": 0, "maximum": 31 },
+                    "width": { "type": "integer", "minimum": 1, "maximum": 32 },
+                    "enum": { "$ref": "#/definitions/enum_map" }
+                }
+            },
+            "field": {
+                "type": "object",
+                "additionalProperties": false,
+                "required": ["name", "type"],
+                "properties": {
+                    "name": { "type": "string", "minLength": 1 },
+                    "type": { "$ref": "#/definitions/data_type" },
+                    "byte_order": { "$ref": "#/definitions/byte_order" },
+                    "scale": { "type": "number" },
+                    "offset": { "type": "number" },
+                    "unit": { "type": "string" },
+                    "length": { "type": "integer", "minimum": 0 }
+                }
+            },
+            "checksum": {
+                "type": "object",
+                "additionalProperties": false,
+                "required": ["algorithm"],
+                "properties": {
+                    "algorithm": { "enum": ["crc8_sae_j1850", "crc16_ccitt", "sum"] },
+                    "width": { "type": "integer", "minimum": 1, "maximum": 4 },
+                    "start": { "type": "integer", "minimum": 0 },
+                    "end": { "type": "integer", "minimum": 0 }
+                }
+            },
+            "did_definition": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "id": { "type": "string", "minLength": 1 },
+                    "name": { "type": "string" },
+                    "description": { "type": "string" },
+                    "type": { "$ref": "#/definitions/data_type" },
+                    "byte_order": { "$ref": "#/definitions/byte_order" },
+                    "scale": { "type": "number" },
+                    "offset": { "type": "number" },
+                    "unit": { "type": "string" },
+                    "min": { "type": "number" },
+                    "max": { "type": "number" },
+                    "length": { "type": "integer", "minimum": 0 },
+                    "encoding": { "enum": ["utf8", "utf16be", "utf16le", "latin1", "ascii"] },
+                    "null_terminated": { "type": "boolean" },
+                    "fill_byte": { "type": "integer", "minimum": 0, "maximum": 255 },
+                    "array": { "type": "integer", "minimum": 1 },
+                    "labels": { "$ref": "#/definitions/labels" },
+                    "map": { "$ref": "#/definitions/map" },
+                    "histogram": { "$ref": "#/definitions/histogram" },
+                    "lookup": { "$ref": "#/definitions/lookup" },
+                    "enum": { "$ref": "#/definitions/did_enum_map" },
+                    "bits": { "type": "array", "items": { "$ref": "#/definitions/bit_field" } },
+                    "fields": { "type": "array", "items": { "$ref": "#/definitions/field" } },
+                    "checksum": { "$ref": "#/definitions/checksum" },
+                    "precision": { "type": "integer", "minimum": 0, "maximum": 255 },
+                    "precision_policy": { "$ref": "#/definitions/precision_policy" },
+                    "bit_mask": { "type": "integer", "minimum": 0, "maximum": 4294967295u32 },
+                    "bit_shift": { "type": "integer", "minimum": 0, "maximum": 255 },
+                    "writable": { "type": "boolean" },
+                    "cacheable": { "type": "boolean" },
+                    "category": { "$ref": "#/definitions/category" },
+                    "read_via": {
+                        "description": "UDS service used to read the DID",
+                        "enum": ["standard", "periodic"]
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The module doc example, kept in sync by hand.
+    const EXAMPLE_YAML: &str = r#"
+meta:
+  name: Engine ECU
+  component_id: engine_ecu
+  version: "1.0"
+
+dids:
+  0xF405:
+    id: coolant_temperature
+    name: Coolant Temperature
+    type: uint8
+    offset: -40.0
+    unit: °C
+    min: -40
+    max: 215
+
+  0xF401:
+    id: gear_position
+    type: uint8
+    enum:
+      0: P
+      1: R
+      2: N
+      3: D
+
+  0xF410:
+    id: engine_status
+    type: uint8
+    bits:
+      - { name: running, bit: 0 }
+      - { name: mode, bit: 1, width: 2, enum: { 0: idle, 1: eco, 2: sport } }
+
+  0xF420:
+    id: wheel_speeds
+    type: uint16
+    scale: 0.01
+    unit: km/h
+    array: 4
+    labels: [FL, FR, RL, RR]
+
+  0xF500:
+    id: fuel_injection_map
+    type: uint16
+    scale: 0.01
+    unit: ms
+    map:
+      rows: 2
+      cols: 3
+      row_axis: { name: RPM, unit: rpm, breakpoints: [800, 1200] }
+      col_axis: { name: Load, unit: "%", breakpoints: [0, 50, 100] }
+
+  0xF600:
+    id: coolant_histogram
+    type: uint32
+    histogram:
+      bins: [-40, 0, 40, 80, 120]
+      overflow: true
+      axis_name: Temperature
+      axis_unit: °C
+"#;
+
+    fn validate(yaml: &str) -> bool {
+        let instance: Value = serde_yaml::from_str(yaml).unwrap();
+        jsonschema::is_valid(&definition_json_schema(), &instance)
+    }
+
+    #[test]
+    fn doc_example_validates_and_loads() {
+        assert!(validate(EXAMPLE_YAML));
+        // The example is also a file the loader accepts.
+        let store = crate::DidStore::from_yaml(EXAMPLE_YAML).unwrap();
+        assert_eq!(store.len(), 6);
+    }
+
+    #[test]
+    fn shipped_definition_file_validates() {
+        let yaml = include_str!("../../../config/did-definitions/engine_ecu.did.yaml");
+        assert!(validate(yaml));
+    }
+
+    #[test]
+    fn malformed_definitions_are_rejected() {
+        // Unknown data type
+        assert!(!validate("dids:\n  0xF405:\n    type: uint12\n"));
+        // Misspelt key
+        assert!(!validate(
+            "dids:\n  0xF405:\n    type: uint8\n    sacle: 0.5\n"
+        ));
+        // Map without dimensions
+        assert!(!validate(
+            "dids:\n  0xF500:\n    type: uint8\n    map:\n      rows: 2\n"
+        ));
+        // Bit field without a position
+        assert!(!validate(
+            "dids:\n  0xF410:\n    type: uint8\n    bits:\n      - { name: running }\n"
+        ));
+        // Composite field without a type
+        assert!(!validate(
+            "dids:\n  0xF430:\n    fields:\n      - { name: speed }\n"
+        ));
+        // Unsupported checksum algorithm
+        assert!(!validate(
+            "dids:\n  0xF40C:\n    type: uint16\n    checksum: { algorithm: crc32 }\n"
+        ));
+        // DID key that is not a 16-bit identifier
+        assert!(!validate("dids:\n  engine_rpm:\n    type: uint16\n"));
+    }
+
+    #[test]
+    fn composite_definition_validates() {
+        let yaml = "dids:\n  0xF430:\n    id: motor_status\n    fields:\n      \
+                    - { name: speed, type: uint16 }\n      \
+                    - { name: odometer, type: uint32, byte_order: little, scale: 0.1 }\n";
+        assert!(validate(yaml));
+        let store = crate::DidStore::from_yaml(yaml).unwrap();
+        assert_eq!(
+            store
+                .decode(0xF430, &[0x00, 0x2A, 0x0A, 0x00, 0x00, 0x00])
+                .unwrap(),
+            serde_json::json!({ "speed": 42, "odometer": 1 })
+        );
+    }
+
+    #[test]
+    fn ranged_enum_validates() {
+        let yaml = "dids:\n  0xF420:\n    type: uint16\n    enum:\n      \
+                    0x00-0x0F: idle\n      0x10 - 0x1F: active\n      0x0100: fault\n";
+        assert!(validate(yaml));
+        let store = crate::DidStore::from_yaml(yaml).unwrap();
+        assert_eq!(
+            store.decode(0xF420, &[0x00, 0x12]).unwrap(),
+            serde_json::json!({ "value": 0x12, "label": "active" })
+        );
+        // Ranges are for DID enums only, not bit fields
+        assert!(!validate(
+            "dids:\n  0xF410:\n    type: uint8\n    bits:\n      \
+             - { name: mode, bit: 0, width: 2, enum: { 0-1: low } }\n"
+        ));
+    }
+
+    #[test]
+    fn string_encoding_validates() {
+        let yaml = "dids:\n  0xF197:\n    type: string\n    length: 8\n    \
+                    encoding: latin1\n    null_terminated: true\n    fill_byte: 0x20\n";
+        assert!(validate(yaml));
+        let store = crate::DidStore::from_yaml(yaml).unwrap();
+        assert_eq!(
+            store.decode(0xF197, b"Gr\xFC\0    ").unwrap(),
+            serde_json::json!("Grü")
+        );
+        assert!(!validate(
+            "dids:\n  0xF197:\n    type: string\n    encoding: ebcdic\n"
+        ));
+    }
+
+    #[test]
+    fn precision_policy_validates() {
+        let yaml = "meta:\n  precision_policy: { mode: significant, digits: 3 }\n\
+                    dids:\n  0xF40D:\n    type: uint16\n  \
+                    0xF40E:\n    type: uint16\n    scale: 0.01\n    \
+                    precision_policy: { mode: decimals, digits: 1, rounding: half_even }\n";
+        assert!(validate(yaml));
+        let store = crate::DidStore::from_yaml(yaml).unwrap();
+        // The meta default applies where a DID declares none
+        assert_eq!(
+            store.decode(0xF40D, &[0x30, 0x39]).unwrap(),
+            serde_json::json!(12300)
+        );
+        assert_eq!(
+            store.decode(0xF40E, &[0x00, 0x7D]).unwrap(),
+            serde_json::json!(1.2)
+        );
+        assert!(!validate(
+            "dids:\n  0xF40D:\n    type: uint16\n    precision_policy: { mode: decimals }\n"
+        ));
+    }
+
+    #[test]
+    fn checksum_definition_validates() {
+        let yaml = "dids:\n  0xF40C:\n    type: uint16\n    scale: 0.25\n    \
+                    checksum: { algorithm: crc8_sae_j1850 }\n";
+        assert!(validate(yaml));
+        let store = crate::DidStore::from_yaml(yaml).unwrap();
+        assert_eq!(
+            store.decode(0xF40C, &[0x1C, 0x20, 0xFA]).unwrap(),
+            serde_json::json!(1800)
+        );
+        assert!(matches!(
+            store.decode(0xF40C, &[0x1C, 0x20, 0x00]),
+            Err(crate::ConvError::ChecksumMismatch {
+                expected: 0xFA,
+                actual: 0x00
+            })
+        ));
+    }
+
+    #[test]
+    fn data_type_enum_matches_serde_names() {
+        let schema = definition_json_schema();
+        let names = schema["definitions"]["data_type"]["enum"]
+            .as_array()
+            .unwrap();
+        for ty in DATA_TYPES {
+            let wire = serde_json::to_value(ty).unwrap();
+            assert!(names.contains(&wire), "{wire} missing from schema");
+        }
+    }
+}
@@ -4,7 +4,8 @@
 
 use std::collections::HashMap;
 use std::path::Path;
-use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -13,6 +14,8 @@ use crate::decode;
 use crate::definition::DidDefinition;
 use crate::encode;
 use crate::error::{parse_did, ConvError, ConvResult};
+use crate::precision::PrecisionPolicy;
+use crate::processor::{PostProcessor, PreProcessor, Processors};
 
 /// Thread-safe store for DID definitions
 ///
@@ -28,6 +31,10 @@ pub struct DidStore {
     name_index: RwLock<HashMap<String, u16>>,
     /// Metadata about the store
     meta: RwLock<StoreMeta>,
+    /// Decode undefined DIDs to an annotated hex view instead of erroring
+    fallback_decode: AtomicBool,
+    /// Custom conversion hooks, keyed by DID
+    processors: Processors,
 }
 
 /// Metadata about the store
@@ -46,6 +53,9 @@ pub struct StoreMeta {
     /// Description
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Rounding for every DID in the file that declares no `precision_policy`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision_policy: Option<PrecisionPolicy>,
 }
 
 impl DidStore {
@@ -64,7 +74,10 @@ impl DidStore {
                 component_id: None,
                 version: Some(version.into()),
                 description: None,
+                precision_policy: None,
             }),
+            fallback_decode: AtomicBool::new(false),
+            processors: Processors::default(),
         }
     }
 
@@ -79,7 +92,10 @@ impl DidStore {
                 component_id: Some(component_id),
                 version: None,
                 description: None,
+                precision_policy: None,
             }),
+            fallback_decode: AtomicBool::new(false),
+            processors: Processors::default(),
         }
     }
 
@@ -96,6 +112,7 @@ impl DidStore {
 
         // Get component_id from meta - all DIDs in this file belong to this component
         let file_component_id = file.meta.as_ref().and_then(|m| m.component_id.clone());
+        let file_precision_policy = file.meta.as_ref().and_then(|m| m.precision_policy);
 
         // Set metadata
         if let Some(meta) = file.meta {
@@ -109,6 +126,9 @@ impl DidStore {
 
                 // Set component_id from file meta
                 def.component_id = file_component_id.clone();
+                if def.precision_policy.is_none() {
+                    def.precision_policy = file_precision_policy;
+                }
 
                 // Use register to properly handle storage and indexing
                 store.register(did, def);
@@ -376,14 +396,50 @@ impl DidStore {
         *self.meta.write().unwrap() = meta;
     }
 
+    /// Enable or disable the fallback decode of undefined DIDs
+    ///
+    /// When enabled, [`decode`](Self::decode) and
+    /// [`decode_batch`](Self::decode_batch) return
+    /// `{ raw_hex, length, ascii_preview }` for a DID without a definition
+    /// instead of [`ConvError::UnknownDid`]. Disabled by default.
+    pub fn set_fallback_decode(&self, enabled: bool) {
+        self.fallback_decode.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether undefined DIDs decode to the annotated hex view
+    pub fn fallback_decode(&self) -> bool {
+        self.fallback_decode.load(Ordering::Relaxed)
+    }
+
+    /// Register a hook that transforms the decoded value of `did`
+    ///
+    /// Runs after the standard decode of a defined DID, replacing any
+    /// post-processor registered for it before.
+    pub fn register_post_processor(&self, did: u16, processor: impl PostProcessor + 'static) {
+        self.processors.set_post(did, Arc::new(processor));
+    }
+
+    /// Register a hook that transforms a value before the standard encode
+    /// of `did`, replacing any pre-processor registered for it before
+    pub fn register_pre_processor(&self, did: u16, processor: impl PreProcessor + 'static) {
+        self.processors.set_pre(did, Arc::new(processor));
+    }
+
+    /// Remove both hooks of `did`; `false` if it had none
+    pub fn remove_processors(&self, did: u16) -> bool {
+        self.processors.remove(did)
+    }
+
     // =========================================================================
     // Decode/Encode Operations
     // =========================================================================
 
     /// Decode raw bytes for a DID
     pub fn decode(&self, did: u16, data: &[u8]) -> ConvResult<Value> {
-        let def = self.get(did).ok_or(ConvError::UnknownDid(did))?;
-        decode::decode(&def, data)
+        match self.get(did) {
+            Some(def) => self.decode_defined(did, &def, data),
+            None => self.decode_undefined(did, data),
+        }
     }
 
     /// Decode raw bytes for a DID (string version)
@@ -392,10 +448,45 @@ impl DidStore {
         self.decode(did, data)
     }
 
+    /// Decode several DIDs in one pass
+    ///
+    /// Definitions are looked up under a single read lock and borrowed rather
+    /// than cloned per item. Each DID gets its own result, so one undefined or
+    /// malformed entry does not abort the rest; order follows `items`.
+    pub fn decode_batch(&self, items: &[(u16, &[u8])]) -> Vec<(u16, ConvResult<Value>)> {
+        let defs = self.definitions.read().unwrap();
+        items
+            .iter()
+            .map(|&(did, data)| {
+                let result = match defs.get(&did).and_then(|v| v.first()) {
+                    Some(def) => self.decode_defined(did, def, data),
+                    None => self.decode_undefined(did, data),
+                };
+                (did, result)
+            })
+            .collect()
+    }
+
+    /// Standard decode followed by the DID's post-processor
+    fn decode_defined(&self, did: u16, def: &DidDefinition, data: &[u8]) -> ConvResult<Value> {
+        let value = decode::decode(def, data)?;
+        self.processors.post_decode(did, value, data)
+    }
+
+    /// Annotated hex view of an undefined DID, or `UnknownDid` in strict mode
+    fn decode_undefined(&self, did: u16, data: &[u8]) -> ConvResult<Value> {
+        if self.fallback_decode() {
+            Ok(decode::decode_annotated(data))
+        } else {
+            Err(ConvError::UnknownDid(did))
+        }
+    }
+
     /// Decode raw bytes, returning raw hex if DID is not registered
     pub fn decode_or_raw(&self, did: u16, data: &[u8]) -> Value {
         if let Some(def) = self.get(did) {
-            decode::decode(&def, data).unwrap_or_else(|_| decode::decode_bytes(data))
+            self.decode_defined(did, &def, data)
+                .unwrap_or_else(|_| decode::decode_bytes(data))
         } else {
             decode::decode_bytes(data)
         }
@@ -404,7 +495,10 @@ impl DidStore {
     /// Encode a value for a DID
     pub fn encode(&self, did: u16, value: &Value) -> ConvResult<Vec<u8>> {
         let def = self.get(did).ok_or(ConvError::UnknownDid(did))?;
-        encode::encode(&def, value)
+        match self.processors.pre_encode(did, value) {
+            Some(value) => encode::encode(&def, &value?),
+            None => encode::encode(&def, value),
+        }
     }
 
     /// Encode a value for a DID (string version)
@@ -450,6 +544,7 @@ struct DefinitionFile {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::definition::FieldDef;
     use crate::types::DataType;
     use serde_json::json;
 
@@ -479,6 +574,98 @@ mod tests {
         assert_eq!(value, json!(92));
     }
 
+    #[test]
+    fn test_store_decode_batch() {
+        let store = DidStore::new();
+        store.register(0xF405, DidDefinition::scaled(DataType::Uint8, 1.0, -40.0));
+        store.register(0xF40C, DidDefinition::scaled(DataType::Uint16, 0.25, 0.0));
+
+        let results = store.decode_batch(&[
+            (0xF405, &[132][..]),
+            (0xFFFF, &[0x01][..]),
+            (0xF40C, &[0x1C, 0x20][..]),
+            (0xF40C, &[0x1C][..]),
+        ]);
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].0, 0xF405);
+        assert_eq!(results[0].1.as_ref().unwrap(), &json!(92));
+        assert_eq!(results[1].0, 0xFFFF);
+        assert!(matches!(results[1].1, Err(ConvError::UnknownDid(0xFFFF))));
+        assert_eq!(results[2].1.as_ref().unwrap(), &json!(1800));
+        assert!(matches!(results[3].1, Err(ConvError::DataTooShort { .. })));
+    }
+
+    /// Speed (0.5 km/h) and its direction bit, stored as separate fields
+    fn velocity_store() -> DidStore {
+        let store = DidStore::new();
+        let mut def = DidDefinition::scalar(DataType::Bytes);
+        def.fields = Some(vec![
+            FieldDef::new("speed", DataType::Uint16).with_scale(0.5, 0.0),
+            FieldDef::new("reverse", DataType::Uint8),
+        ]);
+        store.register(0xF4A0, def);
+        store
+    }
+
+    #[test]
+    fn test_store_post_processor_merges_fields() {
+        let store = velocity_store();
+        store.register_post_processor(0xF4A0, |value: Value, raw: &[u8]| {
+            let speed = value["speed"].as_f64().unwrap_or_default();
+            let sign = if value["reverse"] == json!(1) {
+                -1.0
+            } else {
+                1.0
+            };
+            assert_eq!(raw.len(), 3);
+            Ok(json!({ "velocity": sign * speed }))
+        });
+
+        assert_eq!(
+            store.decode(0xF4A0, &[0x00, 0x64, 0x01]).unwrap(),
+            json!({ "velocity": -50.0 })
+        );
+        let batch = store.decode_batch(&[(0xF4A0, &[0x00, 0x64, 0x00][..])]);
+        assert_eq!(batch[0].1.as_ref().unwrap(), &json!({ "velocity": 50.0 }));
+        assert_eq!(
+            store.decode_or_raw(0xF4A0, &[0x00, 0x64, 0x00]),
+            json!({ "velocity": 50.0 })
+        );
+
+        // A failed standard decode never reaches the hook
+        assert!(matches!(
+            store.decode(0xF4A0, &[0x00]),
+            Err(ConvError::DataTooShort { .. })
+        ));
+
+        assert!(store.remove_processors(0xF4A0));
+        assert_eq!(
+            store.decode(0xF4A0, &[0x00, 0x64, 0x01]).unwrap(),
+            json!({ "speed": 50, "reverse": 1 })
+        );
+    }
+
+    #[test]
+    fn test_store_pre_processor_splits_value() {
+        let store = velocity_store();
+        store.register_pre_processor(0xF4A0, |value: &Value| {
+            let velocity = value["velocity"]
+                .as_f64()
+                .ok_or_else(|| ConvError::InvalidData("velocity must be a number".into()))?;
+            Ok(json!({ "speed": velocity.abs(), "reverse": u8::from(velocity < 0.0) }))
+        });
+
+        assert_eq!(
+            store.encode(0xF4A0, &json!({ "velocity": -50.0 })).unwrap(),
+            vec![0x00, 0x64, 0x01]
+        );
+        assert!(matches!(
+            store.encode(0xF4A0, &json!({ "velocity": "fast" })),
+            Err(ConvError::InvalidData(_))
+        ));
+    }
+
     #[test]
     fn test_store_encode() {
         let store = DidStore::new();
@@ -532,6 +719,35 @@ dids:
         assert!(matches!(result, Err(ConvError::UnknownDid(0xFFFF))));
     }
 
+    #[test]
+    fn test_store_fallback_decode() {
+        let store = DidStore::new();
+        store.register(0xF405, DidDefinition::scaled(DataType::Uint8, 1.0, -40.0));
+        assert!(!store.fallback_decode());
+
+        store.set_fallback_decode(true);
+        let value = store.decode(0xF190, b"WVW\x00\x7f1").unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "raw_hex": "575657007f31",
+                "length": 6,
+                "ascii_preview": "WVW..1",
+            })
+        );
+
+        // Defined DIDs still decode through their definition
+        assert_eq!(store.decode(0xF405, &[132]).unwrap(), json!(92));
+        let results = store.decode_batch(&[(0xF405, &[132][..]), (0xFFFF, &[0x41][..])]);
+        assert_eq!(results[1].1.as_ref().unwrap()["ascii_preview"], "A");
+
+        store.set_fallback_decode(false);
+        assert!(matches!(
+            store.decode(0xF190, b"WVW"),
+            Err(ConvError::UnknownDid(0xF190))
+        ));
+    }
+
     #[test]
     fn test_store_decode_or_raw() {
         let store = DidStore::new();
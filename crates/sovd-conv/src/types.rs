@@ -5,8 +5,11 @@
 use serde::{Deserialize, Serialize};
 
 /// Primitive data type for raw byte interpretation
+///
+/// Serialized as its name (`uint8`, `bcd`, `date:YYMMDD`, ...), see the
+/// `Display`/`FromStr` impls.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[serde(try_from = "String", into = "String")]
 pub enum DataType {
     /// Unsigned 8-bit integer (1 byte)
     Uint8,
@@ -29,6 +32,10 @@ pub enum DataType {
     /// Raw bytes (hex encoded in JSON)
     #[default]
     Bytes,
+    /// Packed BCD, two decimal digits per byte (all bytes, or `length`)
+    Bcd,
+    /// Packed BCD date, emitted as an ISO-8601 `YYYY-MM-DD` string
+    Date { format: DateFormat },
 }
 
 impl std::fmt::Display for DataType {
@@ -44,21 +51,128 @@ impl std::fmt::Display for DataType {
             DataType::Float64 => "float64",
             DataType::String => "string",
             DataType::Bytes => "bytes",
+            DataType::Bcd => "bcd",
+            DataType::Date { format } => return write!(f, "date:{}", format),
         };
         f.write_str(s)
     }
 }
 
+impl std::str::FromStr for DataType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "uint8" => DataType::Uint8,
+            "uint16" => DataType::Uint16,
+            "uint32" => DataType::Uint32,
+            "int8" => DataType::Int8,
+            "int16" => DataType::Int16,
+            "int32" => DataType::Int32,
+            "float32" => DataType::Float32,
+            "float64" => DataType::Float64,
+            "string" => DataType::String,
+            "bytes" => DataType::Bytes,
+            "bcd" => DataType::Bcd,
+            _ => match s.strip_prefix("date:") {
+                Some(format) => DataType::Date {
+                    format: format.parse()?,
+                },
+                None => return Err(format!("unknown data type '{}'", s)),
+            },
+        })
+    }
+}
+
+impl TryFrom<String> for DataType {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<DataType> for String {
+    fn from(data_type: DataType) -> Self {
+        data_type.to_string()
+    }
+}
+
+/// Digit layout of a packed BCD date
+///
+/// Two-digit years are taken as 2000–2099.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateFormat {
+    /// `YYYYMMDD` (4 bytes)
+    Yyyymmdd,
+    /// `YYMMDD` (3 bytes)
+    Yymmdd,
+    /// `DDMMYYYY` (4 bytes)
+    Ddmmyyyy,
+    /// `DDMMYY` (3 bytes)
+    Ddmmyy,
+}
+
+impl DateFormat {
+    /// Encoded size in bytes
+    pub fn byte_len(&self) -> usize {
+        if self.has_short_year() {
+            3
+        } else {
+            4
+        }
+    }
+
+    /// Whether the year is stored as two digits
+    pub fn has_short_year(&self) -> bool {
+        matches!(self, DateFormat::Yymmdd | DateFormat::Ddmmyy)
+    }
+
+    /// Whether the day comes first
+    pub fn is_day_first(&self) -> bool {
+        matches!(self, DateFormat::Ddmmyyyy | DateFormat::Ddmmyy)
+    }
+}
+
+impl std::fmt::Display for DateFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DateFormat::Yyyymmdd => "YYYYMMDD",
+            DateFormat::Yymmdd => "YYMMDD",
+            DateFormat::Ddmmyyyy => "DDMMYYYY",
+            DateFormat::Ddmmyy => "DDMMYY",
+        })
+    }
+}
+
+impl std::str::FromStr for DateFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "YYYYMMDD" => Ok(DateFormat::Yyyymmdd),
+            "YYMMDD" => Ok(DateFormat::Yymmdd),
+            "DDMMYYYY" => Ok(DateFormat::Ddmmyyyy),
+            "DDMMYY" => Ok(DateFormat::Ddmmyy),
+            _ => Err(format!(
+                "unknown date format '{}' (YYYYMMDD, YYMMDD, DDMMYYYY or DDMMYY)",
+                s
+            )),
+        }
+    }
+}
+
 impl DataType {
     /// Get the byte size for a single element of this type
-    /// Returns None for variable-length types (String, Bytes)
+    /// Returns None for types that are not array elements (String, Bytes,
+    /// Bcd, Date)
     pub fn byte_size(&self) -> Option<usize> {
         match self {
             DataType::Uint8 | DataType::Int8 => Some(1),
             DataType::Uint16 | DataType::Int16 => Some(2),
             DataType::Uint32 | DataType::Int32 | DataType::Float32 => Some(4),
             DataType::Float64 => Some(8),
-            DataType::String | DataType::Bytes => None,
+            DataType::String | DataType::Bytes | DataType::Bcd | DataType::Date { .. } => None,
         }
     }
 
@@ -71,6 +185,19 @@ impl DataType {
     pub fn is_float(&self) -> bool {
         matches!(self, DataType::Float32 | DataType::Float64)
     }
+
+    /// Largest raw value of an integer type, where counters saturate
+    pub fn max_value(&self) -> Option<f64> {
+        match self {
+            DataType::Uint8 => Some(u8::MAX as f64),
+            DataType::Uint16 => Some(u16::MAX as f64),
+            DataType::Uint32 => Some(u32::MAX as f64),
+            DataType::Int8 => Some(i8::MAX as f64),
+            DataType::Int16 => Some(i16::MAX as f64),
+            DataType::Int32 => Some(i32::MAX as f64),
+            _ => None,
+        }
+    }
 }
 
 /// Byte order for multi-byte values
@@ -84,6 +211,95 @@ pub enum ByteOrder {
     Little,
 }
 
+/// Character encoding of a `string` DID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StringEncoding {
+    /// UTF-8 (also plain ASCII payloads)
+    #[default]
+    Utf8,
+    /// UTF-16, big-endian code units
+    Utf16be,
+    /// UTF-16, little-endian code units
+    Utf16le,
+    /// ISO 8859-1, one byte per character
+    Latin1,
+    /// 7-bit ASCII
+    Ascii,
+}
+
+impl StringEncoding {
+    /// Whether this is the default UTF-8 (skipped when serializing)
+    pub fn is_utf8(&self) -> bool {
+        matches!(self, StringEncoding::Utf8)
+    }
+
+    /// Bytes per code unit; terminators and padding come in whole units
+    pub fn unit_len(&self) -> usize {
+        match self {
+            StringEncoding::Utf16be | StringEncoding::Utf16le => 2,
+            _ => 1,
+        }
+    }
+
+    /// Decode bytes, replacing anything undecodable with U+FFFD
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        match self {
+            StringEncoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+            StringEncoding::Utf16be | StringEncoding::Utf16le => {
+                let units: Vec<u16> = bytes
+                    .chunks(2)
+                    .map(|pair| match (self, pair) {
+                        (StringEncoding::Utf16be, &[hi, lo]) => u16::from_be_bytes([hi, lo]),
+                        (_, &[lo, hi]) => u16::from_le_bytes([lo, hi]),
+                        // Odd trailing byte
+                        _ => 0xFFFD,
+                    })
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            StringEncoding::Latin1 => bytes.iter().map(|&b| char::from(b)).collect(),
+            StringEncoding::Ascii => bytes
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii() {
+                        char::from(b)
+                    } else {
+                        '\u{FFFD}'
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Encode a string; `Err` carries the first character the encoding
+    /// can't represent
+    pub fn encode(&self, s: &str) -> Result<Vec<u8>, char> {
+        match self {
+            StringEncoding::Utf8 => Ok(s.as_bytes().to_vec()),
+            StringEncoding::Utf16be => Ok(s.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+            StringEncoding::Utf16le => Ok(s.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            StringEncoding::Latin1 => s.chars().map(|c| u8::try_from(c).map_err(|_| c)).collect(),
+            StringEncoding::Ascii => s
+                .chars()
+                .map(|c| if c.is_ascii() { Ok(c as u8) } else { Err(c) })
+                .collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for StringEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            StringEncoding::Utf8 => "utf8",
+            StringEncoding::Utf16be => "utf16be",
+            StringEncoding::Utf16le => "utf16le",
+            StringEncoding::Latin1 => "latin1",
+            StringEncoding::Ascii => "ascii",
+        })
+    }
+}
+
 /// Shape of the data (scalar, array, or matrix)
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -206,6 +422,23 @@ mod tests {
         assert_eq!(DataType::Float64.byte_size(), Some(8));
         assert_eq!(DataType::String.byte_size(), None);
         assert_eq!(DataType::Bytes.byte_size(), None);
+        assert_eq!(DataType::Bcd.byte_size(), None);
+    }
+
+    #[test]
+    fn test_data_type_names_round_trip() {
+        for name in ["uint16", "bcd", "date:YYYYMMDD", "date:YYMMDD"] {
+            let ty: DataType = serde_json::from_value(serde_json::json!(name)).unwrap();
+            assert_eq!(serde_json::to_value(ty).unwrap(), serde_json::json!(name));
+        }
+        assert_eq!(
+            "date:DDMMYY".parse::<DataType>(),
+            Ok(DataType::Date {
+                format: DateFormat::Ddmmyy
+            })
+        );
+        assert!("date:MMDDYY".parse::<DataType>().is_err());
+        assert!("uint12".parse::<DataType>().is_err());
     }
 
     #[test]
@@ -0,0 +1,160 @@
+//! Physical unit conversion
+//!
+//! A fixed registry of the units diagnostic definitions commonly carry,
+//! grouped by quantity. Every unit is a linear map onto its quantity's base
+//! unit (`base = value * factor + offset`), so any two units of the same
+//! quantity convert into each other; units of different quantities, or
+//! units the registry doesn't know, don't convert at all.
+
+use serde_json::Value;
+
+use crate::precision::to_json_number;
+
+/// Quantity a unit measures; only units of the same quantity convert
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantity {
+    Pressure,
+    Temperature,
+    Speed,
+    Length,
+    Volume,
+    Mass,
+    Time,
+    Voltage,
+    Current,
+    Power,
+    Torque,
+}
+
+/// `(symbol, quantity, factor, offset)` onto the quantity's base unit
+const UNITS: &[(&str, Quantity, f64, f64)] = &[
+    // Pressure, base Pa
+    ("Pa", Quantity::Pressure, 1.0, 0.0),
+    ("hPa", Quantity::Pressure, 100.0, 0.0),
+    ("kPa", Quantity::Pressure, 1_000.0, 0.0),
+    ("MPa", Quantity::Pressure, 1_000_000.0, 0.0),
+    ("mbar", Quantity::Pressure, 100.0, 0.0),
+    ("bar", Quantity::Pressure, 100_000.0, 0.0),
+    ("psi", Quantity::Pressure, 6_894.757_293_168, 0.0),
+    ("atm", Quantity::Pressure, 101_325.0, 0.0),
+    // Temperature, base K
+    ("K", Quantity::Temperature, 1.0, 0.0),
+    ("°C", Quantity::Temperature, 1.0, 273.15),
+    ("degC", Quantity::Temperature, 1.0, 273.15),
+    ("°F", Quantity::Temperature, 5.0 / 9.0, 459.67 * 5.0 / 9.0),
+    ("degF", Quantity::Temperature, 5.0 / 9.0, 459.67 * 5.0 / 9.0),
+    // Speed, base m/s
+    ("m/s", Quantity::Speed, 1.0, 0.0),
+    ("km/h", Quantity::Speed, 1.0 / 3.6, 0.0),
+    ("mph", Quantity::Speed, 0.447_04, 0.0),
+    ("kn", Quantity::Speed, 1_852.0 / 3_600.0, 0.0),
+    // Length, base m
+    ("mm", Quantity::Length, 0.001, 0.0),
+    ("cm", Quantity::Length, 0.01, 0.0),
+    ("m", Quantity::Length, 1.0, 0.0),
+    ("km", Quantity::Length, 1_000.0, 0.0),
+    ("mi", Quantity::Length, 1_609.344, 0.0),
+    // Volume, base l
+    ("ml", Quantity::Volume, 0.001, 0.0),
+    ("l", Quantity::Volume, 1.0, 0.0),
+    ("L", Quantity::Volume, 1.0, 0.0),
+    ("gal", Quantity::Volume, 3.785_411_784, 0.0),
+    // Mass, base kg
+    ("g", Quantity::Mass, 0.001, 0.0),
+    ("kg", Quantity::Mass, 1.0, 0.0),
+    ("lb", Quantity::Mass, 0.453_592_37, 0.0),
+    // Time, base s
+    ("ms", Quantity::Time, 0.001, 0.0),
+    ("s", Quantity::Time, 1.0, 0.0),
+    ("min", Quantity::Time, 60.0, 0.0),
+    ("h", Quantity::Time, 3_600.0, 0.0),
+    // Voltage, base V
+    ("mV", Quantity::Voltage, 0.001, 0.0),
+    ("V", Quantity::Voltage, 1.0, 0.0),
+    // Current, base A
+    ("mA", Quantity::Current, 0.001, 0.0),
+    ("A", Quantity::Current, 1.0, 0.0),
+    // Power, base W
+    ("W", Quantity::Power, 1.0, 0.0),
+    ("kW", Quantity::Power, 1_000.0, 0.0),
+    ("hp", Quantity::Power, 745.699_872, 0.0),
+    // Torque, base N·m
+    ("Nm", Quantity::Torque, 1.0, 0.0),
+    ("N·m", Quantity::Torque, 1.0, 0.0),
+    ("lbft", Quantity::Torque, 1.355_817_948_3, 0.0),
+];
+
+/// Decimal places kept after a conversion, enough for any display while
+/// dropping float noise like `2.5000000000000004`
+const CONVERTED_PRECISION: f64 = 0.000_001;
+
+fn lookup(unit: &str) -> Option<(Quantity, f64, f64)> {
+    UNITS
+        .iter()
+        .find(|(symbol, ..)| *symbol == unit.trim())
+        .map(|&(_, quantity, factor, offset)| (quantity, factor, offset))
+}
+
+/// Quantity `unit` measures, if the registry knows it
+pub fn quantity(unit: &str) -> Option<Quantity> {
+    lookup(unit).map(|(quantity, ..)| quantity)
+}
+
+/// Convert `value` from unit `from` to unit `to`
+///
+/// `None` when either unit is unknown or they measure different quantities.
+pub fn convert_unit(value: f64, from: &str, to: &str) -> Option<f64> {
+    let (from_quantity, from_factor, from_offset) = lookup(from)?;
+    let (to_quantity, to_factor, to_offset) = lookup(to)?;
+    if from_quantity != to_quantity {
+        return None;
+    }
+    Some((value * from_factor + from_offset - to_offset) / to_factor)
+}
+
+/// Convert a decoded JSON number from unit `from` to unit `to`
+///
+/// `None` when the value isn't a number or the units don't convert.
+pub fn convert_json(value: &Value, from: &str, to: &str) -> Option<Value> {
+    let converted = convert_unit(value.as_f64()?, from, to)?;
+    Some(to_json_number(converted, CONVERTED_PRECISION))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_pressure() {
+        assert_eq!(convert_json(&json!(250), "kPa", "bar"), Some(json!(2.5)));
+        assert_eq!(convert_json(&json!(1), "bar", "kPa"), Some(json!(100)));
+        assert_eq!(convert_json(&json!(1), "atm", "mbar"), Some(json!(1013.25)));
+    }
+
+    #[test]
+    fn test_temperature_offsets() {
+        assert_eq!(convert_json(&json!(100), "°C", "°F"), Some(json!(212)));
+        assert_eq!(convert_json(&json!(0), "°C", "K"), Some(json!(273.15)));
+        assert_eq!(convert_json(&json!(32), "degF", "degC"), Some(json!(0)));
+    }
+
+    #[test]
+    fn test_identity() {
+        assert_eq!(convert_json(&json!(42), "km/h", "km/h"), Some(json!(42)));
+        // An unknown unit doesn't even convert to itself
+        assert_eq!(convert_unit(42.0, "rpm", "rpm"), None);
+    }
+
+    #[test]
+    fn test_unsupported() {
+        // Different quantities
+        assert_eq!(convert_unit(1.0, "kPa", "°C"), None);
+        // Unknown unit
+        assert_eq!(convert_unit(1.0, "furlong", "m"), None);
+        // Not a number
+        assert_eq!(convert_json(&json!("P"), "kPa", "bar"), None);
+        assert_eq!(quantity("kPa"), Some(Quantity::Pressure));
+        assert_eq!(quantity("rpm"), None);
+    }
+}
@@ -1,7 +1,9 @@
 //! DiagnosticBackend trait - the core abstraction for SOVD backends
 
+use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -13,10 +15,11 @@ use tokio::sync::broadcast;
 use crate::error::BackendResult;
 use crate::models::{
     BulkCategory, BulkDataDownload, BulkDataFilter, BulkDataItem, Capabilities, ClearFaultsResult,
-    CommControlMode, DataPoint, DataValue, DtcSettingMode, EntityInfo, Fault, FaultFilter,
-    FaultsResult, IoControlAction, IoControlResult, LinkControlResult, LinkMode, LogEntry,
-    LogFilter, LogPage, OperationExecution, OperationInfo, OutputDetail, OutputInfo, ParameterInfo,
-    SecurityMode, SessionMode,
+    CommControlMode, DataPoint, DataValue, DtcSettingMode, EntityInfo, Fault, FaultExtendedData,
+    FaultFilter, FaultSnapshot, FaultsResult, Fingerprint, HealthStatus, IoControlAction,
+    IoControlResult, LinkControlResult, LinkMode, LogEntry, LogFilter, LogPage, OperationExecution,
+    OperationInfo, OutputDetail, OutputInfo, ParameterInfo, SecurityMode, SessionMode,
+    TopologyNode, TransportMetrics, TransportStats,
 };
 
 /// Byte stream for streaming package upload (HTTP/1.1 chunked transfer).
@@ -140,6 +143,65 @@ pub struct FlashStatus {
     /// Error message (if failed)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Outcome record, set once the data transfer has completed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<FlashSummary>,
+}
+
+/// Summary of a completed flash transfer, for one-line ops reporting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlashSummary {
+    /// Payload bytes transferred
+    pub bytes_transferred: u64,
+    /// Number of TransferData blocks sent
+    pub blocks_transferred: u32,
+    /// Wall-clock time from RequestDownload to the last block
+    pub duration_ms: u64,
+    /// Average throughput over `duration_ms`
+    pub throughput_bytes_per_sec: f64,
+    /// Requests that had to be repeated (e.g. after a transparent unlock)
+    pub retries: u32,
+    /// Software version read back from the ECU once the image is committed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_version: Option<String>,
+    /// The run was a dry run: nothing was written to the ECU
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    /// Block plan negotiated by RequestDownload (dry runs only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<FlashPlan>,
+}
+
+/// TransferData plan a flash would follow, as negotiated with the ECU
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlashPlan {
+    /// Payload bytes to transfer
+    pub bytes_total: u64,
+    /// Number of TransferData blocks
+    pub block_count: u32,
+    /// Payload bytes carried per block
+    pub block_size: u32,
+}
+
+impl FlashSummary {
+    /// Build a summary, deriving throughput from bytes and duration
+    pub fn new(bytes_transferred: u64, blocks_transferred: u32, duration: Duration) -> Self {
+        let secs = duration.as_secs_f64();
+        Self {
+            bytes_transferred,
+            blocks_transferred,
+            duration_ms: duration.as_millis() as u64,
+            throughput_bytes_per_sec: if secs > 0.0 {
+                bytes_transferred as f64 / secs
+            } else {
+                0.0
+            },
+            retries: 0,
+            verified_version: None,
+            dry_run: false,
+            plan: None,
+        }
+    }
 }
 
 /// State of a flash transfer.
@@ -388,6 +450,27 @@ pub trait DiagnosticBackend: Send + Sync {
     /// Read one or more data parameters
     async fn read_data(&self, param_ids: &[String]) -> BackendResult<Vec<DataValue>>;
 
+    /// Read several data parameters, one result per id in request order.
+    /// Unlike [`Self::read_data`] a parameter that fails does not fail the
+    /// others. The default reads them one at a time.
+    async fn read_parameters_batch(
+        &self,
+        ids: &[String],
+    ) -> Vec<(String, BackendResult<DataValue>)> {
+        let mut results = Vec::with_capacity(ids.len());
+        for id in ids {
+            let value = match self.read_data(std::slice::from_ref(id)).await {
+                Ok(values) => values
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| crate::error::BackendError::ParameterNotFound(id.clone())),
+                Err(e) => Err(e),
+            };
+            results.push((id.clone(), value));
+        }
+        results
+    }
+
     /// Write a data parameter (if supported)
     async fn write_data(&self, param_id: &str, value: &[u8]) -> BackendResult<()> {
         let _ = (param_id, value);
@@ -404,6 +487,16 @@ pub trait DiagnosticBackend: Send + Sync {
         ))
     }
 
+    /// Read raw bytes from a DID the ECU only serves through
+    /// ReadDataByPeriodicIdentifier (0x2A): one periodic transmission is
+    /// started, its first sample returned, and the transmission stopped
+    async fn read_raw_did_periodic(&self, did: u16) -> BackendResult<Vec<u8>> {
+        let _ = did;
+        Err(crate::error::BackendError::NotSupported(
+            "read_raw_did_periodic".to_string(),
+        ))
+    }
+
     /// Write raw bytes to a DID (for dynamic/generic access)
     async fn write_raw_did(&self, did: u16, data: &[u8]) -> BackendResult<()> {
         let _ = (did, data);
@@ -412,6 +505,86 @@ pub trait DiagnosticBackend: Send + Sync {
         ))
     }
 
+    /// Read the entity's identification block (VIN, part number, software
+    /// version, ...), one value per field with the field name as `id`
+    /// (`vin`, `part_number`, ...). `fields` limits which fields are read;
+    /// `None` reads all of them. Fields the entity does not support are left
+    /// out rather than failing the read.
+    async fn read_identification(
+        &self,
+        fields: Option<&[String]>,
+    ) -> BackendResult<Vec<DataValue>> {
+        let _ = fields;
+        Err(crate::error::BackendError::NotSupported(
+            "read_identification".to_string(),
+        ))
+    }
+
+    /// Read the software fingerprints (boot software, application software,
+    /// application data), decoded where the entity knows their layout.
+    /// Fingerprints the entity does not carry are left out.
+    async fn read_fingerprints(&self) -> BackendResult<Vec<Fingerprint>> {
+        Err(crate::error::BackendError::NotSupported(
+            "read_fingerprints".to_string(),
+        ))
+    }
+
+    /// Link-level view of the transport the entity is reached over
+    async fn transport_stats(&self) -> BackendResult<TransportStats> {
+        Err(crate::error::BackendError::NotSupported(
+            "transport_stats".to_string(),
+        ))
+    }
+
+    /// Traffic counters of the transport the entity is reached over
+    async fn transport_metrics(&self) -> BackendResult<TransportMetrics> {
+        Err(crate::error::BackendError::NotSupported(
+            "transport_metrics".to_string(),
+        ))
+    }
+
+    /// Probe whether the backend's target is reachable, cheaply enough to
+    /// poll. Default: reachable (nothing outside the process to probe).
+    async fn health_check(&self) -> BackendResult<HealthStatus> {
+        Ok(HealthStatus::reachable())
+    }
+
+    /// Read `size` bytes of raw memory starting at `address` (UDS
+    /// ReadMemoryByAddress 0x23 on a UDS ECU), for regions such as
+    /// calibration data that are not exposed as data identifiers
+    async fn read_memory(&self, address: u64, size: u32) -> BackendResult<Vec<u8>> {
+        let _ = (address, size);
+        Err(crate::error::BackendError::NotSupported(
+            "read_memory".to_string(),
+        ))
+    }
+
+    /// Write `data` to raw memory starting at `address` (UDS
+    /// WriteMemoryByAddress 0x3D on a UDS ECU)
+    async fn write_memory(&self, address: u64, data: &[u8]) -> BackendResult<()> {
+        let _ = (address, data);
+        Err(crate::error::BackendError::NotSupported(
+            "write_memory".to_string(),
+        ))
+    }
+
+    /// Read `size` bytes of memory out of the ECU as a transfer (UDS
+    /// RequestUpload 0x35, TransferData 0x36 until `size` bytes are in,
+    /// RequestTransferExit 0x37), for regions such as crash logs that are
+    /// too large for one [`read_memory`](Self::read_memory). `data_format`
+    /// is the dataFormatIdentifier (0x00: neither compressed nor encrypted).
+    async fn upload_memory(
+        &self,
+        address: u64,
+        size: u32,
+        data_format: u8,
+    ) -> BackendResult<Vec<u8>> {
+        let _ = (address, size, data_format);
+        Err(crate::error::BackendError::NotSupported(
+            "upload_memory".to_string(),
+        ))
+    }
+
     /// Define a dynamic data identifier (DDID)
     /// Sources are tuples of (source_did, position, size)
     async fn define_data_identifier(
@@ -425,6 +598,19 @@ pub trait DiagnosticBackend: Send + Sync {
         ))
     }
 
+    /// Define a dynamic data identifier (DDID) from raw memory regions
+    /// `(address, size)`, concatenated in order (UDS 0x2C 0x02 on a UDS ECU)
+    async fn define_data_identifier_by_memory(
+        &self,
+        ddid: u16,
+        regions: &[(u64, u32)],
+    ) -> BackendResult<()> {
+        let _ = (ddid, regions);
+        Err(crate::error::BackendError::NotSupported(
+            "define_data_identifier_by_memory".to_string(),
+        ))
+    }
+
     /// Clear a dynamic data identifier
     async fn clear_data_identifier(&self, ddid: u16) -> BackendResult<()> {
         let _ = ddid;
@@ -481,6 +667,40 @@ pub trait DiagnosticBackend: Send + Sync {
             .ok_or_else(|| crate::error::BackendError::EntityNotFound(fault_id.to_string()))
     }
 
+    /// Freeze-frame (snapshot) records stored with a fault. Records hold
+    /// DID values back to back, so `did_lengths` gives the byte length of
+    /// each data identifier the caller knows how to size.
+    async fn get_fault_snapshots(
+        &self,
+        _fault_id: &str,
+        _did_lengths: &HashMap<u16, usize>,
+    ) -> BackendResult<Vec<FaultSnapshot>> {
+        Err(crate::error::BackendError::NotSupported(
+            "get_fault_snapshots".to_string(),
+        ))
+    }
+
+    /// Extended data records stored with a fault; `record_number` 0xFF
+    /// requests all of them
+    async fn get_fault_extended_data(
+        &self,
+        _fault_id: &str,
+        _record_number: u8,
+    ) -> BackendResult<Vec<FaultExtendedData>> {
+        Err(crate::error::BackendError::NotSupported(
+            "get_fault_extended_data".to_string(),
+        ))
+    }
+
+    /// Status bits this entity actually supports in fault status bytes
+    /// (UDS DTCStatusAvailabilityMask). Bits outside the mask carry no
+    /// information and should not be interpreted.
+    async fn get_dtc_status_availability_mask(&self) -> BackendResult<u8> {
+        Err(crate::error::BackendError::NotSupported(
+            "get_dtc_status_availability_mask".to_string(),
+        ))
+    }
+
     /// Clear faults (if supported)
     async fn clear_faults(&self, _group: Option<u32>) -> BackendResult<ClearFaultsResult> {
         Err(crate::error::BackendError::NotSupported(
@@ -607,6 +827,32 @@ pub trait DiagnosticBackend: Send + Sync {
         params: &[u8],
     ) -> BackendResult<OperationExecution>;
 
+    /// Encode named routine parameters into the option record that
+    /// `start_operation` sends after the sub-function byte
+    ///
+    /// Returns `InvalidRequest` when `params` does not match the operation's
+    /// parameter layout.
+    async fn encode_operation_params(
+        &self,
+        operation_id: &str,
+        params: &serde_json::Value,
+    ) -> BackendResult<Vec<u8>> {
+        let _ = (operation_id, params);
+        Err(crate::error::BackendError::NotSupported(
+            "encode_operation_params".to_string(),
+        ))
+    }
+
+    /// Check the session and security preconditions of an operation
+    ///
+    /// Returns `SessionRequired` or `SecurityRequired` for the first one
+    /// not met.  Lets the API refuse an execution up front instead of
+    /// failing the one it already accepted.
+    async fn check_operation_preconditions(&self, operation_id: &str) -> BackendResult<()> {
+        let _ = operation_id;
+        Ok(())
+    }
+
     /// Get status of a running operation
     async fn get_operation_status(&self, execution_id: &str) -> BackendResult<OperationExecution> {
         let _ = execution_id;
@@ -640,6 +886,21 @@ pub trait DiagnosticBackend: Send + Sync {
         ))
     }
 
+    /// Check a short-term adjustment value before the output is controlled
+    ///
+    /// Returns the value to send, which may differ from `value` (e.g.
+    /// clamped to the output's range), or `InvalidRequest` if it must not be
+    /// sent at all.  Lets the API refuse a request up front instead of
+    /// failing the execution it already accepted.
+    async fn check_output_value(
+        &self,
+        output_id: &str,
+        value: &serde_json::Value,
+    ) -> BackendResult<serde_json::Value> {
+        let _ = output_id;
+        Ok(value.clone())
+    }
+
     /// Control an output (UDS 0x2F)
     ///
     /// The `value` parameter carries the original JSON value from the API
@@ -672,6 +933,27 @@ pub trait DiagnosticBackend: Send + Sync {
         Err(crate::error::BackendError::EntityNotFound(id.to_string()))
     }
 
+    /// This entity's place in the vehicle topology: its transport, address
+    /// and reachability, with its sub-entities as children. Gateways
+    /// override this to nest the backends they federate.
+    async fn topology(&self) -> BackendResult<TopologyNode> {
+        let mut node = TopologyNode::from_entity(self.entity_info());
+        if let Ok(stats) = self.transport_stats().await {
+            node.transport = Some(stats.kind);
+            node.address = stats.address;
+        }
+        node.reachable = self.health_check().await.ok().map(|h| h.reachable);
+        for info in self.list_sub_entities().await.unwrap_or_default() {
+            let child = match self.get_sub_entity(&info.id).await {
+                Ok(sub) => sub.topology().await.ok(),
+                Err(_) => None,
+            };
+            let child = child.unwrap_or_else(|| TopologyNode::from_entity(&info));
+            node.children.push(child.listed_as(&info));
+        }
+        Ok(node)
+    }
+
     // =========================================================================
     // Software Information
     // =========================================================================
@@ -812,6 +1094,20 @@ pub trait DiagnosticBackend: Send + Sync {
         ))
     }
 
+    /// Start a dry-run flash transfer.
+    ///
+    /// Runs the same flow as `start_flash` — package selection, session and
+    /// security setup, RequestDownload negotiation — but sends no
+    /// TransferData. The transfer ends `Complete` with a `FlashSummary`
+    /// flagged `dry_run` that carries the negotiated `FlashPlan`.
+    ///
+    /// Returns the transfer ID for monitoring progress.
+    async fn start_flash_dry_run(&self) -> BackendResult<String> {
+        Err(crate::error::BackendError::NotSupported(
+            "start_flash_dry_run".to_string(),
+        ))
+    }
+
     /// Describe the update shape so the `/updates` execute path can
     /// order Banked-vs-Singleshot finalize/commit correctly per ISO 17978-3
     /// §7.13 (and `tasks/sw-update-architecture.md` §5).
@@ -983,6 +1279,14 @@ pub trait DiagnosticBackend: Send + Sync {
         ))
     }
 
+    /// Counter bumped whenever the ECU may have left or re-entered its
+    /// session: a session change, a reset, or a lapsed S3 timer.  Values
+    /// read in one epoch may not hold in the next.  `None` (default) when
+    /// the backend does not track one.
+    fn session_epoch(&self) -> Option<u64> {
+        None
+    }
+
     /// Get current communication-control mode (UDS CommunicationControl 0x28).
     ///
     /// 0x28 is write-only on the wire — there is no UDS read for the active
@@ -1058,6 +1362,9 @@ pub trait DiagnosticBackend: Send + Sync {
 
     /// Control link baud rate
     /// - action: "verify_fixed", "verify_specific", or "transition"
+    /// - action "switch" runs the whole handshake: verify (fixed when
+    ///   `baud_rate_id` is given, else specific), transition, then moves
+    ///   the bus to the new rate; a refused verify transitions nothing
     async fn set_link_mode(
         &self,
         action: &str,
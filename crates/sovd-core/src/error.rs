@@ -6,7 +6,7 @@ use thiserror::Error;
 pub type BackendResult<T> = Result<T, BackendError>;
 
 /// Errors that can occur in diagnostic backends
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum BackendError {
     /// Entity (component) not found
     #[error("Entity not found: {0}")]
@@ -32,6 +32,11 @@ pub enum BackendError {
     #[error("Session change required: {0}")]
     SessionRequired(String),
 
+    /// Refused by server-side policy (e.g. a UDS service denied for this
+    /// ECU) without reaching the ECU
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     /// Operation not supported by this backend
     #[error("Operation not supported: {0}")]
     NotSupported(String),
@@ -55,6 +60,11 @@ pub enum BackendError {
     #[error("Rate limited: {0}")]
     RateLimited(String),
 
+    /// Security access locked out after too many invalid keys (or the ECU's
+    /// NRC 0x36/0x37); retry once `remaining_ms` has passed
+    #[error("Security access locked out, retry in {remaining_ms} ms")]
+    SecurityLockout { remaining_ms: u64 },
+
     /// Transport/communication error
     #[error("Transport error: {0}")]
     Transport(String),
@@ -107,10 +117,12 @@ impl BackendError {
             BackendError::OutputNotFound(_) => 404,
             BackendError::SecurityRequired(_) => 403,
             BackendError::SessionRequired(_) => 409,
+            BackendError::Forbidden(_) => 403,
             BackendError::NotSupported(_) => 501,
             BackendError::Protocol(_) => 502,
             BackendError::EcuError { nrc, .. } => nrc_to_status(*nrc),
             BackendError::RateLimited(_) => 429,
+            BackendError::SecurityLockout { .. } => 429,
             BackendError::Transport(_) => 503,
             BackendError::InvalidRequest(_) => 400,
             BackendError::Timeout => 504,
@@ -6,13 +6,15 @@
 pub mod backend;
 pub mod error;
 pub mod models;
+pub mod nrc;
 pub mod routing;
 
 pub use backend::{
     default_descriptor_from_context, ActivationState, DiagnosticBackend, EntityStatus,
-    EntityStatusBody, FlashProgress, FlashState, FlashStatus, PackageInfo, PackageStatus,
-    PackageStream, ResetKind, SoftwareInfo, UpdatePackageContext, UpdatePackageDescriptor,
-    UpdatePartRef, VerifyResult,
+    EntityStatusBody, FlashPlan, FlashProgress, FlashState, FlashStatus, FlashSummary, PackageInfo,
+    PackageStatus, PackageStream, ResetKind, SoftwareInfo, UpdatePackageContext,
+    UpdatePackageDescriptor, UpdatePartRef, VerifyResult,
 };
 pub use error::{BackendError, BackendResult};
 pub use models::*;
+pub use nrc::NegativeResponseCode;
@@ -203,6 +203,48 @@ pub struct DataPoint {
     pub timestamp: DateTime<Utc>,
 }
 
+impl DataPoint {
+    /// `id` of the marker a backend puts into a subscription stream when
+    /// delivery resumes after an interruption, such as an ECU reset
+    pub const RESUMED: &'static str = "x-sumo-resumed";
+
+    /// Marker that delivery resumed after an interruption; values sampled
+    /// in the gap are lost
+    pub fn resumed() -> Self {
+        Self {
+            id: Self::RESUMED.to_string(),
+            value: serde_json::Value::Null,
+            unit: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Whether this is the [`Self::resumed`] marker rather than a sample
+    pub fn is_resumed(&self) -> bool {
+        self.id == Self::RESUMED
+    }
+}
+
+/// A software fingerprint: who programmed a logical block, and when
+/// (UDS DIDs 0xF183–0xF185)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    /// `boot_software`, `application_software` or `application_data`
+    #[serde(rename = "type")]
+    pub fingerprint_type: String,
+    /// DID identifier in hex
+    pub did: String,
+    /// Programming date (`YYYY-MM-DD`); absent when the format is unknown
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub date: Option<String>,
+    /// Serial number of the tester that programmed the block; absent when
+    /// the format is unknown
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub tester_serial: Option<String>,
+    /// Raw hex-encoded bytes
+    pub raw: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -20,6 +20,12 @@ pub struct EntityInfo {
     /// Current status (e.g., "running", "stopped")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
+    /// Friendly name for service UIs (e.g., "Engine ECU"); the id is unaffected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Grouping for service UIs (e.g., "powertrain")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
 }
 
 /// Capabilities of a diagnostic entity
@@ -54,6 +60,10 @@ pub struct Capabilities {
     /// capability doc from an older server (no field) deserializes as `false`.
     #[serde(default)]
     pub bulk_data: bool,
+    /// ReadDTCInformation reports the ECU implements (`status-mask`,
+    /// `severity-mask`, `user-def-memory`); empty when not declared
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dtc_reports: Vec<String>,
 }
 
 impl Capabilities {
@@ -73,6 +83,7 @@ impl Capabilities {
             sub_entities: false,
             subscriptions: true,
             bulk_data: false,
+            dtc_reports: Vec::new(),
         }
     }
 
@@ -94,6 +105,7 @@ impl Capabilities {
             // Logs are retrieved via bulk-data (§7.21/C-121), so an HPC that has
             // logs also exposes the bulk-data collection.
             bulk_data: true,
+            dtc_reports: Vec::new(),
         }
     }
 
@@ -113,6 +125,7 @@ impl Capabilities {
             sub_entities: false,
             subscriptions: true,
             bulk_data: true,
+            dtc_reports: Vec::new(),
         }
     }
 
@@ -132,6 +145,7 @@ impl Capabilities {
             sub_entities: true, // Gateway always has sub-entities
             subscriptions: false,
             bulk_data: false,
+            dtc_reports: Vec::new(),
         }
     }
 }
@@ -94,6 +94,30 @@ pub struct FaultFilter {
     /// Maximum number of faults to return
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<usize>,
+    /// Fault memory to read (primary when unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<FaultMemory>,
+    /// DTC status bits (ISO 14229-1 statusOfDTC); a fault matches when any
+    /// of them is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_mask: Option<u8>,
+    /// DTCSeverity bits; a fault matches when the severity the ECU reported
+    /// for it has any of them set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dtc_severity_mask: Option<u8>,
+}
+
+/// Fault memory area to read faults from (ISO 14229-1 DTC memories).
+///
+/// `Primary` is the ECU's regular DTC memory. `Mirror` is the secondary
+/// memory that keeps entries after a clear; UDS ECUs expose it as a
+/// user-defined memory selected by a manufacturer-specific byte.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultMemory {
+    #[default]
+    Primary,
+    Mirror,
 }
 
 /// Result of clearing faults
@@ -105,6 +129,19 @@ pub struct ClearFaultsResult {
     pub cleared_count: u32,
     /// Message describing the result
     pub message: String,
+    /// Re-read of the fault memory after the clear, when the backend
+    /// verifies clears
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub verification: Option<ClearVerification>,
+}
+
+/// Outcome of re-reading the fault memory after a clear
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClearVerification {
+    /// No fault of the cleared group was found on the re-read
+    pub cleared_confirmed: bool,
+    /// Faults of the cleared group still stored
+    pub remaining: Vec<Fault>,
 }
 
 /// Result of getting faults (includes metadata)
@@ -115,3 +152,37 @@ pub struct FaultsResult {
     /// Status availability mask (UDS-specific, indicates which status bits are supported)
     pub status_availability_mask: Option<u8>,
 }
+
+/// A freeze-frame (snapshot) record stored with a fault
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultSnapshot {
+    /// Snapshot record number (UDS DTCSnapshotRecordNumber)
+    pub record_number: u8,
+    /// Data identifiers captured in the record, in the order stored
+    pub items: Vec<FaultSnapshotItem>,
+}
+
+/// One data identifier captured in a snapshot record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultSnapshotItem {
+    /// Data identifier (DID)
+    pub did: u16,
+    /// Raw value bytes
+    pub data: Vec<u8>,
+}
+
+/// An extended data record stored with a fault (occurrence counter, aging
+/// counter, OEM-specific data, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultExtendedData {
+    /// Extended data record number (UDS DTCExtDataRecordNumber)
+    pub record_number: u8,
+    /// Name of the record, when the backend knows its meaning
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Record interpreted as an unsigned counter, when it is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<u64>,
+    /// Raw record bytes
+    pub data: Vec<u8>,
+}
@@ -0,0 +1,53 @@
+//! Backend health models
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a cheap liveness probe of whatever a backend talks to (the
+/// ECU, an upstream server), telling "target asleep" apart from "server
+/// broken"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthStatus {
+    /// Whether the target answered the probe
+    pub reachable: bool,
+    /// Round-trip time of the probe, if one was sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Why the target is considered unreachable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Probes of the sub-entities behind the target (a gateway's ECUs), by
+    /// sub-entity id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub children: BTreeMap<String, HealthStatus>,
+}
+
+impl HealthStatus {
+    /// Target answered
+    pub fn reachable() -> Self {
+        Self {
+            reachable: true,
+            latency_ms: None,
+            detail: None,
+            children: BTreeMap::new(),
+        }
+    }
+
+    /// Target did not answer, for `detail`
+    pub fn unreachable(detail: impl Into<String>) -> Self {
+        Self {
+            reachable: false,
+            latency_ms: None,
+            detail: Some(detail.into()),
+            children: BTreeMap::new(),
+        }
+    }
+
+    /// Record the probe's round-trip time
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency_ms = Some(latency.as_millis() as u64);
+        self
+    }
+}
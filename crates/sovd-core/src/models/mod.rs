@@ -5,17 +5,23 @@ mod data;
 mod entity;
 pub mod error;
 mod fault;
+mod health;
 mod log;
 mod mode;
 mod operation;
 mod output;
+mod topology;
+mod transport;
 
 pub use bulk_data::*;
 pub use data::*;
 pub use entity::*;
 pub use error::{error_code, DataError, GenericError};
 pub use fault::*;
+pub use health::*;
 pub use log::*;
 pub use mode::*;
 pub use operation::*;
 pub use output::*;
+pub use topology::*;
+pub use transport::*;
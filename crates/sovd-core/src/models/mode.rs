@@ -11,6 +11,18 @@ pub struct SessionMode {
     pub session: String,
     /// Current session UDS ID
     pub session_id: u8,
+    /// P2/P2* the ECU advertised for this session, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<SessionTimingParameters>,
+}
+
+/// Server timing advertised in a DiagnosticSessionControl (0x10) response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionTimingParameters {
+    /// P2_server_max in milliseconds
+    pub p2_server_max_ms: u32,
+    /// P2*_server_max in milliseconds
+    pub p2_star_server_max_ms: u32,
 }
 
 /// Security access state
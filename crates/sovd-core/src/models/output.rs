@@ -1,5 +1,6 @@
 //! I/O control output models (UDS 0x2F)
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Information about an I/O output
@@ -69,6 +70,21 @@ pub struct OutputDetail {
     /// Allowed string values for enum-like outputs
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub allowed: Vec<String>,
+    /// Last I/O control action the ECU accepted for this output (e.g.
+    /// `"short_term_adjust"`); absent when the tester never controlled it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_action: Option<String>,
+    /// Typed value commanded by the last short-term adjustment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_value: Option<serde_json::Value>,
+    /// When the last I/O control action was accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commanded_at: Option<DateTime<Utc>>,
+    /// The session the tester took control in has since ended (session
+    /// change, S3 timeout, ECU reset), which handed the output back to the
+    /// ECU; `controlled_by_tester` and `frozen` already account for it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub session_reset: bool,
 }
 
 /// I/O control action types
@@ -131,6 +147,9 @@ pub struct IoControlResult {
     /// Typed new value (decoded from raw bytes using type metadata)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<serde_json::Value>,
+    /// Raw control state sent with a short-term adjustment (hex string)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_sent: Option<String>,
     /// Error message if failed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
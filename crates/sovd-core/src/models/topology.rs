@@ -0,0 +1,71 @@
+//! Vehicle topology models
+
+use serde::{Deserialize, Serialize};
+
+use super::EntityInfo;
+
+/// One node of the vehicle's E/E topology as a server sees it: a gateway,
+/// an ECU or proxied server behind it, or one of their sub-entities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopologyNode {
+    /// Entity identifier
+    pub id: String,
+    /// Human-readable name
+    pub name: String,
+    /// Entity type (gateway, ecu, app, etc.)
+    #[serde(rename = "type")]
+    pub entity_type: String,
+    /// Link to this entity's resources
+    pub href: String,
+    /// Friendly name for service UIs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Grouping for service UIs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Transport kind the entity is reached over (`socketcan`, `doip`, ...);
+    /// absent when it has no transport of its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<String>,
+    /// Where the entity sits on that transport
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Whether the entity answered its health probe; absent when the probe
+    /// itself failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reachable: Option<bool>,
+    /// Entities behind this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TopologyNode>,
+}
+
+impl TopologyNode {
+    /// Node for `info`, with nothing known about its link yet
+    pub fn from_entity(info: &EntityInfo) -> Self {
+        Self {
+            id: info.id.clone(),
+            name: info.name.clone(),
+            entity_type: info.entity_type.clone(),
+            href: info.href.clone(),
+            display_name: info.display_name.clone(),
+            category: info.category.clone(),
+            transport: None,
+            address: None,
+            reachable: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Take the href and naming the parent lists this entity under, which
+    /// may differ from what the entity reports for itself
+    pub fn listed_as(mut self, info: &EntityInfo) -> Self {
+        self.href = info.href.clone();
+        if info.display_name.is_some() {
+            self.display_name = info.display_name.clone();
+        }
+        if info.category.is_some() {
+            self.category = info.category.clone();
+        }
+        self
+    }
+}
@@ -0,0 +1,88 @@
+//! Transport link models
+
+use serde::{Deserialize, Serialize};
+
+/// Link-level view of the transport an entity is reached over, for tuning
+/// and diagnosing transfers (e.g. why a download uses a given block size)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransportStats {
+    /// Transport kind: `socketcan`, `doip`, `mock`, `replay` or a custom kind
+    pub kind: String,
+    /// Where the entity sits on that transport, e.g. `can0 0x7E0/0x7E8`
+    /// (interface, TX/RX CAN IDs) or `10.0.0.2:13400 0x0E00` (DoIP entity,
+    /// logical address)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// ISO-TP TX data length in effect: payload bytes per CAN frame, 8 on
+    /// classic CAN
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_dl: Option<u8>,
+    /// Whether frames are sent as CAN FD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_fd: Option<bool>,
+    /// Largest UDS message one request can carry, if the link limits it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_message_len: Option<usize>,
+    /// ISO-TP block size advertised in our flow control (0 = no limit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_size: Option<u8>,
+    /// ISO-TP separation time (STmin) advertised in our flow control, in
+    /// microseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub st_min_us: Option<u32>,
+}
+
+/// Upper bounds (milliseconds) of the round-trip latency histogram buckets
+pub const LATENCY_BUCKETS_MS: [u64; 12] = [1, 2, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+/// Counters of the traffic a transport carried since it was created, for
+/// correlating slow requests with the link's ISO-TP settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransportMetrics {
+    /// Requests sent expecting a response
+    pub requests: u64,
+    /// Requests that got no response in time
+    pub timeouts: u64,
+    /// Requests that failed on the transport other than by timing out
+    pub errors: u64,
+    /// Requests re-sent after the identical previous request timed out
+    pub retransmits: u64,
+    /// UDS messages sent, with or without an expected response
+    pub messages_sent: u64,
+    /// UDS responses received
+    pub messages_received: u64,
+    /// Link frames sent. On ISO-TP links these are derived from message
+    /// lengths and the link settings (the kernel does the segmentation):
+    /// single/first/consecutive frames plus the flow control frames we send
+    /// for multi-frame responses. Other links count one frame per message.
+    pub frames_sent: u64,
+    /// Link frames received, derived like `frames_sent`
+    pub frames_received: u64,
+    /// Time (microseconds) the STmin in our flow control makes the ECU wait
+    /// between consecutive frames of its responses — a lower bound on the
+    /// flow-control share of response latency
+    pub flow_control_wait_us: u64,
+    /// Round-trip latency of answered requests
+    pub latency: LatencyHistogram,
+}
+
+/// Round-trip latency distribution
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyHistogram {
+    /// Cumulative counts per [`LATENCY_BUCKETS_MS`] upper bound; a latency
+    /// above the last bound is only in `count`
+    pub buckets: Vec<LatencyBucket>,
+    /// Sum of all observed latencies, in milliseconds
+    pub sum_ms: f64,
+    /// Number of observed latencies
+    pub count: u64,
+}
+
+/// One histogram bucket: the requests answered within `le_ms`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencyBucket {
+    /// Upper bound, in milliseconds
+    pub le_ms: u64,
+    /// Requests answered within `le_ms`
+    pub count: u64,
+}
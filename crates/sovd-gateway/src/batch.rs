@@ -0,0 +1,117 @@
+//! Read batching window
+//!
+//! A dashboard loading through the gateway fires dozens of near-simultaneous
+//! reads, each a separate bus transaction. With a batching window, reads for
+//! the same backend that arrive within the window are coalesced into one
+//! [`DiagnosticBackend::read_parameters_batch`] call — a UDS backend packs
+//! those into multi-DID 0x22 requests (`data_read.max_dids_per_request`) —
+//! and the results are handed back to each waiting read.
+//!
+//! The first read of a batch pays the window as extra latency, so it should
+//! stay in the low milliseconds.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use sovd_core::{BackendError, BackendResult, DataValue, DiagnosticBackend};
+use tokio::sync::oneshot;
+use tracing::debug;
+
+/// A read waiting for its batch to be flushed
+struct Waiter {
+    ids: Vec<String>,
+    reply: oneshot::Sender<BackendResult<Vec<DataValue>>>,
+}
+
+/// Coalesces concurrent reads per backend
+pub(crate) struct ReadBatcher {
+    window: Duration,
+    /// Open batches by backend ID
+    pending: Arc<Mutex<HashMap<String, Vec<Waiter>>>>,
+}
+
+impl ReadBatcher {
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Read `ids` from `backend` as part of its open batch
+    ///
+    /// Same all-or-nothing result as [`DiagnosticBackend::read_data`].
+    pub(crate) async fn read(
+        &self,
+        backend_id: &str,
+        backend: &Arc<dyn DiagnosticBackend>,
+        ids: Vec<String>,
+    ) -> BackendResult<Vec<DataValue>> {
+        let (reply, rx) = oneshot::channel();
+        let opens_batch = {
+            let mut pending = self.pending.lock().unwrap();
+            let waiters = pending.entry(backend_id.to_string()).or_default();
+            waiters.push(Waiter { ids, reply });
+            waiters.len() == 1
+        };
+
+        // The flush runs detached so a cancelled first read can't strand
+        // the reads that joined its batch
+        if opens_batch {
+            let pending = self.pending.clone();
+            let backend = backend.clone();
+            let backend_id = backend_id.to_string();
+            let window = self.window;
+            tokio::spawn(async move {
+                tokio::time::sleep(window).await;
+                let waiters = pending
+                    .lock()
+                    .unwrap()
+                    .remove(&backend_id)
+                    .unwrap_or_default();
+                flush(&backend_id, backend.as_ref(), waiters).await;
+            });
+        }
+
+        rx.await
+            .unwrap_or_else(|_| Err(BackendError::Internal("read batch dropped".to_string())))
+    }
+}
+
+/// Issue one batch read for all waiters and answer each of them
+async fn flush(backend_id: &str, backend: &dyn DiagnosticBackend, waiters: Vec<Waiter>) {
+    let mut ids: Vec<String> = Vec::new();
+    for id in waiters.iter().flat_map(|w| &w.ids) {
+        if !ids.contains(id) {
+            ids.push(id.clone());
+        }
+    }
+    debug!(
+        backend_id = %backend_id,
+        reads = waiters.len(),
+        params = ids.len(),
+        "Flushing coalesced reads"
+    );
+
+    let results: HashMap<String, BackendResult<DataValue>> = backend
+        .read_parameters_batch(&ids)
+        .await
+        .into_iter()
+        .collect();
+
+    for waiter in waiters {
+        let values = waiter
+            .ids
+            .iter()
+            .map(|id| {
+                results
+                    .get(id)
+                    .cloned()
+                    .unwrap_or_else(|| Err(BackendError::ParameterNotFound(id.clone())))
+            })
+            .collect();
+        // The read may have been cancelled meanwhile
+        let _ = waiter.reply.send(values);
+    }
+}
@@ -10,18 +10,24 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use sovd_core::routing;
 use sovd_core::{
     BackendError, BackendResult, Capabilities, ClearFaultsResult, DataPoint, DataValue,
-    DiagnosticBackend, EntityInfo, Fault, FaultFilter, FaultsResult, IoControlAction,
-    IoControlResult, LogEntry, LogFilter, OperationExecution, OperationInfo, OutputDetail,
-    OutputInfo, ParameterInfo, SoftwareInfo,
+    DiagnosticBackend, EntityInfo, Fault, FaultExtendedData, FaultFilter, FaultSnapshot,
+    FaultsResult, HealthStatus, IoControlAction, IoControlResult, LogEntry, LogFilter,
+    OperationExecution, OperationInfo, OutputDetail, OutputInfo, ParameterInfo, SoftwareInfo,
+    TopologyNode,
 };
 use tokio::sync::broadcast;
+use tokio::task::JoinSet;
 use tracing::{debug, info, warn};
 
+use crate::batch::ReadBatcher;
+use crate::metadata::ComponentMetadata;
+
 /// Gateway backend that federates multiple diagnostic backends
 ///
 /// This backend acts as a central hub that:
@@ -38,6 +44,10 @@ pub struct GatewayBackend {
     capabilities: Capabilities,
     /// Registered backends by ID
     backends: HashMap<String, Arc<dyn DiagnosticBackend>>,
+    /// Coalesces concurrent reads per backend (`[gateway] batch_window_ms`)
+    read_batcher: Option<ReadBatcher>,
+    /// Friendly names and categories by backend ID (`[gateway.components]`)
+    metadata: HashMap<String, ComponentMetadata>,
 }
 
 impl GatewayBackend {
@@ -50,15 +60,45 @@ impl GatewayBackend {
             description,
             href: format!("/vehicle/v1/components/{}", id),
             status: Some("operational".to_string()),
+            display_name: None,
+            category: None,
         };
 
         Self {
             entity_info,
             capabilities: Capabilities::gateway(),
             backends: HashMap::new(),
+            read_batcher: None,
+            metadata: HashMap::new(),
         }
     }
 
+    /// Coalesce reads for the same backend arriving within `window` into
+    /// one batch read (see [`crate::batch`])
+    pub fn with_batch_window(mut self, window: Duration) -> Self {
+        info!(
+            window_ms = window.as_millis() as u64,
+            "Gateway read batching enabled"
+        );
+        self.read_batcher = Some(ReadBatcher::new(window));
+        self
+    }
+
+    /// Overlay friendly names and categories onto the listed backends
+    /// (see [`crate::metadata`])
+    pub fn with_component_metadata(mut self, metadata: HashMap<String, ComponentMetadata>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Metadata for `id` where the configured overlay has none, e.g. for an
+    /// auto-discovered ECU
+    pub fn set_default_metadata(&mut self, id: &str, defaults: ComponentMetadata) {
+        let configured = self.metadata.remove(id).unwrap_or_default();
+        self.metadata
+            .insert(id.to_string(), configured.or(&defaults));
+    }
+
     /// Register a backend with this gateway
     pub fn register_backend(&mut self, backend: Arc<dyn DiagnosticBackend>) {
         let id = backend.entity_info().id.clone();
@@ -162,7 +202,10 @@ impl DiagnosticBackend for GatewayBackend {
                 BackendError::EntityNotFound(format!("Backend not found: {}", backend_id))
             })?;
 
-            let values = backend.read_data(&local_ids).await?;
+            let values = match &self.read_batcher {
+                Some(batcher) => batcher.read(&backend_id, backend, local_ids).await?,
+                None => backend.read_data(&local_ids).await?,
+            };
 
             for mut value in values {
                 value.id = routing::prefixed_id(&value.id, Some(&backend_id));
@@ -173,6 +216,57 @@ impl DiagnosticBackend for GatewayBackend {
         Ok(all_values)
     }
 
+    async fn read_parameters_batch(
+        &self,
+        ids: &[String],
+    ) -> Vec<(String, BackendResult<DataValue>)> {
+        // Already a batch: forward it per backend rather than reading (and
+        // waiting out the batching window) one id at a time
+        let mut results: Vec<Option<BackendResult<DataValue>>> = Vec::new();
+        let mut by_backend: HashMap<&str, Vec<(usize, String)>> = HashMap::new();
+        for (index, id) in ids.iter().enumerate() {
+            match routing::split_entity_prefix(id) {
+                Some((backend_id, local_id)) if self.backends.contains_key(backend_id) => {
+                    results.push(None);
+                    by_backend
+                        .entry(backend_id)
+                        .or_default()
+                        .push((index, local_id.to_string()));
+                }
+                Some((backend_id, _)) => results.push(Some(Err(BackendError::EntityNotFound(
+                    format!("Backend not found: {}", backend_id),
+                )))),
+                None => results.push(Some(Err(BackendError::ParameterNotFound(format!(
+                    "Parameter ID must be prefixed with backend ID: {}",
+                    id
+                ))))),
+            }
+        }
+
+        for (backend_id, entries) in by_backend {
+            let local_ids: Vec<String> = entries.iter().map(|(_, id)| id.clone()).collect();
+            let values = self.backends[backend_id]
+                .read_parameters_batch(&local_ids)
+                .await;
+            for ((index, _), (_, value)) in entries.into_iter().zip(values) {
+                results[index] = Some(value.map(|mut value| {
+                    value.id = routing::prefixed_id(&value.id, Some(backend_id));
+                    value
+                }));
+            }
+        }
+
+        ids.iter()
+            .cloned()
+            .zip(results)
+            .map(|(id, result)| {
+                let result =
+                    result.unwrap_or_else(|| Err(BackendError::ParameterNotFound(id.clone())));
+                (id, result)
+            })
+            .collect()
+    }
+
     async fn write_data(&self, param_id: &str, value: &[u8]) -> BackendResult<()> {
         let (backend_id, local_id) = routing::split_entity_prefix(param_id).ok_or_else(|| {
             BackendError::ParameterNotFound(format!(
@@ -278,6 +372,46 @@ impl DiagnosticBackend for GatewayBackend {
         Ok(fault)
     }
 
+    async fn get_fault_snapshots(
+        &self,
+        fault_id: &str,
+        did_lengths: &HashMap<u16, usize>,
+    ) -> BackendResult<Vec<FaultSnapshot>> {
+        let (backend_id, local_id) = routing::split_entity_prefix(fault_id).ok_or_else(|| {
+            BackendError::EntityNotFound(format!(
+                "Fault ID must be prefixed with backend ID: {}",
+                fault_id
+            ))
+        })?;
+
+        let backend = self.backends.get(backend_id).ok_or_else(|| {
+            BackendError::EntityNotFound(format!("Backend not found: {}", backend_id))
+        })?;
+
+        backend.get_fault_snapshots(local_id, did_lengths).await
+    }
+
+    async fn get_fault_extended_data(
+        &self,
+        fault_id: &str,
+        record_number: u8,
+    ) -> BackendResult<Vec<FaultExtendedData>> {
+        let (backend_id, local_id) = routing::split_entity_prefix(fault_id).ok_or_else(|| {
+            BackendError::EntityNotFound(format!(
+                "Fault ID must be prefixed with backend ID: {}",
+                fault_id
+            ))
+        })?;
+
+        let backend = self.backends.get(backend_id).ok_or_else(|| {
+            BackendError::EntityNotFound(format!("Backend not found: {}", backend_id))
+        })?;
+
+        backend
+            .get_fault_extended_data(local_id, record_number)
+            .await
+    }
+
     async fn clear_faults(&self, group: Option<u32>) -> BackendResult<ClearFaultsResult> {
         let mut total_cleared = 0u32;
         let mut any_success = false;
@@ -304,6 +438,7 @@ impl DiagnosticBackend for GatewayBackend {
             success: any_success,
             cleared_count: total_cleared,
             message: messages.join("; "),
+            verification: None,
         })
     }
 
@@ -378,6 +513,42 @@ impl DiagnosticBackend for GatewayBackend {
         Ok(execution)
     }
 
+    async fn encode_operation_params(
+        &self,
+        operation_id: &str,
+        params: &serde_json::Value,
+    ) -> BackendResult<Vec<u8>> {
+        let (backend_id, local_id) =
+            routing::split_entity_prefix(operation_id).ok_or_else(|| {
+                BackendError::OperationNotFound(format!(
+                    "Operation ID must be prefixed with backend ID: {}",
+                    operation_id
+                ))
+            })?;
+
+        let backend = self.backends.get(backend_id).ok_or_else(|| {
+            BackendError::EntityNotFound(format!("Backend not found: {}", backend_id))
+        })?;
+
+        backend.encode_operation_params(local_id, params).await
+    }
+
+    async fn check_operation_preconditions(&self, operation_id: &str) -> BackendResult<()> {
+        let (backend_id, local_id) =
+            routing::split_entity_prefix(operation_id).ok_or_else(|| {
+                BackendError::OperationNotFound(format!(
+                    "Operation ID must be prefixed with backend ID: {}",
+                    operation_id
+                ))
+            })?;
+
+        let backend = self.backends.get(backend_id).ok_or_else(|| {
+            BackendError::EntityNotFound(format!("Backend not found: {}", backend_id))
+        })?;
+
+        backend.check_operation_preconditions(local_id).await
+    }
+
     async fn get_operation_status(&self, execution_id: &str) -> BackendResult<OperationExecution> {
         let (backend_id, local_id) =
             routing::split_entity_prefix(execution_id).ok_or_else(|| {
@@ -473,6 +644,25 @@ impl DiagnosticBackend for GatewayBackend {
         backend.control_output(local_id, action, value).await
     }
 
+    async fn check_output_value(
+        &self,
+        output_id: &str,
+        value: &serde_json::Value,
+    ) -> BackendResult<serde_json::Value> {
+        let (backend_id, local_id) = routing::split_entity_prefix(output_id).ok_or_else(|| {
+            BackendError::OutputNotFound(format!(
+                "Output ID must be prefixed with backend ID: {}",
+                output_id
+            ))
+        })?;
+
+        let backend = self.backends.get(backend_id).ok_or_else(|| {
+            BackendError::EntityNotFound(format!("Backend not found: {}", backend_id))
+        })?;
+
+        backend.check_output_value(local_id, value).await
+    }
+
     async fn list_sub_entities(&self) -> BackendResult<Vec<EntityInfo>> {
         let mut entities: Vec<EntityInfo> = self
             .backends
@@ -481,6 +671,9 @@ impl DiagnosticBackend for GatewayBackend {
                 let mut info = b.entity_info().clone();
                 // Update href to be relative to gateway
                 info.href = format!("/vehicle/v1/components/{}/{}", self.entity_info.id, info.id);
+                if let Some(metadata) = self.metadata.get(&info.id) {
+                    metadata.apply(&mut info);
+                }
                 info
             })
             .collect();
@@ -498,6 +691,83 @@ impl DiagnosticBackend for GatewayBackend {
             .ok_or_else(|| BackendError::EntityNotFound(id.to_string()))
     }
 
+    /// The gateway with every registered backend below it, each probed
+    /// concurrently so one unreachable ECU does not hold up the rest
+    async fn topology(&self) -> BackendResult<TopologyNode> {
+        let mut node = TopologyNode::from_entity(&self.entity_info);
+        node.reachable = Some(true);
+
+        let mut probes = JoinSet::new();
+        for info in self.list_sub_entities().await? {
+            let Some(backend) = self.backends.get(&info.id).cloned() else {
+                continue;
+            };
+            probes.spawn(async move {
+                let child = match backend.topology().await {
+                    Ok(child) => child,
+                    Err(e) => {
+                        debug!(backend_id = %info.id, error = %e, "Topology probe failed");
+                        TopologyNode::from_entity(&info)
+                    }
+                };
+                child.listed_as(&info)
+            });
+        }
+        while let Some(child) = probes.join_next().await {
+            match child {
+                Ok(child) => node.children.push(child),
+                Err(e) => warn!(error = %e, "Topology probe panicked"),
+            }
+        }
+        node.children.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(node)
+    }
+
+    /// Every registered backend probed concurrently, each reported under
+    /// `children`; the gateway counts as reachable while any of them is
+    async fn health_check(&self) -> BackendResult<HealthStatus> {
+        let started = std::time::Instant::now();
+        let mut probes = JoinSet::new();
+        for (id, backend) in &self.backends {
+            let (id, backend) = (id.clone(), backend.clone());
+            probes.spawn(async move {
+                let status = match backend.health_check().await {
+                    Ok(status) => status,
+                    Err(e) => {
+                        debug!(backend_id = %id, error = %e, "Health check failed");
+                        HealthStatus::unreachable(e.to_string())
+                    }
+                };
+                (id, status)
+            });
+        }
+        let mut children = std::collections::BTreeMap::new();
+        while let Some(probe) = probes.join_next().await {
+            match probe {
+                Ok((id, status)) => {
+                    children.insert(id, status);
+                }
+                Err(e) => warn!(error = %e, "Health check panicked"),
+            }
+        }
+
+        let down = children.values().filter(|c| !c.reachable).count();
+        let mut status = if down > 0 && down == children.len() {
+            HealthStatus::unreachable("no backend behind the gateway answered")
+        } else {
+            HealthStatus::reachable().with_latency(started.elapsed())
+        };
+        if down > 0 && down < children.len() {
+            status.detail = Some(format!(
+                "{} of {} backends unreachable",
+                down,
+                children.len()
+            ));
+        }
+        status.children = children;
+        Ok(status)
+    }
+
     async fn get_software_info(&self) -> BackendResult<SoftwareInfo> {
         let mut details = serde_json::Map::new();
         details.insert(
@@ -532,6 +802,89 @@ impl DiagnosticBackend for GatewayBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use sovd_core::{OperationExecution, OperationInfo};
+
+    /// ECU recording every read call it receives
+    struct CountingEcu {
+        info: EntityInfo,
+        capabilities: Capabilities,
+        single_reads: AtomicUsize,
+        batches: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl CountingEcu {
+        fn new(id: &str) -> Arc<Self> {
+            Arc::new(Self {
+                info: EntityInfo {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    entity_type: "ecu".to_string(),
+                    description: None,
+                    href: format!("/vehicle/v1/components/{}", id),
+                    status: None,
+                    display_name: None,
+                    category: None,
+                },
+                capabilities: Capabilities::default(),
+                single_reads: AtomicUsize::new(0),
+                batches: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn value(id: &str) -> BackendResult<DataValue> {
+            if id == "missing" {
+                return Err(BackendError::ParameterNotFound(id.to_string()));
+            }
+            Ok(DataValue::new(id, id, serde_json::json!(id.len())))
+        }
+    }
+
+    #[async_trait]
+    impl DiagnosticBackend for CountingEcu {
+        fn entity_info(&self) -> &EntityInfo {
+            &self.info
+        }
+        fn capabilities(&self) -> &Capabilities {
+            &self.capabilities
+        }
+        async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+            Ok(vec![])
+        }
+        async fn read_data(&self, ids: &[String]) -> BackendResult<Vec<DataValue>> {
+            self.single_reads.fetch_add(1, Ordering::SeqCst);
+            ids.iter().map(|id| Self::value(id)).collect()
+        }
+        async fn read_parameters_batch(
+            &self,
+            ids: &[String],
+        ) -> Vec<(String, BackendResult<DataValue>)> {
+            self.batches.lock().unwrap().push(ids.to_vec());
+            ids.iter().map(|id| (id.clone(), Self::value(id))).collect()
+        }
+        async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+            Ok(FaultsResult {
+                faults: vec![],
+                status_availability_mask: None,
+            })
+        }
+        async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+            Ok(vec![])
+        }
+        async fn start_operation(
+            &self,
+            op: &str,
+            _params: &[u8],
+        ) -> BackendResult<OperationExecution> {
+            Err(BackendError::OperationNotFound(op.to_string()))
+        }
+    }
+
+    async fn read(gateway: &GatewayBackend, id: &str) -> BackendResult<Vec<DataValue>> {
+        gateway.read_data(&[id.to_string()]).await
+    }
 
     #[test]
     fn test_gateway_creation() {
@@ -550,4 +903,152 @@ mod tests {
         assert!(!caps.faults);
         assert!(caps.sub_entities); // Gateway always has sub_entities
     }
+
+    #[tokio::test]
+    async fn concurrent_reads_within_the_window_are_coalesced() {
+        let ecu = CountingEcu::new("ecu");
+        let mut gateway =
+            GatewayBackend::new("gw", "Gateway", None).with_batch_window(Duration::from_millis(20));
+        gateway.register_backend(ecu.clone());
+
+        let (rpm, speed, rpm_again, temp) = tokio::join!(
+            read(&gateway, "ecu/rpm"),
+            read(&gateway, "ecu/speed"),
+            read(&gateway, "ecu/rpm"),
+            read(&gateway, "ecu/temp"),
+        );
+
+        assert_eq!(rpm.unwrap()[0].id, "ecu/rpm");
+        assert_eq!(speed.unwrap()[0].id, "ecu/speed");
+        assert_eq!(rpm_again.unwrap()[0].id, "ecu/rpm");
+        assert_eq!(temp.unwrap()[0].value, serde_json::json!(4));
+
+        // One transaction for all four reads, the duplicate read once
+        let batches = ecu.batches.lock().unwrap().clone();
+        assert_eq!(batches, vec![vec!["rpm", "speed", "temp"]]);
+        assert_eq!(ecu.single_reads.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn coalesced_read_fails_only_the_read_that_asked_for_it() {
+        let ecu = CountingEcu::new("ecu");
+        let mut gateway =
+            GatewayBackend::new("gw", "Gateway", None).with_batch_window(Duration::from_millis(20));
+        gateway.register_backend(ecu.clone());
+
+        let (rpm, missing) = tokio::join!(read(&gateway, "ecu/rpm"), read(&gateway, "ecu/missing"));
+        assert!(rpm.is_ok());
+        assert!(matches!(missing, Err(BackendError::ParameterNotFound(_))));
+        assert_eq!(ecu.batches.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reads_are_forwarded_one_by_one_without_a_window() {
+        let ecu = CountingEcu::new("ecu");
+        let mut gateway = GatewayBackend::new("gw", "Gateway", None);
+        gateway.register_backend(ecu.clone());
+
+        let (a, b) = tokio::join!(read(&gateway, "ecu/rpm"), read(&gateway, "ecu/speed"));
+        assert!(a.is_ok() && b.is_ok());
+        assert_eq!(ecu.single_reads.load(Ordering::SeqCst), 2);
+        assert!(ecu.batches.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn batch_read_is_forwarded_per_backend() {
+        let ecu = CountingEcu::new("ecu");
+        let mut gateway =
+            GatewayBackend::new("gw", "Gateway", None).with_batch_window(Duration::from_millis(20));
+        gateway.register_backend(ecu.clone());
+
+        let ids = ["ecu/rpm", "nope/rpm", "ecu/speed"].map(String::from);
+        let results = gateway.read_parameters_batch(&ids).await;
+        assert_eq!(results[0].1.as_ref().unwrap().id, "ecu/rpm");
+        assert!(matches!(results[1].1, Err(BackendError::EntityNotFound(_))));
+        assert_eq!(results[2].0, "ecu/speed");
+        assert_eq!(*ecu.batches.lock().unwrap(), vec![vec!["rpm", "speed"]]);
+    }
+
+    fn metadata(display_name: &str, category: &str) -> ComponentMetadata {
+        ComponentMetadata {
+            display_name: Some(display_name.to_string()),
+            category: Some(category.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn configured_metadata_is_listed_under_the_raw_id() {
+        let mut gateway = GatewayBackend::new("gw", "Gateway", None).with_component_metadata(
+            HashMap::from([("ecu_0x10".to_string(), metadata("Engine ECU", "powertrain"))]),
+        );
+        gateway.register_backend(CountingEcu::new("ecu_0x10"));
+        gateway.register_backend(CountingEcu::new("ecu_0x20"));
+
+        let listed = gateway.list_sub_entities().await.unwrap();
+        assert_eq!(listed[0].id, "ecu_0x10");
+        assert_eq!(listed[0].href, "/vehicle/v1/components/gw/ecu_0x10");
+        assert_eq!(listed[0].display_name.as_deref(), Some("Engine ECU"));
+        assert_eq!(listed[0].category.as_deref(), Some("powertrain"));
+        // Unconfigured children are listed as the backend reports them
+        assert_eq!(listed[1].display_name, None);
+
+        // Reads still route by the raw id
+        let values = read(&gateway, "ecu_0x10/rpm").await.unwrap();
+        assert_eq!(values[0].id, "ecu_0x10/rpm");
+    }
+
+    #[tokio::test]
+    async fn defaults_fill_only_what_config_leaves_unset() {
+        let configured = ComponentMetadata {
+            display_name: Some("Engine ECU".to_string()),
+            category: None,
+        };
+        let mut gateway = GatewayBackend::new("gw", "Gateway", None)
+            .with_component_metadata(HashMap::from([("ecu_0x10".to_string(), configured)]));
+        gateway.register_backend(CountingEcu::new("ecu_0x10"));
+        gateway.register_backend(CountingEcu::new("ecu_0x20"));
+        gateway.set_default_metadata("ecu_0x10", metadata("ECU 0x10", "discovered"));
+        gateway.set_default_metadata("ecu_0x20", metadata("ECU 0x20", "discovered"));
+
+        let listed = gateway.list_sub_entities().await.unwrap();
+        assert_eq!(listed[0].display_name.as_deref(), Some("Engine ECU"));
+        assert_eq!(listed[0].category.as_deref(), Some("discovered"));
+        assert_eq!(listed[1].display_name.as_deref(), Some("ECU 0x20"));
+    }
+
+    #[tokio::test]
+    async fn topology_nests_backends_under_their_gateway() {
+        let mut body = GatewayBackend::new("body_gw", "Body Gateway", None);
+        body.register_backend(CountingEcu::new("door_ecu"));
+        let mut gateway =
+            GatewayBackend::new("gw", "Gateway", None).with_component_metadata(HashMap::from([(
+                "engine_ecu".to_string(),
+                metadata("Engine ECU", "powertrain"),
+            )]));
+        gateway.register_backend(CountingEcu::new("engine_ecu"));
+        gateway.register_backend(Arc::new(body));
+
+        let root = gateway.topology().await.unwrap();
+        assert_eq!(
+            (root.id.as_str(), root.entity_type.as_str()),
+            ("gw", "gateway")
+        );
+        let ids: Vec<_> = root.children.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["body_gw", "engine_ecu"]);
+
+        let engine = &root.children[1];
+        assert_eq!(engine.href, "/vehicle/v1/components/gw/engine_ecu");
+        assert_eq!(engine.display_name.as_deref(), Some("Engine ECU"));
+        assert_eq!(engine.reachable, Some(true));
+        // No transport_stats: nothing to report about the link
+        assert_eq!(engine.transport, None);
+
+        let body = &root.children[0];
+        assert_eq!(body.children.len(), 1);
+        assert_eq!(body.children[0].id, "door_ecu");
+        assert_eq!(
+            body.children[0].href,
+            "/vehicle/v1/components/body_gw/door_ecu"
+        );
+    }
 }
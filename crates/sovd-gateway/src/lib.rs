@@ -46,9 +46,12 @@
 //! // Returns: ["engine_ecu/rpm", "engine_ecu/coolant_temp", ...]
 //! ```
 
+mod batch;
 mod gateway;
+pub mod metadata;
 
 pub use gateway::GatewayBackend;
+pub use metadata::ComponentMetadata;
 
 // Re-export core types for convenience
 pub use sovd_core::{BackendError, BackendResult, Capabilities, DiagnosticBackend, EntityInfo};
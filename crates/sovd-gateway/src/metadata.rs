@@ -0,0 +1,42 @@
+//! Component metadata overlay
+//!
+//! Gateway children are listed under their raw ids (`ecu_0x10`), which mean
+//! little in a service UI. `[gateway.components.<id>]` attaches a friendly
+//! `display_name` and a `category` to a child's [`EntityInfo`] when the
+//! gateway lists it. Only the listing changes: ids, hrefs and request
+//! routing stay exactly as they were.
+
+use serde::Deserialize;
+use sovd_core::EntityInfo;
+
+/// Friendly naming for one gateway child
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ComponentMetadata {
+    /// Name shown in service UIs (e.g. "Engine ECU")
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Grouping shown in service UIs (e.g. "powertrain")
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+impl ComponentMetadata {
+    /// Fill the fields this entry leaves unset from `defaults`
+    pub(crate) fn or(self, defaults: &ComponentMetadata) -> Self {
+        Self {
+            display_name: self.display_name.or_else(|| defaults.display_name.clone()),
+            category: self.category.or_else(|| defaults.category.clone()),
+        }
+    }
+
+    /// Overlay onto `info`, keeping what the backend reported where this
+    /// entry is silent
+    pub(crate) fn apply(&self, info: &mut EntityInfo) {
+        if let Some(display_name) = &self.display_name {
+            info.display_name = Some(display_name.clone());
+        }
+        if let Some(category) = &self.category {
+            info.category = Some(category.clone());
+        }
+    }
+}
@@ -6,5 +6,9 @@
 //! CAN bus access.
 
 mod proxy;
+mod resilience;
+mod token;
 
 pub use proxy::SovdProxyBackend;
+pub use resilience::{CacheConfig, RetryConfig};
+pub use token::TokenProvider;
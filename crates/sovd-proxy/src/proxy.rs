@@ -1,20 +1,26 @@
 //! SovdProxyBackend - DiagnosticBackend that proxies to a remote SOVD server
 
+use std::future::Future;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use sovd_client::SovdClient;
+use sovd_client::{SovdClient, SovdClientError};
 use sovd_core::models::{FaultSeverity, LogPriority, OperationStatus};
 use sovd_core::routing;
 use sovd_core::{
     ActivationState, BackendError, BackendResult, Capabilities, ClearFaultsResult, DataCategory,
     DataValue, DiagnosticBackend, EntityInfo, Fault, FaultFilter, FaultsResult, FlashStatus,
-    IoControlAction, IoControlResult, LogEntry, LogFilter, OperationExecution, OperationInfo,
-    OutputDetail, OutputInfo, PackageInfo, PackageStream, ParameterInfo, SecurityMode,
-    SecurityState, SessionMode, VerifyResult,
+    HealthStatus, IoControlAction, IoControlResult, LogEntry, LogFilter, OperationExecution,
+    OperationInfo, OutputDetail, OutputInfo, PackageInfo, PackageStream, ParameterInfo,
+    SecurityMode, SecurityState, SessionMode, VerifyResult,
 };
 
+use crate::resilience::{
+    is_transient, is_transient_status, CacheConfig, IdentificationCache, RetryConfig,
+};
+use crate::token::{is_unauthorized, TokenProvider, Upstream};
+
 /// Convert client-side capabilities to core Capabilities.
 /// The upstream is authoritative — no local overrides.
 fn to_capabilities(rc: sovd_client::ComponentCapabilities) -> Capabilities {
@@ -32,6 +38,7 @@ fn to_capabilities(rc: sovd_client::ComponentCapabilities) -> Capabilities {
         sub_entities: rc.sub_entities,
         subscriptions: rc.subscriptions,
         bulk_data: rc.bulk_data,
+        dtc_reports: rc.dtc_reports,
     }
 }
 
@@ -73,13 +80,16 @@ struct UpstreamErrorResp {
 /// Used for tier-1 supplier containers that have no direct CAN access
 /// and reach ECUs exclusively through the SOVD HTTP API.
 pub struct SovdProxyBackend {
-    client: SovdClient,
+    /// Shared with sub-entity proxies, so they use (and refresh) one token
+    upstream: Arc<Upstream>,
     component_id: String,
     /// When routing through a gateway, parameter/operation IDs are prefixed
     /// with the sub-entity ID (e.g., "vtx_vx500/boost_pressure").
     sub_entity_prefix: Option<String>,
     entity_info: EntityInfo,
     capabilities: Capabilities,
+    retry: RetryConfig,
+    cache: IdentificationCache,
 }
 
 impl SovdProxyBackend {
@@ -135,12 +145,36 @@ impl SovdProxyBackend {
         auth_token: Option<&str>,
         upstream_gateway: Option<&str>,
     ) -> Result<Self, String> {
-        let client = if let Some(token) = auth_token {
-            SovdClient::with_bearer_token(base_url, token)
-        } else {
-            SovdClient::new(base_url)
-        }
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+        let upstream = Upstream::fixed(base_url, auth_token)?;
+        Self::connect(local_id, remote_component_id, upstream, upstream_gateway).await
+    }
+
+    /// Create a new proxy backend that gets its bearer tokens from
+    /// `provider`.
+    ///
+    /// The first token is fetched before connecting. Whenever the upstream
+    /// answers 401 the proxy fetches a new one and sends the refused
+    /// request once more; concurrent requests share a single refresh.
+    /// Otherwise like [`Self::with_options`].
+    pub async fn with_token_provider(
+        local_id: &str,
+        base_url: &str,
+        remote_component_id: &str,
+        provider: Arc<dyn TokenProvider>,
+        upstream_gateway: Option<&str>,
+    ) -> Result<Self, String> {
+        let upstream = Upstream::with_provider(base_url, provider).await?;
+        Self::connect(local_id, remote_component_id, upstream, upstream_gateway).await
+    }
+
+    async fn connect(
+        local_id: &str,
+        remote_component_id: &str,
+        upstream: Upstream,
+        upstream_gateway: Option<&str>,
+    ) -> Result<Self, String> {
+        let client = upstream.client().await;
+        let base_url = upstream.base_url();
 
         // Determine routing component, entity info, and upstream capabilities.
         let (routing_component_id, entity_info, remote_caps) =
@@ -163,6 +197,8 @@ impl SovdProxyBackend {
                     description: app.description,
                     href: format!("/vehicle/v1/components/{}", local_id),
                     status: app.status,
+                    display_name: None,
+                    category: None,
                 };
 
                 // Route all requests through the gateway component
@@ -181,6 +217,8 @@ impl SovdProxyBackend {
                             description: component.description,
                             href: format!("/vehicle/v1/components/{}", local_id),
                             status: component.status,
+                            display_name: None,
+                            category: None,
                         };
                         (remote_component_id.to_string(), info, caps)
                     }
@@ -242,6 +280,8 @@ impl SovdProxyBackend {
                             description: desc,
                             href: format!("/vehicle/v1/components/{}", local_id),
                             status,
+                            display_name: None,
+                            category: None,
                         };
                         (gateway_id, info, caps)
                     }
@@ -269,21 +309,171 @@ impl SovdProxyBackend {
         };
 
         Ok(Self {
-            client,
+            upstream: Arc::new(upstream),
             component_id: routing_component_id,
             sub_entity_prefix,
             entity_info,
             capabilities,
+            retry: RetryConfig::default(),
+            cache: IdentificationCache::new(&CacheConfig::default()),
         })
     }
 
+    /// Retry transient failures of idempotent reads per `retry` (default:
+    /// three attempts, backing off from 100 ms)
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Serve identification DIDs from a short-lived cache (default: off)
+    pub fn with_cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = IdentificationCache::new(&cache);
+        self
+    }
+
+    /// Run a state-changing upstream call, then drop the identification
+    /// cache: the change may alter identification values. Clearing also
+    /// bumps the cache generation, so a read racing the change cannot
+    /// cache the old value afterwards.
+    async fn invalidating<T>(
+        &self,
+        change: impl Future<Output = BackendResult<T>>,
+    ) -> BackendResult<T> {
+        let result = change.await;
+        self.cache.clear();
+        result
+    }
+
+    /// Client carrying the current upstream token
+    async fn client(&self) -> SovdClient {
+        self.upstream.client().await
+    }
+
+    /// Run an upstream request. When the upstream refuses the token (401)
+    /// and a token provider is configured, the token is refreshed and the
+    /// request sent once more.
+    async fn call<T, F, Fut>(&self, mut request: F) -> Result<T, SovdClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, SovdClientError>>,
+    {
+        let generation = self.upstream.generation().await;
+        match request().await {
+            Err(e) if is_unauthorized(&e) && self.upstream.refresh(generation).await => {
+                request().await
+            }
+            result => result,
+        }
+    }
+
+    /// Send a request `SovdClient` has no method for, built by `build`
+    /// from the current HTTP client, refreshing the token like
+    /// [`Self::call`]
+    async fn send_upstream(
+        &self,
+        build: impl Fn(&reqwest::Client) -> reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let generation = self.upstream.generation().await;
+        let response = build(self.client().await.http_client()).send().await?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED
+            && self.upstream.refresh(generation).await
+        {
+            return build(self.client().await.http_client()).send().await;
+        }
+        Ok(response)
+    }
+
+    /// Run an idempotent upstream read, retrying transient failures with
+    /// exponential backoff
+    async fn retry_read<T, F, Fut>(&self, mut read: F) -> BackendResult<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, SovdClientError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match self.call(&mut read).await {
+                Err(e) if attempt < self.retry.max_attempts && is_transient(&e) => {
+                    self.back_off(attempt, &e.to_string()).await;
+                    attempt += 1;
+                }
+                result => return result.map_err(Self::map_err),
+            }
+        }
+    }
+
+    /// GET an upstream URL `SovdClient` has no method for, retrying
+    /// transient failures like [`Self::retry_read`]
+    async fn get_upstream(&self, url: &str) -> BackendResult<reqwest::Response> {
+        let mut attempt = 1;
+        loop {
+            let retry = attempt < self.retry.max_attempts;
+            match self.send_upstream(|http| http.get(url)).await {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) if retry && is_transient_status(response.status().as_u16()) => {
+                    self.back_off(attempt, response.status().as_str()).await;
+                }
+                Ok(response) => return Err(Self::map_response_error(response).await),
+                Err(e) if retry && (e.is_connect() || e.is_timeout()) => {
+                    self.back_off(attempt, &e.to_string()).await;
+                }
+                Err(e) => return Err(BackendError::Transport(e.to_string())),
+            }
+            attempt += 1;
+        }
+    }
+
+    async fn back_off(&self, attempt: u32, error: &str) {
+        let delay = self.retry.delay(attempt);
+        tracing::debug!(
+            attempt,
+            delay_ms = delay.as_millis() as u64,
+            error,
+            "Proxy: retrying upstream read"
+        );
+        tokio::time::sleep(delay).await;
+    }
+
+    /// Read one data item upstream, or from the identification cache
+    /// while it holds the item
+    async fn read_upstream(&self, id: &str) -> BackendResult<sovd_client::DataResponse> {
+        if let Some(cached) = self.cache.get(id) {
+            return Ok(cached);
+        }
+        let generation = self.cache.generation();
+        // Use sub-entity route when proxying through a gateway
+        let resp = self
+            .retry_read(|| async {
+                if let Some(ref prefix) = self.sub_entity_prefix {
+                    self.client()
+                        .await
+                        .read_sub_entity_data(&self.component_id, prefix, id)
+                        .await
+                } else {
+                    self.client().await.read_data(&self.component_id, id).await
+                }
+            })
+            .await?;
+        self.cache.insert(id, &resp, generation);
+        Ok(resp)
+    }
+
     /// Map a SovdClientError to a BackendError
-    fn map_err(e: sovd_client::SovdClientError) -> BackendError {
-        use sovd_client::SovdClientError;
+    fn map_err(e: SovdClientError) -> BackendError {
         match e {
             SovdClientError::ComponentNotFound(m) => BackendError::EntityNotFound(m),
             SovdClientError::ParameterNotFound(m) => BackendError::ParameterNotFound(m),
             SovdClientError::SecurityAccessDenied(_) => BackendError::SecurityRequired(1),
+            SovdClientError::NegativeResponse {
+                nrc,
+                service,
+                message,
+            } => BackendError::EcuError {
+                nrc: nrc.into(),
+                sid: service,
+                message,
+            },
             SovdClientError::Timeout => BackendError::Timeout,
             SovdClientError::HttpError(e) => BackendError::Transport(e.to_string()),
             SovdClientError::ServerError { status, message } => match status {
@@ -336,7 +526,7 @@ impl SovdProxyBackend {
 
     /// Build a full URL string for a flash/file endpoint on the upstream server.
     fn flash_url(&self, suffix: &str) -> Result<String, BackendError> {
-        let base = self.client.base_url().as_str().trim_end_matches('/');
+        let base = self.upstream.base_url().trim_end_matches('/');
         Ok(format!("{}{}{}", base, self.flash_path_prefix(), suffix))
     }
 
@@ -380,20 +570,37 @@ impl DiagnosticBackend for SovdProxyBackend {
         &self.capabilities
     }
 
+    async fn health_check(&self) -> BackendResult<HealthStatus> {
+        // Liveness of the upstream server; its own ECUs report on its
+        // health endpoint.
+        let started = std::time::Instant::now();
+        Ok(match self.client().await.health().await {
+            Ok(_) => HealthStatus::reachable().with_latency(started.elapsed()),
+            Err(e) => HealthStatus::unreachable(e.to_string()),
+        })
+    }
+
     // =========================================================================
     // Data Access
     // =========================================================================
 
     async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
         // Use sub-entity route when proxying through a gateway
-        let resp = if let Some(ref prefix) = self.sub_entity_prefix {
-            self.client
-                .list_sub_entity_parameters(&self.component_id, prefix)
-                .await
-        } else {
-            self.client.list_parameters(&self.component_id).await
-        }
-        .map_err(Self::map_err)?;
+        let resp = self
+            .retry_read(|| async {
+                if let Some(ref prefix) = self.sub_entity_prefix {
+                    self.client()
+                        .await
+                        .list_sub_entity_parameters(&self.component_id, prefix)
+                        .await
+                } else {
+                    self.client()
+                        .await
+                        .list_parameters(&self.component_id)
+                        .await
+                }
+            })
+            .await?;
 
         let params = resp
             .items
@@ -426,15 +633,7 @@ impl DiagnosticBackend for SovdProxyBackend {
     async fn read_data(&self, param_ids: &[String]) -> BackendResult<Vec<DataValue>> {
         let mut values = Vec::new();
         for param_id in param_ids {
-            // Use sub-entity route when proxying through a gateway
-            let resp = if let Some(ref prefix) = self.sub_entity_prefix {
-                self.client
-                    .read_sub_entity_data(&self.component_id, prefix, param_id)
-                    .await
-            } else {
-                self.client.read_data(&self.component_id, param_id).await
-            }
-            .map_err(Self::map_err)?;
+            let resp = self.read_upstream(param_id).await?;
 
             values.push(DataValue {
                 id: param_id.clone(),
@@ -451,41 +650,44 @@ impl DiagnosticBackend for SovdProxyBackend {
     }
 
     async fn write_data(&self, param_id: &str, value: &[u8]) -> BackendResult<()> {
-        let hex_value = hex::encode(value);
-        // Use sub-entity route when proxying through a gateway
-        if let Some(ref prefix) = self.sub_entity_prefix {
-            self.client
-                .write_sub_entity_data(
-                    &self.component_id,
-                    prefix,
-                    param_id,
-                    serde_json::Value::String(hex_value),
-                )
+        self.invalidating(async {
+            let hex_value = hex::encode(value);
+            // Use sub-entity route when proxying through a gateway
+            if let Some(ref prefix) = self.sub_entity_prefix {
+                self.call(|| async {
+                    self.client()
+                        .await
+                        .write_sub_entity_data(
+                            &self.component_id,
+                            prefix,
+                            param_id,
+                            serde_json::Value::String(hex_value.clone()),
+                        )
+                        .await
+                })
                 .await
                 .map_err(Self::map_err)
-        } else {
-            self.client
-                .write_data(
-                    &self.component_id,
-                    param_id,
-                    serde_json::Value::String(hex_value),
-                )
+            } else {
+                self.call(|| async {
+                    self.client()
+                        .await
+                        .write_data(
+                            &self.component_id,
+                            param_id,
+                            serde_json::Value::String(hex_value.clone()),
+                        )
+                        .await
+                })
                 .await
                 .map_err(Self::map_err)
-        }
+            }
+        })
+        .await
     }
 
     async fn read_raw_did(&self, did: u16) -> BackendResult<Vec<u8>> {
         let did_str = format!("{:04X}", did);
-        // Use sub-entity route when proxying through a gateway
-        let resp = if let Some(ref prefix) = self.sub_entity_prefix {
-            self.client
-                .read_sub_entity_data(&self.component_id, prefix, &did_str)
-                .await
-        } else {
-            self.client.read_data(&self.component_id, &did_str).await
-        }
-        .map_err(Self::map_err)?;
+        let resp = self.read_upstream(&did_str).await?;
 
         if let Some(raw) = &resp.raw {
             hex::decode(raw)
@@ -500,65 +702,75 @@ impl DiagnosticBackend for SovdProxyBackend {
     }
 
     async fn write_raw_did(&self, did: u16, data: &[u8]) -> BackendResult<()> {
-        let hex_value = hex::encode(data);
-        let did_str = format!("{:04X}", did);
-        let prefixed = routing::prefixed_id(&did_str, self.sub_entity_prefix.as_deref());
-        self.client
-            .write_data(
-                &self.component_id,
-                &prefixed,
-                serde_json::Value::String(hex_value),
-            )
+        self.invalidating(async {
+            let hex_value = hex::encode(data);
+            let did_str = format!("{:04X}", did);
+            let prefixed = routing::prefixed_id(&did_str, self.sub_entity_prefix.as_deref());
+            self.call(|| async {
+                self.client()
+                    .await
+                    .write_data(
+                        &self.component_id,
+                        &prefixed,
+                        serde_json::Value::String(hex_value.clone()),
+                    )
+                    .await
+            })
             .await
             .map_err(Self::map_err)
+        })
+        .await
     }
 
     async fn ecu_reset(&self, reset_type: u8) -> BackendResult<Option<u8>> {
-        let type_str = match reset_type {
-            0x01 => "hard",
-            0x02 => "key_off_on",
-            0x03 => "soft",
-            _ => "hard",
-        };
+        self.invalidating(async {
+            let type_str = match reset_type {
+                0x01 => "hard",
+                0x02 => "key_off_on",
+                0x03 => "soft",
+                _ => "hard",
+            };
 
-        if self.sub_entity_prefix.is_some() {
-            // Route through sub-entity path on the upstream server
-            let url = self.flash_url("/reset")?;
-            let body = serde_json::json!({ "reset_type": type_str });
+            if self.sub_entity_prefix.is_some() {
+                // Route through sub-entity path on the upstream server
+                let url = self.flash_url("/reset")?;
+                let body = serde_json::json!({ "reset_type": type_str });
 
-            tracing::info!(url = %url, reset_type = %type_str, "Proxy: sub-entity ECU reset");
+                tracing::info!(url = %url, reset_type = %type_str, "Proxy: sub-entity ECU reset");
 
-            let response = self
-                .client
-                .http_client()
-                .post(&url)
-                .json(&body)
-                .send()
-                .await
-                .map_err(|e| BackendError::Transport(e.to_string()))?;
+                let response = self
+                    .send_upstream(|http| http.post(&url).json(&body))
+                    .await
+                    .map_err(|e| BackendError::Transport(e.to_string()))?;
 
-            if !response.status().is_success() {
-                return Err(Self::map_response_error(response).await);
-            }
+                if !response.status().is_success() {
+                    return Err(Self::map_response_error(response).await);
+                }
 
-            #[derive(Deserialize)]
-            struct ResetResp {
-                power_down_time: Option<u8>,
-            }
-            let resp: ResetResp = response.json().await.map_err(|e| {
-                BackendError::Protocol(format!("Failed to parse reset response: {}", e))
-            })?;
+                #[derive(Deserialize)]
+                struct ResetResp {
+                    power_down_time: Option<u8>,
+                }
+                let resp: ResetResp = response.json().await.map_err(|e| {
+                    BackendError::Protocol(format!("Failed to parse reset response: {}", e))
+                })?;
 
-            Ok(resp.power_down_time)
-        } else {
-            let resp = self
-                .client
-                .ecu_reset(&self.component_id, type_str)
-                .await
-                .map_err(Self::map_err)?;
+                Ok(resp.power_down_time)
+            } else {
+                let resp = self
+                    .call(|| async {
+                        self.client()
+                            .await
+                            .ecu_reset(&self.component_id, type_str)
+                            .await
+                    })
+                    .await
+                    .map_err(Self::map_err)?;
 
-            Ok(resp.power_down_time)
-        }
+                Ok(resp.power_down_time)
+            }
+        })
+        .await
     }
 
     // =========================================================================
@@ -568,17 +780,18 @@ impl DiagnosticBackend for SovdProxyBackend {
     async fn get_faults(&self, filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
         let category = filter.and_then(|f| f.category.as_deref());
 
-        let faults = if let Some(cat) = category {
-            self.client
-                .get_faults_filtered(&self.component_id, Some(cat))
-                .await
-                .map_err(Self::map_err)?
-        } else {
-            self.client
-                .get_faults(&self.component_id)
-                .await
-                .map_err(Self::map_err)?
-        };
+        let faults = self
+            .retry_read(|| async {
+                if let Some(cat) = category {
+                    self.client()
+                        .await
+                        .get_faults_filtered(&self.component_id, Some(cat))
+                        .await
+                } else {
+                    self.client().await.get_faults(&self.component_id).await
+                }
+            })
+            .await?;
 
         let converted: Vec<Fault> = faults
             .into_iter()
@@ -615,10 +828,13 @@ impl DiagnosticBackend for SovdProxyBackend {
 
     async fn get_fault_detail(&self, fault_id: &str) -> BackendResult<Fault> {
         let f = self
-            .client
-            .get_fault(&self.component_id, fault_id)
-            .await
-            .map_err(Self::map_err)?;
+            .retry_read(|| async {
+                self.client()
+                    .await
+                    .get_fault(&self.component_id, fault_id)
+                    .await
+            })
+            .await?;
 
         let active = f
             .status
@@ -643,8 +859,7 @@ impl DiagnosticBackend for SovdProxyBackend {
 
     async fn clear_faults(&self, _group: Option<u32>) -> BackendResult<ClearFaultsResult> {
         let resp = self
-            .client
-            .clear_faults(&self.component_id)
+            .call(|| async { self.client().await.clear_faults(&self.component_id).await })
             .await
             .map_err(Self::map_err)?;
 
@@ -652,6 +867,7 @@ impl DiagnosticBackend for SovdProxyBackend {
             success: resp.success,
             cleared_count: resp.cleared_count.unwrap_or(0),
             message: resp.message.unwrap_or_else(|| "Faults cleared".to_string()),
+            verification: None,
         })
     }
 
@@ -661,10 +877,13 @@ impl DiagnosticBackend for SovdProxyBackend {
 
     async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
         let ops = self
-            .client
-            .list_operations(&self.component_id)
-            .await
-            .map_err(Self::map_err)?;
+            .retry_read(|| async {
+                self.client()
+                    .await
+                    .list_operations(&self.component_id)
+                    .await
+            })
+            .await?;
 
         let prefix = self.sub_entity_prefix.as_deref();
         let converted = ops
@@ -703,8 +922,12 @@ impl DiagnosticBackend for SovdProxyBackend {
 
         let prefixed = routing::prefixed_id(operation_id, self.sub_entity_prefix.as_deref());
         let resp = self
-            .client
-            .start_operation_execution(&self.component_id, &prefixed, params_str.as_deref())
+            .call(|| async {
+                self.client()
+                    .await
+                    .start_operation_execution(&self.component_id, &prefixed, params_str.as_deref())
+                    .await
+            })
             .await
             .map_err(Self::map_err)?;
 
@@ -741,10 +964,8 @@ impl DiagnosticBackend for SovdProxyBackend {
 
     async fn list_outputs(&self) -> BackendResult<Vec<OutputInfo>> {
         let outputs = self
-            .client
-            .list_outputs(&self.component_id)
-            .await
-            .map_err(Self::map_err)?;
+            .retry_read(|| async { self.client().await.list_outputs(&self.component_id).await })
+            .await?;
 
         let prefix = self.sub_entity_prefix.as_deref();
         let converted = outputs
@@ -774,10 +995,13 @@ impl DiagnosticBackend for SovdProxyBackend {
     async fn get_output(&self, output_id: &str) -> BackendResult<OutputDetail> {
         let prefixed = routing::prefixed_id(output_id, self.sub_entity_prefix.as_deref());
         let o = self
-            .client
-            .get_output(&self.component_id, &prefixed)
-            .await
-            .map_err(Self::map_err)?;
+            .retry_read(|| async {
+                self.client()
+                    .await
+                    .get_output(&self.component_id, &prefixed)
+                    .await
+            })
+            .await?;
 
         Ok(OutputDetail {
             id: o.id.clone(),
@@ -801,6 +1025,14 @@ impl DiagnosticBackend for SovdProxyBackend {
                 .into_iter()
                 .filter_map(|v| v.as_str().map(|s| s.to_string()))
                 .collect(),
+            last_action: o.last_action,
+            last_value: o.last_value,
+            last_commanded_at: o
+                .last_commanded_at
+                .as_deref()
+                .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+                .map(|at| at.with_timezone(&chrono::Utc)),
+            session_reset: o.session_reset.unwrap_or(false),
         })
     }
 
@@ -819,8 +1051,12 @@ impl DiagnosticBackend for SovdProxyBackend {
 
         let prefixed = routing::prefixed_id(output_id, self.sub_entity_prefix.as_deref());
         let resp = self
-            .client
-            .control_output(&self.component_id, &prefixed, action_str, value)
+            .call(|| async {
+                self.client()
+                    .await
+                    .control_output(&self.component_id, &prefixed, action_str, value.clone())
+                    .await
+            })
             .await
             .map_err(Self::map_err)?;
 
@@ -832,6 +1068,7 @@ impl DiagnosticBackend for SovdProxyBackend {
             frozen: resp.frozen,
             new_value: resp.new_value,
             value: resp.value,
+            raw_sent: resp.raw_sent,
             error: resp.error,
         })
     }
@@ -843,10 +1080,8 @@ impl DiagnosticBackend for SovdProxyBackend {
     async fn get_sub_entity(&self, id: &str) -> BackendResult<Arc<dyn DiagnosticBackend>> {
         // Fetch sub-entity detail (includes capabilities per §6.4)
         let app = self
-            .client
-            .get_app(&self.component_id, id)
-            .await
-            .map_err(Self::map_err)?;
+            .retry_read(|| async { self.client().await.get_app(&self.component_id, id).await })
+            .await?;
 
         let entity_info = EntityInfo {
             id: id.to_string(),
@@ -855,6 +1090,8 @@ impl DiagnosticBackend for SovdProxyBackend {
             description: app.description,
             href: app.href.unwrap_or_default(),
             status: app.status,
+            display_name: None,
+            category: None,
         };
 
         let capabilities = to_capabilities(app.capabilities.unwrap_or_default());
@@ -862,20 +1099,20 @@ impl DiagnosticBackend for SovdProxyBackend {
         // Create a sub-proxy that routes through the same remote component
         // but with a sub_entity_prefix so session/security calls are targeted
         Ok(Arc::new(SovdProxyBackend {
-            client: self.client.clone(),
+            upstream: self.upstream.clone(),
             component_id: self.component_id.clone(),
             sub_entity_prefix: Some(id.to_string()),
             entity_info,
             capabilities,
+            retry: self.retry.clone(),
+            cache: self.cache.empty_copy(),
         }))
     }
 
     async fn list_sub_entities(&self) -> BackendResult<Vec<EntityInfo>> {
         let apps = self
-            .client
-            .list_apps(&self.component_id)
-            .await
-            .map_err(Self::map_err)?;
+            .retry_read(|| async { self.client().await.list_apps(&self.component_id).await })
+            .await?;
 
         let entities = apps
             .into_iter()
@@ -886,6 +1123,8 @@ impl DiagnosticBackend for SovdProxyBackend {
                 description: app.description,
                 href: app.href.unwrap_or_default(),
                 status: app.status,
+                display_name: app.display_name,
+                category: app.category,
             })
             .collect();
 
@@ -898,10 +1137,8 @@ impl DiagnosticBackend for SovdProxyBackend {
 
     async fn get_logs(&self, _filter: &LogFilter) -> BackendResult<Vec<LogEntry>> {
         let resp = self
-            .client
-            .get_logs(&self.component_id)
-            .await
-            .map_err(Self::map_err)?;
+            .retry_read(|| async { self.client().await.get_logs(&self.component_id).await })
+            .await?;
 
         let entries = resp
             .items
@@ -927,10 +1164,13 @@ impl DiagnosticBackend for SovdProxyBackend {
 
     async fn get_log(&self, log_id: &str) -> BackendResult<LogEntry> {
         let l = self
-            .client
-            .get_log(&self.component_id, log_id)
-            .await
-            .map_err(Self::map_err)?;
+            .retry_read(|| async {
+                self.client()
+                    .await
+                    .get_log(&self.component_id, log_id)
+                    .await
+            })
+            .await?;
 
         Ok(LogEntry {
             id: l.id,
@@ -949,17 +1189,24 @@ impl DiagnosticBackend for SovdProxyBackend {
     }
 
     async fn get_log_content(&self, log_id: &str) -> BackendResult<Vec<u8>> {
-        self.client
-            .get_log_content(&self.component_id, log_id)
-            .await
-            .map_err(Self::map_err)
+        self.retry_read(|| async {
+            self.client()
+                .await
+                .get_log_content(&self.component_id, log_id)
+                .await
+        })
+        .await
     }
 
     async fn delete_log(&self, log_id: &str) -> BackendResult<()> {
-        self.client
-            .delete_log(&self.component_id, log_id)
-            .await
-            .map_err(Self::map_err)
+        self.call(|| async {
+            self.client()
+                .await
+                .delete_log(&self.component_id, log_id)
+                .await
+        })
+        .await
+        .map_err(Self::map_err)
     }
 
     // =========================================================================
@@ -969,10 +1216,13 @@ impl DiagnosticBackend for SovdProxyBackend {
     async fn get_session_mode(&self) -> BackendResult<SessionMode> {
         let target = self.sub_entity_prefix.as_deref();
         let resp = self
-            .client
-            .get_mode_targeted(&self.component_id, "session", target)
-            .await
-            .map_err(Self::map_err)?;
+            .retry_read(|| async {
+                self.client()
+                    .await
+                    .get_mode_targeted(&self.component_id, "session", target)
+                    .await
+            })
+            .await?;
 
         let session_name = resp
             .value
@@ -988,6 +1238,7 @@ impl DiagnosticBackend for SovdProxyBackend {
             mode: "session".to_string(),
             session: session_name.to_string(),
             session_id: session_type.as_uds_byte(),
+            timing: None,
         })
     }
 
@@ -998,25 +1249,33 @@ impl DiagnosticBackend for SovdProxyBackend {
 
         let target = self.sub_entity_prefix.as_deref();
         let body = serde_json::json!({ "value": session });
-        self.client
-            .set_mode_targeted(&self.component_id, "session", body, target)
-            .await
-            .map_err(Self::map_err)?;
+        self.call(|| async {
+            self.client()
+                .await
+                .set_mode_targeted(&self.component_id, "session", body.clone(), target)
+                .await
+        })
+        .await
+        .map_err(Self::map_err)?;
 
         Ok(SessionMode {
             mode: "session".to_string(),
             session: session.to_string(),
             session_id: session_type.as_uds_byte(),
+            timing: None,
         })
     }
 
     async fn get_security_mode(&self) -> BackendResult<SecurityMode> {
         let target = self.sub_entity_prefix.as_deref();
         let resp = self
-            .client
-            .get_mode_targeted(&self.component_id, "security", target)
-            .await
-            .map_err(Self::map_err)?;
+            .retry_read(|| async {
+                self.client()
+                    .await
+                    .get_mode_targeted(&self.component_id, "security", target)
+                    .await
+            })
+            .await?;
 
         let value_str = resp
             .value
@@ -1062,8 +1321,12 @@ impl DiagnosticBackend for SovdProxyBackend {
         if value.contains("requestseed") {
             let level = sovd_client::SecurityLevel::LEVEL_1;
             let seed = self
-                .client
-                .security_access_request_seed_targeted(&self.component_id, level, target)
+                .call(|| async {
+                    self.client()
+                        .await
+                        .security_access_request_seed_targeted(&self.component_id, level, target)
+                        .await
+                })
                 .await
                 .map_err(Self::map_err)?;
 
@@ -1077,10 +1340,14 @@ impl DiagnosticBackend for SovdProxyBackend {
             })
         } else if let Some(key_bytes) = key {
             let level = sovd_client::SecurityLevel::LEVEL_1;
-            self.client
-                .security_access_send_key_targeted(&self.component_id, level, key_bytes, target)
-                .await
-                .map_err(Self::map_err)?;
+            self.call(|| async {
+                self.client()
+                    .await
+                    .security_access_send_key_targeted(&self.component_id, level, key_bytes, target)
+                    .await
+            })
+            .await
+            .map_err(Self::map_err)?;
 
             Ok(SecurityMode {
                 mode: "security".to_string(),
@@ -1092,8 +1359,12 @@ impl DiagnosticBackend for SovdProxyBackend {
         } else {
             let body = serde_json::json!({ "value": value });
             let resp = self
-                .client
-                .set_mode_targeted(&self.component_id, "security", body, target)
+                .call(|| async {
+                    self.client()
+                        .await
+                        .set_mode_targeted(&self.component_id, "security", body.clone(), target)
+                        .await
+                })
                 .await
                 .map_err(Self::map_err)?;
 
@@ -1122,11 +1393,7 @@ impl DiagnosticBackend for SovdProxyBackend {
         tracing::info!(url = %url, size = data.len(), "Proxy: uploading package");
 
         let response = self
-            .client
-            .http_client()
-            .post(&url)
-            .body(data.to_vec())
-            .send()
+            .send_upstream(|http| http.post(&url).body(data.to_vec()))
             .await
             .map_err(|e| BackendError::Transport(e.to_string()))?;
 
@@ -1152,8 +1419,12 @@ impl DiagnosticBackend for SovdProxyBackend {
         // Convert PackageStream to reqwest Body for stream-forwarding
         let body = reqwest::Body::wrap_stream(stream);
 
+        // A stream cannot be sent twice: on 401 the token is refreshed for
+        // the next request, but this one fails
+        let generation = self.upstream.generation().await;
         let mut req = self
-            .client
+            .client()
+            .await
             .http_client()
             .post(&url)
             .header("Content-Type", "application/octet-stream");
@@ -1171,6 +1442,9 @@ impl DiagnosticBackend for SovdProxyBackend {
             .await
             .map_err(|e| BackendError::Transport(e.to_string()))?;
 
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            self.upstream.refresh(generation).await;
+        }
         if !response.status().is_success() {
             return Err(Self::map_response_error(response).await);
         }
@@ -1184,17 +1458,7 @@ impl DiagnosticBackend for SovdProxyBackend {
 
     async fn list_packages(&self) -> BackendResult<Vec<PackageInfo>> {
         let url = self.flash_url("/files")?;
-        let response = self
-            .client
-            .http_client()
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| BackendError::Transport(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(Self::map_response_error(response).await);
-        }
+        let response = self.get_upstream(&url).await?;
 
         let resp: ListFilesResp = response
             .json()
@@ -1206,17 +1470,7 @@ impl DiagnosticBackend for SovdProxyBackend {
 
     async fn get_package(&self, package_id: &str) -> BackendResult<PackageInfo> {
         let url = self.flash_url(&format!("/files/{}", package_id))?;
-        let response = self
-            .client
-            .http_client()
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| BackendError::Transport(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(Self::map_response_error(response).await);
-        }
+        let response = self.get_upstream(&url).await?;
 
         response
             .json()
@@ -1227,10 +1481,7 @@ impl DiagnosticBackend for SovdProxyBackend {
     async fn verify_package(&self, package_id: &str) -> BackendResult<VerifyResult> {
         let url = self.flash_url(&format!("/files/{}/verify", package_id))?;
         let response = self
-            .client
-            .http_client()
-            .post(&url)
-            .send()
+            .send_upstream(|http| http.post(&url))
             .await
             .map_err(|e| BackendError::Transport(e.to_string()))?;
 
@@ -1247,10 +1498,7 @@ impl DiagnosticBackend for SovdProxyBackend {
     async fn delete_package(&self, package_id: &str) -> BackendResult<()> {
         let url = self.flash_url(&format!("/files/{}", package_id))?;
         let response = self
-            .client
-            .http_client()
-            .delete(&url)
-            .send()
+            .send_upstream(|http| http.delete(&url))
             .await
             .map_err(|e| BackendError::Transport(e.to_string()))?;
 
@@ -1272,11 +1520,7 @@ impl DiagnosticBackend for SovdProxyBackend {
         tracing::info!(url = %url, "Proxy: starting flash transfer");
 
         let response = self
-            .client
-            .http_client()
-            .post(&url)
-            .json(&body)
-            .send()
+            .send_upstream(|http| http.post(&url).json(&body))
             .await
             .map_err(|e| BackendError::Transport(e.to_string()))?;
 
@@ -1293,17 +1537,7 @@ impl DiagnosticBackend for SovdProxyBackend {
 
     async fn get_flash_status(&self, transfer_id: &str) -> BackendResult<FlashStatus> {
         let url = self.flash_url(&format!("/flash/transfer/{}", transfer_id))?;
-        let response = self
-            .client
-            .http_client()
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| BackendError::Transport(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(Self::map_response_error(response).await);
-        }
+        let response = self.get_upstream(&url).await?;
 
         response
             .json()
@@ -1313,17 +1547,7 @@ impl DiagnosticBackend for SovdProxyBackend {
 
     async fn list_flash_transfers(&self) -> BackendResult<Vec<FlashStatus>> {
         let url = self.flash_url("/flash/transfer")?;
-        let response = self
-            .client
-            .http_client()
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| BackendError::Transport(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(Self::map_response_error(response).await);
-        }
+        let response = self.get_upstream(&url).await?;
 
         let resp: ListTransfersResp = response.json().await.map_err(|e| {
             BackendError::Protocol(format!("Failed to parse transfers list: {}", e))
@@ -1335,10 +1559,7 @@ impl DiagnosticBackend for SovdProxyBackend {
     async fn abort_flash(&self, transfer_id: &str) -> BackendResult<()> {
         let url = self.flash_url(&format!("/flash/transfer/{}", transfer_id))?;
         let response = self
-            .client
-            .http_client()
-            .delete(&url)
-            .send()
+            .send_upstream(|http| http.delete(&url))
             .await
             .map_err(|e| BackendError::Transport(e.to_string()))?;
 
@@ -1350,29 +1571,26 @@ impl DiagnosticBackend for SovdProxyBackend {
     }
 
     async fn finalize_flash(&self) -> BackendResult<()> {
-        let url = self.flash_url("/flash/transferexit")?;
-        let response = self
-            .client
-            .http_client()
-            .put(&url)
-            .send()
-            .await
-            .map_err(|e| BackendError::Transport(e.to_string()))?;
+        self.invalidating(async {
+            let url = self.flash_url("/flash/transferexit")?;
+            let response = self
+                .send_upstream(|http| http.put(&url))
+                .await
+                .map_err(|e| BackendError::Transport(e.to_string()))?;
 
-        if !response.status().is_success() {
-            return Err(Self::map_response_error(response).await);
-        }
+            if !response.status().is_success() {
+                return Err(Self::map_response_error(response).await);
+            }
 
-        Ok(())
+            Ok(())
+        })
+        .await
     }
 
     async fn validate(&self) -> BackendResult<()> {
         let url = self.flash_url("/flash/validate")?;
         let response = self
-            .client
-            .http_client()
-            .post(&url)
-            .send()
+            .send_upstream(|http| http.post(&url))
             .await
             .map_err(|e| BackendError::Transport(e.to_string()))?;
 
@@ -1384,86 +1602,76 @@ impl DiagnosticBackend for SovdProxyBackend {
     }
 
     async fn invalidate(&self) -> BackendResult<()> {
-        let url = self.flash_url("/flash/invalidate")?;
-        let response = self
-            .client
-            .http_client()
-            .post(&url)
-            .send()
-            .await
-            .map_err(|e| BackendError::Transport(e.to_string()))?;
+        self.invalidating(async {
+            let url = self.flash_url("/flash/invalidate")?;
+            let response = self
+                .send_upstream(|http| http.post(&url))
+                .await
+                .map_err(|e| BackendError::Transport(e.to_string()))?;
 
-        if !response.status().is_success() {
-            return Err(Self::map_response_error(response).await);
-        }
+            if !response.status().is_success() {
+                return Err(Self::map_response_error(response).await);
+            }
 
-        Ok(())
+            Ok(())
+        })
+        .await
     }
 
     async fn activate(&self) -> BackendResult<()> {
-        let url = self.flash_url("/flash/activate")?;
-        let response = self
-            .client
-            .http_client()
-            .post(&url)
-            .send()
-            .await
-            .map_err(|e| BackendError::Transport(e.to_string()))?;
+        self.invalidating(async {
+            let url = self.flash_url("/flash/activate")?;
+            let response = self
+                .send_upstream(|http| http.post(&url))
+                .await
+                .map_err(|e| BackendError::Transport(e.to_string()))?;
 
-        if !response.status().is_success() {
-            return Err(Self::map_response_error(response).await);
-        }
+            if !response.status().is_success() {
+                return Err(Self::map_response_error(response).await);
+            }
 
-        Ok(())
+            Ok(())
+        })
+        .await
     }
 
     async fn commit_flash(&self) -> BackendResult<()> {
-        let url = self.flash_url("/flash/commit")?;
-        let response = self
-            .client
-            .http_client()
-            .post(&url)
-            .send()
-            .await
-            .map_err(|e| BackendError::Transport(e.to_string()))?;
+        self.invalidating(async {
+            let url = self.flash_url("/flash/commit")?;
+            let response = self
+                .send_upstream(|http| http.post(&url))
+                .await
+                .map_err(|e| BackendError::Transport(e.to_string()))?;
 
-        if !response.status().is_success() {
-            return Err(Self::map_response_error(response).await);
-        }
+            if !response.status().is_success() {
+                return Err(Self::map_response_error(response).await);
+            }
 
-        Ok(())
+            Ok(())
+        })
+        .await
     }
 
     async fn rollback_flash(&self) -> BackendResult<()> {
-        let url = self.flash_url("/flash/rollback")?;
-        let response = self
-            .client
-            .http_client()
-            .post(&url)
-            .send()
-            .await
-            .map_err(|e| BackendError::Transport(e.to_string()))?;
+        self.invalidating(async {
+            let url = self.flash_url("/flash/rollback")?;
+            let response = self
+                .send_upstream(|http| http.post(&url))
+                .await
+                .map_err(|e| BackendError::Transport(e.to_string()))?;
 
-        if !response.status().is_success() {
-            return Err(Self::map_response_error(response).await);
-        }
+            if !response.status().is_success() {
+                return Err(Self::map_response_error(response).await);
+            }
 
-        Ok(())
+            Ok(())
+        })
+        .await
     }
 
     async fn get_activation_state(&self) -> BackendResult<ActivationState> {
         let url = self.flash_url("/flash/activation")?;
-        let response = self
-            .client
-            .http_client()
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| BackendError::Transport(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(Self::map_response_error(response).await);
-        }
+        let response = self.get_upstream(&url).await?;
 
         response
             .json()
@@ -0,0 +1,243 @@
+//! Retry and caching for upstream reads
+//!
+//! An OEM gateway in front of the upstream answers the odd 502/504 while
+//! it restarts or reconnects an ECU, and testers read the same
+//! identification DIDs over and over. Idempotent reads are therefore
+//! retried with exponential backoff, and identification values can be
+//! served from a short-lived cache. Writes, operations, resets and flash
+//! steps are never retried: a lost response does not mean the upstream
+//! did not act on them.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use sovd_client::{DataResponse, SovdClientError};
+use sovd_core::DataCategory;
+
+/// Retry policy for idempotent upstream reads
+///
+/// ```toml
+/// [proxy.tier1.retry]
+/// max_attempts = 4
+/// base_delay_ms = 200
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    /// Attempts per read, the first one included; 1 disables retry
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubles with every further one
+    pub base_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_ms: 100,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// No retry: every read is tried once
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Wait before retry number `retry` (1 for the first)
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        let factor = 1u64 << retry.saturating_sub(1).min(16);
+        Duration::from_millis(self.base_delay_ms.saturating_mul(factor))
+    }
+}
+
+/// Identification DID cache
+///
+/// ```toml
+/// [proxy.tier1.cache]
+/// identification_ttl_ms = 5000
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// How long an identification value is served from the cache; unset
+    /// disables the cache
+    pub identification_ttl_ms: Option<u64>,
+}
+
+/// Whether a failed read may succeed when simply tried again: the
+/// upstream (or a gateway in front of it) was unavailable, not the
+/// request wrong
+pub(crate) fn is_transient(e: &SovdClientError) -> bool {
+    match e {
+        SovdClientError::ServerError { status, .. } => matches!(status, 502..=504),
+        SovdClientError::HttpError(e) => e.is_connect() || e.is_timeout(),
+        SovdClientError::Timeout | SovdClientError::ConnectionFailed(_) => true,
+        _ => false,
+    }
+}
+
+/// Whether an upstream HTTP status is worth retrying a read for
+pub(crate) fn is_transient_status(status: u16) -> bool {
+    matches!(status, 502..=504)
+}
+
+/// Identification values read recently, keyed by the upstream data ID
+pub(crate) struct IdentificationCache {
+    ttl: Option<Duration>,
+    entries: Mutex<HashMap<String, (Instant, DataResponse)>>,
+    /// Bumped by every [`Self::clear`], so a read that started before it
+    /// cannot put its (possibly stale) value back
+    generation: AtomicU64,
+}
+
+impl IdentificationCache {
+    pub(crate) fn new(config: &CacheConfig) -> Self {
+        Self {
+            ttl: config.identification_ttl_ms.map(Duration::from_millis),
+            entries: Mutex::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Cached value of `id`, unless it has expired
+    pub(crate) fn get(&self, id: &str) -> Option<DataResponse> {
+        let ttl = self.ttl?;
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(id) {
+            Some((read_at, value)) if read_at.elapsed() < ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Generation to capture before an upstream read and hand to
+    /// [`Self::insert`]
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Remember `value` for `id` when it is an identification DID, unless
+    /// the cache was cleared since `generation` was captured
+    pub(crate) fn insert(&self, id: &str, value: &DataResponse, generation: u64) {
+        let identification = value
+            .did
+            .as_deref()
+            .is_some_and(|did| DataCategory::from_did_str(did) == DataCategory::IdentData);
+        if self.ttl.is_none() || !identification {
+            return;
+        }
+        // Checked under the lock `clear` bumps under
+        let mut entries = self.entries.lock().unwrap();
+        if self.generation.load(Ordering::SeqCst) == generation {
+            entries.insert(id.to_string(), (Instant::now(), value.clone()));
+        }
+    }
+
+    /// An empty cache with the same TTL
+    pub(crate) fn empty_copy(&self) -> Self {
+        Self {
+            ttl: self.ttl,
+            entries: Mutex::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Forget everything, e.g. after a write, a reset or new software
+    pub(crate) fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(did: &str) -> DataResponse {
+        serde_json::from_value(serde_json::json!({
+            "did": did,
+            "value": "WVW0000000000000",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn backoff_doubles_from_the_base_delay() {
+        let retry = RetryConfig {
+            max_attempts: 4,
+            base_delay_ms: 100,
+        };
+        assert_eq!(retry.delay(1), Duration::from_millis(100));
+        assert_eq!(retry.delay(2), Duration::from_millis(200));
+        assert_eq!(retry.delay(3), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn only_gateway_and_availability_failures_are_transient() {
+        let status = |status| SovdClientError::ServerError {
+            status,
+            message: String::new(),
+        };
+        assert!(is_transient(&status(502)));
+        assert!(is_transient(&status(504)));
+        assert!(is_transient(&SovdClientError::Timeout));
+        assert!(!is_transient(&status(500)));
+        assert!(!is_transient(&status(404)));
+        assert!(!is_transient(&SovdClientError::ParameterNotFound(
+            "vin".to_string()
+        )));
+    }
+
+    #[test]
+    fn cache_keeps_only_identification_dids() {
+        let cache = IdentificationCache::new(&CacheConfig {
+            identification_ttl_ms: Some(60_000),
+        });
+        cache.insert("vin", &response("F190"), cache.generation());
+        cache.insert("engine_rpm", &response("F40C"), cache.generation());
+        assert!(cache.get("vin").is_some());
+        assert!(cache.get("engine_rpm").is_none());
+
+        cache.clear();
+        assert!(cache.get("vin").is_none());
+    }
+
+    #[test]
+    fn cache_entries_expire() {
+        let cache = IdentificationCache::new(&CacheConfig {
+            identification_ttl_ms: Some(0),
+        });
+        cache.insert("vin", &response("F190"), cache.generation());
+        assert!(cache.get("vin").is_none());
+
+        let disabled = IdentificationCache::new(&CacheConfig::default());
+        disabled.insert("vin", &response("F190"), disabled.generation());
+        assert!(disabled.get("vin").is_none());
+    }
+
+    #[test]
+    fn insert_from_before_a_clear_is_dropped() {
+        let cache = IdentificationCache::new(&CacheConfig {
+            identification_ttl_ms: Some(60_000),
+        });
+        let before = cache.generation();
+        cache.clear();
+        cache.insert("vin", &response("F190"), before);
+        assert!(cache.get("vin").is_none());
+
+        cache.insert("vin", &response("F190"), cache.generation());
+        assert!(cache.get("vin").is_some());
+    }
+}
@@ -0,0 +1,196 @@
+//! Refreshable upstream bearer tokens
+//!
+//! A fixed `auth_token` stops working once the upstream's token expires.
+//! A proxy built with a [`TokenProvider`] instead asks it for a token on
+//! connect and again whenever the upstream answers 401, then sends the
+//! refused request once more. The client carrying the current token sits
+//! behind a `tokio::sync::RwLock`: requests share it for reading, and the
+//! first one refused takes the write lock to refresh while the others
+//! wait for its token instead of fetching their own.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sovd_client::{SovdClient, SovdClientError};
+use tokio::sync::RwLock;
+
+/// Source of bearer tokens for the upstream server
+///
+/// Implemented for async closures, so a provider can be as short as
+/// `|| async { fetch_token().await }`.
+#[async_trait]
+pub trait TokenProvider: Send + Sync {
+    /// A token the upstream currently accepts
+    async fn token(&self) -> Result<String, String>;
+}
+
+#[async_trait]
+impl<F, Fut> TokenProvider for F
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Result<String, String>> + Send,
+{
+    async fn token(&self) -> Result<String, String> {
+        self().await
+    }
+}
+
+/// Whether the upstream refused the request's credentials
+pub(crate) fn is_unauthorized(e: &SovdClientError) -> bool {
+    matches!(e, SovdClientError::ServerError { status: 401, .. })
+}
+
+/// The current upstream client
+struct Current {
+    /// Bumped on every refresh, so a refused request can tell whether the
+    /// token it was sent with has been replaced already
+    generation: u64,
+    client: SovdClient,
+}
+
+/// Client for the upstream server, re-created with a new token when the
+/// provider (if any) hands one out
+pub(crate) struct Upstream {
+    base_url: String,
+    provider: Option<Arc<dyn TokenProvider>>,
+    current: RwLock<Current>,
+}
+
+impl Upstream {
+    /// Upstream with a fixed token, or none
+    pub(crate) fn fixed(base_url: &str, auth_token: Option<&str>) -> Result<Self, String> {
+        let client = match auth_token {
+            Some(token) => SovdClient::with_bearer_token(base_url, token),
+            None => SovdClient::new(base_url),
+        }
+        .map_err(|e| format!("Failed to create client: {}", e))?;
+        Ok(Self::with_client(base_url, None, client))
+    }
+
+    /// Upstream authenticated with tokens from `provider`, holding its
+    /// first one
+    pub(crate) async fn with_provider(
+        base_url: &str,
+        provider: Arc<dyn TokenProvider>,
+    ) -> Result<Self, String> {
+        let client = Self::connect(base_url, provider.as_ref()).await?;
+        Ok(Self::with_client(base_url, Some(provider), client))
+    }
+
+    fn with_client(
+        base_url: &str,
+        provider: Option<Arc<dyn TokenProvider>>,
+        client: SovdClient,
+    ) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            provider,
+            current: RwLock::new(Current {
+                generation: 0,
+                client,
+            }),
+        }
+    }
+
+    async fn connect(base_url: &str, provider: &dyn TokenProvider) -> Result<SovdClient, String> {
+        let token = provider
+            .token()
+            .await
+            .map_err(|e| format!("Failed to get upstream token: {}", e))?;
+        SovdClient::with_bearer_token(base_url, &token)
+            .map_err(|e| format!("Failed to create client: {}", e))
+    }
+
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Client carrying the current token
+    pub(crate) async fn client(&self) -> SovdClient {
+        self.current.read().await.client.clone()
+    }
+
+    /// Generation of the current token; pass it to [`Self::refresh`]
+    pub(crate) async fn generation(&self) -> u64 {
+        self.current.read().await.generation
+    }
+
+    /// Replace the token of `generation` after the upstream refused it.
+    /// Returns whether a newer token is in place, i.e. whether the refused
+    /// request is worth sending again.
+    pub(crate) async fn refresh(&self, generation: u64) -> bool {
+        let Some(provider) = &self.provider else {
+            return false;
+        };
+        let mut current = self.current.write().await;
+        if current.generation != generation {
+            // Another request refreshed while this one waited for the lock
+            return true;
+        }
+        match Self::connect(&self.base_url, provider.as_ref()).await {
+            Ok(client) => {
+                current.client = client;
+                current.generation += 1;
+                tracing::info!(base_url = %self.base_url, "Proxy: refreshed upstream token");
+                true
+            }
+            Err(e) => {
+                tracing::warn!(base_url = %self.base_url, error = %e, "Proxy: token refresh failed");
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn counting_provider(fetches: Arc<AtomicU32>) -> Arc<dyn TokenProvider> {
+        Arc::new(move || {
+            let fetches = fetches.clone();
+            async move {
+                let n = fetches.fetch_add(1, Ordering::SeqCst);
+                Ok::<_, String>(format!("token-{}", n))
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn concurrent_refreshes_fetch_one_token() {
+        let fetches = Arc::new(AtomicU32::new(0));
+        let upstream =
+            Upstream::with_provider("http://localhost:9", counting_provider(fetches.clone()))
+                .await
+                .unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        // Both requests were refused with the token of generation 0
+        let (first, second) = tokio::join!(upstream.refresh(0), upstream.refresh(0));
+        assert!(first && second);
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+        assert_eq!(upstream.generation().await, 1);
+    }
+
+    #[tokio::test]
+    async fn fixed_tokens_are_not_refreshed() {
+        let upstream = Upstream::fixed("http://localhost:9", Some("static")).unwrap();
+        assert!(!upstream.refresh(0).await);
+        assert_eq!(upstream.generation().await, 0);
+    }
+
+    #[tokio::test]
+    async fn failed_refresh_keeps_the_token() {
+        let provider: Arc<dyn TokenProvider> =
+            Arc::new(|| async { Err::<String, _>("idp down".to_string()) });
+        let upstream = Upstream::with_client(
+            "http://localhost:9",
+            Some(provider),
+            SovdClient::new("http://localhost:9").unwrap(),
+        );
+        assert!(!upstream.refresh(0).await);
+        assert_eq!(upstream.generation().await, 0);
+    }
+}
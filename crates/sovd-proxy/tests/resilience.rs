@@ -0,0 +1,237 @@
+//! Retry and identification cache of the proxy against an in-process
+//! upstream that answers 502 on demand.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::extract::{Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use sovd_api::{create_router, AppState};
+use sovd_client::testing::TestServer;
+use sovd_core::{
+    BackendError, BackendResult, Capabilities, DataValue, DiagnosticBackend, EntityInfo,
+    FaultFilter, FaultsResult, OperationExecution, OperationInfo, ParameterInfo,
+};
+use sovd_proxy::{CacheConfig, RetryConfig, SovdProxyBackend};
+
+const VIN: u16 = 0xF190;
+
+/// Upstream ECU holding DID values in memory
+struct UpstreamEcu {
+    info: EntityInfo,
+    capabilities: Capabilities,
+    dids: Mutex<HashMap<u16, Vec<u8>>>,
+}
+
+impl UpstreamEcu {
+    fn new() -> Self {
+        Self {
+            info: EntityInfo {
+                id: "ecu".to_string(),
+                name: "Upstream ECU".to_string(),
+                entity_type: "ecu".to_string(),
+                description: None,
+                href: "/vehicle/v1/components/ecu".to_string(),
+                status: Some("online".to_string()),
+                display_name: None,
+                category: None,
+            },
+            capabilities: Capabilities::uds_ecu(),
+            dids: Mutex::new(HashMap::from([(VIN, b"WVW0000000000001".to_vec())])),
+        }
+    }
+}
+
+#[async_trait]
+impl DiagnosticBackend for UpstreamEcu {
+    fn entity_info(&self) -> &EntityInfo {
+        &self.info
+    }
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+    async fn list_parameters(&self) -> BackendResult<Vec<ParameterInfo>> {
+        Ok(vec![])
+    }
+    async fn read_data(&self, _ids: &[String]) -> BackendResult<Vec<DataValue>> {
+        Ok(vec![])
+    }
+    async fn read_raw_did(&self, did: u16) -> BackendResult<Vec<u8>> {
+        self.dids
+            .lock()
+            .unwrap()
+            .get(&did)
+            .cloned()
+            .ok_or_else(|| BackendError::ParameterNotFound(format!("{did:04X}")))
+    }
+    async fn write_raw_did(&self, did: u16, data: &[u8]) -> BackendResult<()> {
+        self.dids.lock().unwrap().insert(did, data.to_vec());
+        Ok(())
+    }
+    async fn get_faults(&self, _filter: Option<&FaultFilter>) -> BackendResult<FaultsResult> {
+        Ok(FaultsResult {
+            faults: vec![],
+            status_availability_mask: None,
+        })
+    }
+    async fn list_operations(&self) -> BackendResult<Vec<OperationInfo>> {
+        Ok(vec![])
+    }
+    async fn start_operation(&self, op: &str, _params: &[u8]) -> BackendResult<OperationExecution> {
+        Err(BackendError::OperationNotFound(op.to_string()))
+    }
+}
+
+/// Data requests reaching the upstream, and the ones it should fail with 502
+#[derive(Default)]
+struct Upstream {
+    reads: AtomicUsize,
+    writes: AtomicUsize,
+    failing_reads: AtomicUsize,
+    failing_writes: AtomicBool,
+    /// Hold read responses back this long after the upstream answered
+    read_delay_ms: AtomicU64,
+}
+
+impl Upstream {
+    /// Whether the next read should fail, counting it down
+    fn take_read_failure(&self) -> bool {
+        self.failing_reads
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+    }
+}
+
+/// Count data requests, answering 502 (as a gateway in front of the
+/// upstream would) while `upstream` asks for failures
+async fn flaky_gateway(
+    State(upstream): State<Arc<Upstream>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.uri().path().contains("/data/") {
+        if request.method() == Method::GET {
+            upstream.reads.fetch_add(1, Ordering::SeqCst);
+            if upstream.take_read_failure() {
+                return StatusCode::BAD_GATEWAY.into_response();
+            }
+            let delay = upstream.read_delay_ms.load(Ordering::SeqCst);
+            let response = next.run(request).await;
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            return response;
+        } else if request.method() == Method::PUT {
+            upstream.writes.fetch_add(1, Ordering::SeqCst);
+            if upstream.failing_writes.load(Ordering::SeqCst) {
+                return StatusCode::BAD_GATEWAY.into_response();
+            }
+        }
+    }
+    next.run(request).await
+}
+
+/// Upstream server behind a flaky gateway, and a proxy to its ECU
+async fn proxy(retry: RetryConfig) -> (TestServer, Arc<Upstream>, SovdProxyBackend) {
+    let upstream = Arc::new(Upstream::default());
+    let ecu: Arc<dyn DiagnosticBackend> = Arc::new(UpstreamEcu::new());
+    let router = create_router(AppState::new(HashMap::from([("ecu".to_string(), ecu)]))).layer(
+        middleware::from_fn_with_state(upstream.clone(), flaky_gateway),
+    );
+    let server = TestServer::start(router).await.expect("test server");
+    let proxy = SovdProxyBackend::new("proxied", &server.base_url(), "ecu")
+        .await
+        .expect("proxy")
+        .with_retry(retry)
+        .with_cache(CacheConfig {
+            identification_ttl_ms: Some(60_000),
+        });
+    (server, upstream, proxy)
+}
+
+fn retry(max_attempts: u32) -> RetryConfig {
+    RetryConfig {
+        max_attempts,
+        base_delay_ms: 1,
+    }
+}
+
+#[tokio::test]
+async fn read_is_retried_on_bad_gateway() {
+    let (_server, upstream, proxy) = proxy(retry(3)).await;
+    upstream.failing_reads.store(2, Ordering::SeqCst);
+
+    let vin = proxy.read_raw_did(VIN).await.expect("read");
+
+    assert_eq!(vin, b"WVW0000000000001");
+    assert_eq!(upstream.reads.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn failed_write_is_sent_once() {
+    let (_server, upstream, proxy) = proxy(retry(3)).await;
+    upstream.failing_writes.store(true, Ordering::SeqCst);
+
+    assert!(proxy.write_raw_did(VIN, b"WVW0000000000002").await.is_err());
+    assert_eq!(upstream.writes.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn write_drops_cached_identification() {
+    let (_server, upstream, proxy) = proxy(retry(1)).await;
+
+    proxy.read_raw_did(VIN).await.expect("read");
+    proxy.read_raw_did(VIN).await.expect("cached read");
+    assert_eq!(upstream.reads.load(Ordering::SeqCst), 1);
+
+    proxy
+        .write_raw_did(VIN, b"WVW0000000000002")
+        .await
+        .expect("write");
+    let vin = proxy.read_raw_did(VIN).await.expect("read after write");
+
+    assert_eq!(vin, b"WVW0000000000002");
+    assert_eq!(upstream.reads.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn rollback_drops_cached_identification_even_when_it_fails() {
+    let (_server, upstream, proxy) = proxy(retry(1)).await;
+
+    proxy.read_raw_did(VIN).await.expect("read");
+    // The stub upstream has no flash rollback, so this fails; the ECU may
+    // still have switched images, so the cache must not be trusted
+    assert!(proxy.rollback_flash().await.is_err());
+    proxy.read_raw_did(VIN).await.expect("read after rollback");
+
+    assert_eq!(upstream.reads.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn read_racing_a_write_does_not_cache_the_old_value() {
+    let (_server, upstream, proxy) = proxy(retry(1)).await;
+    let proxy = Arc::new(proxy);
+
+    // The read fetches the old VIN, but its response arrives after the write
+    upstream.read_delay_ms.store(300, Ordering::SeqCst);
+    let racing = tokio::spawn({
+        let proxy = proxy.clone();
+        async move { proxy.read_raw_did(VIN).await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    proxy
+        .write_raw_did(VIN, b"WVW0000000000002")
+        .await
+        .expect("write");
+    let raced = racing.await.unwrap().expect("racing read");
+    assert_eq!(raced, b"WVW0000000000001");
+
+    upstream.read_delay_ms.store(0, Ordering::SeqCst);
+    let vin = proxy.read_raw_did(VIN).await.expect("read after write");
+
+    assert_eq!(vin, b"WVW0000000000002");
+    assert_eq!(upstream.reads.load(Ordering::SeqCst), 2);
+}
@@ -315,9 +315,10 @@ block_counter_wrap = {}
         let workspace = Self::workspace_root();
 
         // Per-ECU sub-tables injected under [ecu.vtx_ecm] before its
-        // operations/outputs arrays: flash commit/rollback and (optionally)
-        // transparent server-side SecurityAccess. The unlock secret matches the
-        // example-ecu default (0xFF) so its real gate accepts the server key.
+        // operations/outputs arrays: flash commit/rollback, DTC re-read after
+        // clear and (optionally) transparent server-side SecurityAccess. The
+        // unlock secret matches the example-ecu default (0xFF) so its real gate
+        // accepts the server key.
         let flash_section = if self.options.supports_rollback {
             "[ecu.vtx_ecm.flash]\nsupports_rollback = true\ncommit_routine = \"0xFF01\"\nrollback_routine = \"0xFF02\"\n"
         } else {
@@ -328,7 +329,11 @@ block_counter_wrap = {}
         } else {
             ""
         };
-        let extra_ecu_sections = format!("{}{}", flash_section, unlock_section);
+        let fault_memory_section = "[ecu.vtx_ecm.fault_memory]\nverify_clear = true\n";
+        let extra_ecu_sections = format!(
+            "{}{}{}",
+            flash_section, fault_memory_section, unlock_section
+        );
 
         let content = format!(
             r#"
@@ -2260,6 +2265,15 @@ async fn test_clear_faults() {
     assert!(clear_resp.success, "Expected success from clear_faults");
     eprintln!("DTCs cleared successfully");
 
+    // The harness enables verify_clear, so the server re-read the DTCs
+    assert_eq!(
+        clear_resp.cleared_confirmed,
+        Some(true),
+        "Expected the clear to be confirmed, remaining: {:?}",
+        clear_resp.remaining
+    );
+    assert!(clear_resp.remaining.is_empty());
+
     // Verify DTCs are cleared
     let final_faults = client
         .get_faults("vtx_ecm")
@@ -4,32 +4,45 @@
 //! for traditional ECUs accessible via UDS over CAN/ISO-TP.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use chrono::Utc;
+use futures::StreamExt;
 use parking_lot::RwLock;
 use sovd_core::{
-    ActivationState, BackendError, BackendResult, Capabilities, ClearFaultsResult, CommControlMode,
-    DataPoint, DataValue, DiagnosticBackend, DtcSettingMode, EntityInfo, Fault, FaultFilter,
-    FaultSeverity, FaultsResult, FlashProgress, FlashState, FlashStatus, IoControlAction,
-    IoControlResult, LinkControlResult, LinkMode, LogEntry, LogFilter, OperationExecution,
-    OperationInfo, OperationStatus, OutputDetail, OutputInfo, PackageInfo, PackageStatus,
-    ParameterInfo, SecurityMode, SecurityState, SessionMode, SoftwareInfo, VerifyResult,
+    ActivationState, BackendError, BackendResult, Capabilities, ClearFaultsResult,
+    ClearVerification, CommControlMode, DataPoint, DataValue, DiagnosticBackend, DtcSettingMode,
+    EntityInfo, Fault, FaultExtendedData, FaultFilter, FaultMemory, FaultSeverity, FaultSnapshot,
+    FaultSnapshotItem, FaultsResult, Fingerprint, FlashPlan, FlashProgress, FlashState,
+    FlashStatus, FlashSummary, HealthStatus, IoControlAction, IoControlResult, LinkControlResult,
+    LinkMode, LogEntry, LogFilter, OperationExecution, OperationInfo, OperationStatus,
+    OutputDetail, OutputInfo, PackageInfo, PackageStatus, PackageStream, ParameterInfo,
+    SecurityMode, SecurityState, SessionMode, SessionTimingParameters, SoftwareInfo,
+    TransportMetrics, TransportStats, VerifyResult,
 };
 use tokio::sync::broadcast;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use crate::config::{FlashCommitConfig, UdsBackendConfig};
+use crate::config::{DtcReport, FlashCommitConfig, OperationConfig, UdsBackendConfig};
 use crate::error::UdsBackendError;
 use crate::output_conv;
+use crate::routine_conv;
 use crate::session::{SessionError, SessionManager};
+use crate::signature::PackageVerifier;
 use crate::subscription::StreamManager;
-use crate::transport::{create_transport, TransportAdapter};
+use crate::transport::{create_transport, MeteredTransport, TransportAdapter};
 use crate::uds::{
-    dtc::{parse_dtc_by_status_mask_response, status_bit, Dtc},
-    link_baud_rate, NegativeResponseCode, ServiceIds, UdsError, UdsService,
+    dtc::{
+        parse_dtc_by_severity_mask_response, parse_dtc_by_status_mask_response,
+        parse_dtc_extended_data_records, parse_dtc_snapshot_records,
+        parse_user_def_memory_dtc_by_status_mask_response, severity_bit, status_bit, Dtc,
+        SnapshotParseError,
+    },
+    fingerprint, link_baud_rate, standard_did, CompressionMethod, NegativeResponseCode,
+    RequestQueue, ServiceIds, UdsError, UdsService,
 };
 use crate::unlock::{provider_from_config, UnlockProvider};
 
@@ -54,6 +67,19 @@ pub enum IoControlState {
     DefaultReset,
 }
 
+/// The last successful 0x2F command for an IOID
+#[derive(Debug, Clone)]
+struct IoControlRecord {
+    state: IoControlState,
+    action: &'static str,
+    /// Typed value of a short-term adjustment
+    value: Option<serde_json::Value>,
+    at: chrono::DateTime<Utc>,
+    /// Session epoch the command was accepted in; the ECU drops overrides
+    /// with the session
+    session_epoch: u64,
+}
+
 // =============================================================================
 // Internal Package and Flash State
 // =============================================================================
@@ -74,6 +100,8 @@ struct FlashTransfer {
     state: FlashState,
     progress: FlashProgress,
     error: Option<String>,
+    /// Set by the transfer task once the last block is acknowledged
+    summary: Option<FlashSummary>,
     /// Handle to abort the transfer task
     abort_handle: Option<tokio::task::AbortHandle>,
 }
@@ -100,6 +128,74 @@ fn is_security_access_denied(err: &UdsError) -> bool {
     )
 }
 
+/// Map a client-driven SecurityAccess failure to a [`BackendError`], keeping
+/// a lockout typed so the API can answer 429 with `Retry-After`
+fn security_error(err: SessionError) -> BackendError {
+    match err {
+        SessionError::SecurityLockout { remaining_ms } => {
+            BackendError::SecurityLockout { remaining_ms }
+        }
+        other => BackendError::Protocol(other.to_string()),
+    }
+}
+
+/// Largest TransferData message (SID + block counter + data) the tester may
+/// send: the tighter of the transport's own message limit and the configured
+/// `transfer_data_max_block_length`, if either is set.
+fn transfer_block_limit(transport_max: Option<usize>, configured_max: Option<u32>) -> Option<u32> {
+    let transport_max = transport_max.map(|len| u32::try_from(len).unwrap_or(u32::MAX));
+    match (transport_max, configured_max) {
+        (Some(t), Some(c)) => Some(t.min(c)),
+        (t, c) => t.or(c),
+    }
+}
+
+/// Largest DID value one WriteDataByIdentifier may carry: the configured
+/// `write_data_max_length` and the transport's message limit less the SID
+/// and DID bytes, whichever is tighter, if either is set.
+fn write_data_limit(transport_max: Option<usize>, configured_max: Option<u32>) -> Option<usize> {
+    let transport_max = transport_max.map(|len| len.saturating_sub(3));
+    let configured_max = configured_max.map(|len| len as usize);
+    match (transport_max, configured_max) {
+        (Some(t), Some(c)) => Some(t.min(c)),
+        (t, c) => t.or(c),
+    }
+}
+
+/// Split a multi-DID ReadDataByIdentifier response (`62 DID data DID data
+/// ...`) into each DID's data. The records carry no length, so each DID's
+/// echo must occur exactly once after the previous one; `None` when the
+/// response does not split unambiguously.
+fn split_read_data_response(dids: &[u16], response: &[u8]) -> Option<Vec<Vec<u8>>> {
+    let body = response.get(1..)?;
+    if body.get(..2)? != dids.first()?.to_be_bytes() {
+        return None;
+    }
+    let mut starts = vec![0];
+    for did in &dids[1..] {
+        let from = starts.last()? + 2;
+        let echo = did.to_be_bytes();
+        let mut hits = body
+            .get(from..)?
+            .windows(2)
+            .enumerate()
+            .filter(|(_, window)| window[..] == echo)
+            .map(|(i, _)| from + i);
+        starts.push(hits.next()?);
+        if hits.next().is_some() {
+            return None;
+        }
+    }
+    let ends = starts[1..].iter().copied().chain([body.len()]);
+    Some(
+        starts
+            .iter()
+            .zip(ends)
+            .map(|(start, end)| body[start + 2..end].to_vec())
+            .collect(),
+    )
+}
+
 /// UDS diagnostic backend
 ///
 /// Implements the DiagnosticBackend trait for ECUs accessible via UDS over CAN/ISO-TP.
@@ -123,13 +219,19 @@ pub struct UdsBackend {
     packages: Arc<RwLock<HashMap<String, StoredPackage>>>,
     /// Current flash transfer state
     flash_state: Arc<RwLock<Option<FlashTransfer>>>,
+    /// The ECU's one up/download at a time: held by a memory upload, and
+    /// by the flash task from queueing until it finishes
+    transfer_lock: Arc<tokio::sync::Mutex<()>>,
     /// Per-output I/O control state (tester-side bookkeeping).
-    /// Key is the IOID (u16). Cleared on session change per ISO 14229.
-    io_control_states: Arc<RwLock<HashMap<u16, IoControlState>>>,
+    /// Key is the IOID (u16). Overrides taken in an earlier session epoch
+    /// have been released by the ECU per ISO 14229.
+    io_control_states: Arc<RwLock<HashMap<u16, IoControlRecord>>>,
     /// Firmware activation state for commit/rollback flow
     activation_state: Arc<RwLock<ActivationState>>,
     /// Flash commit/rollback configuration
     flash_commit_config: FlashCommitConfig,
+    /// Signature every package must carry to pass verification
+    package_verifier: Option<PackageVerifier>,
     /// Last-set CommunicationControl (0x28) subfunction, kebab-case.
     /// 0x28 is write-only on the wire, so GET returns this tester-side value.
     comm_control_state: Arc<RwLock<String>>,
@@ -139,6 +241,15 @@ pub struct UdsBackend {
     /// Transparent server-side SecurityAccess context, if this ECU configured
     /// an `unlock` section. Shared into the flash task via `Arc`.
     unlock: Option<Arc<TransparentUnlock>>,
+    /// DTCStatusAvailabilityMask, learned from the first 0x19 response.
+    /// Static per ECU, so never re-read once known.
+    dtc_status_availability_mask: RwLock<Option<u8>>,
+    /// Set once the ECU refused the configured severity report (0x19 0x08);
+    /// the primary memory is then read with the status-mask report.
+    severity_report_refused: AtomicBool,
+    /// Synthetic faults raised by routine results (`result_faults`), keyed
+    /// by operation id. Reported alongside the ECU's primary DTCs.
+    routine_faults: RwLock<HashMap<String, Fault>>,
 }
 
 /// CommunicationControl (0x28) subfunctions exposed via `modes/comm-ctrl`,
@@ -169,6 +280,16 @@ const COMM_CONTROL_DEFAULT: &str = "enable-rx-tx";
 /// Initial / power-on ControlDTCSetting state.
 const DTC_SETTING_DEFAULT: &str = "on";
 
+/// Longest a one-shot periodic read (`read_via: periodic`) waits for the
+/// first sample; covers one slow-rate (≈1 Hz) period with margin.
+const PERIODIC_SAMPLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Most a package upload reserves up front from its announced length
+const PACKAGE_RESERVE_LIMIT: u64 = 16 * 1024 * 1024;
+
+/// Most a memory upload reserves up front from its requested size
+const MEMORY_UPLOAD_RESERVE_LIMIT: usize = 1024 * 1024;
+
 /// Map a `modes/comm-ctrl` enum member to its UDS 0x28 subfunction byte.
 fn comm_control_subfunction(value: &str) -> Option<u8> {
     COMM_CONTROL_VALUES
@@ -187,9 +308,48 @@ fn dtc_setting_subfunction(value: &str) -> Option<u8> {
     }
 }
 
+/// Whether a ClearDiagnosticInformation of `dtc_group` covers the DTC
+/// `dtc` names (its 6-hex-digit ID): all of them for 0xFFFFFF, else the
+/// one it equals
+fn in_clear_group(dtc: &str, dtc_group: u32) -> bool {
+    dtc_group == 0xFFFFFF
+        || Dtc::parse_id(dtc)
+            .is_some_and(|b| u32::from_be_bytes([0, b[0], b[1], b[2]]) == dtc_group)
+}
+
 impl UdsBackend {
     /// Create a new UDS backend from configuration
     pub async fn new(config: UdsBackendConfig) -> Result<Self, UdsBackendError> {
+        // Create transport from configuration
+        let transport = create_transport(&config.transport)
+            .await
+            .map_err(|e| UdsBackendError::Transport(e.to_string()))?;
+
+        Self::with_transport(config, transport)
+    }
+
+    /// Create a UDS backend over an already-built transport. `config.transport`
+    /// is not consulted; used for replay sessions and tests.
+    pub fn with_transport(
+        config: UdsBackendConfig,
+        transport: Arc<dyn TransportAdapter>,
+    ) -> Result<Self, UdsBackendError> {
+        // A routine gated on a session this ECU does not define could never
+        // run; refuse the config rather than fail every start
+        for op in &config.operations {
+            if let Some(session) = op.required_session.as_deref() {
+                if config.sessions.session_id(session).is_none() {
+                    return Err(UdsBackendError::Config(format!(
+                        "operation '{}': unknown required_session '{}'",
+                        op.id, session
+                    )));
+                }
+            }
+        }
+
+        // Every request goes through the counters behind `transport_metrics`
+        let transport: Arc<dyn TransportAdapter> = Arc::new(MeteredTransport::new(transport));
+
         let entity_info = EntityInfo {
             id: config.id.clone(),
             name: config.name.clone(),
@@ -197,30 +357,44 @@ impl UdsBackend {
             description: config.description.clone(),
             href: format!("/vehicle/v1/components/{}", config.id),
             status: Some("connected".to_string()),
+            display_name: None,
+            category: None,
         };
 
-        let capabilities = Capabilities::uds_ecu();
-
-        // Create transport from configuration
-        let transport = create_transport(&config.transport)
-            .await
-            .map_err(|e| UdsBackendError::Transport(e.to_string()))?;
+        let capabilities = Capabilities {
+            dtc_reports: config
+                .fault_memory
+                .reports
+                .iter()
+                .map(|report| report.as_str().to_string())
+                .collect(),
+            ..Capabilities::uds_ecu()
+        };
 
         // Create service IDs with any OEM overrides
         let service_ids = ServiceIds::from_overrides(&config.service_overrides);
 
+        // One request at a time to this ECU, whichever service sends it
+        let queue = Arc::new(RequestQueue::new(&config.request_queue));
+
         // Create UDS service layer
-        let uds = UdsService::with_service_ids(transport.clone(), service_ids);
+        let uds = UdsService::with_service_ids(transport.clone(), service_ids)
+            .with_request_queue(queue.clone())
+            .with_memory_format(config.memory.clone())
+            .with_service_policy(config.service_policy.clone())
+            .with_response_pending(config.response_pending.clone())
+            .with_first_frame_retry(config.first_frame_retry.clone())
+            .with_timing(config.timing.clone());
 
         // Create session manager
-        let session_manager = Arc::new(SessionManager::with_service_ids(
+        let session_manager = Arc::new(SessionManager::with_uds(
             transport.clone(),
             config.sessions.clone(),
-            service_ids,
+            uds.clone(),
         ));
 
         // Create stream manager for periodic data
-        let stream_manager = Arc::new(StreamManager::new(transport.clone(), config.clone()));
+        let stream_manager = Arc::new(StreamManager::new(transport.clone(), config.clone(), queue));
 
         // Transparent server-side SecurityAccess (UDS 0x27), if configured.
         // The level is taken from an explicit override, else the ECU's
@@ -243,6 +417,15 @@ impl UdsBackend {
         };
 
         let flash_commit_config = config.flash_commit.clone();
+        let package_verifier = match &flash_commit_config.signature {
+            Some(cfg) => {
+                let verifier = PackageVerifier::from_config(cfg)
+                    .map_err(|e| UdsBackendError::Config(format!("flash.signature: {}", e)))?;
+                info!(algorithm = %cfg.algorithm, "Package signature verification enabled");
+                Some(verifier)
+            }
+            None => None,
+        };
         let activation_state = ActivationState {
             supports_rollback: flash_commit_config.supports_rollback,
             state: FlashState::Complete,
@@ -263,20 +446,34 @@ impl UdsBackend {
             stream_manager,
             packages: Arc::new(RwLock::new(HashMap::new())),
             flash_state: Arc::new(RwLock::new(None)),
+            transfer_lock: Arc::new(tokio::sync::Mutex::new(())),
             io_control_states: Arc::new(RwLock::new(HashMap::new())),
             activation_state: Arc::new(RwLock::new(activation_state)),
             flash_commit_config,
+            package_verifier,
             comm_control_state: Arc::new(RwLock::new(COMM_CONTROL_DEFAULT.to_string())),
             dtc_setting_state: Arc::new(RwLock::new(DTC_SETTING_DEFAULT.to_string())),
             unlock,
+            dtc_status_availability_mask: RwLock::new(None),
+            severity_report_refused: AtomicBool::new(false),
+            routine_faults: RwLock::new(HashMap::new()),
         })
     }
 
+    /// Use `provider` for transparent SecurityAccess at `level`, replacing
+    /// any provider built from `config.unlock`. This is how an OEM-specific
+    /// seed/key algorithm is plugged in without adding it to
+    /// [`provider_from_config`].
+    pub fn with_unlock_provider(mut self, provider: Arc<dyn UnlockProvider>, level: u8) -> Self {
+        self.unlock = Some(Arc::new(TransparentUnlock { provider, level }));
+        self
+    }
+
     /// Perform the server-side SecurityAccess (UDS 0x27) seed/key dance for
-    /// `level` using `provider`, driving the existing [`SessionManager`]
-    /// primitives (`request_security_seed` → `send_security_key`). Returns
-    /// `Ok` once unlocked — or when the ECU reports it is already unlocked via
-    /// a zero seed.
+    /// `level` using `provider`, via [`SessionManager::unlock_with`] (seed
+    /// retries and the seed validity window apply). Returns `Ok` once
+    /// unlocked — or when the ECU reports it is already unlocked via a zero
+    /// seed.
     ///
     /// This is an associated fn (not a `&self` method) so both the request
     /// path and the spawned flash task can call it.
@@ -285,15 +482,13 @@ impl UdsBackend {
         provider: &dyn UnlockProvider,
         level: u8,
     ) -> Result<(), SessionError> {
-        let seed = session_manager.request_security_seed(level).await?;
-        if seed.is_empty() {
-            // Zero seed ⇒ the ECU is already unlocked; nothing to send.
-            return Ok(());
-        }
-        let key = provider
-            .compute_key(level, &seed)
-            .map_err(|e| SessionError::SecurityAccessFailed(format!("compute key: {}", e)))?;
-        session_manager.send_security_key(level, &key).await
+        session_manager
+            .unlock_with(level, |seed| {
+                provider
+                    .compute_key(level, seed)
+                    .map_err(|e| format!("compute key: {}", e))
+            })
+            .await
     }
 
     /// If `err` is `securityAccessDenied` (NRC 0x33) and this ECU has a
@@ -329,6 +524,53 @@ impl UdsBackend {
         }
     }
 
+    /// TransferData (0x36) requests of an accepted RequestUpload until `size`
+    /// bytes are in. Each block must echo its counter and is inflated on its
+    /// own, as the download side compresses it.
+    async fn upload_blocks(
+        &self,
+        size: usize,
+        compression: CompressionMethod,
+    ) -> BackendResult<Vec<u8>> {
+        let sessions = &self.config.sessions;
+        let mut block_counter = sessions.transfer_data_block_counter_start;
+        let mut data = Vec::with_capacity(size.min(MEMORY_UPLOAD_RESERVE_LIMIT));
+        while data.len() < size {
+            let (echo, block) = self
+                .uds
+                .transfer_data_upload(block_counter)
+                .await
+                .map_err(crate::error::convert_uds_error)?;
+            if echo != block_counter {
+                return Err(BackendError::Protocol(format!(
+                    "TransferData answered block {}, expected {}",
+                    echo, block_counter
+                )));
+            }
+            let block = compression
+                .decompress(&block, size - data.len())
+                .map_err(|e| {
+                    BackendError::Protocol(format!("TransferData block {}: {}", block_counter, e))
+                })?;
+            // An empty block would never finish the upload
+            if block.is_empty() {
+                return Err(BackendError::Protocol(format!(
+                    "TransferData block {} is empty after {} of {} bytes",
+                    block_counter,
+                    data.len(),
+                    size
+                )));
+            }
+            data.extend_from_slice(&block);
+            block_counter = block_counter.wrapping_add(1);
+            // Wrap to configured value (some ECUs skip 0)
+            if block_counter == 0 && sessions.transfer_data_block_counter_wrap > 0 {
+                block_counter = sessions.transfer_data_block_counter_wrap;
+            }
+        }
+        Ok(data)
+    }
+
     /// Proactive counterpart to [`Self::unlock_on_denied`] for operations that
     /// enforce security with a tester-side pre-check (RoutineControl 0x31 via
     /// `start_operation`, IOControl 0x2F via `control_output`) and therefore
@@ -360,15 +602,151 @@ impl UdsBackend {
         }
     }
 
+    /// Refuse `op` unless the ECU is in its `required_session` (if any),
+    /// then unlock for its `security_level` as [`Self::ensure_unlocked_for`]
+    async fn ensure_operation_preconditions(&self, op: &OperationConfig) -> BackendResult<()> {
+        if let Some(session) = op.required_session.as_deref() {
+            let required = self.config.sessions.session_id(session).ok_or_else(|| {
+                BackendError::Internal(format!(
+                    "Invalid required_session for operation '{}': {}",
+                    op.id, session
+                ))
+            })?;
+            if self.session_manager.current_session_id() != required {
+                return Err(BackendError::SessionRequired(format!(
+                    "{} session required for operation '{}'",
+                    self.session_id_to_name(required),
+                    op.id
+                )));
+            }
+        }
+        self.ensure_unlocked_for(op.security_level).await
+    }
+
+    /// P2/P2* adopted from the active session's 0x10 response, in the
+    /// backend-neutral form reported by the session mode
+    /// Keep `data` as a new pending package, returning its id
+    fn store_package(&self, data: Vec<u8>) -> String {
+        let package_id = Uuid::new_v4().to_string();
+        let size = data.len();
+
+        let package = StoredPackage {
+            id: package_id.clone(),
+            data,
+            status: PackageStatus::Pending,
+            created_at: Utc::now(),
+            verified_at: None,
+        };
+
+        {
+            let mut packages = self.packages.write();
+            packages.insert(package_id.clone(), package);
+        }
+
+        info!(
+            package_id = %package_id,
+            size,
+            "Package received and stored"
+        );
+
+        package_id
+    }
+
+    fn session_timing(&self) -> Option<SessionTimingParameters> {
+        self.uds.session_timing().map(|t| SessionTimingParameters {
+            p2_server_max_ms: t.p2_server_max.as_millis() as u32,
+            p2_star_server_max_ms: t.p2_star_server_max.as_millis() as u32,
+        })
+    }
+
     /// Parse a hex DID string to u16
     fn parse_did(did_str: &str) -> Option<u16> {
         let cleaned = did_str.trim_start_matches("0x").trim_start_matches("0X");
         u16::from_str_radix(cleaned, 16).ok()
     }
 
-    /// Convert a UDS DTC to a SOVD Fault
-    fn dtc_to_fault(&self, dtc: &Dtc) -> Fault {
-        let severity = if dtc.status.warning_indicator_requested {
+    /// Raw DID value as returned by `read_data` - conversions are applied in
+    /// the API layer
+    fn raw_data_value(did_str: &str, did: u16, raw_bytes: &[u8]) -> DataValue {
+        let raw_hex = hex::encode(raw_bytes);
+        DataValue {
+            id: did_str.to_uppercase(),
+            name: did_str.to_uppercase(),
+            value: serde_json::json!(&raw_hex),
+            unit: None,
+            timestamp: Utc::now(),
+            raw: Some(raw_hex),
+            did: Some(format!("{:04X}", did)),
+            length: Some(raw_bytes.len()),
+        }
+    }
+
+    /// Read the primary DTC memory with the best report the ECU supports:
+    /// the severity report (0x08) when declared, else the status-mask report
+    /// (0x02). Returns the status availability mask and every DTC with its
+    /// DTCSeverity byte, if the report carried one.
+    async fn read_primary_dtcs(
+        &self,
+        status_mask: u8,
+    ) -> BackendResult<(u8, Vec<(Option<u8>, Dtc)>)> {
+        let reports = &self.config.fault_memory.reports;
+        let status_mask_supported = reports.is_empty() || reports.contains(&DtcReport::StatusMask);
+
+        if reports.contains(&DtcReport::SeverityMask)
+            && !self.severity_report_refused.load(Ordering::Relaxed)
+        {
+            match self.uds.read_dtc_by_severity_mask(0xFF, status_mask).await {
+                Ok(response) => {
+                    let (mask, dtcs) = parse_dtc_by_severity_mask_response(&response)
+                        .map_err(BackendError::Protocol)?;
+                    let dtcs = dtcs
+                        .into_iter()
+                        .map(|(severity, dtc)| (Some(severity), dtc))
+                        .collect();
+                    return Ok((mask, dtcs));
+                }
+                Err(UdsError::NegativeResponse {
+                    nrc: NegativeResponseCode::SubFunctionNotSupported,
+                    ..
+                }) if status_mask_supported => {
+                    warn!(
+                        ecu = %self.config.id,
+                        "ECU refused the DTC severity report, falling back to the status-mask report"
+                    );
+                    self.severity_report_refused.store(true, Ordering::Relaxed);
+                }
+                Err(e) => return Err(crate::error::convert_uds_error(e)),
+            }
+        } else if !status_mask_supported {
+            return Err(BackendError::NotSupported(format!(
+                "ECU '{}' declares no DTC report for the primary memory \
+                 (fault_memory.reports)",
+                self.config.id
+            )));
+        }
+
+        // Call UDS ReadDTCInformation (0x19) sub-function 0x02
+        let response = self
+            .uds
+            .read_dtc_by_status_mask(status_mask)
+            .await
+            .map_err(crate::error::convert_uds_error)?;
+        let (mask, dtcs) =
+            parse_dtc_by_status_mask_response(&response).map_err(BackendError::Protocol)?;
+        Ok((mask, dtcs.into_iter().map(|dtc| (None, dtc)).collect()))
+    }
+
+    /// Convert a UDS DTC to a SOVD Fault, taking the severity from the
+    /// ECU's DTCSeverity byte when it reported one
+    fn dtc_to_fault(&self, dtc: &Dtc, dtc_severity: Option<u8>) -> Fault {
+        let dtc_severity = dtc_severity.unwrap_or(0);
+        let severity = if dtc_severity & severity_bit::CHECK_IMMEDIATELY != 0 {
+            FaultSeverity::Critical
+        } else if dtc_severity & severity_bit::CHECK_AT_NEXT_HALT != 0 {
+            FaultSeverity::Error
+        } else if dtc_severity & severity_bit::MAINTENANCE_ONLY != 0 {
+            FaultSeverity::Warning
+        } else if dtc.status.warning_indicator_requested {
             FaultSeverity::Critical
         } else if dtc.status.confirmed_dtc {
             FaultSeverity::Error
@@ -405,6 +783,48 @@ impl UdsBackend {
         }
     }
 
+    /// Raise or clear `op`'s synthetic fault from a routine status record.
+    ///
+    /// A mapped result code (first record byte) raises its fault; any other
+    /// code means the routine passed and clears it.
+    fn record_routine_result(&self, op: &OperationConfig, status_record: &[u8]) {
+        let Some(&result) = status_record.first() else {
+            return;
+        };
+        let Some(mapping) = op.result_faults.iter().find(|m| m.result == result) else {
+            if self.routine_faults.write().remove(&op.id).is_some() {
+                info!(operation = %op.id, "Routine passed, cleared synthetic fault");
+            }
+            return;
+        };
+
+        let id = format!("routine-{}", op.id);
+        let now = Utc::now();
+        let mut faults = self.routine_faults.write();
+        let previous = faults.get(&op.id).filter(|f| f.code == mapping.code);
+        let fault = Fault {
+            id: id.clone(),
+            code: mapping.code.clone(),
+            severity: FaultSeverity::Error,
+            message: mapping.description.clone(),
+            category: mapping.category.clone(),
+            first_occurrence: previous.and_then(|f| f.first_occurrence).or(Some(now)),
+            last_occurrence: Some(now),
+            occurrence_count: Some(previous.and_then(|f| f.occurrence_count).unwrap_or(0) + 1),
+            active: true,
+            // `x-sumo-synthetic` tells the fault apart from ECU-reported DTCs.
+            status: Some(serde_json::json!({
+                "testFailed": true,
+                "x-sumo-synthetic": true,
+                "x-sumo-operation": op.id,
+                "x-sumo-routine-result": format!("0x{:02X}", result),
+            })),
+            href: format!("/vehicle/v1/components/{}/faults/{}", self.config.id, id),
+        };
+        warn!(operation = %op.id, code = %fault.code, "Routine result raised synthetic fault");
+        faults.insert(op.id.clone(), fault);
+    }
+
     /// Parse routine ID from hex string
     fn parse_rid(rid_str: &str) -> Result<u16, UdsBackendError> {
         let cleaned = rid_str.trim_start_matches("0x").trim_start_matches("0X");
@@ -421,67 +841,19 @@ impl UdsBackend {
 
     /// Convert session ID to name
     fn session_id_to_name(&self, session_id: u8) -> String {
-        let sessions = &self.config.sessions;
-        if session_id == sessions.default_session {
-            "default".to_string()
-        } else if session_id == sessions.programming_session {
-            "programming".to_string()
-        } else if session_id == sessions.extended_session {
-            "extended".to_string()
-        } else if session_id == sessions.engineering_session {
-            "engineering".to_string()
-        } else {
-            // Check custom sessions
-            for (name, &id) in &sessions.custom_sessions {
-                if id == session_id {
-                    return name.clone();
-                }
-            }
-            format!("0x{:02X}", session_id)
-        }
+        self.config.sessions.session_name(session_id)
     }
 
-    /// Parse session name to UDS session ID
+    /// Parse session name to UDS session ID via the configured session map
     fn parse_session_name(&self, s: &str) -> Result<u8, BackendError> {
-        let sessions = &self.config.sessions;
-        let s_lower = s.to_lowercase();
-
-        tracing::debug!(
-            "parse_session_name: input={}, default={:#x}, programming={:#x}, extended={:#x}",
-            s,
-            sessions.default_session,
-            sessions.programming_session,
-            sessions.extended_session
-        );
-
-        match s_lower.as_str() {
-            "default" => Ok(sessions.default_session),
-            "programming" => Ok(sessions.programming_session),
-            "extended" => Ok(sessions.extended_session),
-            "engineering" => Ok(sessions.engineering_session),
-            _ => {
-                // Check custom sessions (e.g., "telematics")
-                if let Some(&id) = sessions.custom_sessions.get(&s_lower) {
-                    return Ok(id);
-                }
-
-                // Try to parse as hex number (e.g., "0x60" or "0x40")
-                let s = s.trim();
-                if let Some(hex_str) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
-                    u8::from_str_radix(hex_str, 16).map_err(|_| {
-                        BackendError::InvalidRequest(format!("Invalid session: {}", s))
-                    })
-                } else {
-                    // Try as decimal
-                    s.parse::<u8>().map_err(|_| {
-                        BackendError::InvalidRequest(format!(
-                            "Invalid session: {}. Use 'default', 'extended', 'programming', 'engineering', or hex value",
-                            s
-                        ))
-                    })
-                }
-            }
-        }
+        let session_id = self.config.sessions.session_id(s).ok_or_else(|| {
+            BackendError::InvalidRequest(format!(
+                "Invalid session: {}. Use 'default', 'extended', 'programming', 'engineering', or hex value",
+                s
+            ))
+        })?;
+        tracing::debug!("parse_session_name: {} -> {:#04x}", s, session_id);
+        Ok(session_id)
     }
 
     /// Parse security level from string like "level1" or "level3"
@@ -495,6 +867,17 @@ impl UdsBackend {
     }
 
     /// Parse baud rate ID string to (UDS ID, actual rate)
+    /// Range a specific baud rate (LinkControl 0x02) must be in
+    fn check_specific_baud_rate(rate: u32) -> Result<(), BackendError> {
+        if !(10000..=1000000).contains(&rate) {
+            return Err(BackendError::InvalidRequest(format!(
+                "Baud rate {} out of range (10000-1000000)",
+                rate
+            )));
+        }
+        Ok(())
+    }
+
     fn parse_baud_rate_id(s: &str) -> Result<(u8, u32), BackendError> {
         match s.to_lowercase().as_str() {
             "125k" | "125000" => Ok((link_baud_rate::CAN_125K, 125000)),
@@ -552,24 +935,81 @@ impl DiagnosticBackend for UdsBackend {
                 .ok_or_else(|| BackendError::InvalidRequest(format!("Invalid DID: {}", did_str)))?;
 
             let raw_bytes = self.read_raw_did(did).await?;
-
-            // Return raw hex - conversions are applied in the API layer
-            let raw_hex = hex::encode(&raw_bytes);
-            values.push(DataValue {
-                id: did_str.to_uppercase(),
-                name: did_str.to_uppercase(),
-                value: serde_json::json!(&raw_hex),
-                unit: None,
-                timestamp: Utc::now(),
-                raw: Some(raw_hex),
-                did: Some(format!("{:04X}", did)),
-                length: Some(raw_bytes.len()),
-            });
+            values.push(Self::raw_data_value(did_str, did, &raw_bytes));
         }
 
         Ok(values)
     }
 
+    async fn read_parameters_batch(
+        &self,
+        did_strs: &[String],
+    ) -> Vec<(String, BackendResult<DataValue>)> {
+        let mut results: Vec<Option<BackendResult<DataValue>>> = Vec::new();
+        let mut dids = Vec::new();
+        for (index, did_str) in did_strs.iter().enumerate() {
+            match Self::parse_did(did_str) {
+                Some(did) => {
+                    results.push(None);
+                    dids.push((index, did));
+                }
+                None => results.push(Some(Err(BackendError::InvalidRequest(format!(
+                    "Invalid DID: {}",
+                    did_str
+                ))))),
+            }
+        }
+
+        // Still one request at a time on the transport, just fewer of them
+        let per_request = self.config.data_read.max_dids_per_request.unwrap_or(1);
+        for chunk in dids.chunks(per_request.max(1)) {
+            if chunk.len() > 1 {
+                let chunk_dids: Vec<u16> = chunk.iter().map(|(_, did)| *did).collect();
+                match self.uds.read_data_by_id(&chunk_dids).await {
+                    Ok(response) => {
+                        if let Some(parts) = split_read_data_response(&chunk_dids, &response) {
+                            for ((index, did), raw_bytes) in chunk.iter().zip(parts) {
+                                let value =
+                                    Self::raw_data_value(&did_strs[*index], *did, &raw_bytes);
+                                results[*index] = Some(Ok(value));
+                            }
+                            continue;
+                        }
+                        debug!(dids = ?chunk_dids, "Ambiguous multi-DID response, reading singly");
+                    }
+                    // Rejected by the ECU (e.g. too many DIDs): read singly
+                    Err(e @ UdsError::NegativeResponse { .. }) => {
+                        debug!(
+                            dids = ?chunk_dids,
+                            error = %e,
+                            "Multi-DID read rejected, reading singly"
+                        );
+                    }
+                    Err(e) => {
+                        for (index, _) in chunk {
+                            let err = crate::error::convert_uds_error(e.clone());
+                            results[*index] = Some(Err(err));
+                        }
+                        continue;
+                    }
+                }
+            }
+            for (index, did) in chunk {
+                let value = self
+                    .read_raw_did(*did)
+                    .await
+                    .map(|raw_bytes| Self::raw_data_value(&did_strs[*index], *did, &raw_bytes));
+                results[*index] = Some(value);
+            }
+        }
+
+        did_strs
+            .iter()
+            .cloned()
+            .zip(results.into_iter().flatten())
+            .collect()
+    }
+
     async fn write_data(&self, did_str: &str, value: &[u8]) -> BackendResult<()> {
         // Interpret param_id as DID (hex string like "F405" or "0xF405")
         let did = Self::parse_did(did_str)
@@ -597,6 +1037,49 @@ impl DiagnosticBackend for UdsBackend {
         Ok(response[3..].to_vec())
     }
 
+    async fn read_raw_did_periodic(&self, did: u16) -> BackendResult<Vec<u8>> {
+        debug!(
+            did = format!("0x{:04X}", did),
+            "Reading DID via periodic one-shot"
+        );
+
+        // 0x2A addresses the DID by its periodic identifier, the low byte
+        // (as in `StreamManager`, which also knows whether a subscription
+        // already streams it). Subscribe before starting so the first sample
+        // cannot slip past.
+        let pid = (did & 0xFF) as u8;
+        let mut incoming = self.transport.subscribe();
+        self.stream_manager
+            .start_one_shot(did)
+            .await
+            .map_err(crate::error::convert_uds_error)?;
+
+        let sample = tokio::time::timeout(PERIODIC_SAMPLE_TIMEOUT, async {
+            loop {
+                match incoming.recv().await {
+                    Ok(msg) if msg.data.first() == Some(&pid) => {
+                        return Ok(msg.data[1..].to_vec());
+                    }
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(BackendError::Transport(
+                            "incoming message channel closed".to_string(),
+                        ));
+                    }
+                }
+            }
+        })
+        .await;
+
+        // Stop the transmission whether or not a sample arrived, unless a
+        // subscription or another one-shot still needs it.
+        if let Err(e) = self.stream_manager.stop_one_shot(did).await {
+            warn!(error = %e, did = format!("0x{:04X}", did), "Failed to stop periodic one-shot");
+        }
+
+        sample.unwrap_or(Err(BackendError::Timeout))
+    }
+
     async fn write_raw_did(&self, did: u16, data: &[u8]) -> BackendResult<()> {
         debug!(
             did = format!("0x{:04X}", did),
@@ -604,6 +1087,23 @@ impl DiagnosticBackend for UdsBackend {
             "Writing raw DID"
         );
 
+        // Over-limit writes get a clear error here instead of failing in the
+        // transport. Anything within the limit goes out as one request; the
+        // transport segments it (ISO-TP multi-frame) as needed.
+        if let Some(limit) = write_data_limit(
+            self.uds.max_message_len(),
+            self.config.sessions.write_data_max_length,
+        ) {
+            if data.len() > limit {
+                return Err(BackendError::InvalidRequest(format!(
+                    "DID 0x{:04X} value is {} bytes, exceeding the {}-byte write limit for this ECU",
+                    did,
+                    data.len(),
+                    limit
+                )));
+            }
+        }
+
         // Call UDS WriteDataByIdentifier (0x2E). If the ECU rejects with NRC
         // 0x33 (securityAccessDenied) and this ECU has a transparent unlock
         // provider, unlock server-side and retry once — transparent to the
@@ -623,39 +1123,270 @@ impl DiagnosticBackend for UdsBackend {
         }
     }
 
-    async fn subscribe_data(
-        &self,
-        param_ids: &[String],
-        rate_hz: u32,
-    ) -> BackendResult<broadcast::Receiver<DataPoint>> {
-        // Use stream manager for UDS 0x2A periodic data
-        self.stream_manager
-            .subscribe(param_ids.to_vec(), rate_hz)
-            .await
-            .map_err(|e| BackendError::Protocol(e.to_string()))
-    }
+    async fn read_memory(&self, address: u64, size: u32) -> BackendResult<Vec<u8>> {
+        if size == 0 {
+            return Err(BackendError::InvalidRequest(
+                "memory size must be at least 1 byte".to_string(),
+            ));
+        }
+        debug!(address = format!("0x{:X}", address), size, "Reading memory");
 
-    async fn define_data_identifier(
-        &self,
-        ddid: u16,
-        sources: &[(u16, u8, u8)],
-    ) -> BackendResult<()> {
-        self.uds
-            .define_data_identifier(ddid, sources)
-            .await
-            .map_err(crate::error::convert_uds_error)
+        // Call UDS ReadMemoryByAddress (0x23); calibration regions are often
+        // protected, so unlock server-side and retry once on NRC 0x33 like
+        // `write_raw_did`.
+        match self.uds.read_memory_by_address(address, size).await {
+            Ok(data) => Ok(data),
+            Err(e) => {
+                if self.unlock_on_denied(&e).await {
+                    self.uds
+                        .read_memory_by_address(address, size)
+                        .await
+                        .map_err(crate::error::convert_uds_error)
+                } else {
+                    Err(crate::error::convert_uds_error(e))
+                }
+            }
+        }
     }
 
-    async fn clear_data_identifier(&self, ddid: u16) -> BackendResult<()> {
+    async fn write_memory(&self, address: u64, data: &[u8]) -> BackendResult<()> {
+        if data.is_empty() {
+            return Err(BackendError::InvalidRequest(
+                "memory write must carry at least 1 byte".to_string(),
+            ));
+        }
+
+        // Memory writes bypass every DID-level check, so require the
+        // configured session and security level before anything is sent.
+        let memory = &self.config.memory;
+        let required_session = self
+            .config
+            .sessions
+            .session_id(&memory.write_session)
+            .ok_or_else(|| {
+                BackendError::Internal(format!(
+                    "Invalid memory write_session: {}",
+                    memory.write_session
+                ))
+            })?;
+        if self.session_manager.current_session_id() != required_session {
+            return Err(BackendError::SessionRequired(format!(
+                "{} session required for memory writes",
+                self.session_id_to_name(required_session)
+            )));
+        }
+        self.ensure_unlocked_for(memory.write_security_level)
+            .await?;
+
+        debug!(
+            address = format!("0x{:X}", address),
+            size = data.len(),
+            "Writing memory"
+        );
         self.uds
-            .clear_data_identifier(ddid)
+            .write_memory_by_address(address, data)
             .await
             .map_err(crate::error::convert_uds_error)
     }
 
-    async fn ecu_reset(&self, reset_type: u8) -> BackendResult<Option<u8>> {
-        // ECU reset is special: the ECU may reboot before sending a response,
-        // so timeout/transport errors are treated as success (ECU rebooted).
+    async fn upload_memory(
+        &self,
+        address: u64,
+        size: u32,
+        data_format: u8,
+    ) -> BackendResult<Vec<u8>> {
+        if size == 0 {
+            return Err(BackendError::InvalidRequest(
+                "memory size must be at least 1 byte".to_string(),
+            ));
+        }
+        // Compressed blocks are inflated here; encrypted ones could not be
+        let compression = CompressionMethod::from_nibble(data_format >> 4)
+            .filter(|_| data_format & 0x0F == 0)
+            .ok_or_else(|| {
+                BackendError::InvalidRequest(format!(
+                    "Unsupported dataFormatIdentifier 0x{:02X}",
+                    data_format
+                ))
+            })?;
+        // The ECU runs one transfer at a time; a flash in flight owns it,
+        // and this upload keeps one from starting until it is done
+        let _transfer = self.transfer_lock.try_lock().map_err(|_| {
+            BackendError::Busy("Another transfer with the ECU is in progress".to_string())
+        })?;
+        debug!(
+            address = format!("0x{:X}", address),
+            size, "Uploading memory"
+        );
+
+        // RequestUpload (0x35); unlock server-side and retry once on NRC 0x33
+        // like `read_memory`.
+        if let Err(e) = self
+            .uds
+            .request_upload_memory(data_format, address, size)
+            .await
+        {
+            if !self.unlock_on_denied(&e).await {
+                return Err(crate::error::convert_uds_error(e));
+            }
+            self.uds
+                .request_upload_memory(data_format, address, size)
+                .await
+                .map_err(crate::error::convert_uds_error)?;
+        }
+
+        match self.upload_blocks(size as usize, compression).await {
+            Ok(data) => {
+                self.uds
+                    .request_transfer_exit(&[])
+                    .await
+                    .map_err(crate::error::convert_uds_error)?;
+                Ok(data)
+            }
+            Err(e) => {
+                // Close the upload so the ECU accepts the next transfer
+                if let Err(exit) = self.uds.request_transfer_exit(&[]).await {
+                    warn!(error = %exit, "Failed to send transfer exit after failed upload");
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn read_identification(
+        &self,
+        fields: Option<&[String]>,
+    ) -> BackendResult<Vec<DataValue>> {
+        let mut values = Vec::new();
+        for &(did, key, label) in standard_did::IDENTIFICATION_DIDS {
+            if fields.is_some_and(|f| !f.iter().any(|f| f.eq_ignore_ascii_case(key))) {
+                continue;
+            }
+            let data = match self.read_raw_did(did).await {
+                Ok(data) => data,
+                // Negative response: the ECU does not carry this DID.
+                Err(BackendError::EcuError { nrc, .. }) => {
+                    debug!(
+                        did = format!("0x{:04X}", did),
+                        nrc, "Identification DID skipped"
+                    );
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let text = String::from_utf8_lossy(&data)
+                .trim_end_matches('\0')
+                .to_string();
+            let mut value = DataValue::new(key, label, serde_json::Value::String(text));
+            value.did = Some(format!("{:04X}", did));
+            value.raw = Some(hex::encode(&data));
+            value.length = Some(data.len());
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    async fn transport_stats(&self) -> BackendResult<TransportStats> {
+        Ok(self.transport.stats())
+    }
+
+    async fn transport_metrics(&self) -> BackendResult<TransportMetrics> {
+        self.transport
+            .metrics()
+            .ok_or_else(|| BackendError::NotSupported("transport_metrics".to_string()))
+    }
+
+    async fn health_check(&self) -> BackendResult<HealthStatus> {
+        // TesterPresent is the cheapest request every ECU answers in every
+        // session; a negative response still proves the ECU is awake.
+        let started = std::time::Instant::now();
+        match self.uds.tester_present(false).await {
+            Ok(()) | Err(UdsError::NegativeResponse { .. }) => {
+                Ok(HealthStatus::reachable().with_latency(started.elapsed()))
+            }
+            Err(UdsError::Timeout) => Ok(HealthStatus::unreachable("no response to TesterPresent")),
+            Err(UdsError::Transport(e)) => Ok(HealthStatus::unreachable(e)),
+            Err(e) => Err(crate::error::convert_uds_error(e)),
+        }
+    }
+
+    async fn read_fingerprints(&self) -> BackendResult<Vec<Fingerprint>> {
+        let format = self.config.fingerprints.format.as_deref();
+        let mut fingerprints = Vec::new();
+        for &(did, fingerprint_type) in fingerprint::FINGERPRINT_DIDS {
+            let data = match self.read_raw_did(did).await {
+                Ok(data) => data,
+                // Negative response: the ECU does not carry this fingerprint.
+                Err(BackendError::EcuError { nrc, .. }) => {
+                    debug!(
+                        did = format!("0x{:04X}", did),
+                        nrc, "Fingerprint DID skipped"
+                    );
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let decoded = format.and_then(|f| fingerprint::decode_fingerprint(f, &data));
+            fingerprints.push(Fingerprint {
+                fingerprint_type: fingerprint_type.to_string(),
+                did: format!("{:04X}", did),
+                date: decoded.as_ref().map(|d| d.date.clone()),
+                tester_serial: decoded.map(|d| d.tester_serial),
+                raw: hex::encode(&data),
+            });
+        }
+        Ok(fingerprints)
+    }
+
+    async fn subscribe_data(
+        &self,
+        param_ids: &[String],
+        rate_hz: u32,
+    ) -> BackendResult<broadcast::Receiver<DataPoint>> {
+        // Use stream manager for UDS 0x2A periodic data (or 0x86 events)
+        self.stream_manager
+            .subscribe(param_ids.to_vec(), rate_hz)
+            .await
+            .map_err(|e| BackendError::Protocol(e.to_string()))
+    }
+
+    async fn define_data_identifier(
+        &self,
+        ddid: u16,
+        sources: &[(u16, u8, u8)],
+    ) -> BackendResult<()> {
+        self.uds
+            .define_data_identifier(ddid, sources)
+            .await
+            .map_err(crate::error::convert_uds_error)
+    }
+
+    async fn define_data_identifier_by_memory(
+        &self,
+        ddid: u16,
+        regions: &[(u64, u32)],
+    ) -> BackendResult<()> {
+        if regions.is_empty() || regions.iter().any(|(_, size)| *size == 0) {
+            return Err(BackendError::InvalidRequest(
+                "every memory region must be at least 1 byte".to_string(),
+            ));
+        }
+        self.uds
+            .define_data_identifier_by_memory(ddid, regions)
+            .await
+            .map_err(crate::error::convert_uds_error)
+    }
+
+    async fn clear_data_identifier(&self, ddid: u16) -> BackendResult<()> {
+        self.uds
+            .clear_data_identifier(ddid)
+            .await
+            .map_err(crate::error::convert_uds_error)
+    }
+
+    async fn ecu_reset(&self, reset_type: u8) -> BackendResult<Option<u8>> {
+        // ECU reset is special: the ECU may reboot before sending a response,
+        // so timeout/transport errors are treated as success (ECU rebooted).
         let result = match self.uds.ecu_reset(reset_type).await {
             Ok(r) => r,
             Err(crate::uds::UdsError::Timeout) | Err(crate::uds::UdsError::Transport(_)) => {
@@ -665,9 +1396,38 @@ impl DiagnosticBackend for UdsBackend {
             Err(e) => return Err(crate::error::convert_uds_error(e)),
         };
 
+        // Remember what the ECU had before it forgets, for subscription recovery
+        let prior_session = self.session_manager.current_session_id();
+        let prior_security = self.session_manager.security_state();
+
         // ECU rebooted → back in default session with security locked
         self.session_manager.notify_ecu_reset().await;
 
+        // The reboot also cleared the periodic schedule: re-arm it once the
+        // ECU answers again, if configured
+        let recovery = self.config.subscription_recovery.clone();
+        if recovery.enabled && self.stream_manager.has_subscriptions() {
+            self.stream_manager.suspend();
+            let uds = self.uds.clone();
+            let default_session = self.config.sessions.default_session;
+            let session_manager = self.session_manager.clone();
+            let stream_manager = self.stream_manager.clone();
+            let unlock = self.unlock.clone();
+            let security_level = prior_security.unlocked.then_some(prior_security.level);
+            tokio::spawn(async move {
+                Self::recover_subscriptions(
+                    uds,
+                    recovery,
+                    session_manager,
+                    stream_manager,
+                    unlock,
+                    (prior_session != default_session).then_some(prior_session),
+                    security_level,
+                )
+                .await;
+            });
+        }
+
         // If firmware is awaiting reset, transition to Activated now that the ECU has rebooted
         let needs_transition = {
             let activation = self.activation_state.read();
@@ -698,19 +1458,71 @@ impl DiagnosticBackend for UdsBackend {
             _ => 0xFF, // All DTCs
         };
 
-        // Call UDS ReadDTCInformation (0x19) sub-function 0x02
-        let response = self
-            .uds
-            .read_dtc_by_status_mask(status_mask)
-            .await
-            .map_err(crate::error::convert_uds_error)?;
-
         // Parse DTC response - returns (status_availability_mask, dtcs)
-        let (status_availability_mask, dtcs) =
-            parse_dtc_by_status_mask_response(&response).map_err(BackendError::Protocol)?;
+        let (status_availability_mask, dtcs) = match filter.and_then(|f| f.memory) {
+            None | Some(FaultMemory::Primary) => self.read_primary_dtcs(status_mask).await?,
+            Some(FaultMemory::Mirror) => {
+                let reports = &self.config.fault_memory.reports;
+                if !reports.is_empty() && !reports.contains(&DtcReport::UserDefMemory) {
+                    return Err(BackendError::NotSupported(format!(
+                        "ECU '{}' does not support user-defined DTC memories \
+                         (fault_memory.reports)",
+                        self.config.id
+                    )));
+                }
+                // Mirror memory is a user-defined memory (sub-function 0x17)
+                // addressed by a manufacturer-specific MemorySelection byte.
+                let selection = self
+                    .config
+                    .fault_memory
+                    .mirror_memory_selection
+                    .ok_or_else(|| {
+                        BackendError::NotSupported(format!(
+                            "ECU '{}' has no mirror DTC memory configured \
+                             (fault_memory.mirror_memory_selection)",
+                            self.config.id
+                        ))
+                    })?;
+                let response = self
+                    .uds
+                    .read_user_def_memory_dtc_by_status_mask(status_mask, selection)
+                    .await
+                    .map_err(crate::error::convert_uds_error)?;
+                let (mask, dtcs) =
+                    parse_user_def_memory_dtc_by_status_mask_response(&response, selection)
+                        .map_err(BackendError::Protocol)?;
+                (mask, dtcs.into_iter().map(|dtc| (None, dtc)).collect())
+            }
+        };
+
+        *self.dtc_status_availability_mask.write() = Some(status_availability_mask);
 
-        // Convert DTCs to Faults
-        let mut faults: Vec<Fault> = dtcs.iter().map(|dtc| self.dtc_to_fault(dtc)).collect();
+        // Status bits the ECU does not support carry no information; clear
+        // them before deriving `active`/severity or filtering on them.
+        let status_filter = filter.and_then(|f| f.status_mask);
+        let severity_filter = filter.and_then(|f| f.dtc_severity_mask);
+        let mut faults: Vec<Fault> = dtcs
+            .into_iter()
+            .filter_map(|(severity, mut dtc)| {
+                dtc.status = dtc.status.masked(status_availability_mask);
+                let status_matches = status_filter.is_none_or(|mask| dtc.status.matches_mask(mask));
+                // Without a severity report nothing is known to match
+                let severity_matches = severity_filter
+                    .is_none_or(|mask| severity.is_some_and(|severity| severity & mask != 0));
+                (status_matches && severity_matches).then(|| self.dtc_to_fault(&dtc, severity))
+            })
+            .collect();
+
+        // Routine-result faults live alongside the primary memory only, and
+        // have no status or severity byte to match bit filters against.
+        if matches!(
+            filter.and_then(|f| f.memory),
+            None | Some(FaultMemory::Primary)
+        ) && status_filter.is_none()
+            && severity_filter.is_none()
+        {
+            faults.extend(self.routine_faults.read().values().cloned());
+        }
 
         // Apply additional filters
         if let Some(f) = filter {
@@ -732,7 +1544,29 @@ impl DiagnosticBackend for UdsBackend {
         })
     }
 
+    async fn get_dtc_status_availability_mask(&self) -> BackendResult<u8> {
+        if let Some(mask) = *self.dtc_status_availability_mask.read() {
+            return Ok(mask);
+        }
+        let mask = self
+            .uds
+            .read_dtc_status_availability_mask()
+            .await
+            .map_err(crate::error::convert_uds_error)?;
+        *self.dtc_status_availability_mask.write() = Some(mask);
+        Ok(mask)
+    }
+
     async fn get_fault_detail(&self, fault_id: &str) -> BackendResult<Fault> {
+        if let Some(fault) = self
+            .routine_faults
+            .read()
+            .values()
+            .find(|f| f.id == fault_id)
+        {
+            return Ok(fault.clone());
+        }
+
         // Validate fault ID format by parsing it
         let _dtc_bytes = Dtc::parse_id(fault_id).ok_or_else(|| {
             BackendError::EntityNotFound(format!("Invalid fault ID: {}", fault_id))
@@ -748,6 +1582,116 @@ impl DiagnosticBackend for UdsBackend {
             .ok_or_else(|| BackendError::EntityNotFound(format!("Fault not found: {}", fault_id)))
     }
 
+    async fn get_fault_snapshots(
+        &self,
+        fault_id: &str,
+        did_lengths: &HashMap<u16, usize>,
+    ) -> BackendResult<Vec<FaultSnapshot>> {
+        let dtc_bytes = Dtc::parse_id(fault_id).ok_or_else(|| {
+            BackendError::EntityNotFound(format!("Invalid fault ID: {}", fault_id))
+        })?;
+
+        let read = move |record_number| {
+            self.uds
+                .read_dtc_snapshot(dtc_bytes[0], dtc_bytes[1], dtc_bytes[2], record_number)
+        };
+        // Record number 0xFF requests all stored snapshot records
+        let response = read(0xFF).await.map_err(crate::error::convert_uds_error)?;
+
+        // A record closing in a DID of unknown length cannot be told from
+        // the records after it; read alone, it is the final record and that
+        // DID takes the rest, which sizes it for the all-records answer
+        let mut lengths = did_lengths.clone();
+        let records = loop {
+            match parse_dtc_snapshot_records(&response, 0xFF, |did| lengths.get(&did).copied()) {
+                Ok((_dtc, records)) => break records,
+                Err(SnapshotParseError::UnsizedLastDid { record_number, did }) => {
+                    let single = read(record_number)
+                        .await
+                        .map_err(crate::error::convert_uds_error)?;
+                    let (_dtc, single) =
+                        parse_dtc_snapshot_records(&single, record_number, |did| {
+                            lengths.get(&did).copied()
+                        })
+                        .map_err(|e| BackendError::Protocol(e.to_string()))?;
+                    let len = single
+                        .iter()
+                        .find(|r| r.record_number == record_number)
+                        .and_then(|r| r.data.last())
+                        .filter(|item| item.did == did)
+                        .map(|item| item.data.len())
+                        .ok_or_else(|| {
+                            BackendError::Protocol(format!(
+                                "Snapshot record {} read alone does not close in DID 0x{:04X}",
+                                record_number, did
+                            ))
+                        })?;
+                    lengths.insert(did, len);
+                }
+                Err(e) => return Err(BackendError::Protocol(e.to_string())),
+            }
+        };
+
+        Ok(records
+            .into_iter()
+            .map(|record| FaultSnapshot {
+                record_number: record.record_number,
+                items: record
+                    .data
+                    .into_iter()
+                    .map(|item| FaultSnapshotItem {
+                        did: item.did,
+                        data: item.data,
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+
+    async fn get_fault_extended_data(
+        &self,
+        fault_id: &str,
+        record_number: u8,
+    ) -> BackendResult<Vec<FaultExtendedData>> {
+        let dtc_bytes = Dtc::parse_id(fault_id).ok_or_else(|| {
+            BackendError::EntityNotFound(format!("Invalid fault ID: {}", fault_id))
+        })?;
+
+        let response = self
+            .uds
+            .read_dtc_extended_data(dtc_bytes[0], dtc_bytes[1], dtc_bytes[2], record_number)
+            .await
+            .map_err(crate::error::convert_uds_error)?;
+
+        let layout = &self.config.fault_memory.extended_data;
+        let layout_of = |record: u8| layout.iter().find(|r| r.record == record);
+        let (_dtc, records) = parse_dtc_extended_data_records(&response, |record| {
+            layout_of(record).map(|r| r.length)
+        })
+        .map_err(BackendError::Protocol)?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| {
+                let config = layout_of(record.record_number);
+                let value = config
+                    .filter(|_| (1..=8).contains(&record.data.len()))
+                    .map(|_| {
+                        record
+                            .data
+                            .iter()
+                            .fold(0u64, |acc, &b| (acc << 8) | u64::from(b))
+                    });
+                FaultExtendedData {
+                    record_number: record.record_number,
+                    name: config.map(|c| c.name.clone()),
+                    value,
+                    data: record.data,
+                }
+            })
+            .collect())
+    }
+
     async fn clear_faults(&self, group: Option<u32>) -> BackendResult<ClearFaultsResult> {
         let dtc_group = group.unwrap_or(0xFFFFFF); // Default to all DTCs
 
@@ -757,10 +1701,44 @@ impl DiagnosticBackend for UdsBackend {
             .await
             .map_err(crate::error::convert_uds_error)?;
 
+        // UDS doesn't return a count; only the synthetic faults are counted.
+        // A group clear takes those whose code names a DTC in the group.
+        let cleared_count = {
+            let mut routine_faults = self.routine_faults.write();
+            let before = routine_faults.len();
+            routine_faults.retain(|_, f| !in_clear_group(&f.code, dtc_group));
+            (before - routine_faults.len()) as u32
+        };
+
+        // Some ECUs acknowledge the clear before it has taken effect
+        let verification = if self.config.fault_memory.verify_clear {
+            let remaining: Vec<Fault> = self
+                .get_faults(None)
+                .await?
+                .faults
+                .into_iter()
+                .filter(|f| in_clear_group(&f.id, dtc_group))
+                .collect();
+            if !remaining.is_empty() {
+                warn!(
+                    group = format!("0x{:06X}", dtc_group),
+                    remaining = remaining.len(),
+                    "DTCs still stored after clear"
+                );
+            }
+            Some(ClearVerification {
+                cleared_confirmed: remaining.is_empty(),
+                remaining,
+            })
+        } else {
+            None
+        };
+
         Ok(ClearFaultsResult {
             success: true,
-            cleared_count: 0, // UDS doesn't return count
+            cleared_count,
             message: format!("Cleared DTCs for group 0x{:06X}", dtc_group),
+            verification,
         })
     }
 
@@ -789,6 +1767,31 @@ impl DiagnosticBackend for UdsBackend {
             .collect())
     }
 
+    async fn encode_operation_params(
+        &self,
+        operation_id: &str,
+        params: &serde_json::Value,
+    ) -> BackendResult<Vec<u8>> {
+        let op = self
+            .config
+            .operations
+            .iter()
+            .find(|o| o.id == operation_id)
+            .ok_or_else(|| BackendError::OperationNotFound(operation_id.to_string()))?;
+        routine_conv::encode_routine_params(op, params)
+            .map_err(|e| BackendError::InvalidRequest(e.to_string()))
+    }
+
+    async fn check_operation_preconditions(&self, operation_id: &str) -> BackendResult<()> {
+        let op = self
+            .config
+            .operations
+            .iter()
+            .find(|o| o.id == operation_id)
+            .ok_or_else(|| BackendError::OperationNotFound(operation_id.to_string()))?;
+        self.ensure_operation_preconditions(op).await
+    }
+
     async fn start_operation(
         &self,
         operation_id: &str,
@@ -801,11 +1804,11 @@ impl DiagnosticBackend for UdsBackend {
             .find(|o| o.id == operation_id)
             .ok_or_else(|| BackendError::OperationNotFound(operation_id.to_string()))?;
 
-        // Security pre-check with proactive transparent unlock: a locked
-        // session never reaches the wire here, so the reactive NRC-0x33 seam
-        // cannot fire — unlock server-side instead when a provider is
+        // Session and security pre-check with proactive transparent unlock: a
+        // locked session never reaches the wire here, so the reactive NRC-0x33
+        // seam cannot fire — unlock server-side instead when a provider is
         // configured, else SecurityRequired as before.
-        self.ensure_unlocked_for(op.security_level).await?;
+        self.ensure_operation_preconditions(op).await?;
 
         // Parse routine ID
         let rid = Self::parse_rid(&op.rid).map_err(|e| BackendError::Protocol(e.to_string()))?;
@@ -845,6 +1848,11 @@ impl DiagnosticBackend for UdsBackend {
             }
         };
 
+        // Stop carries no verdict; start and request-results do.
+        if sub_function != 0x02 {
+            self.record_routine_result(op, &result);
+        }
+
         let execution_id = Uuid::new_v4().to_string();
 
         Ok(OperationExecution {
@@ -922,8 +1930,17 @@ impl DiagnosticBackend for UdsBackend {
             _ => output.default_value.clone(), // Fall back to config default
         };
 
-        // Read tester-side control state for this output
-        let (controlled_by_tester, frozen) = match self.io_control_states.read().get(&ioid) {
+        // Read tester-side control state for this output. An override from
+        // an earlier session epoch was released when that session ended.
+        let record = self.io_control_states.read().get(&ioid).cloned();
+        let session_reset = record.as_ref().is_some_and(|r| {
+            matches!(
+                r.state,
+                IoControlState::TesterControlled | IoControlState::Frozen
+            ) && r.session_epoch != self.session_manager.session_epoch()
+        });
+        let (controlled_by_tester, frozen) = match record.as_ref().map(|r| r.state) {
+            _ if session_reset => (false, false),
             Some(IoControlState::TesterControlled) => (true, false),
             Some(IoControlState::Frozen) => (true, true),
             Some(IoControlState::DefaultReset) => (false, false),
@@ -951,6 +1968,10 @@ impl DiagnosticBackend for UdsBackend {
             min: None,
             max: None,
             allowed: Vec::new(),
+            last_action: record.as_ref().map(|r| r.action.to_string()),
+            last_value: record.as_ref().and_then(|r| r.value.clone()),
+            last_commanded_at: record.as_ref().map(|r| r.at),
+            session_reset,
         })
     }
 
@@ -976,6 +1997,8 @@ impl DiagnosticBackend for UdsBackend {
         let ioid =
             Self::parse_ioid(&output.ioid).map_err(|e| BackendError::Protocol(e.to_string()))?;
 
+        // controlState bytes of a short-term adjustment, echoed in the result
+        let mut sent: Option<Vec<u8>> = None;
         let result = match action {
             IoControlAction::ReturnToEcu => self.uds.io_control_return_to_ecu(ioid).await,
             IoControlAction::ResetToDefault => self.uds.io_control_reset_to_default(ioid).await,
@@ -984,12 +2007,18 @@ impl DiagnosticBackend for UdsBackend {
                 let json_val = value.ok_or_else(|| {
                     BackendError::InvalidRequest("Value required for short_term_adjust".to_string())
                 })?;
+                // Range-check (reject or clamp) before anything is sent
+                let json_val = output_conv::check_output_range(output, &json_val)
+                    .map_err(|e| BackendError::InvalidRequest(e.to_string()))?;
                 // Encode JSON value to raw bytes using this output's config
                 let data = output_conv::encode_output_value(output, &json_val)
                     .map_err(|e| BackendError::InvalidRequest(format!("Invalid value: {}", e)))?;
-                self.uds
+                let result = self
+                    .uds
                     .io_control_short_term_adjustment(ioid, &data, None)
-                    .await
+                    .await;
+                sent = Some(data);
+                result
             }
         };
 
@@ -1016,8 +2045,22 @@ impl DiagnosticBackend for UdsBackend {
 
         match result {
             Ok(response) => {
+                // The physical value the sent bytes stand for
+                let value = sent
+                    .as_deref()
+                    .map(|data| output_conv::decode_output_value(output, data));
+
                 // Store tester-side control state for this output
-                self.io_control_states.write().insert(ioid, io_state);
+                self.io_control_states.write().insert(
+                    ioid,
+                    IoControlRecord {
+                        state: io_state,
+                        action: action_str,
+                        value: value.clone(),
+                        at: Utc::now(),
+                        session_epoch: self.session_manager.session_epoch(),
+                    },
+                );
 
                 // UdsService::io_control_*() already strips the 4-byte UDS header
                 // (0x6F, DID_hi, DID_lo, controlParam) and returns only the
@@ -1034,7 +2077,8 @@ impl DiagnosticBackend for UdsBackend {
                     controlled_by_tester,
                     frozen,
                     new_value,
-                    value: None,
+                    value,
+                    raw_sent: sent.as_deref().map(hex::encode),
                     error: None,
                 })
             }
@@ -1046,11 +2090,27 @@ impl DiagnosticBackend for UdsBackend {
                 frozen: false,
                 new_value: None,
                 value: None,
+                raw_sent: sent.as_deref().map(hex::encode),
                 error: Some(e.to_string()),
             }),
         }
     }
 
+    async fn check_output_value(
+        &self,
+        output_id: &str,
+        value: &serde_json::Value,
+    ) -> BackendResult<serde_json::Value> {
+        let output = self
+            .config
+            .outputs
+            .iter()
+            .find(|o| o.id == output_id)
+            .ok_or_else(|| BackendError::OutputNotFound(output_id.to_string()))?;
+        output_conv::check_output_range(output, value)
+            .map_err(|e| BackendError::InvalidRequest(e.to_string()))
+    }
+
     async fn get_software_info(&self) -> BackendResult<SoftwareInfo> {
         // Read standard identification DIDs
         let mut details = serde_json::Map::new();
@@ -1109,27 +2169,36 @@ impl DiagnosticBackend for UdsBackend {
             mode: "session".to_string(),
             session: session_name,
             session_id,
+            timing: self.session_timing(),
         })
     }
 
     async fn set_session_mode(&self, session: &str) -> BackendResult<SessionMode> {
         let session_id = self.parse_session_name(session)?;
+        let prior_epoch = self.session_manager.session_epoch();
 
         self.session_manager
             .change_session(session_id)
             .await
             .map_err(|e| BackendError::Protocol(e.to_string()))?;
 
-        // Per ISO 14229: all I/O overrides revert on session change.
-        // Clear tester-side bookkeeping to stay in sync.
-        let cleared = {
-            let mut states = self.io_control_states.write();
-            let count = states.len();
-            states.clear();
-            count
-        };
-        if cleared > 0 {
-            info!(cleared, "Cleared I/O control states on session change");
+        // Per ISO 14229: all I/O overrides revert on session change. The
+        // session epoch moved on, so get_output reports them released.
+        let released = self
+            .io_control_states
+            .read()
+            .values()
+            .filter(|r| {
+                r.session_epoch == prior_epoch
+                    && prior_epoch != self.session_manager.session_epoch()
+                    && matches!(
+                        r.state,
+                        IoControlState::TesterControlled | IoControlState::Frozen
+                    )
+            })
+            .count();
+        if released > 0 {
+            info!(released, "I/O control overrides released by session change");
         }
 
         let session_name = self.session_id_to_name(session_id);
@@ -1138,9 +2207,14 @@ impl DiagnosticBackend for UdsBackend {
             mode: "session".to_string(),
             session: session_name,
             session_id,
+            timing: self.session_timing(),
         })
     }
 
+    fn session_epoch(&self) -> Option<u64> {
+        Some(self.session_manager.session_epoch())
+    }
+
     async fn get_security_mode(&self) -> BackendResult<SecurityMode> {
         let security_state = self.session_manager.security_state();
         let available_levels = self.session_manager.available_security_levels();
@@ -1182,7 +2256,7 @@ impl DiagnosticBackend for UdsBackend {
                 .session_manager
                 .request_security_seed(level)
                 .await
-                .map_err(|e| BackendError::Protocol(e.to_string()))?;
+                .map_err(security_error)?;
 
             if seed.is_empty() {
                 // Already unlocked (zero seed)
@@ -1215,7 +2289,7 @@ impl DiagnosticBackend for UdsBackend {
             self.session_manager
                 .send_security_key(level, key_bytes)
                 .await
-                .map_err(|e| BackendError::Protocol(e.to_string()))?;
+                .map_err(security_error)?;
 
             Ok(SecurityMode {
                 mode: "security".to_string(),
@@ -1340,13 +2414,7 @@ impl DiagnosticBackend for UdsBackend {
                         "Missing 'baud_rate' for verify_specific action".to_string(),
                     )
                 })?;
-
-                if !(10000..=1000000).contains(&rate) {
-                    return Err(BackendError::InvalidRequest(format!(
-                        "Baud rate {} out of range (10000-1000000)",
-                        rate
-                    )));
-                }
+                Self::check_specific_baud_rate(rate)?;
 
                 self.uds
                     .link_control_verify_specific(rate)
@@ -1388,8 +2456,62 @@ impl DiagnosticBackend for UdsBackend {
                     message: format!("Transitioned to {} bps", rate),
                 })
             }
+            "switch" => {
+                // Verify first: an ECU refusing the rate answers the verify
+                // step with an NRC, and nothing is transitioned
+                let rate = match (baud_rate_id, baud_rate) {
+                    (Some(id), _) => {
+                        let (id, rate) = Self::parse_baud_rate_id(id)?;
+                        self.uds
+                            .link_control_verify_fixed(id)
+                            .await
+                            .map_err(crate::error::convert_uds_error)?;
+                        rate
+                    }
+                    (None, Some(rate)) => {
+                        Self::check_specific_baud_rate(rate)?;
+                        self.uds
+                            .link_control_verify_specific(rate)
+                            .await
+                            .map_err(crate::error::convert_uds_error)?;
+                        rate
+                    }
+                    (None, None) => {
+                        return Err(BackendError::InvalidRequest(
+                            "Missing 'baud_rate_id' or 'baud_rate' for switch action".to_string(),
+                        ))
+                    }
+                };
+                self.session_manager.set_pending_baud_rate(Some(rate));
+
+                // The ECU answers the transition at the old rate and
+                // switches after that (ISO 14229-1 LinkControl), so the bus
+                // follows once the response is in, before any other request
+                // to the ECU goes out
+                let followed = self
+                    .uds
+                    .link_control_transition_then(self.transport.set_bitrate(rate))
+                    .await
+                    .map_err(crate::error::convert_uds_error)?;
+                self.session_manager.set_pending_baud_rate(None);
+                followed.map_err(|e| {
+                    BackendError::Transport(format!(
+                        "ECU switched to {} bps but the bus did not follow: {}",
+                        rate, e
+                    ))
+                })?;
+                self.session_manager.set_current_baud_rate(rate);
+                info!(baud_rate = rate, "LinkControl: bus switched");
+
+                Ok(LinkControlResult {
+                    success: true,
+                    action: "switch".to_string(),
+                    baud_rate: Some(rate),
+                    message: format!("Switched to {} bps", rate),
+                })
+            }
             _ => Err(BackendError::InvalidRequest(format!(
-                "Unknown action: {}. Use 'verify_fixed', 'verify_specific', or 'transition'",
+                "Unknown action: {}. Use 'switch', 'verify_fixed', 'verify_specific', or 'transition'",
                 action
             ))),
         }
@@ -1400,28 +2522,24 @@ impl DiagnosticBackend for UdsBackend {
     // =========================================================================
 
     async fn receive_package(&self, data: &[u8]) -> BackendResult<String> {
-        let package_id = Uuid::new_v4().to_string();
-
-        let package = StoredPackage {
-            id: package_id.clone(),
-            data: data.to_vec(),
-            status: PackageStatus::Pending,
-            created_at: Utc::now(),
-            verified_at: None,
-        };
+        Ok(self.store_package(data.to_vec()))
+    }
 
-        {
-            let mut packages = self.packages.write();
-            packages.insert(package_id.clone(), package);
+    async fn receive_package_stream(
+        &self,
+        mut stream: PackageStream,
+        content_length: Option<u64>,
+    ) -> BackendResult<String> {
+        // Chunks go straight into the stored package rather than through a
+        // buffer of their own; the announced length only reserves so much
+        let reserve = content_length.unwrap_or(0).min(PACKAGE_RESERVE_LIMIT) as usize;
+        let mut data = Vec::with_capacity(reserve);
+        while let Some(chunk) = stream.next().await {
+            let chunk =
+                chunk.map_err(|e| BackendError::Internal(format!("stream read error: {e}")))?;
+            data.extend_from_slice(&chunk);
         }
-
-        info!(
-            package_id = %package_id,
-            size = data.len(),
-            "Package received and stored"
-        );
-
-        Ok(package_id)
+        Ok(self.store_package(data))
     }
 
     async fn list_packages(&self) -> BackendResult<Vec<PackageInfo>> {
@@ -1461,13 +2579,28 @@ impl DiagnosticBackend for UdsBackend {
             BackendError::EntityNotFound(format!("Package not found: {}", package_id))
         })?;
 
-        // Compute CRC-32 checksum
-        let crc_alg = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+        // A signed package is reduced to its image once the signature
+        // verifies, so the ECU is flashed with exactly what was signed (and
+        // a re-verify of that image has no trailer left to check)
+        let signature_error = match &self.package_verifier {
+            Some(_) if package.status == PackageStatus::Verified => None,
+            Some(verifier) => match verifier.verify(&package.data) {
+                Ok(image) => {
+                    package.data = image.to_vec();
+                    None
+                }
+                Err(e) => Some(format!("Signature check failed: {}", e)),
+            },
+            None => None,
+        };
+
+        // Compute CRC-32 checksum
+        let crc_alg = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
         let crc = crc_alg.checksum(&package.data);
         let checksum = format!("{:08X}", crc);
 
         // Basic validation: ensure non-empty
-        let valid = !package.data.is_empty();
+        let valid = signature_error.is_none() && !package.data.is_empty();
 
         let now = Utc::now();
         package.status = if valid {
@@ -1492,7 +2625,7 @@ impl DiagnosticBackend for UdsBackend {
             error: if valid {
                 None
             } else {
-                Some("Package is empty".to_string())
+                Some(signature_error.unwrap_or_else(|| "Package is empty".to_string()))
             },
         })
     }
@@ -1512,129 +2645,11 @@ impl DiagnosticBackend for UdsBackend {
     // =========================================================================
 
     async fn start_flash(&self) -> BackendResult<String> {
-        // Check if there's already an active transfer.
-        // Allow restart from terminal states and post-transfer states
-        // (AwaitingReboot/Activated — user may have power-cycled the ECU).
-        {
-            let flash_state = self.flash_state.read();
-            if let Some(ref transfer) = *flash_state {
-                if matches!(
-                    transfer.state,
-                    FlashState::Queued
-                        | FlashState::Preparing
-                        | FlashState::Transferring
-                        | FlashState::AwaitingActivation
-                        | FlashState::Validated
-                        | FlashState::Verifying
-                ) {
-                    // Spec C-111 + Table 18: 409 with
-                    // `update-process-in-progress` error_code.
-                    return Err(BackendError::UpdateInProgress(format!(
-                        "Flash transfer already in progress: {}",
-                        transfer.id
-                    )));
-                }
-            }
-        }
-
-        // Find the most recently verified package to flash.
-        // Multiple verified uploads can coexist in the package store across
-        // flash cycles, so picking the first HashMap entry is nondeterministic
-        // and can reuse a stale package from an earlier transfer.
-        let (manifest_id, package_data) = {
-            let packages = self.packages.read();
-            let (id, pkg) = packages
-                .iter()
-                .filter(|(_, p)| p.status == PackageStatus::Verified)
-                .max_by_key(|(_, p)| p.verified_at.as_ref().cloned())
-                .ok_or_else(|| {
-                    BackendError::InvalidRequest(
-                        "No verified package available for flashing".to_string(),
-                    )
-                })?;
-            (id.clone(), pkg.data.clone())
-        };
-
-        // Capture current SW version before flashing (for rollback support)
-        if self.flash_commit_config.supports_rollback {
-            // Read DID 0xF189 (ECU Software Version)
-            match self.uds.read_data_by_id(&[0xF189]).await {
-                Ok(response) if response.len() > 3 => {
-                    let version_bytes = &response[3..];
-                    let version = String::from_utf8_lossy(version_bytes).trim().to_string();
-                    let mut activation = self.activation_state.write();
-                    activation.previous_version = Some(version.clone());
-                    info!(version = %version, "Captured current SW version for rollback");
-                }
-                _ => {
-                    warn!("Could not read current SW version (DID 0xF189) for rollback");
-                }
-            }
-        }
-
-        // Caller is responsible for session and security setup before starting flash.
-
-        let transfer_id = Uuid::new_v4().to_string();
-        let data_len = package_data.len() as u64;
-
-        // Create initial transfer state
-        let transfer = FlashTransfer {
-            id: transfer_id.clone(),
-            package_id: manifest_id.clone(),
-            state: FlashState::Queued,
-            progress: FlashProgress {
-                bytes_transferred: 0,
-                bytes_total: data_len,
-                blocks_transferred: 0,
-                blocks_total: 0,
-                percent: 0.0,
-            },
-            error: None,
-            abort_handle: None,
-        };
-
-        {
-            let mut flash_state = self.flash_state.write();
-            *flash_state = Some(transfer);
-        }
-
-        // Spawn the flash task
-        let uds = self.uds.clone();
-        let flash_state = self.flash_state.clone();
-        let transfer_id_clone = transfer_id.clone();
-        let sessions = self.config.sessions.clone();
-        let session_manager = self.session_manager.clone();
-        let unlock = self.unlock.clone();
-
-        let task = tokio::spawn(async move {
-            Self::run_flash_transfer(
-                uds,
-                flash_state,
-                sessions,
-                session_manager,
-                unlock,
-                transfer_id_clone,
-                package_data,
-            )
-            .await
-        });
-
-        // Store the abort handle
-        {
-            let mut flash_state = self.flash_state.write();
-            if let Some(ref mut transfer) = *flash_state {
-                transfer.abort_handle = Some(task.abort_handle());
-            }
-        }
-
-        info!(
-            transfer_id = %transfer_id,
-            manifest_id = %manifest_id,
-            size = data_len,
-            "Flash transfer started"
-        );
+        self.spawn_flash(false).await
+    }
 
-        Ok(transfer_id)
+    async fn start_flash_dry_run(&self) -> BackendResult<String> {
+        self.spawn_flash(true).await
     }
 
     async fn get_flash_status(&self, transfer_id: &str) -> BackendResult<FlashStatus> {
@@ -1656,6 +2671,7 @@ impl DiagnosticBackend for UdsBackend {
             state: transfer.state,
             progress: Some(transfer.progress.clone()),
             error: transfer.error.clone(),
+            summary: transfer.summary.clone(),
         })
     }
 
@@ -1668,6 +2684,7 @@ impl DiagnosticBackend for UdsBackend {
                 state: transfer.state,
                 progress: Some(transfer.progress.clone()),
                 error: transfer.error.clone(),
+                summary: transfer.summary.clone(),
             }]),
             None => Ok(vec![]),
         }
@@ -1731,6 +2748,15 @@ impl DiagnosticBackend for UdsBackend {
             warn!(transfer_id = %transfer_id, error = %e, "Failed to send transfer exit to ECU during abort");
         }
 
+        // The aborted task never got to re-enable communication or DTC logging
+        if self.flash_commit_config.disable_communication {
+            Self::flash_comm_control(&self.uds, &self.comm_control_state, COMM_CONTROL_DEFAULT)
+                .await;
+        }
+        if self.flash_commit_config.disable_dtc_setting {
+            Self::flash_dtc_setting(&self.uds, &self.dtc_setting_state, DTC_SETTING_DEFAULT).await;
+        }
+
         // Return to default session so the ECU fully resets its state.
         // Per ISO 14229, session change to default clears active transfers.
         // Without this, a subsequent start_flash re-enters programming session
@@ -1879,7 +2905,7 @@ impl DiagnosticBackend for UdsBackend {
         }
 
         // Auto-detect reset if still in AwaitingReboot (handles external power cycles)
-        self.check_activation_transition().await;
+        let active_version = self.check_activation_transition().await;
 
         // Validate activation state
         {
@@ -1923,6 +2949,9 @@ impl DiagnosticBackend for UdsBackend {
             let mut flash_state = self.flash_state.write();
             if let Some(ref mut transfer) = *flash_state {
                 transfer.state = FlashState::Committed;
+                if let Some(ref mut summary) = transfer.summary {
+                    summary.verified_version = active_version;
+                }
             }
         }
 
@@ -2063,120 +3092,461 @@ impl UdsBackend {
 }
 
 impl UdsBackend {
-    /// Internal method to run the flash transfer process
-    #[allow(clippy::too_many_arguments)]
-    async fn run_flash_transfer(
+    /// Wait for the ECU to come back after a reset, restore the session and
+    /// security level the subscriptions ran under, then re-arm them
+    async fn recover_subscriptions(
         uds: UdsService,
-        flash_state: Arc<RwLock<Option<FlashTransfer>>>,
-        sessions: crate::config::SessionConfig,
+        recovery: crate::config::SubscriptionRecoveryConfig,
         session_manager: Arc<SessionManager>,
+        stream_manager: Arc<StreamManager>,
         unlock: Option<Arc<TransparentUnlock>>,
-        transfer_id: String,
-        data: Vec<u8>,
+        session: Option<u8>,
+        security_level: Option<u8>,
     ) {
-        // Helper to update state
-        let update_state = |state: FlashState| {
-            let mut fs = flash_state.write();
-            if let Some(ref mut transfer) = *fs {
-                if transfer.id == transfer_id {
-                    transfer.state = state;
-                }
+        let probe_interval = std::time::Duration::from_millis(recovery.probe_interval_ms);
+        let deadline =
+            tokio::time::Instant::now() + std::time::Duration::from_millis(recovery.timeout_ms);
+        loop {
+            tokio::time::sleep(probe_interval).await;
+            if uds.tester_present(false).await.is_ok() {
+                break;
             }
-        };
-
-        let update_error = |error: String| {
-            let mut fs = flash_state.write();
-            if let Some(ref mut transfer) = *fs {
-                if transfer.id == transfer_id {
-                    transfer.state = FlashState::Failed;
-                    transfer.error = Some(error);
-                }
+            if tokio::time::Instant::now() >= deadline {
+                warn!(
+                    timeout_ms = recovery.timeout_ms,
+                    "ECU did not answer after reset, subscriptions stay stopped"
+                );
+                return;
             }
-        };
+        }
 
-        let update_progress = |bytes: u64, blocks: u32, total_blocks: u32| {
-            let mut fs = flash_state.write();
-            if let Some(ref mut transfer) = *fs {
-                if transfer.id == transfer_id {
-                    transfer.progress.bytes_transferred = bytes;
-                    transfer.progress.blocks_transferred = blocks;
-                    transfer.progress.blocks_total = total_blocks;
-                    transfer.progress.percent = if transfer.progress.bytes_total > 0 {
-                        (bytes as f64 / transfer.progress.bytes_total as f64) * 100.0
-                    } else {
-                        100.0
-                    };
+        if let Some(session_id) = session {
+            if let Err(e) = session_manager.change_session(session_id).await {
+                warn!(
+                    error = %e,
+                    session_id = format!("0x{:02X}", session_id),
+                    "Could not restore session after reset"
+                );
+            }
+        }
+        if let Some(level) = security_level {
+            match unlock.as_ref() {
+                Some(unlock) => {
+                    if let Err(e) =
+                        Self::perform_unlock(&session_manager, unlock.provider.as_ref(), level)
+                            .await
+                    {
+                        warn!(error = %e, level, "Could not restore security after reset");
+                    }
                 }
+                None => warn!(
+                    level,
+                    "No unlock provider, security not restored after reset"
+                ),
             }
-        };
-
-        // Caller is responsible for session and security setup before starting flash.
-        // Step 1: Preparing - request download
-        update_state(FlashState::Preparing);
+        }
 
-        // Step 2: Request Download (UDS 0x34)
-        let memory_address: &[u8] = &[0x00, 0x00, 0x00, 0x00];
-        let memory_size = (data.len() as u32).to_be_bytes();
+        if let Err(e) = stream_manager.resume().await {
+            warn!(error = %e, "Could not re-arm subscriptions after reset");
+        }
+    }
 
-        let max_block_size = match uds
-            .request_download(0x00, 0x44, memory_address, &memory_size)
-            .await
+    /// Queue a flash of the most recently verified package and spawn its
+    /// transfer task; a dry run stops after RequestDownload
+    async fn spawn_flash(&self, dry_run: bool) -> BackendResult<String> {
+        // Check if there's already an active transfer.
+        // Allow restart from terminal states and post-transfer states
+        // (AwaitingReboot/Activated — user may have power-cycled the ECU).
         {
-            Ok(size) => size,
-            // Transparent server-side SecurityAccess: if RequestDownload is
-            // denied (NRC 0x33) and this ECU has an unlock provider, unlock and
-            // retry once. Covers classic clients that set the programming
-            // session but skip the security step.
-            Err(e) if unlock.is_some() && is_security_access_denied(&e) => {
-                let unlock = unlock.as_ref().unwrap();
-                if let Err(e) =
-                    Self::perform_unlock(&session_manager, unlock.provider.as_ref(), unlock.level)
-                        .await
-                {
-                    update_error(format!("Transparent SecurityAccess failed: {}", e));
-                    return;
-                }
-                info!(
-                    level = unlock.level,
-                    "Transparent server-side SecurityAccess granted for flash download"
-                );
-                match uds
-                    .request_download(0x00, 0x44, memory_address, &memory_size)
-                    .await
-                {
-                    Ok(size) => size,
-                    Err(e) => {
-                        update_error(format!("RequestDownload failed: {}", e));
-                        return;
-                    }
+            let flash_state = self.flash_state.read();
+            if let Some(ref transfer) = *flash_state {
+                if matches!(
+                    transfer.state,
+                    FlashState::Queued
+                        | FlashState::Preparing
+                        | FlashState::Transferring
+                        | FlashState::AwaitingActivation
+                        | FlashState::Validated
+                        | FlashState::Verifying
+                ) {
+                    // Spec C-111 + Table 18: 409 with
+                    // `update-process-in-progress` error_code.
+                    return Err(BackendError::UpdateInProgress(format!(
+                        "Flash transfer already in progress: {}",
+                        transfer.id
+                    )));
                 }
             }
-            Err(e) => {
-                update_error(format!("RequestDownload failed: {}", e));
-                return;
-            }
-        };
-
-        // Calculate block count
-        let block_size = (max_block_size as usize).saturating_sub(2); // Account for block counter
-        if block_size == 0 {
-            update_error("Invalid block size from ECU".to_string());
-            return;
         }
 
-        let total_blocks = data.len().div_ceil(block_size) as u32;
-
-        // Step 3: Transfer Data (UDS 0x36)
-        update_state(FlashState::Transferring);
+        // Held by the flash task until it finishes, so no memory upload
+        // starts in between
+        let transfer_guard = self.transfer_lock.clone().try_lock_owned().map_err(|_| {
+            BackendError::Busy("Another transfer with the ECU is in progress".to_string())
+        })?;
 
-        let block_counter_start = sessions.transfer_data_block_counter_start;
+        // Find the most recently verified package to flash.
+        // Multiple verified uploads can coexist in the package store across
+        // flash cycles, so picking the first HashMap entry is nondeterministic
+        // and can reuse a stale package from an earlier transfer.
+        let (manifest_id, package_data) = {
+            let packages = self.packages.read();
+            let (id, pkg) = packages
+                .iter()
+                .filter(|(_, p)| p.status == PackageStatus::Verified)
+                .max_by_key(|(_, p)| p.verified_at.as_ref().cloned())
+                .ok_or_else(|| {
+                    BackendError::InvalidRequest(
+                        "No verified package available for flashing".to_string(),
+                    )
+                })?;
+            (id.clone(), pkg.data.clone())
+        };
+
+        // Capture current SW version before flashing (for rollback support)
+        if self.flash_commit_config.supports_rollback && !dry_run {
+            // Read DID 0xF189 (ECU Software Version)
+            match self.uds.read_data_by_id(&[0xF189]).await {
+                Ok(response) if response.len() > 3 => {
+                    let version_bytes = &response[3..];
+                    let version = String::from_utf8_lossy(version_bytes).trim().to_string();
+                    let mut activation = self.activation_state.write();
+                    activation.previous_version = Some(version.clone());
+                    info!(version = %version, "Captured current SW version for rollback");
+                }
+                _ => {
+                    warn!("Could not read current SW version (DID 0xF189) for rollback");
+                }
+            }
+        }
+
+        // Caller is responsible for session and security setup before starting flash.
+
+        let transfer_id = Uuid::new_v4().to_string();
+        let data_len = package_data.len() as u64;
+
+        // Create initial transfer state
+        let transfer = FlashTransfer {
+            id: transfer_id.clone(),
+            package_id: manifest_id.clone(),
+            state: FlashState::Queued,
+            progress: FlashProgress {
+                bytes_transferred: 0,
+                bytes_total: data_len,
+                blocks_transferred: 0,
+                blocks_total: 0,
+                percent: 0.0,
+            },
+            error: None,
+            summary: None,
+            abort_handle: None,
+        };
+
+        {
+            let mut flash_state = self.flash_state.write();
+            *flash_state = Some(transfer);
+        }
+
+        // Spawn the flash task
+        let uds = self.uds.clone();
+        let flash_state = self.flash_state.clone();
+        let transfer_id_clone = transfer_id.clone();
+        let sessions = self.config.sessions.clone();
+        let session_manager = self.session_manager.clone();
+        let unlock = self.unlock.clone();
+        let compression = self.flash_commit_config.compression;
+        // A dry run sends no data, so there's nothing to quiesce the bus for
+        let quiesce = self.flash_commit_config.disable_communication && !dry_run;
+        let suppress_dtcs = self.flash_commit_config.disable_dtc_setting && !dry_run;
+        let comm_control_state = self.comm_control_state.clone();
+        let dtc_setting_state = self.dtc_setting_state.clone();
+
+        let task = tokio::spawn(async move {
+            // DTC logging off first, so quiescing the bus logs nothing
+            if suppress_dtcs {
+                Self::flash_dtc_setting(&uds, &dtc_setting_state, "off").await;
+            }
+            if quiesce {
+                Self::flash_comm_control(&uds, &comm_control_state, "disable-rx-tx").await;
+            }
+            Self::run_flash_transfer(
+                uds.clone(),
+                flash_state,
+                sessions,
+                session_manager,
+                unlock,
+                compression,
+                transfer_id_clone,
+                package_data,
+                dry_run,
+            )
+            .await;
+            if quiesce {
+                Self::flash_comm_control(&uds, &comm_control_state, COMM_CONTROL_DEFAULT).await;
+            }
+            if suppress_dtcs {
+                Self::flash_dtc_setting(&uds, &dtc_setting_state, DTC_SETTING_DEFAULT).await;
+            }
+            drop(transfer_guard);
+        });
+
+        // Store the abort handle
+        {
+            let mut flash_state = self.flash_state.write();
+            if let Some(ref mut transfer) = *flash_state {
+                transfer.abort_handle = Some(task.abort_handle());
+            }
+        }
+
+        info!(
+            transfer_id = %transfer_id,
+            manifest_id = %manifest_id,
+            size = data_len,
+            dry_run,
+            "Flash transfer started"
+        );
+
+        Ok(transfer_id)
+    }
+
+    /// CommunicationControl (0x28) around a flash transfer. Best effort: a
+    /// refusal is logged and leaves the tracked `modes/comm-ctrl` state as
+    /// it was, so a client can see the bus wasn't quiesced.
+    async fn flash_comm_control(uds: &UdsService, state: &RwLock<String>, value: &str) {
+        let Some(subfunction) = comm_control_subfunction(value) else {
+            return;
+        };
+        match uds
+            .communication_control(
+                subfunction,
+                crate::uds::comm_control_type::NORMAL_COMMUNICATION_MESSAGES,
+            )
+            .await
+        {
+            Ok(()) => {
+                *state.write() = value.to_string();
+                info!(value, "CommunicationControl (0x28) set for flash");
+            }
+            Err(e) => warn!(value, error = %e, "CommunicationControl (0x28) for flash refused"),
+        }
+    }
+
+    /// ControlDTCSetting (0x85) around a flash transfer; best effort like
+    /// [`Self::flash_comm_control`], tracked in `modes/dtcsetting`
+    async fn flash_dtc_setting(uds: &UdsService, state: &RwLock<String>, value: &str) {
+        let Some(subfunction) = dtc_setting_subfunction(value) else {
+            return;
+        };
+        match uds.control_dtc_setting(subfunction).await {
+            Ok(()) => {
+                *state.write() = value.to_string();
+                info!(value, "ControlDTCSetting (0x85) set for flash");
+            }
+            Err(e) => warn!(value, error = %e, "ControlDTCSetting (0x85) for flash refused"),
+        }
+    }
+
+    /// Internal method to run the flash transfer process
+    #[allow(clippy::too_many_arguments)]
+    async fn run_flash_transfer(
+        uds: UdsService,
+        flash_state: Arc<RwLock<Option<FlashTransfer>>>,
+        sessions: crate::config::SessionConfig,
+        session_manager: Arc<SessionManager>,
+        unlock: Option<Arc<TransparentUnlock>>,
+        compression: CompressionMethod,
+        transfer_id: String,
+        data: Vec<u8>,
+        dry_run: bool,
+    ) {
+        // Helper to update state
+        let update_state = |state: FlashState| {
+            let mut fs = flash_state.write();
+            if let Some(ref mut transfer) = *fs {
+                if transfer.id == transfer_id {
+                    transfer.state = state;
+                }
+            }
+        };
+
+        let update_error = |error: String| {
+            let mut fs = flash_state.write();
+            if let Some(ref mut transfer) = *fs {
+                if transfer.id == transfer_id {
+                    transfer.state = FlashState::Failed;
+                    transfer.error = Some(error);
+                }
+            }
+        };
+
+        let update_progress = |bytes: u64, blocks: u32, total_blocks: u32| {
+            let mut fs = flash_state.write();
+            if let Some(ref mut transfer) = *fs {
+                if transfer.id == transfer_id {
+                    transfer.progress.bytes_transferred = bytes;
+                    transfer.progress.blocks_transferred = blocks;
+                    transfer.progress.blocks_total = total_blocks;
+                    transfer.progress.percent = if transfer.progress.bytes_total > 0 {
+                        (bytes as f64 / transfer.progress.bytes_total as f64) * 100.0
+                    } else {
+                        100.0
+                    };
+                }
+            }
+        };
+
+        // Caller is responsible for session and security setup before starting flash.
+        // Step 1: Preparing - request download
+        update_state(FlashState::Preparing);
+        let started = std::time::Instant::now();
+        let mut retries: u32 = 0;
+
+        // Step 2: Request Download (UDS 0x34)
+        let memory_address: &[u8] = &[0x00, 0x00, 0x00, 0x00];
+        // memorySize is the uncompressed size; the compression method rides
+        // in the dataFormatIdentifier.
+        let memory_size = (data.len() as u32).to_be_bytes();
+        let data_format = compression.data_format_identifier();
+
+        let max_block_size = match uds
+            .request_download(data_format, 0x44, memory_address, &memory_size)
+            .await
+        {
+            Ok(size) => size,
+            // Transparent server-side SecurityAccess: if RequestDownload is
+            // denied (NRC 0x33) and this ECU has an unlock provider, unlock and
+            // retry once. Covers classic clients that set the programming
+            // session but skip the security step.
+            Err(e) if unlock.is_some() && is_security_access_denied(&e) => {
+                let unlock = unlock.as_ref().unwrap();
+                if let Err(e) =
+                    Self::perform_unlock(&session_manager, unlock.provider.as_ref(), unlock.level)
+                        .await
+                {
+                    update_error(format!("Transparent SecurityAccess failed: {}", e));
+                    return;
+                }
+                info!(
+                    level = unlock.level,
+                    "Transparent server-side SecurityAccess granted for flash download"
+                );
+                retries += 1;
+                match uds
+                    .request_download(data_format, 0x44, memory_address, &memory_size)
+                    .await
+                {
+                    Ok(size) => size,
+                    Err(e) => {
+                        update_error(format!("RequestDownload failed: {}", e));
+                        return;
+                    }
+                }
+            }
+            Err(e) => {
+                update_error(format!("RequestDownload failed: {}", e));
+                return;
+            }
+        };
+
+        // Some ECUs advertise a maxNumberOfBlockLength the transport cannot
+        // carry in one message (e.g. > 4095 bytes over classic ISO-TP).
+        // Clamp to what fits rather than failing every TransferData.
+        let advertised_block_length = max_block_size.saturating_add(2);
+        let mut clamped_to = None;
+        let max_block_size = match transfer_block_limit(
+            uds.max_message_len(),
+            sessions.transfer_data_max_block_length,
+        ) {
+            Some(limit) if advertised_block_length > limit => {
+                warn!(
+                    transfer_id = %transfer_id,
+                    advertised = advertised_block_length,
+                    clamped = limit,
+                    "ECU maxNumberOfBlockLength exceeds transport limit, clamping TransferData blocks"
+                );
+                clamped_to = Some(limit);
+                limit.saturating_sub(2)
+            }
+            _ => max_block_size,
+        };
+
+        // Calculate block count
+        let block_size = (max_block_size as usize).saturating_sub(2); // Account for block counter
+        if block_size == 0 {
+            update_error("Invalid block size from ECU".to_string());
+            return;
+        }
+
+        // Compressed blocks carry fewer raw bytes so that incompressible data
+        // still fits the block length.
+        let raw_block_size = compression.raw_block_size(block_size);
+        if raw_block_size == 0 {
+            update_error(format!(
+                "Block size {} too small for {:?} compression",
+                block_size, compression
+            ));
+            return;
+        }
+        let total_blocks = data.len().div_ceil(raw_block_size) as u32;
+
+        // Dry run: the plan is all we wanted. Close the negotiated download
+        // without sending a single block, as abort does (errors ignored).
+        if dry_run {
+            if let Err(e) = uds.request_transfer_exit(&[]).await {
+                warn!(transfer_id = %transfer_id, error = %e, "Failed to send transfer exit after dry run");
+            }
+            let summary = FlashSummary {
+                retries,
+                dry_run: true,
+                plan: Some(FlashPlan {
+                    bytes_total: data.len() as u64,
+                    block_count: total_blocks,
+                    block_size: raw_block_size as u32,
+                }),
+                ..FlashSummary::new(0, 0, started.elapsed())
+            };
+            info!(
+                transfer_id = %transfer_id,
+                blocks = total_blocks,
+                block_size = raw_block_size,
+                "Dry-run flash planned, no TransferData sent"
+            );
+            let mut fs = flash_state.write();
+            if let Some(ref mut transfer) = *fs {
+                if transfer.id == transfer_id {
+                    transfer.state = FlashState::Complete;
+                    transfer.progress.blocks_total = total_blocks;
+                    transfer.summary = Some(summary);
+                }
+            }
+            return;
+        }
+
+        // Step 3: Transfer Data (UDS 0x36)
+        update_state(FlashState::Transferring);
+
+        let block_counter_start = sessions.transfer_data_block_counter_start;
         let mut block_counter: u8 = block_counter_start;
         let mut bytes_sent: u64 = 0;
-
-        for chunk in data.chunks(block_size) {
-            match uds.transfer_data(block_counter, chunk).await {
+        let mut blocks_sent: u32 = 0;
+        let mut wire_bytes: u64 = 0;
+
+        for chunk in data.chunks(raw_block_size) {
+            let block = compression.compress(chunk);
+            if block.len() > block_size {
+                update_error(format!(
+                    "Compressed block {} is {} bytes, over the {}-byte block length",
+                    block_counter,
+                    block.len(),
+                    block_size
+                ));
+                return;
+            }
+            match uds.transfer_data(block_counter, &block).await {
                 Ok(_) => {
                     bytes_sent += chunk.len() as u64;
+                    wire_bytes += block.len() as u64;
+                    blocks_sent += 1;
                     update_progress(bytes_sent, block_counter as u32, total_blocks);
                     block_counter = block_counter.wrapping_add(1);
                     // Wrap to configured value (some ECUs skip 0)
@@ -2185,9 +3555,25 @@ impl UdsBackend {
                     }
                 }
                 Err(e) => {
+                    let hint = match clamped_to {
+                        // The ECU refused the very first clamped block: neither
+                        // its advertised length nor ours works for this link.
+                        Some(limit)
+                            if bytes_sent == 0
+                                && matches!(e, UdsError::NegativeResponse { .. }) =>
+                        {
+                            format!(
+                                " (ECU advertised maxNumberOfBlockLength {} and rejected the \
+                                 clamped length {}; set session.transfer_data_max_block_length \
+                                 to a block length this ECU accepts)",
+                                advertised_block_length, limit
+                            )
+                        }
+                        _ => String::new(),
+                    };
                     update_error(format!(
-                        "TransferData failed at block {}: {}",
-                        block_counter, e
+                        "TransferData failed at block {}: {}{}",
+                        block_counter, e, hint
                     ));
                     return;
                 }
@@ -2195,33 +3581,45 @@ impl UdsBackend {
         }
 
         // Step 4: Ready for RequestTransferExit
-        update_state(FlashState::AwaitingActivation);
+        let summary = FlashSummary {
+            retries,
+            ..FlashSummary::new(bytes_sent, blocks_sent, started.elapsed())
+        };
         info!(
             transfer_id = %transfer_id,
             bytes_sent,
-            blocks = total_blocks,
+            wire_bytes,
+            blocks = blocks_sent,
+            duration_ms = summary.duration_ms,
+            throughput_bytes_per_sec = summary.throughput_bytes_per_sec,
+            retries,
             "Flash transfer complete, awaiting finalize"
         );
+        {
+            let mut fs = flash_state.write();
+            if let Some(ref mut transfer) = *fs {
+                if transfer.id == transfer_id {
+                    transfer.state = FlashState::AwaitingActivation;
+                    transfer.summary = Some(summary);
+                }
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{MockConfig, TransportConfig, UnlockConfig};
+    use crate::config::{MockConfig, ResultFaultConfig, TransportConfig, UnlockConfig};
 
     fn test_config() -> UdsBackendConfig {
         UdsBackendConfig {
-            id: "example_ecu".to_string(),
-            name: "Test ECU".to_string(),
             description: Some("Test ECU for unit tests".to_string()),
-            transport: TransportConfig::Mock(MockConfig { latency_ms: 0 }),
-            operations: vec![],
-            outputs: vec![],
-            service_overrides: Default::default(),
-            sessions: Default::default(),
-            flash_commit: Default::default(),
-            unlock: None,
+            ..UdsBackendConfig::new(
+                "example_ecu",
+                "Test ECU",
+                TransportConfig::Mock(MockConfig { latency_ms: 0 }),
+            )
         }
     }
 
@@ -2364,7 +3762,37 @@ mod tests {
         }
     }
 
-    #[tokio::test]
+    /// Key = seed with every byte incremented, standing in for an OEM
+    /// algorithm the crate does not ship.
+    struct IncrementUnlock;
+
+    impl UnlockProvider for IncrementUnlock {
+        fn compute_key(
+            &self,
+            _level: u8,
+            seed: &[u8],
+        ) -> Result<Vec<u8>, crate::unlock::UnlockError> {
+            Ok(seed.iter().map(|b| b.wrapping_add(1)).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_unlock_provider_drives_the_handshake() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x27, 0x03], vec![0x67, 0x03, 0x10, 0x20]);
+        mock.add_response(vec![0x27, 0x04], vec![0x67, 0x04]);
+        let backend = UdsBackend::with_transport(test_config(), mock.clone())
+            .unwrap()
+            .with_unlock_provider(Arc::new(IncrementUnlock), 2);
+
+        backend.ensure_unlocked_for(2).await.unwrap();
+
+        assert!(mock.sent_requests().contains(&vec![0x27, 0x04, 0x11, 0x21]));
+    }
+
+    #[tokio::test]
     async fn ensure_unlocked_for_level_zero_is_noop() {
         // security_level 0 ⇒ no gate at all, provider or not.
         let backend = UdsBackend::new(test_config()).await.unwrap();
@@ -2396,4 +3824,2286 @@ mod tests {
             "locked + failed unlock must be SecurityRequired(2), got {err:?}"
         );
     }
+
+    // -------------------------------------------------------------------------
+    // TransferData block length vs transport message limit
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn transfer_block_limit_takes_tighter_bound() {
+        assert_eq!(transfer_block_limit(None, None), None);
+        assert_eq!(transfer_block_limit(Some(4095), None), Some(4095));
+        assert_eq!(transfer_block_limit(None, Some(512)), Some(512));
+        assert_eq!(transfer_block_limit(Some(4095), Some(512)), Some(512));
+        assert_eq!(transfer_block_limit(Some(256), Some(512)), Some(256));
+    }
+
+    /// Mock ECU whose RequestDownload advertises maxNumberOfBlockLength
+    /// 0x1002 (4098) over a transport that carries at most 1024 bytes.
+    async fn oversized_block_backend(
+        transfer_reply: Vec<u8>,
+    ) -> (
+        UdsBackend,
+        Arc<crate::transport::mock::MockTransportAdapter>,
+    ) {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.set_max_message_len(Some(1024));
+        mock.add_response(vec![0x34], vec![0x74, 0x20, 0x10, 0x02]);
+        mock.add_response(vec![0x36], transfer_reply);
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+        (backend, mock)
+    }
+
+    async fn run_flash(backend: &UdsBackend, size: usize) -> FlashStatus {
+        let package_id = backend.receive_package(&vec![0xA5; size]).await.unwrap();
+        backend.verify_package(&package_id).await.unwrap();
+        let transfer_id = backend.start_flash().await.unwrap();
+        for _ in 0..200 {
+            let status = backend.get_flash_status(&transfer_id).await.unwrap();
+            if matches!(
+                status.state,
+                FlashState::AwaitingActivation | FlashState::Failed
+            ) {
+                return status;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        panic!("flash transfer did not finish");
+    }
+
+    #[tokio::test]
+    async fn completed_transfer_reports_summary() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 2 },
+        ));
+        // maxNumberOfBlockLength 0x0102 → 2000 bytes fit in 8 blocks
+        mock.add_response(vec![0x34], vec![0x74, 0x20, 0x01, 0x02]);
+        let backend = UdsBackend::with_transport(test_config(), mock).unwrap();
+
+        let status = run_flash(&backend, 2000).await;
+        assert_eq!(status.state, FlashState::AwaitingActivation);
+
+        let summary = status.summary.expect("summary once the transfer completed");
+        assert_eq!(summary.bytes_transferred, 2000);
+        assert_eq!(summary.blocks_transferred, 8);
+        assert_eq!(summary.retries, 0);
+        assert_eq!(summary.verified_version, None);
+        // 9 mock round trips at 2 ms each
+        assert!(summary.duration_ms >= 18, "{summary:?}");
+        let implied_bytes = summary.throughput_bytes_per_sec * summary.duration_ms as f64 / 1000.0;
+        assert!(
+            (implied_bytes - 2000.0).abs() / 2000.0 < 0.1,
+            "throughput inconsistent with bytes/duration: {summary:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn streamed_package_is_stored_whole() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let backend = UdsBackend::with_transport(test_config(), mock).unwrap();
+        let chunks: Vec<Result<bytes::Bytes, Box<dyn std::error::Error + Send + Sync>>> = vec![
+            Ok(bytes::Bytes::from(vec![0xA5; 1000])),
+            Ok(bytes::Bytes::from(vec![0x5A; 24])),
+        ];
+        // Announcing far more than arrives only bounds the reservation
+        let package_id = backend
+            .receive_package_stream(Box::pin(futures::stream::iter(chunks)), Some(u64::MAX))
+            .await
+            .unwrap();
+        assert_eq!(backend.get_package(&package_id).await.unwrap().size, 1024);
+
+        let failing: Vec<Result<bytes::Bytes, Box<dyn std::error::Error + Send + Sync>>> =
+            vec![Ok(bytes::Bytes::from(vec![0xA5; 10])), Err("reset".into())];
+        let err = backend
+            .receive_package_stream(Box::pin(futures::stream::iter(failing)), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BackendError::Internal(_)), "{err:?}");
+        assert_eq!(backend.list_packages().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn dry_run_reports_plan_without_transfer_data() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x34], vec![0x74, 0x20, 0x01, 0x02]);
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        let package_id = backend.receive_package(&[0xA5; 2000]).await.unwrap();
+        backend.verify_package(&package_id).await.unwrap();
+        let transfer_id = backend.start_flash_dry_run().await.unwrap();
+        let mut status = backend.get_flash_status(&transfer_id).await.unwrap();
+        for _ in 0..200 {
+            if matches!(status.state, FlashState::Complete | FlashState::Failed) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            status = backend.get_flash_status(&transfer_id).await.unwrap();
+        }
+
+        assert_eq!(status.state, FlashState::Complete, "{status:?}");
+        let summary = status.summary.expect("dry run reports a summary");
+        assert!(summary.dry_run);
+        assert_eq!(summary.blocks_transferred, 0);
+        // Same plan as the real transfer in completed_transfer_reports_summary
+        let plan = summary.plan.expect("dry run reports its plan");
+        assert_eq!(plan.block_count, 8);
+        assert_eq!(plan.bytes_total, 2000);
+        let sent = mock.sent_requests();
+        assert!(sent.iter().any(|r| r.first() == Some(&0x34)));
+        assert!(
+            !sent.iter().any(|r| r.first() == Some(&0x36)),
+            "{sent:02x?}"
+        );
+        // Finalizing a dry run would commit an empty image
+        assert!(backend.finalize_flash().await.is_err());
+    }
+
+    fn signing_config() -> (UdsBackendConfig, ed25519_dalek::SigningKey) {
+        use ed25519_dalek::pkcs8::{EncodePublicKey, LineEnding};
+        let key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let mut config = test_config();
+        config.flash_commit.signature = Some(crate::config::SignatureConfig {
+            algorithm: crate::signature::ALGORITHM_ED25519.to_string(),
+            public_key_pem: key
+                .verifying_key()
+                .to_public_key_pem(LineEnding::LF)
+                .unwrap(),
+        });
+        (config, key)
+    }
+
+    #[tokio::test]
+    async fn signed_package_flashes_the_bare_image() {
+        use ed25519_dalek::Signer;
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x34], vec![0x74, 0x20, 0x01, 0x02]);
+        let (config, key) = signing_config();
+        let backend = UdsBackend::with_transport(config, mock).unwrap();
+        let image = [0xA5; 2000];
+        let package = crate::signature::append_signature(&image, &key.sign(&image).to_bytes());
+
+        let package_id = backend.receive_package(&package).await.unwrap();
+        let result = backend.verify_package(&package_id).await.unwrap();
+        assert!(result.valid, "{result:?}");
+        // Re-verifying the stripped image stays valid
+        assert!(backend.verify_package(&package_id).await.unwrap().valid);
+
+        let transfer_id = backend.start_flash().await.unwrap();
+        let mut status = backend.get_flash_status(&transfer_id).await.unwrap();
+        for _ in 0..200 {
+            if matches!(
+                status.state,
+                FlashState::AwaitingActivation | FlashState::Failed
+            ) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            status = backend.get_flash_status(&transfer_id).await.unwrap();
+        }
+        assert_eq!(status.summary.unwrap().bytes_transferred, 2000);
+    }
+
+    #[tokio::test]
+    async fn unsigned_or_tampered_package_is_never_flashed() {
+        use ed25519_dalek::Signer;
+        let (config, key) = signing_config();
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+        let image = [0xA5; 64];
+        let mut tampered = crate::signature::append_signature(&image, &key.sign(&image).to_bytes());
+        tampered[0] ^= 0xFF;
+
+        for (package, reason) in [
+            (&image[..], "not signed"),
+            (&tampered[..], "does not match"),
+        ] {
+            let package_id = backend.receive_package(package).await.unwrap();
+            let result = backend.verify_package(&package_id).await.unwrap();
+            assert!(!result.valid);
+            assert!(result.error.unwrap().contains(reason));
+        }
+
+        assert!(matches!(
+            backend.start_flash().await,
+            Err(BackendError::InvalidRequest(_))
+        ));
+        assert!(mock.sent_requests().is_empty());
+    }
+
+    #[test]
+    fn bad_signature_key_fails_construction() {
+        let mut config = test_config();
+        config.flash_commit.signature = Some(crate::config::SignatureConfig {
+            algorithm: crate::signature::ALGORITHM_ED25519.to_string(),
+            public_key_pem: "not a key".to_string(),
+        });
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+
+        assert!(matches!(
+            UdsBackend::with_transport(config, mock),
+            Err(UdsBackendError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_required_session_fails_construction() {
+        let operation = |session: &str| OperationConfig {
+            id: "self_test".to_string(),
+            name: "Self Test".to_string(),
+            rid: "0x0203".to_string(),
+            description: None,
+            security_level: 0,
+            required_session: Some(session.to_string()),
+            result_faults: vec![],
+            params_def: vec![],
+        };
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+
+        let config = UdsBackendConfig {
+            operations: vec![operation("extended")],
+            ..test_config()
+        };
+        assert!(UdsBackend::with_transport(config, mock.clone()).is_ok());
+        // A typo, not a session
+        let config = UdsBackendConfig {
+            operations: vec![operation("extneded")],
+            ..test_config()
+        };
+        assert!(matches!(
+            UdsBackend::with_transport(config, mock),
+            Err(UdsBackendError::Config(_))
+        ));
+    }
+
+    /// Wait until `modes/comm-ctrl` and `modes/dtcsetting` read back the
+    /// power-on defaults, i.e. the flash task has restored both
+    async fn await_modes_restored(backend: &UdsBackend) {
+        for _ in 0..200 {
+            let comm = backend.get_communication_control().await.unwrap().value;
+            let dtc = backend.get_dtc_setting().await.unwrap().value;
+            if comm == COMM_CONTROL_DEFAULT && dtc == DTC_SETTING_DEFAULT {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        panic!("comm-ctrl / dtcsetting never restored");
+    }
+
+    #[tokio::test]
+    async fn flash_disables_communication_around_transfer() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x34], vec![0x74, 0x20, 0x01, 0x02]);
+        let mut config = test_config();
+        config.flash_commit.disable_communication = true;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let status = run_flash(&backend, 2000).await;
+        assert_eq!(status.state, FlashState::AwaitingActivation, "{status:?}");
+        await_modes_restored(&backend).await;
+
+        // disable-rx-tx before RequestDownload, enable-rx-tx after the last block
+        let sent = mock.sent_requests();
+        let position = |prefix: &[u8]| sent.iter().position(|r| r.starts_with(prefix)).unwrap();
+        let last_block = sent.iter().rposition(|r| r.first() == Some(&0x36)).unwrap();
+        assert!(position(&[0x28, 0x03, 0x01]) < position(&[0x34]));
+        assert!(position(&[0x28, 0x00, 0x01]) > last_block);
+    }
+
+    #[tokio::test]
+    async fn flash_suppresses_dtc_setting_around_transfer() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x34], vec![0x74, 0x20, 0x01, 0x02]);
+        let mut config = test_config();
+        config.flash_commit.disable_dtc_setting = true;
+        config.flash_commit.disable_communication = true;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let status = run_flash(&backend, 2000).await;
+        assert_eq!(status.state, FlashState::AwaitingActivation, "{status:?}");
+        await_modes_restored(&backend).await;
+
+        // 0x85 off → 0x28 disable → transfer → 0x28 enable → 0x85 on
+        let sent = mock.sent_requests();
+        let position = |prefix: &[u8]| sent.iter().position(|r| r.starts_with(prefix)).unwrap();
+        assert!(position(&[0x85, 0x02]) < position(&[0x28, 0x03]));
+        assert!(position(&[0x28, 0x03]) < position(&[0x34]));
+        assert!(position(&[0x28, 0x00]) < position(&[0x85, 0x01]));
+    }
+
+    #[tokio::test]
+    async fn flash_leaves_communication_alone_by_default() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x34], vec![0x74, 0x20, 0x01, 0x02]);
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        run_flash(&backend, 2000).await;
+
+        assert!(!mock
+            .sent_requests()
+            .iter()
+            .any(|r| r.first() == Some(&0x28)));
+    }
+
+    #[tokio::test]
+    async fn oversized_block_length_is_clamped_to_transport_limit() {
+        let (backend, _mock) = oversized_block_backend(vec![0x76, 0x00]).await;
+
+        let status = run_flash(&backend, 3000).await;
+
+        // The mock rejects any message over 1024 bytes, so finishing at all
+        // means every TransferData fit. 1024-byte limit → 1020 data bytes
+        // per block → 3 blocks (the ECU's 4098 would have been 1).
+        assert_eq!(status.state, FlashState::AwaitingActivation, "{status:?}");
+        let progress = status.progress.unwrap();
+        assert_eq!(progress.blocks_total, 3);
+        assert_eq!(progress.bytes_transferred, 3000);
+    }
+
+    #[tokio::test]
+    async fn rejected_clamped_block_length_suggests_config() {
+        // ECU refuses the clamped block with incorrectMessageLength.
+        let (backend, _mock) = oversized_block_backend(vec![0x7F, 0x36, 0x13]).await;
+
+        let status = run_flash(&backend, 3000).await;
+
+        assert_eq!(status.state, FlashState::Failed);
+        let error = status.error.unwrap();
+        assert!(
+            error.contains("transfer_data_max_block_length") && error.contains("4098"),
+            "error must point at the config knob, got: {error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn configured_block_length_caps_transfer() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x34], vec![0x74, 0x20, 0x10, 0x02]);
+        mock.add_response(vec![0x36], vec![0x76, 0x00]);
+        let mut config = test_config();
+        config.sessions.transfer_data_max_block_length = Some(514);
+        let backend = UdsBackend::with_transport(config, mock).unwrap();
+
+        let status = run_flash(&backend, 2040).await;
+
+        // 514-byte messages → 510 data bytes per block → 4 blocks.
+        assert_eq!(status.state, FlashState::AwaitingActivation, "{status:?}");
+        assert_eq!(status.progress.unwrap().blocks_total, 4);
+    }
+
+    #[tokio::test]
+    async fn deflate_compression_announces_format_and_inflates_back() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        // maxNumberOfBlockLength 0x0102 → 256 data bytes per block
+        mock.add_response(vec![0x34], vec![0x74, 0x20, 0x01, 0x02]);
+        mock.add_response(vec![0x36], vec![0x76, 0x00]);
+        let mut config = test_config();
+        config.flash_commit.compression = CompressionMethod::Deflate;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let status = run_flash(&backend, 2000).await;
+        assert_eq!(status.state, FlashState::AwaitingActivation, "{status:?}");
+        assert_eq!(status.progress.unwrap().bytes_transferred, 2000);
+
+        let sent = mock.sent_requests();
+        let download = sent.iter().find(|r| r[0] == 0x34).unwrap();
+        assert_eq!(download[1], 0x10, "dataFormatIdentifier");
+        // memorySize stays the uncompressed length
+        assert_eq!(&download[download.len() - 4..], &2000u32.to_be_bytes());
+
+        let mut inflated = Vec::new();
+        for block in sent.iter().filter(|r| r[0] == 0x36) {
+            assert!(block.len() - 2 <= 256);
+            let data = CompressionMethod::Deflate
+                .decompress(&block[2..], 2000)
+                .unwrap();
+            inflated.extend_from_slice(&data);
+        }
+        assert_eq!(inflated, vec![0xA5; 2000]);
+    }
+
+    // -------------------------------------------------------------------------
+    // Fault memory selection — ?memory=mirror → 0x19 0x17
+    // -------------------------------------------------------------------------
+
+    fn mirror_filter() -> FaultFilter {
+        FaultFilter {
+            memory: Some(FaultMemory::Mirror),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn mirror_memory_reads_user_def_memory() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(
+            vec![0x19, 0x17],
+            vec![0x59, 0x17, 0x42, 0xFF, 0x01, 0x01, 0x00, 0x08],
+        );
+        let mut config = test_config();
+        config.fault_memory.mirror_memory_selection = Some(0x42);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let result = backend.get_faults(Some(&mirror_filter())).await.unwrap();
+
+        assert_eq!(result.faults.len(), 1);
+        assert_eq!(result.faults[0].code, "P0101");
+        // reportUserDefMemoryDTCByStatusMask, all statuses, configured memory
+        assert!(
+            mock.sent_requests().contains(&vec![0x19, 0x17, 0xFF, 0x42]),
+            "sent: {:02X?}",
+            mock.sent_requests()
+        );
+    }
+
+    #[tokio::test]
+    async fn primary_memory_is_the_default() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        let primary = FaultFilter {
+            memory: Some(FaultMemory::Primary),
+            ..Default::default()
+        };
+        backend.get_faults(Some(&primary)).await.unwrap();
+        backend.get_faults(None).await.unwrap();
+
+        let sent = mock.sent_requests();
+        assert!(
+            sent.iter().all(|r| r[..2] == [0x19, 0x02]),
+            "sent: {sent:02X?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn mirror_memory_without_selection_is_not_supported() {
+        let backend = UdsBackend::new(test_config()).await.unwrap();
+        let err = backend
+            .get_faults(Some(&mirror_filter()))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, BackendError::NotSupported(msg) if msg.contains("mirror_memory_selection")),
+            "unconfigured mirror memory must be NotSupported, got {err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn mirror_memory_rejected_by_ecu_keeps_nrc() {
+        // ECU without 0x19 0x17 answers subFunctionNotSupported; the NRC is
+        // surfaced like any other (→ Table-18 error body via nrc_to_status).
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x19, 0x17], vec![0x7F, 0x19, 0x12]);
+        let mut config = test_config();
+        config.fault_memory.mirror_memory_selection = Some(0x01);
+        let backend = UdsBackend::with_transport(config, mock).unwrap();
+
+        let err = backend
+            .get_faults(Some(&mirror_filter()))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                BackendError::EcuError {
+                    nrc: 0x12,
+                    sid: 0x19,
+                    ..
+                }
+            ),
+            "got {err:?}"
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    // Declared DTC reports — fault_memory.reports picks the 0x19 sub-function
+    // -------------------------------------------------------------------------
+
+    /// Mock ECU that refuses the severity report (0x19 0x08)
+    fn status_mask_only_ecu() -> Arc<crate::transport::mock::MockTransportAdapter> {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x19, 0x08], vec![0x7F, 0x19, 0x12]);
+        mock
+    }
+
+    fn severity_requests(mock: &crate::transport::mock::MockTransportAdapter) -> usize {
+        mock.sent_requests()
+            .iter()
+            .filter(|r| r[..2] == [0x19, 0x08])
+            .count()
+    }
+
+    #[tokio::test]
+    async fn status_mask_only_ecu_is_never_asked_for_severity() {
+        let mock = status_mask_only_ecu();
+        let mut config = test_config();
+        config.fault_memory.reports = vec![DtcReport::StatusMask];
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let result = backend.get_faults(None).await.unwrap();
+
+        assert_eq!(result.faults.len(), 2);
+        assert_eq!(severity_requests(&mock), 0);
+        assert_eq!(backend.capabilities().dtc_reports, vec!["status-mask"]);
+    }
+
+    #[tokio::test]
+    async fn severity_report_sets_fault_severity() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(
+            vec![0x19, 0x08],
+            vec![
+                0x59, 0x08, 0xFF, // Header + status availability mask
+                0x80, 0x10, 0x01, 0x23, 0x45, 0x08, // check immediately
+                0x20, 0x10, 0x06, 0x78, 0x90, 0x08, // maintenance only
+            ],
+        );
+        let mut config = test_config();
+        config.fault_memory.reports = vec![DtcReport::StatusMask, DtcReport::SeverityMask];
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let result = backend.get_faults(None).await.unwrap();
+
+        let severities: Vec<_> = result.faults.iter().map(|f| f.severity).collect();
+        assert_eq!(
+            severities,
+            vec![FaultSeverity::Critical, FaultSeverity::Warning]
+        );
+        // All severities, all statuses
+        assert!(
+            mock.sent_requests().contains(&vec![0x19, 0x08, 0xFF, 0xFF]),
+            "sent: {:02X?}",
+            mock.sent_requests()
+        );
+    }
+
+    #[tokio::test]
+    async fn refused_severity_report_falls_back_to_status_mask() {
+        let mock = status_mask_only_ecu();
+        let mut config = test_config();
+        config.fault_memory.reports = vec![DtcReport::StatusMask, DtcReport::SeverityMask];
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        assert_eq!(backend.get_faults(None).await.unwrap().faults.len(), 2);
+        assert_eq!(backend.get_faults(None).await.unwrap().faults.len(), 2);
+
+        // Refused once, then not asked again
+        assert_eq!(severity_requests(&mock), 1);
+    }
+
+    #[tokio::test]
+    async fn undeclared_user_def_memory_is_not_supported() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let mut config = test_config();
+        config.fault_memory.reports = vec![DtcReport::StatusMask];
+        config.fault_memory.mirror_memory_selection = Some(0x01);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let err = backend
+            .get_faults(Some(&mirror_filter()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BackendError::NotSupported(_)), "got {err:?}");
+        assert!(mock.sent_requests().is_empty());
+    }
+
+    // -------------------------------------------------------------------------
+    // DTCStatusAvailabilityMask — 0x19 0x01, cached per ECU
+    // -------------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn status_availability_mask_is_read_once() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        // reportNumberOfDTCByStatusMask: mask 0x2F, format 0x01, count 0
+        mock.add_response(vec![0x19, 0x01], vec![0x59, 0x01, 0x2F, 0x01, 0x00, 0x00]);
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        assert_eq!(
+            backend.get_dtc_status_availability_mask().await.unwrap(),
+            0x2F
+        );
+        assert_eq!(
+            backend.get_dtc_status_availability_mask().await.unwrap(),
+            0x2F
+        );
+
+        let reads = mock
+            .sent_requests()
+            .iter()
+            .filter(|r| r[..2] == [0x19, 0x01])
+            .count();
+        assert_eq!(reads, 1, "mask is static, read it once");
+    }
+
+    #[tokio::test]
+    async fn unsupported_status_bits_are_ignored() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        // ECU supports only confirmedDTC (0x08) but sets testFailed too.
+        mock.add_response(
+            vec![0x19, 0x02],
+            vec![0x59, 0x02, 0x08, 0x01, 0x01, 0x00, 0x09],
+        );
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        let all = backend.get_faults(None).await.unwrap();
+        assert_eq!(all.status_availability_mask, Some(0x08));
+        assert_eq!(all.faults.len(), 1);
+        assert!(!all.faults[0].active, "testFailed is not a supported bit");
+        assert_eq!(all.faults[0].status.as_ref().unwrap()["mask"], "0x08");
+
+        let active_only = FaultFilter {
+            active_only: Some(true),
+            ..Default::default()
+        };
+        let active = backend.get_faults(Some(&active_only)).await.unwrap();
+        assert!(active.faults.is_empty());
+
+        // The 0x02 response already carried the mask — no extra 0x19 0x01.
+        assert_eq!(
+            backend.get_dtc_status_availability_mask().await.unwrap(),
+            0x08
+        );
+        assert!(mock.sent_requests().iter().all(|r| r[..2] != [0x19, 0x01]));
+    }
+
+    #[tokio::test]
+    async fn status_filter_matches_any_named_bit() {
+        use crate::uds::dtc::status_bit;
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(
+            vec![0x19, 0x02],
+            vec![
+                0x59, 0x02, 0xFF, // Header + status availability mask
+                0x01, 0x01, 0x00, 0x08, // confirmed
+                0x01, 0x02, 0x00, 0x04, // pending
+                0x01, 0x03, 0x00, 0x01, // test failed only
+            ],
+        );
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        let filter = FaultFilter {
+            status_mask: Some(status_bit::CONFIRMED_DTC | status_bit::PENDING_DTC),
+            ..Default::default()
+        };
+        let result = backend.get_faults(Some(&filter)).await.unwrap();
+
+        let codes: Vec<_> = result.faults.iter().map(|f| f.code.as_str()).collect();
+        assert_eq!(codes, vec!["P0101", "P0102"]);
+    }
+
+    #[tokio::test]
+    async fn severity_filter_needs_a_reported_severity() {
+        use crate::uds::dtc::severity_bit;
+        let filter = FaultFilter {
+            dtc_severity_mask: Some(severity_bit::CHECK_IMMEDIATELY),
+            ..Default::default()
+        };
+
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(
+            vec![0x19, 0x08],
+            vec![
+                0x59, 0x08, 0xFF, // Header + status availability mask
+                0x80, 0x10, 0x01, 0x23, 0x45, 0x08, // check immediately
+                0x20, 0x10, 0x06, 0x78, 0x90, 0x08, // maintenance only
+            ],
+        );
+        let mut config = test_config();
+        config.fault_memory.reports = vec![DtcReport::StatusMask, DtcReport::SeverityMask];
+        let backend = UdsBackend::with_transport(config, mock).unwrap();
+        let result = backend.get_faults(Some(&filter)).await.unwrap();
+        assert_eq!(result.faults.len(), 1);
+        assert_eq!(result.faults[0].severity, FaultSeverity::Critical);
+
+        // Status-mask report only: no severity byte, nothing matches
+        let mut config = test_config();
+        config.fault_memory.reports = vec![DtcReport::StatusMask];
+        let backend = UdsBackend::with_transport(config, status_mask_only_ecu()).unwrap();
+        let result = backend.get_faults(Some(&filter)).await.unwrap();
+        assert!(result.faults.is_empty());
+    }
+
+    // -------------------------------------------------------------------------
+    // Routine results surfaced as synthetic faults
+    // -------------------------------------------------------------------------
+
+    /// `self_test` (RID 0x0203) answers result 0x01 (failed) to option
+    /// byte 0xAA and 0x00 (passed) to 0xBB; the ECU holds one DTC.
+    fn routine_fault_backend() -> UdsBackend {
+        routine_fault_backend_with_mock("RT-SELFTEST-01").0
+    }
+
+    /// `self_test` raising a synthetic fault with `code` on result 0x01
+    fn routine_fault_backend_with_mock(
+        code: &str,
+    ) -> (
+        UdsBackend,
+        Arc<crate::transport::mock::MockTransportAdapter>,
+    ) {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(
+            vec![0x31, 0x01, 0x02, 0x03, 0xAA],
+            vec![0x71, 0x01, 0x02, 0x03, 0x01],
+        );
+        mock.add_response(
+            vec![0x31, 0x01, 0x02, 0x03, 0xBB],
+            vec![0x71, 0x01, 0x02, 0x03, 0x00],
+        );
+        mock.add_response(
+            vec![0x19, 0x02],
+            vec![0x59, 0x02, 0xFF, 0x01, 0x01, 0x00, 0x09],
+        );
+        let config = UdsBackendConfig {
+            operations: vec![OperationConfig {
+                id: "self_test".to_string(),
+                name: "Self Test".to_string(),
+                rid: "0x0203".to_string(),
+                description: None,
+                security_level: 0,
+                required_session: None,
+                result_faults: vec![ResultFaultConfig {
+                    result: 0x01,
+                    code: code.to_string(),
+                    category: Some("self_test".to_string()),
+                    description: "Self test failed".to_string(),
+                }],
+                params_def: vec![],
+            }],
+            ..test_config()
+        };
+        (
+            UdsBackend::with_transport(config, mock.clone()).unwrap(),
+            mock,
+        )
+    }
+
+    fn synthetic(faults: &FaultsResult) -> Vec<&Fault> {
+        faults
+            .faults
+            .iter()
+            .filter(|f| f.status.as_ref().unwrap()["x-sumo-synthetic"] == true)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn failing_routine_raises_synthetic_fault_until_it_passes() {
+        let backend = routine_fault_backend();
+
+        backend
+            .start_operation("self_test", &[0x01, 0xAA])
+            .await
+            .unwrap();
+        let faults = backend.get_faults(None).await.unwrap();
+        assert_eq!(faults.faults.len(), 2, "ECU DTC plus the routine fault");
+        let raised = synthetic(&faults);
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].code, "RT-SELFTEST-01");
+        assert_eq!(raised[0].category.as_deref(), Some("self_test"));
+        assert!(raised[0].active);
+        assert_eq!(
+            backend.get_fault_detail(&raised[0].id).await.unwrap().code,
+            "RT-SELFTEST-01"
+        );
+
+        // Failing again counts another occurrence of the same fault.
+        backend
+            .start_operation("self_test", &[0x01, 0xAA])
+            .await
+            .unwrap();
+        let faults = backend.get_faults(None).await.unwrap();
+        assert_eq!(synthetic(&faults)[0].occurrence_count, Some(2));
+
+        backend
+            .start_operation("self_test", &[0x01, 0xBB])
+            .await
+            .unwrap();
+        let faults = backend.get_faults(None).await.unwrap();
+        assert!(synthetic(&faults).is_empty(), "passing run clears it");
+        assert_eq!(faults.faults.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn clear_faults_clears_synthetic_faults() {
+        let backend = routine_fault_backend();
+        backend
+            .start_operation("self_test", &[0x01, 0xAA])
+            .await
+            .unwrap();
+
+        let cleared = backend.clear_faults(None).await.unwrap();
+        assert_eq!(cleared.cleared_count, 1);
+        let faults = backend.get_faults(None).await.unwrap();
+        assert!(synthetic(&faults).is_empty());
+    }
+
+    #[tokio::test]
+    async fn group_clear_takes_synthetic_faults_naming_a_dtc_in_it() {
+        let (backend, mock) = routine_fault_backend_with_mock("C12300");
+        mock.add_response(vec![0x14, 0xC1, 0x23, 0x01], vec![0x54]);
+        mock.add_response(vec![0x14, 0xC1, 0x23, 0x00], vec![0x54]);
+        backend
+            .start_operation("self_test", &[0x01, 0xAA])
+            .await
+            .unwrap();
+
+        let other = backend.clear_faults(Some(0xC12301)).await.unwrap();
+        assert_eq!(other.cleared_count, 0);
+        let faults = backend.get_faults(None).await.unwrap();
+        assert_eq!(synthetic(&faults).len(), 1);
+
+        let cleared = backend.clear_faults(Some(0xC12300)).await.unwrap();
+        assert_eq!(cleared.cleared_count, 1);
+        let faults = backend.get_faults(None).await.unwrap();
+        assert!(synthetic(&faults).is_empty());
+    }
+
+    #[tokio::test]
+    async fn clear_faults_keeps_synthetic_faults_without_positive_response() {
+        // A refusal, and an answer that is not the 0x54 confirming the clear
+        for answer in [vec![0x7F, 0x14, 0x22], vec![0x50, 0x01]] {
+            let (backend, mock) = routine_fault_backend_with_mock("RT-SELFTEST-01");
+            mock.replace_response(vec![0x14, 0xFF, 0xFF, 0xFF], answer);
+            backend
+                .start_operation("self_test", &[0x01, 0xAA])
+                .await
+                .unwrap();
+
+            assert!(backend.clear_faults(None).await.is_err());
+            let faults = backend.get_faults(None).await.unwrap();
+            assert_eq!(synthetic(&faults).len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn verify_clear_reports_dtcs_still_stored() {
+        // The mock's 0x19 02 keeps answering with two DTCs after the clear
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let mut config = test_config();
+        config.fault_memory.verify_clear = true;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let result = backend.clear_faults(None).await.unwrap();
+        let verification = result.verification.expect("verification");
+        assert!(!verification.cleared_confirmed);
+        assert_eq!(verification.remaining.len(), 2);
+
+        // Group clear only looks at that DTC
+        mock.add_response(vec![0x14, 0x01, 0x23, 0x45], vec![0x54]);
+        let result = backend.clear_faults(Some(0x012345)).await.unwrap();
+        let remaining = result.verification.unwrap().remaining;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(Dtc::parse_id(&remaining[0].id), Some([0x01, 0x23, 0x45]));
+    }
+
+    #[tokio::test]
+    async fn fault_snapshots_split_dids_by_known_lengths() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(
+            vec![0x19, 0x04, 0x01, 0x23, 0x45, 0xFF],
+            vec![
+                0x59, 0x04, 0x01, 0x23, 0x45, 0x09, // DTC + status
+                0x01, 0x02, // record 1, two DIDs
+                0xF4, 0x0D, 0x32, // vehicle speed (1 byte)
+                0xF4, 0x0C, 0x1F, 0x40, // engine speed (2 bytes)
+            ],
+        );
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        let lengths = HashMap::from([(0xF40D, 1), (0xF40C, 2)]);
+        let snapshots = backend
+            .get_fault_snapshots("012345", &lengths)
+            .await
+            .unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].record_number, 1);
+        assert_eq!(snapshots[0].items[0].did, 0xF40D);
+        assert_eq!(snapshots[0].items[0].data, vec![0x32]);
+        assert_eq!(snapshots[0].items[1].data, vec![0x1F, 0x40]);
+
+        // Without the first DID's length the record cannot be split
+        let lengths = HashMap::from([(0xF40C, 2)]);
+        let err = backend
+            .get_fault_snapshots("012345", &lengths)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BackendError::Protocol(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn fault_snapshots_size_a_closing_did_by_reading_its_record_alone() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(
+            vec![0x19, 0x04, 0x01, 0x23, 0x45, 0xFF],
+            vec![
+                0x59, 0x04, 0x01, 0x23, 0x45, 0x09, // DTC + status
+                0x01, 0x01, // record 1, one DID
+                0x12, 0x34, 0xDE, 0xAD, // undefined
+                0x02, 0x01, // record 2, one DID
+                0xF4, 0x0D, 0x32, // vehicle speed (1 byte)
+            ],
+        );
+        mock.add_response(
+            vec![0x19, 0x04, 0x01, 0x23, 0x45, 0x01],
+            vec![
+                0x59, 0x04, 0x01, 0x23, 0x45, 0x09, // DTC + status
+                0x01, 0x01, 0x12, 0x34, 0xDE, 0xAD, // record 1 alone
+            ],
+        );
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        let lengths = HashMap::from([(0xF40D, 1)]);
+        let snapshots = backend
+            .get_fault_snapshots("012345", &lengths)
+            .await
+            .unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].items[0].did, 0x1234);
+        assert_eq!(snapshots[0].items[0].data, vec![0xDE, 0xAD]);
+        assert_eq!(snapshots[1].record_number, 2);
+        assert_eq!(snapshots[1].items[0].data, vec![0x32]);
+    }
+
+    #[tokio::test]
+    async fn fault_extended_data_reports_configured_counters() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(
+            vec![0x19, 0x06, 0x01, 0x23, 0x45, 0xFF],
+            vec![
+                0x59, 0x06, 0x01, 0x23, 0x45, 0x09, // DTC + status
+                0x01, 0x05, // occurrence counter
+                0x02, 0x00, 0x10, // aging counter
+                0x90, 0xDE, 0xAD, // OEM record, not configured
+            ],
+        );
+        let mut config = test_config();
+        config.fault_memory.extended_data = vec![
+            crate::config::ExtendedDataRecordConfig {
+                record: 0x01,
+                name: "occurrence_counter".to_string(),
+                length: 1,
+            },
+            crate::config::ExtendedDataRecordConfig {
+                record: 0x02,
+                name: "aging_counter".to_string(),
+                length: 2,
+            },
+        ];
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let records = backend
+            .get_fault_extended_data("012345", 0xFF)
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].name.as_deref(), Some("occurrence_counter"));
+        assert_eq!(records[0].value, Some(5));
+        assert_eq!(records[1].value, Some(0x10));
+        assert_eq!(records[1].data, vec![0x00, 0x10]);
+        assert_eq!(records[2].record_number, 0x90);
+        assert_eq!(records[2].name, None);
+        assert_eq!(records[2].value, None);
+        assert_eq!(records[2].data, vec![0xDE, 0xAD]);
+    }
+
+    #[tokio::test]
+    async fn verify_clear_confirms_empty_fault_memory() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x19, 0x02, 0xFF], vec![0x59, 0x02, 0xFF]);
+        let mut config = test_config();
+        config.fault_memory.verify_clear = true;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let verification = backend.clear_faults(None).await.unwrap().verification;
+        let verification = verification.expect("verification");
+        assert!(verification.cleared_confirmed);
+        assert!(verification.remaining.is_empty());
+        assert_eq!(
+            mock.sent_requests(),
+            vec![vec![0x14, 0xFF, 0xFF, 0xFF], vec![0x19, 0x02, 0xFF]]
+        );
+
+        // Off by default: no re-read
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+        assert!(backend
+            .clear_faults(None)
+            .await
+            .unwrap()
+            .verification
+            .is_none());
+        assert_eq!(mock.sent_requests().len(), 3);
+    }
+
+    // -------------------------------------------------------------------------
+    // Session mode mapping
+    // -------------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn session_mode_uses_configured_sub_function() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let mut config = test_config();
+        config.sessions.extended_session = 0x43;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let mode = backend.set_session_mode("extended").await.unwrap();
+
+        assert!(
+            mock.sent_requests().contains(&vec![0x10, 0x43]),
+            "sent: {:02X?}",
+            mock.sent_requests()
+        );
+        assert_eq!(mode.session, "extended");
+        assert_eq!(mode.session_id, 0x43);
+        let mode = backend.get_session_mode().await.unwrap();
+        assert_eq!((mode.session.as_str(), mode.session_id), ("extended", 0x43));
+    }
+
+    #[tokio::test]
+    async fn standard_sub_function_is_not_taken_for_remapped_session() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let mut config = test_config();
+        config.sessions.extended_session = 0x43;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        // 0x03 is no longer "extended" on this ECU — it is sent as given.
+        let mode = backend.set_session_mode("0x03").await.unwrap();
+        assert_eq!(mode.session, "0x03");
+        assert!(mock.sent_requests().contains(&vec![0x10, 0x03]));
+    }
+
+    // -------------------------------------------------------------------------
+    // I/O control readback
+    // -------------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn output_readback_reports_override_until_session_resets() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x2F, 0xF0, 0x04, 0x03], vec![0x6F, 0xF0, 0x04, 0x03]);
+        let mut config = test_config();
+        config.outputs = vec![crate::config::OutputConfig {
+            id: "fan".to_string(),
+            name: "Fan".to_string(),
+            ioid: "0xF004".to_string(),
+            default_value: "00".to_string(),
+            description: None,
+            security_level: 0,
+            data_type: Some(crate::config::DataType::Uint8),
+            unit: None,
+            scale: 1.0,
+            offset: 0.0,
+            min: None,
+            max: None,
+            allowed: vec![],
+            range_policy: Default::default(),
+        }];
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let untouched = backend.get_output("fan").await.unwrap();
+        assert!(untouched.last_action.is_none());
+
+        backend
+            .control_output(
+                "fan",
+                IoControlAction::ShortTermAdjust,
+                Some(serde_json::json!(40)),
+            )
+            .await
+            .unwrap();
+        let held = backend.get_output("fan").await.unwrap();
+        assert!(held.controlled_by_tester);
+        assert_eq!(held.last_action.as_deref(), Some("short_term_adjust"));
+        assert_eq!(held.last_value, Some(serde_json::json!(40)));
+        assert!(held.last_commanded_at.is_some());
+        assert!(!held.session_reset);
+
+        // Leaving the session handed the output back to the ECU
+        backend.set_session_mode("extended").await.unwrap();
+        let released = backend.get_output("fan").await.unwrap();
+        assert!(!released.controlled_by_tester);
+        assert!(released.session_reset);
+        assert_eq!(released.last_value, Some(serde_json::json!(40)));
+        assert_eq!(released.last_commanded_at, held.last_commanded_at);
+    }
+
+    #[test]
+    fn session_timing_parses_parameter_record() {
+        let timing =
+            crate::uds::SessionTiming::from_response(&[0x50, 0x03, 0x00, 0x32, 0x01, 0xF4])
+                .unwrap();
+        assert_eq!(timing.p2_server_max, std::time::Duration::from_millis(50));
+        // P2* is in 10 ms units.
+        assert_eq!(
+            timing.p2_star_server_max,
+            std::time::Duration::from_millis(5000)
+        );
+
+        assert_eq!(
+            crate::uds::SessionTiming::from_response(&[0x50, 0x02]),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn session_change_adopts_advertised_timing() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(
+            mock.sent_timeouts().last(),
+            Some(&std::time::Duration::from_millis(5000))
+        );
+
+        // The mock's extended session advertises P2 25 ms, P2* 5000 ms.
+        let mode = backend.set_session_mode("extended").await.unwrap();
+        assert_eq!(
+            mode.timing,
+            Some(SessionTimingParameters {
+                p2_server_max_ms: 25,
+                p2_star_server_max_ms: 5000,
+            })
+        );
+        assert_eq!(
+            backend.get_session_mode().await.unwrap().timing,
+            mode.timing
+        );
+
+        // Later requests wait P2 plus the network margin.
+        backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(
+            mock.sent_timeouts().last(),
+            Some(&std::time::Duration::from_millis(525))
+        );
+
+        // A bare 0x50 response drops back to the static timeout.
+        let mode = backend.set_session_mode("programming").await.unwrap();
+        assert_eq!(mode.timing, None);
+        backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(
+            mock.sent_timeouts().last(),
+            Some(&std::time::Duration::from_millis(5000))
+        );
+    }
+
+    #[tokio::test]
+    async fn configured_timing_applies_until_session_advertises_its_own() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let mut config = test_config();
+        config.timing.p2_ms = Some(50);
+        config.timing.p2_star_ms = Some(100);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        // Configured P2 plus the network margin
+        backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(
+            mock.sent_timeouts().last(),
+            Some(&std::time::Duration::from_millis(550))
+        );
+
+        // The extended session's advertised P2 (25 ms) replaces it
+        backend.set_session_mode("extended").await.unwrap();
+        backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(
+            mock.sent_timeouts().last(),
+            Some(&std::time::Duration::from_millis(525))
+        );
+
+        // A bare 0x50 response drops back to the configured P2
+        backend.set_session_mode("programming").await.unwrap();
+        backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(
+            mock.sent_timeouts().last(),
+            Some(&std::time::Duration::from_millis(550))
+        );
+    }
+
+    #[tokio::test]
+    async fn configured_p2_star_bounds_pending_wait() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x22, 0xF1, 0x90], vec![0x7F, 0x22, 0x78]);
+        let mut config = test_config();
+        config.timing.p2_star_ms = Some(100);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        // No frame follows the 0x78: P2* (100 ms + margin), not 5000 ms
+        let started = std::time::Instant::now();
+        let err = backend.read_raw_did(0xF190).await.unwrap_err();
+        assert!(matches!(err, BackendError::Timeout), "{err:?}");
+        assert!(started.elapsed() >= std::time::Duration::from_millis(600));
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    }
+
+    // -------------------------------------------------------------------------
+    // Service allow/deny list
+    // -------------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn denied_service_is_refused_locally() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let mut config = test_config();
+        config.service_policy.deny = vec![crate::uds::service_id::ECU_RESET];
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let err = backend.ecu_reset(0x01).await.unwrap_err();
+        assert!(matches!(err, BackendError::Forbidden(_)), "{err:?}");
+        assert_eq!(err.status_code(), 403);
+        assert!(mock.sent_requests().is_empty());
+
+        // Reads are unaffected.
+        backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(mock.sent_requests(), vec![vec![0x22, 0xF1, 0x90]]);
+    }
+
+    #[tokio::test]
+    async fn allow_list_denies_unlisted_services() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let mut config = test_config();
+        config.service_policy.allow = Some(vec![crate::uds::service_id::READ_DATA_BY_ID]);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        backend.read_raw_did(0xF190).await.unwrap();
+        let err = backend.write_raw_did(0xF190, &[0x01]).await.unwrap_err();
+        assert!(matches!(err, BackendError::Forbidden(_)), "{err:?}");
+        assert_eq!(mock.sent_requests(), vec![vec![0x22, 0xF1, 0x90]]);
+    }
+
+    // -------------------------------------------------------------------------
+    // Response pending (NRC 0x78)
+    // -------------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn response_pending_waits_without_resending() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x22, 0xF1, 0x90], vec![0x7F, 0x22, 0x78]);
+        mock.add_follow_up(
+            vec![0x22, 0xF1, 0x90],
+            vec![vec![0x7F, 0x22, 0x78], vec![0x62, 0xF1, 0x90, 0xAB, 0xCD]],
+        );
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        let value = backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(value, vec![0xAB, 0xCD]);
+        assert_eq!(mock.sent_requests(), vec![vec![0x22, 0xF1, 0x90]]);
+    }
+
+    #[tokio::test]
+    async fn response_right_behind_pending_is_not_missed() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x22, 0xF1, 0x90], vec![0x7F, 0x22, 0x78]);
+        mock.add_immediate_follow_up(
+            vec![0x22, 0xF1, 0x90],
+            vec![vec![0x62, 0xF1, 0x90, 0xAB, 0xCD]],
+        );
+        let mut config = test_config();
+        config.timing.p2_star_ms = Some(200);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let value = backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(value, vec![0xAB, 0xCD]);
+        assert_eq!(mock.sent_requests(), vec![vec![0x22, 0xF1, 0x90]]);
+    }
+
+    #[tokio::test]
+    async fn response_pending_gives_up_per_config() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x22, 0xF1, 0x90], vec![0x7F, 0x22, 0x78]);
+        mock.add_follow_up(vec![0x22, 0xF1, 0x90], vec![vec![0x7F, 0x22, 0x78]]);
+        let mut config = test_config();
+        config.response_pending.max_pending = 1;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        // Second 0x78 exceeds max_pending
+        let err = backend.read_raw_did(0xF190).await.unwrap_err();
+        assert!(matches!(err, BackendError::Timeout), "{err:?}");
+
+        // No follow-up within P2*
+        let mut config = test_config();
+        config.timing.p2_star_ms = Some(50);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+        mock.add_response(vec![0x22, 0xF1, 0x91], vec![0x7F, 0x22, 0x78]);
+        let started = std::time::Instant::now();
+        let err = backend.read_raw_did(0xF191).await.unwrap_err();
+        assert!(matches!(err, BackendError::Timeout), "{err:?}");
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    }
+
+    // -------------------------------------------------------------------------
+    // First-frame retry
+    // -------------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn read_is_resent_after_first_frame_timeout() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_silence(vec![0x22, 0xF1, 0x90], 1);
+        let mut config = test_config();
+        config.first_frame_retry.retries = 2;
+        config.first_frame_retry.first_frame_timeout_ms = Some(50);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let value = backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(value, b"1HGCM82633A123456".to_vec());
+        assert_eq!(
+            mock.sent_requests(),
+            vec![vec![0x22, 0xF1, 0x90], vec![0x22, 0xF1, 0x90]]
+        );
+        assert_eq!(
+            mock.sent_timeouts(),
+            vec![std::time::Duration::from_millis(50); 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn slow_multi_frame_read_is_not_cut_off() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        // First frame at once, last one well past the first-frame timeout
+        mock.set_transfer_time(
+            vec![0x22, 0xF1, 0x90],
+            std::time::Duration::from_millis(100),
+        );
+        let mut config = test_config();
+        config.first_frame_retry.retries = 2;
+        config.first_frame_retry.first_frame_timeout_ms = Some(50);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        let value = backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(value, b"1HGCM82633A123456".to_vec());
+        assert_eq!(mock.sent_requests(), vec![vec![0x22, 0xF1, 0x90]]);
+    }
+
+    #[tokio::test]
+    async fn first_frame_retry_gives_up_after_configured_retries() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_silence(vec![0x22, 0xF1, 0x90], 3);
+        let mut config = test_config();
+        config.first_frame_retry.retries = 1;
+        config.first_frame_retry.first_frame_timeout_ms = Some(20);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        backend.read_raw_did(0xF190).await.unwrap_err();
+        assert_eq!(mock.sent_requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn writes_are_not_resent_by_default() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_silence(vec![0x2E, 0xF1, 0x90], 1);
+        let mut config = test_config();
+        config.first_frame_retry.retries = 2;
+        config.first_frame_retry.first_frame_timeout_ms = Some(20);
+        let backend = UdsBackend::with_transport(config.clone(), mock.clone()).unwrap();
+
+        backend.write_raw_did(0xF190, &[0x01]).await.unwrap_err();
+        assert_eq!(mock.sent_requests(), vec![vec![0x2E, 0xF1, 0x90, 0x01]]);
+
+        // Opted in, the write is re-sent too
+        mock.add_silence(vec![0x2E, 0xF1, 0x90], 1);
+        config.first_frame_retry.writes = true;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+        backend.write_raw_did(0xF190, &[0x01]).await.unwrap();
+        assert_eq!(mock.sent_requests().len(), 3);
+    }
+
+    // -------------------------------------------------------------------------
+    // Batch reads
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn multi_did_response_splits_on_unique_echoes() {
+        let dids = [0xF190, 0xF405];
+        assert_eq!(
+            split_read_data_response(&dids, &[0x62, 0xF1, 0x90, 0x41, 0x42, 0xF4, 0x05, 0x5A]),
+            Some(vec![vec![0x41, 0x42], vec![0x5A]])
+        );
+        // F405 also appears inside F190's data
+        assert_eq!(
+            split_read_data_response(&dids, &[0x62, 0xF1, 0x90, 0xF4, 0x05, 0xF4, 0x05, 0x5A]),
+            None
+        );
+        // Records out of order
+        assert_eq!(
+            split_read_data_response(&dids, &[0x62, 0xF4, 0x05, 0x5A, 0xF1, 0x90, 0x41]),
+            None
+        );
+    }
+
+    fn batch_backend(
+        max_dids_per_request: Option<usize>,
+    ) -> (
+        UdsBackend,
+        Arc<crate::transport::mock::MockTransportAdapter>,
+    ) {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let mut config = test_config();
+        config.data_read.max_dids_per_request = max_dids_per_request;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+        (backend, mock)
+    }
+
+    #[tokio::test]
+    async fn batch_read_sends_one_multi_did_request() {
+        let (backend, mock) = batch_backend(Some(8));
+        mock.add_response(
+            vec![0x22, 0xF4, 0x0C, 0xF4, 0x05],
+            vec![0x62, 0xF4, 0x0C, 0x0B, 0xB8, 0xF4, 0x05, 0x5A],
+        );
+
+        let ids = ["F40C", "0xF405", "nope"].map(String::from);
+        let results = backend.read_parameters_batch(&ids).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, "F40C");
+        assert_eq!(results[0].1.as_ref().unwrap().raw.as_deref(), Some("0bb8"));
+        assert_eq!(results[1].1.as_ref().unwrap().did.as_deref(), Some("F405"));
+        assert_eq!(results[1].1.as_ref().unwrap().raw.as_deref(), Some("5a"));
+        assert!(matches!(results[2].1, Err(BackendError::InvalidRequest(_))));
+        assert_eq!(
+            mock.sent_requests(),
+            vec![vec![0x22, 0xF4, 0x0C, 0xF4, 0x05]]
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_read_falls_back_to_single_dids() {
+        // ECU rejects the multi-DID request (incorrectMessageLength)
+        let (backend, mock) = batch_backend(Some(8));
+        mock.add_response(vec![0x22, 0xF4, 0x0C, 0xF4, 0x05], vec![0x7F, 0x22, 0x13]);
+
+        let ids = ["F40C", "F405"].map(String::from);
+        let results = backend.read_parameters_batch(&ids).await;
+
+        assert!(
+            results.iter().all(|(_, value)| value.is_ok()),
+            "{results:?}"
+        );
+        assert_eq!(
+            mock.sent_requests(),
+            vec![
+                vec![0x22, 0xF4, 0x0C, 0xF4, 0x05],
+                vec![0x22, 0xF4, 0x0C],
+                vec![0x22, 0xF4, 0x05],
+            ]
+        );
+
+        // Not configured: one DID per request
+        let (backend, mock) = batch_backend(None);
+        backend.read_parameters_batch(&ids).await;
+        assert_eq!(
+            mock.sent_requests(),
+            vec![vec![0x22, 0xF4, 0x0C], vec![0x22, 0xF4, 0x05]]
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    // Identification
+    // -------------------------------------------------------------------------
+
+    fn identification_backend() -> (
+        UdsBackend,
+        Arc<crate::transport::mock::MockTransportAdapter>,
+    ) {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let mut vin = vec![0x62, 0xF1, 0x90];
+        vin.extend_from_slice(b"WVWZZZ1JZXW000001");
+        mock.add_response(vec![0x22, 0xF1, 0x90], vin);
+        mock.add_response(
+            vec![0x22, 0xF1, 0x87],
+            vec![0x62, 0xF1, 0x87, b'P', b'N', b'-', b'7', 0x00, 0x00],
+        );
+        // requestOutOfRange for every other DID
+        mock.add_response(vec![0x22], vec![0x7F, 0x22, 0x31]);
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+        (backend, mock)
+    }
+
+    #[tokio::test]
+    async fn identification_skips_dids_the_ecu_rejects() {
+        let (backend, _mock) = identification_backend();
+        let values = backend.read_identification(None).await.unwrap();
+
+        let fields: Vec<(&str, &serde_json::Value)> =
+            values.iter().map(|v| (v.id.as_str(), &v.value)).collect();
+        assert_eq!(
+            fields,
+            [
+                ("vin", &serde_json::json!("WVWZZZ1JZXW000001")),
+                ("part_number", &serde_json::json!("PN-7")),
+            ]
+        );
+        assert_eq!(values[0].did.as_deref(), Some("F190"));
+    }
+
+    #[tokio::test]
+    async fn identification_reads_only_requested_fields() {
+        let (backend, mock) = identification_backend();
+        let values = backend
+            .read_identification(Some(&["part_number".to_string()]))
+            .await
+            .unwrap();
+
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].id, "part_number");
+        assert_eq!(mock.sent_requests(), vec![vec![0x22, 0xF1, 0x87]]);
+    }
+
+    #[tokio::test]
+    async fn memory_read_sizes_alfid_to_the_request() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(
+            vec![0x23, 0x12, 0x20, 0x48, 0x04],
+            vec![0x63, 0xDE, 0xAD, 0xBE, 0xEF],
+        );
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        let data = backend.read_memory(0x2048, 4).await.unwrap();
+
+        assert_eq!(data, [0xDE, 0xAD, 0xBE, 0xEF]);
+        // ALFID 0x12: 1-byte size, 2-byte address
+        assert_eq!(
+            mock.sent_requests(),
+            vec![vec![0x23, 0x12, 0x20, 0x48, 0x04]]
+        );
+    }
+
+    #[tokio::test]
+    async fn memory_read_uses_configured_widths() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x23], vec![0x63, 0x01, 0x02]);
+        let mut config = test_config();
+        config.memory.address_bytes = Some(4);
+        config.memory.size_bytes = Some(2);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+
+        backend.read_memory(0x2048, 2).await.unwrap();
+        assert_eq!(
+            mock.sent_requests(),
+            vec![vec![0x23, 0x24, 0x00, 0x00, 0x20, 0x48, 0x00, 0x02]]
+        );
+
+        // A configured width too narrow for the address is rejected unsent.
+        let err = backend.read_memory(0x1_0000_0000, 2).await.unwrap_err();
+        assert!(matches!(err, BackendError::InvalidRequest(_)), "{err:?}");
+        assert_eq!(mock.sent_requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn ddid_by_memory_shares_one_alfid() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x2C], vec![0x6C, 0x02, 0xF2, 0x00]);
+        mock.add_response(
+            vec![0x22, 0xF2, 0x00],
+            vec![0x62, 0xF2, 0x00, 0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x02],
+        );
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        backend
+            .define_data_identifier_by_memory(0xF200, &[(0x2048, 4), (0x01_0000, 2)])
+            .await
+            .unwrap();
+        let data = backend.read_raw_did(0xF200).await.unwrap();
+
+        // ALFID 0x13: 1-byte sizes, 3-byte addresses (the widest needed)
+        assert_eq!(
+            mock.sent_requests()[0],
+            [0x2C, 0x02, 0xF2, 0x00, 0x13, 0x00, 0x20, 0x48, 0x04, 0x01, 0x00, 0x00, 0x02]
+        );
+        assert_eq!(data, [0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x02]);
+    }
+
+    #[tokio::test]
+    async fn ddid_by_memory_rejects_empty_region_unsent() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        let err = backend
+            .define_data_identifier_by_memory(0xF200, &[(0x2048, 0)])
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, BackendError::InvalidRequest(_)), "{err:?}");
+        assert!(mock.sent_requests().is_empty());
+    }
+
+    /// Backend in the programming session whose ECU answers 0x3D with
+    /// `reply`; memory writes gated at security level `level`
+    async fn memory_write_backend(
+        level: u8,
+        reply: Vec<u8>,
+    ) -> (
+        UdsBackend,
+        Arc<crate::transport::mock::MockTransportAdapter>,
+    ) {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x3D], reply);
+        let mut config = test_config_with_unlock();
+        config.memory.write_security_level = level;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+        backend.set_session_mode("programming").await.unwrap();
+        (backend, mock)
+    }
+
+    #[tokio::test]
+    async fn memory_write_requires_configured_session() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        let err = backend.write_memory(0x2048, &[0xAA]).await.unwrap_err();
+
+        assert!(matches!(err, BackendError::SessionRequired(_)), "{err:?}");
+        assert!(mock.sent_requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn memory_write_requires_security_level() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+        backend.set_session_mode("programming").await.unwrap();
+
+        // No unlock provider: refused before any 0x3D goes out.
+        let err = backend.write_memory(0x2048, &[0xAA]).await.unwrap_err();
+
+        assert!(matches!(err, BackendError::SecurityRequired(1)), "{err:?}");
+        assert!(!mock.sent_requests().iter().any(|r| r[0] == 0x3D));
+    }
+
+    #[tokio::test]
+    async fn memory_write_unlocks_and_verifies_echo() {
+        let (backend, mock) = memory_write_backend(1, vec![0x7D, 0x12, 0x20, 0x48, 0x02]).await;
+        mock.add_response(vec![0x27, 0x01], vec![0x67, 0x01, 0xAA]);
+        mock.add_response(vec![0x27, 0x02], vec![0x67, 0x02]);
+
+        backend.write_memory(0x2048, &[0xCA, 0xFE]).await.unwrap();
+
+        assert!(backend.session_manager.security_state().unlocked);
+        assert_eq!(
+            mock.sent_requests().last().unwrap(),
+            &vec![0x3D, 0x12, 0x20, 0x48, 0x02, 0xCA, 0xFE]
+        );
+    }
+
+    #[tokio::test]
+    async fn memory_write_rejects_mismatched_echo() {
+        // ECU echoes address 0x2049 for a write to 0x2048.
+        let (backend, _mock) = memory_write_backend(0, vec![0x7D, 0x12, 0x20, 0x49, 0x02]).await;
+
+        let err = backend
+            .write_memory(0x2048, &[0xCA, 0xFE])
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, BackendError::Protocol(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn memory_write_surfaces_request_out_of_range() {
+        let (backend, mock) = memory_write_backend(0, vec![0x7F, 0x3D, 0x31]).await;
+
+        let err = UdsService::new(mock.clone())
+            .write_memory_by_address(0xFFFF_0000, &[0x00])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, UdsError::RequestOutOfRange { service_id: 0x3D }),
+            "{err:?}"
+        );
+
+        let err = backend
+            .write_memory(0xFFFF_0000, &[0x00])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                BackendError::EcuError {
+                    nrc: 0x31,
+                    sid: 0x3D,
+                    ..
+                }
+            ),
+            "{err:?}"
+        );
+    }
+
+    /// Backend whose ECU accepts RequestUpload and answers TransferData
+    /// with `blocks` in order, starting at block counter 1
+    fn upload_backend(
+        blocks: Vec<Vec<u8>>,
+    ) -> (
+        UdsBackend,
+        Arc<crate::transport::mock::MockTransportAdapter>,
+    ) {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x35], vec![0x75, 0x20, 0x01, 0x02]);
+        for (counter, block) in (1u8..).zip(blocks) {
+            let mut resp = vec![0x76, counter];
+            resp.extend(block);
+            mock.add_response(vec![0x36, counter], resp);
+        }
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+        (backend, mock)
+    }
+
+    #[tokio::test]
+    async fn memory_upload_collects_blocks_until_size() {
+        let (backend, mock) = upload_backend(vec![vec![1, 2, 3, 4], vec![5, 6]]);
+
+        let data = backend.upload_memory(0x2048, 6, 0x00).await.unwrap();
+
+        assert_eq!(data, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            mock.sent_requests(),
+            [
+                vec![0x35, 0x00, 0x12, 0x20, 0x48, 0x06],
+                vec![0x36, 0x01],
+                vec![0x36, 0x02],
+                vec![0x37],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn memory_upload_inflates_compressed_blocks() {
+        let block = CompressionMethod::Deflate
+            .compress(&[0xAB; 64])
+            .into_owned();
+        let (backend, mock) = upload_backend(vec![block]);
+
+        let data = backend.upload_memory(0x2048, 64, 0x10).await.unwrap();
+
+        assert_eq!(data, [0xAB; 64]);
+        assert_eq!(mock.sent_requests()[0][1], 0x10);
+
+        // Encrypted blocks cannot be read back: refused unsent
+        let err = backend.upload_memory(0x2048, 64, 0x01).await.unwrap_err();
+        assert!(matches!(err, BackendError::InvalidRequest(_)), "{err:?}");
+        assert_eq!(mock.sent_requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn memory_upload_exits_transfer_on_bad_block() {
+        // Four bytes for a two-byte region
+        let (backend, mock) = upload_backend(vec![vec![1, 2, 3, 4]]);
+
+        let err = backend.upload_memory(0x2048, 2, 0x00).await.unwrap_err();
+        assert!(matches!(err, BackendError::Protocol(_)), "{err:?}");
+        assert_eq!(mock.sent_requests().last().unwrap(), &vec![0x37]);
+    }
+
+    #[tokio::test]
+    async fn flash_cannot_start_during_memory_upload() {
+        let (backend, mock) = upload_backend(vec![vec![1, 2, 3, 4]]);
+        mock.set_transfer_time(vec![0x36], std::time::Duration::from_millis(100));
+
+        let (upload, flash) = tokio::join!(backend.upload_memory(0x2048, 4, 0x00), async {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            backend.start_flash().await
+        });
+
+        assert_eq!(upload.unwrap(), [1, 2, 3, 4]);
+        assert!(matches!(flash, Err(BackendError::Busy(_))), "{flash:?}");
+    }
+
+    #[tokio::test]
+    async fn periodic_read_starts_captures_and_stops() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        // Play the ECU: once the periodic start is on the wire, send one
+        // sample for periodic identifier 0x10 (plus one for another PID).
+        let ecu = mock.clone();
+        tokio::spawn(async move {
+            while !ecu.sent_requests().contains(&vec![0x2A, 0x01, 0x10]) {
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            }
+            ecu.inject_incoming(vec![0x11, 0xFF]);
+            ecu.inject_incoming(vec![0x10, 0x0B, 0xB8]);
+        });
+
+        let data = backend.read_raw_did_periodic(0xF210).await.unwrap();
+
+        assert_eq!(data, [0x0B, 0xB8]);
+        assert_eq!(
+            mock.sent_requests(),
+            vec![vec![0x2A, 0x01, 0x10], vec![0x2A, 0x04, 0x10]]
+        );
+    }
+
+    #[tokio::test]
+    async fn periodic_read_leaves_a_subscribed_did_streaming() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+        let _stream = backend
+            .subscribe_data(&["F210".to_string()], 10)
+            .await
+            .unwrap();
+        let subscribed = mock.sent_requests();
+
+        let ecu = mock.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            ecu.inject_incoming(vec![0x10, 0x0B, 0xB8]);
+        });
+        let data = backend.read_raw_did_periodic(0xF210).await.unwrap();
+
+        // The subscription's transmission serves the read: neither started
+        // again at the slow rate nor stopped afterwards
+        assert_eq!(data, [0x0B, 0xB8]);
+        assert_eq!(mock.sent_requests(), subscribed);
+    }
+
+    #[tokio::test]
+    async fn periodic_read_stops_when_no_sample_arrives() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        let err = backend.read_raw_did_periodic(0xF210).await.unwrap_err();
+
+        assert!(matches!(err, BackendError::Timeout), "{err:?}");
+        assert_eq!(mock.sent_requests().last(), Some(&vec![0x2A, 0x04, 0x10]));
+    }
+
+    #[test]
+    fn write_data_limit_takes_tighter_bound() {
+        assert_eq!(write_data_limit(None, None), None);
+        assert_eq!(write_data_limit(Some(4095), None), Some(4092));
+        assert_eq!(write_data_limit(None, Some(512)), Some(512));
+        assert_eq!(write_data_limit(Some(4095), Some(512)), Some(512));
+        assert_eq!(write_data_limit(Some(256), Some(512)), Some(253));
+    }
+
+    /// Backend whose transport carries 4095 bytes (classic ISO-TP) and whose
+    /// config caps DID writes at 1024 bytes
+    fn write_limited_backend() -> (
+        UdsBackend,
+        Arc<crate::transport::mock::MockTransportAdapter>,
+    ) {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.set_max_message_len(Some(4095));
+        let mut config = test_config();
+        config.sessions.write_data_max_length = Some(1024);
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+        (backend, mock)
+    }
+
+    #[tokio::test]
+    async fn write_at_configured_max_is_sent_whole() {
+        let (backend, mock) = write_limited_backend();
+        let value = vec![0x5A; 1024];
+
+        backend.write_raw_did(0xF1A0, &value).await.unwrap();
+
+        // One 1027-byte request; ISO-TP segments it below the UDS layer.
+        let mut request = vec![0x2E, 0xF1, 0xA0];
+        request.extend_from_slice(&value);
+        assert_eq!(mock.sent_requests(), vec![request]);
+    }
+
+    #[tokio::test]
+    async fn write_over_configured_max_is_rejected_unsent() {
+        let (backend, mock) = write_limited_backend();
+
+        let err = backend
+            .write_raw_did(0xF1A0, &[0x5A; 1025])
+            .await
+            .unwrap_err();
+
+        match err {
+            BackendError::InvalidRequest(msg) => {
+                assert!(msg.contains("1025") && msg.contains("1024"), "{msg}")
+            }
+            other => panic!("expected InvalidRequest, got {other:?}"),
+        }
+        assert!(mock.sent_requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn write_over_transport_limit_is_rejected_unsent() {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.set_max_message_len(Some(4095));
+        let backend = UdsBackend::with_transport(test_config(), mock.clone()).unwrap();
+
+        backend.write_raw_did(0xF1A0, &[0x00; 4092]).await.unwrap();
+        let err = backend
+            .write_raw_did(0xF1A0, &[0x00; 4093])
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, BackendError::InvalidRequest(_)), "{err:?}");
+        assert_eq!(mock.sent_requests().len(), 1);
+    }
+
+    // -------------------------------------------------------------------------
+    // Subscription recovery after ECU reset
+    // -------------------------------------------------------------------------
+
+    /// Backend over the mock ECU with a transparent unlock at level 2 and
+    /// `subscription_recovery` as given
+    fn recovery_backend(
+        enabled: bool,
+    ) -> (
+        UdsBackend,
+        Arc<crate::transport::mock::MockTransportAdapter>,
+    ) {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x27, 0x03], vec![0x67, 0x03, 0x10, 0x20]);
+        mock.add_response(vec![0x27, 0x04], vec![0x67, 0x04]);
+        let mut config = test_config();
+        config.subscription_recovery.enabled = enabled;
+        config.subscription_recovery.probe_interval_ms = 10;
+        config.subscription_recovery.timeout_ms = 2000;
+        let backend = UdsBackend::with_transport(config, mock.clone())
+            .unwrap()
+            .with_unlock_provider(Arc::new(IncrementUnlock), 2);
+        (backend, mock)
+    }
+
+    fn count_sent(mock: &crate::transport::mock::MockTransportAdapter, request: &[u8]) -> usize {
+        mock.sent_requests()
+            .iter()
+            .filter(|r| r.as_slice() == request)
+            .count()
+    }
+
+    async fn next_point(rx: &mut broadcast::Receiver<DataPoint>) -> DataPoint {
+        tokio::time::timeout(std::time::Duration::from_secs(2), rx.recv())
+            .await
+            .expect("data point in time")
+            .expect("stream open")
+    }
+
+    #[tokio::test]
+    async fn subscription_resumes_after_ecu_reset() {
+        let (backend, mock) = recovery_backend(true);
+        backend.session_manager.change_session(0x03).await.unwrap();
+        backend.ensure_unlocked_for(2).await.unwrap();
+        let mut rx = backend
+            .subscribe_data(&["F40C".to_string()], 5)
+            .await
+            .unwrap();
+        mock.inject_incoming(vec![0x0C, 0x0B, 0xB8]);
+        assert_eq!(next_point(&mut rx).await.value, "0bb8");
+        let starts = mock.sent_requests().iter().filter(|r| r[0] == 0x2A).count();
+
+        // The ECU reboots and stays silent for a while
+        backend.ecu_reset(0x01).await.unwrap();
+        mock.set_connected(false);
+        let ecu = mock.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            ecu.set_connected(true);
+        });
+
+        assert!(next_point(&mut rx).await.is_resumed());
+        // Session, security and the periodic schedule were restored
+        assert_eq!(count_sent(&mock, &[0x10, 0x03]), 2);
+        assert_eq!(count_sent(&mock, &[0x27, 0x04, 0x11, 0x21]), 2);
+        let rearmed = mock.sent_requests().iter().filter(|r| r[0] == 0x2A).count();
+        assert_eq!(rearmed, starts * 2);
+
+        mock.inject_incoming(vec![0x0C, 0x0C, 0x80]);
+        assert_eq!(next_point(&mut rx).await.value, "0c80");
+    }
+
+    #[tokio::test]
+    async fn subscription_recovery_is_off_by_default() {
+        let (backend, mock) = recovery_backend(false);
+        let _rx = backend
+            .subscribe_data(&["F40C".to_string()], 5)
+            .await
+            .unwrap();
+        let sent = mock.sent_requests().len();
+
+        backend.ecu_reset(0x01).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        // Only the reset itself went out: no probes, no re-arm
+        assert_eq!(mock.sent_requests().len(), sent + 1);
+    }
+
+    // -------------------------------------------------------------------------
+    // ResponseOnEvent (0x86) subscriptions
+    // -------------------------------------------------------------------------
+
+    /// Backend over the mock ECU with `[subscriptions] mode = "event"`
+    fn event_backend(
+        event: crate::config::RoeEvent,
+    ) -> (
+        UdsBackend,
+        Arc<crate::transport::mock::MockTransportAdapter>,
+    ) {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        let mut config = test_config();
+        config.subscriptions.mode = crate::config::SubscriptionMode::Event;
+        config.subscriptions.event = event;
+        config.subscriptions.dtc_status_mask = 0x08;
+        let backend = UdsBackend::with_transport(config, mock.clone()).unwrap();
+        (backend, mock)
+    }
+
+    #[tokio::test]
+    async fn event_subscription_streams_unsolicited_responses() {
+        let (backend, mock) = event_backend(crate::config::RoeEvent::ChangeOfDid);
+        let mut rx = backend
+            .subscribe_data(&["F40C".to_string()], 5)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            count_sent(&mock, &[0x86, 0x03, 0x02, 0xF4, 0x0C, 0x22, 0xF4, 0x0C]),
+            1
+        );
+        assert_eq!(count_sent(&mock, &[0x86, 0x05, 0x02]), 1);
+        assert!(!mock.sent_requests().iter().any(|r| r[0] == 0x2A));
+
+        // Responses for other DIDs are not this subscription's
+        mock.inject_incoming(vec![0x62, 0xF4, 0x05, 0x84]);
+        mock.inject_incoming(vec![0x62, 0xF4, 0x0C, 0x0B, 0xB8]);
+        let point = next_point(&mut rx).await;
+        assert_eq!(point.id, "F40C");
+        assert_eq!(point.value, "0bb8");
+    }
+
+    #[tokio::test]
+    async fn event_subscription_on_dtc_status_change() {
+        let (backend, mock) = event_backend(crate::config::RoeEvent::DtcStatusChange);
+        let _rx = backend
+            .subscribe_data(&["F40C".to_string()], 5)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            count_sent(&mock, &[0x86, 0x01, 0x02, 0x08, 0x22, 0xF4, 0x0C]),
+            1
+        );
+        assert_eq!(count_sent(&mock, &[0x86, 0x05, 0x02]), 1);
+    }
+
+    #[tokio::test]
+    async fn event_subscriptions_replace_the_ecu_event_list() {
+        let (backend, mock) = event_backend(crate::config::RoeEvent::ChangeOfDid);
+        let _rx = backend
+            .subscribe_data(&["F40C".to_string()], 5)
+            .await
+            .unwrap();
+        assert_eq!(count_sent(&mock, &[0x86, 0x00]), 0);
+
+        let mut rx = backend
+            .subscribe_data(&["F405".to_string()], 1)
+            .await
+            .unwrap();
+
+        // The first list is stopped and cleared, then both DIDs are set up
+        assert_eq!(count_sent(&mock, &[0x86, 0x00]), 1);
+        assert_eq!(count_sent(&mock, &[0x86, 0x06, 0x02]), 1);
+        assert_eq!(
+            count_sent(&mock, &[0x86, 0x03, 0x02, 0xF4, 0x0C, 0x22, 0xF4, 0x0C]),
+            2
+        );
+        assert_eq!(
+            count_sent(&mock, &[0x86, 0x03, 0x02, 0xF4, 0x05, 0x22, 0xF4, 0x05]),
+            1
+        );
+        assert_eq!(count_sent(&mock, &[0x86, 0x05, 0x02]), 2);
+
+        mock.inject_incoming(vec![0x62, 0xF4, 0x05, 0x84]);
+        assert_eq!(next_point(&mut rx).await.value, "84");
+    }
+
+    // -------------------------------------------------------------------------
+    // Tester-polled subscriptions
+    // -------------------------------------------------------------------------
+
+    /// Backend over the mock ECU with `[subscriptions] mode = "poll"`
+    fn poll_backend(max_jitter_ms: u64) -> UdsBackend {
+        let mock = Arc::new(crate::transport::mock::MockTransportAdapter::new(
+            &MockConfig { latency_ms: 0 },
+        ));
+        mock.add_response(vec![0x22, 0xF4, 0x05], vec![0x62, 0xF4, 0x05, 0x84]);
+        mock.add_response(vec![0x22, 0xF4, 0x0C], vec![0x62, 0xF4, 0x0C, 0x0B, 0xB8]);
+        let mut config = test_config();
+        config.subscriptions.mode = crate::config::SubscriptionMode::Poll;
+        config.subscriptions.max_jitter_ms = max_jitter_ms;
+        UdsBackend::with_transport(config, mock).unwrap()
+    }
+
+    /// When each point `rx` received within `window` was published
+    async fn poll_times(
+        rx: &mut broadcast::Receiver<DataPoint>,
+        window: std::time::Duration,
+    ) -> Vec<chrono::DateTime<Utc>> {
+        let deadline = tokio::time::Instant::now() + window;
+        let mut times = Vec::new();
+        while let Ok(Ok(point)) = tokio::time::timeout_at(deadline, rx.recv()).await {
+            times.push(point.timestamp);
+        }
+        times
+    }
+
+    #[tokio::test]
+    async fn jittered_polls_spread_out_but_keep_their_rate() {
+        let backend = poll_backend(50);
+        let mut coolant = backend
+            .subscribe_data(&["F405".to_string()], 10)
+            .await
+            .unwrap();
+        let mut rpm = backend
+            .subscribe_data(&["F40C".to_string()], 10)
+            .await
+            .unwrap();
+        assert_eq!(next_point(&mut rpm).await.value, "0bb8");
+
+        let window = std::time::Duration::from_secs(1);
+        let (coolant, rpm) = tokio::join!(
+            poll_times(&mut coolant, window),
+            poll_times(&mut rpm, window)
+        );
+
+        // 10 Hz on average: each poll stays in its 100 ms slot, delayed by
+        // at most the 50 ms jitter
+        for times in [&coolant, &rpm] {
+            assert!((9..=11).contains(&times.len()), "{} polls", times.len());
+            for gap in times.windows(2).map(|w| (w[1] - w[0]).num_milliseconds()) {
+                assert!((30..=170).contains(&gap), "gap of {} ms", gap);
+            }
+        }
+        // Same rate, yet not polled in lockstep
+        let aligned = coolant
+            .iter()
+            .zip(&rpm)
+            .filter(|(a, b)| (**a - **b).num_milliseconds().abs() < 1)
+            .count();
+        assert!(aligned < coolant.len().min(rpm.len()), "{aligned} aligned");
+    }
+
+    #[tokio::test]
+    async fn suspended_polls_stop() {
+        let backend = poll_backend(0);
+        let mut rx = backend
+            .subscribe_data(&["F405".to_string()], 20)
+            .await
+            .unwrap();
+        assert_eq!(next_point(&mut rx).await.value, "84");
+
+        backend.stream_manager.suspend();
+        let late = poll_times(&mut rx, std::time::Duration::from_millis(200)).await;
+        // At most the poll already in flight when the task was stopped
+        assert!(late.len() <= 2, "{} polls after stop", late.len());
+    }
 }
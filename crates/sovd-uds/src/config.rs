@@ -5,6 +5,9 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::uds::CompressionMethod;
 
 /// Configuration for a UDS backend
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,13 +44,542 @@ pub struct UdsBackendConfig {
     /// the ECU's NRC (today's behaviour).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unlock: Option<UnlockConfig>,
+    /// DTC memory areas beyond the primary memory
+    #[serde(default)]
+    pub fault_memory: FaultMemoryConfig,
+    /// Read/WriteMemoryByAddress (0x23/0x3D) encoding and write gate
+    #[serde(default)]
+    pub memory: MemoryAccessConfig,
+    /// UDS services this backend may send
+    #[serde(default)]
+    pub service_policy: ServicePolicy,
+    /// Waiting out responsePending (NRC 0x78)
+    #[serde(default)]
+    pub response_pending: ResponsePendingConfig,
+    /// Re-sending requests the ECU did not answer at all
+    #[serde(default)]
+    pub first_frame_retry: FirstFrameRetryConfig,
+    /// Layout of the software fingerprint DIDs (0xF183–0xF185)
+    #[serde(default)]
+    pub fingerprints: FingerprintConfig,
+    /// Batching of ReadDataByIdentifier (0x22) requests
+    #[serde(default)]
+    pub data_read: DataReadConfig,
+    /// Re-arming subscriptions after an ECU reset
+    #[serde(default)]
+    pub subscription_recovery: SubscriptionRecoveryConfig,
+    /// How subscribed DIDs are sampled: periodic (0x2A) or ECU events (0x86)
+    #[serde(default)]
+    pub subscriptions: SubscriptionConfig,
+    /// P2/P2* response timeouts until the ECU advertises its own
+    #[serde(default)]
+    pub timing: TimingConfig,
+    /// Serialization of the requests sent to this ECU
+    #[serde(default)]
+    pub request_queue: RequestQueueConfig,
+}
+
+impl UdsBackendConfig {
+    /// ECU `id` reached over `transport`, with every other setting at its
+    /// default. There is no `Default`, as the transport must be chosen.
+    pub fn new(id: impl Into<String>, name: impl Into<String>, transport: TransportConfig) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            description: None,
+            transport,
+            operations: Vec::new(),
+            outputs: Vec::new(),
+            service_overrides: ServiceOverrides::default(),
+            sessions: SessionConfig::default(),
+            flash_commit: FlashCommitConfig::default(),
+            unlock: None,
+            fault_memory: FaultMemoryConfig::default(),
+            memory: MemoryAccessConfig::default(),
+            service_policy: ServicePolicy::default(),
+            response_pending: ResponsePendingConfig::default(),
+            first_frame_retry: FirstFrameRetryConfig::default(),
+            fingerprints: FingerprintConfig::default(),
+            data_read: DataReadConfig::default(),
+            subscription_recovery: SubscriptionRecoveryConfig::default(),
+            subscriptions: SubscriptionConfig::default(),
+            timing: TimingConfig::default(),
+            request_queue: RequestQueueConfig::default(),
+        }
+    }
+}
+
+/// Per-ECU allow/deny list of UDS service IDs, enforced before anything
+/// is sent.
+///
+/// A denied service fails locally with `ServiceDenied` (HTTP 403). `deny`
+/// always wins; when `allow` is set, every service not listed is denied
+/// too. IDs are the bytes on the wire, i.e. after `service_overrides`:
+///
+/// ```toml
+/// [ecu.vtx_ecm.service_policy]
+/// deny = [0x11, 0x34, 0x36]        # no ECUReset, no flashing
+/// # allow = [0x10, 0x22, 0x27]     # or: only these
+/// ```
+///
+/// The session keepalive (TesterPresent) is sent outside this check.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServicePolicy {
+    /// Only these service IDs may be sent (unset ⇒ all)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Vec<u8>>,
+    /// These service IDs may never be sent
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<u8>,
+}
+
+impl ServicePolicy {
+    /// Whether `service_id` may be sent
+    pub fn permits(&self, service_id: u8) -> bool {
+        !self.deny.contains(&service_id)
+            && self
+                .allow
+                .as_ref()
+                .is_none_or(|allow| allow.contains(&service_id))
+    }
+}
+
+/// Handling of responsePending (NRC 0x78)
+///
+/// An ECU busy with a long job (erase memory, a self-test routine) answers
+/// `7F <sid> 78` and sends the real response later, repeating the 0x78
+/// while it is still working. The request is not re-sent; each 0x78
+/// re-arms the wait for the next frame, which is P2* (see
+/// [`TimingConfig`]):
+///
+/// ```toml
+/// [ecu.vtx_ecm.response_pending]
+/// max_pending = 120       # give up after this many 0x78 in a row
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponsePendingConfig {
+    /// Consecutive 0x78 answers tolerated before the request times out
+    #[serde(default = "default_max_pending")]
+    pub max_pending: u32,
+}
+
+fn default_max_pending() -> u32 {
+    50
+}
+
+impl Default for ResponsePendingConfig {
+    fn default() -> Self {
+        Self {
+            max_pending: default_max_pending(),
+        }
+    }
+}
+
+/// Response timeouts (ISO 14229-2 P2 / P2*)
+///
+/// P2 bounds the wait for the first answer to a request, P2* the wait for
+/// each frame after a responsePending (0x78). An ECU that returns a
+/// sessionParameterRecord in its DiagnosticSessionControl response sets
+/// both for the session; these apply until it does, or for an ECU that
+/// never does:
+///
+/// ```toml
+/// [ecu.vtx_ecm.timing]
+/// p2_ms = 50          # ISO 14229-2 default
+/// p2_star_ms = 5000   # ISO 14229-2 default
+/// ```
+///
+/// Like negotiated timing, each gets a 500 ms network allowance on top.
+/// Unset, both waits are 5000 ms.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimingConfig {
+    /// P2_server_max, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p2_ms: Option<u64>,
+    /// P2*_server_max, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p2_star_ms: Option<u64>,
+}
+
+/// Queue of the requests waiting for the ECU
+///
+/// ISO-TP is point-to-point and half-duplex: two requests in flight to one
+/// ECU at once (an API read and a subscription poll, say) can get each
+/// other's responses. Every exchange with the ECU therefore waits for the
+/// one before it, first come first served; requests to other ECUs are not
+/// held up. Rather than letting the queue grow without bound under load, a
+/// request finding `max_depth` others already waiting is refused (HTTP
+/// 503) without being sent:
+///
+/// ```toml
+/// [ecu.vtx_ecm.request_queue]
+/// max_depth = 16      # requests waiting behind the one on the wire
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestQueueConfig {
+    /// Requests allowed to wait behind the one in flight; zero refuses any
+    /// request while another is in flight
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+}
+
+fn default_max_depth() -> usize {
+    16
+}
+
+impl Default for RequestQueueConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: default_max_depth(),
+        }
+    }
+}
+
+/// Re-sending a request that got no answer at all
+///
+/// Under bus contention the request, or the first frame of the response,
+/// can get lost; the read then times out although a second attempt would
+/// succeed. With retries configured, a request answered by nothing within
+/// the first-frame timeout is sent again as a whole. This is independent of
+/// NRC handling: an ECU that answered (even with 0x78) is never re-sent to.
+///
+/// ```toml
+/// [ecu.vtx_ecm.first_frame_retry]
+/// retries = 2                  # re-sends after the first attempt
+/// first_frame_timeout_ms = 150 # wait per attempt; unset ⇒ response timeout
+/// # writes = true              # also services that change ECU state
+/// ```
+///
+/// Only read services (0x22, 0x19, 0x23) are retried unless `writes` is
+/// set, since a write whose response was lost may have been executed.
+///
+/// The first-frame timeout bounds only the start of the response; the rest
+/// of a multi-frame response still gets the response timeout. Transports
+/// that cannot see the first frame arrive (kernel ISO-TP, DoIP) ignore it
+/// and wait the full response timeout on every attempt.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FirstFrameRetryConfig {
+    /// Re-sends after the first attempt; zero disables the retry
+    #[serde(default)]
+    pub retries: u32,
+    /// Wait for the first frame of each attempt; unset ⇒ the response
+    /// timeout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_frame_timeout_ms: Option<u64>,
+    /// Also retry services other than reads
+    #[serde(default)]
+    pub writes: bool,
+}
+
+/// Layout of the software fingerprint DIDs (0xF183–0xF185)
+///
+/// Fingerprints are manufacturer-specific records; with a known `format`
+/// they are decoded into a programming date and tester serial:
+///
+/// ```toml
+/// [ecu.vtx_ecm.fingerprints]
+/// format = "bcd-date-ascii"   # BCD YYMMDD + ASCII tester serial
+/// # format = "bcd-date-hex"   # BCD YYMMDD + tester serial as hex
+/// ```
+///
+/// Unset or unknown ⇒ fingerprints are returned raw only.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FingerprintConfig {
+    /// Record layout name (see [`crate::uds::fingerprint`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+/// Batching of ReadDataByIdentifier (0x22) for batch parameter reads
+///
+/// ISO 14229-1 lets one 0x22 request carry several DIDs, but ECUs limit how
+/// many (or accept only one), so batching is opt-in:
+///
+/// ```toml
+/// [ecu.vtx_ecm.data_read]
+/// max_dids_per_request = 8
+/// ```
+///
+/// A batch the ECU rejects, or whose response cannot be split back into
+/// its DIDs unambiguously, is re-read one DID at a time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataReadConfig {
+    /// DIDs per 0x22 request; unset ⇒ one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_dids_per_request: Option<usize>,
+}
+
+/// Re-arming subscriptions after an ECU reset
+///
+/// A reset clears the ECU's periodic schedule (0x2A) and drops it back to
+/// the default session, so the streams of live subscriptions go quiet. With
+/// `enabled`, the backend polls TesterPresent until the ECU answers again,
+/// restores the session and security level that were active, re-arms the
+/// periodic DIDs and marks the gap in every stream with a resumed event:
+///
+/// ```toml
+/// [ecu.vtx_ecm.subscription_recovery]
+/// enabled = true
+/// probe_interval_ms = 200  # TesterPresent poll while the ECU reboots
+/// timeout_ms = 10000       # give up and leave the streams stopped
+/// ```
+///
+/// Security is only restored with a transparent `unlock` provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionRecoveryConfig {
+    /// Resume subscriptions after a reset; off ⇒ clients re-subscribe
+    #[serde(default)]
+    pub enabled: bool,
+    /// Delay between TesterPresent probes
+    #[serde(default = "default_recovery_probe_interval")]
+    pub probe_interval_ms: u64,
+    /// How long the ECU may take to answer again
+    #[serde(default = "default_recovery_timeout")]
+    pub timeout_ms: u64,
+}
+
+fn default_recovery_probe_interval() -> u64 {
+    200
+}
+
+fn default_recovery_timeout() -> u64 {
+    10_000
+}
+
+impl Default for SubscriptionRecoveryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            probe_interval_ms: default_recovery_probe_interval(),
+            timeout_ms: default_recovery_timeout(),
+        }
+    }
+}
+
+/// How subscribed DIDs get out of the ECU
+///
+/// By default every subscription is a 0x2A periodic schedule. ECUs that
+/// support ResponseOnEvent (0x86) can push a DID only when something
+/// happens instead, which suits change-driven signals and keeps the bus
+/// quiet in between:
+///
+/// ```toml
+/// [ecu.vtx_ecm.subscriptions]
+/// mode = "event"                 # "periodic" (0x2A, default) or "event" (0x86)
+/// event = "change_of_did"        # or "dtc_status_change"
+/// dtc_status_mask = 0x08         # dtc_status_change only
+/// ```
+///
+/// Each subscribed DID is registered as one event whose response is a
+/// ReadDataByIdentifier (0x22) of that DID; the rate of a subscription is
+/// ignored in event mode.
+///
+/// ECUs with neither service are polled by the tester instead, one 0x22 per
+/// DID at its subscription's rate. Same-rate polls would otherwise all go
+/// out at once each period; `max_jitter_ms` delays each poll by a random
+/// amount up to that bound (at most one period), keeping each DID's ticks
+/// on its rate's grid so it is still read at that rate on average:
+///
+/// ```toml
+/// [ecu.vtx_ecm.subscriptions]
+/// mode = "poll"
+/// max_jitter_ms = 20
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionConfig {
+    /// Periodic schedule, ECU events or tester polls
+    #[serde(default)]
+    pub mode: SubscriptionMode,
+    /// Event that triggers a response (event mode only)
+    #[serde(default)]
+    pub event: RoeEvent,
+    /// DTC status bits whose change triggers `dtc_status_change`
+    #[serde(default = "default_roe_dtc_status_mask")]
+    pub dtc_status_mask: u8,
+    /// Upper bound of the random delay of each poll, in milliseconds (poll
+    /// mode only); zero polls same-rate DIDs together
+    #[serde(default)]
+    pub max_jitter_ms: u64,
+}
+
+fn default_roe_dtc_status_mask() -> u8 {
+    0xFF
+}
+
+impl Default for SubscriptionConfig {
+    fn default() -> Self {
+        Self {
+            mode: SubscriptionMode::default(),
+            event: RoeEvent::default(),
+            dtc_status_mask: default_roe_dtc_status_mask(),
+            max_jitter_ms: 0,
+        }
+    }
+}
+
+/// Sampling mechanism behind a subscription
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionMode {
+    /// ReadDataByPeriodicIdentifier (0x2A) at the subscription's rate
+    #[default]
+    Periodic,
+    /// ResponseOnEvent (0x86): the ECU sends the DID when the event fires
+    Event,
+    /// ReadDataByIdentifier (0x22) sent by the tester at the subscription's
+    /// rate
+    Poll,
+}
+
+/// ResponseOnEvent (0x86) trigger
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoeEvent {
+    /// onChangeOfDataIdentifier: the subscribed DID's value changed
+    #[default]
+    ChangeOfDid,
+    /// onDTCStatusChange: a DTC status bit in `dtc_status_mask` changed
+    DtcStatusChange,
+}
+
+/// Per-ECU DTC memory selection for ReadDTCInformation (0x19).
+///
+/// The primary memory is read with sub-function 0x02, or with 0x08 when
+/// `reports` lists `severity-mask`. The mirror memory is read as a
+/// user-defined memory (sub-function 0x17) whose MemorySelection byte is
+/// manufacturer-specific, so it must be configured:
+///
+/// ```toml
+/// [ecu.vtx_ecm.fault_memory]
+/// reports = ["status-mask", "severity-mask", "user-def-memory"]
+/// mirror_memory_selection = 0x01
+/// verify_clear = true
+///
+/// [[ecu.vtx_ecm.fault_memory.extended_data]]
+/// record = 0x01
+/// name = "occurrence_counter"
+/// length = 1
+/// ```
+///
+/// Unset ⇒ `?memory=mirror` is rejected as not supported for this ECU.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FaultMemoryConfig {
+    /// 0x19 report sub-functions the ECU implements; empty ⇒ undeclared,
+    /// only the status-mask report is used
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reports: Vec<DtcReport>,
+    /// MemorySelection byte addressing the mirror memory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirror_memory_selection: Option<u8>,
+    /// Re-read the DTCs after a ClearDiagnosticInformation (0x14) and
+    /// report whether any of the cleared group are still stored, for ECUs
+    /// that clear asynchronously
+    #[serde(default)]
+    pub verify_clear: bool,
+    /// Layout of the DTC extended data records (0x19 0x06)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extended_data: Vec<ExtendedDataRecordConfig>,
+}
+
+/// A ReadDTCInformation (0x19) report sub-function used to list DTCs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DtcReport {
+    /// reportDTCByStatusMask (0x02)
+    StatusMask,
+    /// reportDTCBySeverityMaskRecord (0x08), which adds the ECU's own
+    /// severity to every DTC
+    SeverityMask,
+    /// reportUserDefMemoryDTCByStatusMask (0x17), for the mirror memory
+    UserDefMemory,
+}
+
+impl DtcReport {
+    /// Name as configured and reported in the component capabilities
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::StatusMask => "status-mask",
+            Self::SeverityMask => "severity-mask",
+            Self::UserDefMemory => "user-def-memory",
+        }
+    }
+}
+
+/// Size and meaning of one DTC extended data record
+///
+/// Reading all records returns them back to back without lengths, so every
+/// record but the last needs its length to be split out. A record of up to
+/// 8 bytes is also reported as a big-endian counter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtendedDataRecordConfig {
+    /// DTCExtDataRecordNumber
+    pub record: u8,
+    /// Name in the response, e.g. `occurrence_counter`
+    pub name: String,
+    /// Record size in bytes
+    pub length: usize,
+}
+
+/// Address and size encoding for ReadMemoryByAddress (UDS 0x23) and
+/// WriteMemoryByAddress (UDS 0x3D), plus the gate on memory writes.
+///
+/// The addressAndLengthFormatIdentifier (ALFID) gives the byte width of the
+/// memorySize (high nibble) and memoryAddress (low nibble). A width left
+/// unset is sized to each request — the fewest bytes that hold the value.
+/// ECUs that expect a fixed layout pin it:
+///
+/// ```toml
+/// [ecu.vtx_ecm.memory]
+/// address_bytes = 4
+/// size_bytes = 2
+/// write_session = "programming"   # default
+/// write_security_level = 1        # default; 0 disables the security gate
+/// ```
+///
+/// Memory writes are refused unless the ECU is in `write_session` and
+/// unlocked at `write_security_level`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryAccessConfig {
+    /// Fixed memoryAddress width in bytes (1-8)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_bytes: Option<u8>,
+    /// Fixed memorySize width in bytes (1-4)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u8>,
+    /// Session that must be active for memory writes (a session mode name
+    /// or sub-function, resolved through [`SessionConfig::session_id`])
+    #[serde(default = "default_memory_write_session")]
+    pub write_session: String,
+    /// SecurityAccess level memory writes require (0 = none)
+    #[serde(default = "default_memory_write_security_level")]
+    pub write_security_level: u8,
+}
+
+impl Default for MemoryAccessConfig {
+    fn default() -> Self {
+        Self {
+            address_bytes: None,
+            size_bytes: None,
+            write_session: default_memory_write_session(),
+            write_security_level: default_memory_write_security_level(),
+        }
+    }
+}
+
+fn default_memory_write_session() -> String {
+    "programming".to_string()
+}
+
+fn default_memory_write_security_level() -> u8 {
+    1
 }
 
 /// Per-ECU transparent SecurityAccess (UDS 0x27) configuration.
 ///
 /// The `algorithm` selects a pluggable key-derivation provider (see
-/// [`crate::unlock`]); `"xor"` is the simulation gate. `secret_hex` is the
-/// shared secret as a hex string. Example:
+/// [`crate::unlock`]): `"xor"` (the simulation gate), `"add"` or
+/// `"aes128-cmac"` (16-byte key). `secret_hex` is the shared secret as a hex
+/// string. Example:
 ///
 /// ```toml
 /// [ecu.vtx_ecm.unlock]
@@ -57,7 +589,7 @@ pub struct UdsBackendConfig {
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnlockConfig {
-    /// Key-derivation algorithm (e.g. `"xor"`).
+    /// Key-derivation algorithm (`"xor"`, `"add"` or `"aes128-cmac"`).
     pub algorithm: String,
     /// Shared secret as a hex string (e.g. `"ff"`, `"deadbeef"`).
     pub secret_hex: String,
@@ -67,7 +599,8 @@ pub struct UnlockConfig {
     pub level: Option<u8>,
 }
 
-/// Flash commit/rollback configuration for A/B bank firmware updates
+/// Flash configuration (`[ecu.*.flash]`): commit/rollback for A/B bank
+/// firmware updates and TransferData compression
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FlashCommitConfig {
     /// Whether this ECU supports firmware rollback
@@ -79,6 +612,44 @@ pub struct FlashCommitConfig {
     /// UDS Routine ID for rollback (e.g., "0xFF02")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rollback_routine: Option<String>,
+    /// Compression applied to each TransferData block; announced in the
+    /// RequestDownload dataFormatIdentifier
+    #[serde(default)]
+    pub compression: CompressionMethod,
+    /// Disable normal communication (CommunicationControl 0x28
+    /// `disable-rx-tx`) while the image transfers, re-enabled when it ends
+    #[serde(default)]
+    pub disable_communication: bool,
+    /// Switch DTC logging off (ControlDTCSetting 0x85 `off`) while the image
+    /// transfers, back on when it ends
+    #[serde(default)]
+    pub disable_dtc_setting: bool,
+    /// Require packages signed by this key; unsigned or tampered packages
+    /// fail verification and are never flashed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<SignatureConfig>,
+}
+
+/// Package signature check (`[ecu.*.flash.signature]`), see
+/// [`crate::signature`]. `algorithm` is `"ed25519"` or `"rsa-pkcs1-sha256"`;
+/// `public_key_pem` the signer's public key as a PEM SubjectPublicKeyInfo
+/// (`-----BEGIN PUBLIC KEY-----`).
+///
+/// ```toml
+/// [ecu.engine_ecu.flash.signature]
+/// algorithm = "ed25519"
+/// public_key_pem = """
+/// -----BEGIN PUBLIC KEY-----
+/// MCowBQYDK2VwAyEA...
+/// -----END PUBLIC KEY-----
+/// """
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureConfig {
+    /// Signature algorithm (`"ed25519"` or `"rsa-pkcs1-sha256"`).
+    pub algorithm: String,
+    /// PEM-encoded public key the signature must verify against.
+    pub public_key_pem: String,
 }
 
 // =============================================================================
@@ -101,6 +672,15 @@ pub enum TransportConfig {
     /// deserialize with an unknown-variant error.
     #[cfg(feature = "mock-transport")]
     Mock(MockConfig),
+    /// Replay of a recorded UDS session (see `transport::replay`)
+    Replay(ReplayConfig),
+    /// Adapter built by the factory registered for `kind` (see
+    /// `transport::registry`); `params` is passed to it as-is
+    Custom {
+        kind: String,
+        #[serde(default)]
+        params: serde_json::Value,
+    },
 }
 
 /// SocketCAN configuration
@@ -111,6 +691,11 @@ pub struct SocketCanConfig {
     /// CAN bus bitrate
     #[serde(default = "default_bitrate")]
     pub bitrate: u32,
+    /// Open the ISO-TP socket in CAN FD mode (with bit rate switching)
+    ///
+    /// Required for an `isotp.tx_dl` above 8.
+    #[serde(default)]
+    pub can_fd: bool,
     /// ISO-TP configuration
     pub isotp: IsoTpConfig,
 }
@@ -120,6 +705,9 @@ fn default_bitrate() -> u32 {
 }
 
 /// ISO-TP addressing and options
+///
+/// IDs up to `0x7FF` are sent as 11-bit (standard) frames, larger IDs as
+/// 29-bit (extended) frames.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IsoTpConfig {
     /// Transmit CAN ID (tester -> ECU)
@@ -132,15 +720,50 @@ pub struct IsoTpConfig {
     /// RX padding byte value
     #[serde(default = "default_padding")]
     pub rx_padding: u8,
-    /// Block size for flow control
+    /// Block size we advertise in flow control when receiving a
+    /// multi-frame response (0 = send all consecutive frames at once)
     #[serde(default)]
     pub block_size: u8,
-    /// Separation time minimum (microseconds)
+    /// Separation time we ask the sender to keep between consecutive frames
+    /// (microseconds): 100-900 in steps of 100, or 0-127 ms in whole
+    /// milliseconds
     #[serde(default)]
     pub st_min_us: u32,
-    /// TX data length
+    /// TX data length: 8 on classic CAN; 8, 12, 16, 20, 24, 32, 48 or 64
+    /// with `can_fd`
     #[serde(default = "default_tx_dl")]
     pub tx_dl: u8,
+    /// Send and receive 29-bit (extended) identifiers; unset ⇒ 29-bit, as
+    /// before this option existed. Set `false` for 11-bit IDs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extended_ids: Option<bool>,
+    /// Retry with the other identifier width when a request times out
+    #[serde(default)]
+    pub addressing_fallback: Option<AddressingFallbackConfig>,
+}
+
+/// Alternate addressing tried when the configured CAN IDs get no response
+///
+/// For bring-up on buses mixing 11-bit OBD and 29-bit UDS addressing. The
+/// alternate IDs are recomputed from the other width's convention:
+/// ISO 15765-2 normal fixed addressing (`0x18DA<ecu><tester>` /
+/// `0x18DA<tester><ecu>`) for 29-bit, the ISO 15765-4 physical pair
+/// (`0x7E0 + n` / `0x7E8 + n`) for 11-bit. Whichever addressing answers
+/// first is kept for the lifetime of the connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressingFallbackConfig {
+    /// ECU (target) address for 29-bit normal fixed addressing
+    pub ecu_address: u8,
+    /// Tester (source) address for 29-bit normal fixed addressing
+    #[serde(default = "default_tester_address")]
+    pub tester_address: u8,
+    /// ECU index `n` (0-7) for the 11-bit pair `0x7E0 + n` / `0x7E8 + n`
+    #[serde(default)]
+    pub obd_ecu_index: u8,
+}
+
+fn default_tester_address() -> u8 {
+    0xF1
 }
 
 fn default_padding() -> u8 {
@@ -163,9 +786,14 @@ pub struct DoIpConfig {
     pub source_address: u16,
     /// DoIP target address (ECU logical address)
     pub target_address: u16,
-    /// Activation type (default: 0x00)
-    #[serde(default)]
+    /// Routing activation type: 0x00 default, 0x01 WWH-OBD, 0xE0 central
+    /// security (default: 0x00)
+    #[serde(default, alias = "routing_activation_type")]
     pub activation_type: u8,
+    /// OEM-specific 4 bytes some gateways require in the routing activation
+    /// request; sent as zeros when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oem_specific: Option<[u8; 4]>,
     /// Connection timeout in milliseconds
     #[serde(default = "default_doip_connect_timeout")]
     pub connect_timeout_ms: u64,
@@ -178,6 +806,12 @@ pub struct DoIpConfig {
     /// Keep-alive interval in seconds (0 to disable)
     #[serde(default = "default_doip_keepalive")]
     pub keepalive_interval_secs: u64,
+    /// Close the TCP connection after this long without traffic, in
+    /// milliseconds (0 to keep it open). The next request reconnects and
+    /// repeats routing activation; frames received from the ECU, such as
+    /// periodic subscription data, count as traffic.
+    #[serde(default)]
+    pub idle_close_ms: u64,
     /// Enable vehicle discovery via UDP broadcast
     #[serde(default)]
     pub auto_discover: bool,
@@ -215,6 +849,13 @@ pub struct MockConfig {
     pub latency_ms: u64,
 }
 
+/// Replay transport configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayConfig {
+    /// Path to the recorded trace (JSON, or YAML by `.yaml`/`.yml` extension)
+    pub trace: String,
+}
+
 // =============================================================================
 // Parameter Configuration
 // =============================================================================
@@ -319,6 +960,67 @@ pub struct OperationConfig {
     /// Required security level
     #[serde(default)]
     pub security_level: u8,
+    /// Session that must be active to run the routine (a session mode name
+    /// or sub-function, resolved through [`SessionConfig::session_id`]);
+    /// unset ⇒ any session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_session: Option<String>,
+    /// Routine result codes surfaced as synthetic faults in `/faults`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub result_faults: Vec<ResultFaultConfig>,
+    /// Layout of the start option record, so executions can pass named
+    /// `params` instead of hex
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params_def: Vec<RoutineParamConfig>,
+}
+
+/// One field of a routine's option record, in wire order (big-endian)
+///
+/// ```toml
+/// [[ecu.vtx_ecm.operations]]
+/// id = "set_idle_target"
+/// rid = "0x0210"
+/// params_def = [
+///     { name = "mode", data_type = "uint8" },
+///     { name = "target_rpm", data_type = "uint16", scale = 0.5 },
+/// ]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutineParamConfig {
+    /// Key of the field in the `params` object
+    pub name: String,
+    /// Wire type
+    pub data_type: DataType,
+    /// Scale factor: physical = raw * scale + offset
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+    /// Offset: physical = raw * scale + offset
+    #[serde(default)]
+    pub offset: f64,
+    /// Fixed byte length for `string`/`bytes` (padded with zeros)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<usize>,
+}
+
+/// Maps one routine result code to a synthetic fault.
+///
+/// The result code is the first byte of the routine status record
+/// (after the RID) returned by start or request-results. A run ending in
+/// a mapped code raises the fault; a run ending in any other code clears
+/// it, as does ClearDiagnosticInformation for all groups, or for the
+/// single DTC `code` names when it is a 6-hex-digit DTC ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultFaultConfig {
+    /// Routine result code (first status-record byte)
+    pub result: u8,
+    /// Fault code reported in `/faults` (e.g., "RT-SELFTEST-01", or a DTC
+    /// ID such as "C12300" to let a clear of that DTC take it too)
+    pub code: String,
+    /// Fault category
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Human-readable description
+    pub description: String,
 }
 
 // =============================================================================
@@ -364,6 +1066,20 @@ pub struct OutputConfig {
     /// Allowed string values for enum-like outputs (index maps to raw integer value)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub allowed: Vec<String>,
+    /// What a short-term adjustment outside `min`/`max` does
+    #[serde(default)]
+    pub range_policy: RangePolicy,
+}
+
+/// Handling of a physical output value outside the configured `min`/`max`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RangePolicy {
+    /// Refuse the request before anything is sent to the ECU
+    #[default]
+    Reject,
+    /// Send the nearest bound instead
+    Clamp,
 }
 
 // =============================================================================
@@ -388,6 +1104,20 @@ pub struct SessionConfig {
     /// TransferData block counter wrap (what to use after 255, typically same as start)
     #[serde(default = "default_block_counter_wrap")]
     pub transfer_data_block_counter_wrap: u8,
+    /// Upper bound on the TransferData message length (maxNumberOfBlockLength)
+    ///
+    /// The ECU's advertised block length is clamped to this and to the
+    /// transport's own message limit. Set it when an ECU advertises a length
+    /// it cannot actually accept.
+    #[serde(default)]
+    pub transfer_data_max_block_length: Option<u32>,
+    /// Largest DID value, in bytes, one WriteDataByIdentifier may carry
+    ///
+    /// Writes above this (or above what the transport can carry after the
+    /// SID and DID) are rejected before anything is sent. Unset ⇒ only the
+    /// transport's limit applies.
+    #[serde(default)]
+    pub write_data_max_length: Option<u32>,
     /// Default session sub-function (0x01)
     #[serde(default = "default_session")]
     pub default_session: u8,
@@ -406,6 +1136,9 @@ pub struct SessionConfig {
     /// Keepalive configuration
     #[serde(default)]
     pub keepalive: KeepaliveConfig,
+    /// SecurityAccess (0x27) handshake timing
+    #[serde(default)]
+    pub security_handshake: SecurityHandshakeConfig,
 }
 
 impl Default for SessionConfig {
@@ -416,12 +1149,66 @@ impl Default for SessionConfig {
             custom_sessions: HashMap::new(),
             transfer_data_block_counter_start: default_block_counter_start(),
             transfer_data_block_counter_wrap: default_block_counter_wrap(),
+            transfer_data_max_block_length: None,
+            write_data_max_length: None,
             default_session: default_session(),
             programming_session: programming_session(),
             extended_session: extended_session(),
             engineering_session: engineering_session(),
             security: None,
             keepalive: KeepaliveConfig::default(),
+            security_handshake: SecurityHandshakeConfig::default(),
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Resolve a SOVD session mode value to its UDS sub-function
+    ///
+    /// Accepts the standard names (`default`, `programming`, `extended`,
+    /// `engineering`) and any `custom_sessions` name, case-insensitively,
+    /// each mapped to its configured byte, or a raw hex (`0x43`) / decimal
+    /// sub-function. This is the single name→byte mapping every mode
+    /// handler goes through.
+    pub fn session_id(&self, name: &str) -> Option<u8> {
+        let name = name.trim().to_lowercase();
+        match name.as_str() {
+            "default" => Some(self.default_session),
+            "programming" => Some(self.programming_session),
+            "extended" => Some(self.extended_session),
+            "engineering" => Some(self.engineering_session),
+            _ => {
+                if let Some(&id) = self.custom_sessions.get(&name) {
+                    return Some(id);
+                }
+                match name.strip_prefix("0x") {
+                    Some(hex) => u8::from_str_radix(hex, 16).ok(),
+                    None => name.parse().ok(),
+                }
+            }
+        }
+    }
+
+    /// Name of a UDS session sub-function, the inverse of [`session_id`]
+    ///
+    /// Unnamed sub-functions are rendered as `0xNN`.
+    ///
+    /// [`session_id`]: Self::session_id
+    pub fn session_name(&self, session_id: u8) -> String {
+        if session_id == self.default_session {
+            "default".to_string()
+        } else if session_id == self.programming_session {
+            "programming".to_string()
+        } else if session_id == self.extended_session {
+            "extended".to_string()
+        } else if session_id == self.engineering_session {
+            "engineering".to_string()
+        } else {
+            self.custom_sessions
+                .iter()
+                .find(|(_, &id)| id == session_id)
+                .map(|(name, _)| name.clone())
+                .unwrap_or_else(|| format!("0x{:02X}", session_id))
         }
     }
 }
@@ -498,6 +1285,94 @@ impl Default for KeepaliveConfig {
     }
 }
 
+/// SecurityAccess (0x27) handshake timing
+///
+/// Seed/key over a marginal link can lose the seed response; the seed request
+/// is idempotent on the ECU so it is retried on transport failures. The key
+/// is never retried: a wrong key counts against the ECU's attempt limit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityHandshakeConfig {
+    /// Per-request timeout for the seed and key messages
+    #[serde(default = "default_security_timeout")]
+    pub timeout_ms: u64,
+    /// Extra seed-request attempts after a transport error or timeout
+    #[serde(default = "default_seed_retries")]
+    pub seed_retries: u32,
+    /// Delay between seed-request attempts
+    #[serde(default = "default_seed_retry_delay")]
+    pub retry_delay_ms: u64,
+    /// How long the ECU accepts a key for an issued seed (0 = no limit).
+    /// A seed older than this is discarded and a fresh one requested.
+    #[serde(default)]
+    pub seed_validity_ms: u64,
+    /// Invalid keys (NRC 0x35) tolerated before access is locked out locally
+    /// (0 = only lock out when the ECU reports NRC 0x36/0x37)
+    #[serde(default = "default_security_max_attempts")]
+    pub max_attempts: u32,
+    /// Length of a lockout; seed requests are refused until it has passed
+    #[serde(default = "default_security_lockout")]
+    pub lockout_ms: u64,
+    /// Dev-only dump of seed→key pairs; unset ⇒ nothing is captured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_capture: Option<SeedKeyCaptureConfig>,
+}
+
+/// Dev-only capture of SecurityAccess (0x27) seed→key pairs
+///
+/// For verifying an OEM seed/key algorithm on an authorized bench: every key
+/// sent is appended to `path` together with the seed it answers and whether
+/// the ECU accepted it, one JSON object per line. The file holds live key
+/// material, so nothing is written unless `unsafe_capture_keys` is set as
+/// well. The logs never carry seeds or keys either way.
+///
+/// ```toml
+/// [session.security_handshake.key_capture]
+/// path = "/tmp/seed-key.jsonl"
+/// unsafe_capture_keys = true
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedKeyCaptureConfig {
+    /// File the pairs are appended to
+    pub path: PathBuf,
+    /// Acknowledges that `path` will hold working keys
+    #[serde(default)]
+    pub unsafe_capture_keys: bool,
+}
+
+fn default_security_timeout() -> u64 {
+    5000
+}
+
+fn default_seed_retries() -> u32 {
+    2
+}
+
+fn default_seed_retry_delay() -> u64 {
+    200
+}
+
+fn default_security_max_attempts() -> u32 {
+    3
+}
+
+fn default_security_lockout() -> u64 {
+    10_000
+}
+
+impl Default for SecurityHandshakeConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: default_security_timeout(),
+            seed_retries: default_seed_retries(),
+            retry_delay_ms: default_seed_retry_delay(),
+            seed_validity_ms: 0,
+            max_attempts: default_security_max_attempts(),
+            lockout_ms: default_security_lockout(),
+            key_capture: None,
+        }
+    }
+}
+
 // =============================================================================
 // Service Overrides
 // =============================================================================
@@ -532,6 +1407,9 @@ pub struct ServiceOverrides {
     /// ReadDataByIdentifier (standard: 0x22)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub read_data_by_id: Option<u8>,
+    /// ReadMemoryByAddress (standard: 0x23)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_memory_by_address: Option<u8>,
     /// SecurityAccess (standard: 0x27)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub security_access: Option<u8>,
@@ -562,6 +1440,9 @@ pub struct ServiceOverrides {
     /// RequestTransferExit (standard: 0x37)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_transfer_exit: Option<u8>,
+    /// WriteMemoryByAddress (standard: 0x3D)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_memory_by_address: Option<u8>,
     /// TesterPresent (standard: 0x3E)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tester_present: Option<u8>,
@@ -3,7 +3,7 @@
 use sovd_core::BackendError;
 use thiserror::Error;
 
-use crate::uds::UdsError;
+use crate::uds::{NegativeResponseCode, UdsError};
 
 /// UDS-specific backend errors
 #[derive(Debug, Error)]
@@ -57,11 +57,24 @@ impl From<UdsError> for BackendError {
                 let nrc_byte: u8 = nrc.into();
                 map_nrc_to_backend_error(service_id, nrc_byte, &nrc.to_string())
             }
+            UdsError::RequestOutOfRange { service_id } => {
+                let nrc = NegativeResponseCode::RequestOutOfRange;
+                map_nrc_to_backend_error(service_id, nrc.into(), &nrc.to_string())
+            }
+            UdsError::ServiceDenied { service_id } => BackendError::Forbidden(format!(
+                "UDS service 0x{:02X} is denied for this ECU",
+                service_id
+            )),
             UdsError::Timeout => BackendError::Timeout,
+            UdsError::QueueFull { waiting } => BackendError::RateLimited(format!(
+                "{} requests already waiting for this ECU",
+                waiting
+            )),
             UdsError::Transport(msg) => BackendError::Transport(msg),
             UdsError::InvalidResponse(msg) => {
                 BackendError::Protocol(format!("Invalid response: {}", msg))
             }
+            UdsError::InvalidRequest(msg) => BackendError::InvalidRequest(msg),
             UdsError::SecurityAccessFailed(_) => BackendError::SecurityRequired(1),
             UdsError::SessionTransitionFailed(msg) => {
                 BackendError::Protocol(format!("Session transition failed: {}", msg))
@@ -31,7 +31,9 @@ pub mod backend;
 pub mod config;
 pub mod error;
 pub mod output_conv;
+pub mod routine_conv;
 pub mod session;
+pub mod signature;
 pub mod subscription;
 pub mod transport;
 pub mod uds;
@@ -41,10 +43,11 @@ pub use backend::UdsBackend;
 pub use config::UdsBackendConfig;
 pub use error::UdsBackendError;
 pub use session::{SessionError, SessionManager, SessionState};
+pub use signature::{PackageVerifier, SignatureError};
 pub use subscription::{StreamError, StreamManager, StreamSubscription};
 pub use transport::{create_transport, TransportAdapter, TransportError};
 pub use uds::{NegativeResponseCode, ServiceIds, UdsError, UdsService};
-pub use unlock::{UnlockError, UnlockProvider, XorUnlock};
+pub use unlock::{AddUnlock, Aes128CmacUnlock, UnlockError, UnlockProvider, XorUnlock};
 
 // Re-export CAN bus scanner (Linux + socketcan feature only)
 #[cfg(all(target_os = "linux", feature = "socketcan"))]
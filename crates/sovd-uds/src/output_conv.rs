@@ -2,10 +2,190 @@
 //!
 //! Converts between typed JSON values (booleans, enums, numbers) and raw UDS bytes.
 //! Operates on `OutputConfig` type metadata to determine encoding/decoding strategy.
+//! Also expands `ramp` and `sequence` requests into the timed short-term
+//! adjustments ([`OutputProfile`]) that drive an output through them.
+
+use std::time::Duration;
+
+use crate::config::{DataType, OutputConfig, RangePolicy};
+use anyhow::{anyhow, bail, Result};
+use serde_json::{Map, Value};
+
+/// Shortest interval between two adjustments of a profile, so a ramp
+/// cannot flood the bus with 0x2F requests
+pub const MIN_PROFILE_STEP: Duration = Duration::from_millis(10);
+
+/// Most adjustments one pass of a profile may hold
+const MAX_PROFILE_SETPOINTS: usize = 10_000;
+
+/// Ramp step when a request leaves out `step_ms`
+const DEFAULT_RAMP_STEP: Duration = Duration::from_millis(100);
+
+/// Timed short-term adjustments (0x2F 0x03) an IO control `ramp` or
+/// `sequence` expands to
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputProfile {
+    /// One pass: each value with its offset from the start of the pass
+    pub setpoints: Vec<(Duration, Value)>,
+    /// Length of one pass; the next one starts after it
+    pub period: Duration,
+    /// Passes to run; `None` repeats until cancelled
+    pub repeat: Option<u32>,
+}
+
+impl OutputProfile {
+    /// Profile for an IO control request whose `action` is `ramp` or
+    /// `sequence`, `None` for any other action
+    ///
+    /// ```json
+    /// {"action": "ramp", "start": 0, "end": 80, "duration_ms": 2000, "step_ms": 100}
+    /// {"action": "sequence", "setpoints": [{"value": 10, "hold_ms": 500}], "repeat": 3}
+    /// ```
+    ///
+    /// `step_ms` defaults to 100; `repeat` to 1, and 0 repeats the sequence
+    /// until it is cancelled.
+    pub fn from_params(params: &Map<String, Value>) -> Result<Option<Self>> {
+        let millis = |key: &str| -> Result<Option<Duration>> {
+            match params.get(key) {
+                None => Ok(None),
+                Some(v) => v
+                    .as_u64()
+                    .map(|ms| Some(Duration::from_millis(ms)))
+                    .ok_or_else(|| anyhow!("`{}` must be a whole number of milliseconds", key)),
+            }
+        };
+        let number = |key: &str| -> Result<f64> {
+            params
+                .get(key)
+                .and_then(Value::as_f64)
+                .ok_or_else(|| anyhow!("ramp needs a numeric `{}`", key))
+        };
+
+        match params.get("action").and_then(Value::as_str) {
+            Some("ramp") => {
+                let duration =
+                    millis("duration_ms")?.ok_or_else(|| anyhow!("ramp needs `duration_ms`"))?;
+                let step = millis("step_ms")?.unwrap_or(DEFAULT_RAMP_STEP);
+                Self::ramp(number("start")?, number("end")?, duration, step).map(Some)
+            }
+            Some("sequence") => {
+                let steps = params
+                    .get("setpoints")
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| anyhow!("sequence needs a `setpoints` array"))?
+                    .iter()
+                    .map(|step| {
+                        let value = step
+                            .get("value")
+                            .cloned()
+                            .ok_or_else(|| anyhow!("every setpoint needs a `value`"))?;
+                        let hold = step
+                            .get("hold_ms")
+                            .and_then(Value::as_u64)
+                            .ok_or_else(|| anyhow!("every setpoint needs a `hold_ms`"))?;
+                        Ok((value, Duration::from_millis(hold)))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let repeat = match params.get("repeat") {
+                    None => Some(1),
+                    Some(v) => match v.as_u64() {
+                        Some(0) => None,
+                        Some(n) => Some(u32::try_from(n).unwrap_or(u32::MAX)),
+                        None => bail!("`repeat` must be a whole number"),
+                    },
+                };
+                Self::sequence(steps, repeat).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Step linearly from `start` to `end` over `duration`, one adjustment
+    /// every `step`; the last one is `end` exactly, at `duration`
+    pub fn ramp(start: f64, end: f64, duration: Duration, step: Duration) -> Result<Self> {
+        if step < MIN_PROFILE_STEP {
+            bail!(
+                "ramp step must be at least {} ms",
+                MIN_PROFILE_STEP.as_millis()
+            );
+        }
+        let steps = (duration.as_secs_f64() / step.as_secs_f64()).ceil() as usize;
+        if steps > MAX_PROFILE_SETPOINTS {
+            bail!(
+                "ramp of {} steps is longer than the {} allowed",
+                steps,
+                MAX_PROFILE_SETPOINTS
+            );
+        }
+        let setpoints = (0..=steps)
+            .map(|i| {
+                let at = (step * i as u32).min(duration);
+                let fraction = if duration.is_zero() {
+                    1.0
+                } else {
+                    at.as_secs_f64() / duration.as_secs_f64()
+                };
+                (at, to_json_number(start + (end - start) * fraction))
+            })
+            .collect();
+        Ok(Self {
+            setpoints,
+            period: duration,
+            repeat: Some(1),
+        })
+    }
+
+    /// Hold each value for its duration, in order, `repeat` times
+    /// (`None`: until cancelled)
+    pub fn sequence(steps: Vec<(Value, Duration)>, repeat: Option<u32>) -> Result<Self> {
+        if steps.is_empty() {
+            bail!("sequence needs at least one setpoint");
+        }
+        if steps.len() > MAX_PROFILE_SETPOINTS {
+            bail!(
+                "sequence of {} setpoints is longer than the {} allowed",
+                steps.len(),
+                MAX_PROFILE_SETPOINTS
+            );
+        }
+        if steps.iter().any(|(_, hold)| *hold < MIN_PROFILE_STEP) {
+            bail!(
+                "every setpoint must be held at least {} ms",
+                MIN_PROFILE_STEP.as_millis()
+            );
+        }
+        let mut at = Duration::ZERO;
+        let mut setpoints = Vec::with_capacity(steps.len());
+        for (value, hold) in steps {
+            setpoints.push((at, value));
+            at = at
+                .checked_add(hold)
+                .ok_or_else(|| anyhow!("sequence holds add up to more than can be scheduled"))?;
+        }
+        if repeat.is_some_and(|passes| at.checked_mul(passes).is_none()) {
+            bail!("sequence repeats for longer than can be scheduled");
+        }
+        Ok(Self {
+            setpoints,
+            period: at,
+            repeat,
+        })
+    }
+
+    /// Time the whole profile takes; `None` when it repeats until
+    /// cancelled
+    pub fn length(&self) -> Option<Duration> {
+        self.repeat
+            .and_then(|passes| self.period.checked_mul(passes))
+    }
 
-use crate::config::{DataType, OutputConfig};
-use anyhow::{anyhow, Result};
-use serde_json::Value;
+    /// Adjustments sent over the whole profile; `None` when it repeats
+    /// until cancelled
+    pub fn total(&self) -> Option<u64> {
+        self.repeat
+            .map(|passes| passes as u64 * self.setpoints.len() as u64)
+    }
+}
 
 /// Encode a typed JSON value into raw bytes for UDS I/O control.
 ///
@@ -82,6 +262,38 @@ pub fn encode_output_value(config: &OutputConfig, value: &Value) -> Result<Vec<u
     ))
 }
 
+/// Check a physical value against the output's `min`/`max` before it is
+/// encoded.
+///
+/// Returns the value to send: unchanged when in range, the violated bound
+/// under [`RangePolicy::Clamp`], an error under [`RangePolicy::Reject`].
+/// Only physical numbers are checked — labels, indices into `allowed`,
+/// booleans and raw hex pass through.
+pub fn check_output_range(config: &OutputConfig, value: &Value) -> Result<Value> {
+    if config.data_type.is_none() || !config.allowed.is_empty() {
+        return Ok(value.clone());
+    }
+    let Some(physical) = value.as_f64() else {
+        return Ok(value.clone());
+    };
+
+    let bound = match (config.min, config.max) {
+        (Some(min), _) if physical < min => min,
+        (_, Some(max)) if physical > max => max,
+        _ => return Ok(value.clone()),
+    };
+    match config.range_policy {
+        RangePolicy::Clamp => Ok(to_json_number(bound)),
+        RangePolicy::Reject => Err(anyhow!(
+            "value {} for output '{}' is outside the allowed range [{}, {}]",
+            physical,
+            config.id,
+            config.min.unwrap_or(f64::NEG_INFINITY),
+            config.max.unwrap_or(f64::INFINITY)
+        )),
+    }
+}
+
 /// Decode raw bytes into a typed JSON value for API responses.
 ///
 /// Conversion strategy by priority:
@@ -180,6 +392,7 @@ fn to_json_number(v: f64) -> Value {
 mod tests {
     use super::*;
     use crate::config::OutputConfig;
+    use serde_json::json;
 
     fn make_config(
         data_type: Option<DataType>,
@@ -201,6 +414,17 @@ mod tests {
             min: None,
             max: None,
             allowed,
+            range_policy: RangePolicy::Reject,
+        }
+    }
+
+    /// throttle in percent: 0..100 % over a uint8
+    fn throttle(range_policy: RangePolicy) -> OutputConfig {
+        OutputConfig {
+            min: Some(0.0),
+            max: Some(100.0),
+            range_policy,
+            ..make_config(Some(DataType::Uint8), 0.392157, 0.0, vec![])
         }
     }
 
@@ -349,4 +573,110 @@ mod tests {
         let bytes = encode_output_value(&cfg, &Value::Bool(true)).unwrap();
         assert_eq!(bytes, vec![0x01]);
     }
+
+    #[test]
+    fn test_range_accepts_value_within_bounds() {
+        let cfg = throttle(RangePolicy::Reject);
+        let value = check_output_range(&cfg, &serde_json::json!(50.0)).unwrap();
+        assert_eq!(value, serde_json::json!(50.0));
+    }
+
+    #[test]
+    fn test_range_reject_policy() {
+        let cfg = throttle(RangePolicy::Reject);
+        let err = check_output_range(&cfg, &serde_json::json!(150)).unwrap_err();
+        assert!(err.to_string().contains("[0, 100]"), "{err}");
+        assert!(check_output_range(&cfg, &serde_json::json!(-1)).is_err());
+    }
+
+    #[test]
+    fn test_range_clamp_policy() {
+        let cfg = throttle(RangePolicy::Clamp);
+        let value = check_output_range(&cfg, &serde_json::json!(150)).unwrap();
+        assert_eq!(value, serde_json::json!(100));
+        assert_eq!(
+            encode_output_value(&cfg, &value).unwrap(),
+            vec![255] // round(100 / 0.392157)
+        );
+        let value = check_output_range(&cfg, &serde_json::json!(-5.5)).unwrap();
+        assert_eq!(value, serde_json::json!(0));
+    }
+
+    #[test]
+    fn test_ramp_steps_evenly_and_ends_on_target() {
+        let ramp = OutputProfile::ramp(
+            0.0,
+            50.0,
+            Duration::from_millis(250),
+            Duration::from_millis(100),
+        )
+        .unwrap();
+        let values: Vec<_> = ramp.setpoints.iter().map(|(_, v)| v.clone()).collect();
+        assert_eq!(values, [json!(0), json!(20), json!(40), json!(50)]);
+        assert_eq!(ramp.setpoints[3].0, Duration::from_millis(250));
+        assert_eq!(ramp.total(), Some(4));
+    }
+
+    #[test]
+    fn test_sequence_from_params() {
+        let params = json!({
+            "action": "sequence",
+            "setpoints": [
+                {"value": "on", "hold_ms": 500},
+                {"value": 0, "hold_ms": 250}
+            ],
+            "repeat": 0
+        });
+        let profile = OutputProfile::from_params(params.as_object().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            profile.setpoints,
+            [
+                (Duration::ZERO, json!("on")),
+                (Duration::from_millis(500), json!(0))
+            ]
+        );
+        assert_eq!(profile.period, Duration::from_millis(750));
+        assert_eq!(profile.repeat, None);
+        assert_eq!(profile.total(), None);
+    }
+
+    #[test]
+    fn test_profile_rejects_flooding_steps() {
+        let params = json!({
+            "action": "ramp", "start": 0, "end": 100, "duration_ms": 1000, "step_ms": 1
+        });
+        assert!(OutputProfile::from_params(params.as_object().unwrap()).is_err());
+        // Plain actions are no profile
+        let params = json!({"action": "freeze"});
+        assert_eq!(
+            OutputProfile::from_params(params.as_object().unwrap()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sequence_rejects_unschedulable_lengths() {
+        let hold = Duration::from_secs(u64::MAX / 2);
+        let steps = vec![(json!(1), hold), (json!(0), hold), (json!(1), hold)];
+        assert!(OutputProfile::sequence(steps, Some(1)).is_err());
+
+        let steps = vec![(json!(1), hold)];
+        assert!(OutputProfile::sequence(steps.clone(), Some(3)).is_err());
+        // Until cancelled, only one pass needs to fit
+        let profile = OutputProfile::sequence(steps, None).unwrap();
+        assert_eq!(profile.length(), None);
+    }
+
+    #[test]
+    fn test_range_skips_labels_and_hex() {
+        let cfg = OutputConfig {
+            max: Some(1.0),
+            ..make_config(Some(DataType::Uint8), 1.0, 0.0, vec![])
+        };
+        // Raw hex is not a physical value
+        let value = check_output_range(&cfg, &Value::String("ff".into())).unwrap();
+        assert_eq!(value, Value::String("ff".into()));
+    }
 }
@@ -0,0 +1,185 @@
+//! Routine option-record encoding
+//!
+//! Builds the RoutineControl (0x31) start option record from a named JSON
+//! `params` object, using the operation's `params_def` layout. Fields are
+//! written in declaration order, big-endian.
+
+use crate::config::{DataType, OperationConfig, RoutineParamConfig};
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// Encode `params` (an object keyed by field name) into an option record.
+///
+/// Every field in `params_def` must be present; keys not in it are
+/// rejected so a misspelt name is not silently dropped.
+pub fn encode_routine_params(config: &OperationConfig, params: &Value) -> Result<Vec<u8>> {
+    if config.params_def.is_empty() {
+        return Err(anyhow!(
+            "operation '{}' has no params_def; pass raw_option_record instead",
+            config.id
+        ));
+    }
+    let obj = params
+        .as_object()
+        .ok_or_else(|| anyhow!("params must be an object, got {}", params))?;
+    if let Some(unknown) = obj
+        .keys()
+        .find(|k| !config.params_def.iter().any(|p| &p.name == *k))
+    {
+        return Err(anyhow!(
+            "unknown parameter '{}' for operation '{}'",
+            unknown,
+            config.id
+        ));
+    }
+
+    let mut record = Vec::new();
+    for param in &config.params_def {
+        let value = obj
+            .get(&param.name)
+            .ok_or_else(|| anyhow!("missing parameter '{}'", param.name))?;
+        record.extend(encode_param(param, value)?);
+    }
+    Ok(record)
+}
+
+fn encode_param(param: &RoutineParamConfig, value: &Value) -> Result<Vec<u8>> {
+    match param.data_type {
+        DataType::String => {
+            let s = value
+                .as_str()
+                .ok_or_else(|| anyhow!("parameter '{}' must be a string", param.name))?;
+            fixed_length(param, s.as_bytes().to_vec())
+        }
+        DataType::Bytes => {
+            let s = value
+                .as_str()
+                .ok_or_else(|| anyhow!("parameter '{}' must be a hex string", param.name))?;
+            let bytes = hex::decode(s)
+                .map_err(|e| anyhow!("parameter '{}': invalid hex: {}", param.name, e))?;
+            fixed_length(param, bytes)
+        }
+        DataType::Float => {
+            let physical = physical_value(param, value)?;
+            let raw = (physical - param.offset) / param.scale;
+            Ok((raw as f32).to_be_bytes().to_vec())
+        }
+        ref dt => {
+            let physical = physical_value(param, value)?;
+            let raw = ((physical - param.offset) / param.scale).round();
+            let (min, max) = integer_range(dt);
+            if raw < min || raw > max {
+                return Err(anyhow!(
+                    "parameter '{}': {} does not fit in {}",
+                    param.name,
+                    physical,
+                    dt
+                ));
+            }
+            let raw = raw as i64;
+            Ok(match dt.byte_size() {
+                Some(1) => vec![raw as u8],
+                Some(2) => (raw as u16).to_be_bytes().to_vec(),
+                _ => (raw as u32).to_be_bytes().to_vec(),
+            })
+        }
+    }
+}
+
+fn physical_value(param: &RoutineParamConfig, value: &Value) -> Result<f64> {
+    match value {
+        Value::Bool(b) => Ok(if *b { 1.0 } else { 0.0 }),
+        _ => value
+            .as_f64()
+            .ok_or_else(|| anyhow!("parameter '{}' must be a number", param.name)),
+    }
+}
+
+/// Raw value range of an integer type
+fn integer_range(dt: &DataType) -> (f64, f64) {
+    match dt {
+        DataType::Int8 => (i8::MIN as f64, i8::MAX as f64),
+        DataType::Int16 => (i16::MIN as f64, i16::MAX as f64),
+        DataType::Int32 => (i32::MIN as f64, i32::MAX as f64),
+        DataType::Uint16 => (0.0, u16::MAX as f64),
+        DataType::Uint32 => (0.0, u32::MAX as f64),
+        _ => (0.0, u8::MAX as f64),
+    }
+}
+
+fn fixed_length(param: &RoutineParamConfig, mut bytes: Vec<u8>) -> Result<Vec<u8>> {
+    if let Some(len) = param.length {
+        if bytes.len() > len {
+            return Err(anyhow!(
+                "parameter '{}' is {} bytes, at most {} allowed",
+                param.name,
+                bytes.len(),
+                len
+            ));
+        }
+        bytes.resize(len, 0);
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn param(name: &str, data_type: DataType, scale: f64) -> RoutineParamConfig {
+        RoutineParamConfig {
+            name: name.into(),
+            data_type,
+            scale,
+            offset: 0.0,
+            length: None,
+        }
+    }
+
+    fn operation(params_def: Vec<RoutineParamConfig>) -> OperationConfig {
+        OperationConfig {
+            id: "set_idle_target".into(),
+            name: "Set idle target".into(),
+            rid: "0x0210".into(),
+            description: None,
+            security_level: 0,
+            required_session: None,
+            result_faults: vec![],
+            params_def,
+        }
+    }
+
+    #[test]
+    fn test_encode_fields_in_order() {
+        let mut tag = param("tag", DataType::String, 1.0);
+        tag.length = Some(4);
+        let op = operation(vec![
+            param("mode", DataType::Uint8, 1.0),
+            param("target_rpm", DataType::Uint16, 0.5),
+            param("trim", DataType::Int8, 1.0),
+            tag,
+        ]);
+
+        let record = encode_routine_params(
+            &op,
+            &json!({ "mode": 1, "target_rpm": 850, "trim": -2, "tag": "ab" }),
+        )
+        .unwrap();
+        assert_eq!(record, vec![0x01, 0x06, 0xA4, 0xFE, b'a', b'b', 0, 0]);
+    }
+
+    #[test]
+    fn test_encode_rejects_bad_params() {
+        let op = operation(vec![param("mode", DataType::Uint8, 1.0)]);
+
+        assert!(encode_routine_params(&op, &json!({})).is_err());
+        assert!(encode_routine_params(&op, &json!({ "mode": 1, "mdoe": 2 })).is_err());
+        assert!(encode_routine_params(&op, &json!({ "mode": 256 })).is_err());
+        assert!(encode_routine_params(&op, &json!({ "mode": "fast" })).is_err());
+        assert!(encode_routine_params(&op, &json!([1])).is_err());
+
+        // Without a layout only raw option records are possible
+        assert!(encode_routine_params(&operation(vec![]), &json!({ "mode": 1 })).is_err());
+    }
+}
@@ -1,17 +1,19 @@
 //! Session manager for UDS communication
 
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use parking_lot::RwLock;
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use super::SessionState;
 use crate::config::SessionConfig;
 use crate::transport::TransportAdapter;
-use crate::uds::{ServiceIds, UdsService};
+use crate::uds::{NegativeResponseCode, ServiceIds, UdsError, UdsService};
 
 /// Security access state for tracking two-step client-driven flow
 #[derive(Debug, Clone, Default)]
@@ -20,10 +22,21 @@ pub struct SecurityAccessState {
     pub level: u8,
     /// Pending seed (if seed was requested but key not yet sent)
     pub pending_seed: Option<Vec<u8>>,
+    /// When the pending seed was issued (for the seed validity window)
+    pub seed_issued_at: Option<Instant>,
     /// Whether security is currently unlocked
     pub unlocked: bool,
 }
 
+/// Invalid-key count and lockout window. Kept apart from
+/// [`SecurityAccessState`] because a session change re-locks security but
+/// does not reset the ECU's delay timer.
+#[derive(Debug, Default)]
+struct SecurityLockout {
+    failed_attempts: u32,
+    locked_until: Option<Instant>,
+}
+
 /// Link control state for tracking baud rate transitions
 #[derive(Debug, Clone)]
 pub struct LinkState {
@@ -47,9 +60,17 @@ pub struct SessionManager {
     transport: Arc<dyn TransportAdapter>,
     config: SessionConfig,
     uds: UdsService,
+    /// UDS service with the 0x27 handshake timeout applied
+    security_uds: UdsService,
     current_state: RwLock<SessionState>,
     security_state: RwLock<SecurityAccessState>,
+    lockout: RwLock<SecurityLockout>,
     link_state: RwLock<LinkState>,
+    /// Bumped whenever the ECU's session may have been re-entered or
+    /// left: a 0x10 transition, an ECU reset, or a failed tester present
+    /// (S3 may lapse). State the ECU drops with the session, like I/O
+    /// control overrides, is only current within one epoch.
+    session_epoch: Arc<AtomicU64>,
     keepalive_handle: Mutex<Option<JoinHandle<()>>>,
 }
 
@@ -65,13 +86,38 @@ impl SessionManager {
         service_ids: ServiceIds,
     ) -> Self {
         let uds = UdsService::with_service_ids(transport.clone(), service_ids);
+        Self::with_uds(transport, config, uds)
+    }
+
+    /// Create a session manager on an existing UDS service, so the session
+    /// timing it adopts from 0x10 responses is shared with that service's
+    /// other clones
+    pub fn with_uds(
+        transport: Arc<dyn TransportAdapter>,
+        config: SessionConfig,
+        uds: UdsService,
+    ) -> Self {
+        let security_uds = uds
+            .clone()
+            .with_timeout(Duration::from_millis(config.security_handshake.timeout_ms));
+        match &config.security_handshake.key_capture {
+            Some(capture) if capture.unsafe_capture_keys => warn!(
+                path = %capture.path.display(),
+                "Capturing SecurityAccess seed/key pairs - bench use only"
+            ),
+            Some(_) => warn!("key_capture is set without unsafe_capture_keys, nothing is captured"),
+            None => {}
+        }
         Self {
             transport,
             config,
             uds,
+            security_uds,
             current_state: RwLock::new(SessionState::Default),
             security_state: RwLock::new(SecurityAccessState::default()),
+            lockout: RwLock::new(SecurityLockout::default()),
             link_state: RwLock::new(LinkState::default()),
+            session_epoch: Arc::new(AtomicU64::new(0)),
             keepalive_handle: Mutex::new(None),
         }
     }
@@ -102,12 +148,33 @@ impl SessionManager {
     }
 
     /// Request a seed for security access (UDS 0x27 step 1)
+    ///
+    /// Transport errors and timeouts are retried up to
+    /// `security_handshake.seed_retries` times; an NRC is returned as-is.
+    ///
+    /// Refused with [`SessionError::SecurityLockout`] while a lockout is
+    /// active; an ECU answering NRC 0x36/0x37 starts one.
     pub async fn request_security_seed(&self, level: u8) -> Result<Vec<u8>, SessionError> {
-        let seed = self
-            .uds
-            .security_access_request_seed(level)
-            .await
-            .map_err(|e| SessionError::SecurityAccessFailed(format!("Request seed: {}", e)))?;
+        self.check_lockout()?;
+        let timing = &self.config.security_handshake;
+        let mut attempt = 0;
+        let seed = loop {
+            match self.security_uds.security_access_request_seed(level).await {
+                Ok(seed) => break seed,
+                Err(e) if is_transient(&e) && attempt < timing.seed_retries => {
+                    attempt += 1;
+                    warn!(level, attempt, error = %e, "Seed request failed, retrying");
+                    tokio::time::sleep(Duration::from_millis(timing.retry_delay_ms)).await;
+                }
+                Err(e) if is_lockout_nrc(&e) => return Err(self.lock_out(level, &e)),
+                Err(e) => {
+                    return Err(SessionError::SecurityAccessFailed(format!(
+                        "Request seed: {}",
+                        e
+                    )))
+                }
+            }
+        };
 
         if seed.is_empty() || seed.iter().all(|&b| b == 0) {
             // Zero seed means already unlocked
@@ -115,6 +182,7 @@ impl SessionManager {
             let mut state = self.security_state.write();
             state.level = level;
             state.pending_seed = None;
+            state.seed_issued_at = None;
             state.unlocked = true;
             return Ok(vec![]);
         }
@@ -124,6 +192,7 @@ impl SessionManager {
             let mut state = self.security_state.write();
             state.level = level;
             state.pending_seed = Some(seed.clone());
+            state.seed_issued_at = Some(Instant::now());
             state.unlocked = false;
         }
 
@@ -134,31 +203,78 @@ impl SessionManager {
     /// Send a key for security access (UDS 0x27 step 2)
     pub async fn send_security_key(&self, level: u8, key: &[u8]) -> Result<(), SessionError> {
         // Verify we have a pending seed for this level
-        {
+        let seed = {
             let state = self.security_state.read();
-            if state.pending_seed.is_none() {
+            let Some(seed) = state.pending_seed.clone() else {
                 return Err(SessionError::SecurityAccessFailed(
                     "No pending seed - call request_security_seed first".to_string(),
                 ));
-            }
+            };
             if state.level != level {
                 return Err(SessionError::SecurityAccessFailed(format!(
                     "Level mismatch: expected {}, got {}",
                     state.level, level
                 )));
             }
+            seed
+        };
+
+        // A key for an expired seed would only burn one of the ECU's
+        // attempts; make the caller fetch a fresh seed instead.
+        if self.pending_seed_expired() {
+            let mut state = self.security_state.write();
+            state.pending_seed = None;
+            state.seed_issued_at = None;
+            return Err(SessionError::SecurityAccessFailed(
+                "Seed expired - request a new seed".to_string(),
+            ));
         }
 
-        // Send key to ECU
-        self.uds
-            .security_access_send_key(level, key)
-            .await
-            .map_err(|e| SessionError::SecurityAccessFailed(format!("Send key: {}", e)))?;
+        self.check_lockout()?;
+
+        // Send key to ECU (never retried: a wrong key counts as an attempt)
+        let result = self.security_uds.security_access_send_key(level, key).await;
+        self.capture_seed_key(level, &seed, key, result.is_ok());
+        if let Err(e) = result {
+            // The seed is spent whatever the ECU's answer
+            {
+                let mut state = self.security_state.write();
+                state.pending_seed = None;
+                state.seed_issued_at = None;
+            }
+            if is_lockout_nrc(&e) {
+                return Err(self.lock_out(level, &e));
+            }
+            if matches!(
+                e,
+                UdsError::NegativeResponse {
+                    nrc: NegativeResponseCode::InvalidKey,
+                    ..
+                }
+            ) {
+                let max_attempts = self.config.security_handshake.max_attempts;
+                let failed = {
+                    let mut lockout = self.lockout.write();
+                    lockout.failed_attempts += 1;
+                    lockout.failed_attempts
+                };
+                warn!(level, failed, max_attempts, "Invalid security key");
+                if max_attempts > 0 && failed >= max_attempts {
+                    return Err(self.lock_out(level, &e));
+                }
+            }
+            return Err(SessionError::SecurityAccessFailed(format!(
+                "Send key: {}",
+                e
+            )));
+        }
 
         // Update state
+        self.lockout.write().failed_attempts = 0;
         {
             let mut state = self.security_state.write();
             state.pending_seed = None;
+            state.seed_issued_at = None;
             state.unlocked = true;
         }
 
@@ -166,6 +282,121 @@ impl SessionManager {
         Ok(())
     }
 
+    /// Run the full seed/key handshake for `level`, computing the key with
+    /// `compute_key`. Reuses a still-valid pending seed; if the seed goes
+    /// stale before the key is ready, a fresh seed is requested once and the
+    /// key recomputed. Returns `Ok` immediately on a zero (already unlocked)
+    /// seed.
+    pub async fn unlock_with<F>(&self, level: u8, compute_key: F) -> Result<(), SessionError>
+    where
+        F: Fn(&[u8]) -> Result<Vec<u8>, String>,
+    {
+        let mut refreshed = false;
+        loop {
+            let pending = {
+                let state = self.security_state.read();
+                state.pending_seed.clone().filter(|_| state.level == level)
+            };
+            let seed = match pending {
+                Some(seed) if !self.pending_seed_expired() => seed,
+                _ => self.request_security_seed(level).await?,
+            };
+            if seed.is_empty() {
+                return Ok(());
+            }
+
+            let key = compute_key(&seed).map_err(SessionError::SecurityAccessFailed)?;
+
+            if !refreshed && self.pending_seed_expired() {
+                info!(
+                    level,
+                    "Seed expired before key was ready, requesting fresh seed"
+                );
+                refreshed = true;
+                let mut state = self.security_state.write();
+                state.pending_seed = None;
+                state.seed_issued_at = None;
+                continue;
+            }
+
+            return self.send_security_key(level, &key).await;
+        }
+    }
+
+    /// Append a sent key and its seed to the dev-only capture file, if
+    /// `security_handshake.key_capture` is enabled. A failed write is logged
+    /// and does not affect the unlock.
+    fn capture_seed_key(&self, level: u8, seed: &[u8], key: &[u8], accepted: bool) {
+        let Some(capture) = self
+            .config
+            .security_handshake
+            .key_capture
+            .as_ref()
+            .filter(|c| c.unsafe_capture_keys)
+        else {
+            return;
+        };
+        let line = serde_json::json!({
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "level": level,
+            "seed": hex::encode(seed),
+            "key": hex::encode(key),
+            "accepted": accepted,
+        });
+        let written = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&capture.path)
+            .and_then(|mut file| writeln!(file, "{}", line));
+        if let Err(e) = written {
+            warn!(path = %capture.path.display(), error = %e, "Seed/key capture failed");
+        }
+    }
+
+    /// Time left on the security lockout, if one is active
+    pub fn security_lockout_remaining(&self) -> Option<Duration> {
+        let mut lockout = self.lockout.write();
+        let until = lockout.locked_until?;
+        let remaining = until.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            *lockout = SecurityLockout::default();
+            return None;
+        }
+        Some(remaining)
+    }
+
+    fn check_lockout(&self) -> Result<(), SessionError> {
+        match self.security_lockout_remaining() {
+            Some(remaining) => Err(SessionError::SecurityLockout {
+                remaining_ms: remaining.as_millis() as u64,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Start a `lockout_ms` lockout after `cause`
+    fn lock_out(&self, level: u8, cause: &UdsError) -> SessionError {
+        let lockout_ms = self.config.security_handshake.lockout_ms;
+        self.lockout.write().locked_until =
+            Some(Instant::now() + Duration::from_millis(lockout_ms));
+        warn!(level, lockout_ms, %cause, "Security access locked out");
+        SessionError::SecurityLockout {
+            remaining_ms: lockout_ms,
+        }
+    }
+
+    /// Whether the pending seed has outlived `seed_validity_ms`
+    fn pending_seed_expired(&self) -> bool {
+        let validity_ms = self.config.security_handshake.seed_validity_ms;
+        if validity_ms == 0 {
+            return false;
+        }
+        self.security_state
+            .read()
+            .seed_issued_at
+            .is_some_and(|at| at.elapsed() >= Duration::from_millis(validity_ms))
+    }
+
     /// Get available security levels (from config)
     pub fn available_security_levels(&self) -> Vec<u8> {
         if let Some(ref security) = self.config.security {
@@ -175,13 +406,18 @@ impl SessionManager {
         }
     }
 
-    /// Get the current UDS session ID
+    /// Current session epoch; see the `session_epoch` field
+    pub fn session_epoch(&self) -> u64 {
+        self.session_epoch.load(Ordering::SeqCst)
+    }
+
+    /// Get the current UDS session ID (as configured for the current state)
     pub fn current_session_id(&self) -> u8 {
         match *self.current_state.read() {
-            SessionState::Default => 0x01,
-            SessionState::Programming => 0x02,
-            SessionState::Extended => 0x03,
-            SessionState::Engineering { .. } => 0x60,
+            SessionState::Default => self.config.default_session,
+            SessionState::Programming => self.config.programming_session,
+            SessionState::Extended => self.config.extended_session,
+            SessionState::Engineering { .. } => self.config.engineering_session,
         }
     }
 
@@ -200,24 +436,19 @@ impl SessionManager {
         self.diagnostic_session_control(session_id).await?;
 
         // Update internal state
-        let new_state = match session_id {
-            0x01 => {
-                self.stop_keepalive().await;
-                SessionState::Default
-            }
-            0x02 => {
-                self.start_keepalive().await;
-                SessionState::Programming
-            }
-            0x03 => {
-                self.start_keepalive().await;
-                SessionState::Extended
-            }
-            _ => {
-                // Custom sessions (like 0x60) treated as engineering
-                self.start_keepalive().await;
-                SessionState::Engineering { security_level: 0 }
-            }
+        let new_state = if session_id == self.config.default_session {
+            self.stop_keepalive().await;
+            SessionState::Default
+        } else if session_id == self.config.programming_session {
+            self.start_keepalive().await;
+            SessionState::Programming
+        } else if session_id == self.config.extended_session {
+            self.start_keepalive().await;
+            SessionState::Extended
+        } else {
+            // Engineering and custom sessions (like 0x40) are tracked as engineering
+            self.start_keepalive().await;
+            SessionState::Engineering { security_level: 0 }
         };
 
         *self.current_state.write() = new_state;
@@ -319,6 +550,7 @@ impl SessionManager {
             .map_err(|e| {
                 SessionError::TransitionFailed(format!("Session 0x{:02X}: {}", session, e))
             })?;
+        self.session_epoch.fetch_add(1, Ordering::SeqCst);
         Ok(())
     }
 
@@ -330,8 +562,10 @@ impl SessionManager {
         self.stop_keepalive().await;
 
         let transport = self.transport.clone();
+        let uds = self.uds.clone();
         let interval = Duration::from_millis(self.config.keepalive.interval_ms);
         let suppress_response = self.config.keepalive.suppress_response;
+        let session_epoch = self.session_epoch.clone();
 
         let handle = tokio::spawn(async move {
             let mut ticker = tokio::time::interval(interval);
@@ -344,9 +578,18 @@ impl SessionManager {
             loop {
                 ticker.tick().await;
 
+                // The transport is half-duplex: skip a beat rather than
+                // interleave with a request awaiting its response. The ECU
+                // restarts S3 on that response, so the session stays alive.
+                let Some(_idle) = uds.try_idle() else {
+                    debug!("Request in flight, tester present skipped");
+                    continue;
+                };
+
                 if suppress_response {
                     if let Err(e) = transport.send(&request).await {
                         error!(?e, "Tester present send failed");
+                        session_epoch.fetch_add(1, Ordering::SeqCst);
                     }
                 } else {
                     match transport
@@ -358,6 +601,7 @@ impl SessionManager {
                         }
                         Err(e) => {
                             error!(?e, "Tester present failed");
+                            session_epoch.fetch_add(1, Ordering::SeqCst);
                         }
                     }
                 }
@@ -381,6 +625,7 @@ impl SessionManager {
         self.stop_keepalive().await;
         *self.current_state.write() = SessionState::Default;
         *self.security_state.write() = SecurityAccessState::default();
+        self.session_epoch.fetch_add(1, Ordering::SeqCst);
         info!("Session state reset to default (ECU reset detected)");
     }
 
@@ -401,6 +646,24 @@ impl Drop for SessionManager {
     }
 }
 
+/// Transport-level failures worth retrying a seed request on
+fn is_transient(err: &UdsError) -> bool {
+    matches!(err, UdsError::Transport(_) | UdsError::Timeout)
+}
+
+/// NRCs with which the ECU refuses further security access for a while:
+/// exceededNumberOfAttempts (0x36) and requiredTimeDelayNotExpired (0x37)
+fn is_lockout_nrc(err: &UdsError) -> bool {
+    matches!(
+        err,
+        UdsError::NegativeResponse {
+            nrc: NegativeResponseCode::ExceededNumberOfAttempts
+                | NegativeResponseCode::RequiredTimeDelayNotExpired,
+            ..
+        }
+    )
+}
+
 /// Session management errors
 #[derive(Debug, thiserror::Error)]
 pub enum SessionError {
@@ -409,4 +672,375 @@ pub enum SessionError {
 
     #[error("Security access failed: {0}")]
     SecurityAccessFailed(String),
+
+    #[error("Security access locked out, retry in {remaining_ms} ms")]
+    SecurityLockout { remaining_ms: u64 },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use parking_lot::Mutex as SyncMutex;
+    use tokio::sync::broadcast;
+
+    use super::*;
+    use crate::config::{KeepaliveConfig, SecurityHandshakeConfig, SeedKeyCaptureConfig};
+    use crate::transport::{AddressInfo, IncomingMessage, TransportError};
+
+    /// Answers requests strictly in script order and records what was sent.
+    struct ScriptedTransport {
+        script: SyncMutex<VecDeque<Result<Vec<u8>, TransportError>>>,
+        sent: SyncMutex<Vec<Vec<u8>>>,
+        incoming_tx: broadcast::Sender<IncomingMessage>,
+        /// Delay before each scripted answer
+        latency: Duration,
+    }
+
+    impl ScriptedTransport {
+        fn new(script: Vec<Result<Vec<u8>, TransportError>>) -> Arc<Self> {
+            Self::with_latency(script, Duration::ZERO)
+        }
+
+        fn with_latency(
+            script: Vec<Result<Vec<u8>, TransportError>>,
+            latency: Duration,
+        ) -> Arc<Self> {
+            let (incoming_tx, _) = broadcast::channel(1);
+            Arc::new(Self {
+                script: SyncMutex::new(script.into()),
+                sent: SyncMutex::new(Vec::new()),
+                incoming_tx,
+                latency,
+            })
+        }
+
+        fn sent(&self) -> Vec<Vec<u8>> {
+            self.sent.lock().clone()
+        }
+    }
+
+    #[async_trait]
+    impl TransportAdapter for ScriptedTransport {
+        async fn send_receive(
+            &self,
+            request: &[u8],
+            _timeout: Duration,
+        ) -> Result<Vec<u8>, TransportError> {
+            self.sent.lock().push(request.to_vec());
+            tokio::time::sleep(self.latency).await;
+            self.script
+                .lock()
+                .pop_front()
+                .unwrap_or_else(|| Err(TransportError::ReceiveFailed("script exhausted".into())))
+        }
+
+        async fn send(&self, request: &[u8]) -> Result<(), TransportError> {
+            self.sent.lock().push(request.to_vec());
+            Ok(())
+        }
+
+        fn subscribe(&self) -> broadcast::Receiver<IncomingMessage> {
+            self.incoming_tx.subscribe()
+        }
+
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn reconnect(&self) -> Result<(), TransportError> {
+            Ok(())
+        }
+
+        fn address_info(&self) -> AddressInfo {
+            AddressInfo::default()
+        }
+    }
+
+    fn manager(
+        transport: Arc<ScriptedTransport>,
+        handshake: SecurityHandshakeConfig,
+    ) -> SessionManager {
+        let config = SessionConfig {
+            security_handshake: handshake,
+            ..Default::default()
+        };
+        SessionManager::new(transport, config)
+    }
+
+    fn invert(seed: &[u8]) -> Result<Vec<u8>, String> {
+        Ok(seed.iter().map(|b| !b).collect())
+    }
+
+    #[tokio::test]
+    async fn seed_request_retries_after_timeout() {
+        let transport = ScriptedTransport::new(vec![
+            Err(TransportError::Timeout("no seed response".into())),
+            Ok(vec![0x67, 0x01, 0xAA, 0xBB]),
+        ]);
+        let sm = manager(
+            transport.clone(),
+            SecurityHandshakeConfig {
+                retry_delay_ms: 0,
+                ..Default::default()
+            },
+        );
+
+        let seed = sm.request_security_seed(1).await.unwrap();
+        assert_eq!(seed, vec![0xAA, 0xBB]);
+        assert_eq!(transport.sent(), vec![vec![0x27, 0x01], vec![0x27, 0x01]]);
+        assert_eq!(sm.security_state().pending_seed, Some(vec![0xAA, 0xBB]));
+    }
+
+    #[tokio::test]
+    async fn seed_request_gives_up_after_configured_retries() {
+        let transport = ScriptedTransport::new(vec![
+            Err(TransportError::Timeout("1".into())),
+            Err(TransportError::Timeout("2".into())),
+        ]);
+        let sm = manager(
+            transport.clone(),
+            SecurityHandshakeConfig {
+                seed_retries: 1,
+                retry_delay_ms: 0,
+                ..Default::default()
+            },
+        );
+
+        assert!(sm.request_security_seed(1).await.is_err());
+        assert_eq!(transport.sent().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn stale_seed_is_refreshed_before_sending_key() {
+        let transport = ScriptedTransport::new(vec![
+            Ok(vec![0x67, 0x01, 0x11, 0x22]),
+            Ok(vec![0x67, 0x01, 0x33, 0x44]),
+            Ok(vec![0x67, 0x02]),
+        ]);
+        let sm = manager(
+            transport.clone(),
+            SecurityHandshakeConfig {
+                seed_validity_ms: 20,
+                ..Default::default()
+            },
+        );
+
+        // The first key computation is slow enough to outlive the seed.
+        let calls = AtomicUsize::new(0);
+        sm.unlock_with(1, |seed| {
+            if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                std::thread::sleep(Duration::from_millis(40));
+            }
+            invert(seed)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            transport.sent(),
+            vec![
+                vec![0x27, 0x01],
+                vec![0x27, 0x01],
+                // Key derived from the fresh seed, not the stale one.
+                vec![0x27, 0x02, 0xCC, 0xBB],
+            ]
+        );
+        assert!(sm.security_state().unlocked);
+    }
+
+    #[tokio::test]
+    async fn client_key_for_expired_seed_is_rejected_locally() {
+        let transport = ScriptedTransport::new(vec![Ok(vec![0x67, 0x01, 0x11, 0x22])]);
+        let sm = manager(
+            transport.clone(),
+            SecurityHandshakeConfig {
+                seed_validity_ms: 10,
+                ..Default::default()
+            },
+        );
+
+        sm.request_security_seed(1).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        assert!(sm.send_security_key(1, &[0xEE, 0xDD]).await.is_err());
+        // Nothing was sent for the stale key, and the seed is discarded.
+        assert_eq!(transport.sent().len(), 1);
+        assert_eq!(sm.security_state().pending_seed, None);
+    }
+
+    #[tokio::test]
+    async fn wrong_key_nrc_is_not_retried() {
+        let transport = ScriptedTransport::new(vec![
+            Ok(vec![0x67, 0x01, 0xAA, 0xBB]),
+            // invalidKey
+            Ok(vec![0x7F, 0x27, 0x35]),
+        ]);
+        let sm = manager(
+            transport.clone(),
+            SecurityHandshakeConfig {
+                retry_delay_ms: 0,
+                ..Default::default()
+            },
+        );
+
+        assert!(sm.unlock_with(1, invert).await.is_err());
+        assert_eq!(transport.sent().len(), 2);
+        assert!(!sm.security_state().unlocked);
+    }
+
+    /// Fresh path in the temp dir for a capture file
+    fn capture_path() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("seed-key-{}.jsonl", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn key_capture_records_seed_and_sent_key() {
+        let transport =
+            ScriptedTransport::new(vec![Ok(vec![0x67, 0x01, 0xAA, 0xBB]), Ok(vec![0x67, 0x02])]);
+        let path = capture_path();
+        let sm = manager(
+            transport,
+            SecurityHandshakeConfig {
+                key_capture: Some(SeedKeyCaptureConfig {
+                    path: path.clone(),
+                    unsafe_capture_keys: true,
+                }),
+                ..Default::default()
+            },
+        );
+
+        sm.unlock_with(1, invert).await.unwrap();
+
+        let dump = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<serde_json::Value> = dump
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 1, "{dump}");
+        assert_eq!(lines[0]["level"], 1);
+        assert_eq!(lines[0]["seed"], "aabb");
+        assert_eq!(lines[0]["key"], "5544");
+        assert_eq!(lines[0]["accepted"], true);
+    }
+
+    #[tokio::test]
+    async fn key_capture_needs_the_unsafe_flag() {
+        let transport =
+            ScriptedTransport::new(vec![Ok(vec![0x67, 0x01, 0xAA, 0xBB]), Ok(vec![0x67, 0x02])]);
+        let path = capture_path();
+        let sm = manager(
+            transport,
+            SecurityHandshakeConfig {
+                key_capture: Some(SeedKeyCaptureConfig {
+                    path: path.clone(),
+                    unsafe_capture_keys: false,
+                }),
+                ..Default::default()
+            },
+        );
+
+        sm.unlock_with(1, invert).await.unwrap();
+
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn keepalive_pauses_while_request_in_flight() {
+        let transport = ScriptedTransport::with_latency(
+            vec![Ok(vec![0x50, 0x03]), Ok(vec![0x62, 0xF1, 0x90, 0x01])],
+            Duration::from_millis(200),
+        );
+        let config = SessionConfig {
+            keepalive: KeepaliveConfig {
+                enabled: true,
+                interval_ms: 20,
+                suppress_response: true,
+            },
+            ..Default::default()
+        };
+        let sm = SessionManager::new(transport.clone(), config);
+        let tester_present = || transport.sent().iter().filter(|r| r[0] == 0x3E).count();
+
+        sm.change_session(0x03).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        assert!(
+            tester_present() > 0,
+            "keepalive runs in the extended session"
+        );
+
+        let uds = sm.uds.clone();
+        let read = tokio::spawn(async move { uds.read_data_by_id(&[0xF190]).await });
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let before = tester_present();
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert_eq!(
+            tester_present(),
+            before,
+            "no 0x3E while 0x22 awaits its response"
+        );
+
+        read.await.unwrap().unwrap();
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        assert!(tester_present() > before, "keepalive resumes once idle");
+    }
+
+    #[tokio::test]
+    async fn invalid_keys_lock_out_seed_requests() {
+        let transport = ScriptedTransport::new(vec![
+            Ok(vec![0x67, 0x01, 0xAA, 0xBB]),
+            Ok(vec![0x7F, 0x27, 0x35]),
+            Ok(vec![0x67, 0x01, 0xAA, 0xBB]),
+            Ok(vec![0x7F, 0x27, 0x35]),
+            Ok(vec![0x67, 0x01, 0xCC, 0xDD]),
+        ]);
+        let sm = manager(
+            transport.clone(),
+            SecurityHandshakeConfig {
+                max_attempts: 2,
+                lockout_ms: 50,
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(
+            sm.unlock_with(1, invert).await,
+            Err(SessionError::SecurityAccessFailed(_))
+        ));
+        assert!(matches!(
+            sm.unlock_with(1, invert).await,
+            Err(SessionError::SecurityLockout { remaining_ms: 50 })
+        ));
+
+        // Refused locally while locked out
+        match sm.request_security_seed(1).await {
+            Err(SessionError::SecurityLockout { remaining_ms }) => assert!(remaining_ms <= 50),
+            other => panic!("expected lockout, got {:?}", other),
+        }
+        assert_eq!(transport.sent().len(), 4);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(sm.security_lockout_remaining().is_none());
+        assert_eq!(sm.request_security_seed(1).await.unwrap(), vec![0xCC, 0xDD]);
+    }
+
+    #[tokio::test]
+    async fn ecu_time_delay_nrc_starts_lockout() {
+        // requiredTimeDelayNotExpired
+        let transport = ScriptedTransport::new(vec![Ok(vec![0x7F, 0x27, 0x37])]);
+        let sm = manager(transport.clone(), SecurityHandshakeConfig::default());
+
+        assert!(matches!(
+            sm.request_security_seed(1).await,
+            Err(SessionError::SecurityLockout {
+                remaining_ms: 10_000
+            })
+        ));
+        assert!(sm.security_lockout_remaining().is_some());
+        // Not retried like a transport error
+        assert_eq!(transport.sent().len(), 1);
+    }
 }
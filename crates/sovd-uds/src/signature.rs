@@ -0,0 +1,239 @@
+//! Firmware package signature verification.
+//!
+//! With `[ecu.*.flash.signature]` configured, a package is only verified
+//! (and so only ever flashed) when it carries a valid signature from the
+//! configured public key. The signature travels as a trailer behind the
+//! image:
+//!
+//! ```text
+//! ┌──────────────────────────────┐
+//! │  Image (variable)            │  the bytes that are signed and flashed
+//! ├──────────────────────────────┤
+//! │  Signature (variable)        │
+//! │  Signature length (u16 BE)   │
+//! │  Trailer magic (8 bytes)     │  [`SIGNATURE_MAGIC`]
+//! └──────────────────────────────┘
+//! ```
+//!
+//! The trailer is stripped once the signature verifies, so the ECU receives
+//! the image exactly as it was signed. Construction from config is a match
+//! on an algorithm string ([`PackageVerifier::from_config`]), as for the
+//! unlock providers.
+
+use ed25519_dalek::pkcs8::DecodePublicKey as _;
+use rsa::pkcs8::DecodePublicKey as _;
+use rsa::signature::Verifier as _;
+
+use crate::config::SignatureConfig;
+
+/// Algorithm identifier for Ed25519 signatures.
+pub const ALGORITHM_ED25519: &str = "ed25519";
+
+/// Algorithm identifier for RSASSA-PKCS1-v1_5 signatures over SHA-256.
+pub const ALGORITHM_RSA_PKCS1_SHA256: &str = "rsa-pkcs1-sha256";
+
+/// Magic closing a signed package.
+pub const SIGNATURE_MAGIC: &[u8] = b"SOVDSIG\0";
+
+/// Signature length (2) + trailer magic.
+const TRAILER_FIXED_SIZE: usize = 2 + SIGNATURE_MAGIC.len();
+
+/// Error raised while building a [`PackageVerifier`] or verifying a package.
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureError {
+    /// The package has no signature trailer.
+    #[error("package is not signed")]
+    Unsigned,
+
+    /// The trailer's signature length runs past the start of the package.
+    #[error("malformed signature trailer")]
+    MalformedTrailer,
+
+    /// The signature does not verify over the image: the package was
+    /// tampered with or signed by another key.
+    #[error("signature does not match the package")]
+    Mismatch,
+
+    /// The configured `public_key_pem` could not be parsed for the algorithm.
+    #[error("invalid {algorithm} public key: {reason}")]
+    InvalidKey {
+        algorithm: &'static str,
+        reason: String,
+    },
+
+    /// The configured `algorithm` string is not recognised.
+    #[error("unknown signature algorithm: {0}")]
+    UnknownAlgorithm(String),
+}
+
+/// Public key a package signature is checked against.
+#[derive(Debug, Clone)]
+pub enum PackageVerifier {
+    /// Ed25519
+    Ed25519(ed25519_dalek::VerifyingKey),
+    /// RSASSA-PKCS1-v1_5 with SHA-256
+    RsaPkcs1Sha256(rsa::pkcs1v15::VerifyingKey<rsa::sha2::Sha256>),
+}
+
+impl PackageVerifier {
+    /// Build a verifier from a [`SignatureConfig`]. The `algorithm` string
+    /// selects the key type; this match is the single place new algorithms
+    /// are wired in.
+    pub fn from_config(config: &SignatureConfig) -> Result<Self, SignatureError> {
+        match config.algorithm.as_str() {
+            ALGORITHM_ED25519 => {
+                ed25519_dalek::VerifyingKey::from_public_key_pem(&config.public_key_pem)
+                    .map(Self::Ed25519)
+                    .map_err(|e| SignatureError::InvalidKey {
+                        algorithm: ALGORITHM_ED25519,
+                        reason: e.to_string(),
+                    })
+            }
+            ALGORITHM_RSA_PKCS1_SHA256 => {
+                rsa::RsaPublicKey::from_public_key_pem(&config.public_key_pem)
+                    .map(|key| Self::RsaPkcs1Sha256(rsa::pkcs1v15::VerifyingKey::new(key)))
+                    .map_err(|e| SignatureError::InvalidKey {
+                        algorithm: ALGORITHM_RSA_PKCS1_SHA256,
+                        reason: e.to_string(),
+                    })
+            }
+            other => Err(SignatureError::UnknownAlgorithm(other.to_string())),
+        }
+    }
+
+    /// Verify a signed package and return the image inside it.
+    pub fn verify<'a>(&self, package: &'a [u8]) -> Result<&'a [u8], SignatureError> {
+        let (image, signature) = split_signed(package)?;
+        let verified = match self {
+            Self::Ed25519(key) => ed25519_dalek::Signature::from_slice(signature)
+                .is_ok_and(|signature| key.verify_strict(image, &signature).is_ok()),
+            Self::RsaPkcs1Sha256(key) => rsa::pkcs1v15::Signature::try_from(signature)
+                .is_ok_and(|signature| key.verify(image, &signature).is_ok()),
+        };
+        if verified {
+            Ok(image)
+        } else {
+            Err(SignatureError::Mismatch)
+        }
+    }
+}
+
+/// Split a signed package into its image and signature.
+pub fn split_signed(package: &[u8]) -> Result<(&[u8], &[u8]), SignatureError> {
+    if !package.ends_with(SIGNATURE_MAGIC) || package.len() < TRAILER_FIXED_SIZE {
+        return Err(SignatureError::Unsigned);
+    }
+    let len_offset = package.len() - TRAILER_FIXED_SIZE;
+    let signature_len = u16::from_be_bytes([package[len_offset], package[len_offset + 1]]) as usize;
+    let image_len = len_offset
+        .checked_sub(signature_len)
+        .ok_or(SignatureError::MalformedTrailer)?;
+    Ok((&package[..image_len], &package[image_len..len_offset]))
+}
+
+/// Append a signature trailer to `image` (for packaging tools and tests).
+pub fn append_signature(image: &[u8], signature: &[u8]) -> Vec<u8> {
+    let mut package = Vec::with_capacity(image.len() + signature.len() + TRAILER_FIXED_SIZE);
+    package.extend_from_slice(image);
+    package.extend_from_slice(signature);
+    package.extend_from_slice(&(signature.len() as u16).to_be_bytes());
+    package.extend_from_slice(SIGNATURE_MAGIC);
+    package
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::pkcs8::{EncodePublicKey, LineEnding};
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7; 32])
+    }
+
+    fn ed25519_config(key: &SigningKey) -> SignatureConfig {
+        SignatureConfig {
+            algorithm: ALGORITHM_ED25519.to_string(),
+            public_key_pem: key
+                .verifying_key()
+                .to_public_key_pem(LineEnding::LF)
+                .unwrap(),
+        }
+    }
+
+    fn signed(key: &SigningKey, image: &[u8]) -> Vec<u8> {
+        append_signature(image, &key.sign(image).to_bytes())
+    }
+
+    #[test]
+    fn signed_package_yields_the_image() {
+        let key = signing_key();
+        let verifier = PackageVerifier::from_config(&ed25519_config(&key)).unwrap();
+
+        let package = signed(&key, b"firmware image");
+
+        assert_eq!(verifier.verify(&package).unwrap(), b"firmware image");
+    }
+
+    #[test]
+    fn tampered_package_is_rejected() {
+        let key = signing_key();
+        let verifier = PackageVerifier::from_config(&ed25519_config(&key)).unwrap();
+        let mut package = signed(&key, b"firmware image");
+        package[0] ^= 0xFF;
+
+        assert!(matches!(
+            verifier.verify(&package),
+            Err(SignatureError::Mismatch)
+        ));
+    }
+
+    #[test]
+    fn other_key_is_rejected() {
+        let verifier = PackageVerifier::from_config(&ed25519_config(&signing_key())).unwrap();
+
+        let package = signed(&SigningKey::from_bytes(&[9; 32]), b"firmware image");
+
+        assert!(matches!(
+            verifier.verify(&package),
+            Err(SignatureError::Mismatch)
+        ));
+    }
+
+    #[test]
+    fn unsigned_and_malformed_packages() {
+        let verifier = PackageVerifier::from_config(&ed25519_config(&signing_key())).unwrap();
+
+        assert!(matches!(
+            verifier.verify(b"firmware image"),
+            Err(SignatureError::Unsigned)
+        ));
+        let mut package = append_signature(b"", &[0; 4]);
+        package[4] = 0xFF; // length high byte: runs past the start
+        assert!(matches!(
+            verifier.verify(&package),
+            Err(SignatureError::MalformedTrailer)
+        ));
+    }
+
+    #[test]
+    fn bad_config_is_rejected() {
+        let unknown = SignatureConfig {
+            algorithm: "md5".to_string(),
+            public_key_pem: String::new(),
+        };
+        assert!(matches!(
+            PackageVerifier::from_config(&unknown),
+            Err(SignatureError::UnknownAlgorithm(_))
+        ));
+
+        let garbage = SignatureConfig {
+            algorithm: ALGORITHM_RSA_PKCS1_SHA256.to_string(),
+            public_key_pem: "not a key".to_string(),
+        };
+        assert!(matches!(
+            PackageVerifier::from_config(&garbage),
+            Err(SignatureError::InvalidKey { .. })
+        ));
+    }
+}
@@ -2,22 +2,39 @@
 //!
 //! Handles UDS 0x2A ReadDataByPeriodicIdentifier for efficient streaming.
 //! Returns raw DID data - conversions are applied at the API layer.
+//!
+//! In the default periodic mode each rate group is handed to the ECU as one
+//! 0x2A request and the ECU schedules the transmissions itself.
+//!
+//! With `[subscriptions] mode = "event"` the ECU samples instead: every
+//! subscribed DID is registered as a ResponseOnEvent (0x86) event whose
+//! response is a ReadDataByIdentifier of that DID, and the unsolicited
+//! `0x62` responses are streamed like periodic data.
+//!
+//! With `mode = "poll"` the tester samples: one task per subscribed DID
+//! reads it (0x22) at its rate. Those ticks are ours to place, so
+//! `max_jitter_ms` spreads same-rate polls over the period instead of
+//! sending them as one burst; see [`poll_delay`].
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::Utc;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use rand::Rng;
 use sovd_core::DataPoint;
 use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use crate::config::UdsBackendConfig;
+use crate::config::{RoeEvent, SubscriptionMode, UdsBackendConfig};
 use crate::transport::{IncomingMessage, TransportAdapter};
-use crate::uds::{PeriodicRate, ServiceIds, UdsService};
+use crate::uds::{
+    roe_event_type, roe_event_window, PeriodicRate, RequestQueue, ServiceIds, UdsError, UdsService,
+};
 
 /// Parse a hex DID string to u16
 fn parse_did(did_str: &str) -> Option<u16> {
@@ -34,10 +51,9 @@ pub struct StreamSubscription {
     pub rate_hz: u32,
 }
 
-/// Manages streaming subscriptions using UDS 0x2A
+/// Manages streaming subscriptions using UDS 0x2A (or 0x86 in event mode)
 pub struct StreamManager {
     transport: Arc<dyn TransportAdapter>,
-    #[allow(dead_code)]
     config: UdsBackendConfig,
     uds: UdsService,
 
@@ -47,14 +63,23 @@ pub struct StreamManager {
     /// Broadcast channel for each subscription
     streams: Arc<RwLock<HashMap<String, broadcast::Sender<DataPoint>>>>,
 
-    /// Current periodic configuration (merged from all subscriptions)
+    /// Current periodic (or event) configuration, merged from all subscriptions
     active_periodic: RwLock<ActivePeriodicConfig>,
 
+    /// DIDs one-shot periodic reads are waiting on, with how many
+    one_shots: Mutex<HashMap<u16, usize>>,
+
+    /// Held while the ECU's periodic schedule is being changed
+    periodic_changes: tokio::sync::Mutex<()>,
+
     /// Sequence counter for data points
     sequence: Arc<AtomicU64>,
 
     /// Background listener task handle
     listener_handle: RwLock<Option<JoinHandle<()>>>,
+
+    /// One task per polled DID (poll mode)
+    pollers: RwLock<Vec<JoinHandle<()>>>,
 }
 
 struct SubscriptionState {
@@ -69,10 +94,20 @@ struct ActivePeriodicConfig {
 }
 
 impl StreamManager {
-    pub fn new(transport: Arc<dyn TransportAdapter>, config: UdsBackendConfig) -> Self {
+    /// Stream manager whose 0x2A/0x86 requests take turns on `queue` with
+    /// the backend's own
+    pub fn new(
+        transport: Arc<dyn TransportAdapter>,
+        config: UdsBackendConfig,
+        queue: Arc<RequestQueue>,
+    ) -> Self {
         // Create UDS service with configured service IDs (for OEM variants like Vortex Motors)
         let service_ids = ServiceIds::from_overrides(&config.service_overrides);
-        let uds = UdsService::with_service_ids(transport.clone(), service_ids);
+        let uds = UdsService::with_service_ids(transport.clone(), service_ids)
+            .with_request_queue(queue)
+            .with_service_policy(config.service_policy.clone())
+            .with_response_pending(config.response_pending.clone())
+            .with_first_frame_retry(config.first_frame_retry.clone());
 
         let manager = Self {
             transport,
@@ -81,8 +116,11 @@ impl StreamManager {
             subscriptions: Arc::new(RwLock::new(HashMap::new())),
             streams: Arc::new(RwLock::new(HashMap::new())),
             active_periodic: RwLock::new(ActivePeriodicConfig::default()),
+            one_shots: Mutex::new(HashMap::new()),
+            periodic_changes: tokio::sync::Mutex::new(()),
             sequence: Arc::new(AtomicU64::new(0)),
             listener_handle: RwLock::new(None),
+            pollers: RwLock::new(Vec::new()),
         };
 
         // Start the incoming message listener
@@ -129,7 +167,7 @@ impl StreamManager {
         }
 
         // Reconfigure ECU periodic
-        if let Err(e) = self.reconfigure_periodic().await {
+        if let Err(e) = self.reconfigure().await {
             warn!(?e, "Failed to configure ECU periodic");
             // Clean up on failure
             self.subscriptions.write().remove(&id);
@@ -150,7 +188,7 @@ impl StreamManager {
         }
 
         // Reconfigure ECU if needed
-        self.reconfigure_periodic().await?;
+        self.reconfigure().await?;
 
         info!(subscription_id = %id, "Stream subscription removed");
         Ok(())
@@ -161,9 +199,149 @@ impl StreamManager {
         self.streams.read().get(id).map(|tx| tx.subscribe())
     }
 
+    /// Whether any subscription is active
+    pub fn has_subscriptions(&self) -> bool {
+        !self.subscriptions.read().is_empty()
+    }
+
+    /// Have the ECU send `did` for a one-shot periodic read
+    /// (`read_via: periodic`)
+    ///
+    /// Nothing is sent when a subscription or another one-shot read already
+    /// has the DID transmitted. Pair with [`Self::stop_one_shot`].
+    pub async fn start_one_shot(&self, did: u16) -> Result<(), UdsError> {
+        let _changes = self.periodic_changes.lock().await;
+        if !self.is_transmitted(did) {
+            let pid = (did & 0xFF) as u8;
+            self.uds.start_periodic(PeriodicRate::Slow, &[pid]).await?;
+        }
+        *self.one_shots.lock().entry(did).or_default() += 1;
+        Ok(())
+    }
+
+    /// End a one-shot periodic read of `did`, stopping the transmission
+    /// unless a subscription or another one-shot read still needs it
+    pub async fn stop_one_shot(&self, did: u16) -> Result<(), UdsError> {
+        let _changes = self.periodic_changes.lock().await;
+        {
+            let mut one_shots = self.one_shots.lock();
+            match one_shots.get_mut(&did) {
+                Some(count) if *count > 1 => *count -= 1,
+                _ => {
+                    one_shots.remove(&did);
+                }
+            }
+        }
+        if self.is_transmitted(did) {
+            return Ok(());
+        }
+        let pid = (did & 0xFF) as u8;
+        self.uds.stop_periodic(&[pid]).await
+    }
+
+    /// Whether `did` is on the ECU's periodic schedule for a subscription or
+    /// a one-shot read
+    fn is_transmitted(&self, did: u16) -> bool {
+        self.active_periodic.read().active_dids.contains(&did)
+            || self.one_shots.lock().contains_key(&did)
+    }
+
+    /// Forget the ECU's periodic schedule after it was lost (e.g. on reset),
+    /// so the next reconfiguration doesn't try to stop it
+    pub fn suspend(&self) {
+        *self.active_periodic.write() = ActivePeriodicConfig::default();
+        self.stop_polls();
+    }
+
+    /// Re-arm the periodic schedule for every active subscription and put a
+    /// [`DataPoint::resumed`] marker on each stream
+    pub async fn resume(&self) -> Result<(), StreamError> {
+        self.reconfigure().await?;
+
+        for tx in self.streams.read().values() {
+            let _ = tx.send(DataPoint::resumed());
+        }
+        info!("Stream subscriptions resumed");
+        Ok(())
+    }
+
+    /// Reconfigure the ECU for all active subscriptions in the configured mode
+    async fn reconfigure(&self) -> Result<(), StreamError> {
+        match self.config.subscriptions.mode {
+            SubscriptionMode::Periodic => self.reconfigure_periodic().await,
+            SubscriptionMode::Event => self.reconfigure_events().await,
+            SubscriptionMode::Poll => {
+                self.reconfigure_polls();
+                Ok(())
+            }
+        }
+    }
+
+    /// Restart the poll tasks: one per subscribed DID, at the fastest rate
+    /// any subscription asks for it
+    fn reconfigure_polls(&self) {
+        let mut rates: HashMap<u16, u32> = HashMap::new();
+        {
+            let subs = self.subscriptions.read();
+            for state in subs.values() {
+                for &did in &state.did_set {
+                    let rate = rates.entry(did).or_default();
+                    *rate = (*rate).max(state.subscription.rate_hz);
+                }
+            }
+        }
+
+        self.stop_polls();
+        let max_jitter = Duration::from_millis(self.config.subscriptions.max_jitter_ms);
+        let mut pollers = self.pollers.write();
+        for (did, rate_hz) in rates {
+            let period = Duration::from_secs(1) / rate_hz.max(1);
+            let max_delay = max_jitter.min(period);
+            let uds = self.uds.clone();
+            let subscriptions = self.subscriptions.clone();
+            let streams = self.streams.clone();
+            let sequence = self.sequence.clone();
+            pollers.push(tokio::spawn(async move {
+                // Start of the current period; ticks stay on this grid
+                let mut slot = tokio::time::Instant::now();
+                loop {
+                    tokio::time::sleep_until(slot + poll_delay(max_delay)).await;
+                    slot += period;
+                    // A read that took longer than a period drops the missed
+                    // ticks instead of sending them back to back
+                    let now = tokio::time::Instant::now();
+                    if slot + period < now {
+                        slot = now;
+                    }
+
+                    let values = uds
+                        .read_data_by_id(&[did])
+                        .await
+                        .and_then(|response| UdsService::parse_read_response(&response));
+                    match values {
+                        Ok(values) => {
+                            for (did, data) in values {
+                                Self::publish(did, &data, &subscriptions, &streams, &sequence);
+                            }
+                        }
+                        Err(e) => debug!(?e, "Poll of DID 0x{:04X} failed", did),
+                    }
+                }
+            }));
+        }
+        debug!(dids = pollers.len(), "Started polls");
+    }
+
+    fn stop_polls(&self) {
+        for handle in self.pollers.write().drain(..) {
+            handle.abort();
+        }
+    }
+
     /// Reconfigure ECU periodic based on all active subscriptions
     async fn reconfigure_periodic(&self) -> Result<(), StreamError> {
         debug!("Reconfiguring ECU periodic");
+        let _changes = self.periodic_changes.lock().await;
 
         // Collect all DIDs needed, grouped by rate
         let mut rate_groups: HashMap<u32, HashSet<u16>> = HashMap::new();
@@ -177,12 +355,15 @@ impl StreamManager {
             }
         }
 
-        // Stop current periodic DIDs
+        // Stop current periodic DIDs, except those a one-shot read is still
+        // waiting on; its stop_one_shot ends them
         let active_dids_to_stop: Vec<u16> = {
+            let one_shots = self.one_shots.lock();
             self.active_periodic
                 .read()
                 .active_dids
                 .iter()
+                .filter(|did| !one_shots.contains_key(did))
                 .cloned()
                 .collect()
         };
@@ -224,18 +405,93 @@ impl StreamManager {
         Ok(())
     }
 
+    /// Re-register one ResponseOnEvent (0x86) event per subscribed DID
+    ///
+    /// The ECU keeps a single event list, so the old events are stopped and
+    /// cleared before the new set is set up and started.
+    async fn reconfigure_events(&self) -> Result<(), StreamError> {
+        debug!("Reconfiguring ECU events");
+
+        let dids: BTreeSet<u16> = {
+            let subs = self.subscriptions.read();
+            subs.values()
+                .flat_map(|state| state.did_set.iter().copied())
+                .collect()
+        };
+
+        if !self.active_periodic.read().active_dids.is_empty() {
+            if let Err(e) = self.uds.response_on_event_stop().await {
+                warn!(?e, "Failed to stop ECU events");
+            }
+            if let Err(e) = self.uds.response_on_event_clear().await {
+                warn!(?e, "Failed to clear ECU events");
+            }
+            *self.active_periodic.write() = ActivePeriodicConfig::default();
+        }
+
+        if dids.is_empty() {
+            return Ok(());
+        }
+
+        let events = &self.config.subscriptions;
+        let read_sid = self.uds.service_ids().read_data_by_id;
+        for &did in &dids {
+            let [hi, lo] = did.to_be_bytes();
+            // eventTypeRecord followed by serviceToRespondTo (0x22 of the DID)
+            let (event_type, record) = match events.event {
+                RoeEvent::ChangeOfDid => (
+                    roe_event_type::ON_CHANGE_OF_DATA_IDENTIFIER,
+                    vec![hi, lo, read_sid, hi, lo],
+                ),
+                RoeEvent::DtcStatusChange => (
+                    roe_event_type::ON_DTC_STATUS_CHANGE,
+                    vec![events.dtc_status_mask, read_sid, hi, lo],
+                ),
+            };
+            if let Err(e) = self
+                .uds
+                .response_on_event_setup(event_type, roe_event_window::INFINITE, &record)
+                .await
+            {
+                error!(?e, "Failed to set up event for DID 0x{:04X}", did);
+                return Err(StreamError::UdsError(e.to_string()));
+            }
+        }
+
+        if let Err(e) = self.uds.response_on_event_start().await {
+            error!(?e, "Failed to start ECU events");
+            return Err(StreamError::UdsError(e.to_string()));
+        }
+        debug!(dids = ?dids, "Started events");
+
+        *self.active_periodic.write() = ActivePeriodicConfig {
+            active_dids: dids.into_iter().collect(),
+        };
+
+        Ok(())
+    }
+
     /// Start the background listener for incoming ECU data
     fn start_listener(&self) {
         let mut incoming_rx = self.transport.subscribe();
         let subscriptions = self.subscriptions.clone();
         let streams = self.streams.clone();
         let sequence = self.sequence.clone();
+        // Positive response SID of the 0x22 the ECU sends when an event fires
+        let event_sid = (self.config.subscriptions.mode == SubscriptionMode::Event)
+            .then(|| self.uds.service_ids().read_data_by_id + 0x40);
 
         let handle = tokio::spawn(async move {
             loop {
                 match incoming_rx.recv().await {
                     Ok(msg) => {
-                        Self::handle_incoming_message(&msg, &subscriptions, &streams, &sequence);
+                        Self::handle_incoming_message(
+                            &msg,
+                            event_sid,
+                            &subscriptions,
+                            &streams,
+                            &sequence,
+                        );
                     }
                     Err(broadcast::error::RecvError::Lagged(n)) => {
                         warn!(skipped = n, "Incoming message listener lagged");
@@ -253,6 +509,7 @@ impl StreamManager {
 
     fn handle_incoming_message(
         msg: &IncomingMessage,
+        event_sid: Option<u8>,
         subscriptions: &RwLock<HashMap<String, SubscriptionState>>,
         streams: &RwLock<HashMap<String, broadcast::Sender<DataPoint>>>,
         sequence: &AtomicU64,
@@ -267,6 +524,16 @@ impl StreamManager {
 
         let first_byte = msg.data[0];
 
+        // Event response (0x86 → 0x62): [0x62] [DID_HI] [DID_LO] [DATA...]
+        if Some(first_byte) == event_sid {
+            if msg.data.len() < 3 {
+                return;
+            }
+            let did = u16::from_be_bytes([msg.data[1], msg.data[2]]);
+            Self::publish(did, &msg.data[3..], subscriptions, streams, sequence);
+            return;
+        }
+
         // Skip if this looks like a normal response (positive or negative)
         // Positive responses start with 0x40+ of the request SID
         // Negative responses start with 0x7F
@@ -304,6 +571,46 @@ impl StreamManager {
 
         let _ = sequence.fetch_add(1, Ordering::SeqCst);
     }
+
+    /// Send `data` of `did` to every subscription that includes the DID
+    fn publish(
+        did: u16,
+        data: &[u8],
+        subscriptions: &RwLock<HashMap<String, SubscriptionState>>,
+        streams: &RwLock<HashMap<String, broadcast::Sender<DataPoint>>>,
+        sequence: &AtomicU64,
+    ) {
+        let subs = subscriptions.read();
+        let streams_guard = streams.read();
+        for (sub_id, state) in subs.iter() {
+            if !state.did_set.contains(&did) {
+                continue;
+            }
+            let data_point = DataPoint {
+                id: format!("{:04X}", did),
+                value: serde_json::json!(hex::encode(data)),
+                unit: None,
+                timestamp: Utc::now(),
+            };
+            if let Some(tx) = streams_guard.get(sub_id) {
+                let _ = tx.send(data_point);
+            }
+        }
+
+        let _ = sequence.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Random delay of one poll within its period, uniform up to `max_delay`
+///
+/// Each tick is delayed from its slot on the rate's grid, never from the
+/// previous tick, so the delays do not add up: a DID is polled exactly once
+/// per period however they fall.
+fn poll_delay(max_delay: Duration) -> Duration {
+    if max_delay.is_zero() {
+        return Duration::ZERO;
+    }
+    rand::thread_rng().gen_range(Duration::ZERO..=max_delay)
 }
 
 impl Drop for StreamManager {
@@ -311,6 +618,7 @@ impl Drop for StreamManager {
         if let Some(handle) = self.listener_handle.write().take() {
             handle.abort();
         }
+        self.stop_polls();
     }
 }
 
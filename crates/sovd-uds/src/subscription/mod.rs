@@ -1,7 +1,8 @@
 //! Subscription management for UDS periodic data streaming
 //!
 //! This module provides real-time data streaming using UDS 0x2A
-//! (ReadDataByPeriodicIdentifier) for efficient ECU data collection.
+//! (ReadDataByPeriodicIdentifier) for efficient ECU data collection, or
+//! ResponseOnEvent (0x86) for ECUs that push change-driven data themselves.
 
 mod manager;
 
@@ -3,6 +3,7 @@
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use sovd_core::{TransportMetrics, TransportStats};
 use tokio::sync::broadcast;
 
 use super::TransportError;
@@ -47,6 +48,32 @@ pub trait TransportAdapter: Send + Sync {
         timeout: Duration,
     ) -> Result<Vec<u8>, TransportError>;
 
+    /// Send a UDS request and wait up to `timeout` for the response, giving
+    /// up with a timeout once `first_frame_timeout` passes without even its
+    /// first frame
+    ///
+    /// Only transports whose [`Self::bounds_first_frame`] is true tell the
+    /// first frame apart; the default waits for the whole response under
+    /// `timeout` alone.
+    async fn send_receive_first_frame(
+        &self,
+        request: &[u8],
+        first_frame_timeout: Duration,
+        timeout: Duration,
+    ) -> Result<Vec<u8>, TransportError> {
+        let _ = first_frame_timeout;
+        self.send_receive(request, timeout).await
+    }
+
+    /// Whether [`Self::send_receive_first_frame`] bounds the arrival of the
+    /// first frame on its own
+    ///
+    /// Transports handed only complete messages (a kernel ISO-TP socket, a
+    /// DoIP stream) cannot see a first frame and keep the default.
+    fn bounds_first_frame(&self) -> bool {
+        false
+    }
+
     /// Send a UDS request without waiting for a response
     ///
     /// Useful for tester present with suppress positive response,
@@ -67,4 +94,50 @@ pub trait TransportAdapter: Send + Sync {
 
     /// Get the current address configuration
     fn address_info(&self) -> AddressInfo;
+
+    /// Largest UDS message (in bytes) this transport can carry in one
+    /// request, or `None` if it has no practical limit
+    ///
+    /// Used to clamp the TransferData block length an ECU advertises in its
+    /// RequestDownload response.
+    fn max_message_len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Whether the response that follows a responsePending (NRC 0x78)
+    /// arrives on [`Self::subscribe`] without re-sending the request
+    ///
+    /// Live transports return `true`. Transports that only answer requests
+    /// (e.g. a replayed capture) keep the default and have the request
+    /// re-sent after each 0x78 instead.
+    fn delivers_pending_responses(&self) -> bool {
+        false
+    }
+
+    /// Link-level view of the transport (kind, CAN frame data length, ...)
+    fn stats(&self) -> TransportStats {
+        TransportStats {
+            kind: "custom".to_string(),
+            max_message_len: self.max_message_len(),
+            ..Default::default()
+        }
+    }
+
+    /// Switch the bus to `bitrate` bit/s, following the ECU through a
+    /// LinkControl (0x87) baud-rate transition
+    ///
+    /// Only buses whose bitrate the tester sets (CAN) support this; the
+    /// default refuses.
+    async fn set_bitrate(&self, bitrate: u32) -> Result<(), TransportError> {
+        Err(TransportError::Unsupported(format!(
+            "switching this transport to {} bit/s",
+            bitrate
+        )))
+    }
+
+    /// Traffic counters, when the transport keeps them (see
+    /// [`MeteredTransport`](super::MeteredTransport))
+    fn metrics(&self) -> Option<TransportMetrics> {
+        None
+    }
 }
@@ -1,6 +1,7 @@
 //! DoIP Transport Adapter Implementation
 //!
-//! Supports TLS auto-negotiation, multi-ECU, keep-alive, and auto-reconnect.
+//! Supports TLS auto-negotiation, multi-ECU, keep-alive, idle close, and
+//! auto-reconnect.
 
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -13,8 +14,9 @@ use doip_definitions::payload::{
     DoipPayload, RoutingActivationRequest,
 };
 use doip_sockets::tcp::{DoIpSslStream, TcpStream as DoIpTcpStream};
+use parking_lot::Mutex as SyncMutex;
 use tokio::sync::{broadcast, Mutex, RwLock};
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
 use crate::config::DoIpConfig;
 use crate::transport::{AddressInfo, IncomingMessage, TransportAdapter, TransportError};
@@ -62,6 +64,11 @@ pub struct DoIpAdapter {
     address_info: AddressInfo,
     receiver_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
     keepalive_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    idle_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    /// Last diagnostic frame sent or received
+    last_activity: Arc<SyncMutex<Instant>>,
+    /// Set when the connection was closed by `idle_close_ms` rather than lost
+    idle_closed: Arc<AtomicBool>,
 }
 
 impl DoIpAdapter {
@@ -88,6 +95,9 @@ impl DoIpAdapter {
             address_info,
             receiver_handle: Mutex::new(None),
             keepalive_handle: Mutex::new(None),
+            idle_handle: Mutex::new(None),
+            last_activity: Arc::new(SyncMutex::new(Instant::now())),
+            idle_closed: Arc::new(AtomicBool::new(false)),
         };
 
         adapter.connect_with_retry(MAX_RECONNECT_ATTEMPTS).await?;
@@ -110,6 +120,8 @@ impl DoIpAdapter {
         for attempt in 1..=max_attempts {
             match self.connect().await {
                 Ok(()) => return Ok(()),
+                // The gateway's answer won't change on a retry
+                Err(e @ TransportError::RoutingActivationDenied { .. }) => return Err(e),
                 Err(e) => {
                     warn!(attempt, max_attempts, %e, "Connection failed");
                     last_error = e;
@@ -148,11 +160,16 @@ impl DoIpAdapter {
         }
 
         self.connected.store(true, Ordering::SeqCst);
+        self.idle_closed.store(false, Ordering::SeqCst);
+        *self.last_activity.lock() = Instant::now();
         self.start_receiver().await;
 
         if self.config.keepalive_interval_secs > 0 {
             self.start_keepalive().await;
         }
+        if self.config.idle_close_ms > 0 {
+            self.start_idle_close().await;
+        }
 
         info!(tls = self.use_tls.load(Ordering::SeqCst), "DoIP connected");
         Ok(())
@@ -176,14 +193,21 @@ impl DoIpAdapter {
     /// Perform routing activation handshake
     async fn routing_activation(&self) -> Result<(), TransportError> {
         let activation_type = match self.config.activation_type {
+            0x00 => ActivationType::Default,
             0x01 => ActivationType::WwhObd,
-            _ => ActivationType::Default,
+            0xE0 => ActivationType::CentralSecurity,
+            other => {
+                return Err(TransportError::InvalidConfig(format!(
+                    "unsupported routing activation type 0x{:02X}",
+                    other
+                )))
+            }
         };
 
         let payload = DoipPayload::RoutingActivationRequest(RoutingActivationRequest {
             source_address: self.config.source_address.to_be_bytes(),
             activation_type,
-            buffer: [0; 4],
+            buffer: self.config.oem_specific.unwrap_or([0; 4]),
         });
 
         let mut guard = self.connection.lock().await;
@@ -204,7 +228,10 @@ impl DoIpAdapter {
                 ActivationCode::DeniedRequestEncryptedTLSConnection => {
                     Err(TransportError::TlsRequired)
                 }
-                code => Err(TransportError::ConnectionFailed(format!("{:?}", code))),
+                code => {
+                    warn!(?code, "Routing activation denied");
+                    Err(TransportError::RoutingActivationDenied { code: code as u8 })
+                }
             },
             DoipPayload::GenericNack(n) => Err(TransportError::ConnectionFailed(format!(
                 "NACK: {:?}",
@@ -220,6 +247,7 @@ impl DoIpAdapter {
         let connected = self.connected.clone();
         let incoming_tx = self.incoming_tx.clone();
         let ecu_channels = self.ecu_channels.clone();
+        let last_activity = self.last_activity.clone();
         let source_address = self.config.source_address;
 
         let handle = tokio::spawn(async move {
@@ -229,6 +257,9 @@ impl DoIpAdapter {
 
                 match tokio::time::timeout(Duration::from_millis(100), conn.read()).await {
                     Ok(Ok(Some(payload))) => {
+                        if matches!(payload, DoipPayload::DiagnosticMessage(_)) {
+                            *last_activity.lock() = Instant::now();
+                        }
                         Self::handle_message(
                             payload,
                             &incoming_tx,
@@ -280,6 +311,49 @@ impl DoIpAdapter {
         *self.keepalive_handle.lock().await = Some(handle);
     }
 
+    /// Start the idle-close task: once no diagnostic frame has passed for
+    /// `idle_close_ms`, drop the connection and let the next request reopen it
+    async fn start_idle_close(&self) {
+        let connection = self.connection.clone();
+        let connected = self.connected.clone();
+        let idle_closed = self.idle_closed.clone();
+        let last_activity = self.last_activity.clone();
+        let idle = Duration::from_millis(self.config.idle_close_ms);
+
+        let handle = tokio::spawn(async move {
+            while connected.load(Ordering::SeqCst) {
+                let idle_for = last_activity.lock().elapsed();
+                if idle_for < idle {
+                    tokio::time::sleep(idle - idle_for).await;
+                    continue;
+                }
+                connected.store(false, Ordering::SeqCst);
+                idle_closed.store(true, Ordering::SeqCst);
+                *connection.lock().await = None;
+                info!(
+                    idle_ms = idle.as_millis() as u64,
+                    "DoIP connection closed while idle"
+                );
+                break;
+            }
+        });
+
+        *self.idle_handle.lock().await = Some(handle);
+    }
+
+    /// Reopen a connection closed for idleness; a lost connection stays
+    /// closed until [`TransportAdapter::reconnect`] is called
+    async fn ensure_connected(&self) -> Result<(), TransportError> {
+        if self.connected.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        if !self.idle_closed.load(Ordering::SeqCst) {
+            return Err(TransportError::ConnectionClosed);
+        }
+        debug!("Reopening idle DoIP connection");
+        self.reconnect().await
+    }
+
     /// Handle incoming message
     async fn handle_message(
         payload: DoipPayload,
@@ -319,6 +393,7 @@ impl DoIpAdapter {
 
     /// Send diagnostic message
     async fn send_diagnostic(&self, target: u16, data: &[u8]) -> Result<(), TransportError> {
+        *self.last_activity.lock() = Instant::now();
         let payload = DoipPayload::DiagnosticMessage(DiagnosticMessage {
             source_address: self.config.source_address.to_be_bytes(),
             target_address: target.to_be_bytes(),
@@ -355,10 +430,9 @@ impl DoIpAdapter {
                         if first == expected {
                             return Ok(msg.data);
                         }
+                        // responsePending (0x78) included: the UDS layer
+                        // waits out the follow-up with P2*
                         if first == 0x7F && msg.data.get(1) == Some(&sid) {
-                            if msg.data.get(2) == Some(&0x78) {
-                                continue; // response pending
-                            }
                             return Ok(msg.data);
                         }
                     }
@@ -376,9 +450,7 @@ impl DoIpAdapter {
         request: &[u8],
         timeout: Duration,
     ) -> Result<Vec<u8>, TransportError> {
-        if !self.connected.load(Ordering::SeqCst) {
-            return Err(TransportError::ConnectionClosed);
-        }
+        self.ensure_connected().await?;
         self.add_ecu(target).await;
         self.send_diagnostic(target, request).await?;
         self.wait_for_response(request.first().copied().unwrap_or(0), timeout)
@@ -402,9 +474,7 @@ impl TransportAdapter for DoIpAdapter {
     }
 
     async fn send(&self, request: &[u8]) -> Result<(), TransportError> {
-        if !self.connected.load(Ordering::SeqCst) {
-            return Err(TransportError::ConnectionClosed);
-        }
+        self.ensure_connected().await?;
         self.send_diagnostic(self.config.target_address, request)
             .await
     }
@@ -413,6 +483,10 @@ impl TransportAdapter for DoIpAdapter {
         self.incoming_tx.subscribe()
     }
 
+    fn delivers_pending_responses(&self) -> bool {
+        true
+    }
+
     async fn is_connected(&self) -> bool {
         self.connected.load(Ordering::SeqCst)
     }
@@ -426,12 +500,27 @@ impl TransportAdapter for DoIpAdapter {
         if let Some(h) = self.keepalive_handle.lock().await.take() {
             h.abort();
         }
+        if let Some(h) = self.idle_handle.lock().await.take() {
+            h.abort();
+        }
         self.connect_with_retry(MAX_RECONNECT_ATTEMPTS).await
     }
 
     fn address_info(&self) -> AddressInfo {
         self.address_info.clone()
     }
+
+    fn stats(&self) -> sovd_core::TransportStats {
+        sovd_core::TransportStats {
+            kind: "doip".to_string(),
+            address: Some(format!(
+                "{}:{} 0x{:04X}",
+                self.config.gateway_host, self.config.gateway_port, self.config.target_address
+            )),
+            max_message_len: self.max_message_len(),
+            ..Default::default()
+        }
+    }
 }
 
 impl Drop for DoIpAdapter {
@@ -442,8 +531,177 @@ impl Drop for DoIpAdapter {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
     use super::*;
 
+    /// Connections accepted and routing activations answered by [`gateway`]
+    #[derive(Default)]
+    struct GatewayStats {
+        connections: AtomicUsize,
+        activations: AtomicUsize,
+        closed: AtomicUsize,
+        /// Payload of the last routing activation request
+        activation_request: SyncMutex<Vec<u8>>,
+    }
+
+    fn frame(payload_type: u16, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0x02, 0xFD];
+        frame.extend_from_slice(&payload_type.to_be_bytes());
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    async fn read_frame(stream: &mut TcpStream) -> Option<(u16, Vec<u8>)> {
+        let mut header = [0u8; 8];
+        stream.read_exact(&mut header).await.ok()?;
+        let payload_type = u16::from_be_bytes([header[2], header[3]]);
+        let len = u32::from_be_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        let mut payload = vec![0; len];
+        stream.read_exact(&mut payload).await.ok()?;
+        Some((payload_type, payload))
+    }
+
+    /// Minimal DoIP gateway: activates routing for any tester and answers
+    /// every diagnostic request positively, echoing its parameters
+    async fn gateway() -> (u16, Arc<GatewayStats>) {
+        gateway_answering(0x10).await
+    }
+
+    /// [`gateway`] answering routing activation with response `code`
+    async fn gateway_answering(code: u8) -> (u16, Arc<GatewayStats>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let stats = Arc::new(GatewayStats::default());
+        let server_stats = stats.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let stats = server_stats.clone();
+                stats.connections.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    while let Some((payload_type, payload)) = read_frame(&mut stream).await {
+                        let reply = match payload_type {
+                            // Routing activation request → `code`
+                            0x0005 => {
+                                stats.activations.fetch_add(1, Ordering::SeqCst);
+                                *stats.activation_request.lock() = payload.clone();
+                                let mut resp = payload[..2].to_vec();
+                                resp.extend_from_slice(&[0x00, 0x10, code, 0, 0, 0, 0]);
+                                frame(0x0006, &resp)
+                            }
+                            // Diagnostic message → positive response
+                            0x8001 => {
+                                let mut resp = payload[2..4].to_vec();
+                                resp.extend_from_slice(&payload[..2]);
+                                resp.push(payload[4] + 0x40);
+                                resp.extend_from_slice(&payload[5..]);
+                                frame(0x8001, &resp)
+                            }
+                            _ => continue,
+                        };
+                        if stream.write_all(&reply).await.is_err() {
+                            break;
+                        }
+                    }
+                    stats.closed.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+        });
+
+        (port, stats)
+    }
+
+    fn config(port: u16, idle_close_ms: u64) -> DoIpConfig {
+        DoIpConfig {
+            gateway_host: "127.0.0.1".to_string(),
+            gateway_port: port,
+            source_address: 0x0E80,
+            target_address: 0x0010,
+            activation_type: 0,
+            oem_specific: None,
+            connect_timeout_ms: 1000,
+            activation_timeout_ms: 1000,
+            response_timeout_ms: 1000,
+            keepalive_interval_secs: 0,
+            idle_close_ms,
+            auto_discover: false,
+            discovery_port: 13400,
+        }
+    }
+
+    #[tokio::test]
+    async fn idle_connection_closes_and_reopens_on_next_request() {
+        let (port, stats) = gateway().await;
+        let adapter = DoIpAdapter::new(&config(port, 150)).await.unwrap();
+        let timeout = Duration::from_secs(1);
+
+        let resp = adapter.send_receive(&[0x22, 0xF1, 0x90], timeout).await;
+        assert_eq!(resp.unwrap(), vec![0x62, 0xF1, 0x90]);
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(!adapter.is_connected().await, "closed after idling");
+        assert_eq!(stats.closed.load(Ordering::SeqCst), 1);
+
+        // The next read reconnects with a fresh routing activation
+        let resp = adapter.send_receive(&[0x22, 0xF1, 0x90], timeout).await;
+        assert_eq!(resp.unwrap(), vec![0x62, 0xF1, 0x90]);
+        assert_eq!(stats.connections.load(Ordering::SeqCst), 2);
+        assert_eq!(stats.activations.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn traffic_keeps_connection_open() {
+        let (port, stats) = gateway().await;
+        let adapter = DoIpAdapter::new(&config(port, 150)).await.unwrap();
+
+        for _ in 0..5 {
+            tokio::time::sleep(Duration::from_millis(60)).await;
+            adapter
+                .send_receive(&[0x3E, 0x00], Duration::from_secs(1))
+                .await
+                .unwrap();
+        }
+        assert!(adapter.is_connected().await);
+        assert_eq!(stats.connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn routing_activation_carries_type_and_oem_field() {
+        let (port, stats) = gateway().await;
+        let config = DoIpConfig {
+            activation_type: 0xE0,
+            oem_specific: Some([0xDE, 0xAD, 0xBE, 0xEF]),
+            ..config(port, 0)
+        };
+        DoIpAdapter::new(&config).await.unwrap();
+
+        // Source address, activation type, then the 4-byte field
+        assert_eq!(
+            *stats.activation_request.lock(),
+            vec![0x0E, 0x80, 0xE0, 0xDE, 0xAD, 0xBE, 0xEF]
+        );
+    }
+
+    #[tokio::test]
+    async fn denied_routing_activation_reports_the_code() {
+        // 0x06: unsupported routing activation type
+        let (port, stats) = gateway_answering(0x06).await;
+
+        let err = DoIpAdapter::new(&config(port, 0)).await.err().unwrap();
+
+        assert!(
+            matches!(err, TransportError::RoutingActivationDenied { code: 0x06 }),
+            "{err:?}"
+        );
+        // A denial is final, not retried
+        assert_eq!(stats.activations.load(Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn test_address_info() {
         let info = AddressInfo {
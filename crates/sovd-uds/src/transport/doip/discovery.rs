@@ -5,12 +5,32 @@ use std::time::Duration;
 
 use doip_definitions::payload::{
     DoipPayload, VehicleAnnouncementMessage, VehicleIdentificationRequest,
+    VehicleIdentificationRequestEid, VehicleIdentificationRequestVin,
 };
 use doip_sockets::udp::UdpSocket;
 use tracing::{debug, info};
 
 use crate::transport::TransportError;
 
+/// Where and how long targeted discovery looks for a gateway
+#[derive(Debug, Clone)]
+pub struct DiscoveryConfig {
+    /// Destination of the identification request; the default broadcast
+    /// address reaches every gateway on the subnet
+    pub target: SocketAddr,
+    /// How long to wait for the matching gateway to answer
+    pub timeout_ms: u64,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            target: SocketAddr::from(([255, 255, 255, 255], 13400)),
+            timeout_ms: 2000,
+        }
+    }
+}
+
 /// Discovered DoIP gateway
 #[derive(Debug, Clone)]
 pub struct DiscoveredGateway {
@@ -83,6 +103,93 @@ pub async fn discover_gateways(timeout_ms: u64) -> Result<Vec<DiscoveredGateway>
     Ok(gateways)
 }
 
+/// Discover the gateway of the vehicle with `vin`
+///
+/// Sends a VehicleIdentificationRequestWithVIN, so only that vehicle's
+/// gateway answers, and waits for its announcement; on a bench shared by
+/// several vehicles no other gateway is picked up. Fails with
+/// [`TransportError::Timeout`] if it does not answer within the timeout.
+pub async fn discover_gateway_by_vin(
+    vin: &str,
+    config: &DiscoveryConfig,
+) -> Result<DiscoveredGateway, TransportError> {
+    let vin = vin_bytes(vin)?;
+    discover_gateway(
+        DoipPayload::VehicleIdentificationRequestVin(VehicleIdentificationRequestVin { vin }),
+        |gateway| gateway.vin == vin,
+        config,
+    )
+    .await
+}
+
+/// Discover the gateway with entity identification `eid` (usually its MAC
+/// address), like [`discover_gateway_by_vin`]
+pub async fn discover_gateway_by_eid(
+    eid: [u8; 6],
+    config: &DiscoveryConfig,
+) -> Result<DiscoveredGateway, TransportError> {
+    discover_gateway(
+        DoipPayload::VehicleIdentificationRequestEid(VehicleIdentificationRequestEid { eid }),
+        |gateway| gateway.eid == eid,
+        config,
+    )
+    .await
+}
+
+/// Send a targeted identification `request` and wait for the first
+/// announcement satisfying `matches`
+async fn discover_gateway(
+    request: DoipPayload,
+    matches: impl Fn(&DiscoveredGateway) -> bool,
+    config: &DiscoveryConfig,
+) -> Result<DiscoveredGateway, TransportError> {
+    let mut socket = UdpSocket::bind("0.0.0.0:0")
+        .await
+        .map_err(|e| TransportError::ConnectionFailed(e.to_string()))?;
+    socket
+        .send(request, config.target)
+        .await
+        .map_err(|e| TransportError::SendFailed(e.to_string()))?;
+
+    info!(target = %config.target, "Sent targeted VIR");
+
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(config.timeout_ms);
+    loop {
+        match tokio::time::timeout_at(deadline, socket.recv()).await {
+            Ok(Some(Ok((msg, addr)))) => {
+                let DoipPayload::VehicleAnnouncementMessage(vam) = msg.payload else {
+                    continue;
+                };
+                let gateway = DiscoveredGateway::from((vam, addr));
+                if matches(&gateway) {
+                    info!(ip = %gateway.ip, vin = %gateway.vin_string(), "Found gateway");
+                    return Ok(gateway);
+                }
+                // A gateway ignoring the VIN/EID filter, e.g. answering a
+                // concurrent broadcast
+                debug!(ip = %addr.ip(), "Ignoring VAM of another gateway");
+            }
+            Ok(Some(Err(e))) => {
+                debug!(error = %e, "Ignoring undecodable discovery datagram");
+            }
+            Ok(None) => return Err(TransportError::ConnectionClosed),
+            Err(_) => {
+                return Err(TransportError::Timeout(format!(
+                    "no matching gateway answered within {} ms",
+                    config.timeout_ms
+                )))
+            }
+        }
+    }
+}
+
+/// The 17 bytes of a VIN as carried in DoIP messages
+fn vin_bytes(vin: &str) -> Result<[u8; 17], TransportError> {
+    vin.as_bytes().try_into().map_err(|_| {
+        TransportError::InvalidConfig(format!("VIN must be 17 characters, got {:?}", vin))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,4 +204,31 @@ mod tests {
         };
         assert_eq!(gw.vin_string(), "WVWZZZ3CZWE123456");
     }
+
+    #[test]
+    fn test_vin_bytes_requires_17_characters() {
+        assert_eq!(
+            &vin_bytes("WVWZZZ3CZWE123456").unwrap(),
+            b"WVWZZZ3CZWE123456"
+        );
+        assert!(matches!(
+            vin_bytes("WVWZZZ3CZWE"),
+            Err(TransportError::InvalidConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_targeted_discovery_times_out_without_answer() {
+        // A bound socket that never answers
+        let silent = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let config = DiscoveryConfig {
+            target: silent.local_addr().unwrap(),
+            timeout_ms: 100,
+        };
+
+        let err = discover_gateway_by_eid([0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E], &config)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TransportError::Timeout(_)), "{err:?}");
+    }
 }
@@ -12,6 +12,8 @@
 //! - Multi-ECU support (multiple targets per gateway connection)
 //! - Active keep-alive (periodic alive check requests)
 //! - Automatic reconnection with retry logic
+//! - Idle close (`idle_close_ms`), reopened with routing activation on the
+//!   next request
 //! - Vehicle discovery via UDP broadcast (VIR/VAM)
 //!
 //! # Example Configuration
@@ -28,22 +30,32 @@
 //! # Vehicle Discovery
 //!
 //! ```ignore
-//! use sovd_uds::transport::doip::discovery::{discover_gateways, DiscoveryConfig};
+//! use sovd_uds::transport::doip::discovery::discover_gateways;
 //!
-//! let config = DiscoveryConfig::default();
-//! let gateways = discover_gateways(&config).await?;
+//! let gateways = discover_gateways(2000).await?;
 //!
 //! for gateway in &gateways {
-//!     println!("Found {} at {} (VIN: {})",
-//!         gateway.logical_address_hex(),
+//!     println!("Found 0x{:04X} at {} (VIN: {})",
+//!         gateway.logical_address,
 //!         gateway.ip,
 //!         gateway.vin_string()
 //!     );
 //! }
 //! ```
+//!
+//! To reach one vehicle on a shared bench, ask only its gateway to answer:
+//!
+//! ```ignore
+//! use sovd_uds::transport::doip::discovery::{discover_gateway_by_vin, DiscoveryConfig};
+//!
+//! let gateway = discover_gateway_by_vin("WVWZZZ3CZWE123456", &DiscoveryConfig::default()).await?;
+//! ```
 
 mod adapter;
 pub mod discovery;
 
 pub use adapter::DoIpAdapter;
-pub use discovery::{discover_gateways, DiscoveredGateway};
+pub use discovery::{
+    discover_gateway_by_eid, discover_gateway_by_vin, discover_gateways, DiscoveredGateway,
+    DiscoveryConfig,
+};
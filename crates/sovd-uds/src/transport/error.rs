@@ -30,4 +30,10 @@ pub enum TransportError {
 
     #[error("TLS connection required")]
     TlsRequired,
+
+    /// The DoIP gateway answered routing activation with a denial code
+    /// (ISO 13400-2, e.g. 0x00 unknown source address, 0x06 unsupported
+    /// activation type)
+    #[error("Routing activation denied: 0x{code:02X}")]
+    RoutingActivationDenied { code: u8 },
 }
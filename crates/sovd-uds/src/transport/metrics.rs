@@ -0,0 +1,362 @@
+//! Transport traffic counters
+//!
+//! [`MeteredTransport`] wraps any [`TransportAdapter`] and counts what goes
+//! over it: requests, timeouts, retransmits, messages and link frames, the
+//! STmin pacing our flow control imposes, and a round-trip latency
+//! histogram. The counters are read back with
+//! [`TransportAdapter::metrics`], so a slow read can be correlated with the
+//! ISO-TP block size / STmin in [`TransportAdapter::stats`] without extra
+//! logging.
+//!
+//! The kernel ISO-TP stack segments messages itself, so on CAN the frame
+//! counts are derived from each message's length and the link's TX data
+//! length rather than observed on the bus.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use sovd_core::{
+    LatencyBucket, LatencyHistogram, TransportMetrics, TransportStats, LATENCY_BUCKETS_MS,
+};
+use tokio::sync::broadcast;
+
+use super::{AddressInfo, IncomingMessage, TransportAdapter, TransportError};
+
+/// Largest message an ISO-TP FirstFrame announces without the 32-bit
+/// length escape
+const FF_DL_MAX: usize = 4095;
+
+/// Wraps a transport, counting its traffic
+pub struct MeteredTransport {
+    inner: Arc<dyn TransportAdapter>,
+    /// Link settings, read once: ISO-TP framing applies when `tx_dl` is set
+    link: TransportStats,
+    counters: Counters,
+    /// Last request that timed out, to recognise its re-send
+    last_timeout: Mutex<Option<Vec<u8>>>,
+}
+
+#[derive(Default)]
+struct Counters {
+    requests: AtomicU64,
+    timeouts: AtomicU64,
+    errors: AtomicU64,
+    retransmits: AtomicU64,
+    messages_sent: AtomicU64,
+    messages_received: AtomicU64,
+    frames_sent: AtomicU64,
+    frames_received: AtomicU64,
+    flow_control_wait_us: AtomicU64,
+    /// Latencies per bucket (not cumulative); the extra slot is +Inf
+    latency_buckets: [AtomicU64; LATENCY_BUCKETS_MS.len() + 1],
+    latency_sum_us: AtomicU64,
+}
+
+/// ISO-TP frames carrying a `len`-byte message at TX data length `tx_dl`:
+/// `(data frames, consecutive frames)`
+fn isotp_frames(len: usize, tx_dl: u8) -> (u64, u64) {
+    let dl = usize::from(tx_dl.max(8));
+    // SingleFrame PCI: 1 byte on classic CAN, 2 with the CAN FD escape
+    let sf_max = if dl == 8 { 7 } else { dl - 2 };
+    if len <= sf_max {
+        return (1, 0);
+    }
+    // FirstFrame PCI: 2 bytes, 6 with the 32-bit length escape
+    let ff_data = if len > FF_DL_MAX { dl - 6 } else { dl - 2 };
+    let consecutive = (len - ff_data).div_ceil(dl - 1) as u64;
+    (1 + consecutive, consecutive)
+}
+
+impl MeteredTransport {
+    pub fn new(inner: Arc<dyn TransportAdapter>) -> Self {
+        let link = inner.stats();
+        Self {
+            inner,
+            link,
+            counters: Counters::default(),
+            last_timeout: Mutex::new(None),
+        }
+    }
+
+    /// Count a message we sent
+    fn count_sent(&self, request: &[u8]) {
+        let c = &self.counters;
+        c.messages_sent.fetch_add(1, Ordering::Relaxed);
+        let frames = match self.link.tx_dl {
+            Some(tx_dl) => {
+                let (frames, consecutive) = isotp_frames(request.len(), tx_dl);
+                // The ECU's FlowControl for a segmented request; its block
+                // size is unknown, so at least one
+                if consecutive > 0 {
+                    c.frames_received.fetch_add(1, Ordering::Relaxed);
+                }
+                frames
+            }
+            None => 1,
+        };
+        c.frames_sent.fetch_add(frames, Ordering::Relaxed);
+    }
+
+    /// Count a response we received
+    fn count_received(&self, response: &[u8]) {
+        let c = &self.counters;
+        c.messages_received.fetch_add(1, Ordering::Relaxed);
+        let frames = match self.link.tx_dl {
+            Some(tx_dl) => {
+                let (frames, consecutive) = isotp_frames(response.len(), tx_dl);
+                if consecutive > 0 {
+                    // One FlowControl per block we advertised
+                    let fc = match self.link.block_size.unwrap_or(0) {
+                        0 => 1,
+                        bs => 1 + (consecutive - 1) / u64::from(bs),
+                    };
+                    c.frames_sent.fetch_add(fc, Ordering::Relaxed);
+                    let st_min_us = u64::from(self.link.st_min_us.unwrap_or(0));
+                    c.flow_control_wait_us
+                        .fetch_add((consecutive - 1) * st_min_us, Ordering::Relaxed);
+                }
+                frames
+            }
+            None => 1,
+        };
+        c.frames_received.fetch_add(frames, Ordering::Relaxed);
+    }
+
+    /// Count a request about to go out, recognising the re-send of one
+    /// that timed out
+    fn count_request(&self, request: &[u8]) {
+        let c = &self.counters;
+        c.requests.fetch_add(1, Ordering::Relaxed);
+        let previous_timeout = self.last_timeout.lock().take();
+        if previous_timeout.as_deref() == Some(request) {
+            c.retransmits.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Count how the exchange of `request`, begun at `started`, ended
+    fn count_outcome(
+        &self,
+        request: &[u8],
+        started: Instant,
+        result: &Result<Vec<u8>, TransportError>,
+    ) {
+        let c = &self.counters;
+        match result {
+            Ok(response) => {
+                self.count_sent(request);
+                self.count_received(response);
+                self.observe_latency(started.elapsed());
+            }
+            Err(TransportError::Timeout(_)) => {
+                self.count_sent(request);
+                c.timeouts.fetch_add(1, Ordering::Relaxed);
+                *self.last_timeout.lock() = Some(request.to_vec());
+            }
+            Err(_) => {
+                c.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn observe_latency(&self, latency: Duration) {
+        let ms = latency.as_secs_f64() * 1000.0;
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&le| ms <= le as f64)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.counters.latency_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.counters
+            .latency_sum_us
+            .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Snapshot of the counters
+    pub fn snapshot(&self) -> TransportMetrics {
+        let c = &self.counters;
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let mut cumulative = 0;
+        let buckets = LATENCY_BUCKETS_MS
+            .iter()
+            .zip(&c.latency_buckets)
+            .map(|(&le_ms, count)| {
+                cumulative += load(count);
+                LatencyBucket {
+                    le_ms,
+                    count: cumulative,
+                }
+            })
+            .collect();
+        let count: u64 = c.latency_buckets.iter().map(load).sum();
+        TransportMetrics {
+            requests: load(&c.requests),
+            timeouts: load(&c.timeouts),
+            errors: load(&c.errors),
+            retransmits: load(&c.retransmits),
+            messages_sent: load(&c.messages_sent),
+            messages_received: load(&c.messages_received),
+            frames_sent: load(&c.frames_sent),
+            frames_received: load(&c.frames_received),
+            flow_control_wait_us: load(&c.flow_control_wait_us),
+            latency: LatencyHistogram {
+                buckets,
+                sum_ms: load(&c.latency_sum_us) as f64 / 1000.0,
+                count,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl TransportAdapter for MeteredTransport {
+    async fn send_receive(
+        &self,
+        request: &[u8],
+        timeout: Duration,
+    ) -> Result<Vec<u8>, TransportError> {
+        self.count_request(request);
+        let started = Instant::now();
+        let result = self.inner.send_receive(request, timeout).await;
+        self.count_outcome(request, started, &result);
+        result
+    }
+
+    async fn send_receive_first_frame(
+        &self,
+        request: &[u8],
+        first_frame_timeout: Duration,
+        timeout: Duration,
+    ) -> Result<Vec<u8>, TransportError> {
+        self.count_request(request);
+        let started = Instant::now();
+        let result = self
+            .inner
+            .send_receive_first_frame(request, first_frame_timeout, timeout)
+            .await;
+        self.count_outcome(request, started, &result);
+        result
+    }
+
+    fn bounds_first_frame(&self) -> bool {
+        self.inner.bounds_first_frame()
+    }
+
+    async fn send(&self, request: &[u8]) -> Result<(), TransportError> {
+        let result = self.inner.send(request).await;
+        match &result {
+            Ok(()) => self.count_sent(request),
+            Err(_) => {
+                self.counters.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        result
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<IncomingMessage> {
+        self.inner.subscribe()
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.inner.is_connected().await
+    }
+
+    async fn reconnect(&self) -> Result<(), TransportError> {
+        self.inner.reconnect().await
+    }
+
+    fn address_info(&self) -> AddressInfo {
+        self.inner.address_info()
+    }
+
+    fn max_message_len(&self) -> Option<usize> {
+        self.inner.max_message_len()
+    }
+
+    fn delivers_pending_responses(&self) -> bool {
+        self.inner.delivers_pending_responses()
+    }
+
+    fn stats(&self) -> TransportStats {
+        self.inner.stats()
+    }
+
+    async fn set_bitrate(&self, bitrate: u32) -> Result<(), TransportError> {
+        self.inner.set_bitrate(bitrate).await
+    }
+
+    fn metrics(&self) -> Option<TransportMetrics> {
+        Some(self.snapshot())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MockConfig;
+    use crate::transport::mock::MockTransportAdapter;
+
+    #[test]
+    fn isotp_frame_counts() {
+        // Classic CAN: SF up to 7 bytes, FF carries 6, CFs 7 each
+        assert_eq!(isotp_frames(3, 8), (1, 0));
+        assert_eq!(isotp_frames(7, 8), (1, 0));
+        assert_eq!(isotp_frames(8, 8), (2, 1));
+        assert_eq!(isotp_frames(20, 8), (3, 2));
+        // CAN FD at 64 bytes: SF up to 62, FF 62, CFs 63
+        assert_eq!(isotp_frames(62, 64), (1, 0));
+        assert_eq!(isotp_frames(200, 64), (4, 3));
+    }
+
+    fn metered() -> (Arc<MockTransportAdapter>, MeteredTransport) {
+        let mock = Arc::new(MockTransportAdapter::new(&MockConfig { latency_ms: 0 }));
+        let metered = MeteredTransport::new(mock.clone());
+        (mock, metered)
+    }
+
+    #[tokio::test]
+    async fn counts_requests_and_latency() {
+        let (_, metered) = metered();
+
+        for _ in 0..3 {
+            metered
+                .send_receive(&[0x22, 0xF1, 0x90], Duration::from_secs(1))
+                .await
+                .unwrap();
+        }
+        metered.send(&[0x3E, 0x80]).await.unwrap();
+
+        let m = metered.metrics().unwrap();
+        assert_eq!(m.requests, 3);
+        assert_eq!(m.messages_sent, 4);
+        assert_eq!(m.messages_received, 3);
+        // Not an ISO-TP link: one frame per message
+        assert_eq!(m.frames_sent, 4);
+        assert_eq!(m.latency.count, 3);
+        assert_eq!(m.latency.buckets.len(), LATENCY_BUCKETS_MS.len());
+        assert_eq!(m.latency.buckets.last().unwrap().count, 3);
+    }
+
+    #[tokio::test]
+    async fn resend_after_timeout_is_a_retransmit() {
+        let (mock, metered) = metered();
+        mock.add_silence(vec![0x22, 0xF1, 0x90], 1);
+
+        let request = [0x22, 0xF1, 0x90];
+        let timeout = Duration::from_millis(10);
+        assert!(metered.send_receive(&request, timeout).await.is_err());
+        metered.send_receive(&request, timeout).await.unwrap();
+        // A different request after a success is not
+        metered
+            .send_receive(&[0x22, 0xF1, 0x91], timeout)
+            .await
+            .unwrap();
+
+        let m = metered.metrics().unwrap();
+        assert_eq!(m.requests, 3);
+        assert_eq!(m.timeouts, 1);
+        assert_eq!(m.retransmits, 1);
+        assert_eq!(m.latency.count, 2);
+    }
+}
@@ -10,6 +10,9 @@ use tokio::sync::broadcast;
 use super::{AddressInfo, IncomingMessage, TransportAdapter, TransportError};
 use crate::config::MockConfig;
 
+/// Delay before each frame registered with `add_follow_up`
+const FOLLOW_UP_DELAY: Duration = Duration::from_millis(20);
+
 /// Mock transport adapter for testing
 pub struct MockTransportAdapter {
     config: MockConfig,
@@ -17,6 +20,26 @@ pub struct MockTransportAdapter {
     incoming_tx: broadcast::Sender<IncomingMessage>,
     /// Predefined responses for testing (request -> response mapping)
     responses: RwLock<Vec<(Vec<u8>, Vec<u8>)>>,
+    /// Simulated transport message size limit
+    max_message_len: RwLock<Option<usize>>,
+    /// Requests seen by `send_receive`, in order
+    sent: RwLock<Vec<Vec<u8>>>,
+    /// Response timeout passed with each of `sent`
+    timeouts: RwLock<Vec<Duration>>,
+    /// Frames injected after answering a request (request -> frames)
+    follow_ups: RwLock<Vec<(Vec<u8>, Vec<Vec<u8>>)>>,
+    /// Frames injected before the answer is handed back (request -> frames)
+    immediate_follow_ups: RwLock<Vec<(Vec<u8>, Vec<Vec<u8>>)>>,
+    /// Requests left unanswered (request prefix -> remaining count)
+    silences: RwLock<Vec<(Vec<u8>, usize)>>,
+    /// Time from first to last frame of a response (request prefix -> time)
+    transfer_times: RwLock<Vec<(Vec<u8>, Duration)>>,
+    /// Bitrates the bus was switched to via `set_bitrate`, in order
+    bitrates: RwLock<Vec<u32>>,
+    /// Time `set_bitrate` takes to switch the bus
+    bitrate_delay: RwLock<Duration>,
+    /// Whether `set_bitrate` fails
+    refuse_bitrates: AtomicBool,
 }
 
 impl MockTransportAdapter {
@@ -27,6 +50,16 @@ impl MockTransportAdapter {
             connected: AtomicBool::new(true),
             incoming_tx,
             responses: RwLock::new(Self::default_responses()),
+            max_message_len: RwLock::new(None),
+            sent: RwLock::new(Vec::new()),
+            timeouts: RwLock::new(Vec::new()),
+            follow_ups: RwLock::new(Vec::new()),
+            immediate_follow_ups: RwLock::new(Vec::new()),
+            silences: RwLock::new(Vec::new()),
+            transfer_times: RwLock::new(Vec::new()),
+            bitrates: RwLock::new(Vec::new()),
+            bitrate_delay: RwLock::new(Duration::ZERO),
+            refuse_bitrates: AtomicBool::new(false),
         }
     }
 
@@ -35,6 +68,41 @@ impl MockTransportAdapter {
         self.responses.write().push((request, response));
     }
 
+    /// Answer `request` with `response` instead of any response added for
+    /// it before, including the defaults
+    pub fn replace_response(&self, request: Vec<u8>, response: Vec<u8>) {
+        let mut responses = self.responses.write();
+        responses.retain(|(req, _)| *req != request);
+        responses.push((request, response));
+    }
+
+    /// Frames the ECU sends on its own shortly after answering `request`,
+    /// e.g. the final response following a responsePending (0x78)
+    pub fn add_follow_up(&self, request: Vec<u8>, frames: Vec<Vec<u8>>) {
+        self.follow_ups.write().push((request, frames));
+    }
+
+    /// Like [`Self::add_follow_up`], but the frames are on the bus before
+    /// `send_receive` has returned the answer, as when the ECU sends its
+    /// final response right behind a responsePending (0x78)
+    pub fn add_immediate_follow_up(&self, request: Vec<u8>, frames: Vec<Vec<u8>>) {
+        self.immediate_follow_ups.write().push((request, frames));
+    }
+
+    /// Leave the next `count` requests starting with `request` unanswered,
+    /// as if the response got lost: they time out after the caller's timeout
+    pub fn add_silence(&self, request: Vec<u8>, count: usize) {
+        self.silences.write().push((request, count));
+    }
+
+    /// Make the responses to requests starting with `request` take
+    /// `duration` from their first frame to their last, as a long
+    /// multi-frame (ISO-TP) response does; one that cannot finish within
+    /// the caller's timeout times out
+    pub fn set_transfer_time(&self, request: Vec<u8>, duration: Duration) {
+        self.transfer_times.write().push((request, duration));
+    }
+
     /// Inject an incoming message (simulates ECU sending periodic data)
     pub fn inject_incoming(&self, data: Vec<u8>) {
         let msg = IncomingMessage {
@@ -45,6 +113,37 @@ impl MockTransportAdapter {
         let _ = self.incoming_tx.send(msg);
     }
 
+    /// Requests received so far via `send_receive`, oldest first
+    pub fn sent_requests(&self) -> Vec<Vec<u8>> {
+        self.sent.read().clone()
+    }
+
+    /// Response timeout the caller passed with each of
+    /// [`Self::sent_requests`], or its first-frame timeout where it gave one
+    pub fn sent_timeouts(&self) -> Vec<Duration> {
+        self.timeouts.read().clone()
+    }
+
+    /// Bitrates the bus was switched to so far, oldest first
+    pub fn bitrate_changes(&self) -> Vec<u32> {
+        self.bitrates.read().clone()
+    }
+
+    /// Make every bus switch via `set_bitrate` take `delay`
+    pub fn set_bitrate_delay(&self, delay: Duration) {
+        *self.bitrate_delay.write() = delay;
+    }
+
+    /// Simulate a bus that cannot be switched: `set_bitrate` fails
+    pub fn refuse_bitrates(&self, refuse: bool) {
+        self.refuse_bitrates.store(refuse, Ordering::SeqCst);
+    }
+
+    /// Simulate a transport that cannot carry messages longer than `len`
+    pub fn set_max_message_len(&self, len: Option<usize>) {
+        *self.max_message_len.write() = len;
+    }
+
     /// Set connection state
     pub fn set_connected(&self, connected: bool) {
         self.connected.store(connected, Ordering::SeqCst);
@@ -109,6 +208,106 @@ impl MockTransportAdapter {
         ]
     }
 
+    /// Answer `request` like an ECU would; `first_frame_timeout` bounds
+    /// the wait for the start of the response, `timeout` the whole of it
+    async fn exchange(
+        &self,
+        request: &[u8],
+        first_frame_timeout: Option<Duration>,
+        timeout: Duration,
+    ) -> Result<Vec<u8>, TransportError> {
+        if !self.connected.load(Ordering::SeqCst) {
+            return Err(TransportError::ConnectionClosed);
+        }
+
+        self.sent.write().push(request.to_vec());
+        self.timeouts
+            .write()
+            .push(first_frame_timeout.unwrap_or(timeout));
+
+        if let Some(max) = *self.max_message_len.read() {
+            if request.len() > max {
+                return Err(TransportError::SendFailed(format!(
+                    "message of {} bytes exceeds transport limit of {}",
+                    request.len(),
+                    max
+                )));
+            }
+        }
+
+        let silent = self
+            .silences
+            .write()
+            .iter_mut()
+            .find(|(req, remaining)| *remaining > 0 && request.starts_with(req))
+            .map(|(_, remaining)| *remaining -= 1)
+            .is_some();
+        if silent {
+            tokio::time::sleep(first_frame_timeout.unwrap_or(timeout)).await;
+            return Err(TransportError::Timeout("Response timeout".to_string()));
+        }
+
+        // Simulate latency
+        let latency = Duration::from_millis(self.config.latency_ms);
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+
+        // The rest of a multi-frame response
+        let transfer = self
+            .transfer_times
+            .read()
+            .iter()
+            .find(|(req, _)| request.starts_with(req))
+            .map(|(_, duration)| *duration);
+        if let Some(transfer) = transfer {
+            if latency + transfer > timeout {
+                tokio::time::sleep(timeout.saturating_sub(latency)).await;
+                return Err(TransportError::Timeout(
+                    "Response incomplete at timeout".to_string(),
+                ));
+            }
+            tokio::time::sleep(transfer).await;
+        }
+
+        let response = self.find_response(request).ok_or_else(|| {
+            TransportError::ReceiveFailed("No mock response configured".to_string())
+        })?;
+        let follow_up = self
+            .follow_ups
+            .read()
+            .iter()
+            .find(|(req, _)| req.as_slice() == request)
+            .map(|(_, frames)| frames.clone());
+        let immediate = self
+            .immediate_follow_ups
+            .read()
+            .iter()
+            .find(|(req, _)| req.as_slice() == request)
+            .map(|(_, frames)| frames.clone());
+        for data in immediate.into_iter().flatten() {
+            let _ = self.incoming_tx.send(IncomingMessage {
+                timestamp: Instant::now(),
+                data,
+                source: AddressInfo::default(),
+            });
+        }
+        if let Some(frames) = follow_up {
+            let incoming_tx = self.incoming_tx.clone();
+            tokio::spawn(async move {
+                for data in frames {
+                    tokio::time::sleep(FOLLOW_UP_DELAY).await;
+                    let _ = incoming_tx.send(IncomingMessage {
+                        timestamp: Instant::now(),
+                        data,
+                        source: AddressInfo::default(),
+                    });
+                }
+            });
+        }
+        Ok(response)
+    }
+
     fn find_response(&self, request: &[u8]) -> Option<Vec<u8>> {
         let responses = self.responses.read();
 
@@ -143,19 +342,23 @@ impl TransportAdapter for MockTransportAdapter {
     async fn send_receive(
         &self,
         request: &[u8],
-        _timeout: Duration,
+        timeout: Duration,
     ) -> Result<Vec<u8>, TransportError> {
-        if !self.connected.load(Ordering::SeqCst) {
-            return Err(TransportError::ConnectionClosed);
-        }
+        self.exchange(request, None, timeout).await
+    }
 
-        // Simulate latency
-        if self.config.latency_ms > 0 {
-            tokio::time::sleep(Duration::from_millis(self.config.latency_ms)).await;
-        }
+    async fn send_receive_first_frame(
+        &self,
+        request: &[u8],
+        first_frame_timeout: Duration,
+        timeout: Duration,
+    ) -> Result<Vec<u8>, TransportError> {
+        self.exchange(request, Some(first_frame_timeout), timeout)
+            .await
+    }
 
-        self.find_response(request)
-            .ok_or_else(|| TransportError::ReceiveFailed("No mock response configured".to_string()))
+    fn bounds_first_frame(&self) -> bool {
+        true
     }
 
     async fn send(&self, request: &[u8]) -> Result<(), TransportError> {
@@ -176,6 +379,10 @@ impl TransportAdapter for MockTransportAdapter {
         self.incoming_tx.subscribe()
     }
 
+    fn delivers_pending_responses(&self) -> bool {
+        true
+    }
+
     async fn is_connected(&self) -> bool {
         self.connected.load(Ordering::SeqCst)
     }
@@ -191,4 +398,31 @@ impl TransportAdapter for MockTransportAdapter {
             rx_id: 0x7E8,
         }
     }
+
+    fn max_message_len(&self) -> Option<usize> {
+        *self.max_message_len.read()
+    }
+
+    async fn set_bitrate(&self, bitrate: u32) -> Result<(), TransportError> {
+        let delay = *self.bitrate_delay.read();
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        if self.refuse_bitrates.load(Ordering::SeqCst) {
+            return Err(TransportError::Unsupported(format!(
+                "switching the mock bus to {} bit/s",
+                bitrate
+            )));
+        }
+        self.bitrates.write().push(bitrate);
+        Ok(())
+    }
+
+    fn stats(&self) -> sovd_core::TransportStats {
+        sovd_core::TransportStats {
+            kind: "mock".to_string(),
+            max_message_len: self.max_message_len(),
+            ..Default::default()
+        }
+    }
 }
@@ -4,6 +4,10 @@
 //! - SocketCAN adapter for CAN/ISO-TP (Linux only, feature `socketcan`)
 //! - DoIP adapter for Diagnostics over IP (ISO 13400, feature `doip`)
 //! - Mock adapter for testing (feature `mock-transport`, opt-in)
+//! - Replay adapter serving a recorded session (demos, regression tests)
+//! - Custom adapters registered by kind in the [`TransportRegistry`]
+//!
+//! [`MeteredTransport`] wraps any of them to count their traffic.
 //!
 //! # Example
 //!
@@ -19,10 +23,14 @@
 
 mod adapter;
 pub mod error;
+pub mod metrics;
 
 #[cfg(feature = "mock-transport")]
 pub mod mock;
 
+pub mod registry;
+pub mod replay;
+
 #[cfg(all(target_os = "linux", feature = "socketcan"))]
 pub mod socketcan;
 
@@ -31,6 +39,8 @@ pub mod doip;
 
 pub use adapter::{AddressInfo, IncomingMessage, TransportAdapter};
 pub use error::TransportError;
+pub use metrics::MeteredTransport;
+pub use registry::{TransportFactory, TransportRegistry};
 
 use std::sync::Arc;
 
@@ -41,6 +51,9 @@ pub async fn create_transport(
     config: &TransportConfig,
 ) -> Result<Arc<dyn TransportAdapter>, TransportError> {
     match config {
+        TransportConfig::Custom { kind, params } => {
+            TransportRegistry::global().create(kind, params)
+        }
         #[cfg(all(target_os = "linux", feature = "socketcan"))]
         TransportConfig::SocketCan(cfg) => {
             let adapter = socketcan::SocketCanAdapter::new(cfg).await?;
@@ -64,5 +77,9 @@ pub async fn create_transport(
             let adapter = mock::MockTransportAdapter::new(cfg);
             Ok(Arc::new(adapter))
         }
+        TransportConfig::Replay(cfg) => {
+            let adapter = replay::ReplayTransportAdapter::new(cfg)?;
+            Ok(Arc::new(adapter))
+        }
     }
 }
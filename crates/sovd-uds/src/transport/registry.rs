@@ -0,0 +1,174 @@
+//! Registry of custom transport factories
+//!
+//! Integrators with a link SOVDd does not ship (e.g. a proprietary USB
+//! dongle) register a factory under a `kind` name instead of forking
+//! [`super::create_transport`], which builds
+//! `TransportConfig::Custom { kind, params }` through it:
+//!
+//! ```ignore
+//! TransportRegistry::global().register("acme-usb", |params| {
+//!     let serial = params["serial"].as_str().unwrap_or_default();
+//!     Ok(Arc::new(AcmeUsbAdapter::open(serial)?))
+//! });
+//! ```
+//!
+//! Factories must be registered before the backends using them are created.
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::RwLock;
+
+use super::{TransportAdapter, TransportError};
+
+/// Builds a transport adapter from the `params` of its custom config
+pub type TransportFactory = Arc<
+    dyn Fn(&serde_json::Value) -> Result<Arc<dyn TransportAdapter>, TransportError> + Send + Sync,
+>;
+
+/// Transport `kind` → factory
+#[derive(Default)]
+pub struct TransportRegistry {
+    factories: RwLock<HashMap<String, TransportFactory>>,
+}
+
+impl TransportRegistry {
+    /// The process-wide registry consulted by [`super::create_transport`]
+    pub fn global() -> &'static TransportRegistry {
+        static GLOBAL: OnceLock<TransportRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(TransportRegistry::default)
+    }
+
+    /// Register `factory` for `kind`, replacing any earlier one
+    pub fn register<F>(&self, kind: impl Into<String>, factory: F)
+    where
+        F: Fn(&serde_json::Value) -> Result<Arc<dyn TransportAdapter>, TransportError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.factories
+            .write()
+            .insert(kind.into(), Arc::new(factory));
+    }
+
+    /// Registered kinds, sorted
+    pub fn kinds(&self) -> Vec<String> {
+        let mut kinds: Vec<String> = self.factories.read().keys().cloned().collect();
+        kinds.sort();
+        kinds
+    }
+
+    /// Build a `kind` adapter from `params`
+    pub fn create(
+        &self,
+        kind: &str,
+        params: &serde_json::Value,
+    ) -> Result<Arc<dyn TransportAdapter>, TransportError> {
+        // Clone out so the factory runs without holding the lock
+        let factory = self.factories.read().get(kind).cloned().ok_or_else(|| {
+            TransportError::Unsupported(format!("no transport registered for kind '{}'", kind))
+        })?;
+        factory(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio::sync::broadcast;
+
+    use super::*;
+    use crate::config::TransportConfig;
+    use crate::transport::{create_transport, AddressInfo, IncomingMessage};
+
+    /// Answers every request with the configured bytes
+    struct FixedAdapter {
+        response: Vec<u8>,
+        incoming_tx: broadcast::Sender<IncomingMessage>,
+    }
+
+    #[async_trait]
+    impl TransportAdapter for FixedAdapter {
+        async fn send_receive(
+            &self,
+            _request: &[u8],
+            _timeout: Duration,
+        ) -> Result<Vec<u8>, TransportError> {
+            Ok(self.response.clone())
+        }
+        async fn send(&self, _request: &[u8]) -> Result<(), TransportError> {
+            Ok(())
+        }
+        fn subscribe(&self) -> broadcast::Receiver<IncomingMessage> {
+            self.incoming_tx.subscribe()
+        }
+        async fn is_connected(&self) -> bool {
+            true
+        }
+        async fn reconnect(&self) -> Result<(), TransportError> {
+            Ok(())
+        }
+        fn address_info(&self) -> AddressInfo {
+            AddressInfo::default()
+        }
+    }
+
+    fn fixed_factory(
+        params: &serde_json::Value,
+    ) -> Result<Arc<dyn TransportAdapter>, TransportError> {
+        let response = params["response"]
+            .as_str()
+            .and_then(|hex_str| hex::decode(hex_str).ok())
+            .ok_or_else(|| TransportError::InvalidConfig("response must be hex".to_string()))?;
+        Ok(Arc::new(FixedAdapter {
+            response,
+            incoming_tx: broadcast::channel(1).0,
+        }))
+    }
+
+    #[tokio::test]
+    async fn create_transport_builds_registered_custom_kind() {
+        TransportRegistry::global().register("fixed-test", fixed_factory);
+        assert!(TransportRegistry::global()
+            .kinds()
+            .contains(&"fixed-test".to_string()));
+
+        let config: TransportConfig = serde_json::from_value(serde_json::json!({
+            "type": "custom",
+            "kind": "fixed-test",
+            "params": { "response": "62f190" },
+        }))
+        .unwrap();
+        let transport = create_transport(&config).await.unwrap();
+        let response = transport
+            .send_receive(&[0x22, 0xF1, 0x90], Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(response, vec![0x62, 0xF1, 0x90]);
+
+        // Factory errors are passed through
+        let config = TransportConfig::Custom {
+            kind: "fixed-test".to_string(),
+            params: serde_json::json!({}),
+        };
+        assert!(matches!(
+            create_transport(&config).await,
+            Err(TransportError::InvalidConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn unregistered_kind_is_unsupported() {
+        let config = TransportConfig::Custom {
+            kind: "never-registered".to_string(),
+            params: serde_json::Value::Null,
+        };
+        assert!(matches!(
+            create_transport(&config).await,
+            Err(TransportError::Unsupported(_))
+        ));
+    }
+}
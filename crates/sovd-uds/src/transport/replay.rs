@@ -0,0 +1,327 @@
+//! Replay transport adapter: serves a recorded UDS session
+//!
+//! Unlike the mock adapter (hand-written canned answers, default positive
+//! response for anything unknown), this adapter is driven entirely by a
+//! capture of a real ECU session. Every request must appear in the trace;
+//! an unmatched request is an error so a regression run cannot silently
+//! drift away from the recording.
+//!
+//! # Trace format
+//!
+//! JSON or YAML (chosen by file extension, `.yaml`/`.yml` → YAML, anything
+//! else → JSON). Payloads are hex strings; whitespace is ignored.
+//!
+//! ```yaml
+//! ecu: engine_ecu            # optional, informational
+//! exchanges:
+//!   - request: "22 F1 90"
+//!     response: "62 F1 90 31 48 47 43 4D 38 32 36 33 33 41 31 32 33 34 35 36"
+//!   - request: "31 01 02 03"
+//!     response: "7F 31 78"   # responsePending, then the final answer
+//!   - request: "31 01 02 03"
+//!     response: "71 01 02 03 00"
+//! ```
+//!
+//! Repeated requests are answered in recorded order; once a request's
+//! recordings are exhausted its last response keeps being served, so a
+//! polled DID keeps returning the final captured value.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use super::{AddressInfo, IncomingMessage, TransportAdapter, TransportError};
+use crate::config::ReplayConfig;
+
+/// One recorded request → response pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayExchange {
+    /// Raw UDS request (hex)
+    pub request: String,
+    /// Raw UDS response (hex); empty for a suppressed response
+    #[serde(default)]
+    pub response: String,
+}
+
+/// A recorded UDS session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayTrace {
+    /// ECU the trace was captured from (informational)
+    #[serde(default)]
+    pub ecu: Option<String>,
+    /// Exchanges in capture order
+    #[serde(default)]
+    pub exchanges: Vec<ReplayExchange>,
+}
+
+impl ReplayTrace {
+    /// Load a trace file, picking the parser from the extension
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TransportError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            TransportError::InvalidConfig(format!(
+                "cannot read replay trace {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml") | Some("yml") => Self::from_yaml_str(&content),
+            _ => Self::from_json_str(&content),
+        }
+    }
+
+    pub fn from_json_str(s: &str) -> Result<Self, TransportError> {
+        serde_json::from_str(s)
+            .map_err(|e| TransportError::InvalidConfig(format!("invalid replay trace: {}", e)))
+    }
+
+    pub fn from_yaml_str(s: &str) -> Result<Self, TransportError> {
+        serde_yaml::from_str(s)
+            .map_err(|e| TransportError::InvalidConfig(format!("invalid replay trace: {}", e)))
+    }
+}
+
+/// Recorded responses for one request, served in order
+struct ReplayQueue {
+    responses: Vec<Vec<u8>>,
+    next: usize,
+}
+
+impl ReplayQueue {
+    fn next_response(&mut self) -> Vec<u8> {
+        let idx = self.next.min(self.responses.len() - 1);
+        if self.next < self.responses.len() {
+            self.next += 1;
+        }
+        self.responses[idx].clone()
+    }
+}
+
+/// Transport adapter answering from a [`ReplayTrace`]
+pub struct ReplayTransportAdapter {
+    queues: Mutex<HashMap<Vec<u8>, ReplayQueue>>,
+    incoming_tx: broadcast::Sender<IncomingMessage>,
+}
+
+impl ReplayTransportAdapter {
+    /// Load the trace named by `config`
+    pub fn new(config: &ReplayConfig) -> Result<Self, TransportError> {
+        Self::from_trace(ReplayTrace::load(&config.trace)?)
+    }
+
+    /// Build directly from an in-memory trace
+    pub fn from_trace(trace: ReplayTrace) -> Result<Self, TransportError> {
+        let mut queues: HashMap<Vec<u8>, ReplayQueue> = HashMap::new();
+        for (idx, exchange) in trace.exchanges.iter().enumerate() {
+            let request = decode_hex(&exchange.request).map_err(|e| {
+                TransportError::InvalidConfig(format!("exchange {}: request: {}", idx, e))
+            })?;
+            if request.is_empty() {
+                return Err(TransportError::InvalidConfig(format!(
+                    "exchange {}: empty request",
+                    idx
+                )));
+            }
+            let response = decode_hex(&exchange.response).map_err(|e| {
+                TransportError::InvalidConfig(format!("exchange {}: response: {}", idx, e))
+            })?;
+            queues
+                .entry(request)
+                .or_insert_with(|| ReplayQueue {
+                    responses: Vec::new(),
+                    next: 0,
+                })
+                .responses
+                .push(response);
+        }
+
+        tracing::info!(
+            ecu = trace.ecu.as_deref().unwrap_or("-"),
+            exchanges = trace.exchanges.len(),
+            distinct_requests = queues.len(),
+            "Loaded replay trace"
+        );
+
+        let (incoming_tx, _) = broadcast::channel(16);
+        Ok(Self {
+            queues: Mutex::new(queues),
+            incoming_tx,
+        })
+    }
+
+    fn replay(&self, request: &[u8]) -> Result<Vec<u8>, TransportError> {
+        self.queues
+            .lock()
+            .get_mut(request)
+            .map(ReplayQueue::next_response)
+            .ok_or_else(|| {
+                TransportError::ProtocolError(format!(
+                    "no recorded response for request {}",
+                    hex::encode_upper(request)
+                ))
+            })
+    }
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, hex::FromHexError> {
+    let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    hex::decode(compact)
+}
+
+#[async_trait]
+impl TransportAdapter for ReplayTransportAdapter {
+    async fn send_receive(
+        &self,
+        request: &[u8],
+        _timeout: Duration,
+    ) -> Result<Vec<u8>, TransportError> {
+        self.replay(request)
+    }
+
+    async fn send(&self, request: &[u8]) -> Result<(), TransportError> {
+        // Fire-and-forget requests (suppressed TesterPresent) carry no
+        // answer to replay; accept them whether or not they were captured.
+        tracing::debug!(request = %hex::encode_upper(request), "Replay transport: sent message");
+        Ok(())
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<IncomingMessage> {
+        self.incoming_tx.subscribe()
+    }
+
+    async fn is_connected(&self) -> bool {
+        true
+    }
+
+    async fn reconnect(&self) -> Result<(), TransportError> {
+        Ok(())
+    }
+
+    fn address_info(&self) -> AddressInfo {
+        AddressInfo::default()
+    }
+
+    fn stats(&self) -> sovd_core::TransportStats {
+        sovd_core::TransportStats {
+            kind: "replay".to_string(),
+            max_message_len: self.max_message_len(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use sovd_core::{BackendError, DiagnosticBackend};
+
+    use super::*;
+    use crate::config::{OperationConfig, TransportConfig, UdsBackendConfig};
+    use crate::UdsBackend;
+
+    /// A short capture: VIN, two successive RPM reads, and a self-test
+    /// routine that answered responsePending before completing.
+    const TRACE_YAML: &str = r#"
+ecu: engine_ecu
+exchanges:
+  - request: "22 F1 90"
+    response: "62 F1 90 57 46 30 58 58 58 47 43 44 58 31 32 33 34 35 36 37"
+  - request: "22 F4 0C"
+    response: "62 F4 0C 1C 20"
+  - request: "22 F4 0C"
+    response: "62 F4 0C 1D 4C"
+  - request: "31 01 02 03"
+    response: "7F 31 78"
+  - request: "31 01 02 03"
+    response: "71 01 02 03 00"
+"#;
+
+    fn replay_backend() -> UdsBackend {
+        let trace = ReplayTrace::from_yaml_str(TRACE_YAML).unwrap();
+        let transport = Arc::new(ReplayTransportAdapter::from_trace(trace).unwrap());
+        let config = UdsBackendConfig {
+            operations: vec![OperationConfig {
+                id: "self_test".to_string(),
+                name: "Self Test".to_string(),
+                rid: "0x0203".to_string(),
+                description: None,
+                security_level: 0,
+                required_session: None,
+                result_faults: vec![],
+                params_def: vec![],
+            }],
+            ..UdsBackendConfig::new(
+                "engine_ecu",
+                "Replayed ECU",
+                TransportConfig::Replay(ReplayConfig {
+                    trace: "inline".to_string(),
+                }),
+            )
+        };
+        UdsBackend::with_transport(config, transport).unwrap()
+    }
+
+    #[tokio::test]
+    async fn reads_return_recorded_values() {
+        let backend = replay_backend();
+
+        let vin = backend.read_raw_did(0xF190).await.unwrap();
+        assert_eq!(vin, b"WF0XXXGCDX1234567");
+
+        // Successive reads follow the capture, then the last value sticks.
+        assert_eq!(backend.read_raw_did(0xF40C).await.unwrap(), [0x1C, 0x20]);
+        assert_eq!(backend.read_raw_did(0xF40C).await.unwrap(), [0x1D, 0x4C]);
+        assert_eq!(backend.read_raw_did(0xF40C).await.unwrap(), [0x1D, 0x4C]);
+    }
+
+    #[tokio::test]
+    async fn operation_replays_through_response_pending() {
+        let backend = replay_backend();
+
+        let exec = backend.start_operation("self_test", &[]).await.unwrap();
+        assert_eq!(
+            exec.result,
+            Some(serde_json::json!({ "routine_result": "00" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn unmatched_request_is_an_error() {
+        let backend = replay_backend();
+
+        let err = backend.read_raw_did(0xF191).await.unwrap_err();
+        assert!(
+            matches!(&err, BackendError::Transport(msg) if msg.contains("22F191")),
+            "unrecorded DID must not be answered, got {err:?}"
+        );
+    }
+
+    #[test]
+    fn json_trace_loads() {
+        let trace = ReplayTrace::from_json_str(
+            r#"{"exchanges":[{"request":"2EF1A0","response":"6EF1A0"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(trace.ecu, None);
+        assert_eq!(trace.exchanges.len(), 1);
+        assert!(ReplayTransportAdapter::from_trace(trace).is_ok());
+    }
+
+    #[test]
+    fn invalid_hex_is_rejected() {
+        let trace =
+            ReplayTrace::from_json_str(r#"{"exchanges":[{"request":"22F1ZZ","response":"62"}]}"#)
+                .unwrap();
+        assert!(matches!(
+            ReplayTransportAdapter::from_trace(trace),
+            Err(TransportError::InvalidConfig(_))
+        ));
+    }
+}
@@ -128,6 +128,66 @@ pub async fn scan_can_bus(config: &ScanConfig) -> Result<Vec<DiscoveredEcu>, Tra
     Ok(ecus)
 }
 
+/// Scan several CAN interfaces concurrently and merge the results.
+///
+/// Each interface is scanned as by [`scan_can_bus`]. ECUs are deduplicated
+/// on `(interface, address)`, so the same address answering on two buses is
+/// reported as two ECUs, each tagged with its own interface. An interface
+/// that fails to scan is logged and skipped; the call only fails when every
+/// interface does.
+pub async fn scan_can_buses(
+    interfaces: &[String],
+    timeout_ms: u64,
+) -> Result<Vec<DiscoveredEcu>, TransportError> {
+    let scans: Vec<_> = interfaces
+        .iter()
+        .map(|interface| {
+            let config = ScanConfig {
+                interface: interface.clone(),
+                timeout_ms,
+            };
+            tokio::spawn(async move {
+                let result = scan_can_bus(&config).await;
+                (config.interface, result)
+            })
+        })
+        .collect();
+
+    let mut seen = HashSet::new();
+    let mut ecus = Vec::new();
+    let mut failures = 0;
+    let mut last_error = None;
+    for scan in scans {
+        let (interface, result) = scan
+            .await
+            .map_err(|e| TransportError::SendFailed(format!("Scan task join error: {}", e)))?;
+        match result {
+            Ok(found) => ecus.extend(
+                found
+                    .into_iter()
+                    .filter(|ecu| seen.insert((ecu.interface.clone(), ecu.address))),
+            ),
+            Err(e) => {
+                warn!(interface = %interface, error = %e, "CAN bus scan failed on interface");
+                failures += 1;
+                last_error = Some(e);
+            }
+        }
+    }
+
+    match last_error {
+        Some(e) if failures == interfaces.len() => Err(e),
+        _ => {
+            info!(
+                interfaces = interfaces.len(),
+                discovered = ecus.len(),
+                "Multi-interface CAN bus scan complete"
+            );
+            Ok(ecus)
+        }
+    }
+}
+
 /// Send a TesterPresent functional broadcast and collect unique ECU addresses.
 fn broadcast_tester_present(interface: &str, timeout: Duration) -> Result<Vec<u8>, TransportError> {
     let socket = CanSocket::open(interface).map_err(|e| {
@@ -299,3 +359,95 @@ fn read_did_string(socket: &mut IsoTpSocket, did: u16) -> Option<String> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+
+    /// Simulated ECU at `address` on a raw CAN socket: answers the functional
+    /// TesterPresent, reports `sw_version` for 0xF195 and rejects every other
+    /// DID read, all as single frames.
+    fn spawn_ecu(
+        interface: &'static str,
+        address: u8,
+        sw_version: &'static str,
+        stop: Arc<AtomicBool>,
+    ) -> std::thread::JoinHandle<()> {
+        let socket = CanSocket::open(interface).unwrap();
+        socket.set_nonblocking(true).unwrap();
+        let physical_rx = 0x18DA0000 | ((address as u32) << 8) | 0xF1;
+        let response_id = ExtendedId::new(RESPONSE_PREFIX | address as u32).unwrap();
+
+        std::thread::spawn(move || {
+            while !stop.load(Ordering::SeqCst) {
+                let Ok(frame) = socket.read_frame() else {
+                    std::thread::sleep(Duration::from_millis(1));
+                    continue;
+                };
+                let data = frame.data();
+                let mut reply = match (frame.raw_id(), data) {
+                    (FUNCTIONAL_CAN_ID, [_, 0x3E, ..]) => vec![0x02, 0x7E, 0x00],
+                    (id, [_, 0x22, 0xF1, 0x95, ..]) if id == physical_rx => {
+                        let mut reply = vec![3 + sw_version.len() as u8, 0x62, 0xF1, 0x95];
+                        reply.extend_from_slice(sw_version.as_bytes());
+                        reply
+                    }
+                    (id, [_, 0x22, ..]) if id == physical_rx => vec![0x03, 0x7F, 0x22, 0x31],
+                    _ => continue,
+                };
+                reply.resize(8, 0xCC);
+                let _ = socket.write_frame(&CanFrame::new(response_id, &reply).unwrap());
+            }
+        })
+    }
+
+    // Needs `vcan0` and `vcan1` interfaces (see sovd-tests); skipped when absent.
+    #[tokio::test]
+    async fn scans_interfaces_concurrently_and_tags_each_ecu() {
+        if !["vcan0", "vcan1"]
+            .iter()
+            .all(|i| std::path::Path::new(&format!("/sys/class/net/{i}")).exists())
+        {
+            eprintln!("vcan0/vcan1 not available, skipping");
+            return;
+        }
+
+        // The same address on both buses: two distinct ECUs.
+        let stop = Arc::new(AtomicBool::new(false));
+        let ecus = [
+            spawn_ecu("vcan0", 0x10, "A0", stop.clone()),
+            spawn_ecu("vcan1", 0x10, "B1", stop.clone()),
+        ];
+
+        let interfaces = ["vcan0".to_string(), "vcan1".to_string()];
+        let result = scan_can_buses(&interfaces, 300).await;
+        stop.store(true, Ordering::SeqCst);
+        for ecu in ecus {
+            ecu.join().unwrap();
+        }
+
+        let mut found: Vec<_> = result
+            .unwrap()
+            .into_iter()
+            .map(|ecu| (ecu.interface, ecu.address, ecu.software_version))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                ("vcan0".to_string(), 0x10, Some("A0".to_string())),
+                ("vcan1".to_string(), 0x10, Some("B1".to_string())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_only_when_every_interface_fails() {
+        let interfaces = ["sovd-no-such-can0".to_string()];
+        assert!(scan_can_buses(&interfaces, 10).await.is_err());
+        assert!(scan_can_buses(&[], 10).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+//! TransferData compression (RequestDownload dataFormatIdentifier)
+//!
+//! The high nibble of the dataFormatIdentifier sent with RequestDownload
+//! (0x34) names the compression method, the low nibble the encryption
+//! method; both are manufacturer-specific. Method 0x1 is raw deflate
+//! (RFC 1951). Every TransferData block is compressed on its own, so the
+//! ECU can inflate and program each block as it arrives.
+
+use std::borrow::Cow;
+use std::io::{self, Read, Write};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+/// Compression method of a download (`[ecu.*.flash] compression`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionMethod {
+    /// Blocks are sent as-is (method 0x0)
+    #[default]
+    None,
+    /// Raw deflate per block (method 0x1)
+    Deflate,
+}
+
+impl CompressionMethod {
+    /// Compression nibble of the dataFormatIdentifier
+    pub fn nibble(self) -> u8 {
+        match self {
+            Self::None => 0x0,
+            Self::Deflate => 0x1,
+        }
+    }
+
+    /// Method for a compression nibble; `None` for an unsupported method
+    pub fn from_nibble(nibble: u8) -> Option<Self> {
+        match nibble {
+            0x0 => Some(Self::None),
+            0x1 => Some(Self::Deflate),
+            _ => None,
+        }
+    }
+
+    /// dataFormatIdentifier for RequestDownload (no encryption)
+    pub fn data_format_identifier(self) -> u8 {
+        self.nibble() << 4
+    }
+
+    /// Uncompressed bytes to put in a block so that the compressed block
+    /// never exceeds `block_size`, even for incompressible data
+    pub fn raw_block_size(self, block_size: usize) -> usize {
+        match self {
+            Self::None => block_size,
+            // Deflate falls back to stored blocks (5 header bytes per
+            // 64 KiB) when compression does not pay off; leave generous
+            // room for that plus the end-of-stream marker.
+            Self::Deflate => block_size.saturating_sub(block_size / 256 + 16),
+        }
+    }
+
+    /// Compress one TransferData block
+    pub fn compress(self, data: &[u8]) -> Cow<'_, [u8]> {
+        match self {
+            Self::None => Cow::Borrowed(data),
+            Self::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+                // Writing into a Vec cannot fail
+                encoder
+                    .write_all(data)
+                    .and_then(|_| encoder.finish())
+                    .map(Cow::Owned)
+                    .expect("deflate into memory")
+            }
+        }
+    }
+
+    /// Inflate one TransferData block, refusing output beyond `limit` bytes
+    pub fn decompress(self, data: &[u8], limit: usize) -> io::Result<Cow<'_, [u8]>> {
+        let out = match self {
+            Self::None => Cow::Borrowed(data),
+            Self::Deflate => {
+                let mut out = Vec::new();
+                DeflateDecoder::new(data)
+                    .take(limit as u64 + 1)
+                    .read_to_end(&mut out)?;
+                Cow::Owned(out)
+            }
+        };
+        if out.len() > limit {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("block inflates past the {} bytes expected", limit),
+            ));
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_format_identifier_round_trips() {
+        assert_eq!(CompressionMethod::None.data_format_identifier(), 0x00);
+        assert_eq!(CompressionMethod::Deflate.data_format_identifier(), 0x10);
+        assert_eq!(
+            CompressionMethod::from_nibble(0x10 >> 4),
+            Some(CompressionMethod::Deflate)
+        );
+        assert_eq!(CompressionMethod::from_nibble(0x2), None);
+    }
+
+    #[test]
+    fn deflate_block_round_trips() {
+        let data: Vec<u8> = (0..4000u32).map(|i| (i / 16) as u8).collect();
+        let packed = CompressionMethod::Deflate.compress(&data);
+        assert!(packed.len() < data.len() / 4, "{} bytes", packed.len());
+        let unpacked = CompressionMethod::Deflate
+            .decompress(&packed, data.len())
+            .unwrap();
+        assert_eq!(unpacked, data.as_slice());
+
+        // More output than the download announced is refused
+        assert!(CompressionMethod::Deflate
+            .decompress(&packed, data.len() - 1)
+            .is_err());
+    }
+
+    #[test]
+    fn incompressible_block_fits_raw_block_size() {
+        // xorshift noise does not compress
+        let mut x = 0x2545_F491u32;
+        let block_size = 4093;
+        let raw = CompressionMethod::Deflate.raw_block_size(block_size);
+        let data: Vec<u8> = (0..raw)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                x as u8
+            })
+            .collect();
+        assert!(CompressionMethod::Deflate.compress(&data).len() <= block_size);
+    }
+}
@@ -18,8 +18,34 @@ pub mod sub_function {
     pub const REPORT_DTC_STORED_DATA_BY_RECORD_NUMBER: u8 = 0x05;
     /// Report DTC extended data record by DTC number
     pub const REPORT_DTC_EXTENDED_DATA_RECORD_BY_DTC_NUMBER: u8 = 0x06;
+    /// Report DTCs matching a severity and status mask, with their severity
+    pub const REPORT_DTC_BY_SEVERITY_MASK_RECORD: u8 = 0x08;
     /// Report supported DTCs
     pub const REPORT_SUPPORTED_DTC: u8 = 0x0A;
+    /// Report DTCs matching a status mask from a user-defined memory
+    pub const REPORT_USER_DEF_MEMORY_DTC_BY_STATUS_MASK: u8 = 0x17;
+    /// Report DTC snapshot record by DTC number from a user-defined memory
+    pub const REPORT_USER_DEF_MEMORY_DTC_SNAPSHOT_RECORD_BY_DTC_NUMBER: u8 = 0x18;
+}
+
+/// DTCSeverity bits reported by sub-function 0x08 per ISO 14229-1
+pub mod severity_bit {
+    /// Maintenance only - fix at the next service
+    pub const MAINTENANCE_ONLY: u8 = 0x20;
+    /// Check at next halt
+    pub const CHECK_AT_NEXT_HALT: u8 = 0x40;
+    /// Check immediately
+    pub const CHECK_IMMEDIATELY: u8 = 0x80;
+
+    /// Bit for a snake_case severity name (`check_immediately`)
+    pub fn from_name(name: &str) -> Option<u8> {
+        match name {
+            "maintenance_only" => Some(MAINTENANCE_ONLY),
+            "check_at_next_halt" => Some(CHECK_AT_NEXT_HALT),
+            "check_immediately" => Some(CHECK_IMMEDIATELY),
+            _ => None,
+        }
+    }
 }
 
 /// DTC group addresses for ClearDiagnosticInformation (0x14)
@@ -61,6 +87,24 @@ pub mod status_bit {
     pub const CONFIRMED_MASK: u8 = CONFIRMED_DTC;
     /// Common mask for pending faults
     pub const PENDING_MASK: u8 = PENDING_DTC;
+
+    /// Bit for a snake_case status name (`confirmed`,
+    /// `test_failed_this_operation_cycle`)
+    pub fn from_name(name: &str) -> Option<u8> {
+        match name {
+            "test_failed" => Some(TEST_FAILED),
+            "test_failed_this_operation_cycle" => Some(TEST_FAILED_THIS_OPERATION_CYCLE),
+            "pending" => Some(PENDING_DTC),
+            "confirmed" => Some(CONFIRMED_DTC),
+            "test_not_completed_since_last_clear" => Some(TEST_NOT_COMPLETED_SINCE_LAST_CLEAR),
+            "test_failed_since_last_clear" => Some(TEST_FAILED_SINCE_LAST_CLEAR),
+            "test_not_completed_this_operation_cycle" => {
+                Some(TEST_NOT_COMPLETED_THIS_OPERATION_CYCLE)
+            }
+            "warning_indicator_requested" => Some(WARNING_INDICATOR_REQUESTED),
+            _ => None,
+        }
+    }
 }
 
 /// DTC category based on the first character of the DTC code
@@ -156,6 +200,12 @@ impl DtcStatus {
         }
     }
 
+    /// Drop the bits the ECU does not support (per its
+    /// DTCStatusAvailabilityMask), so unsupported bits read as clear
+    pub fn masked(&self, availability_mask: u8) -> Self {
+        Self::from_byte(self.raw & availability_mask)
+    }
+
     /// Check if this DTC is currently active (test failed + confirmed)
     pub fn is_active(&self) -> bool {
         self.test_failed && self.confirmed_dtc
@@ -328,6 +378,72 @@ pub fn parse_dtc_by_status_mask_response(response: &[u8]) -> Result<(u8, Vec<Dtc
     Ok((status_availability_mask, dtcs))
 }
 
+/// Parse response from sub-function 0x08 (reportDTCBySeverityMaskRecord)
+///
+/// Returns the status availability mask and every DTC with its DTCSeverity
+/// byte.
+pub fn parse_dtc_by_severity_mask_response(
+    response: &[u8],
+) -> Result<(u8, Vec<(u8, Dtc)>), String> {
+    // Response: 0x59 0x08 [statusAvailabilityMask] {[DTCSeverity] [DTCFunctionalUnit] [DTCHighByte] [DTCMiddleByte] [DTCLowByte] [statusOfDTC]}*
+    if response.len() < 3 {
+        return Err(format!("Response too short: {} bytes", response.len()));
+    }
+
+    if response[0] != 0x59 {
+        return Err(format!("Invalid response SID: 0x{:02X}", response[0]));
+    }
+
+    if response[1] != sub_function::REPORT_DTC_BY_SEVERITY_MASK_RECORD {
+        return Err(format!("Invalid sub-function: 0x{:02X}", response[1]));
+    }
+
+    let status_availability_mask = response[2];
+    let dtcs = response[3..]
+        .chunks_exact(6)
+        .map(|chunk| (chunk[0], Dtc::new(chunk[2], chunk[3], chunk[4], chunk[5])))
+        .collect();
+
+    Ok((status_availability_mask, dtcs))
+}
+
+/// Parse response from sub-function 0x17 (reportUserDefMemoryDTCByStatusMask)
+///
+/// Returns the status availability mask and the DTCs, after checking the
+/// ECU answered for the requested `memory_selection`.
+pub fn parse_user_def_memory_dtc_by_status_mask_response(
+    response: &[u8],
+    memory_selection: u8,
+) -> Result<(u8, Vec<Dtc>), String> {
+    // Response: 0x59 0x17 [MemorySelection] [statusAvailabilityMask] {[DTCHighByte] [DTCMiddleByte] [DTCLowByte] [statusOfDTC]}*
+    if response.len() < 4 {
+        return Err(format!("Response too short: {} bytes", response.len()));
+    }
+
+    if response[0] != 0x59 {
+        return Err(format!("Invalid response SID: 0x{:02X}", response[0]));
+    }
+
+    if response[1] != sub_function::REPORT_USER_DEF_MEMORY_DTC_BY_STATUS_MASK {
+        return Err(format!("Invalid sub-function: 0x{:02X}", response[1]));
+    }
+
+    if response[2] != memory_selection {
+        return Err(format!(
+            "Memory selection mismatch: requested 0x{:02X}, got 0x{:02X}",
+            memory_selection, response[2]
+        ));
+    }
+
+    let status_availability_mask = response[3];
+    let dtcs = response[4..]
+        .chunks_exact(4)
+        .map(|chunk| Dtc::new(chunk[0], chunk[1], chunk[2], chunk[3]))
+        .collect();
+
+    Ok((status_availability_mask, dtcs))
+}
+
 /// Parse response from sub-function 0x04 (reportDTCSnapshotRecordByDTCNumber)
 pub fn parse_dtc_snapshot_response(
     response: &[u8],
@@ -368,6 +484,124 @@ pub fn parse_dtc_snapshot_response(
     Ok((dtc, records))
 }
 
+/// Why a sub-function 0x04 response could not be split into DID values
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SnapshotParseError {
+    /// The DID closing `record_number` of an all-records answer has no
+    /// known length; read on its own, the record can give it the rest
+    #[error("Unknown length of DID 0x{did:04X} closing snapshot record {record_number}")]
+    UnsizedLastDid { record_number: u8, did: u16 },
+
+    #[error("{0}")]
+    Malformed(String),
+}
+
+/// Parse response from sub-function 0x04 into its individual DID values
+///
+/// Snapshot records store DID values back to back without lengths, so
+/// `did_len` must size each DID. A DID it cannot size is only accepted as
+/// the last identifier of the final record, where it takes the remaining
+/// bytes. That record is only known to be the final one when the request
+/// asked for a single `record_number`; the records of an 0xFF (all
+/// records) answer carry no count, so there the DID is reported as
+/// [`SnapshotParseError::UnsizedLastDid`] and that record can be read on
+/// its own.
+pub fn parse_dtc_snapshot_records(
+    response: &[u8],
+    record_number: u8,
+    did_len: impl Fn(u16) -> Option<usize>,
+) -> Result<(Dtc, Vec<DtcSnapshotRecord>), SnapshotParseError> {
+    // Response: 0x59 0x04 [DTCHigh] [DTCMid] [DTCLow] [statusOfDTC] {[SnapshotRecordNumber] [NumberOfIdentifiers] {[DID_HI] [DID_LO] [data...]}*}*
+    if response.len() < 6 {
+        return Err(SnapshotParseError::Malformed(format!(
+            "Response too short: {} bytes",
+            response.len()
+        )));
+    }
+
+    if response[0] != 0x59 {
+        return Err(SnapshotParseError::Malformed(format!(
+            "Invalid response SID: 0x{:02X}",
+            response[0]
+        )));
+    }
+
+    if response[1] != sub_function::REPORT_DTC_SNAPSHOT_RECORD_BY_DTC_NUMBER {
+        return Err(SnapshotParseError::Malformed(format!(
+            "Invalid sub-function: 0x{:02X}",
+            response[1]
+        )));
+    }
+
+    let dtc = Dtc::new(response[2], response[3], response[4], response[5]);
+
+    let single_record = record_number != 0xFF;
+    let mut records = Vec::new();
+    let mut pos = 6;
+    while pos < response.len() {
+        if pos + 2 > response.len() {
+            return Err(SnapshotParseError::Malformed(format!(
+                "Truncated snapshot record header at byte {}",
+                pos
+            )));
+        }
+        let record_number = response[pos];
+        let number_of_identifiers = response[pos + 1];
+        pos += 2;
+
+        let mut data = Vec::with_capacity(number_of_identifiers as usize);
+        for i in 0..number_of_identifiers {
+            if pos + 2 > response.len() {
+                return Err(SnapshotParseError::Malformed(format!(
+                    "Snapshot record {} ends before identifier {} of {}",
+                    record_number,
+                    i + 1,
+                    number_of_identifiers
+                )));
+            }
+            let did = u16::from_be_bytes([response[pos], response[pos + 1]]);
+            pos += 2;
+            let len = match did_len(did) {
+                Some(len) => len,
+                None if i + 1 == number_of_identifiers => {
+                    if !single_record {
+                        return Err(SnapshotParseError::UnsizedLastDid { record_number, did });
+                    }
+                    response.len() - pos
+                }
+                None => {
+                    return Err(SnapshotParseError::Malformed(format!(
+                        "Unknown length of DID 0x{:04X} in snapshot record {}",
+                        did, record_number
+                    )))
+                }
+            };
+            if pos + len > response.len() {
+                return Err(SnapshotParseError::Malformed(format!(
+                    "DID 0x{:04X} in snapshot record {} needs {} bytes, {} left",
+                    did,
+                    record_number,
+                    len,
+                    response.len() - pos
+                )));
+            }
+            data.push(SnapshotDataItem {
+                did,
+                data: response[pos..pos + len].to_vec(),
+            });
+            pos += len;
+        }
+
+        records.push(DtcSnapshotRecord {
+            record_number,
+            number_of_identifiers,
+            data,
+        });
+    }
+
+    Ok((dtc, records))
+}
+
 /// Parse response from sub-function 0x06 (reportDTCExtendedDataRecordByDTCNumber)
 pub fn parse_dtc_extended_data_response(
     response: &[u8],
@@ -405,10 +639,79 @@ pub fn parse_dtc_extended_data_response(
     Ok((dtc, records))
 }
 
+/// Parse response from sub-function 0x06 into its individual records
+///
+/// Records follow each other without lengths, so `record_len` must size
+/// each record. A record it cannot size takes the remaining bytes, which is
+/// only right when it is the last one of the response.
+pub fn parse_dtc_extended_data_records(
+    response: &[u8],
+    record_len: impl Fn(u8) -> Option<usize>,
+) -> Result<(Dtc, Vec<DtcExtendedDataRecord>), String> {
+    // Response: 0x59 0x06 [DTCHigh] [DTCMid] [DTCLow] [statusOfDTC] {[ExtendedDataRecordNumber] [data...]}*
+    if response.len() < 6 {
+        return Err(format!("Response too short: {} bytes", response.len()));
+    }
+
+    if response[0] != 0x59 {
+        return Err(format!("Invalid response SID: 0x{:02X}", response[0]));
+    }
+
+    if response[1] != sub_function::REPORT_DTC_EXTENDED_DATA_RECORD_BY_DTC_NUMBER {
+        return Err(format!("Invalid sub-function: 0x{:02X}", response[1]));
+    }
+
+    let dtc = Dtc::new(response[2], response[3], response[4], response[5]);
+
+    let mut records = Vec::new();
+    let mut pos = 6;
+    while pos < response.len() {
+        let record_number = response[pos];
+        pos += 1;
+        let remaining = response.len() - pos;
+        let len = match record_len(record_number) {
+            Some(len) if len > remaining => {
+                return Err(format!(
+                    "Extended data record 0x{:02X} needs {} bytes, {} left",
+                    record_number, len, remaining
+                ))
+            }
+            Some(len) => len,
+            None => remaining,
+        };
+        records.push(DtcExtendedDataRecord {
+            record_number,
+            data: response[pos..pos + len].to_vec(),
+        });
+        pos += len;
+    }
+
+    Ok((dtc, records))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_dtc_by_severity_mask_response() {
+        let response = vec![
+            0x59, 0x08, 0xFF, // Header + status availability mask
+            0x80, 0x10, 0x01, 0x23, 0x45, 0x09, // check immediately
+            0x20, 0x10, 0xC1, 0x00, 0x01, 0x08, // maintenance only
+        ];
+        let (mask, dtcs) = parse_dtc_by_severity_mask_response(&response).unwrap();
+        assert_eq!(mask, 0xFF);
+        assert_eq!(dtcs.len(), 2);
+        assert_eq!(dtcs[0].0, severity_bit::CHECK_IMMEDIATELY);
+        assert_eq!(dtcs[0].1.dtc_number, [0x01, 0x23, 0x45]);
+        assert_eq!(dtcs[1].0, severity_bit::MAINTENANCE_ONLY);
+        assert_eq!(dtcs[1].1.status.raw, 0x08);
+
+        // A status-mask answer is not a severity report
+        assert!(parse_dtc_by_severity_mask_response(&[0x59, 0x02, 0xFF]).is_err());
+    }
+
     #[test]
     fn test_dtc_code_string_powertrain() {
         // P0101 = 0x01 0x01 0x00
@@ -496,4 +799,135 @@ mod tests {
         assert_eq!(dtcs[1].to_code_string(), "C0420");
         assert!(dtcs[1].status.pending_dtc);
     }
+
+    #[test]
+    fn test_parse_user_def_memory_dtc_by_status_mask_response() {
+        let response = vec![
+            0x59, 0x17, 0x01, 0xFF, // Header + memory selection + availability mask
+            0x01, 0x01, 0x00, 0x08, // P0101, confirmed (stored, not active)
+        ];
+        let (mask, dtcs) =
+            parse_user_def_memory_dtc_by_status_mask_response(&response, 0x01).unwrap();
+        assert_eq!(mask, 0xFF);
+        assert_eq!(dtcs.len(), 1);
+        assert_eq!(dtcs[0].to_code_string(), "P0101");
+
+        // Answer for a different memory than requested is rejected.
+        assert!(parse_user_def_memory_dtc_by_status_mask_response(&response, 0x02).is_err());
+    }
+
+    #[test]
+    fn test_parse_dtc_snapshot_records() {
+        let response = vec![
+            0x59, 0x04, 0x01, 0x23, 0x45, 0x08, // DTC + status
+            0x01, 0x02, 0xF1, 0x90, 0x0A, 0x0B, 0xF1, 0x91, 0x0C, // record 1
+            0x02, 0x01, 0xF1, 0x91, 0x0D, // record 2
+        ];
+        let lengths = |did| match did {
+            0xF190 => Some(2),
+            0xF191 => Some(1),
+            _ => None,
+        };
+        let (dtc, records) = parse_dtc_snapshot_records(&response, 0xFF, lengths).unwrap();
+        assert_eq!(dtc.dtc_number, [0x01, 0x23, 0x45]);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].record_number, 1);
+        assert_eq!(records[0].data.len(), 2);
+        assert_eq!(records[0].data[0].data, vec![0x0A, 0x0B]);
+        assert_eq!(records[0].data[1].did, 0xF191);
+        assert_eq!(records[1].record_number, 2);
+        assert_eq!(records[1].data[0].data, vec![0x0D]);
+
+        // Too few bytes for a known length
+        assert!(parse_dtc_snapshot_records(&response[..9], 0xFF, lengths).is_err());
+    }
+
+    #[test]
+    fn test_parse_dtc_snapshot_records_unknown_length() {
+        // The last DID of a single requested record may take the remaining bytes
+        let response = vec![
+            0x59, 0x04, 0x01, 0x23, 0x45, 0x08, 0x01, 0x01, 0xF1, 0x90, 0x0A, 0x0B,
+        ];
+        let (_, records) = parse_dtc_snapshot_records(&response, 0x01, |_| None).unwrap();
+        assert_eq!(records[0].data[0].data, vec![0x0A, 0x0B]);
+
+        // Anywhere else it cannot be split
+        let response = vec![
+            0x59, 0x04, 0x01, 0x23, 0x45, 0x08, 0x01, 0x02, 0xF1, 0x90, 0x0A, 0xF1, 0x91, 0x0B,
+        ];
+        assert!(parse_dtc_snapshot_records(&response, 0x01, |_| None).is_err());
+    }
+
+    #[test]
+    fn test_parse_dtc_snapshot_records_unknown_length_before_next_record() {
+        // Record 1 ends in a DID of unknown length, record 2 follows it
+        let response = vec![
+            0x59, 0x04, 0x01, 0x23, 0x45, 0x08, // DTC + status
+            0x01, 0x01, 0xF1, 0x90, 0x0A, 0x0B, // record 1
+            0x02, 0x01, 0xF1, 0x91, 0x0C, // record 2
+        ];
+        let lengths = |did| match did {
+            0xF191 => Some(1),
+            _ => None,
+        };
+        // Taking the remaining bytes would swallow record 2
+        assert_eq!(
+            parse_dtc_snapshot_records(&response, 0xFF, lengths).unwrap_err(),
+            SnapshotParseError::UnsizedLastDid {
+                record_number: 1,
+                did: 0xF190
+            }
+        );
+
+        // Sized, both records come out
+        let lengths = |did| match did {
+            0xF190 => Some(2),
+            0xF191 => Some(1),
+            _ => None,
+        };
+        let (_, records) = parse_dtc_snapshot_records(&response, 0xFF, lengths).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].data[0].data, vec![0x0A, 0x0B]);
+        assert_eq!(records[1].data[0].data, vec![0x0C]);
+    }
+
+    #[test]
+    fn test_parse_dtc_extended_data_records() {
+        let response = vec![
+            0x59, 0x06, 0x01, 0x23, 0x45, 0x08, // DTC + status
+            0x01, 0x05, // occurrence counter
+            0x02, 0x00, 0x10, // 2-byte aging counter
+            0x90, 0xDE, 0xAD, 0xBE, // unsized OEM record, last
+        ];
+        let lengths = |record| match record {
+            0x01 => Some(1),
+            0x02 => Some(2),
+            _ => None,
+        };
+        let (dtc, records) = parse_dtc_extended_data_records(&response, lengths).unwrap();
+        assert_eq!(dtc.dtc_number, [0x01, 0x23, 0x45]);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].record_number, 0x01);
+        assert_eq!(records[0].data, vec![0x05]);
+        assert_eq!(records[1].data, vec![0x00, 0x10]);
+        assert_eq!(records[2].record_number, 0x90);
+        assert_eq!(records[2].data, vec![0xDE, 0xAD, 0xBE]);
+
+        // No records stored
+        let (_, records) = parse_dtc_extended_data_records(&response[..6], lengths).unwrap();
+        assert!(records.is_empty());
+
+        // Fewer bytes than the record's length
+        assert!(parse_dtc_extended_data_records(&response[..9], lengths).is_err());
+    }
+
+    #[test]
+    fn test_dtc_status_masked_by_availability() {
+        // ECU reports testFailed + confirmed, but only supports confirmed.
+        let status = DtcStatus::from_byte(0x09).masked(status_bit::CONFIRMED_DTC);
+        assert_eq!(status.raw, 0x08);
+        assert!(!status.test_failed);
+        assert!(status.confirmed_dtc);
+        assert!(!status.is_active());
+    }
 }
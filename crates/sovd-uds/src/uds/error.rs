@@ -12,12 +12,30 @@ pub enum UdsError {
         nrc: NegativeResponseCode,
     },
 
+    /// NRC 0x31 (requestOutOfRange) from a memory access: the address or
+    /// length lies outside what the ECU allows, kept apart from
+    /// [`Self::NegativeResponse`] so callers can tell it from other refusals
+    #[error("Request out of range for service 0x{service_id:02X}")]
+    RequestOutOfRange { service_id: u8 },
+
+    /// The service is denied by the backend's service policy; nothing was
+    /// sent
+    #[error("Service 0x{service_id:02X} is denied for this ECU")]
+    ServiceDenied { service_id: u8 },
+
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
 
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
     #[error("Response timeout")]
     Timeout,
 
+    /// The ECU's request queue is full; nothing was sent
+    #[error("Request queue full: {waiting} requests waiting for the ECU")]
+    QueueFull { waiting: usize },
+
     #[error("Transport error: {0}")]
     Transport(String),
 
@@ -0,0 +1,110 @@
+//! Software fingerprint decoding (DIDs 0xF183–0xF185)
+//!
+//! ISO 14229-1 leaves the fingerprint record layout to the manufacturer.
+//! The common layouts start with the programming date as three BCD bytes
+//! (`YY MM DD`) followed by the tester serial number; the format name in
+//! [`crate::config::FingerprintConfig`] selects how the serial is encoded.
+
+/// Date as BCD `YY MM DD`, tester serial as ASCII
+pub const FORMAT_BCD_DATE_ASCII: &str = "bcd-date-ascii";
+/// Date as BCD `YY MM DD`, tester serial as raw bytes (rendered hex)
+pub const FORMAT_BCD_DATE_HEX: &str = "bcd-date-hex";
+
+/// Fingerprint DIDs with the type reported for each: (did, type)
+pub const FINGERPRINT_DIDS: &[(u16, &str)] = &[
+    (
+        super::standard_did::BOOT_SOFTWARE_FINGERPRINT,
+        "boot_software",
+    ),
+    (
+        super::standard_did::APP_SOFTWARE_FINGERPRINT,
+        "application_software",
+    ),
+    (
+        super::standard_did::APP_DATA_FINGERPRINT,
+        "application_data",
+    ),
+];
+
+/// Fields decoded from a fingerprint record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedFingerprint {
+    /// Programming date, `YYYY-MM-DD`
+    pub date: String,
+    /// Serial number of the programming tester
+    pub tester_serial: String,
+}
+
+/// Decode `data` laid out as `format`; `None` for an unknown format or a
+/// record that does not fit it
+pub fn decode_fingerprint(format: &str, data: &[u8]) -> Option<DecodedFingerprint> {
+    let (date, serial) = (data.get(..3)?, data.get(3..)?);
+    let tester_serial = match format {
+        FORMAT_BCD_DATE_ASCII => String::from_utf8_lossy(serial)
+            .trim_end_matches(['\0', ' '])
+            .to_string(),
+        FORMAT_BCD_DATE_HEX => hex::encode_upper(serial),
+        _ => return None,
+    };
+    Some(DecodedFingerprint {
+        date: bcd_date(date)?,
+        tester_serial,
+    })
+}
+
+/// `YY MM DD` in BCD → `20YY-MM-DD`
+fn bcd_date(bytes: &[u8]) -> Option<String> {
+    let [year, month, day] = [bcd(bytes[0])?, bcd(bytes[1])?, bcd(bytes[2])?];
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(format!("20{:02}-{:02}-{:02}", year, month, day))
+}
+
+fn bcd(byte: u8) -> Option<u8> {
+    let (high, low) = (byte >> 4, byte & 0x0F);
+    (high < 10 && low < 10).then_some(high * 10 + low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_bcd_date_and_ascii_serial() {
+        let data = [0x24, 0x03, 0x15, b'T', b'S', b'1', b'2', b'3', 0x00];
+        assert_eq!(
+            decode_fingerprint(FORMAT_BCD_DATE_ASCII, &data),
+            Some(DecodedFingerprint {
+                date: "2024-03-15".to_string(),
+                tester_serial: "TS123".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn decodes_hex_serial() {
+        let data = [0x19, 0x12, 0x31, 0x00, 0x0A, 0xBC];
+        let decoded = decode_fingerprint(FORMAT_BCD_DATE_HEX, &data).unwrap();
+        assert_eq!(decoded.date, "2019-12-31");
+        assert_eq!(decoded.tester_serial, "000ABC");
+    }
+
+    #[test]
+    fn unknown_format_or_bad_record_is_not_decoded() {
+        let data = [0x24, 0x03, 0x15, b'T'];
+        assert_eq!(decode_fingerprint("oem-special", &data), None);
+        // Not BCD
+        assert_eq!(
+            decode_fingerprint(FORMAT_BCD_DATE_ASCII, &[0x24, 0x0A, 0x15]),
+            None
+        );
+        // Month 13
+        assert_eq!(
+            decode_fingerprint(FORMAT_BCD_DATE_ASCII, &[0x24, 0x13, 0x15]),
+            None
+        );
+        // Too short for a date
+        assert_eq!(decode_fingerprint(FORMAT_BCD_DATE_ASCII, &[0x24]), None);
+    }
+}
@@ -2,18 +2,22 @@
 //!
 //! This module provides the UDS protocol layer for communicating with ECUs.
 
+pub mod compression;
 pub mod dtc;
 mod error;
-mod nrc;
+pub mod fingerprint;
+mod queue;
 mod services;
 
+pub use compression::CompressionMethod;
 pub use dtc::{
     dtc_group, status_bit as dtc_status_bit, sub_function as dtc_sub_function, Dtc, DtcCategory,
     DtcCountResult, DtcExtendedDataRecord, DtcSnapshotRecord, DtcStatus,
 };
 pub use error::UdsError;
-pub use nrc::NegativeResponseCode;
-pub use services::UdsService;
+pub use queue::{QueueTurn, RequestQueue};
+pub use services::{SessionTiming, UdsService};
+pub use sovd_core::NegativeResponseCode;
 
 /// RoutineControl (0x31) sub-functions
 pub mod routine_sub_function {
@@ -96,6 +100,26 @@ pub mod control_dtc_setting_sub_function {
     pub const OFF: u8 = 0x02;
 }
 
+/// ResponseOnEvent (0x86) eventType sub-functions (ISO 14229-1).
+pub mod roe_event_type {
+    /// stopResponseOnEvent
+    pub const STOP: u8 = 0x00;
+    /// onDTCStatusChange
+    pub const ON_DTC_STATUS_CHANGE: u8 = 0x01;
+    /// onChangeOfDataIdentifier
+    pub const ON_CHANGE_OF_DATA_IDENTIFIER: u8 = 0x03;
+    /// startResponseOnEvent
+    pub const START: u8 = 0x05;
+    /// clearResponseOnEvent
+    pub const CLEAR: u8 = 0x06;
+}
+
+/// ResponseOnEvent (0x86) eventWindowTime values (ISO 14229-1).
+pub mod roe_event_window {
+    /// infiniteTimeToResponse: events fire until stopped
+    pub const INFINITE: u8 = 0x02;
+}
+
 /// ECUReset (0x11) sub-functions
 pub mod reset_type {
     /// Hard reset - complete shutdown and restart of ECU
@@ -125,6 +149,7 @@ pub mod service_id {
     pub const REQUEST_UPLOAD: u8 = 0x35;
     pub const TRANSFER_DATA: u8 = 0x36;
     pub const REQUEST_TRANSFER_EXIT: u8 = 0x37;
+    pub const WRITE_MEMORY_BY_ADDRESS: u8 = 0x3D;
     pub const TESTER_PRESENT: u8 = 0x3E;
     pub const CONTROL_DTC_SETTING: u8 = 0x85;
     pub const RESPONSE_ON_EVENT: u8 = 0x86;
@@ -239,6 +264,7 @@ pub struct ServiceIds {
     pub clear_diagnostic_info: u8,
     pub read_dtc_info: u8,
     pub read_data_by_id: u8,
+    pub read_memory_by_address: u8,
     pub security_access: u8,
     pub communication_control: u8,
     pub control_dtc_setting: u8,
@@ -251,6 +277,7 @@ pub struct ServiceIds {
     pub request_upload: u8,
     pub transfer_data: u8,
     pub request_transfer_exit: u8,
+    pub write_memory_by_address: u8,
     pub tester_present: u8,
     pub link_control: u8,
     pub negative_response: u8,
@@ -264,6 +291,7 @@ impl Default for ServiceIds {
             clear_diagnostic_info: service_id::CLEAR_DIAGNOSTIC_INFO,
             read_dtc_info: service_id::READ_DTC_INFO,
             read_data_by_id: service_id::READ_DATA_BY_ID,
+            read_memory_by_address: service_id::READ_MEMORY_BY_ADDRESS,
             security_access: service_id::SECURITY_ACCESS,
             communication_control: service_id::COMMUNICATION_CONTROL,
             control_dtc_setting: service_id::CONTROL_DTC_SETTING,
@@ -276,6 +304,7 @@ impl Default for ServiceIds {
             request_upload: service_id::REQUEST_UPLOAD,
             transfer_data: service_id::TRANSFER_DATA,
             request_transfer_exit: service_id::REQUEST_TRANSFER_EXIT,
+            write_memory_by_address: service_id::WRITE_MEMORY_BY_ADDRESS,
             tester_present: service_id::TESTER_PRESENT,
             link_control: service_id::LINK_CONTROL,
             negative_response: service_id::NEGATIVE_RESPONSE,
@@ -303,6 +332,9 @@ impl ServiceIds {
         if let Some(v) = overrides.read_data_by_id {
             ids.read_data_by_id = v;
         }
+        if let Some(v) = overrides.read_memory_by_address {
+            ids.read_memory_by_address = v;
+        }
         if let Some(v) = overrides.security_access {
             ids.security_access = v;
         }
@@ -333,6 +365,9 @@ impl ServiceIds {
         if let Some(v) = overrides.request_transfer_exit {
             ids.request_transfer_exit = v;
         }
+        if let Some(v) = overrides.write_memory_by_address {
+            ids.write_memory_by_address = v;
+        }
         if let Some(v) = overrides.tester_present {
             ids.tester_present = v;
         }
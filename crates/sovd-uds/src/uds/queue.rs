@@ -0,0 +1,151 @@
+//! Per-ECU request queue
+//!
+//! One ECU answers one request at a time, so every exchange with it takes a
+//! turn from the ECU's [`RequestQueue`]: a tokio mutex, which hands the
+//! turns out in the order they were asked for, and a count of the requests
+//! holding or waiting for one, bounded by [`RequestQueueConfig::max_depth`].
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::sync::{Mutex, MutexGuard};
+
+use super::UdsError;
+use crate::config::RequestQueueConfig;
+
+/// FIFO of the requests to one ECU, shared by every service talking to it
+#[derive(Debug)]
+pub struct RequestQueue {
+    turn: Mutex<()>,
+    /// Requests holding or waiting for a turn
+    pending: AtomicUsize,
+    max_depth: usize,
+}
+
+impl Default for RequestQueue {
+    fn default() -> Self {
+        Self::new(&RequestQueueConfig::default())
+    }
+}
+
+impl RequestQueue {
+    pub fn new(config: &RequestQueueConfig) -> Self {
+        Self {
+            turn: Mutex::new(()),
+            pending: AtomicUsize::new(0),
+            max_depth: config.max_depth,
+        }
+    }
+
+    /// Wait for this request's turn with the ECU, after every request queued
+    /// before it
+    ///
+    /// Fails with [`UdsError::QueueFull`] instead of waiting when
+    /// `max_depth` requests are waiting already.
+    pub async fn acquire(&self) -> Result<QueueTurn<'_>, UdsError> {
+        let ahead = self.pending.fetch_add(1, Ordering::SeqCst);
+        // Counted from here on, so a request dropped while waiting leaves
+        // the queue too
+        let pending = Pending(&self.pending);
+        // One of those ahead is on the wire, the rest are waiting
+        if ahead > self.max_depth {
+            return Err(UdsError::QueueFull { waiting: ahead - 1 });
+        }
+        let turn = self.turn.lock().await;
+        Ok(QueueTurn {
+            _turn: turn,
+            _pending: pending,
+        })
+    }
+
+    /// The turn, if no request holds or waits for one
+    pub fn try_acquire(&self) -> Option<MutexGuard<'_, ()>> {
+        self.turn.try_lock().ok()
+    }
+
+    /// Requests holding or waiting for a turn
+    pub fn depth(&self) -> usize {
+        self.pending.load(Ordering::SeqCst)
+    }
+}
+
+struct Pending<'a>(&'a AtomicUsize);
+
+impl Drop for Pending<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A request's turn with the ECU; the next request's starts when it is
+/// dropped
+pub struct QueueTurn<'a> {
+    _turn: MutexGuard<'a, ()>,
+    _pending: Pending<'a>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn queue(max_depth: usize) -> Arc<RequestQueue> {
+        Arc::new(RequestQueue::new(&RequestQueueConfig { max_depth }))
+    }
+
+    #[tokio::test]
+    async fn turns_are_taken_in_order() {
+        let queue = queue(4);
+        let order = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let first = queue.acquire().await.unwrap();
+
+        let mut waiters = Vec::new();
+        for n in 0..3 {
+            let (queue, order) = (queue.clone(), order.clone());
+            waiters.push(tokio::spawn(async move {
+                let _turn = queue.acquire().await.unwrap();
+                order.lock().push(n);
+            }));
+            // Let each one queue up before the next
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(queue.depth(), 4);
+
+        drop(first);
+        for waiter in waiters {
+            waiter.await.unwrap();
+        }
+        assert_eq!(*order.lock(), [0, 1, 2]);
+        assert_eq!(queue.depth(), 0);
+    }
+
+    #[tokio::test]
+    async fn full_queue_refuses_instead_of_waiting() {
+        let queue = queue(1);
+        let _first = queue.acquire().await.unwrap();
+        let waiting = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.acquire().await.map(|_| ()) }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(matches!(
+            queue.acquire().await,
+            Err(UdsError::QueueFull { waiting: 1 })
+        ));
+        // The refused request left the queue again
+        assert_eq!(queue.depth(), 2);
+        waiting.abort();
+        let _ = waiting.await;
+        assert_eq!(queue.depth(), 1);
+    }
+
+    #[tokio::test]
+    async fn try_acquire_fails_while_a_request_is_in_flight() {
+        let queue = queue(4);
+        let turn = queue.acquire().await.unwrap();
+        assert!(queue.try_acquire().is_none());
+        drop(turn);
+        assert!(queue.try_acquire().is_some());
+    }
+}
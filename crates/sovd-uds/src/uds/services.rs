@@ -1,29 +1,126 @@
 //! UDS service layer for diagnostic communication
 
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
-use super::{service_id, NegativeResponseCode, PeriodicRate, ServiceIds, UdsError};
-use crate::transport::TransportAdapter;
+use parking_lot::RwLock;
+use tokio::sync::broadcast;
+
+use super::{
+    roe_event_type, roe_event_window, service_id, NegativeResponseCode, PeriodicRate, RequestQueue,
+    ServiceIds, UdsError,
+};
+use crate::config::{
+    FirstFrameRetryConfig, MemoryAccessConfig, ResponsePendingConfig, ServicePolicy, TimingConfig,
+};
+use crate::transport::{IncomingMessage, TransportAdapter, TransportError};
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_millis(5000);
-const RESPONSE_PENDING_TIMEOUT: Duration = Duration::from_millis(30000);
+/// Allowance for network delay on top of the ECU-advertised P2/P2* (ΔP2)
+const P2_CLIENT_MARGIN: Duration = Duration::from_millis(500);
+
+/// Server timing from the sessionParameterRecord of a DiagnosticSessionControl
+/// (0x10) positive response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionTiming {
+    /// P2_server_max: time to the first response
+    pub p2_server_max: Duration,
+    /// P2*_server_max: time to the next response after a responsePending (0x78)
+    pub p2_star_server_max: Duration,
+}
+
+impl SessionTiming {
+    /// Parse `[0x50, session, P2 (2 bytes, 1 ms), P2* (2 bytes, 10 ms)]`;
+    /// `None` when the response carries no sessionParameterRecord
+    pub fn from_response(response: &[u8]) -> Option<Self> {
+        let record = response.get(2..6)?;
+        let p2 = u16::from_be_bytes([record[0], record[1]]);
+        let p2_star = u16::from_be_bytes([record[2], record[3]]);
+        Some(Self {
+            p2_server_max: Duration::from_millis(p2 as u64),
+            p2_star_server_max: Duration::from_millis(p2_star as u64 * 10),
+        })
+    }
+}
+
+/// Next frame answering service `sid` (positive or negative) on `rx`
+///
+/// `rx` was subscribed before the request went out, so it also holds the
+/// responsePending (0x78) the transport already handed back from
+/// `send_receive`; pending frames received before `answered_at` are skipped
+/// rather than counted twice. Final responses are never skipped.
+async fn wait_for_response(
+    rx: &mut broadcast::Receiver<IncomingMessage>,
+    sid: u8,
+    timeout: Duration,
+    answered_at: std::time::Instant,
+) -> Result<Vec<u8>, UdsError> {
+    let expected = sid.wrapping_add(0x40);
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        let msg = match tokio::time::timeout_at(deadline, rx.recv()).await {
+            Ok(Ok(msg)) => msg,
+            Ok(Err(broadcast::error::RecvError::Lagged(_))) => continue,
+            Ok(Err(broadcast::error::RecvError::Closed)) => {
+                return Err(UdsError::Transport("Connection closed".to_string()))
+            }
+            Err(_) => return Err(UdsError::Timeout),
+        };
+        match msg.data.first() {
+            Some(&first) if first == expected => return Ok(msg.data),
+            Some(&service_id::NEGATIVE_RESPONSE) if msg.data.get(1) == Some(&sid) => {
+                let pending =
+                    msg.data.get(2) == Some(&u8::from(NegativeResponseCode::ResponsePending));
+                if pending && msg.timestamp <= answered_at {
+                    continue;
+                }
+                return Ok(msg.data);
+            }
+            _ => {}
+        }
+    }
+}
 
 /// UDS Service layer for diagnostic communication
 #[derive(Clone)]
 pub struct UdsService {
     transport: Arc<dyn TransportAdapter>,
-    timeout: Duration,
+    /// Fixed response timeout; `None` follows the session timing
+    timeout: Option<Duration>,
+    /// Timing adopted from the last DiagnosticSessionControl response,
+    /// shared by every clone of this service
+    session_timing: Arc<RwLock<Option<SessionTiming>>>,
     /// Service IDs to use (may include OEM overrides)
     svc: ServiceIds,
+    /// Read/WriteMemoryByAddress address/size widths
+    memory_format: MemoryAccessConfig,
+    /// Services this ECU may be sent
+    service_policy: ServicePolicy,
+    /// P2* override and 0x78 limit
+    response_pending: ResponsePendingConfig,
+    /// Re-sends of requests that got no answer
+    first_frame_retry: FirstFrameRetryConfig,
+    /// P2/P2* until the session advertises its own
+    timing: TimingConfig,
+    /// Turns with the ECU, shared by every clone of this service and by
+    /// any other service given the same queue
+    queue: Arc<RequestQueue>,
 }
 
 impl UdsService {
     pub fn new(transport: Arc<dyn TransportAdapter>) -> Self {
         Self {
             transport,
-            timeout: DEFAULT_TIMEOUT,
+            timeout: None,
+            session_timing: Arc::new(RwLock::new(None)),
             svc: ServiceIds::default(),
+            memory_format: MemoryAccessConfig::default(),
+            service_policy: ServicePolicy::default(),
+            response_pending: ResponsePendingConfig::default(),
+            first_frame_retry: FirstFrameRetryConfig::default(),
+            timing: TimingConfig::default(),
+            queue: Arc::new(RequestQueue::default()),
         }
     }
 
@@ -31,13 +128,84 @@ impl UdsService {
     pub fn with_service_ids(transport: Arc<dyn TransportAdapter>, service_ids: ServiceIds) -> Self {
         Self {
             transport,
-            timeout: DEFAULT_TIMEOUT,
+            timeout: None,
+            session_timing: Arc::new(RwLock::new(None)),
             svc: service_ids,
+            memory_format: MemoryAccessConfig::default(),
+            service_policy: ServicePolicy::default(),
+            response_pending: ResponsePendingConfig::default(),
+            first_frame_retry: FirstFrameRetryConfig::default(),
+            timing: TimingConfig::default(),
+            queue: Arc::new(RequestQueue::default()),
         }
     }
 
+    /// Take turns with every other service sending to the same ECU
+    pub fn with_request_queue(mut self, queue: Arc<RequestQueue>) -> Self {
+        self.queue = queue;
+        self
+    }
+
+    /// Refuse, before sending, every service `policy` does not permit
+    pub fn with_service_policy(mut self, policy: ServicePolicy) -> Self {
+        self.service_policy = policy;
+        self
+    }
+
+    /// Wait out responsePending (0x78) per `config`
+    pub fn with_response_pending(mut self, config: ResponsePendingConfig) -> Self {
+        self.response_pending = config;
+        self
+    }
+
+    /// Re-send unanswered requests per `config`
+    pub fn with_first_frame_retry(mut self, config: FirstFrameRetryConfig) -> Self {
+        self.first_frame_retry = config;
+        self
+    }
+
+    /// P2/P2* to use while the session has not advertised its own
+    pub fn with_timing(mut self, config: TimingConfig) -> Self {
+        self.timing = config;
+        self
+    }
+
+    /// Pin the response timeout, ignoring the session's P2/P2*
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = timeout;
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// P2/P2* adopted from the active session, if the ECU advertised them
+    pub fn session_timing(&self) -> Option<SessionTiming> {
+        *self.session_timing.read()
+    }
+
+    /// Response timeout for the next message: the pinned timeout, else the
+    /// session's P2 (P2* once the ECU answered responsePending) plus
+    /// [`P2_CLIENT_MARGIN`], else the configured [`TimingConfig`] P2 (P2*)
+    /// plus the margin, else [`DEFAULT_TIMEOUT`]
+    fn response_timeout(&self, pending: bool) -> Duration {
+        if let Some(timeout) = self.timeout {
+            return timeout;
+        }
+        let configured = if pending {
+            self.timing.p2_star_ms
+        } else {
+            self.timing.p2_ms
+        };
+        match self.session_timing() {
+            Some(t) if pending => t.p2_star_server_max + P2_CLIENT_MARGIN,
+            Some(t) => t.p2_server_max + P2_CLIENT_MARGIN,
+            None => configured
+                .map(|ms| Duration::from_millis(ms) + P2_CLIENT_MARGIN)
+                .unwrap_or(DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// Fix the address/size widths used by Read/WriteMemoryByAddress (0x23/0x3D)
+    pub fn with_memory_format(mut self, memory_format: MemoryAccessConfig) -> Self {
+        self.memory_format = memory_format;
         self
     }
 
@@ -46,17 +214,50 @@ impl UdsService {
         &self.svc
     }
 
+    /// Claim the transport between requests, if no request is in flight
+    ///
+    /// Returns `None` while any request on this service's queue is in
+    /// flight or waiting. New requests wait until the returned guard is
+    /// dropped, so a fire-and-forget frame sent under it cannot interleave
+    /// with them.
+    pub(crate) fn try_idle(&self) -> Option<tokio::sync::MutexGuard<'_, ()>> {
+        self.queue.try_acquire()
+    }
+
+    /// Largest request the underlying transport can carry, if limited
+    pub fn max_message_len(&self) -> Option<usize> {
+        self.transport.max_message_len()
+    }
+
     /// Send a UDS request and handle response pending
+    ///
+    /// Each responsePending (0x78) re-arms the wait for the ECU's next frame
+    /// with the P2* timeout; the request itself is not repeated unless the
+    /// transport cannot deliver unsolicited frames. More than
+    /// `max_pending` consecutive 0x78 answers fail with [`UdsError::Timeout`].
+    ///
+    /// The whole exchange, 0x78 waits included, holds the ECU's turn from
+    /// the request queue, so no other request to the ECU is sent meanwhile.
     async fn send_request(&self, request: &[u8]) -> Result<Vec<u8>, UdsError> {
-        let start = std::time::Instant::now();
+        self.check_permitted(request)?;
+        let _turn = self.queue.acquire().await?;
+        self.send_in_turn(request).await
+    }
 
-        loop {
-            let response = self
-                .transport
-                .send_receive(request, self.timeout)
-                .await
-                .map_err(|e| UdsError::Transport(e.to_string()))?;
+    /// [`Self::send_request`] for a caller already holding the ECU's turn
+    async fn send_in_turn(&self, request: &[u8]) -> Result<Vec<u8>, UdsError> {
+        let sid = request.first().copied().unwrap_or(0);
+        // Subscribed before sending: the final response can follow a 0x78
+        // before `first_exchange` has even returned
+        let mut incoming = self
+            .transport
+            .delivers_pending_responses()
+            .then(|| self.transport.subscribe());
+        let mut response = self.first_exchange(request).await?;
+        let answered_at = std::time::Instant::now();
+        let mut pending_count = 0;
 
+        loop {
             // Check for negative response
             if response.first() == Some(&service_id::NEGATIVE_RESPONSE) {
                 if response.len() < 3 {
@@ -70,10 +271,19 @@ impl UdsService {
 
                 // Handle response pending
                 if nrc == NegativeResponseCode::ResponsePending {
-                    if start.elapsed() > RESPONSE_PENDING_TIMEOUT {
+                    pending_count += 1;
+                    if pending_count > self.response_pending.max_pending {
                         return Err(UdsError::Timeout);
                     }
-                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    let timeout = self.response_timeout(true);
+                    response = match incoming.as_mut() {
+                        Some(rx) => wait_for_response(rx, sid, timeout, answered_at).await?,
+                        None => self
+                            .transport
+                            .send_receive(request, timeout)
+                            .await
+                            .map_err(|e| UdsError::Transport(e.to_string()))?,
+                    };
                     continue;
                 }
 
@@ -84,10 +294,84 @@ impl UdsService {
         }
     }
 
+    /// Send `request` and receive the ECU's first answer, re-sending it
+    /// while nothing arrives within the first-frame timeout, as configured
+    /// in [`FirstFrameRetryConfig`]
+    async fn first_exchange(&self, request: &[u8]) -> Result<Vec<u8>, UdsError> {
+        let retry = &self.first_frame_retry;
+        let is_read = request.first().is_some_and(|&sid| {
+            sid == self.svc.read_data_by_id
+                || sid == self.svc.read_dtc_info
+                || sid == self.svc.read_memory_by_address
+        });
+        let retries = if is_read || retry.writes {
+            retry.retries
+        } else {
+            0
+        };
+        let timeout = self.response_timeout(false);
+        // Only a transport that sees the first frame arrive can bound it;
+        // elsewhere each attempt waits the full response timeout, so a slow
+        // multi-frame response is never cut off
+        let first_frame_timeout = retry
+            .first_frame_timeout_ms
+            .filter(|_| retries > 0)
+            .filter(|_| {
+                let bounded = self.transport.bounds_first_frame();
+                if !bounded {
+                    tracing::debug!(
+                        "Transport cannot see first frames, first-frame timeout not enforced"
+                    );
+                }
+                bounded
+            })
+            .map(Duration::from_millis);
+
+        let mut attempt = 0;
+        loop {
+            let result = match first_frame_timeout {
+                Some(first_frame_timeout) => {
+                    self.transport
+                        .send_receive_first_frame(request, first_frame_timeout, timeout)
+                        .await
+                }
+                None => self.transport.send_receive(request, timeout).await,
+            };
+            match result {
+                Err(TransportError::Timeout(_)) if attempt < retries => {
+                    attempt += 1;
+                    tracing::debug!(
+                        sid = format_args!("0x{:02X}", request[0]),
+                        attempt,
+                        "No first frame, re-sending request"
+                    );
+                }
+                result => return result.map_err(|e| UdsError::Transport(e.to_string())),
+            }
+        }
+    }
+
+    /// Fail with [`UdsError::ServiceDenied`] if the service policy does not
+    /// permit `request`'s service
+    fn check_permitted(&self, request: &[u8]) -> Result<(), UdsError> {
+        match request.first() {
+            Some(&service_id) if !self.service_policy.permits(service_id) => {
+                Err(UdsError::ServiceDenied { service_id })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Diagnostic Session Control (0x10)
+    ///
+    /// Adopts the P2/P2* from the response's sessionParameterRecord for all
+    /// later requests (see [`Self::session_timing`]); a response without one
+    /// falls back to the static timeouts.
     pub async fn diagnostic_session_control(&self, session: u8) -> Result<Vec<u8>, UdsError> {
         let request = vec![self.svc.diagnostic_session_control, session];
-        self.send_request(&request).await
+        let response = self.send_request(&request).await?;
+        *self.session_timing.write() = SessionTiming::from_response(&response);
+        Ok(response)
     }
 
     /// Tester Present (0x3E)
@@ -96,6 +380,7 @@ impl UdsService {
         let request = vec![self.svc.tester_present, sub_function];
 
         if suppress_response {
+            self.check_permitted(&request)?;
             self.transport
                 .send(&request)
                 .await
@@ -194,6 +479,28 @@ impl UdsService {
         Ok(())
     }
 
+    /// Response On Event (0x86) - Start the events set up so far
+    pub async fn response_on_event_start(&self) -> Result<(), UdsError> {
+        let request = vec![
+            service_id::RESPONSE_ON_EVENT,
+            roe_event_type::START,
+            roe_event_window::INFINITE,
+        ];
+        self.send_request(&request).await?;
+        Ok(())
+    }
+
+    /// Response On Event (0x86) - Clear every event that was set up
+    pub async fn response_on_event_clear(&self) -> Result<(), UdsError> {
+        let request = vec![
+            service_id::RESPONSE_ON_EVENT,
+            roe_event_type::CLEAR,
+            roe_event_window::INFINITE,
+        ];
+        self.send_request(&request).await?;
+        Ok(())
+    }
+
     /// Parse a ReadDataByIdentifier (0x62) response into DID-value pairs
     pub fn parse_read_response(response: &[u8]) -> Result<Vec<(u16, Vec<u8>)>, UdsError> {
         if response.is_empty() || response[0] != 0x62 {
@@ -228,6 +535,15 @@ impl UdsService {
         self.send_request(&request).await
     }
 
+    /// Read the ECU's DTCStatusAvailabilityMask (which status bits it
+    /// supports) via reportNumberOfDTCByStatusMask (sub-function 0x01)
+    pub async fn read_dtc_status_availability_mask(&self) -> Result<u8, UdsError> {
+        let response = self.read_dtc_count(0xFF).await?;
+        super::dtc::parse_dtc_count_response(&response)
+            .map(|count| count.status_availability_mask)
+            .map_err(UdsError::InvalidResponse)
+    }
+
     /// Read DTCs matching a status mask (sub-function 0x02)
     pub async fn read_dtc_by_status_mask(&self, status_mask: u8) -> Result<Vec<u8>, UdsError> {
         let request = vec![
@@ -238,6 +554,38 @@ impl UdsService {
         self.send_request(&request).await
     }
 
+    /// Read DTCs matching a severity and status mask, with their severity
+    /// (sub-function 0x08)
+    pub async fn read_dtc_by_severity_mask(
+        &self,
+        severity_mask: u8,
+        status_mask: u8,
+    ) -> Result<Vec<u8>, UdsError> {
+        let request = vec![
+            self.svc.read_dtc_info,
+            super::dtc::sub_function::REPORT_DTC_BY_SEVERITY_MASK_RECORD,
+            severity_mask,
+            status_mask,
+        ];
+        self.send_request(&request).await
+    }
+
+    /// Read DTCs matching a status mask from a user-defined memory
+    /// (sub-function 0x17), e.g. the mirror memory
+    pub async fn read_user_def_memory_dtc_by_status_mask(
+        &self,
+        status_mask: u8,
+        memory_selection: u8,
+    ) -> Result<Vec<u8>, UdsError> {
+        let request = vec![
+            self.svc.read_dtc_info,
+            super::dtc::sub_function::REPORT_USER_DEF_MEMORY_DTC_BY_STATUS_MASK,
+            status_mask,
+            memory_selection,
+        ];
+        self.send_request(&request).await
+    }
+
     /// Read DTC snapshot record by DTC number (sub-function 0x04)
     pub async fn read_dtc_snapshot(
         &self,
@@ -286,7 +634,15 @@ impl UdsService {
             group_bytes[2], // Mid byte
             group_bytes[3], // Low byte
         ];
-        self.send_request(&request).await?;
+        let response = self.send_request(&request).await?;
+
+        // Response: 0x54; anything else has not confirmed the clear
+        if response.first() != Some(&self.svc.clear_diagnostic_info.wrapping_add(0x40)) {
+            return Err(UdsError::InvalidResponse(format!(
+                "Unexpected clear response: {:02X?}",
+                response
+            )));
+        }
         Ok(())
     }
 
@@ -383,6 +739,47 @@ impl UdsService {
         Ok(())
     }
 
+    /// Define a DDID by composing it from memory regions (sub-function 0x02)
+    ///
+    /// Each region is `(address, size)`. One addressAndLengthFormatIdentifier
+    /// covers every region, so its widths are the configured ones (see
+    /// [`Self::with_memory_format`]) or else the fewest bytes that hold the
+    /// largest address and size.
+    pub async fn define_data_identifier_by_memory(
+        &self,
+        ddid: u16,
+        regions: &[(u64, u32)],
+    ) -> Result<(), UdsError> {
+        let max_address = regions.iter().map(|(address, _)| *address).max();
+        let max_size = regions.iter().map(|(_, size)| *size).max();
+        let address_bytes = memory_field_width(
+            "address",
+            max_address.unwrap_or(0),
+            self.memory_format.address_bytes,
+            8,
+        )?;
+        let size_bytes = memory_field_width(
+            "size",
+            u64::from(max_size.unwrap_or(0)),
+            self.memory_format.size_bytes,
+            4,
+        )?;
+
+        let mut request = vec![
+            self.svc.dynamically_define_data_id,
+            super::ddid_sub_function::DEFINE_BY_MEMORY_ADDRESS,
+        ];
+        request.extend_from_slice(&ddid.to_be_bytes());
+        request.push((size_bytes << 4) | address_bytes);
+        for (address, size) in regions {
+            request.extend_from_slice(&address.to_be_bytes()[8 - address_bytes as usize..]);
+            request.extend_from_slice(&size.to_be_bytes()[4 - size_bytes as usize..]);
+        }
+
+        self.send_request(&request).await?;
+        Ok(())
+    }
+
     /// Clear a dynamically defined data identifier (sub-function 0x03)
     pub async fn clear_data_identifier(&self, ddid: u16) -> Result<(), UdsError> {
         let mut request = vec![
@@ -526,6 +923,100 @@ impl UdsService {
         Ok(max_block_length.saturating_sub(2))
     }
 
+    /// Request Upload (0x35) of `size` bytes starting at `address`
+    ///
+    /// Uses the same addressAndLengthFormatIdentifier layout as
+    /// [`Self::read_memory_by_address`]. Returns the maxNumberOfBlockLength
+    /// as [`Self::request_upload`] does.
+    pub async fn request_upload_memory(
+        &self,
+        data_format: u8,
+        address: u64,
+        size: u32,
+    ) -> Result<u32, UdsError> {
+        let fields = self.memory_address_and_size(address, size)?;
+        let (address_bytes, size_bytes) = fields[1..].split_at((fields[0] & 0x0F) as usize);
+        self.request_upload(data_format, fields[0], address_bytes, size_bytes)
+            .await
+    }
+
+    /// Read Memory By Address (0x23) - Read `size` bytes starting at `address`
+    ///
+    /// The addressAndLengthFormatIdentifier is built from the configured
+    /// address/size widths (see [`Self::with_memory_format`]); a width left
+    /// unset is the fewest bytes that hold the value. Returns the raw memory
+    /// bytes from the positive response.
+    pub async fn read_memory_by_address(
+        &self,
+        address: u64,
+        size: u32,
+    ) -> Result<Vec<u8>, UdsError> {
+        let mut request = vec![self.svc.read_memory_by_address];
+        request.extend(self.memory_address_and_size(address, size)?);
+
+        let response = self.send_request(&request).await?;
+
+        if response.len() != 1 + size as usize {
+            return Err(UdsError::InvalidResponse(format!(
+                "ReadMemoryByAddress returned {} bytes, expected {}",
+                response.len().saturating_sub(1),
+                size
+            )));
+        }
+
+        Ok(response[1..].to_vec())
+    }
+
+    /// Write Memory By Address (0x3D) - Write `data` starting at `address`
+    ///
+    /// Uses the same addressAndLengthFormatIdentifier layout as
+    /// [`Self::read_memory_by_address`]. The positive response must echo the
+    /// ALFID, address and size that were sent. NRC 0x31 is returned as
+    /// [`UdsError::RequestOutOfRange`].
+    pub async fn write_memory_by_address(&self, address: u64, data: &[u8]) -> Result<(), UdsError> {
+        let size = u32::try_from(data.len()).map_err(|_| {
+            UdsError::InvalidRequest(format!("memory write of {} bytes is too large", data.len()))
+        })?;
+        let header = self.memory_address_and_size(address, size)?;
+
+        let mut request = vec![self.svc.write_memory_by_address];
+        request.extend_from_slice(&header);
+        request.extend_from_slice(data);
+
+        let response = match self.send_request(&request).await {
+            Ok(response) => response,
+            Err(UdsError::NegativeResponse {
+                service_id,
+                nrc: NegativeResponseCode::RequestOutOfRange,
+            }) => return Err(UdsError::RequestOutOfRange { service_id }),
+            Err(e) => return Err(e),
+        };
+
+        let echo = response.get(1..).unwrap_or_default();
+        if echo != header.as_slice() {
+            return Err(UdsError::InvalidResponse(format!(
+                "WriteMemoryByAddress echoed {}, expected {}",
+                hex::encode(echo),
+                hex::encode(&header)
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// ALFID followed by the memoryAddress and memorySize fields, sized per
+    /// the configured widths (see [`Self::with_memory_format`])
+    fn memory_address_and_size(&self, address: u64, size: u32) -> Result<Vec<u8>, UdsError> {
+        let address_bytes =
+            memory_field_width("address", address, self.memory_format.address_bytes, 8)?;
+        let size_bytes = memory_field_width("size", size as u64, self.memory_format.size_bytes, 4)?;
+
+        let mut fields = vec![(size_bytes << 4) | address_bytes];
+        fields.extend_from_slice(&address.to_be_bytes()[8 - address_bytes as usize..]);
+        fields.extend_from_slice(&size.to_be_bytes()[4 - size_bytes as usize..]);
+        Ok(fields)
+    }
+
     /// Transfer Data Upload (0x36) - Request data block from ECU
     pub async fn transfer_data_upload(&self, block_counter: u8) -> Result<(u8, Vec<u8>), UdsError> {
         let request = vec![self.svc.transfer_data, block_counter];
@@ -659,13 +1150,29 @@ impl UdsService {
 
     /// Link Control - Transition baud rate (sub-function 0x03)
     pub async fn link_control_transition(&self) -> Result<(), UdsError> {
+        self.link_control_transition_then(async {}).await
+    }
+
+    /// Link Control - Transition baud rate (sub-function 0x03), then run
+    /// `follow` before any other request to the ECU is sent
+    ///
+    /// The ECU switches rate right after its response, so a request queued
+    /// behind the transition would go out at the old rate; `follow` moves
+    /// the bus over while the transition still holds the ECU's turn. It is
+    /// not run when the transition fails.
+    pub async fn link_control_transition_then<T>(
+        &self,
+        follow: impl Future<Output = T>,
+    ) -> Result<T, UdsError> {
         let request = vec![
             self.svc.link_control,
             super::link_control_sub_function::TRANSITION_BAUD_RATE,
         ];
 
-        self.send_request(&request).await?;
-        Ok(())
+        self.check_permitted(&request)?;
+        let _turn = self.queue.acquire().await?;
+        self.send_in_turn(&request).await?;
+        Ok(follow.await)
     }
 
     // =========================================================================
@@ -702,3 +1209,29 @@ impl UdsService {
         Ok(())
     }
 }
+
+/// Byte width of one Read/WriteMemoryByAddress field: the configured width, else
+/// the fewest bytes that hold `value`. Errors when a configured width is out
+/// of range (1..=`max`) or too narrow for `value`.
+fn memory_field_width(
+    field: &str,
+    value: u64,
+    configured: Option<u8>,
+    max: u8,
+) -> Result<u8, UdsError> {
+    let needed = ((u64::BITS - value.leading_zeros()).div_ceil(8) as u8).max(1);
+    let Some(width) = configured else {
+        return Ok(needed);
+    };
+    if !(1..=max).contains(&width) {
+        return Err(UdsError::InvalidRequest(format!(
+            "memory {field} width must be 1-{max} bytes, configured {width}"
+        )));
+    }
+    if needed > width {
+        return Err(UdsError::InvalidRequest(format!(
+            "memory {field} 0x{value:X} does not fit in {width} bytes"
+        )));
+    }
+    Ok(width)
+}
@@ -10,15 +10,27 @@
 //!
 //! The bundled dev/simulation implementation is [`XorUnlock`], byte-for-byte
 //! the sim gate in `example-ecu` (`handle_security_access`) and the XOR
-//! algorithm the retired SOVD-security-helper used.
+//! algorithm the retired SOVD-security-helper used. [`AddUnlock`] (byte-wise
+//! additive) and [`Aes128CmacUnlock`] (AES-128-CMAC over the seed) cover the
+//! common OEM shapes; anything else is an [`UnlockProvider`] handed to
+//! [`crate::UdsBackend::with_unlock_provider`].
 
 use std::sync::Arc;
 
+use aes::Aes128;
+use cmac::{Cmac, Mac};
+
 use crate::config::UnlockConfig;
 
 /// Algorithm identifier for the XOR simulation gate ([`XorUnlock`]).
 pub const ALGORITHM_XOR: &str = "xor";
 
+/// Algorithm identifier for the byte-wise additive scheme ([`AddUnlock`]).
+pub const ALGORITHM_ADD: &str = "add";
+
+/// Algorithm identifier for AES-128-CMAC ([`Aes128CmacUnlock`]).
+pub const ALGORITHM_AES128_CMAC: &str = "aes128-cmac";
+
 /// Error raised while constructing an [`UnlockProvider`] from config or while
 /// computing a SecurityAccess key.
 #[derive(Debug, thiserror::Error)]
@@ -32,6 +44,11 @@ pub enum UnlockError {
     #[error("invalid unlock secret hex: {0}")]
     InvalidSecretHex(String),
 
+    /// The secret has the wrong length for the algorithm (e.g. an AES-128
+    /// key that is not 16 bytes).
+    #[error("unlock secret must be {expected} bytes, got {actual}")]
+    InvalidSecretLength { expected: usize, actual: usize },
+
     /// The configured `algorithm` string is not recognised.
     #[error("unknown unlock algorithm: {0}")]
     UnknownAlgorithm(String),
@@ -74,9 +91,7 @@ impl XorUnlock {
     /// Build an XOR unlock from a hex-encoded secret (e.g. `"ff"`,
     /// `"deadbeef"`). Rejects invalid hex and an empty secret.
     pub fn from_hex(secret_hex: &str) -> Result<Self, UnlockError> {
-        let secret =
-            hex::decode(secret_hex).map_err(|e| UnlockError::InvalidSecretHex(e.to_string()))?;
-        Self::new(secret)
+        Self::new(decode_secret(secret_hex)?)
     }
 }
 
@@ -91,12 +106,84 @@ impl UnlockProvider for XorUnlock {
     }
 }
 
+/// Additive unlock: `key[i] = seed[i] + secret[i % secret.len()]` (mod 256).
+pub struct AddUnlock {
+    secret: Vec<u8>,
+}
+
+impl AddUnlock {
+    /// Build an additive unlock from raw secret bytes. Rejects an empty
+    /// secret.
+    pub fn new(secret: Vec<u8>) -> Result<Self, UnlockError> {
+        if secret.is_empty() {
+            return Err(UnlockError::EmptySecret);
+        }
+        Ok(Self { secret })
+    }
+
+    /// Build an additive unlock from a hex-encoded secret. Rejects invalid
+    /// hex and an empty secret.
+    pub fn from_hex(secret_hex: &str) -> Result<Self, UnlockError> {
+        Self::new(decode_secret(secret_hex)?)
+    }
+}
+
+impl UnlockProvider for AddUnlock {
+    fn compute_key(&self, _level: u8, seed: &[u8]) -> Result<Vec<u8>, UnlockError> {
+        Ok(seed
+            .iter()
+            .enumerate()
+            .map(|(i, b)| b.wrapping_add(self.secret[i % self.secret.len()]))
+            .collect())
+    }
+}
+
+/// AES-128-CMAC unlock: the key is the 16-byte CMAC (RFC 4493) of the seed
+/// under the configured 128-bit secret.
+pub struct Aes128CmacUnlock {
+    secret: [u8; 16],
+}
+
+impl Aes128CmacUnlock {
+    /// Build a CMAC unlock from a 16-byte AES key.
+    pub fn new(secret: &[u8]) -> Result<Self, UnlockError> {
+        let secret = secret
+            .try_into()
+            .map_err(|_| UnlockError::InvalidSecretLength {
+                expected: 16,
+                actual: secret.len(),
+            })?;
+        Ok(Self { secret })
+    }
+
+    /// Build a CMAC unlock from a hex-encoded 16-byte AES key.
+    pub fn from_hex(secret_hex: &str) -> Result<Self, UnlockError> {
+        Self::new(&decode_secret(secret_hex)?)
+    }
+}
+
+impl UnlockProvider for Aes128CmacUnlock {
+    fn compute_key(&self, _level: u8, seed: &[u8]) -> Result<Vec<u8>, UnlockError> {
+        let mut mac = Cmac::<Aes128>::new_from_slice(&self.secret)
+            .map_err(|e| UnlockError::Compute(e.to_string()))?;
+        mac.update(seed);
+        Ok(mac.finalize().into_bytes().to_vec())
+    }
+}
+
+/// Decode a hex-encoded secret.
+fn decode_secret(secret_hex: &str) -> Result<Vec<u8>, UnlockError> {
+    hex::decode(secret_hex).map_err(|e| UnlockError::InvalidSecretHex(e.to_string()))
+}
+
 /// Construct an [`UnlockProvider`] from an [`UnlockConfig`]. The `algorithm`
 /// string selects the implementation; this match is the single place new
 /// algorithms (vendor/HSM) are wired in.
 pub fn provider_from_config(config: &UnlockConfig) -> Result<Arc<dyn UnlockProvider>, UnlockError> {
     match config.algorithm.as_str() {
         ALGORITHM_XOR => Ok(Arc::new(XorUnlock::from_hex(&config.secret_hex)?)),
+        ALGORITHM_ADD => Ok(Arc::new(AddUnlock::from_hex(&config.secret_hex)?)),
+        ALGORITHM_AES128_CMAC => Ok(Arc::new(Aes128CmacUnlock::from_hex(&config.secret_hex)?)),
         other => Err(UnlockError::UnknownAlgorithm(other.to_string())),
     }
 }
@@ -196,6 +283,64 @@ mod tests {
         assert_eq!(key, vec![0xFE, 0xFD]);
     }
 
+    #[test]
+    fn add_known_vector_wraps_secret_and_bytes() {
+        let unlock = AddUnlock::new(vec![0x01, 0x80]).unwrap();
+        let key = unlock.compute_key(1, &[0x10, 0x90, 0xFF]).unwrap();
+        // 0x90 + 0x80 and 0xFF + 0x01 wrap mod 256.
+        assert_eq!(key, vec![0x11, 0x10, 0x00]);
+    }
+
+    #[test]
+    fn aes128_cmac_matches_rfc4493_vectors() {
+        let unlock = Aes128CmacUnlock::from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+        assert_eq!(
+            unlock.compute_key(1, &[]).unwrap(),
+            hex::decode("bb1d6929e95937287fa37d129b756746").unwrap()
+        );
+        let seed = hex::decode("6bc1bee22e409f96e93d7e117393172a").unwrap();
+        assert_eq!(
+            unlock.compute_key(1, &seed).unwrap(),
+            hex::decode("070a16b46b4d4144f79bdd9dd04a287c").unwrap()
+        );
+    }
+
+    #[test]
+    fn aes128_cmac_rejects_wrong_key_length() {
+        assert!(matches!(
+            Aes128CmacUnlock::from_hex("deadbeef"),
+            Err(UnlockError::InvalidSecretLength {
+                expected: 16,
+                actual: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn provider_from_config_selects_add_and_cmac() {
+        let cfg = UnlockConfig {
+            algorithm: "add".to_string(),
+            secret_hex: "01".to_string(),
+            level: None,
+        };
+        let key = provider_from_config(&cfg)
+            .unwrap()
+            .compute_key(1, &[0x01, 0xFF])
+            .unwrap();
+        assert_eq!(key, vec![0x02, 0x00]);
+
+        let cfg = UnlockConfig {
+            algorithm: "aes128-cmac".to_string(),
+            secret_hex: "2b7e151628aed2a6abf7158809cf4f3c".to_string(),
+            level: None,
+        };
+        let key = provider_from_config(&cfg)
+            .unwrap()
+            .compute_key(1, &[])
+            .unwrap();
+        assert_eq!(key.len(), 16);
+    }
+
     #[test]
     fn provider_from_config_rejects_unknown_algorithm() {
         let cfg = UnlockConfig {
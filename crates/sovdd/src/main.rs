@@ -18,13 +18,16 @@ use std::net::SocketAddr;
 use std::path::Path;
 use std::sync::Arc;
 
-use sovd_api::{create_router, AppState, AuthConfig, AuthContext};
+use sovd_api::{
+    create_router, AppState, AuthConfig, AuthContext, DataAliases, ReadCacheConfig,
+    SubscriptionLimits, VehicleStatusThresholds,
+};
 use sovd_conv::DidStore;
-use sovd_gateway::GatewayBackend;
-use sovd_proxy::SovdProxyBackend;
+use sovd_gateway::{ComponentMetadata, GatewayBackend};
+use sovd_proxy::{CacheConfig, RetryConfig, SovdProxyBackend};
 use sovd_uds::{
     config::{
-        FlashCommitConfig, IsoTpConfig, MockConfig, OperationConfig, OutputConfig,
+        FlashCommitConfig, IsoTpConfig, MockConfig, OperationConfig, OutputConfig, ReplayConfig,
         ServiceOverrides, SessionConfig, SocketCanConfig, TransportConfig, UdsBackendConfig,
     },
     DiagnosticBackend, UdsBackend,
@@ -198,8 +201,20 @@ async fn main() -> anyhow::Result<()> {
         );
     }
 
-    // Create the app state with DID store, output configs, and auth context
+    // Per-ECU semantic name → DID aliases. A name claimed by two DIDs on
+    // one component is a config error, so fail startup rather than guess.
+    let data_aliases = load_data_aliases(&config_path)?;
+
+    let subscription_limits = load_subscription_limits(&config_path)?;
+    let vehicle_status = load_vehicle_status_thresholds(&config_path)?;
+    let read_cache = load_read_cache_config(&config_path)?;
+
+    // Create the app state with DID store, output configs, aliases, and auth context
     let state = AppState::with_output_configs(backends, Arc::new(did_store), output_configs)
+        .with_data_aliases(data_aliases)
+        .with_subscription_limits(subscription_limits)
+        .with_vehicle_status_thresholds(vehicle_status)
+        .with_read_cache(read_cache)
         .with_auth(Arc::new(auth));
 
     // Create the router
@@ -350,6 +365,91 @@ fn load_auth_config(path: &str) -> anyhow::Result<AuthConfig> {
     }
 }
 
+/// Parse the `[subscriptions]` limits section. Re-reads the config file like
+/// `load_auth_config`.
+fn load_subscription_limits(path: &str) -> anyhow::Result<SubscriptionLimits> {
+    let content = std::fs::read_to_string(path)?;
+    let config: toml::Value = toml::from_str(&content)?;
+    match config.get("subscriptions") {
+        Some(limits) => Ok(limits
+            .clone()
+            .try_into()
+            .map_err(|e| anyhow::anyhow!("[subscriptions]: {}", e))?),
+        None => Ok(SubscriptionLimits::default()),
+    }
+}
+
+/// Parse the `[vehicle_status]` rollup thresholds. Re-reads the config file
+/// like `load_auth_config`.
+fn load_vehicle_status_thresholds(path: &str) -> anyhow::Result<VehicleStatusThresholds> {
+    let content = std::fs::read_to_string(path)?;
+    let config: toml::Value = toml::from_str(&content)?;
+    match config.get("vehicle_status") {
+        Some(thresholds) => Ok(thresholds
+            .clone()
+            .try_into()
+            .map_err(|e| anyhow::anyhow!("[vehicle_status]: {}", e))?),
+        None => Ok(VehicleStatusThresholds::default()),
+    }
+}
+
+/// Parse the `[data_cache]` section. Re-reads the config file like
+/// `load_auth_config`.
+fn load_read_cache_config(path: &str) -> anyhow::Result<ReadCacheConfig> {
+    let content = std::fs::read_to_string(path)?;
+    let config: toml::Value = toml::from_str(&content)?;
+    match config.get("data_cache") {
+        Some(cache) => Ok(cache
+            .clone()
+            .try_into()
+            .map_err(|e| anyhow::anyhow!("[data_cache]: {}", e))?),
+        None => Ok(ReadCacheConfig::default()),
+    }
+}
+
+/// Parse the `[gateway.components.<id>]` friendly names and categories.
+fn load_component_metadata(
+    gateway_config: &toml::Value,
+) -> anyhow::Result<HashMap<String, ComponentMetadata>> {
+    match gateway_config.get("components") {
+        Some(components) => Ok(components
+            .clone()
+            .try_into()
+            .map_err(|e| anyhow::anyhow!("[gateway.components]: {}", e))?),
+        None => Ok(HashMap::new()),
+    }
+}
+
+/// Parse per-ECU `aliases = [{ name = "...", did = "0x...." }, ...]` into the
+/// API's name → DID table.  Re-reads the config file like `load_auth_config`.
+fn load_data_aliases(path: &str) -> anyhow::Result<DataAliases> {
+    let content = std::fs::read_to_string(path)?;
+    let config: toml::Value = toml::from_str(&content)?;
+    let mut aliases = DataAliases::new();
+
+    let Some(ecus) = config.get("ecu").and_then(|e| e.as_table()) else {
+        return Ok(aliases);
+    };
+    for (ecu_id, ecu_config) in ecus {
+        let Some(entries) = ecu_config.get("aliases").and_then(|a| a.as_array()) else {
+            continue;
+        };
+        for entry in entries {
+            let name = entry
+                .get("name")
+                .and_then(|n| n.as_str())
+                .ok_or_else(|| anyhow::anyhow!("ECU '{}' alias missing 'name'", ecu_id))?;
+            let did_str = entry.get("did").and_then(|d| d.as_str()).ok_or_else(|| {
+                anyhow::anyhow!("ECU '{}' alias '{}' missing 'did'", ecu_id, name)
+            })?;
+            let did = parse_hex_u16(did_str)?;
+            aliases.insert(ecu_id, name, did)?;
+            tracing::debug!(ecu_id = %ecu_id, name = %name, did = %format!("0x{:04X}", did), "Registered data alias");
+        }
+    }
+    Ok(aliases)
+}
+
 /// In-process TLS settings parsed from `[server.tls]`.
 struct TlsConfig {
     cert: String,
@@ -457,6 +557,18 @@ async fn load_config_file(
                 })?;
 
             let auth_token = proxy_config.get("auth_token").and_then(|t| t.as_str());
+            let retry: RetryConfig = match proxy_config.get("retry") {
+                Some(retry) => retry.clone().try_into().map_err(|e| {
+                    anyhow::anyhow!("Proxy '{}' has an invalid 'retry' table: {}", proxy_id, e)
+                })?,
+                None => RetryConfig::default(),
+            };
+            let cache: CacheConfig = match proxy_config.get("cache") {
+                Some(cache) => cache.clone().try_into().map_err(|e| {
+                    anyhow::anyhow!("Proxy '{}' has an invalid 'cache' table: {}", proxy_id, e)
+                })?,
+                None => CacheConfig::default(),
+            };
 
             tracing::info!(
                 proxy_id = %proxy_id,
@@ -469,7 +581,9 @@ async fn load_config_file(
 
             let backend = SovdProxyBackend::with_auth(proxy_id, url, component_id, auth_token)
                 .await
-                .map_err(|e| anyhow::anyhow!("Failed to create proxy '{}': {}", proxy_id, e))?;
+                .map_err(|e| anyhow::anyhow!("Failed to create proxy '{}': {}", proxy_id, e))?
+                .with_retry(retry)
+                .with_cache(cache);
             let backend: Arc<dyn DiagnosticBackend> = Arc::new(backend);
 
             if gateway_enabled {
@@ -498,6 +612,16 @@ async fn load_config_file(
         tracing::info!(gateway_id = %gateway_id, gateway_name = %gateway_name, "Creating gateway");
 
         let mut gateway = GatewayBackend::new(gateway_id, gateway_name, None);
+        if let Some(window_ms) = gw_section
+            .and_then(|g| g.get("batch_window_ms"))
+            .and_then(|w| w.as_integer())
+            .filter(|&w| w > 0)
+        {
+            gateway = gateway.with_batch_window(std::time::Duration::from_millis(window_ms as u64));
+        }
+        if let Some(gw) = gw_section {
+            gateway = gateway.with_component_metadata(load_component_metadata(gw)?);
+        }
 
         // Register all ECU backends with gateway
         let ecu_keys: Vec<String> = backends
@@ -528,33 +652,24 @@ async fn load_config_file(
         // CAN bus auto-discovery: scan for ECUs not explicitly configured
         #[cfg(target_os = "linux")]
         if let Some(scan_config) = gw_section.and_then(|g| g.get("scan")) {
-            let scan_interface = scan_config
-                .get("interface")
-                .and_then(|i| i.as_str())
-                .unwrap_or("can0")
-                .to_string();
+            let scan_interfaces = parse_scan_interfaces(scan_config);
+            let multi_interface = scan_interfaces.len() > 1;
             let scan_timeout = scan_config
                 .get("timeout_ms")
                 .and_then(|t| t.as_integer())
                 .unwrap_or(2000) as u64;
 
             // Collect CAN ID pairs of already-configured ECUs to avoid duplicates
-            let configured_ids: std::collections::HashSet<(u32, u32)> =
-                collect_configured_can_ids(&config);
+            let configured_ids = collect_configured_can_ids(&config);
 
             tracing::info!(
-                interface = %scan_interface,
+                interfaces = ?scan_interfaces,
                 timeout_ms = scan_timeout,
                 configured_ecus = configured_ids.len(),
                 "Running CAN bus ECU auto-discovery"
             );
 
-            let cfg = sovd_uds::scanner::ScanConfig {
-                interface: scan_interface,
-                timeout_ms: scan_timeout,
-            };
-
-            match sovd_uds::scanner::scan_can_bus(&cfg).await {
+            match sovd_uds::scanner::scan_can_buses(&scan_interfaces, scan_timeout).await {
                 Ok(discovered) => {
                     let session_config = if let Some(s) = config.get("session") {
                         parse_session_config(s).unwrap_or_default()
@@ -568,15 +683,26 @@ async fn load_config_file(
 
                     for ecu in discovered {
                         // Skip if this ECU's CAN IDs are already explicitly configured
-                        if configured_ids.contains(&(ecu.tx_can_id, ecu.rx_can_id)) {
+                        if configured_ids.contains(&(
+                            ecu.interface.clone(),
+                            ecu.tx_can_id,
+                            ecu.rx_can_id,
+                        )) {
                             tracing::info!(
+                                interface = %ecu.interface,
                                 address = format!("0x{:02X}", ecu.address),
                                 "Skipping discovered ECU (already configured)"
                             );
                             continue;
                         }
 
-                        let ecu_id = format!("ecu_0x{:02x}", ecu.address);
+                        // One address may answer on several buses; qualify
+                        // the id with the interface when more than one is scanned.
+                        let ecu_id = if multi_interface {
+                            format!("ecu_{}_0x{:02x}", ecu.interface, ecu.address)
+                        } else {
+                            format!("ecu_0x{:02x}", ecu.address)
+                        };
                         let ecu_name = ecu
                             .part_number
                             .as_deref()
@@ -593,6 +719,7 @@ async fn load_config_file(
                             transport: TransportConfig::SocketCan(SocketCanConfig {
                                 interface: ecu.interface.clone(),
                                 bitrate: 500000,
+                                can_fd: false,
                                 isotp: IsoTpConfig {
                                     tx_id: format!("0x{:08X}", ecu.tx_can_id),
                                     rx_id: format!("0x{:08X}", ecu.rx_can_id),
@@ -601,6 +728,9 @@ async fn load_config_file(
                                     block_size: 0,
                                     st_min_us: 0,
                                     tx_dl: 8,
+                                    // The scanner probes 29-bit normal fixed addressing
+                                    extended_ids: Some(true),
+                                    addressing_fallback: None,
                                 },
                             }),
                             operations: vec![],
@@ -610,6 +740,17 @@ async fn load_config_file(
                             flash_commit: scan_flash_config.clone(),
                             // Auto-discovered ECUs have no per-ECU unlock config.
                             unlock: None,
+                            fault_memory: Default::default(),
+                            memory: Default::default(),
+                            service_policy: Default::default(),
+                            response_pending: Default::default(),
+                            first_frame_retry: Default::default(),
+                            fingerprints: Default::default(),
+                            data_read: Default::default(),
+                            subscription_recovery: Default::default(),
+                            subscriptions: Default::default(),
+                            timing: Default::default(),
+                            request_queue: Default::default(),
                         };
 
                         match UdsBackend::new(backend_config).await {
@@ -617,6 +758,7 @@ async fn load_config_file(
                                 tracing::info!(
                                     ecu_id = %ecu_id,
                                     name = %ecu_name,
+                                    interface = %ecu.interface,
                                     address = format!("0x{:02X}", ecu.address),
                                     vin = ?ecu.vin,
                                     "Registered auto-discovered ECU"
@@ -625,6 +767,19 @@ async fn load_config_file(
                                 // Register identification DIDs for this ECU
                                 register_discovered_ecu_dids(&ecu_id, &ecu, did_store);
 
+                                // Configured [gateway.components] entries win
+                                let display_name = ecu
+                                    .part_number
+                                    .as_deref()
+                                    .map(|pn| format!("ECU {} (0x{:02X})", pn, ecu.address))
+                                    .unwrap_or_else(|| format!("ECU 0x{:02X}", ecu.address));
+                                gateway.set_default_metadata(
+                                    &ecu_id,
+                                    ComponentMetadata {
+                                        display_name: Some(display_name),
+                                        category: Some("discovered".to_string()),
+                                    },
+                                );
                                 gateway.register_backend(Arc::new(backend));
                             }
                             Err(e) => {
@@ -719,6 +874,64 @@ async fn create_ecu_backend(
     // Load transparent server-side SecurityAccess (UDS 0x27) config, if any
     let unlock = load_unlock_config(ecu_config)?;
 
+    // Load DTC memory selection (mirror memory), if any
+    let fault_memory = load_fault_memory_config(ecu_config)?;
+
+    // Load ReadMemoryByAddress (0x23) address/size widths, if any
+    let memory = load_memory_access_config(ecu_config)?;
+
+    // Load the UDS service allow/deny list, if any
+    let service_policy = load_service_policy(ecu_config)?;
+
+    // Load the responsePending (0x78) wait, if configured
+    let response_pending = load_response_pending_config(ecu_config)?;
+
+    // Load the re-send of unanswered requests, if configured
+    let first_frame_retry = load_first_frame_retry_config(ecu_config)?;
+
+    // Load the fingerprint DID layout, if any
+    let fingerprints = load_fingerprint_config(ecu_config)?;
+
+    // Load ReadDataByIdentifier batching, if configured
+    let data_read = load_data_read_config(ecu_config)?;
+
+    // Load the resumption of subscriptions after an ECU reset, if configured
+    let subscription_recovery = match ecu_config.get("subscription_recovery") {
+        Some(section) => section
+            .clone()
+            .try_into()
+            .map_err(|e| anyhow::anyhow!("[ecu.*.subscription_recovery] {}", e))?,
+        None => Default::default(),
+    };
+
+    // Load the subscription sampling mode (periodic or ResponseOnEvent), if configured
+    let subscriptions = match ecu_config.get("subscriptions") {
+        Some(section) => section
+            .clone()
+            .try_into()
+            .map_err(|e| anyhow::anyhow!("[ecu.*.subscriptions] {}", e))?,
+        None => Default::default(),
+    };
+
+    // Load the P2/P2* response timeouts, if configured
+    let mut timing = match ecu_config.get("timing") {
+        Some(section) => section
+            .clone()
+            .try_into()
+            .map_err(|e| anyhow::anyhow!("[ecu.*.timing] {}", e))?,
+        None => Default::default(),
+    };
+    apply_deprecated_p2_star(ecu_id, ecu_config, &mut timing)?;
+
+    // Load the request queue depth, if configured
+    let request_queue = match ecu_config.get("request_queue") {
+        Some(section) => section
+            .clone()
+            .try_into()
+            .map_err(|e| anyhow::anyhow!("[ecu.*.request_queue] {}", e))?,
+        None => Default::default(),
+    };
+
     let config = UdsBackendConfig {
         id: ecu_id.to_string(),
         name: name.to_string(),
@@ -730,6 +943,17 @@ async fn create_ecu_backend(
         sessions,
         flash_commit,
         unlock,
+        fault_memory,
+        memory,
+        service_policy,
+        response_pending,
+        first_frame_retry,
+        fingerprints,
+        data_read,
+        subscription_recovery,
+        subscriptions,
+        timing,
+        request_queue,
     };
 
     tracing::info!(ecu_id = %ecu_id, "Creating UDS backend");
@@ -757,6 +981,11 @@ fn parse_transport_config(config: &toml::Value) -> anyhow::Result<TransportConfi
                 .and_then(|b| b.as_integer())
                 .unwrap_or(500000) as u32;
 
+            let can_fd = config
+                .get("can_fd")
+                .and_then(|f| f.as_bool())
+                .unwrap_or(false);
+
             let isotp = config.get("isotp").ok_or_else(|| {
                 anyhow::anyhow!("SocketCAN transport requires isotp configuration")
             })?;
@@ -795,9 +1024,19 @@ fn parse_transport_config(config: &toml::Value) -> anyhow::Result<TransportConfi
 
             let tx_dl = isotp.get("tx_dl").and_then(|t| t.as_integer()).unwrap_or(8) as u8;
 
+            let extended_ids = match isotp.get("extended_ids") {
+                Some(v) => Some(v.as_bool().ok_or_else(|| {
+                    anyhow::anyhow!("[transport.isotp] 'extended_ids' must be true or false")
+                })?),
+                None => None,
+            };
+
+            let addressing_fallback = parse_addressing_fallback(isotp)?;
+
             Ok(TransportConfig::SocketCan(SocketCanConfig {
                 interface,
                 bitrate,
+                can_fd,
                 isotp: IsoTpConfig {
                     tx_id,
                     rx_id,
@@ -806,9 +1045,19 @@ fn parse_transport_config(config: &toml::Value) -> anyhow::Result<TransportConfi
                     block_size,
                     st_min_us,
                     tx_dl,
+                    extended_ids,
+                    addressing_fallback,
                 },
             }))
         }
+        "replay" => {
+            let trace = config
+                .get("trace")
+                .and_then(|t| t.as_str())
+                .ok_or_else(|| anyhow::anyhow!("Replay transport requires a 'trace' path"))?
+                .to_string();
+            Ok(TransportConfig::Replay(ReplayConfig { trace }))
+        }
         _ => Ok(TransportConfig::Mock(MockConfig {
             latency_ms: config
                 .get("latency_ms")
@@ -843,6 +1092,14 @@ fn parse_session_config(config: &toml::Value) -> anyhow::Result<SessionConfig> {
         .get("transfer_data_block_counter_wrap")
         .and_then(|v| v.as_integer())
         .unwrap_or(0) as u8;
+    let transfer_data_max_block_length = config
+        .get("transfer_data_max_block_length")
+        .and_then(|v| v.as_integer())
+        .map(|v| v as u32);
+    let write_data_max_length = config
+        .get("write_data_max_length")
+        .and_then(|v| v.as_integer())
+        .map(|v| v as u32);
 
     tracing::info!(
         "parse_session_config: default={:#x}, programming={:#x}, extended={:#x}, engineering={:#x}, block_counter_start={}, block_counter_wrap={}",
@@ -864,6 +1121,12 @@ fn parse_session_config(config: &toml::Value) -> anyhow::Result<SessionConfig> {
         }
     });
 
+    // Parse [session.security_handshake] (0x27 retry/timeout tuning) if present
+    let security_handshake = match config.get("security_handshake") {
+        Some(h) => h.clone().try_into()?,
+        None => Default::default(),
+    };
+
     Ok(SessionConfig {
         default_session,
         programming_session,
@@ -871,7 +1134,10 @@ fn parse_session_config(config: &toml::Value) -> anyhow::Result<SessionConfig> {
         engineering_session,
         transfer_data_block_counter_start,
         transfer_data_block_counter_wrap,
+        transfer_data_max_block_length,
+        write_data_max_length,
         security,
+        security_handshake,
         ..Default::default()
     })
 }
@@ -898,6 +1164,10 @@ fn parse_service_overrides(config: &toml::Value) -> anyhow::Result<ServiceOverri
             .get("read_data_by_id")
             .and_then(|v| v.as_integer())
             .map(|v| v as u8),
+        read_memory_by_address: config
+            .get("read_memory_by_address")
+            .and_then(|v| v.as_integer())
+            .map(|v| v as u8),
         security_access: config
             .get("security_access")
             .and_then(|v| v.as_integer())
@@ -938,6 +1208,10 @@ fn parse_service_overrides(config: &toml::Value) -> anyhow::Result<ServiceOverri
             .get("request_transfer_exit")
             .and_then(|v| v.as_integer())
             .map(|v| v as u8),
+        write_memory_by_address: config
+            .get("write_memory_by_address")
+            .and_then(|v| v.as_integer())
+            .map(|v| v as u8),
         tester_present: config
             .get("tester_present")
             .and_then(|v| v.as_integer())
@@ -978,6 +1252,24 @@ fn load_operations(ecu_config: &toml::Value) -> anyhow::Result<Vec<OperationConf
                     .get("security_level")
                     .and_then(|s| s.as_integer())
                     .unwrap_or(0) as u8,
+                required_session: op
+                    .get("required_session")
+                    .and_then(|s| s.as_str())
+                    .map(|s| s.to_string()),
+                result_faults: match op.get("result_faults") {
+                    Some(faults) => faults
+                        .clone()
+                        .try_into()
+                        .map_err(|e| anyhow::anyhow!("Operation result_faults: {}", e))?,
+                    None => Vec::new(),
+                },
+                params_def: match op.get("params_def") {
+                    Some(params) => params
+                        .clone()
+                        .try_into()
+                        .map_err(|e| anyhow::anyhow!("Operation params_def: {}", e))?,
+                    None => Vec::new(),
+                },
             });
         }
     }
@@ -1014,10 +1306,36 @@ fn load_flash_commit_config(ecu_config: &toml::Value) -> anyhow::Result<FlashCom
         );
     }
 
+    // TransferData compression: "none" (default) or "deflate"
+    let compression = match flash.get("compression") {
+        Some(c) => c.clone().try_into()?,
+        None => Default::default(),
+    };
+
+    let disable_communication = flash
+        .get("disable_communication")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let disable_dtc_setting = flash
+        .get("disable_dtc_setting")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    // Package signature check: [ecu.*.flash.signature]
+    let signature = match flash.get("signature") {
+        Some(s) => Some(s.clone().try_into()?),
+        None => None,
+    };
+
     Ok(FlashCommitConfig {
         supports_rollback,
         commit_routine,
         rollback_routine,
+        compression,
+        disable_communication,
+        disable_dtc_setting,
+        signature,
     })
 }
 
@@ -1062,8 +1380,329 @@ fn load_unlock_config(
     }))
 }
 
+/// Parse `[ecu.X.fault_memory]` (DTC memory selection for `?memory=mirror`,
+/// extended data record layout).
+fn load_fault_memory_config(
+    ecu_config: &toml::Value,
+) -> anyhow::Result<sovd_uds::config::FaultMemoryConfig> {
+    let fault_memory = match ecu_config.get("fault_memory") {
+        Some(m) => m,
+        None => return Ok(Default::default()),
+    };
+
+    let mirror_memory_selection = match fault_memory.get("mirror_memory_selection") {
+        Some(v) => {
+            let byte = v
+                .as_integer()
+                .and_then(|b| u8::try_from(b).ok())
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "[ecu.*.fault_memory] 'mirror_memory_selection' must be a byte (0x00-0xFF)"
+                    )
+                })?;
+            Some(byte)
+        }
+        None => None,
+    };
+
+    let verify_clear = match fault_memory.get("verify_clear") {
+        Some(v) => v.as_bool().ok_or_else(|| {
+            anyhow::anyhow!("[ecu.*.fault_memory] 'verify_clear' must be a boolean")
+        })?,
+        None => false,
+    };
+
+    let extended_data = match fault_memory.get("extended_data") {
+        Some(v) => v.clone().try_into()?,
+        None => Vec::new(),
+    };
+
+    let reports = match fault_memory.get("reports") {
+        Some(v) => v.clone().try_into().map_err(|e| {
+            anyhow::anyhow!(
+                "[ecu.*.fault_memory] 'reports' must list status-mask, severity-mask \
+                 or user-def-memory: {}",
+                e
+            )
+        })?,
+        None => Vec::new(),
+    };
+
+    Ok(sovd_uds::config::FaultMemoryConfig {
+        reports,
+        mirror_memory_selection,
+        verify_clear,
+        extended_data,
+    })
+}
+
+/// Parse `[ecu.X.memory]` (Read/WriteMemoryByAddress widths and write gate).
+fn load_memory_access_config(
+    ecu_config: &toml::Value,
+) -> anyhow::Result<sovd_uds::config::MemoryAccessConfig> {
+    let memory = match ecu_config.get("memory") {
+        Some(m) => m,
+        None => return Ok(Default::default()),
+    };
+
+    let width = |key: &str, max: u8| -> anyhow::Result<Option<u8>> {
+        match memory.get(key) {
+            Some(v) => {
+                let bytes = v
+                    .as_integer()
+                    .and_then(|b| u8::try_from(b).ok())
+                    .filter(|b| (1..=max).contains(b))
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("[ecu.*.memory] '{}' must be 1-{} bytes", key, max)
+                    })?;
+                Ok(Some(bytes))
+            }
+            None => Ok(None),
+        }
+    };
+
+    let defaults = sovd_uds::config::MemoryAccessConfig::default();
+    let write_session = match memory.get("write_session") {
+        Some(v) => v
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("[ecu.*.memory] 'write_session' must be a string"))?,
+        None => defaults.write_session,
+    };
+    let write_security_level = match memory.get("write_security_level") {
+        Some(v) => v
+            .as_integer()
+            .and_then(|l| u8::try_from(l).ok())
+            .ok_or_else(|| {
+                anyhow::anyhow!("[ecu.*.memory] 'write_security_level' must be 0-255")
+            })?,
+        None => defaults.write_security_level,
+    };
+
+    Ok(sovd_uds::config::MemoryAccessConfig {
+        address_bytes: width("address_bytes", 8)?,
+        size_bytes: width("size_bytes", 4)?,
+        write_session,
+        write_security_level,
+    })
+}
+
+/// Parse `[ecu.X.service_policy]` (UDS service allow/deny list).
+fn load_service_policy(
+    ecu_config: &toml::Value,
+) -> anyhow::Result<sovd_uds::config::ServicePolicy> {
+    let policy = match ecu_config.get("service_policy") {
+        Some(p) => p,
+        None => return Ok(Default::default()),
+    };
+
+    let service_ids = |key: &str| -> anyhow::Result<Option<Vec<u8>>> {
+        let Some(list) = policy.get(key) else {
+            return Ok(None);
+        };
+        list.as_array()
+            .and_then(|ids| {
+                ids.iter()
+                    .map(|v| v.as_integer().and_then(|id| u8::try_from(id).ok()))
+                    .collect::<Option<Vec<u8>>>()
+            })
+            .map(Some)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "[ecu.*.service_policy] '{}' must be a list of service IDs (0x00-0xFF)",
+                    key
+                )
+            })
+    };
+
+    Ok(sovd_uds::config::ServicePolicy {
+        allow: service_ids("allow")?,
+        deny: service_ids("deny")?.unwrap_or_default(),
+    })
+}
+
+/// Parse `[ecu.X.response_pending]` (NRC 0x78 wait).
+fn load_response_pending_config(
+    ecu_config: &toml::Value,
+) -> anyhow::Result<sovd_uds::config::ResponsePendingConfig> {
+    let mut config = sovd_uds::config::ResponsePendingConfig::default();
+    let Some(section) = ecu_config.get("response_pending") else {
+        return Ok(config);
+    };
+
+    if let Some(value) = section.get("max_pending") {
+        config.max_pending = value
+            .as_integer()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "[ecu.*.response_pending] max_pending must be a non-negative integer"
+                )
+            })?;
+    }
+
+    Ok(config)
+}
+
+/// Fold the deprecated `[ecu.X.response_pending] p2_star_ms` into `timing`,
+/// unless `[ecu.X.timing] p2_star_ms` is set as well.
+fn apply_deprecated_p2_star(
+    ecu_id: &str,
+    ecu_config: &toml::Value,
+    timing: &mut sovd_uds::config::TimingConfig,
+) -> anyhow::Result<()> {
+    let Some(value) = ecu_config
+        .get("response_pending")
+        .and_then(|section| section.get("p2_star_ms"))
+    else {
+        return Ok(());
+    };
+    let ms = value
+        .as_integer()
+        .and_then(|v| u64::try_from(v).ok())
+        .filter(|v| *v > 0)
+        .ok_or_else(|| {
+            anyhow::anyhow!("[ecu.*.response_pending] p2_star_ms must be a positive integer")
+        })?;
+
+    if timing.p2_star_ms.is_some() {
+        tracing::warn!(
+            ecu_id = %ecu_id,
+            "[ecu.{ecu_id}.response_pending] p2_star_ms is deprecated and ignored: \
+             [ecu.{ecu_id}.timing] p2_star_ms is set"
+        );
+    } else {
+        tracing::warn!(
+            ecu_id = %ecu_id,
+            "[ecu.{ecu_id}.response_pending] p2_star_ms is deprecated: \
+             move it to [ecu.{ecu_id}.timing] p2_star_ms"
+        );
+        timing.p2_star_ms = Some(ms);
+    }
+    Ok(())
+}
+
+/// Parse `[ecu.X.first_frame_retry]` (re-send of unanswered requests).
+fn load_first_frame_retry_config(
+    ecu_config: &toml::Value,
+) -> anyhow::Result<sovd_uds::config::FirstFrameRetryConfig> {
+    let mut config = sovd_uds::config::FirstFrameRetryConfig::default();
+    let Some(section) = ecu_config.get("first_frame_retry") else {
+        return Ok(config);
+    };
+
+    if let Some(value) = section.get("retries") {
+        config.retries = value
+            .as_integer()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| {
+                anyhow::anyhow!("[ecu.*.first_frame_retry] retries must be a non-negative integer")
+            })?;
+    }
+    if let Some(value) = section.get("first_frame_timeout_ms") {
+        let ms = value
+            .as_integer()
+            .and_then(|v| u64::try_from(v).ok())
+            .filter(|v| *v > 0)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "[ecu.*.first_frame_retry] first_frame_timeout_ms must be a positive integer"
+                )
+            })?;
+        config.first_frame_timeout_ms = Some(ms);
+    }
+    if let Some(value) = section.get("writes") {
+        config.writes = value
+            .as_bool()
+            .ok_or_else(|| anyhow::anyhow!("[ecu.*.first_frame_retry] writes must be a boolean"))?;
+    }
+
+    Ok(config)
+}
+
+/// Parse `[ecu.X.fingerprints]` (fingerprint DID layout).
+fn load_fingerprint_config(
+    ecu_config: &toml::Value,
+) -> anyhow::Result<sovd_uds::config::FingerprintConfig> {
+    let Some(section) = ecu_config.get("fingerprints") else {
+        return Ok(Default::default());
+    };
+    let format = match section.get("format") {
+        Some(value) => Some(
+            value
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("[ecu.*.fingerprints] format must be a string"))?
+                .to_string(),
+        ),
+        None => None,
+    };
+    Ok(sovd_uds::config::FingerprintConfig { format })
+}
+
+/// Parse `[ecu.X.data_read]` (multi-DID ReadDataByIdentifier batching).
+fn load_data_read_config(
+    ecu_config: &toml::Value,
+) -> anyhow::Result<sovd_uds::config::DataReadConfig> {
+    let Some(section) = ecu_config.get("data_read") else {
+        return Ok(Default::default());
+    };
+    let max_dids_per_request = match section.get("max_dids_per_request") {
+        Some(value) => Some(
+            value
+                .as_integer()
+                .and_then(|v| usize::try_from(v).ok())
+                .filter(|v| *v > 0)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "[ecu.*.data_read] max_dids_per_request must be a positive integer"
+                    )
+                })?,
+        ),
+        None => None,
+    };
+    Ok(sovd_uds::config::DataReadConfig {
+        max_dids_per_request,
+    })
+}
+
+/// Parse `[transport.isotp.addressing_fallback]`
+fn parse_addressing_fallback(
+    isotp: &toml::Value,
+) -> anyhow::Result<Option<sovd_uds::config::AddressingFallbackConfig>> {
+    let fallback = match isotp.get("addressing_fallback") {
+        Some(f) => f,
+        None => return Ok(None),
+    };
+
+    let byte = |key: &str| -> anyhow::Result<Option<u8>> {
+        match fallback.get(key) {
+            Some(v) => v
+                .as_integer()
+                .and_then(|b| u8::try_from(b).ok())
+                .map(Some)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "[transport.isotp.addressing_fallback] '{}' must be a byte (0x00-0xFF)",
+                        key
+                    )
+                }),
+            None => Ok(None),
+        }
+    };
+
+    let ecu_address = byte("ecu_address")?.ok_or_else(|| {
+        anyhow::anyhow!("[transport.isotp.addressing_fallback] requires 'ecu_address'")
+    })?;
+
+    Ok(Some(sovd_uds::config::AddressingFallbackConfig {
+        ecu_address,
+        tester_address: byte("tester_address")?.unwrap_or(0xF1),
+        obd_ecu_index: byte("obd_ecu_index")?.unwrap_or(0),
+    }))
+}
+
 fn load_outputs(ecu_config: &toml::Value) -> anyhow::Result<Vec<OutputConfig>> {
-    use sovd_uds::config::DataType;
+    use sovd_uds::config::{DataType, RangePolicy};
 
     let mut outputs = Vec::new();
 
@@ -1095,6 +1734,15 @@ fn load_outputs(ecu_config: &toml::Value) -> anyhow::Result<Vec<OutputConfig>> {
                 })
                 .unwrap_or_default();
 
+            let range_policy = match out.get("range_policy").and_then(|p| p.as_str()) {
+                None | Some("reject") => RangePolicy::Reject,
+                Some("clamp") => RangePolicy::Clamp,
+                Some(other) => anyhow::bail!(
+                    "Output range_policy must be \"reject\" or \"clamp\", got {:?}",
+                    other
+                ),
+            };
+
             outputs.push(OutputConfig {
                 id: out
                     .get("id")
@@ -1134,6 +1782,7 @@ fn load_outputs(ecu_config: &toml::Value) -> anyhow::Result<Vec<OutputConfig>> {
                 min: out.get("min").and_then(|m| m.as_float()),
                 max: out.get("max").and_then(|m| m.as_float()),
                 allowed,
+                range_policy,
             });
         }
     }
@@ -1265,6 +1914,11 @@ fn register_inline_params(
             def.writable = writable;
         }
 
+        // Set cacheable flag
+        if let Some(cacheable) = param.get("cacheable").and_then(|c| c.as_bool()) {
+            def.cacheable = cacheable;
+        }
+
         // Set component_id so this DID is associated with this ECU
         def.component_id = Some(ecu_id.to_string());
 
@@ -1291,15 +1945,44 @@ fn parse_hex_u16(s: &str) -> anyhow::Result<u16> {
     u16::from_str_radix(s, 16).map_err(|e| anyhow::anyhow!("Invalid hex '{}': {}", s, e))
 }
 
-/// Collect (tx_id, rx_id) CAN ID pairs from explicitly configured ECUs.
-/// Used to skip already-configured ECUs during auto-discovery.
+/// `[gateway.scan] interface`: a single name, a comma-separated list
+/// (`"can0,can1"`) or an array (`["can0", "can1"]`). Defaults to `can0`.
 #[cfg(target_os = "linux")]
-fn collect_configured_can_ids(config: &toml::Value) -> std::collections::HashSet<(u32, u32)> {
+fn parse_scan_interfaces(scan_config: &toml::Value) -> Vec<String> {
+    let mut interfaces: Vec<String> = match scan_config.get("interface") {
+        Some(toml::Value::Array(list)) => list
+            .iter()
+            .filter_map(|i| i.as_str())
+            .map(|i| i.trim().to_string())
+            .collect(),
+        Some(toml::Value::String(list)) => list.split(',').map(|i| i.trim().to_string()).collect(),
+        _ => Vec::new(),
+    };
+    let mut seen = std::collections::HashSet::new();
+    interfaces.retain(|i| !i.is_empty() && seen.insert(i.clone()));
+    if interfaces.is_empty() {
+        interfaces.push("can0".to_string());
+    }
+    interfaces
+}
+
+/// Collect (interface, tx_id, rx_id) from explicitly configured ECUs.
+/// Used to skip already-configured ECUs during auto-discovery; the same
+/// CAN IDs on another interface are a different ECU.
+#[cfg(target_os = "linux")]
+fn collect_configured_can_ids(
+    config: &toml::Value,
+) -> std::collections::HashSet<(String, u32, u32)> {
     let mut ids = std::collections::HashSet::new();
 
     if let Some(ecus) = config.get("ecu").and_then(|e| e.as_table()) {
         for (_ecu_id, ecu_config) in ecus {
             if let Some(transport) = ecu_config.get("transport") {
+                // Same default as `parse_transport_config`.
+                let interface = transport
+                    .get("interface")
+                    .and_then(|i| i.as_str())
+                    .unwrap_or("vcan0");
                 if let Some(isotp) = transport.get("isotp") {
                     let tx = isotp
                         .get("tx_id")
@@ -1311,7 +1994,7 @@ fn collect_configured_can_ids(config: &toml::Value) -> std::collections::HashSet
                         .and_then(|s| parse_hex_u32(s).ok());
 
                     if let (Some(tx_id), Some(rx_id)) = (tx, rx) {
-                        ids.insert((tx_id, rx_id));
+                        ids.insert((interface.to_string(), tx_id, rx_id));
                     }
                 }
             }